@@ -25,6 +25,7 @@ export const tauriMockInit = (config: TauriMockConfig) => {
     games: config.games ? [...config.games] : [],
     settings: config.settings ? { ...config.settings } : {},
   };
+  const activeScans = new Set<string>();
 
   const registerListener = (event: string, handlerId: string) => {
     const id = `listener-${++eventId}`;
@@ -65,15 +66,24 @@ export const tauriMockInit = (config: TauriMockConfig) => {
     game_exists_by_path: (args) =>
       state.games.some((game) => game.exe_path === args.exePath),
     get_running_processes: () => config.processes ?? [],
-    scan_executables_stream: () => {
+    scan_executables_stream: (args) => {
+      const scanId = String(args.scanId);
+      activeScans.add(scanId);
       setTimeout(() => {
-        (config.scanEntries ?? []).forEach((entry) => emit("scan:entry", entry));
-        emit("scan:done", null);
+        (config.scanEntries ?? []).forEach((entry) =>
+          emit(`scan:${scanId}:entry`, entry),
+        );
+        activeScans.delete(scanId);
+        emit(`scan:${scanId}:done`, null);
       }, 50);
-      return null;
+      return scanId;
     },
-    cancel_scan: () => {
-      emit("scan:done", null);
+    cancel_scan: (args) => {
+      const ids = args.scanId ? [String(args.scanId)] : [...activeScans];
+      ids.forEach((scanId) => {
+        activeScans.delete(scanId);
+        emit(`scan:${scanId}:done`, null);
+      });
       return null;
     },
     "plugin:autostart|is_enabled": () => false,
@@ -0,0 +1,226 @@
+//! Remembers which app version last opened the database and runs the compatibility hooks
+//! an upgrade needs before anything else reads from it.
+
+use crate::backup::sqoba_manifest;
+use crate::database::{
+    database_status, get_db_path, init_database, schema_version, with_db, DatabaseMode,
+    DatabaseStatus,
+};
+use rusqlite::params;
+use serde::Serialize;
+
+pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+const LAST_VERSION_KEY: &str = "last_app_version";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppInfo {
+    pub version: String,
+    /// Version that opened the database before this launch, if it differed.
+    pub previous_version: Option<String>,
+    pub schema_version: i32,
+    pub data_dir: String,
+    pub database_path: String,
+    pub manifest_cache_path: String,
+    pub backup_dir: String,
+}
+
+struct CompatHook {
+    /// Runs when upgrading from a version older than this one.
+    since: &'static str,
+    name: &'static str,
+    run: fn() -> Result<(), String>,
+}
+
+const COMPAT_HOOKS: &[CompatHook] = &[
+    CompatHook {
+        since: "0.1.0",
+        name: "clear_cached_disk_types",
+        run: clear_cached_disk_types,
+    },
+    CompatHook {
+        since: "0.1.0",
+        name: "retokenise_save_paths",
+        run: retokenise_save_paths,
+    },
+    CompatHook {
+        since: "0.1.0",
+        name: "refresh_manifest",
+        run: refresh_manifest,
+    },
+];
+
+fn clear_cached_disk_types() -> Result<(), String> {
+    crate::backup::clear_cached_disk_types().map(|_| ())
+}
+
+fn retokenise_save_paths() -> Result<(), String> {
+    crate::backup::retokenise_save_paths().map(|_| ())
+}
+
+/// The manifest is re-downloaded lazily on the next lookup rather than blocking startup.
+fn refresh_manifest() -> Result<(), String> {
+    sqoba_manifest::invalidate_manifest_cache().map(|_| ())
+}
+
+/// `major.minor.patch`, ignoring any pre-release or build suffix.
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let core = version.trim().split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+/// Hooks for an upgrade from `previous` to `current`. A database without a recorded
+/// version predates tracking and gets every hook up to `current`; downgrades get none.
+fn hooks_to_run(previous: Option<&str>, current: &str) -> Vec<&'static CompatHook> {
+    let Some(current) = parse_version(current) else {
+        return Vec::new();
+    };
+    let previous = previous.and_then(parse_version);
+    if previous.is_some_and(|previous| previous >= current) {
+        return Vec::new();
+    }
+    COMPAT_HOOKS
+        .iter()
+        .filter(|hook| {
+            parse_version(hook.since).is_some_and(|since| {
+                since <= current && previous.is_none_or(|previous| previous < since)
+            })
+        })
+        .collect()
+}
+
+fn load_setting(key: &str) -> Option<String> {
+    with_db(|conn| {
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![key],
+            |row| row.get::<_, String>(0),
+        )
+    })
+    .ok()
+    .filter(|value| !value.is_empty())
+}
+
+fn store_setting(key: &str, value: &str) -> Result<(), String> {
+    with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Called once after the database is opened. A failed hook is logged and skipped; the new
+/// version is recorded regardless so a broken hook doesn't rerun on every launch.
+fn run_upgrade_hooks() {
+    let previous = load_setting(LAST_VERSION_KEY);
+    if previous.as_deref() == Some(APP_VERSION) {
+        return;
+    }
+    for hook in hooks_to_run(previous.as_deref(), APP_VERSION) {
+        println!("Running compatibility hook: {}", hook.name);
+        if let Err(e) = (hook.run)() {
+            eprintln!("Compatibility hook {} failed: {}", hook.name, e);
+        }
+    }
+    if let Some(previous) = &previous {
+        let _ = store_setting("previous_app_version", previous);
+    }
+    if let Err(e) = store_setting(LAST_VERSION_KEY, APP_VERSION) {
+        eprintln!("Failed to record app version: {}", e);
+    }
+}
+
+/// Opens the database and, once it's writable, runs the upgrade hooks. A read-only or
+/// missing database is left for the user to see through `get_database_status`.
+pub fn start_database() {
+    if let Err(e) = init_database() {
+        eprintln!("Failed to initialize database: {}", e);
+        return;
+    }
+    if !crate::test_mode::is_enabled() && database_status().mode == DatabaseMode::Ready {
+        run_upgrade_hooks();
+    }
+}
+
+#[tauri::command]
+pub fn get_database_status() -> DatabaseStatus {
+    database_status()
+}
+
+/// Tries to open the database again after the user closed whatever held the lock, and
+/// starts the background jobs that were held back while it was read-only.
+#[tauri::command]
+pub fn retry_database_open(app: tauri::AppHandle) -> DatabaseStatus {
+    start_database();
+    crate::start_database_writers(&app);
+    database_status()
+}
+
+#[tauri::command]
+pub fn get_app_info() -> Result<AppInfo, String> {
+    let schema_version = with_db(schema_version).map_err(|e| e.to_string())?;
+    let database_path = get_db_path();
+    let data_dir = database_path
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(AppInfo {
+        version: APP_VERSION.to_string(),
+        previous_version: load_setting("previous_app_version"),
+        schema_version,
+        data_dir,
+        database_path: database_path.to_string_lossy().to_string(),
+        manifest_cache_path: sqoba_manifest::default_cache_path()
+            .to_string_lossy()
+            .to_string(),
+        backup_dir: crate::backup::get_backup_directory()
+            .to_string_lossy()
+            .to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{init_schema, SCHEMA_VERSION};
+    use rusqlite::Connection;
+
+    fn names(hooks: Vec<&CompatHook>) -> Vec<&'static str> {
+        hooks.into_iter().map(|hook| hook.name).collect()
+    }
+
+    #[test]
+    fn hooks_run_only_when_upgrading_past_their_version() {
+        assert_eq!(parse_version("1.2.3-beta.1"), Some((1, 2, 3)));
+        assert_eq!(parse_version("2.0"), Some((2, 0, 0)));
+        assert_eq!(parse_version("dev"), None);
+
+        let all = names(hooks_to_run(None, "0.1.0"));
+        assert_eq!(
+            all,
+            vec![
+                "clear_cached_disk_types",
+                "retokenise_save_paths",
+                "refresh_manifest"
+            ]
+        );
+        assert_eq!(names(hooks_to_run(Some("0.0.9"), "0.1.0")), all);
+        assert!(hooks_to_run(Some("0.1.0"), "0.1.0").is_empty());
+        assert!(hooks_to_run(Some("0.1.0"), "0.2.0").is_empty());
+        assert!(hooks_to_run(Some("0.3.0"), "0.2.0").is_empty());
+        assert!(hooks_to_run(None, "0.0.5").is_empty());
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        assert_eq!(
+            schema_version(&conn).expect("schema version"),
+            SCHEMA_VERSION
+        );
+    }
+}
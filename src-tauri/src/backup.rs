@@ -1,6 +1,9 @@
 use crate::database::with_db;
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
-use rusqlite::params;
+use crate::db::GlobalDb;
+use crate::metadata::pcgamingwiki;
+use crate::services::games as games_service;
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use rusqlite::{params, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 #[cfg(target_os = "windows")]
@@ -9,8 +12,10 @@ use std::fs;
 #[cfg(target_os = "windows")]
 use std::os::windows::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 use tauri::Emitter;
 use uuid::Uuid;
 #[cfg(target_os = "windows")]
@@ -30,22 +35,84 @@ use windows::Win32::System::Ioctl::{
 use windows::Win32::System::IO::DeviceIoControl;
 
 // Import our new native engine
+#[path = "backup/defender.rs"]
+pub mod defender;
+#[path = "backup/diff.rs"]
+pub mod diff;
+#[path = "backup/disk_space.rs"]
+pub mod disk_space;
 #[path = "backup/engine.rs"]
 pub mod engine;
+#[path = "backup/error.rs"]
+pub mod error;
+#[path = "backup/install_archive.rs"]
+pub mod install_archive;
+#[path = "backup/install_scan.rs"]
+pub mod install_scan;
+#[path = "backup/manifest_override.rs"]
+pub mod manifest_override;
+#[path = "backup/manifest_refresh.rs"]
+pub mod manifest_refresh;
+#[path = "backup/naming.rs"]
+pub mod naming;
+#[path = "backup/path_remap.rs"]
+pub mod path_remap;
+#[path = "backup/quota.rs"]
+pub mod quota;
+#[path = "backup/restore_preview.rs"]
+pub mod restore_preview;
+#[path = "backup/save_candidates.rs"]
+pub mod save_candidates;
+#[path = "backup/save_integrity.rs"]
+pub mod save_integrity;
 #[path = "backup/save_locator.rs"]
 pub mod save_locator;
+#[path = "backup/save_watch.rs"]
+pub mod save_watch;
+#[path = "backup/shadow_copy.rs"]
+pub mod shadow_copy;
 #[path = "backup/sqoba_manifest.rs"]
 pub mod sqoba_manifest;
+#[path = "backup/storage_stats.rs"]
+pub mod storage_stats;
+#[path = "backup/throughput.rs"]
+pub mod throughput;
+#[path = "backup/verify.rs"]
+pub mod verify;
+#[path = "backup/watchdog.rs"]
+pub mod watchdog;
+#[path = "backup/wgs.rs"]
+pub mod wgs;
+pub use defender::BackupDiagnostics;
+use defender::{DefenderCpuSampler, ThroughputSample};
+pub use diff::BackupDiff;
 use engine::{
     load_backup_manifest, BackupArchiveManifest, BackupEngine, BackupOptions, BackupProgress,
+    BENCHMARK_LEVELS,
 };
+pub use engine::{BackupMetadata, CompressionBenchmark, ManifestRepair};
+pub use error::BackupError;
+pub use install_archive::InstallArchive;
+pub use manifest_override::ManifestOverride;
+use naming::{parse_backup_name, render_backup_name, BackupNameContext};
+pub use path_remap::RestoreRootMapping;
+use path_remap::{parse_backup_relative, strip_suffix_path, PathTokens, RestorePathRemapper};
+use quota::{plan_quota_evictions, QuotaLimits, QuotaScope, QuotaViolation, StoredBackup};
+pub use restore_preview::RestorePreview;
+pub use save_candidates::SaveCandidate;
+use save_integrity::{find_suspect_saves, has_intact_copies, SuspectFile};
+use save_locator::{
+    expand_game_path_token, ConfiguredRoot, SaveLookup, SymlinkPolicy, GAME_PATH_TOKEN,
+};
+pub use storage_stats::BackupStats;
+use storage_stats::{summarize_backup_storage, BackupStorageRow};
+use verify::{VerifyMode, DEFAULT_VERIFY_SAMPLE_SIZE};
+use watchdog::StallAction;
 
 lazy_static::lazy_static! {
     static ref BACKUP_ENGINE: Mutex<BackupEngine> = Mutex::new(BackupEngine::new());
 }
 
-const SAVE_PATH_GAME_TOKEN: &str = "{PATHTOGAME}";
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Backup {
     pub id: String,
@@ -55,6 +122,47 @@ pub struct Backup {
     pub created_at: String,
     pub is_auto: bool,
     pub notes: Option<String>,
+    /// Playtime and label captured at backup time; `None` for backups made before it was.
+    #[serde(default)]
+    pub metadata: Option<BackupMetadata>,
+}
+
+const BACKUP_COLUMNS: &str = "id, game_id, backup_path, backup_size, created_at, is_auto, notes,
+     playtime_seconds, last_session_seconds, label";
+
+fn backup_from_row(row: &rusqlite::Row) -> rusqlite::Result<Backup> {
+    let metadata = row
+        .get::<_, Option<i64>>(7)?
+        .map(|playtime_seconds| -> rusqlite::Result<BackupMetadata> {
+            Ok(BackupMetadata {
+                playtime_seconds,
+                last_session_seconds: row.get(8)?,
+                label: row.get(9)?,
+            })
+        })
+        .transpose()?;
+    Ok(Backup {
+        id: row.get(0)?,
+        game_id: row.get(1)?,
+        backup_path: row.get(2)?,
+        backup_size: row.get(3)?,
+        created_at: row.get(4)?,
+        is_auto: row.get::<_, i32>(5)? == 1,
+        notes: row.get(6)?,
+        metadata,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEvent {
+    pub id: i64,
+    pub game_id: String,
+    pub backup_id: Option<String>,
+    pub created_at: String,
+    pub is_auto: bool,
+    pub success: bool,
+    pub attempt: i32,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,8 +170,30 @@ pub struct BackupInfo {
     pub game_name: String,
     pub save_path: Option<String>,
     pub registry_path: Option<String>,
+    /// Registry keys the manifest lists for the game, e.g. `HKEY_CURRENT_USER\Software\...`.
+    #[serde(default)]
+    pub registry_keys: Vec<String>,
     pub total_size: u64,
     pub files: Vec<String>,
+    /// Container names for Xbox / Microsoft Store save files, keyed by entry of `files`.
+    #[serde(default)]
+    pub container_names: HashMap<String, String>,
+    /// The saves were only guessed from the install folder and should be checked.
+    #[serde(default)]
+    pub low_confidence: bool,
+}
+
+/// A row of `game_save_paths`. `path` is stored tokenised (`{PATHTOGAME}`) like
+/// `games.save_path`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSavePath {
+    pub id: String,
+    pub game_id: String,
+    pub path: String,
+    pub label: Option<String>,
+    pub enabled: bool,
+    pub exclude: bool,
+    pub created_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,6 +247,47 @@ fn sanitize_folder_name(name: &str) -> String {
     }
 }
 
+/// Checksum of the exact game name. Unlike the id, it survives removing and re-adding the
+/// game, or adding it on another machine, so the game still finds its folders.
+fn folder_name_tag(game_name: &str) -> String {
+    format!("{:08x}", crc32fast::hash(game_name.trim().as_bytes()))
+}
+
+/// `Name-Year [7b111539]`. The name tag keeps games whose names sanitize to the same folder
+/// ("FIFA: 22" and "FIFA 22") from sharing one.
+fn game_backup_folder_name(game_name: &str, year: Option<&str>) -> String {
+    let safe_name = sanitize_folder_name(game_name);
+    let base = match year {
+        Some(y) => format!("{}-{}", safe_name, y),
+        None => safe_name,
+    };
+    format!("{} [{}]", base, folder_name_tag(game_name))
+}
+
+/// Name tag of a folder produced by `game_backup_folder_name`, if any.
+fn folder_tag(folder_name: &str) -> Option<&str> {
+    let inner = folder_name.strip_suffix(']')?;
+    let (_, tag) = inner.rsplit_once(" [")?;
+    (!tag.is_empty() && tag.chars().all(|c| c.is_ascii_alphanumeric())).then_some(tag)
+}
+
+/// True when another game in the library sanitizes to the same folder name, so untagged
+/// (pre-tag) folders cannot be attributed to `game_id` safely.
+fn legacy_folder_name_shared(game_id: &str, game_name: &str) -> bool {
+    let base = sanitize_folder_name(game_name).to_lowercase();
+    with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT name FROM games WHERE id != ?1")?;
+        let names = stmt
+            .query_map(params![game_id], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .collect::<Vec<_>>();
+        Ok(names)
+    })
+    .unwrap_or_default()
+    .iter()
+    .any(|name| sanitize_folder_name(name).to_lowercase() == base)
+}
+
 #[derive(Debug, Clone)]
 pub struct BackupImportResult {
     #[allow(dead_code)]
@@ -146,7 +317,12 @@ pub fn import_existing_backups_for_game(
     }
 
     let year = get_game_year(game_id);
-    let candidate_dirs = find_backup_game_dirs(&backup_root, game_name, year.as_deref());
+    let candidate_dirs = find_backup_game_dirs(
+        &backup_root,
+        game_name,
+        year.as_deref(),
+        !legacy_folder_name_shared(game_id, game_name),
+    );
     if candidate_dirs.is_empty() {
         return Ok(BackupImportResult {
             backups_added: 0,
@@ -155,7 +331,17 @@ pub fn import_existing_backups_for_game(
     }
 
     let mut entries: Vec<BackupImportEntry> = Vec::new();
-    let mut seen_paths = HashMap::new();
+    // Backups already registered (possibly to another game) are never imported twice.
+    let mut seen_paths: HashMap<PathBuf, bool> = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT backup_path FROM backups")?;
+        let paths = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .map(|path| (PathBuf::from(path), true))
+            .collect();
+        Ok(paths)
+    })
+    .map_err(|e| e.to_string())?;
 
     for dir in candidate_dirs {
         if let Ok(dir_entries) = fs::read_dir(&dir) {
@@ -246,11 +432,20 @@ pub fn import_existing_backups_for_game(
     })
 }
 
-fn find_backup_game_dirs(backup_root: &Path, game_name: &str, year: Option<&str>) -> Vec<PathBuf> {
+/// Backup folders belonging to a game: the ones tagged with its name and, when
+/// `include_legacy` is set because no other game claims the name, untagged older folders
+/// named after it.
+fn find_backup_game_dirs(
+    backup_root: &Path,
+    game_name: &str,
+    year: Option<&str>,
+    include_legacy: bool,
+) -> Vec<PathBuf> {
     let base = sanitize_folder_name(game_name).to_lowercase();
     if base.is_empty() {
         return Vec::new();
     }
+    let name_tag = folder_name_tag(game_name);
     let expected_parens = year
         .map(|y| y.trim())
         .filter(|y| !y.is_empty())
@@ -263,6 +458,15 @@ fn find_backup_game_dirs(backup_root: &Path, game_name: &str, year: Option<&str>
                 continue;
             }
             let name = entry.file_name().to_string_lossy().to_lowercase();
+            if let Some(tag) = folder_tag(&name) {
+                if tag == name_tag {
+                    out.push(path);
+                }
+                continue;
+            }
+            if !include_legacy {
+                continue;
+            }
             let matches_default = name == base || name.starts_with(&format!("{}-", base));
             let matches_parens = expected_parens
                 .as_ref()
@@ -289,12 +493,12 @@ fn backup_entry_timestamp(path: &Path) -> DateTime<Utc> {
     Utc::now()
 }
 
+fn backup_name_template() -> String {
+    get_setting_value("backup_name_template").unwrap_or_default()
+}
+
 fn parse_backup_timestamp(name: &str) -> Option<DateTime<Utc>> {
-    let trimmed = name
-        .strip_suffix(".sqoba.zip")
-        .or_else(|| name.strip_suffix(".zip"))
-        .unwrap_or(name);
-    let naive = NaiveDateTime::parse_from_str(trimmed, "%H%M%S_%d%m%Y").ok()?;
+    let naive = parse_backup_name(name, &backup_name_template())?;
     Local
         .from_local_datetime(&naive)
         .single()
@@ -339,51 +543,6 @@ fn derive_save_root_from_manifest(manifest: &BackupArchiveManifest) -> Option<St
         .map(|path| path.to_string_lossy().to_string())
 }
 
-fn parse_backup_relative(backup_path: &str) -> Option<(String, PathBuf)> {
-    let parts: Vec<&str> = backup_path.split('/').filter(|p| !p.is_empty()).collect();
-    if parts.len() < 3 || parts[0] != "files" {
-        return None;
-    }
-    let root_label = parts[1].to_string();
-    let mut rel = PathBuf::new();
-    for part in parts.iter().skip(2) {
-        rel.push(part);
-    }
-    Some((root_label, rel))
-}
-
-fn strip_suffix_path(path: &Path, suffix: &Path) -> Option<PathBuf> {
-    let path_components: Vec<_> = path.components().collect();
-    let suffix_components: Vec<_> = suffix.components().collect();
-    if suffix_components.is_empty() {
-        return Some(path.to_path_buf());
-    }
-    if path_components.len() < suffix_components.len() {
-        return None;
-    }
-    let start = path_components.len() - suffix_components.len();
-    for (a, b) in path_components[start..]
-        .iter()
-        .zip(suffix_components.iter())
-    {
-        let a_str = a.as_os_str().to_string_lossy();
-        let b_str = b.as_os_str().to_string_lossy();
-        let matches = if cfg!(target_os = "windows") {
-            a_str.to_lowercase() == b_str.to_lowercase()
-        } else {
-            a_str == b_str
-        };
-        if !matches {
-            return None;
-        }
-    }
-    let mut out = PathBuf::new();
-    for comp in path_components.iter().take(start) {
-        out.push(comp.as_os_str());
-    }
-    Some(out)
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DiskType {
     Hdd,
@@ -507,8 +666,68 @@ fn detect_disk_type_windows(_path: &Path) -> DiskType {
     DiskType::Unknown
 }
 
+fn load_learned_threads(letter: &str) -> Option<usize> {
+    get_setting_value(&format!("disk_threads_{}", letter))
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|threads| *threads > 0)
+}
+
+/// Persists the parallelism the throughput tuner settled on for the drive holding `path`.
+fn save_learned_threads(path: &Path, threads: usize) {
+    let Some(letter) = get_drive_letter(path) else {
+        return;
+    };
+    let key = format!("disk_threads_{}", letter);
+    let _ = with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            params![key, threads.to_string()],
+        )?;
+        Ok(())
+    });
+}
+
+/// Restores smaller than this finish too fast for a meaningful speed measurement.
+const MIN_MEASURED_RESTORE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Write speed assumed for a drive no restore has been measured on yet.
+fn default_restore_mbps(path: &Path) -> f64 {
+    match detect_disk_type_windows(path) {
+        DiskType::Hdd => 80.0,
+        DiskType::Ssd => 250.0,
+        DiskType::Unknown => 120.0,
+    }
+}
+
+fn load_restore_mbps(letter: &str) -> Option<f64> {
+    get_setting_value(&format!("restore_mbps_{}", letter))
+        .and_then(|value| value.parse::<f64>().ok())
+        .filter(|mbps| *mbps > 0.0)
+}
+
+/// Blends the speed of a finished restore into the estimate kept for its target drive.
+fn save_restore_mbps(letter: &str, bytes: u64, elapsed: Duration) {
+    if bytes < MIN_MEASURED_RESTORE_BYTES {
+        return;
+    }
+    let measured = bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64().max(0.001);
+    let blended =
+        load_restore_mbps(letter).map_or(measured, |previous| (previous + measured) / 2.0);
+    let key = format!("restore_mbps_{}", letter);
+    let _ = with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            params![key, format!("{:.1}", blended)],
+        )?;
+        Ok(())
+    });
+}
+
 fn get_disk_threads(path: &Path) -> usize {
     let cpu_count = num_cpus::get().max(1);
+    if let Some(learned) = get_drive_letter(path).and_then(|letter| load_learned_threads(&letter)) {
+        return learned;
+    }
     match detect_disk_type_windows(path) {
         DiskType::Hdd => 2.min(cpu_count),
         DiskType::Ssd => 8.min(cpu_count),
@@ -568,20 +787,18 @@ fn get_game_exe_path(game_id: &str) -> Option<String> {
     })
 }
 
+fn get_game_dir(game_id: &str) -> Option<PathBuf> {
+    get_game_exe_path(game_id)
+        .and_then(|exe_path| Path::new(&exe_path).parent().map(Path::to_path_buf))
+        .filter(|dir| !dir.as_os_str().is_empty())
+}
+
 fn resolve_save_path(game_id: &str, raw: &str) -> Result<String, String> {
-    if !raw.contains(SAVE_PATH_GAME_TOKEN) {
-        return Ok(raw.to_string());
-    }
-    let exe_path = get_game_exe_path(game_id)
-        .ok_or_else(|| "Не удалось подставить {PATHTOGAME}: путь к игре не найден".to_string())?;
-    let exe_dir = Path::new(&exe_path).parent().ok_or_else(|| {
-        "Не удалось подставить {PATHTOGAME}: у пути к игре нет родительской папки".to_string()
-    })?;
-    Ok(raw.replace(SAVE_PATH_GAME_TOKEN, &exe_dir.to_string_lossy()))
+    expand_game_path_token(raw, get_game_dir(game_id).as_deref())
 }
 
 fn tokenise_save_path(game_id: &str, save_path: &str) -> String {
-    if save_path.contains(SAVE_PATH_GAME_TOKEN) {
+    if save_path.contains(GAME_PATH_TOKEN) {
         return save_path.to_string();
     }
 
@@ -609,10 +826,10 @@ fn tokenise_save_path(game_id: &str, save_path: &str) -> String {
     };
 
     if relative.as_os_str().is_empty() {
-        return SAVE_PATH_GAME_TOKEN.to_string();
+        return GAME_PATH_TOKEN.to_string();
     }
 
-    let mut out = String::from(SAVE_PATH_GAME_TOKEN);
+    let mut out = String::from(GAME_PATH_TOKEN);
     out.push(std::path::MAIN_SEPARATOR);
     out.push_str(&relative.to_string_lossy());
     out
@@ -638,6 +855,15 @@ fn get_game_save_path(game_id: &str) -> Result<Option<String>, String> {
     Ok(Some(resolve_save_path(game_id, trimmed)?))
 }
 
+/// Like `get_game_save_path`, but logs instead of failing so callers can fall back
+/// to manifest/heuristic discovery when `{PATHTOGAME}` cannot be resolved.
+fn load_save_path_override(game_id: &str) -> Option<String> {
+    get_game_save_path(game_id).unwrap_or_else(|e| {
+        eprintln!("Ignoring save path override for {}: {}", game_id, e);
+        None
+    })
+}
+
 fn set_game_save_path(game_id: &str, save_path: &str) -> Result<(), String> {
     let save_path = tokenise_save_path(game_id, save_path);
     with_db(|conn| {
@@ -650,137 +876,661 @@ fn set_game_save_path(game_id: &str, save_path: &str) -> Result<(), String> {
     .map_err(|e| e.to_string())
 }
 
-fn get_compression_settings() -> (bool, u8, bool) {
-    let enabled = get_setting_bool("backup_compression_enabled", true);
-    let level = get_setting_i32("backup_compression_level", 60).clamp(1, 100) as u8;
-    let skip_once = get_setting_bool("backup_skip_compression_once", false);
-    (enabled, level, skip_once)
+/// Re-stores every absolute save path through `tokenise_save_path`, so paths saved before
+/// `{PATHTOGAME}` existed (or before the game was moved under its install) become portable.
+/// Returns how many rows changed.
+pub(crate) fn retokenise_save_paths() -> Result<usize, String> {
+    let rows: Vec<(String, String)> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, save_path FROM games WHERE save_path IS NOT NULL AND save_path != ''",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    })
+    .map_err(|e| e.to_string())?;
+
+    let mut changed = 0;
+    for (game_id, save_path) in rows {
+        if tokenise_save_path(&game_id, save_path.trim()) != save_path {
+            set_game_save_path(&game_id, save_path.trim())?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
 }
 
-fn clear_skip_compression_once() {
-    let _ = with_db(|conn| {
-        conn.execute(
-            "INSERT OR REPLACE INTO settings (key, value) VALUES ('backup_skip_compression_once', 'false')",
-            [],
+/// Forgets the per-drive disk types detected earlier so they are probed again.
+pub(crate) fn clear_cached_disk_types() -> Result<usize, String> {
+    with_db(|conn| conn.execute("DELETE FROM settings WHERE key LIKE 'disk_type_%'", []))
+        .map_err(|e| e.to_string())
+}
+
+fn list_game_save_paths(game_id: &str) -> Result<Vec<GameSavePath>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, game_id, path, label, enabled, exclude, created_at
+             FROM game_save_paths WHERE game_id = ?1 ORDER BY created_at",
         )?;
-        Ok(())
-    });
+        let rows = stmt
+            .query_map(params![game_id], |row| {
+                Ok(GameSavePath {
+                    id: row.get(0)?,
+                    game_id: row.get(1)?,
+                    path: row.get(2)?,
+                    label: row.get(3)?,
+                    enabled: row.get::<_, i32>(4)? == 1,
+                    exclude: row.get::<_, i32>(5)? == 1,
+                    created_at: row.get(6)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    })
+    .map_err(|e| e.to_string())
 }
 
-fn get_max_backups() -> i32 {
-    get_setting_i32("max_backups_per_game", 5).clamp(1, 100)
+/// Enabled `game_save_paths` rows in the form save discovery takes them.
+fn load_extra_save_roots(game_id: &str) -> Vec<ConfiguredRoot> {
+    list_game_save_paths(game_id)
+        .unwrap_or_else(|e| {
+            eprintln!("Ignoring extra save roots for {}: {}", game_id, e);
+            Vec::new()
+        })
+        .into_iter()
+        .filter(|root| root.enabled)
+        .map(|root| ConfiguredRoot {
+            path: root.path,
+            exclude: root.exclude,
+        })
+        .collect()
 }
 
-// Deprecated but kept for API compatibility, always returns true now
-#[tauri::command]
-pub fn check_ludusavi_installed() -> Result<bool, String> {
-    Ok(true)
+/// Every configured save root in the library with its game, for the save watcher: the
+/// main save path and the enabled, non-excluded extra roots. Roots that don't exist yet are
+/// left out.
+fn watched_save_roots() -> Vec<(PathBuf, String)> {
+    let game_ids: Vec<String> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id FROM games WHERE save_path IS NOT NULL AND TRIM(save_path) != ''
+             UNION
+             SELECT game_id FROM game_save_paths WHERE enabled = 1 AND exclude = 0",
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .flatten()
+            .collect();
+        Ok(ids)
+    })
+    .unwrap_or_default();
+
+    let mut roots = Vec::new();
+    for game_id in game_ids {
+        let game_dir = get_game_dir(&game_id);
+        let extra = load_extra_save_roots(&game_id)
+            .into_iter()
+            .filter(|root| !root.exclude)
+            .filter_map(|root| expand_game_path_token(&root.path, game_dir.as_deref()).ok());
+        for path in load_save_path_override(&game_id).into_iter().chain(extra) {
+            let path = PathBuf::from(path);
+            if path.exists() && !roots.iter().any(|(root, _)| root == &path) {
+                roots.push((path, game_id.clone()));
+            }
+        }
+    }
+    roots
 }
 
-#[tauri::command]
-pub fn get_ludusavi_executable_path() -> Result<Option<String>, String> {
-    Ok(Some("native".to_string()))
+fn read_manifest_override(game_id: &str) -> Result<Option<ManifestOverride>, String> {
+    let row: Option<(String, String, i32)> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT files, registry, replace_upstream FROM game_manifest_overrides
+             WHERE game_id = ?1",
+        )?;
+        Ok(stmt
+            .query_row(params![game_id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .ok())
+    })
+    .map_err(|e| e.to_string())?;
+    let Some((files, registry, replace_upstream)) = row else {
+        return Ok(None);
+    };
+    Ok(Some(ManifestOverride {
+        files: serde_json::from_str(&files).map_err(|e| e.to_string())?,
+        registry: serde_json::from_str(&registry).map_err(|e| e.to_string())?,
+        replace_upstream: replace_upstream == 1,
+    }))
 }
 
-#[tauri::command]
-pub fn set_ludusavi_path(_path: String) -> Result<(), String> {
-    Ok(()) // No-op
+/// The game's manifest fragment in the form save discovery takes it.
+fn load_manifest_override(game_id: &str) -> Option<ManifestOverride> {
+    read_manifest_override(game_id).unwrap_or_else(|e| {
+        eprintln!("Ignoring manifest override for {}: {}", game_id, e);
+        None
+    })
 }
 
-#[tauri::command]
-pub fn set_backup_directory(path: String) -> Result<(), String> {
-    fs::create_dir_all(&path).map_err(|e| format!("Не удалось создать папку: {}", e))?;
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestMatch {
+    pub game_id: String,
+    pub manifest_key: Option<String>,
+    /// Fuzzy match score; `None` for confirmed keys.
+    pub score: Option<f32>,
+    pub confirmed: bool,
+    /// Closest manifest names to pick from when the match is wrong.
+    pub suggestions: Vec<String>,
+}
+
+fn load_manifest_key(game_id: &str) -> Result<(Option<String>, bool), String> {
+    with_db(|conn| {
+        conn.query_row(
+            "SELECT manifest_key, COALESCE(manifest_key_confirmed, 0) FROM games WHERE id = ?1",
+            params![game_id],
+            |row| Ok((row.get(0)?, row.get::<_, i32>(1)? == 1)),
+        )
+    })
+    .map_err(|e| e.to_string())
+}
 
+fn store_manifest_key(game_id: &str, key: Option<&str>, confirmed: bool) -> Result<(), String> {
     with_db(|conn| {
         conn.execute(
-            "INSERT OR REPLACE INTO settings (key, value) VALUES ('backup_directory', ?1)",
-            params![path],
+            "UPDATE games SET manifest_key = ?1, manifest_key_confirmed = ?2 WHERE id = ?3",
+            params![key, confirmed as i32, game_id],
         )?;
         Ok(())
     })
     .map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-pub fn get_backup_directory_setting() -> Result<String, String> {
-    Ok(get_backup_directory().to_string_lossy().to_string())
+/// The manifest key the user confirmed for the game, which discovery always reuses.
+fn load_confirmed_manifest_key(game_id: &str) -> Option<String> {
+    match load_manifest_key(game_id) {
+        Ok((key, true)) => key,
+        Ok(_) => None,
+        Err(e) => {
+            eprintln!("Ignoring manifest key for {}: {}", game_id, e);
+            None
+        }
+    }
 }
 
-#[tauri::command]
-pub fn refresh_sqoba_manifest() -> Result<(), String> {
-    crate::backup::sqoba_manifest::refresh_manifest_from_network()
-        .map_err(|e| format!("Не удалось обновить манифест SQOBA: {}", e))?;
-
-    // Reload the in-memory cache so subsequent calls don't re-read/parse the manifest.
-    let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
-    engine
-        .reload_manifest()
-        .map_err(|e| format!("Не удалось перезагрузить манифест: {}", e))?;
-
-    Ok(())
+/// Records which entry name matching picked (unconfirmed) so the UI can show it for
+/// review. Returns the key and its score.
+fn remember_manifest_match(
+    engine: &BackupEngine,
+    game_id: &str,
+    game_name: &str,
+) -> Option<(String, f32)> {
+    let matched = engine
+        .match_manifest_key(game_name)
+        .filter(|(_, score)| *score >= sqoba_manifest::MATCH_THRESHOLD);
+    if let Err(e) = store_manifest_key(
+        game_id,
+        matched.as_ref().map(|(key, _)| key.as_str()),
+        false,
+    ) {
+        eprintln!("Failed to record manifest match for {}: {}", game_id, e);
+    }
+    matched
 }
 
-#[tauri::command]
-pub fn find_game_save_paths(
-    game_name: String,
-    game_id: Option<String>,
-) -> Result<SavePathLookup, String> {
+fn manifest_match(game_id: &str) -> Result<ManifestMatch, String> {
+    let game_name = load_game_exit_state(game_id)?.name;
     let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
-
     engine
         .load_manifest()
         .map_err(|e| format!("Не удалось загрузить манифест: {}", e))?;
-
-    let year = game_id.as_deref().and_then(get_game_year);
-    let name_with_year = year.map(|y| format!("{} ({})", game_name, y));
-
-    let mut last_err: Option<String> = None;
-    let save_override = match game_id.as_deref() {
-        Some(id) => match get_game_save_path(id) {
-            Ok(value) => value,
-            Err(e) => {
-                last_err = Some(e);
-                None
-            }
+    let suggestions = engine.suggest_games(&game_name, 5);
+    let (manifest_key, score, confirmed) = match load_manifest_key(game_id)? {
+        (Some(key), true) => (Some(key), None, true),
+        _ => match remember_manifest_match(&engine, game_id, &game_name) {
+            Some((key, score)) => (Some(key), Some(score), false),
+            None => (None, None, false),
         },
-        None => None,
     };
+    Ok(ManifestMatch {
+        game_id: game_id.to_string(),
+        manifest_key,
+        score,
+        confirmed,
+        suggestions,
+    })
+}
 
-    let mut roots = match engine.discover_game_save_roots(&game_name, save_override.as_deref()) {
-        Ok(value) => value,
-        Err(e) => {
-            last_err = Some(e);
-            Vec::new()
-        }
-    };
+/// The manifest entry the game is matched to, matching it by name unless the user has
+/// confirmed one.
+#[tauri::command]
+pub async fn get_manifest_match(game_id: String) -> Result<ManifestMatch, String> {
+    tauri::async_runtime::spawn_blocking(move || manifest_match(&game_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
 
-    if roots.is_empty() && save_override.is_some() {
-        roots = match engine.discover_game_save_roots(&game_name, None) {
-            Ok(value) => value,
-            Err(e) => {
-                last_err = Some(e);
-                Vec::new()
+/// Pins the game to `manifest_key` for every later discovery; `None` drops the pin and
+/// goes back to matching by name.
+#[tauri::command]
+pub async fn confirm_manifest_match(
+    game_id: String,
+    manifest_key: Option<String>,
+) -> Result<ManifestMatch, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let key = manifest_key
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty());
+        if let Some(key) = &key {
+            let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+            engine
+                .load_manifest()
+                .map_err(|e| format!("Не удалось загрузить манифест: {}", e))?;
+            if !engine.has_manifest_entry(key) {
+                return Err(format!("В манифесте нет записи «{}»", key));
             }
-        };
-    }
-
-    if roots.is_empty() {
-        if let Some(alt) = &name_with_year {
-            roots = match engine.discover_game_save_roots(alt, None) {
-                Ok(value) => value,
-                Err(e) => {
-                    last_err = Some(e);
-                    Vec::new()
-                }
-            };
         }
-    }
+        store_manifest_key(&game_id, key.as_deref(), key.is_some())?;
+        manifest_match(&game_id)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    if roots.is_empty() {
-        let _ = last_err;
-        return Ok(SavePathLookup {
-            save_path: None,
-            candidates: Vec::new(),
+#[tauri::command]
+pub fn get_manifest_override(game_id: String) -> Result<Option<ManifestOverride>, String> {
+    read_manifest_override(&game_id)
+}
+
+/// Stores the game's manifest fragment; `None` or a fragment with no paths and keys clears it.
+#[tauri::command]
+pub fn set_manifest_override(
+    game_id: String,
+    fragment: Option<ManifestOverride>,
+) -> Result<Option<ManifestOverride>, String> {
+    let fragment = fragment.and_then(ManifestOverride::normalized);
+    let Some(fragment) = fragment else {
+        with_db(|conn| {
+            conn.execute(
+                "DELETE FROM game_manifest_overrides WHERE game_id = ?1",
+                params![game_id],
+            )?;
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+        return Ok(None);
+    };
+    let files = serde_json::to_string(&fragment.files).map_err(|e| e.to_string())?;
+    let registry = serde_json::to_string(&fragment.registry).map_err(|e| e.to_string())?;
+    with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO game_manifest_overrides
+             (game_id, files, registry, replace_upstream, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                game_id,
+                files,
+                registry,
+                fragment.replace_upstream as i32,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(Some(fragment))
+}
+
+#[tauri::command]
+pub fn get_game_save_paths(game_id: String) -> Result<Vec<GameSavePath>, String> {
+    list_game_save_paths(&game_id)
+}
+
+/// Adds a save root (or, with `exclude`, a folder to leave out) used alongside the game's
+/// main save path.
+#[tauri::command]
+pub fn add_game_save_path(
+    game_id: String,
+    path: String,
+    label: Option<String>,
+    exclude: Option<bool>,
+) -> Result<GameSavePath, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Путь к сохранениям не указан".to_string());
+    }
+    let entry = GameSavePath {
+        id: Uuid::new_v4().to_string(),
+        game_id: game_id.clone(),
+        path: tokenise_save_path(&game_id, trimmed),
+        label: label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty()),
+        enabled: true,
+        exclude: exclude.unwrap_or(false),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO game_save_paths (id, game_id, path, label, enabled, exclude, created_at)
+             VALUES (?1, ?2, ?3, ?4, 1, ?5, ?6)",
+            params![
+                entry.id,
+                entry.game_id,
+                entry.path,
+                entry.label,
+                entry.exclude as i32,
+                entry.created_at
+            ],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(entry)
+}
+
+#[tauri::command]
+pub fn update_game_save_path(
+    id: String,
+    label: Option<String>,
+    enabled: Option<bool>,
+    exclude: Option<bool>,
+) -> Result<(), String> {
+    let updated = with_db(|conn| {
+        conn.execute(
+            "UPDATE game_save_paths
+             SET label = COALESCE(?2, label),
+                 enabled = COALESCE(?3, enabled),
+                 exclude = COALESCE(?4, exclude)
+             WHERE id = ?1",
+            params![
+                id,
+                label.map(|label| label.trim().to_string()),
+                enabled.map(i32::from),
+                exclude.map(i32::from)
+            ],
+        )
+    })
+    .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err("Путь к сохранениям не найден".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_game_save_path(id: String) -> Result<(), String> {
+    with_db(|conn| {
+        conn.execute("DELETE FROM game_save_paths WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn get_compression_settings() -> (bool, u8, bool) {
+    let enabled = get_setting_bool("backup_compression_enabled", true);
+    let level = get_setting_i32("backup_compression_level", 60).clamp(1, 100) as u8;
+    let skip_once = get_setting_bool("backup_skip_compression_once", false);
+    (enabled, level, skip_once)
+}
+
+/// Verification mode for backups written under `backup_root`; off unless asked for. A
+/// per-drive `backup_verify_<letter>` setting (e.g. `backup_verify_E:` = `all` for a flaky USB
+/// stick) takes precedence over the global `backup_verify_mode`.
+fn get_verify_mode(backup_root: &Path) -> VerifyMode {
+    let sample_size = get_setting_i32(
+        "backup_verify_sample_size",
+        DEFAULT_VERIFY_SAMPLE_SIZE as i32,
+    )
+    .clamp(1, 1000) as usize;
+    let per_disk = get_drive_letter(backup_root)
+        .and_then(|letter| get_setting_value(&format!("backup_verify_{}", letter)));
+    let value = per_disk
+        .or_else(|| get_setting_value("backup_verify_mode"))
+        .unwrap_or_else(|| "off".to_string());
+    VerifyMode::from_setting(&value, sample_size)
+}
+
+fn clear_skip_compression_once() {
+    let _ = with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('backup_skip_compression_once', 'false')",
+            [],
+        )?;
+        Ok(())
+    });
+}
+
+fn get_max_backups() -> i32 {
+    get_setting_i32("max_backups_per_game", 5).clamp(1, 100)
+}
+
+// Deprecated but kept for API compatibility, always returns true now
+#[tauri::command]
+pub fn check_ludusavi_installed() -> Result<bool, String> {
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn get_ludusavi_executable_path() -> Result<Option<String>, String> {
+    Ok(Some("native".to_string()))
+}
+
+#[tauri::command]
+pub fn set_ludusavi_path(_path: String) -> Result<(), String> {
+    Ok(()) // No-op
+}
+
+#[tauri::command]
+pub fn set_backup_directory(path: String) -> Result<(), String> {
+    fs::create_dir_all(&path).map_err(|e| format!("Не удалось создать папку: {}", e))?;
+
+    with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('backup_directory', ?1)",
+            params![path],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_backup_directory_setting() -> Result<String, String> {
+    Ok(get_backup_directory().to_string_lossy().to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestInfo {
+    pub etag: Option<String>,
+    /// `Last-Modified` reported by the server for the cached copy.
+    pub last_modified: Option<String>,
+    /// When the cached copy was downloaded.
+    pub fetched_at: Option<String>,
+    /// When the server was last asked, including checks that found no change.
+    pub checked_at: Option<String>,
+    pub refresh_interval_hours: i32,
+    pub cache_path: String,
+    pub cache_bytes: Option<u64>,
+    /// Games in the loaded manifest; `None` until something has loaded it.
+    pub game_count: Option<usize>,
+}
+
+fn store_setting_value(key: &str, value: &str) -> Result<(), String> {
+    with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn load_manifest_info() -> ManifestInfo {
+    let cache_path = sqoba_manifest::default_cache_path();
+    let game_count = BACKUP_ENGINE
+        .lock()
+        .ok()
+        .and_then(|engine| engine.manifest_game_count());
+    ManifestInfo {
+        etag: get_setting_value("manifest_etag").filter(|v| !v.is_empty()),
+        last_modified: get_setting_value("manifest_last_modified").filter(|v| !v.is_empty()),
+        fetched_at: get_setting_value("manifest_fetched_at").filter(|v| !v.is_empty()),
+        checked_at: get_setting_value("manifest_checked_at").filter(|v| !v.is_empty()),
+        refresh_interval_hours: get_setting_i32(
+            "manifest_refresh_hours",
+            manifest_refresh::DEFAULT_REFRESH_HOURS,
+        ),
+        cache_bytes: fs::metadata(&cache_path).ok().map(|m| m.len()),
+        cache_path: cache_path.to_string_lossy().to_string(),
+        game_count,
+    }
+}
+
+/// Asks the server for a newer manifest and reloads the engine when one arrives. Without
+/// `force` (and with a cache on disk) the stored validators make an unchanged manifest a
+/// cheap `304`. Returns whether a new manifest was downloaded.
+fn refresh_manifest_now(
+    force: bool,
+    progress: sqoba_manifest::DownloadProgress,
+) -> Result<bool, String> {
+    let validators = if force || !sqoba_manifest::default_cache_path().exists() {
+        sqoba_manifest::ManifestValidators::default()
+    } else {
+        sqoba_manifest::ManifestValidators {
+            etag: get_setting_value("manifest_etag").filter(|v| !v.is_empty()),
+            last_modified: get_setting_value("manifest_last_modified").filter(|v| !v.is_empty()),
+        }
+    };
+    let fetch = sqoba_manifest::refresh_manifest_conditional(&validators, progress)
+        .map_err(|e| format!("Не удалось обновить манифест SQOBA: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+    store_setting_value("manifest_checked_at", &now)?;
+
+    let sqoba_manifest::ManifestFetch::Updated { validators, .. } = fetch else {
+        progress("unchanged", 0, None);
+        return Ok(false);
+    };
+    store_setting_value("manifest_etag", validators.etag.as_deref().unwrap_or(""))?;
+    store_setting_value(
+        "manifest_last_modified",
+        validators.last_modified.as_deref().unwrap_or(""),
+    )?;
+    store_setting_value("manifest_fetched_at", &now)?;
+
+    // Reload the in-memory cache so subsequent calls don't re-read/parse the manifest.
+    let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+    engine
+        .reload_manifest()
+        .map_err(|e| format!("Не удалось перезагрузить манифест: {}", e))?;
+    progress("done", 0, None);
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn refresh_sqoba_manifest() -> Result<(), String> {
+    refresh_manifest_now(true, &|_, _, _| {}).map(|_| ())
+}
+
+#[tauri::command]
+pub fn get_manifest_info() -> Result<ManifestInfo, String> {
+    Ok(load_manifest_info())
+}
+
+/// Conditional refresh with `manifest:progress` events; `force` skips the `ETag` check.
+#[tauri::command]
+pub async fn refresh_manifest(
+    app: tauri::AppHandle,
+    force: Option<bool>,
+) -> Result<ManifestInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let progress = |stage: &'static str, downloaded: u64, total: Option<u64>| {
+            manifest_refresh::emit_progress(&app, stage, downloaded, total)
+        };
+        refresh_manifest_now(force.unwrap_or(false), &progress)?;
+        Ok(load_manifest_info())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn find_game_save_paths(
+    game_name: String,
+    game_id: Option<String>,
+) -> Result<SavePathLookup, String> {
+    let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+
+    engine
+        .load_manifest()
+        .map_err(|e| format!("Не удалось загрузить манифест: {}", e))?;
+
+    let year = game_id.as_deref().and_then(get_game_year);
+    let name_with_year = year.map(|y| format!("{} ({})", game_name, y));
+
+    let mut last_err: Option<String> = None;
+    let save_override = match game_id.as_deref() {
+        Some(id) => match get_game_save_path(id) {
+            Ok(value) => value,
+            Err(e) => {
+                last_err = Some(e);
+                None
+            }
+        },
+        None => None,
+    };
+    let extra_roots = game_id
+        .as_deref()
+        .map(load_extra_save_roots)
+        .unwrap_or_default();
+    let game_dir = game_id.as_deref().and_then(get_game_dir);
+    let manifest_override = game_id.as_deref().and_then(load_manifest_override);
+    let manifest_key = game_id.as_deref().and_then(load_confirmed_manifest_key);
+    let lookup = SaveLookup {
+        override_path: save_override.as_deref(),
+        extra_roots: &extra_roots,
+        game_dir: game_dir.as_deref(),
+        manifest_override: manifest_override.as_ref(),
+        manifest_key: manifest_key.as_deref(),
+    };
+
+    let mut roots = match engine.discover_game_save_roots(&game_name, lookup) {
+        Ok(value) => value,
+        Err(e) => {
+            last_err = Some(e);
+            Vec::new()
+        }
+    };
+
+    if roots.is_empty() && lookup.has_overrides() {
+        roots = match engine.discover_game_save_roots(&game_name, lookup.without_override()) {
+            Ok(value) => value,
+            Err(e) => {
+                last_err = Some(e);
+                Vec::new()
+            }
+        };
+    }
+
+    if roots.is_empty() {
+        if let Some(alt) = &name_with_year {
+            roots = match engine.discover_game_save_roots(alt, lookup.without_override()) {
+                Ok(value) => value,
+                Err(e) => {
+                    last_err = Some(e);
+                    Vec::new()
+                }
+            };
+        }
+    }
+
+    if roots.is_empty() {
+        let _ = last_err;
+        return Ok(SavePathLookup {
+            save_path: None,
+            candidates: Vec::new(),
         });
     }
 
@@ -796,7 +1546,7 @@ pub fn find_game_save_paths(
     let first_root = candidates.first().cloned();
     let mut save_path = save_override.clone().or_else(|| first_root.clone());
 
-    if save_override.is_none() && candidates.len() == 1 {
+    if save_override.is_none() && extra_roots.is_empty() && candidates.len() == 1 {
         if let (Some(game_id), Some(candidate)) = (game_id.as_deref(), first_root.clone()) {
             if set_game_save_path(game_id, &candidate).is_ok() {
                 save_path = Some(candidate);
@@ -810,6 +1560,184 @@ pub fn find_game_save_paths(
     })
 }
 
+/// Every save root the manifest and heuristics know of for the game, ranked by how well the
+/// folder name matches, how recently it changed and whether its size looks like saves.
+/// Nothing is stored; the UI commits the root the user picks.
+#[tauri::command]
+pub async fn suggest_save_paths(game_id: String) -> Result<Vec<SaveCandidate>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let game_name = load_game_exit_state(&game_id)?.name;
+        let game_dir = get_game_dir(&game_id);
+        let wiki_paths = pcgamingwiki::confirmed_save_paths(&game_id);
+        let roots = {
+            let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+            if let Err(e) = engine.load_manifest() {
+                eprintln!("Manifest unavailable while suggesting save paths: {}", e);
+            }
+            engine.candidate_save_roots(&game_name, &wiki_paths, game_dir.as_deref())
+        };
+
+        let configured: Vec<PathBuf> = load_save_path_override(&game_id)
+            .into_iter()
+            .chain(
+                load_extra_save_roots(&game_id)
+                    .into_iter()
+                    .filter(|root| !root.exclude)
+                    .filter_map(|root| resolve_save_path(&game_id, &root.path).ok()),
+            )
+            .map(PathBuf::from)
+            .collect();
+        let now = std::time::SystemTime::now();
+        let candidates = roots
+            .into_iter()
+            .map(|(path, source)| {
+                let stats = save_candidates::root_stats(&path);
+                let mut candidate =
+                    save_candidates::score_candidate(&game_name, &path, source, stats, now);
+                candidate.configured = configured.contains(&path);
+                candidate
+            })
+            .collect();
+        Ok(save_candidates::rank(candidates))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Save discovery walks the disk; a few games at a time keeps a bulk toggle from saturating
+/// an HDD while still finishing large libraries quickly.
+const BULK_DISCOVERY_THREADS: usize = 4;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MissingSaveGame {
+    pub game_id: String,
+    pub name: Option<String>,
+    /// Why discovery failed, when it failed rather than finding nothing.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkBackupToggle {
+    pub enabled: bool,
+    /// Games whose `backup_enabled` now matches `enabled`.
+    pub updated: Vec<String>,
+    /// Games without a detectable save folder. Left disabled unless the call was forced.
+    pub missing_saves: Vec<MissingSaveGame>,
+}
+
+/// `(game name, first save root)`, or why the game could not be checked.
+type SaveRootCheck = Result<(String, Option<PathBuf>), String>;
+
+/// First save root the manifest, heuristics or the game's configured paths yield, trying
+/// the same fallbacks as `find_game_save_paths`.
+fn confirm_save_root(engine: &BackupEngine, game_id: &str) -> SaveRootCheck {
+    let game_name = load_game_exit_state(game_id)?.name;
+    let save_override = load_save_path_override(game_id);
+    let extra_roots = load_extra_save_roots(game_id);
+    let game_dir = get_game_dir(game_id);
+    let manifest_override = load_manifest_override(game_id);
+    let manifest_key = load_confirmed_manifest_key(game_id);
+    let lookup = SaveLookup {
+        override_path: save_override.as_deref(),
+        extra_roots: &extra_roots,
+        game_dir: game_dir.as_deref(),
+        manifest_override: manifest_override.as_ref(),
+        manifest_key: manifest_key.as_deref(),
+    };
+    let name_with_year = get_game_year(game_id).map(|y| format!("{} ({})", game_name, y));
+
+    let mut roots = engine.discover_game_save_roots(&game_name, lookup)?;
+    if roots.is_empty() && lookup.has_overrides() {
+        roots = engine.discover_game_save_roots(&game_name, lookup.without_override())?;
+    }
+    if roots.is_empty() {
+        if let Some(alt) = &name_with_year {
+            roots = engine.discover_game_save_roots(alt, lookup.without_override())?;
+        }
+    }
+    Ok((game_name, roots.into_iter().next().map(|root| root.path)))
+}
+
+/// Splits discovery results into the games to flip and the ones without saves. `force`
+/// flips every game but still reports the missing ones.
+fn plan_bulk_enable(
+    results: Vec<(String, SaveRootCheck)>,
+    force: bool,
+) -> (Vec<String>, Vec<MissingSaveGame>) {
+    let mut update = Vec::new();
+    let mut missing = Vec::new();
+    for (game_id, result) in results {
+        let (name, error, found) = match result {
+            Ok((name, root)) => (Some(name), None, root.is_some()),
+            Err(e) => (None, Some(e), false),
+        };
+        if found || force {
+            update.push(game_id.clone());
+        }
+        if !found {
+            missing.push(MissingSaveGame {
+                game_id,
+                name,
+                error,
+            });
+        }
+    }
+    (update, missing)
+}
+
+/// Turns backups on or off for many games at once. Enabling first checks, a few games in
+/// parallel, that each game's saves can be found, and skips those where they can't unless
+/// `force` is set. Disabling needs no checks.
+#[tauri::command]
+pub async fn set_backup_enabled_bulk(
+    game_ids: Vec<String>,
+    enabled: bool,
+    force: Option<bool>,
+) -> Result<BulkBackupToggle, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (update, missing_saves) = if enabled {
+            let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+            if let Err(e) = engine.load_manifest() {
+                eprintln!("Manifest unavailable while enabling backups: {}", e);
+            }
+            let engine: &BackupEngine = &engine;
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(BULK_DISCOVERY_THREADS)
+                .build()
+                .map_err(|e| e.to_string())?;
+            let results = pool.install(|| {
+                use rayon::prelude::*;
+                game_ids
+                    .par_iter()
+                    .map(|game_id| (game_id.clone(), confirm_save_root(engine, game_id)))
+                    .collect()
+            });
+            plan_bulk_enable(results, force.unwrap_or(false))
+        } else {
+            (game_ids, Vec::new())
+        };
+
+        with_db(|conn| {
+            for game_id in &update {
+                conn.execute(
+                    "UPDATE games SET backup_enabled = ?1 WHERE id = ?2",
+                    params![enabled as i32, game_id],
+                )?;
+            }
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+
+        Ok(BulkBackupToggle {
+            enabled,
+            updated: update,
+            missing_saves,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 pub fn find_game_saves(
     game_name: String,
@@ -836,7 +1764,22 @@ pub fn find_game_saves(
         },
         None => None,
     };
-    let mut discovery = match engine.discover_game_saves(&game_name, save_override.as_deref()) {
+    let extra_roots = game_id
+        .as_deref()
+        .map(load_extra_save_roots)
+        .unwrap_or_default();
+    let game_dir = game_id.as_deref().and_then(get_game_dir);
+    let manifest_override = game_id.as_deref().and_then(load_manifest_override);
+    let manifest_key = game_id.as_deref().and_then(load_confirmed_manifest_key);
+    let lookup = SaveLookup {
+        override_path: save_override.as_deref(),
+        extra_roots: &extra_roots,
+        game_dir: game_dir.as_deref(),
+        manifest_override: manifest_override.as_ref(),
+        manifest_key: manifest_key.as_deref(),
+    };
+    let registry_keys = engine.manifest_registry_keys(&game_name, lookup);
+    let mut discovery = match engine.discover_game_saves(&game_name, lookup) {
         Ok(value) => value,
         Err(e) => {
             last_err = Some(e);
@@ -844,8 +1787,8 @@ pub fn find_game_saves(
         }
     };
 
-    if discovery.is_none() && save_override.is_some() {
-        discovery = match engine.discover_game_saves(&game_name, None) {
+    if discovery.is_none() && lookup.has_overrides() {
+        discovery = match engine.discover_game_saves(&game_name, lookup.without_override()) {
             Ok(value) => value,
             Err(e) => {
                 last_err = Some(e);
@@ -854,71 +1797,306 @@ pub fn find_game_saves(
         };
     }
 
-    if discovery.is_none() {
-        if let Some(alt) = &name_with_year {
-            discovery = match engine.discover_game_saves(alt, None) {
-                Ok(value) => value,
-                Err(e) => {
-                    last_err = Some(e);
-                    None
-                }
-            };
+    if discovery.is_none() {
+        if let Some(alt) = &name_with_year {
+            discovery = match engine.discover_game_saves(alt, lookup.without_override()) {
+                Ok(value) => value,
+                Err(e) => {
+                    last_err = Some(e);
+                    None
+                }
+            };
+        }
+    }
+
+    match discovery {
+        Some(discovery) => {
+            let file_strings: Vec<String> = discovery
+                .files
+                .iter()
+                .map(|entry| entry.path.to_string_lossy().to_string())
+                .collect();
+            let first_root = discovery
+                .roots
+                .first()
+                .map(|root| root.path.to_string_lossy().to_string());
+            let mut save_path = save_override.clone().or_else(|| first_root.clone());
+
+            // A guess from the install folder is shown but not committed as the save path.
+            if save_override.is_none()
+                && extra_roots.is_empty()
+                && discovery.roots.len() == 1
+                && !discovery.is_low_confidence()
+            {
+                if let (Some(game_id), Some(candidate)) = (game_id.as_deref(), first_root.clone()) {
+                    if set_game_save_path(game_id, &candidate).is_ok() {
+                        save_path = Some(candidate);
+                    }
+                }
+            }
+
+            Ok(Some(BackupInfo {
+                game_name,
+                save_path,
+                registry_path: registry_keys.first().cloned(),
+                registry_keys,
+                total_size: discovery.total_size,
+                files: file_strings,
+                low_confidence: discovery.is_low_confidence(),
+                container_names: discovery
+                    .container_names
+                    .into_iter()
+                    .map(|(path, name)| (path.to_string_lossy().to_string(), name))
+                    .collect(),
+            }))
+        }
+        None => {
+            // If we couldn't resolve an override or any heuristic, just report "not found".
+            // This keeps the UI behaviour consistent and avoids surfacing low-level errors.
+            let _ = last_err;
+            Ok(None)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn create_backup(
+    app: tauri::AppHandle,
+    game_id: String,
+    game_name: String,
+    is_auto: bool,
+    notes: Option<String>,
+    label: Option<String>,
+) -> Result<Backup, BackupError> {
+    let game_id_clone = game_id.clone();
+    let game_name_clone = game_name.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        guard_running_game(&app, &game_id_clone)?;
+        create_backup_inner(
+            Some(app),
+            game_id_clone,
+            game_name_clone,
+            is_auto,
+            notes,
+            label,
+            None,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    record_backup_event(&game_id, is_auto, 1, &result);
+    result
+}
+
+/// What `create_backup` does while the game is still running (`backup_while_running`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunningGamePolicy {
+    /// Back up anyway and tell the UI the saves may be half-written.
+    Warn,
+    Refuse,
+    /// Block until every instance has exited, then back up.
+    Wait,
+}
+
+impl RunningGamePolicy {
+    fn from_setting(value: &str) -> Self {
+        match value.trim() {
+            "refuse" => RunningGamePolicy::Refuse,
+            "wait" => RunningGamePolicy::Wait,
+            _ => RunningGamePolicy::Warn,
+        }
+    }
+}
+
+const RUNNING_GAME_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const RUNNING_GAME_MAX_WAIT: Duration = Duration::from_secs(6 * 60 * 60);
+
+fn guard_running_game(app: &tauri::AppHandle, game_id: &str) -> Result<(), String> {
+    let is_running =
+        || games_service::get_running_instances(&GlobalDb, game_id.to_string()).map(|n| n > 0);
+    if !is_running()? {
+        return Ok(());
+    }
+
+    let policy = RunningGamePolicy::from_setting(
+        &get_setting_value("backup_while_running").unwrap_or_default(),
+    );
+    let emit = |stage: &str, message: &str| {
+        let _ = app.emit(
+            "backup:progress",
+            BackupProgressEvent {
+                game_id: game_id.to_string(),
+                stage: stage.to_string(),
+                message: message.to_string(),
+                done: 0,
+                total: 0,
+            },
+        );
+    };
+    match policy {
+        RunningGamePolicy::Refuse => {
+            Err("Игра запущена: закройте её перед созданием бэкапа".to_string())
+        }
+        RunningGamePolicy::Warn => {
+            emit(
+                "running",
+                "Игра запущена: сохранения могут быть записаны не полностью",
+            );
+            Ok(())
+        }
+        RunningGamePolicy::Wait => {
+            emit("waiting", "Ожидание закрытия игры");
+            wait_for_exit(
+                RUNNING_GAME_POLL_INTERVAL,
+                RUNNING_GAME_MAX_WAIT,
+                std::thread::sleep,
+                is_running,
+            )
+        }
+    }
+}
+
+/// Polls `is_running` every `interval` until it reports false, giving up after `max_wait`.
+fn wait_for_exit(
+    interval: Duration,
+    max_wait: Duration,
+    sleep: impl Fn(Duration),
+    mut is_running: impl FnMut() -> Result<bool, String>,
+) -> Result<(), String> {
+    let mut waited = Duration::ZERO;
+    while is_running()? {
+        if waited >= max_wait {
+            return Err("Игра не закрылась, бэкап отменён".to_string());
+        }
+        sleep(interval);
+        waited += interval;
+    }
+    Ok(())
+}
+
+/// Delays before each retry of a failed auto backup.
+const AUTO_BACKUP_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_secs(10),
+    Duration::from_secs(60),
+    Duration::from_secs(300),
+];
+
+fn record_backup_event<E: std::fmt::Display>(
+    game_id: &str,
+    is_auto: bool,
+    attempt: u32,
+    result: &Result<Backup, E>,
+) {
+    let (backup_id, error) = match result {
+        Ok(backup) => (Some(backup.id.as_str()), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let recorded = with_db(|conn| {
+        conn.execute(
+            "INSERT INTO backup_events (game_id, backup_id, created_at, is_auto, success, attempt, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                game_id,
+                backup_id,
+                Utc::now().to_rfc3339(),
+                if is_auto { 1 } else { 0 },
+                if result.is_ok() { 1 } else { 0 },
+                attempt,
+                error
+            ],
+        )?;
+        Ok(())
+    });
+    if let Err(e) = recorded {
+        eprintln!("Failed to record backup event for {}: {}", game_id, e);
+    }
+    crate::plugins::dispatch(
+        "backup_completed",
+        serde_json::json!({
+            "game_id": game_id,
+            "is_auto": is_auto,
+            "attempt": attempt,
+            "backup": result.as_ref().ok(),
+            "error": error,
+        }),
+    );
+}
+
+/// Whether a failed backup is worth another try: a save file was locked, held open by
+/// another process, or briefly inaccessible. Anything else (no saves found, a missing save
+/// path, a full disk) fails at once, so a retry never races a relaunched game for its saves.
+fn is_transient_backup_error(error: &str) -> bool {
+    // Windows reports sharing and lock violations as codes without an `ErrorKind` of their own.
+    const SHARING_VIOLATION: i32 = 32;
+    const LOCK_VIOLATION: i32 = 33;
+    let os_code = error
+        .rsplit_once("(os error ")
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .and_then(|(code, _)| code.parse::<i32>().ok());
+    if let Some(code) = os_code {
+        if cfg!(target_os = "windows") && matches!(code, SHARING_VIOLATION | LOCK_VIOLATION) {
+            return true;
         }
+        return matches!(
+            std::io::Error::from_raw_os_error(code).kind(),
+            std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::WouldBlock
+        );
     }
+    let error = error.to_lowercase();
+    error.contains("permission denied") || error.contains("operation would block")
+}
 
-    match discovery {
-        Some(discovery) => {
-            let file_strings: Vec<String> = discovery
-                .files
-                .iter()
-                .map(|entry| entry.path.to_string_lossy().to_string())
-                .collect();
-            let first_root = discovery
-                .roots
-                .first()
-                .map(|root| root.path.to_string_lossy().to_string());
-            let mut save_path = save_override.clone().or_else(|| first_root.clone());
-
-            if save_override.is_none() && discovery.roots.len() == 1 {
-                if let (Some(game_id), Some(candidate)) = (game_id.as_deref(), first_root.clone()) {
-                    if set_game_save_path(game_id, &candidate).is_ok() {
-                        save_path = Some(candidate);
-                    }
+/// Runs `op` (given the 1-based attempt number) until it succeeds, sleeping for the
+/// next entry of `delays` after each failure that `retryable` accepts. Returns the error
+/// as soon as it is not retryable, or the last one once delays run out.
+fn retry_with_backoff<T>(
+    delays: &[Duration],
+    sleep: impl Fn(Duration),
+    retryable: impl Fn(&str) -> bool,
+    mut op: impl FnMut(u32) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if !retryable(&e) {
+                    return Err(e);
                 }
+                let Some(delay) = delays.get(attempt as usize - 1) else {
+                    return Err(e);
+                };
+                sleep(*delay);
+                attempt += 1;
             }
-
-            Ok(Some(BackupInfo {
-                game_name,
-                save_path,
-                registry_path: None,
-                total_size: discovery.total_size,
-                files: file_strings,
-            }))
-        }
-        None => {
-            // If we couldn't resolve an override or any heuristic, just report "not found".
-            // This keeps the UI behaviour consistent and avoids surfacing low-level errors.
-            let _ = last_err;
-            Ok(None)
         }
     }
 }
 
 #[tauri::command]
-pub async fn create_backup(
-    app: tauri::AppHandle,
-    game_id: String,
-    game_name: String,
-    is_auto: bool,
-    notes: Option<String>,
-) -> Result<Backup, String> {
-    let game_id_clone = game_id.clone();
-    let game_name_clone = game_name.clone();
-    tauri::async_runtime::spawn_blocking(move || {
-        create_backup_inner(Some(app), game_id_clone, game_name_clone, is_auto, notes)
+pub fn get_backup_history(game_id: String) -> Result<Vec<BackupEvent>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, game_id, backup_id, created_at, is_auto, success, attempt, error
+             FROM backup_events WHERE game_id = ?1 ORDER BY created_at DESC, id DESC LIMIT 200",
+        )?;
+        let events = stmt
+            .query_map(params![game_id], |row| {
+                Ok(BackupEvent {
+                    id: row.get(0)?,
+                    game_id: row.get(1)?,
+                    backup_id: row.get(2)?,
+                    created_at: row.get(3)?,
+                    is_auto: row.get::<_, i32>(4)? == 1,
+                    success: row.get::<_, i32>(5)? == 1,
+                    attempt: row.get(6)?,
+                    error: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(events)
     })
-    .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
 }
 
 fn create_backup_inner(
@@ -927,44 +2105,74 @@ fn create_backup_inner(
     game_name: String,
     is_auto: bool,
     notes: Option<String>,
-) -> Result<Backup, String> {
+    label: Option<String>,
+    profile: Option<&str>,
+) -> Result<Backup, BackupError> {
     let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
     // Ensure manifest
     engine
         .load_manifest()
         .map_err(|e| format!("Не удалось загрузить манифест: {}", e))?;
-    let save_path_override = get_game_save_path(&game_id).unwrap_or(None);
+    let save_path_override = load_save_path_override(&game_id);
+    let extra_roots = load_extra_save_roots(&game_id);
+    let game_dir = get_game_dir(&game_id);
+    let manifest_override = load_manifest_override(&game_id);
+    let manifest_key = load_confirmed_manifest_key(&game_id);
+    if manifest_key.is_none() {
+        remember_manifest_match(&engine, &game_id, &game_name);
+    }
+    let lookup = SaveLookup {
+        override_path: save_path_override.as_deref(),
+        extra_roots: &extra_roots,
+        game_dir: game_dir.as_deref(),
+        manifest_override: manifest_override.as_ref(),
+        manifest_key: manifest_key.as_deref(),
+    };
 
     let backup_root = get_backup_directory();
     let threads = get_disk_threads(&backup_root);
     let year = get_game_year(&game_id);
-    let safe_name = sanitize_folder_name(&game_name);
-    let game_folder = match year {
-        Some(y) => format!("{}-{}", safe_name, y),
-        None => safe_name,
-    };
-
-    let game_backup_dir = backup_root.join(game_folder);
+    let game_backup_dir = backup_root.join(game_backup_folder_name(&game_name, year.as_deref()));
     fs::create_dir_all(&game_backup_dir)
         .map_err(|e| format!("Не удалось создать папку для бэкапов: {}", e))?;
 
-    // Create timestamped backup folder
-    let timestamp = Local::now().format("%H%M%S_%d%m%Y").to_string();
+    let backup_name = render_backup_name(
+        &backup_name_template(),
+        &BackupNameContext {
+            game: &game_name,
+            created: Local::now().naive_local(),
+            trigger: if is_auto { "auto" } else { "manual" },
+            note: notes.as_deref(),
+            profile,
+        },
+    );
     let (compression_enabled, compression_level, skip_once) = get_compression_settings();
     let use_compression = compression_enabled && !skip_once;
     if skip_once {
         clear_skip_compression_once();
     }
-    let backup_path = if use_compression {
-        game_backup_dir.join(format!("{}.sqoba.zip", timestamp))
-    } else {
-        game_backup_dir.join(&timestamp)
-    };
+    let extension = if use_compression { ".sqoba.zip" } else { "" };
+    let backup_path = (1..)
+        .map(|n| {
+            let suffix = if n == 1 {
+                String::new()
+            } else {
+                format!("-{}", n)
+            };
+            game_backup_dir.join(format!("{}{}{}", backup_name, suffix, extension))
+        })
+        .find(|path| !path.exists())
+        .unwrap_or_else(|| game_backup_dir.join(&backup_name));
     let backup_options = if use_compression {
         BackupOptions::zip(compression_level)
     } else {
         BackupOptions::directory()
-    };
+    }
+    .with_verify(get_verify_mode(&backup_root))
+    .with_symlinks(SymlinkPolicy::from_setting(
+        &get_setting_value("backup_symlink_policy").unwrap_or_default(),
+    ))
+    .with_shadow_copy(get_setting_bool("backup_shadow_copy", true));
 
     // Run native backup
     if let Some(app) = &app {
@@ -1000,24 +2208,33 @@ fn create_backup_inner(
     let year = get_game_year(&game_id);
     let name_with_year = year.map(|y| format!("{} ({})", game_name, y));
 
-    let mut attempts: Vec<(&str, Option<&str>)> = Vec::new();
-    attempts.push((&game_name, save_path_override.as_deref()));
-    if save_path_override.is_some() {
-        attempts.push((&game_name, None));
+    let mut attempts: Vec<(&str, SaveLookup)> = Vec::new();
+    attempts.push((&game_name, lookup));
+    if lookup.has_overrides() {
+        attempts.push((&game_name, lookup.without_override()));
     }
     if let Some(alt) = name_with_year.as_deref() {
-        attempts.push((alt, None));
+        attempts.push((alt, lookup.without_override()));
     }
 
-    let mut last_err: Option<String> = None;
+    let metadata = capture_backup_metadata(&game_id, label);
+    engine.set_metadata(Some(metadata.clone()));
+    let watch = watchdog::register(&game_id, "backup", app.clone());
+    engine.set_monitor(Some(watch.monitor()));
+    let started = std::time::Instant::now();
+    let defender = DefenderCpuSampler::start();
+    let mut last_err: Option<BackupError> = None;
     let mut backup_size: Option<u64> = None;
-    for (name, override_path) in attempts {
+    for (name, lookup) in attempts {
+        if watch.monitor().is_cancelled() {
+            break;
+        }
         match engine.backup_game_with_options_and_progress(
             name,
             &backup_path,
             threads,
             backup_options,
-            override_path,
+            lookup,
             progress.clone(),
         ) {
             Ok(size) => {
@@ -1037,8 +2254,17 @@ fn create_backup_inner(
         }
     }
 
+    engine.set_monitor(None);
+    engine.set_metadata(None);
+    drop(watch);
+    let duration_ms = started.elapsed().as_millis() as i64;
+    let defender_cpu = defender.finish();
+    if let Some(learned) = engine.take_tuned_threads() {
+        save_learned_threads(&backup_root, learned);
+    }
+
     let Some(backup_size) = backup_size else {
-        return Err(last_err.unwrap_or_else(|| "Не удалось создать бэкап".to_string()));
+        return Err(last_err.unwrap_or_else(|| "Не удалось создать бэкап".into()));
     };
 
     if let Some(app) = &app {
@@ -1056,73 +2282,267 @@ fn create_backup_inner(
 
     if backup_size == 0 {
         let _ = fs::remove_dir_all(&backup_path);
-        return Err("No save data found for this game".to_string());
+        return Err("No save data found for this game".into());
+    }
+
+    let backup = Backup {
+        id: Uuid::new_v4().to_string(),
+        game_id,
+        backup_path: backup_path.to_string_lossy().to_string(),
+        backup_size: backup_size as i64,
+        created_at: Utc::now().to_rfc3339(),
+        is_auto,
+        notes,
+        metadata: Some(metadata),
+    };
+    record_backup(&backup, duration_ms, defender_cpu).map_err(|e| e.to_string())?;
+
+    // Cleanup old backups
+    cleanup_old_backups(&backup.game_id)?;
+    if let Some(violation) = enforce_backup_quotas(&backup.game_id)? {
+        // The backup itself is kept; the user still has to know the quota is exceeded.
+        eprintln!(
+            "Backups of {} exceed the quota: {}",
+            backup.game_id,
+            quota_violation_message(&violation)
+        );
+        emit_quota_exceeded(&app, &backup.game_id, &game_name, &violation, false);
     }
 
-    // Record backup in database
-    let backup_id = Uuid::new_v4().to_string();
-    let created_at = Utc::now().to_rfc3339();
+    Ok(backup)
+}
 
+/// Stores a finished backup with its timing and metadata, and updates the game's backup info.
+fn record_backup(
+    backup: &Backup,
+    duration_ms: i64,
+    defender_cpu: Option<f32>,
+) -> rusqlite::Result<()> {
+    let metadata = backup.metadata.clone().unwrap_or_default();
     with_db(|conn| {
         conn.execute(
-            "INSERT INTO backups (id, game_id, backup_path, backup_size, created_at, is_auto, notes)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO backups (id, game_id, backup_path, backup_size, created_at, is_auto, notes,
+                                  duration_ms, defender_cpu, playtime_seconds,
+                                  last_session_seconds, label)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
             params![
-                backup_id,
-                game_id,
-                backup_path.to_string_lossy().to_string(),
-                backup_size as i64,
-                created_at,
-                if is_auto { 1 } else { 0 },
-                notes
+                backup.id,
+                backup.game_id,
+                backup.backup_path,
+                backup.backup_size,
+                backup.created_at,
+                if backup.is_auto { 1 } else { 0 },
+                backup.notes,
+                duration_ms,
+                defender_cpu,
+                metadata.playtime_seconds,
+                metadata.last_session_seconds,
+                metadata.label
             ],
         )?;
 
         // Update game backup info
         conn.execute(
             "UPDATE games SET last_backup = ?1, backup_count = backup_count + 1, backup_enabled = 1 WHERE id = ?2",
-            params![created_at, game_id],
+            params![backup.created_at, backup.game_id],
         )?;
 
         Ok(())
     })
-    .map_err(|e| e.to_string())?;
+}
 
-    // Cleanup old backups
-    cleanup_old_backups(&game_id)?;
+/// The game's playtime and last session length right now, plus the user's label.
+fn capture_backup_metadata(game_id: &str, label: Option<String>) -> BackupMetadata {
+    let label = label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    let (playtime_seconds, last_session_seconds) = with_db(|conn| {
+        let playtime: i64 = conn
+            .query_row(
+                "SELECT COALESCE(total_playtime, 0) FROM games WHERE id = ?1",
+                params![game_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or(0);
+        let last_session: Option<i64> = conn
+            .query_row(
+                "SELECT duration_seconds FROM play_sessions WHERE game_id = ?1
+                 ORDER BY ended_at DESC LIMIT 1",
+                params![game_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok((playtime, last_session))
+    })
+    .unwrap_or((0, None));
+    BackupMetadata {
+        playtime_seconds,
+        last_session_seconds,
+        label,
+    }
+}
 
-    Ok(Backup {
-        id: backup_id,
-        game_id,
-        backup_path: backup_path.to_string_lossy().to_string(),
-        backup_size: backup_size as i64,
-        created_at,
-        is_auto,
-        notes,
+#[derive(Debug, Serialize, Clone)]
+struct BackupQuotaEvent {
+    game_id: String,
+    game_name: String,
+    scope: QuotaScope,
+    used_bytes: u64,
+    quota_bytes: u64,
+    /// True when the backup was skipped, false when it was created but still does not fit.
+    blocked: bool,
+}
+
+fn emit_quota_exceeded(
+    app: &Option<tauri::AppHandle>,
+    game_id: &str,
+    game_name: &str,
+    violation: &QuotaViolation,
+    blocked: bool,
+) {
+    if let Some(app) = app {
+        let _ = app.emit(
+            "backup:quota-exceeded",
+            BackupQuotaEvent {
+                game_id: game_id.to_string(),
+                game_name: game_name.to_string(),
+                scope: violation.scope,
+                used_bytes: violation.used_bytes,
+                quota_bytes: violation.quota_bytes,
+                blocked,
+            },
+        );
+    }
+}
+
+/// Removes a backup folder or archive from disk; a path that is already gone counts as removed.
+fn remove_backup_files(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+fn get_quota_limits() -> QuotaLimits {
+    let mb = |key: &str| {
+        let value = get_setting_value(key)
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        (value > 0).then(|| value * 1024 * 1024)
+    };
+    QuotaLimits {
+        per_game: mb("backup_quota_game_mb"),
+        total: mb("backup_quota_total_mb"),
+    }
+}
+
+fn backup_disk_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        walkdir::WalkDir::new(path)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(|meta| meta.is_file())
+            .map(|meta| meta.len())
+            .sum()
+    } else {
+        fs::metadata(path).map(|meta| meta.len()).unwrap_or(0)
+    }
+}
+
+fn load_stored_backups() -> Result<Vec<(StoredBackup, String)>, String> {
+    let rows: Vec<(String, String, String, String)> = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT id, game_id, created_at, backup_path FROM backups")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
     })
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, game_id, created_at, path)| {
+            let disk_size = backup_disk_size(Path::new(&path));
+            (
+                StoredBackup {
+                    id,
+                    game_id,
+                    created_at,
+                    disk_size,
+                },
+                path,
+            )
+        })
+        .collect())
+}
+
+/// Deletes the oldest backups beyond the configured size quotas. Returns the violation
+/// that remains when even the newest backups do not fit.
+fn enforce_backup_quotas(game_id: &str) -> Result<Option<QuotaViolation>, String> {
+    let limits = get_quota_limits();
+    if limits.per_game.is_none() && limits.total.is_none() {
+        return Ok(None);
+    }
+
+    let stored = load_stored_backups()?;
+    let backups: Vec<StoredBackup> = stored.iter().map(|(b, _)| b.clone()).collect();
+    let plan = plan_quota_evictions(&backups, game_id, limits);
+
+    for id in &plan.evict {
+        let Some((backup, path)) = stored.iter().find(|(b, _)| &b.id == id) else {
+            continue;
+        };
+        // Keep the row while files remain on disk, so the backup stays visible and counted.
+        if let Err(e) = remove_backup_files(Path::new(path)) {
+            eprintln!("Failed to remove backup {} over quota: {}", path, e);
+            continue;
+        }
+        with_db(|conn| {
+            conn.execute("DELETE FROM backups WHERE id = ?1", params![backup.id])?;
+            conn.execute(
+                "UPDATE games SET backup_count = backup_count - 1 WHERE id = ?1 AND backup_count > 0",
+                params![backup.game_id],
+            )?;
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(plan.violation)
+}
+
+fn quota_violation_message(violation: &QuotaViolation) -> String {
+    let scope = match violation.scope {
+        QuotaScope::Game => "для игры",
+        QuotaScope::Total => "общая",
+    };
+    format!(
+        "Превышена квота бэкапов ({}): {} МБ из {} МБ",
+        scope,
+        violation.used_bytes / (1024 * 1024),
+        violation.quota_bytes / (1024 * 1024)
+    )
 }
 
 fn cleanup_old_backups(game_id: &str) -> Result<(), String> {
     let max_backups = get_max_backups();
 
     let backups: Vec<Backup> = with_db(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, game_id, backup_path, backup_size, created_at, is_auto, notes
-             FROM backups WHERE game_id = ?1 ORDER BY created_at DESC",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM backups WHERE game_id = ?1 ORDER BY created_at DESC",
+            BACKUP_COLUMNS
+        ))?;
 
         let backups = stmt
-            .query_map(params![game_id], |row| {
-                Ok(Backup {
-                    id: row.get(0)?,
-                    game_id: row.get(1)?,
-                    backup_path: row.get(2)?,
-                    backup_size: row.get(3)?,
-                    created_at: row.get(4)?,
-                    is_auto: row.get::<_, i32>(5)? == 1,
-                    notes: row.get(6)?,
-                })
-            })?
+            .query_map(params![game_id], backup_from_row)?
             .filter_map(|r| r.ok())
             .collect();
 
@@ -1134,13 +2554,9 @@ fn cleanup_old_backups(game_id: &str) -> Result<(), String> {
     if backups.len() > max_backups as usize {
         for backup in backups.iter().skip(max_backups as usize) {
             // Delete backup path directly
-            let backup_path = Path::new(&backup.backup_path);
-            if backup_path.exists() {
-                if backup_path.is_dir() {
-                    let _ = fs::remove_dir_all(backup_path);
-                } else {
-                    let _ = fs::remove_file(backup_path);
-                }
+            if let Err(e) = remove_backup_files(Path::new(&backup.backup_path)) {
+                eprintln!("Failed to remove old backup {}: {}", backup.backup_path, e);
+                continue;
             }
 
             // Remove from database
@@ -1151,11 +2567,12 @@ fn cleanup_old_backups(game_id: &str) -> Result<(), String> {
             .ok();
         }
 
-        // Update backup count
+        // Update backup count from the rows left, since undeletable backups are kept
         with_db(|conn| {
             conn.execute(
-                "UPDATE games SET backup_count = ?1 WHERE id = ?2",
-                params![max_backups, game_id],
+                "UPDATE games SET backup_count = (SELECT COUNT(*) FROM backups WHERE game_id = ?1)
+                 WHERE id = ?1",
+                params![game_id],
             )?;
             Ok(())
         })
@@ -1166,60 +2583,251 @@ fn cleanup_old_backups(game_id: &str) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn get_game_backups(game_id: String) -> Result<Vec<Backup>, String> {
-    with_db(|conn| {
+pub async fn benchmark_compression(game_id: String) -> Result<Vec<CompressionBenchmark>, String> {
+    let game_name: String = with_db(|conn| {
+        conn.query_row(
+            "SELECT name FROM games WHERE id = ?1",
+            params![game_id],
+            |row| row.get(0),
+        )
+    })
+    .map_err(|_| "Игра не найдена".to_string())?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+        engine
+            .load_manifest()
+            .map_err(|e| format!("Не удалось загрузить манифест: {}", e))?;
+        let save_path_override = load_save_path_override(&game_id);
+        let extra_roots = load_extra_save_roots(&game_id);
+        let game_dir = get_game_dir(&game_id);
+        let manifest_override = load_manifest_override(&game_id);
+        let manifest_key = load_confirmed_manifest_key(&game_id);
+        let lookup = SaveLookup {
+            override_path: save_path_override.as_deref(),
+            extra_roots: &extra_roots,
+            game_dir: game_dir.as_deref(),
+            manifest_override: manifest_override.as_ref(),
+            manifest_key: manifest_key.as_deref(),
+        };
+        engine.benchmark_compression(&game_name, lookup, &BENCHMARK_LEVELS)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Explains slow backups: recent throughput, Defender real-time scanning state and, when
+/// scanning is the likely cause, the exact folder to exclude.
+#[tauri::command]
+pub fn get_backup_diagnostics() -> Result<BackupDiagnostics, String> {
+    let samples: Vec<ThroughputSample> = with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, game_id, backup_path, backup_size, created_at, is_auto, notes
-             FROM backups WHERE game_id = ?1 ORDER BY created_at DESC",
+            "SELECT backup_size, duration_ms, defender_cpu FROM backups
+             WHERE duration_ms IS NOT NULL ORDER BY created_at DESC LIMIT 50",
         )?;
-
-        let backups = stmt
-            .query_map(params![game_id], |row| {
-                Ok(Backup {
-                    id: row.get(0)?,
-                    game_id: row.get(1)?,
-                    backup_path: row.get(2)?,
-                    backup_size: row.get(3)?,
-                    created_at: row.get(4)?,
-                    is_auto: row.get::<_, i32>(5)? == 1,
-                    notes: row.get(6)?,
+        let samples = stmt
+            .query_map([], |row| {
+                Ok(ThroughputSample {
+                    bytes: row.get::<_, i64>(0)?.max(0) as u64,
+                    duration_ms: row.get::<_, i64>(1)?.max(0) as u64,
+                    defender_cpu: row.get::<_, Option<f64>>(2)?.map(|cpu| cpu as f32),
                 })
             })?
             .filter_map(|r| r.ok())
             .collect();
+        Ok(samples)
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(defender::diagnose_backups(
+        &get_backup_directory(),
+        &samples,
+        &defender::defender_status(),
+    ))
+}
+
+/// Storage used by all backups, per game, compression ratios and monthly growth.
+#[tauri::command]
+pub fn get_backup_stats() -> Result<BackupStats, String> {
+    let rows: Vec<(String, String, String, String, i64, String)> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT b.id, b.game_id, COALESCE(g.name, ''), b.created_at, b.backup_size,
+                    b.backup_path
+             FROM backups b LEFT JOIN games g ON g.id = b.game_id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    })
+    .map_err(|e| e.to_string())?;
+
+    let rows = rows
+        .into_iter()
+        .map(
+            |(id, game_id, game_name, created_at, raw_size, path)| BackupStorageRow {
+                id,
+                game_id,
+                game_name,
+                created_at,
+                raw_size: raw_size.max(0) as u64,
+                disk_size: backup_disk_size(Path::new(&path)),
+            },
+        )
+        .collect();
+    Ok(summarize_backup_storage(rows))
+}
+
+#[tauri::command]
+pub fn get_game_backups(game_id: String) -> Result<Vec<Backup>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM backups WHERE game_id = ?1 ORDER BY created_at DESC",
+            BACKUP_COLUMNS
+        ))?;
+
+        let backups = stmt
+            .query_map(params![game_id], backup_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
 
         Ok(backups)
     })
     .map_err(|e| e.to_string())
 }
 
+fn load_backup_record(backup_id: &str) -> Result<Backup, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM backups WHERE id = ?1",
+            BACKUP_COLUMNS
+        ))?;
+
+        stmt.query_row(params![backup_id], backup_from_row)
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn plan_restore_for_backup(
+    backup: &Backup,
+    remapper: &RestorePathRemapper,
+) -> Result<Vec<RestoreRootMapping>, String> {
+    let manifest = load_backup_manifest(Path::new(&backup.backup_path))?;
+    Ok(manifest
+        .map(|manifest| remapper.plan(&manifest))
+        .unwrap_or_default())
+}
+
 #[tauri::command]
-pub async fn restore_backup(app: tauri::AppHandle, backup_id: String) -> Result<(), String> {
-    let backup: Backup = with_db(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, game_id, backup_path, backup_size, created_at, is_auto, notes
-             FROM backups WHERE id = ?1",
-        )?;
+pub fn plan_restore_paths(
+    backup_id: String,
+    path_mappings: Option<HashMap<String, String>>,
+) -> Result<Vec<RestoreRootMapping>, String> {
+    let backup = load_backup_record(&backup_id)?;
+    let game_dir = get_game_dir(&backup.game_id);
+    let remapper =
+        RestorePathRemapper::current(&path_mappings.unwrap_or_default(), game_dir.as_deref());
+    plan_restore_for_backup(&backup, &remapper)
+}
 
-        stmt.query_row(params![backup_id], |row| {
-            Ok(Backup {
-                id: row.get(0)?,
-                game_id: row.get(1)?,
-                backup_path: row.get(2)?,
-                backup_size: row.get(3)?,
-                created_at: row.get(4)?,
-                is_auto: row.get::<_, i32>(5)? == 1,
-                notes: row.get(6)?,
-            })
-        })
+fn build_restore_preview(
+    backup: &Backup,
+    remapper: &RestorePathRemapper,
+) -> Result<RestorePreview, String> {
+    let files: Vec<_> = load_backup_manifest(Path::new(&backup.backup_path))?
+        .map(|manifest| manifest.files)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| entry.link_target.is_none())
+        .collect();
+    // Legacy backups without a manifest only know their stored size.
+    let restore_bytes = if files.is_empty() {
+        backup.backup_size.max(0) as u64
+    } else {
+        files.iter().map(|entry| entry.size).sum()
+    };
+    let drive = restore_preview::busiest_drive(&files, remapper);
+    let (throughput_mbps, throughput_measured) = match &drive {
+        Some((letter, path)) => load_restore_mbps(letter)
+            .map_or_else(|| (default_restore_mbps(path), false), |mbps| (mbps, true)),
+        None => (default_restore_mbps(Path::new("")), false),
+    };
+    Ok(RestorePreview {
+        backup_id: backup.id.clone(),
+        file_count: files.len(),
+        restore_bytes,
+        target_drive: drive.map(|(letter, _)| letter),
+        throughput_mbps,
+        throughput_measured,
+        estimated_seconds: restore_preview::estimate_seconds(restore_bytes, throughput_mbps),
+        newer_files: restore_preview::newer_live_files(&files, remapper),
     })
-    .map_err(|e| e.to_string())?;
+}
+
+/// Expected duration of a restore and the live saves it would replace with older copies.
+#[tauri::command]
+pub fn preview_restore(
+    backup_id: String,
+    path_mappings: Option<HashMap<String, String>>,
+) -> Result<RestorePreview, String> {
+    let backup = load_backup_record(&backup_id)?;
+    let game_dir = get_game_dir(&backup.game_id);
+    let remapper =
+        RestorePathRemapper::current(&path_mappings.unwrap_or_default(), game_dir.as_deref());
+    build_restore_preview(&backup, &remapper)
+}
+
+/// Refuses to replace saves that are newer than the backup unless `overwrite_newer` is set;
+/// the frontend sets it after showing `preview_restore` to the user.
+#[tauri::command]
+pub async fn restore_backup(
+    app: tauri::AppHandle,
+    backup_id: String,
+    path_mappings: Option<HashMap<String, String>>,
+    overwrite_newer: Option<bool>,
+) -> Result<(), BackupError> {
+    let backup = load_backup_record(&backup_id)?;
+
+    let game_dir = get_game_dir(&backup.game_id);
+    let remapper =
+        RestorePathRemapper::current(&path_mappings.unwrap_or_default(), game_dir.as_deref());
+    let unresolved: Vec<String> = plan_restore_for_backup(&backup, &remapper)?
+        .into_iter()
+        .filter(|root| root.needs_mapping)
+        .map(|root| root.original_root)
+        .collect();
+    if !unresolved.is_empty() {
+        return Err(format!(
+            "Требуется сопоставление путей для восстановления: {}",
+            unresolved.join(", ")
+        )
+        .into());
+    }
+    let preview = build_restore_preview(&backup, &remapper)?;
+    if !preview.newer_files.is_empty() && !overwrite_newer.unwrap_or(false) {
+        return Err(format!(
+            "Текущие сохранения новее бэкапа и будут перезаписаны ({} файлов). Подтвердите восстановление",
+            preview.newer_files.len()
+        )
+        .into());
+    }
 
     let backup_path = backup.backup_path.clone();
     let game_id = backup.game_id.clone();
     let threads = get_disk_threads(Path::new(&backup_path));
     tauri::async_runtime::spawn_blocking(move || {
-        let engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+        let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+        let watch = watchdog::register(&game_id, "restore", Some(app.clone()));
+        engine.set_monitor(Some(watch.monitor()));
         let progress: Arc<dyn Fn(BackupProgress) + Send + Sync> = {
             let app = app.clone();
             let game_id = game_id.clone();
@@ -1236,11 +2844,18 @@ pub async fn restore_backup(app: tauri::AppHandle, backup_id: String) -> Result<
                 );
             }) as Arc<dyn Fn(BackupProgress) + Send + Sync>
         };
-        let result = engine.restore_backup_with_threads_and_progress(
+        let started = std::time::Instant::now();
+        let result = engine.restore_backup_with_remap(
             Path::new(&backup_path),
             threads,
+            &remapper,
             Some(progress),
         );
+        if let (Ok(()), Some(letter)) = (&result, &preview.target_drive) {
+            save_restore_mbps(letter, preview.restore_bytes, started.elapsed());
+        }
+        engine.set_monitor(None);
+        drop(watch);
         let _ = app.emit(
             "restore:progress",
             BackupProgressEvent {
@@ -1257,27 +2872,308 @@ pub async fn restore_backup(app: tauri::AppHandle, backup_id: String) -> Result<
     .map_err(|e| e.to_string())?
 }
 
+/// Rebuilds a missing or corrupted manifest of a directory backup so it can be restored
+/// again. Original paths are inferred from the game's current save roots.
 #[tauri::command]
-pub fn delete_backup(backup_id: String) -> Result<(), String> {
-    let backup: Backup = with_db(|conn| {
+pub fn repair_backup(backup_id: String) -> Result<ManifestRepair, String> {
+    let backup = load_backup_record(&backup_id)?;
+    let game_name = load_game_exit_state(&backup.game_id)?.name;
+    let save_path_override = load_save_path_override(&backup.game_id);
+    let extra_roots = load_extra_save_roots(&backup.game_id);
+    let game_dir = get_game_dir(&backup.game_id);
+    let manifest_override = load_manifest_override(&backup.game_id);
+    let manifest_key = load_confirmed_manifest_key(&backup.game_id);
+    let lookup = SaveLookup {
+        override_path: save_path_override.as_deref(),
+        extra_roots: &extra_roots,
+        game_dir: game_dir.as_deref(),
+        manifest_override: manifest_override.as_ref(),
+        manifest_key: manifest_key.as_deref(),
+    };
+
+    let mut engine = BACKUP_ENGINE.lock().map_err(|e| e.to_string())?;
+    if let Err(e) = engine.load_manifest() {
+        eprintln!("Manifest unavailable while repairing backup: {}", e);
+    }
+    let roots: HashMap<String, PathBuf> = engine
+        .discover_game_save_roots(&game_name, lookup)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|root| (root.label, root.path))
+        .collect();
+    engine.repair_directory_manifest(
+        Path::new(&backup.backup_path),
+        &roots,
+        &PathTokens::for_game(game_dir.as_deref()),
+    )
+}
+
+/// Answers a `backup:stalled` warning: cancel the whole operation or give up on the file
+/// that is stuck and carry on with the rest.
+#[tauri::command]
+pub fn resolve_stalled_operation(operation_id: String, action: StallAction) -> Result<(), String> {
+    watchdog::request(&operation_id, action)
+}
+
+/// Compares the manifests (and stored checksums) of two backups without extracting them.
+#[tauri::command]
+pub fn diff_backups(backup_a: String, backup_b: String) -> Result<BackupDiff, String> {
+    let a = load_backup_record(&backup_a)?;
+    let b = load_backup_record(&backup_b)?;
+    diff::diff_backup_paths(
+        &backup_a,
+        Path::new(&a.backup_path),
+        &backup_b,
+        Path::new(&b.backup_path),
+    )
+}
+
+fn load_install_archive(game_id: &str) -> Result<Option<InstallArchive>, String> {
+    with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, game_id, backup_path, backup_size, created_at, is_auto, notes
-             FROM backups WHERE id = ?1",
+            "SELECT id, game_id, archive_path, original_dir, exe_relative, file_count,
+                    original_bytes, archive_bytes, created_at
+             FROM install_archives WHERE game_id = ?1",
         )?;
+        let archive = stmt
+            .query_row(params![game_id], |row| {
+                Ok(InstallArchive {
+                    id: row.get(0)?,
+                    game_id: row.get(1)?,
+                    archive_path: row.get(2)?,
+                    original_dir: row.get(3)?,
+                    exe_relative: row.get(4)?,
+                    file_count: row.get::<_, i64>(5)?.max(0) as usize,
+                    original_bytes: row.get::<_, i64>(6)?.max(0) as u64,
+                    archive_bytes: row.get::<_, i64>(7)?.max(0) as u64,
+                    created_at: row.get(8)?,
+                })
+            })
+            .ok();
+        Ok(archive)
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn emit_install_progress(
+    app: &tauri::AppHandle,
+    game_id: &str,
+    stage: &str,
+    done: usize,
+    total: usize,
+) {
+    let message = match stage {
+        "archive" => "Упаковка файлов игры",
+        "verify" => "Проверка архива",
+        "delete" => "Удаление исходной папки",
+        "extract" => "Распаковка файлов игры",
+        _ => "Готово",
+    };
+    let _ = app.emit(
+        "install-archive:progress",
+        BackupProgressEvent {
+            game_id: game_id.to_string(),
+            stage: stage.to_string(),
+            message: message.to_string(),
+            done,
+            total,
+        },
+    );
+}
+
+#[tauri::command]
+pub fn get_install_archive(game_id: String) -> Result<Option<InstallArchive>, String> {
+    load_install_archive(&game_id)
+}
+
+/// Packs the game's install folder (by default the executable's folder) into
+/// `<backup dir>/installs`, verifies the archive against the originals and deletes them.
+#[tauri::command]
+pub async fn archive_game_install(
+    app: tauri::AppHandle,
+    game_id: String,
+    install_dir: Option<String>,
+) -> Result<InstallArchive, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if load_install_archive(&game_id)?.is_some() {
+            return Err("Установка игры уже находится в архиве".to_string());
+        }
+        if games_service::get_running_instances(&GlobalDb, game_id.clone())? > 0 {
+            return Err("Игра запущена: закройте её перед архивированием".to_string());
+        }
+        let exe_path = get_game_exe_path(&game_id).ok_or("Путь к игре не найден")?;
+        let install_dir = install_dir
+            .filter(|dir| !dir.trim().is_empty())
+            .map(PathBuf::from)
+            .or_else(|| get_game_dir(&game_id))
+            .ok_or("Папка установки не найдена")?;
+        if !install_dir.is_dir() {
+            return Err(format!(
+                "Папка установки не найдена: {}",
+                install_dir.display()
+            ));
+        }
+        let other_exe_paths: Vec<String> = with_db(|conn| {
+            let mut stmt = conn.prepare("SELECT exe_path FROM games WHERE id != ?1")?;
+            let paths = stmt
+                .query_map(params![game_id], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            Ok(paths)
+        })
+        .map_err(|e| e.to_string())?;
+        install_archive::check_install_dir(&install_dir, &other_exe_paths)?;
+        let exe_relative = Path::new(&exe_path)
+            .strip_prefix(&install_dir)
+            .map_err(|_| "Исполняемый файл игры должен находиться внутри папки установки")?
+            .to_string_lossy()
+            .to_string();
+
+        let game_name = load_game_exit_state(&game_id)?.name;
+        let year = get_game_year(&game_id);
+        let archive_path = get_backup_directory().join("installs").join(format!(
+            "{}.zip",
+            game_backup_folder_name(&game_name, year.as_deref())
+        ));
+        if archive_path.exists() {
+            return Err(format!("Архив уже существует: {}", archive_path.display()));
+        }
+        disk_space::ensure_free_space([(archive_path.clone(), backup_disk_size(&install_dir))])
+            .map_err(|shortage| shortage.to_string())?;
 
-        stmt.query_row(params![backup_id], |row| {
-            Ok(Backup {
-                id: row.get(0)?,
-                game_id: row.get(1)?,
-                backup_path: row.get(2)?,
-                backup_size: row.get(3)?,
-                created_at: row.get(4)?,
-                is_auto: row.get::<_, i32>(5)? == 1,
-                notes: row.get(6)?,
+        let progress = |stage: &'static str, done: usize, total: usize| {
+            emit_install_progress(&app, &game_id, stage, done, total)
+        };
+        let packed = install_archive::archive_directory(&install_dir, &archive_path, &progress)
+            .and_then(|summary| {
+                install_archive::verify_archive(&install_dir, &archive_path, summary, &progress)
+                    .map(|_| summary)
             })
+            .and_then(|summary| {
+                // Anything that appeared while packing would be deleted without a copy.
+                let on_disk = install_archive::count_files_on_disk(&install_dir)?;
+                if on_disk != summary.file_count {
+                    return Err(format!(
+                        "В папке установки {} файлов, а в архиве {}: папка не удалена",
+                        on_disk, summary.file_count
+                    ));
+                }
+                Ok(summary)
+            });
+        let summary = match packed {
+            Ok(summary) => summary,
+            Err(e) => {
+                let _ = fs::remove_file(&archive_path);
+                return Err(e);
+            }
+        };
+
+        let archive = InstallArchive {
+            id: Uuid::new_v4().to_string(),
+            game_id: game_id.clone(),
+            archive_path: archive_path.to_string_lossy().to_string(),
+            original_dir: install_dir.to_string_lossy().to_string(),
+            exe_relative,
+            file_count: summary.file_count,
+            original_bytes: summary.original_bytes,
+            archive_bytes: fs::metadata(&archive_path).map(|m| m.len()).unwrap_or(0),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO install_archives (id, game_id, archive_path, original_dir, exe_relative,
+                                               file_count, original_bytes, archive_bytes, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    archive.id,
+                    archive.game_id,
+                    archive.archive_path,
+                    archive.original_dir,
+                    archive.exe_relative,
+                    archive.file_count as i64,
+                    archive.original_bytes as i64,
+                    archive.archive_bytes as i64,
+                    archive.created_at
+                ],
+            )?;
+            Ok(())
         })
+        .map_err(|e| e.to_string())?;
+
+        progress("delete", 0, 0);
+        fs::remove_dir_all(&install_dir).map_err(|e| {
+            format!(
+                "Архив создан, но исходную папку удалить не удалось: {}",
+                e
+            )
+        })?;
+        progress("done", 0, 0);
+        Ok(archive)
     })
-    .map_err(|e| e.to_string())?;
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Unpacks an archived install back to its original folder (or `target_dir`), points the
+/// game at the restored executable and deletes the archive. Returns the new executable path.
+#[tauri::command]
+pub async fn unarchive_game_install(
+    app: tauri::AppHandle,
+    game_id: String,
+    target_dir: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let archive =
+            load_install_archive(&game_id)?.ok_or("Установка игры не находится в архиве")?;
+        let target = PathBuf::from(
+            target_dir
+                .filter(|dir| !dir.trim().is_empty())
+                .unwrap_or_else(|| archive.original_dir.clone()),
+        );
+        let archive_path = Path::new(&archive.archive_path);
+        if !archive_path.is_file() {
+            return Err(format!("Файл архива не найден: {}", archive_path.display()));
+        }
+        let archive_size = fs::metadata(archive_path).map(|m| m.len()).unwrap_or(0);
+        disk_space::ensure_free_space([(target.clone(), archive.original_bytes.max(archive_size))])
+            .map_err(|shortage| shortage.to_string())?;
+
+        let progress = |stage: &'static str, done: usize, total: usize| {
+            emit_install_progress(&app, &game_id, stage, done, total)
+        };
+        install_archive::extract_archive(archive_path, &target, &progress)?;
+
+        let exe_path = target
+            .join(verify::rel_path(&archive.exe_relative.replace('\\', "/")))
+            .to_string_lossy()
+            .to_string();
+        with_db(|conn| {
+            conn.execute(
+                "UPDATE games SET exe_path = ?1 WHERE id = ?2",
+                params![exe_path, game_id],
+            )?;
+            conn.execute(
+                "DELETE FROM install_archives WHERE id = ?1",
+                params![archive.id],
+            )?;
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+        if let Err(e) = fs::remove_file(archive_path) {
+            eprintln!(
+                "Failed to delete install archive {}: {}",
+                archive.archive_path, e
+            );
+        }
+        progress("done", 0, 0);
+        Ok(exe_path)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn delete_backup(backup_id: String) -> Result<(), String> {
+    let backup = load_backup_record(&backup_id)?;
 
     // Delete backup path
     let backup_path = Path::new(&backup.backup_path);
@@ -1346,24 +3242,12 @@ pub fn check_backup_needed(game_id: String, game_name: String) -> Result<bool, S
 
     // Get last backup
     let last_backup: Option<Backup> = with_db(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, game_id, backup_path, backup_size, created_at, is_auto, notes
-             FROM backups WHERE game_id = ?1 ORDER BY created_at DESC LIMIT 1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM backups WHERE game_id = ?1 ORDER BY created_at DESC LIMIT 1",
+            BACKUP_COLUMNS
+        ))?;
 
-        let backup = stmt
-            .query_row(params![game_id], |row| {
-                Ok(Backup {
-                    id: row.get(0)?,
-                    game_id: row.get(1)?,
-                    backup_path: row.get(2)?,
-                    backup_size: row.get(3)?,
-                    created_at: row.get(4)?,
-                    is_auto: row.get::<_, i32>(5)? == 1,
-                    notes: row.get(6)?,
-                })
-            })
-            .ok();
+        let backup = stmt.query_row(params![game_id], backup_from_row).ok();
         Ok(backup)
     })
     .ok()
@@ -1400,6 +3284,26 @@ pub fn check_backup_needed(game_id: String, game_name: String) -> Result<bool, S
 
 #[tauri::command]
 pub fn check_restore_needed(game_id: String, game_name: String) -> Result<RestoreCheck, String> {
+    let restore_on_launch = with_db(|conn| {
+        conn.query_row(
+            "SELECT restore_on_launch FROM games WHERE id = ?1",
+            params![game_id],
+            |row| row.get::<_, Option<i32>>(0),
+        )
+    })
+    .ok()
+    .flatten()
+    .unwrap_or(1)
+        == 1;
+    if !restore_on_launch {
+        return Ok(RestoreCheck {
+            should_restore: false,
+            backup_id: None,
+            current_size: 0,
+            backup_size: 0,
+        });
+    }
+
     let save_info = find_game_saves(game_name, Some(game_id.clone()))?;
 
     if save_info.is_none() {
@@ -1414,24 +3318,12 @@ pub fn check_restore_needed(game_id: String, game_name: String) -> Result<Restor
     let save_info = save_info.unwrap();
 
     let last_backup: Option<Backup> = with_db(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, game_id, backup_path, backup_size, created_at, is_auto, notes
-             FROM backups WHERE game_id = ?1 ORDER BY created_at DESC LIMIT 1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM backups WHERE game_id = ?1 ORDER BY created_at DESC LIMIT 1",
+            BACKUP_COLUMNS
+        ))?;
 
-        let backup = stmt
-            .query_row(params![game_id], |row| {
-                Ok(Backup {
-                    id: row.get(0)?,
-                    game_id: row.get(1)?,
-                    backup_path: row.get(2)?,
-                    backup_size: row.get(3)?,
-                    created_at: row.get(4)?,
-                    is_auto: row.get::<_, i32>(5)? == 1,
-                    notes: row.get(6)?,
-                })
-            })
-            .ok();
+        let backup = stmt.query_row(params![game_id], backup_from_row).ok();
         Ok(backup)
     })
     .ok()
@@ -1466,6 +3358,7 @@ struct SavePathMissingEvent {
 struct GameExitState {
     name: String,
     backup_enabled: bool,
+    backup_on_exit: bool,
     save_path: Option<String>,
     save_path_checked: bool,
 }
@@ -1473,16 +3366,19 @@ struct GameExitState {
 fn load_game_exit_state(game_id: &str) -> Result<GameExitState, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT name, backup_enabled, save_path, save_path_checked FROM games WHERE id = ?1",
+            "SELECT name, backup_enabled, save_path, save_path_checked, backup_on_exit
+             FROM games WHERE id = ?1",
         )?;
         let result = stmt.query_row(params![game_id], |row| {
             let name: String = row.get(0)?;
             let enabled: i32 = row.get(1)?;
             let save_path: Option<String> = row.get(2)?;
             let checked: Option<i32> = row.get(3).ok();
+            let on_exit: Option<i32> = row.get(4)?;
             Ok(GameExitState {
                 name,
                 backup_enabled: enabled == 1,
+                backup_on_exit: on_exit.unwrap_or(1) == 1,
                 save_path,
                 save_path_checked: checked.unwrap_or(0) == 1,
             })
@@ -1510,13 +3406,66 @@ fn try_auto_discover_save_path(game_id: &str, game_name: &str) -> Result<bool, S
     result.map(|info| info.is_some())
 }
 
-pub fn auto_backup_on_exit(game_id: &str, app: Option<tauri::AppHandle>) -> Result<(), String> {
+/// Returns the id of the backup taken, if one was needed.
+/// `profile` is the launch profile the session was started with; it labels the backup notes
+/// and fills the `{profile}` token of the name template.
+/// Payload of `backup:corruption-suspected`. `backup_id` is the newest backup holding a
+/// non-empty copy of every suspect file, offered for a one-click restore.
+#[derive(Debug, Serialize, Clone)]
+struct SaveCorruptionEvent {
+    game_id: String,
+    game_name: String,
+    files: Vec<SuspectFile>,
+    backup_id: Option<String>,
+    backup_created_at: Option<String>,
+}
+
+/// How many recent backups are searched for intact copies of corrupted saves.
+const INTACT_BACKUP_SEARCH: usize = 10;
+
+/// Validates the game's current saves; `None` when nothing looks wrong.
+fn check_save_integrity(
+    game_id: &str,
+    game_name: &str,
+) -> Result<Option<SaveCorruptionEvent>, String> {
+    let Some(info) = find_game_saves(game_name.to_string(), Some(game_id.to_string()))? else {
+        return Ok(None);
+    };
+    let backups = get_game_backups(game_id.to_string())?;
+    let manifest_files = |backup: &Backup| {
+        load_backup_manifest(Path::new(&backup.backup_path))
+            .ok()
+            .flatten()
+            .map(|manifest| manifest.files)
+    };
+    let previous = backups.first().and_then(manifest_files).unwrap_or_default();
+    let files = find_suspect_saves(&info.files, &previous, std::time::SystemTime::now());
+    if files.is_empty() {
+        return Ok(None);
+    }
+    let intact = backups.iter().take(INTACT_BACKUP_SEARCH).find(|backup| {
+        manifest_files(backup).is_some_and(|manifest| has_intact_copies(&manifest, &files))
+    });
+    Ok(Some(SaveCorruptionEvent {
+        game_id: game_id.to_string(),
+        game_name: game_name.to_string(),
+        files,
+        backup_id: intact.map(|backup| backup.id.clone()),
+        backup_created_at: intact.map(|backup| backup.created_at.clone()),
+    }))
+}
+
+pub fn auto_backup_on_exit(
+    game_id: &str,
+    app: Option<tauri::AppHandle>,
+    profile: Option<&str>,
+) -> Result<Option<String>, String> {
     let state = load_game_exit_state(game_id)?;
     if state.save_path.is_none() && !state.save_path_checked {
         match try_auto_discover_save_path(game_id, &state.name) {
             Ok(found) => {
                 if !found {
-                    if let Some(app) = app {
+                    if let Some(app) = &app {
                         let _ = app.emit(
                             "game:save-path-missing",
                             SavePathMissingEvent {
@@ -1533,6 +3482,27 @@ pub fn auto_backup_on_exit(game_id: &str, app: Option<tauri::AppHandle>) -> Resu
         }
     }
 
+    if get_setting_bool("backup_integrity_check", true) {
+        match check_save_integrity(game_id, &state.name) {
+            Ok(Some(alert)) => {
+                // Backing up now would store the damaged saves and could rotate the last
+                // good copy out, so the exit backup waits for the user.
+                let message = format!(
+                    "Сохранения выглядят повреждёнными ({} файлов), автобэкап пропущен",
+                    alert.files.len()
+                );
+                record_backup_event(game_id, true, 1, &Err(message.clone()));
+                if let Some(app) = &app {
+                    let _ = app.emit("backup:corruption-suspected", alert);
+                }
+                eprintln!("Auto-backup skipped for {}: {}", game_id, message);
+                return Ok(None);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Save integrity check failed for {}: {}", game_id, e),
+        }
+    }
+
     let auto_backup: String = with_db(|conn| {
         let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = 'auto_backup'")?;
         let result: String = stmt
@@ -1543,25 +3513,187 @@ pub fn auto_backup_on_exit(game_id: &str, app: Option<tauri::AppHandle>) -> Resu
     .unwrap_or_else(|_| "true".to_string());
 
     if auto_backup != "true" {
-        return Ok(());
+        return Ok(None);
     }
 
-    if !state.backup_enabled {
-        return Ok(());
+    if !state.backup_enabled || !state.backup_on_exit {
+        return Ok(None);
     }
 
     if !check_backup_needed(game_id.to_string(), state.name.clone())? {
-        return Ok(());
+        return Ok(None);
     }
 
-    create_backup_inner(
-        None,
-        game_id.to_string(),
-        state.name,
-        true,
-        Some("Auto backup after exit".to_string()),
-    )?;
-    Ok(())
+    // Run quota cleanup first; if the newest backups still do not fit, block the auto
+    // backup instead of silently filling the drive.
+    if let Some(violation) = enforce_backup_quotas(game_id)? {
+        let message = quota_violation_message(&violation);
+        record_backup_event(game_id, true, 1, &Err(message.clone()));
+        emit_quota_exceeded(&app, game_id, &state.name, &violation, true);
+        eprintln!("Auto-backup blocked for {}: {}", game_id, message);
+        return Ok(None);
+    }
+
+    retry_with_backoff(
+        &AUTO_BACKUP_RETRY_DELAYS,
+        std::thread::sleep,
+        is_transient_backup_error,
+        |attempt| {
+            let result = create_backup_inner(
+                None,
+                game_id.to_string(),
+                state.name.clone(),
+                true,
+                Some(match profile {
+                    Some(profile) => format!("Auto backup after exit ({})", profile),
+                    None => "Auto backup after exit".to_string(),
+                }),
+                None,
+                profile,
+            )
+            .map_err(|e| e.to_string());
+            record_backup_event(game_id, true, attempt, &result);
+            if let Err(e) = &result {
+                eprintln!(
+                    "Auto-backup attempt {} failed for {}: {}",
+                    attempt, game_id, e
+                );
+            }
+            result
+        },
+    )
+    .map(|backup| Some(backup.id))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NightlyBackupFailure {
+    pub game_name: String,
+    pub error: String,
+}
+
+/// Summary of one nightly run, emitted as `backup:nightly-digest`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NightlyBackupDigest {
+    pub backed_up: Vec<String>,
+    pub failed: Vec<NightlyBackupFailure>,
+}
+
+const NIGHTLY_DEFAULT_TIME: &str = "03:00";
+const NIGHTLY_LAST_RUN_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+static NIGHTLY_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// True once the scheduled time of day has passed and the last run was on an earlier day.
+/// A machine that was asleep at the scheduled time catches up on the next idle check.
+fn nightly_backup_due(
+    now: NaiveDateTime,
+    scheduled: NaiveTime,
+    last_run: Option<NaiveDateTime>,
+) -> bool {
+    now.time() >= scheduled && last_run.is_none_or(|last| last.date() < now.date())
+}
+
+/// Called by the tracker while no game is running. Starts the nightly backup in the
+/// background when `backup_nightly_enabled` is set and the configured time has come.
+pub fn maybe_start_nightly_backup(app: &tauri::AppHandle) {
+    if !get_setting_bool("backup_nightly_enabled", false) {
+        return;
+    }
+    let scheduled = get_setting_value("backup_nightly_time")
+        .and_then(|value| NaiveTime::parse_from_str(value.trim(), "%H:%M").ok())
+        .unwrap_or_else(|| {
+            NaiveTime::parse_from_str(NIGHTLY_DEFAULT_TIME, "%H:%M").unwrap_or_default()
+        });
+    let last_run = get_setting_value("backup_nightly_last_run")
+        .and_then(|value| NaiveDateTime::parse_from_str(&value, NIGHTLY_LAST_RUN_FORMAT).ok());
+    let now = Local::now().naive_local();
+    if !nightly_backup_due(now, scheduled, last_run) {
+        return;
+    }
+    if NIGHTLY_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let _ = with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('backup_nightly_last_run', ?1)",
+            params![now.format(NIGHTLY_LAST_RUN_FORMAT).to_string()],
+        )?;
+        Ok(())
+    });
+
+    // Saves touched since the previous run (or within the last day on the first run).
+    let since = last_run.unwrap_or(now - chrono::Duration::days(1));
+    let since = Local
+        .from_local_datetime(&since)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let digest = run_nightly_backup(&app, since);
+        if !digest.backed_up.is_empty() || !digest.failed.is_empty() {
+            let _ = app.emit("backup:nightly-digest", digest);
+        }
+        NIGHTLY_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+fn latest_save_mtime(files: &[String]) -> Option<DateTime<Utc>> {
+    files
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .map(DateTime::<Utc>::from)
+        .max()
+}
+
+fn run_nightly_backup(app: &tauri::AppHandle, since: DateTime<Utc>) -> NightlyBackupDigest {
+    let games: Vec<(String, String)> = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT id, name FROM games WHERE backup_enabled = 1")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|row| row.ok())
+            .collect();
+        Ok(rows)
+    })
+    .unwrap_or_default();
+
+    let mut digest = NightlyBackupDigest::default();
+    for (game_id, game_name) in games {
+        let changed = match find_game_saves(game_name.clone(), Some(game_id.clone())) {
+            Ok(Some(info)) => latest_save_mtime(&info.files).is_some_and(|mtime| mtime > since),
+            Ok(None) => false,
+            Err(e) => {
+                eprintln!(
+                    "Nightly backup: save discovery failed for {}: {}",
+                    game_name, e
+                );
+                false
+            }
+        };
+        if !changed || !check_backup_needed(game_id.clone(), game_name.clone()).unwrap_or(false) {
+            continue;
+        }
+
+        let result = create_backup_inner(
+            Some(app.clone()),
+            game_id.clone(),
+            game_name.clone(),
+            true,
+            Some("Ночная резервная копия".to_string()),
+            None,
+            None,
+        )
+        .map_err(|e| e.to_string());
+        record_backup_event(&game_id, true, 1, &result);
+        match result {
+            Ok(_) => digest.backed_up.push(game_name),
+            Err(error) => digest
+                .failed
+                .push(NightlyBackupFailure { game_name, error }),
+        }
+    }
+    digest
 }
 
 #[tauri::command]
@@ -1585,6 +3717,11 @@ pub fn get_backup_settings() -> Result<serde_json::Value, String> {
 #[tauri::command]
 pub fn update_backup_settings(settings: serde_json::Value) -> Result<(), String> {
     let obj = settings.as_object().ok_or("Settings must be an object")?;
+    for (key, value) in obj {
+        if let Some(val_str) = value.as_str() {
+            crate::settings::validate_setting(key, val_str)?;
+        }
+    }
 
     with_db(|conn| {
         for (key, value) in obj {
@@ -1599,3 +3736,278 @@ pub fn update_backup_settings(settings: serde_json::Value) -> Result<(), String>
     })
     .map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn backup_folders_are_tagged_with_the_game_name() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let fifa_colon = game_backup_folder_name("FIFA: 22", Some("2021"));
+        let fifa_plain = game_backup_folder_name("FIFA 22", Some("2021"));
+        assert_eq!(fifa_colon, "FIFA 22-2021 [65ade7f5]");
+        assert_eq!(fifa_plain, "FIFA 22-2021 [7b111539]");
+        for folder in [fifa_colon.as_str(), fifa_plain.as_str(), "FIFA 22-2021"] {
+            fs::create_dir_all(root.path().join(folder)).expect("create folder");
+        }
+
+        let found = |include_legacy| {
+            let mut dirs: Vec<String> =
+                find_backup_game_dirs(root.path(), "FIFA 22", Some("2021"), include_legacy)
+                    .iter()
+                    .filter_map(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+                    .collect();
+            dirs.sort();
+            dirs
+        };
+        assert_eq!(found(false), vec![fifa_plain.clone()]);
+        assert_eq!(
+            found(true),
+            vec!["FIFA 22-2021".to_string(), fifa_plain.clone()]
+        );
+    }
+
+    #[test]
+    fn captured_backup_metadata_round_trips_through_the_database_and_manifest() {
+        let _lock = crate::database::TEST_DB_MUTEX.lock().unwrap();
+        let conn = rusqlite::Connection::open_in_memory().expect("open db");
+        crate::database::init_schema(&conn).expect("init schema");
+        conn.execute_batch(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, total_playtime)
+             VALUES ('g1', 'Outer Wilds', 'C:/Games/OuterWilds.exe', 'OuterWilds.exe',
+                     '2026-01-01T00:00:00Z', 5400);
+             INSERT INTO play_sessions (game_id, started_at, ended_at, duration_seconds)
+             VALUES ('g1', '2026-05-01T18:00:00Z', '2026-05-01T19:00:00Z', 3600),
+                    ('g1', '2026-05-02T18:00:00Z', '2026-05-02T18:30:00Z', 1800);",
+        )
+        .expect("seed game");
+        let _db = crate::database::set_test_db(conn);
+
+        let metadata = capture_backup_metadata("g1", Some("  before the quantum moon ".into()));
+        assert_eq!(
+            metadata,
+            BackupMetadata {
+                playtime_seconds: 5400,
+                last_session_seconds: Some(1800),
+                label: Some("before the quantum moon".to_string()),
+            }
+        );
+        assert_eq!(capture_backup_metadata("g1", Some("  ".into())).label, None);
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let save_dir = dir.path().join("saves");
+        fs::create_dir_all(&save_dir).expect("create save dir");
+        fs::write(save_dir.join("slot1.sav"), b"loop 12").expect("write save");
+        let save_path = save_dir.to_string_lossy().to_string();
+        let mut engine = BackupEngine::new();
+        engine.set_metadata(Some(metadata.clone()));
+
+        for (name, options) in [
+            ("dir", BackupOptions::directory()),
+            ("archive.sqoba.zip", BackupOptions::zip(6)),
+        ] {
+            let backup_path = dir.path().join(name);
+            let size = engine
+                .backup_game_with_options_and_progress(
+                    "Outer Wilds",
+                    &backup_path,
+                    1,
+                    options,
+                    SaveLookup {
+                        override_path: Some(&save_path),
+                        ..SaveLookup::default()
+                    },
+                    None,
+                )
+                .expect("backup");
+            let backup = Backup {
+                id: name.to_string(),
+                game_id: "g1".to_string(),
+                backup_path: backup_path.to_string_lossy().to_string(),
+                backup_size: size as i64,
+                created_at: Utc::now().to_rfc3339(),
+                is_auto: false,
+                notes: Some("Ship log updated".to_string()),
+                metadata: Some(metadata.clone()),
+            };
+            record_backup(&backup, 120, None).expect("record backup");
+
+            let stored = get_game_backups("g1".to_string())
+                .expect("game backups")
+                .into_iter()
+                .find(|stored| stored.id == name)
+                .expect("stored backup");
+            assert_eq!(stored.metadata.as_ref(), Some(&metadata));
+            assert_eq!(stored.notes, backup.notes);
+            assert_eq!(stored.backup_path, backup.backup_path);
+            assert_eq!(stored.backup_size, backup.backup_size);
+            assert_eq!(stored.created_at, backup.created_at);
+            assert!(!stored.is_auto);
+
+            let manifest = load_backup_manifest(&backup_path)
+                .expect("read manifest")
+                .expect("manifest present");
+            assert_eq!(manifest.metadata, Some(metadata.clone()));
+        }
+    }
+
+    #[test]
+    fn bulk_enable_skips_games_without_saves_unless_forced() {
+        let results = || {
+            vec![
+                (
+                    "found".to_string(),
+                    Ok((
+                        "Arcadia".to_string(),
+                        Some(PathBuf::from("C:/Saves/Arcadia")),
+                    )),
+                ),
+                ("empty".to_string(), Ok(("Nowhere".to_string(), None))),
+                ("broken".to_string(), Err("Игра не найдена".to_string())),
+            ]
+        };
+
+        let (update, missing) = plan_bulk_enable(results(), false);
+        assert_eq!(update, vec!["found".to_string()]);
+        assert_eq!(
+            missing,
+            vec![
+                MissingSaveGame {
+                    game_id: "empty".to_string(),
+                    name: Some("Nowhere".to_string()),
+                    error: None,
+                },
+                MissingSaveGame {
+                    game_id: "broken".to_string(),
+                    name: None,
+                    error: Some("Игра не найдена".to_string()),
+                },
+            ]
+        );
+
+        let (update, missing) = plan_bulk_enable(results(), true);
+        assert_eq!(update, vec!["found", "empty", "broken"]);
+        assert_eq!(missing.len(), 2);
+    }
+
+    #[test]
+    fn retry_with_backoff_sleeps_between_attempts_and_gives_up() {
+        let delays = [Duration::from_secs(1), Duration::from_secs(5)];
+        let slept = RefCell::new(Vec::new());
+
+        let result = retry_with_backoff(
+            &delays,
+            |d| slept.borrow_mut().push(d),
+            |_| true,
+            |attempt| {
+                if attempt == 2 {
+                    Ok(attempt)
+                } else {
+                    Err("busy".to_string())
+                }
+            },
+        );
+        assert_eq!(result, Ok(2));
+        assert_eq!(*slept.borrow(), vec![Duration::from_secs(1)]);
+
+        slept.borrow_mut().clear();
+        let mut attempts = 0;
+        let result: Result<(), String> = retry_with_backoff(
+            &delays,
+            |d| slept.borrow_mut().push(d),
+            |_| true,
+            |attempt| {
+                attempts = attempt;
+                Err(format!("fail {}", attempt))
+            },
+        );
+        assert_eq!(result, Err("fail 3".to_string()));
+        assert_eq!(attempts, 3);
+        assert_eq!(*slept.borrow(), delays.to_vec());
+    }
+
+    #[test]
+    fn only_transient_backup_errors_are_retried() {
+        let locked = std::io::Error::from(std::io::ErrorKind::PermissionDenied).to_string();
+        assert!(is_transient_backup_error(&locked));
+        assert!(is_transient_backup_error(
+            &std::io::Error::from(std::io::ErrorKind::WouldBlock).to_string()
+        ));
+        assert!(!is_transient_backup_error("Сохранения не найдены"));
+        assert!(!is_transient_backup_error(
+            &std::io::Error::from(std::io::ErrorKind::NotFound).to_string()
+        ));
+
+        let slept = RefCell::new(Vec::new());
+        let mut attempts = 0;
+        let result: Result<(), String> = retry_with_backoff(
+            &[Duration::from_secs(1), Duration::from_secs(5)],
+            |d| slept.borrow_mut().push(d),
+            is_transient_backup_error,
+            |attempt| {
+                attempts = attempt;
+                Err("Сохранения не найдены".to_string())
+            },
+        );
+        assert_eq!(result, Err("Сохранения не найдены".to_string()));
+        assert_eq!(attempts, 1);
+        assert!(slept.borrow().is_empty());
+    }
+
+    #[test]
+    fn wait_for_exit_polls_until_game_closes_or_times_out() {
+        let interval = Duration::from_secs(2);
+        let slept = RefCell::new(Duration::ZERO);
+        let mut checks = 0;
+        let result = wait_for_exit(
+            interval,
+            Duration::from_secs(60),
+            |d| *slept.borrow_mut() += d,
+            || {
+                checks += 1;
+                Ok(checks < 4)
+            },
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(*slept.borrow(), Duration::from_secs(6));
+
+        let result = wait_for_exit(interval, Duration::from_secs(4), |_| {}, || Ok(true));
+        assert!(result.is_err());
+        assert_eq!(
+            RunningGamePolicy::from_setting("wait"),
+            RunningGamePolicy::Wait
+        );
+        assert_eq!(RunningGamePolicy::from_setting(""), RunningGamePolicy::Warn);
+    }
+
+    #[test]
+    fn nightly_backup_runs_once_per_day_after_scheduled_time() {
+        let at = |value: &str| {
+            NaiveDateTime::parse_from_str(value, NIGHTLY_LAST_RUN_FORMAT).expect("datetime")
+        };
+        let scheduled = NaiveTime::from_hms_opt(3, 0, 0).expect("time");
+
+        assert!(!nightly_backup_due(
+            at("2026-05-02T02:59:00"),
+            scheduled,
+            None
+        ));
+        assert!(nightly_backup_due(
+            at("2026-05-02T03:00:00"),
+            scheduled,
+            None
+        ));
+        assert!(nightly_backup_due(
+            at("2026-05-02T09:30:00"),
+            scheduled,
+            Some(at("2026-05-01T03:00:10"))
+        ));
+        assert!(!nightly_backup_due(
+            at("2026-05-02T23:00:00"),
+            scheduled,
+            Some(at("2026-05-02T03:00:10"))
+        ));
+    }
+}
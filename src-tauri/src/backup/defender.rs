@@ -0,0 +1,287 @@
+//! Spots backups slowed down by Windows Defender real-time scanning.
+//!
+//! Every backup records its duration and the average CPU load of the Defender engine
+//! (`MsMpEng.exe`) while it ran. When recent backups of meaningful size are both slow and
+//! accompanied by a busy Defender, the diagnostics suggest excluding the backup folder.
+
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use sysinfo::{ProcessesToUpdate, System};
+
+const DEFENDER_PROCESS: &str = "MsMpEng.exe";
+/// Small backups finish before scanning overhead shows up in the numbers.
+const MIN_SAMPLE_BYTES: u64 = 64 * 1024 * 1024;
+/// Below this a backup counts as pathologically slow even for a spinning disk.
+const SLOW_MBPS: f64 = 5.0;
+/// Average Defender load (percent of the whole machine) that counts as hammering.
+const BUSY_CPU_PERCENT: f32 = 15.0;
+/// How many of the latest sizeable backups are considered.
+const RECENT_SAMPLES: usize = 5;
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Throughput of one finished backup (`backups.backup_size` / `duration_ms`).
+#[derive(Debug, Clone, Copy)]
+pub struct ThroughputSample {
+    pub bytes: u64,
+    pub duration_ms: u64,
+    pub defender_cpu: Option<f32>,
+}
+
+impl ThroughputSample {
+    fn mbps(&self) -> f64 {
+        let seconds = self.duration_ms.max(1) as f64 / 1000.0;
+        self.bytes as f64 / (1024.0 * 1024.0) / seconds
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DefenderStatus {
+    pub realtime_enabled: Option<bool>,
+    /// `None` when the exclusion list cannot be read (it needs elevation).
+    pub exclusions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticSuggestion {
+    pub kind: &'static str,
+    pub message: String,
+    /// Folder the user should exclude from real-time scanning.
+    pub folder: String,
+    /// PowerShell command (run as administrator) that adds the exclusion.
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupDiagnostics {
+    pub backup_directory: String,
+    pub realtime_protection: Option<bool>,
+    pub backup_directory_excluded: Option<bool>,
+    /// Median throughput of the recent sizeable backups.
+    pub recent_throughput_mbps: Option<f64>,
+    pub defender_cpu_percent: Option<f32>,
+    pub suggestions: Vec<DiagnosticSuggestion>,
+}
+
+/// Samples the Defender engine's CPU load on a background thread while a backup runs.
+pub struct DefenderCpuSampler {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<Option<f32>>,
+}
+
+impl DefenderCpuSampler {
+    pub fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            let cpus = thread::available_parallelism().map_or(1, |n| n.get()) as f32;
+            let mut sys = System::new();
+            // The first refresh only establishes the baseline cpu_usage is measured against.
+            sys.refresh_processes(ProcessesToUpdate::All, true);
+            let mut loads = Vec::new();
+            loop {
+                thread::sleep(SAMPLE_INTERVAL);
+                if flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                sys.refresh_processes(ProcessesToUpdate::All, true);
+                let load = sys
+                    .processes()
+                    .values()
+                    .filter(|p| {
+                        p.name()
+                            .to_string_lossy()
+                            .eq_ignore_ascii_case(DEFENDER_PROCESS)
+                    })
+                    .map(|p| p.cpu_usage() / cpus)
+                    .reduce(|a, b| a + b);
+                if let Some(load) = load {
+                    loads.push(load);
+                }
+            }
+            (!loads.is_empty()).then(|| loads.iter().sum::<f32>() / loads.len() as f32)
+        });
+        Self { stop, handle }
+    }
+
+    /// Average load seen while the backup ran; `None` when Defender is not running or the
+    /// backup was too short to measure.
+    pub fn finish(self) -> Option<f32> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.join().ok().flatten()
+    }
+}
+
+/// Parses the output of [`DEFENDER_STATUS_SCRIPT`]: the real-time flag on the first line,
+/// then one excluded path per line.
+pub fn parse_defender_status(output: &str) -> DefenderStatus {
+    let mut lines = output.lines().map(str::trim).filter(|l| !l.is_empty());
+    let realtime_enabled = match lines.next() {
+        Some(flag) if flag.eq_ignore_ascii_case("true") => Some(true),
+        Some(flag) if flag.eq_ignore_ascii_case("false") => Some(false),
+        _ => None,
+    };
+    let rest: Vec<String> = lines.map(str::to_string).collect();
+    // Without elevation Get-MpPreference reports "N/A: Must be an administrator ...".
+    let exclusions = if realtime_enabled.is_none() || rest.iter().any(|l| l.starts_with("N/A")) {
+        None
+    } else {
+        Some(rest)
+    };
+    DefenderStatus {
+        realtime_enabled,
+        exclusions,
+    }
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+const DEFENDER_STATUS_SCRIPT: &str = "$s = Get-MpComputerStatus; \
+     Write-Output $s.RealTimeProtectionEnabled; \
+     (Get-MpPreference).ExclusionPath | ForEach-Object { Write-Output $_ }";
+
+#[cfg(windows)]
+pub fn defender_status() -> DefenderStatus {
+    super::shadow_copy::run_powershell(DEFENDER_STATUS_SCRIPT)
+        .map(|output| parse_defender_status(&output))
+        .unwrap_or_default()
+}
+
+#[cfg(not(windows))]
+pub fn defender_status() -> DefenderStatus {
+    DefenderStatus::default()
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('/', "\\")
+        .trim_end_matches('\\')
+        .to_lowercase()
+}
+
+/// True when `folder` equals or lies inside one of the excluded paths.
+pub fn is_excluded(folder: &Path, exclusions: &[String]) -> bool {
+    let folder = normalize(&folder.to_string_lossy());
+    exclusions.iter().map(|e| normalize(e)).any(|excluded| {
+        !excluded.is_empty()
+            && (folder == excluded || folder.starts_with(&format!("{}\\", excluded)))
+    })
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    Some(values[values.len() / 2])
+}
+
+/// `samples` are newest first.
+pub fn diagnose_backups(
+    backup_directory: &Path,
+    samples: &[ThroughputSample],
+    status: &DefenderStatus,
+) -> BackupDiagnostics {
+    let recent: Vec<&ThroughputSample> = samples
+        .iter()
+        .filter(|s| s.bytes >= MIN_SAMPLE_BYTES)
+        .take(RECENT_SAMPLES)
+        .collect();
+    let recent_throughput_mbps = median(recent.iter().map(|s| s.mbps()).collect());
+    let cpu: Vec<f32> = recent.iter().filter_map(|s| s.defender_cpu).collect();
+    let defender_cpu_percent =
+        (!cpu.is_empty()).then(|| cpu.iter().sum::<f32>() / cpu.len() as f32);
+
+    let excluded = status
+        .exclusions
+        .as_ref()
+        .map(|list| is_excluded(backup_directory, list));
+    let hammered = recent
+        .iter()
+        .filter(|s| {
+            s.mbps() < SLOW_MBPS && s.defender_cpu.is_some_and(|cpu| cpu >= BUSY_CPU_PERCENT)
+        })
+        .count();
+
+    let mut suggestions = Vec::new();
+    if hammered * 2 > recent.len()
+        && status.realtime_enabled != Some(false)
+        && excluded != Some(true)
+    {
+        let folder = backup_directory.to_string_lossy().to_string();
+        suggestions.push(DiagnosticSuggestion {
+            kind: "defender_exclusion",
+            message: format!(
+                "Бэкапы идут со скоростью {:.1} МБ/с, пока Защитник Windows проверяет архивы. \
+                 Добавьте папку бэкапов в исключения антивируса.",
+                recent_throughput_mbps.unwrap_or_default()
+            ),
+            command: format!(
+                "Add-MpPreference -ExclusionPath '{}'",
+                folder.replace('\'', "''")
+            ),
+            folder,
+        });
+    }
+
+    BackupDiagnostics {
+        backup_directory: backup_directory.to_string_lossy().to_string(),
+        realtime_protection: status.realtime_enabled,
+        backup_directory_excluded: excluded,
+        recent_throughput_mbps,
+        defender_cpu_percent,
+        suggestions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(mb: u64, seconds: u64, cpu: Option<f32>) -> ThroughputSample {
+        ThroughputSample {
+            bytes: mb * 1024 * 1024,
+            duration_ms: seconds * 1000,
+            defender_cpu: cpu,
+        }
+    }
+
+    #[test]
+    fn suggests_exclusion_only_for_slow_scanned_unexcluded_backups() {
+        let dir = Path::new("D:\\Backups\\Arrancador");
+        let status = parse_defender_status("True\r\nC:\\Games\r\n");
+        assert_eq!(status.realtime_enabled, Some(true));
+        assert_eq!(status.exclusions, Some(vec!["C:\\Games".to_string()]));
+
+        let slow = vec![
+            sample(500, 200, Some(30.0)),
+            sample(400, 160, Some(25.0)),
+            sample(10, 30, Some(40.0)),
+            sample(600, 10, Some(2.0)),
+        ];
+        let report = diagnose_backups(dir, &slow, &status);
+        assert_eq!(report.backup_directory_excluded, Some(false));
+        assert_eq!(report.suggestions.len(), 1);
+        assert_eq!(report.suggestions[0].folder, "D:\\Backups\\Arrancador");
+        assert_eq!(
+            report.suggestions[0].command,
+            "Add-MpPreference -ExclusionPath 'D:\\Backups\\Arrancador'"
+        );
+
+        let excluded = parse_defender_status("True\nd:\\backups\\\n");
+        assert!(diagnose_backups(dir, &slow, &excluded)
+            .suggestions
+            .is_empty());
+
+        let quiet = vec![sample(500, 200, Some(1.0)), sample(500, 200, None)];
+        assert!(diagnose_backups(dir, &quiet, &status)
+            .suggestions
+            .is_empty());
+
+        let unknown =
+            parse_defender_status("True\nN/A: Must be an administrator to view exclusions\n");
+        assert_eq!(unknown.exclusions, None);
+        assert_eq!(diagnose_backups(dir, &slow, &unknown).suggestions.len(), 1);
+    }
+}
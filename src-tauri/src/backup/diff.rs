@@ -0,0 +1,227 @@
+use crate::backup::engine::{load_backup_manifest, long_path, BackupFileEntry};
+use crate::backup::verify::{crc32_of, rel_path};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+use zip::ZipArchive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupFileChange {
+    pub kind: FileChangeKind,
+    /// Original location of the save file (from the newer side when present).
+    pub path: String,
+    pub size_a: Option<u64>,
+    pub size_b: Option<u64>,
+}
+
+/// What changed from backup `a` to backup `b`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupDiff {
+    pub backup_a: String,
+    pub backup_b: String,
+    /// Ordered by (portable) path.
+    pub changes: Vec<BackupFileChange>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+/// Files are matched by their portable path (so a different user name or drive does not
+/// count as a change), case-insensitively like the Windows file system.
+fn entry_key(entry: &BackupFileEntry) -> String {
+    entry
+        .portable_path
+        .as_deref()
+        .unwrap_or(&entry.original_path)
+        .replace('\\', "/")
+        .to_lowercase()
+}
+
+/// Compares two manifests. Files of equal size are only reported as changed when
+/// `checksum` returns different values for them; `None` (unreadable) counts as unchanged.
+pub fn diff_manifests(
+    a: &[BackupFileEntry],
+    b: &[BackupFileEntry],
+    mut checksum: impl FnMut(Side, &BackupFileEntry) -> Option<u32>,
+) -> (Vec<BackupFileChange>, usize) {
+    let mut pairs: BTreeMap<String, (Option<&BackupFileEntry>, Option<&BackupFileEntry>)> =
+        BTreeMap::new();
+    for entry in a {
+        pairs.entry(entry_key(entry)).or_default().0 = Some(entry);
+    }
+    for entry in b {
+        pairs.entry(entry_key(entry)).or_default().1 = Some(entry);
+    }
+
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+    for (old, new) in pairs.into_values() {
+        let kind = match (old, new) {
+            (Some(_), None) => FileChangeKind::Removed,
+            (None, Some(_)) => FileChangeKind::Added,
+            (Some(old), Some(new)) => {
+                let differs = if old.link_target.is_some() || new.link_target.is_some() {
+                    old.link_target != new.link_target
+                } else if old.size != new.size {
+                    true
+                } else {
+                    match (checksum(Side::A, old), checksum(Side::B, new)) {
+                        (Some(old_crc), Some(new_crc)) => old_crc != new_crc,
+                        _ => false,
+                    }
+                };
+                if !differs {
+                    unchanged += 1;
+                    continue;
+                }
+                FileChangeKind::Changed
+            }
+            (None, None) => continue,
+        };
+        let shown = new.or(old).expect("one side is present");
+        changes.push(BackupFileChange {
+            kind,
+            path: shown.original_path.clone(),
+            size_a: old.map(|entry| entry.size),
+            size_b: new.map(|entry| entry.size),
+        });
+    }
+    (changes, unchanged)
+}
+
+/// Reads stored checksums without extracting: zip archives keep a CRC32 per entry in the
+/// central directory, directory backups are hashed in place.
+enum ChecksumSource {
+    Dir(std::path::PathBuf),
+    Zip(ZipArchive<File>),
+}
+
+impl ChecksumSource {
+    fn open(backup_path: &Path) -> Result<Self, String> {
+        if backup_path.is_dir() {
+            return Ok(Self::Dir(backup_path.to_path_buf()));
+        }
+        let file = File::open(backup_path).map_err(|e| e.to_string())?;
+        ZipArchive::new(file)
+            .map(Self::Zip)
+            .map_err(|e| e.to_string())
+    }
+
+    fn checksum(&mut self, entry: &BackupFileEntry) -> Option<u32> {
+        match self {
+            Self::Dir(root) => File::open(long_path(&root.join(rel_path(&entry.backup_path))))
+                .ok()
+                .and_then(|file| crc32_of(file).ok()),
+            Self::Zip(archive) => archive
+                .by_name(&entry.backup_path)
+                .ok()
+                .map(|file| file.crc32()),
+        }
+    }
+}
+
+pub fn diff_backup_paths(
+    backup_a: &str,
+    path_a: &Path,
+    backup_b: &str,
+    path_b: &Path,
+) -> Result<BackupDiff, String> {
+    let manifest = |path: &Path| {
+        load_backup_manifest(path)?
+            .ok_or_else(|| format!("Манифест бэкапа не найден: {}", path.display()))
+    };
+    let manifest_a = manifest(path_a)?;
+    let manifest_b = manifest(path_b)?;
+    let mut source_a = ChecksumSource::open(path_a)?;
+    let mut source_b = ChecksumSource::open(path_b)?;
+
+    let (changes, unchanged) = diff_manifests(
+        &manifest_a.files,
+        &manifest_b.files,
+        |side, entry| match side {
+            Side::A => source_a.checksum(entry),
+            Side::B => source_b.checksum(entry),
+        },
+    );
+    Ok(BackupDiff {
+        backup_a: backup_a.to_string(),
+        backup_b: backup_b.to_string(),
+        changes,
+        unchanged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, size: u64) -> BackupFileEntry {
+        BackupFileEntry {
+            backup_path: format!("files/{}", path),
+            original_path: format!("C:\\Users\\me\\Saves\\{}", path),
+            portable_path: Some(format!("<home>/Saves/{}", path)),
+            link_target: None,
+            size,
+            mtime: None,
+        }
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_content_changes() {
+        let a = vec![
+            entry("keep.sav", 10),
+            entry("gone.sav", 5),
+            entry("edit.sav", 8),
+        ];
+        let mut moved_user = entry("KEEP.sav", 10);
+        moved_user.original_path = "D:\\Users\\other\\Saves\\KEEP.sav".to_string();
+        let b = vec![moved_user, entry("edit.sav", 8), entry("new.sav", 3)];
+
+        let (changes, unchanged) = diff_manifests(&a, &b, |side, entry| {
+            let edited = side == Side::B && entry.backup_path.ends_with("edit.sav");
+            Some(if edited { 2 } else { 1 })
+        });
+
+        assert_eq!(unchanged, 1);
+        let summary: Vec<(FileChangeKind, &str, Option<u64>, Option<u64>)> = changes
+            .iter()
+            .map(|c| (c.kind, c.path.as_str(), c.size_a, c.size_b))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    FileChangeKind::Changed,
+                    "C:\\Users\\me\\Saves\\edit.sav",
+                    Some(8),
+                    Some(8)
+                ),
+                (
+                    FileChangeKind::Removed,
+                    "C:\\Users\\me\\Saves\\gone.sav",
+                    Some(5),
+                    None
+                ),
+                (
+                    FileChangeKind::Added,
+                    "C:\\Users\\me\\Saves\\new.sav",
+                    None,
+                    Some(3)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Free-space pre-checks so a backup or restore fails before copying anything instead
+//! of running out of space halfway through.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use sysinfo::Disks;
+
+/// Headroom on top of the payload for manifests, directory entries and cluster slack.
+const SPACE_MARGIN_BYTES: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiskSpaceShortage {
+    /// Volume mount point (`C:\`) the data would be written to.
+    pub mount_point: String,
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl fmt::Display for DiskSpaceShortage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Недостаточно места на диске {}: требуется {} МБ, свободно {} МБ",
+            self.mount_point,
+            self.required_bytes.div_ceil(1024 * 1024),
+            self.available_bytes / 1024 / 1024
+        )
+    }
+}
+
+/// Mount points and their free space, as reported by the OS.
+fn volumes() -> Vec<(PathBuf, u64)> {
+    Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .map(|disk| (disk.mount_point().to_path_buf(), disk.available_space()))
+        .collect()
+}
+
+fn normalized(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    if cfg!(windows) {
+        text.replace('/', "\\")
+            .trim_start_matches(r"\\?\")
+            .to_lowercase()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Index of the volume with the longest mount point containing `path`.
+fn volume_for(volumes: &[(PathBuf, u64)], path: &Path) -> Option<usize> {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let target = normalized(&path);
+    volumes
+        .iter()
+        .enumerate()
+        .filter(|(_, (mount, _))| {
+            let mount = normalized(mount);
+            let sep = if cfg!(windows) { '\\' } else { '/' };
+            target == mount.trim_end_matches(sep)
+                || target.starts_with(&format!("{}{}", mount.trim_end_matches(sep), sep))
+        })
+        .max_by_key(|(_, (mount, _))| mount.as_os_str().len())
+        .map(|(index, _)| index)
+}
+
+/// Sums `needs` (target path, bytes) per volume and reports the first volume that cannot
+/// hold its share. Paths on volumes the OS does not report are not checked.
+fn find_shortage(
+    volumes: &[(PathBuf, u64)],
+    needs: impl IntoIterator<Item = (PathBuf, u64)>,
+) -> Option<DiskSpaceShortage> {
+    let mut required: BTreeMap<usize, u64> = BTreeMap::new();
+    for (path, bytes) in needs {
+        if let Some(index) = volume_for(volumes, &path) {
+            *required.entry(index).or_default() += bytes;
+        }
+    }
+    required.into_iter().find_map(|(index, bytes)| {
+        let (mount, available) = &volumes[index];
+        let required_bytes = bytes + SPACE_MARGIN_BYTES;
+        (required_bytes > *available).then(|| DiskSpaceShortage {
+            mount_point: mount.to_string_lossy().to_string(),
+            required_bytes,
+            available_bytes: *available,
+        })
+    })
+}
+
+/// Fails when writing `needs` would not fit on the volumes they land on.
+pub fn ensure_free_space(
+    needs: impl IntoIterator<Item = (PathBuf, u64)>,
+) -> Result<(), DiskSpaceShortage> {
+    match find_shortage(&volumes(), needs) {
+        Some(shortage) => Err(shortage),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortage_is_reported_per_volume() {
+        let (root, data) = if cfg!(windows) {
+            (r"C:\", r"D:\")
+        } else {
+            ("/", "/mnt/data")
+        };
+        let gib = 1024 * 1024 * 1024;
+        let volumes = vec![(PathBuf::from(root), 10 * gib), (PathBuf::from(data), gib)];
+        let on_data = Path::new(data).join("Backups").join("game.sqoba.zip");
+        let on_root = Path::new(root).join("Saves").join("slot1.sav");
+
+        assert_eq!(
+            find_shortage(
+                &volumes,
+                vec![(on_root.clone(), 5 * gib), (on_data.clone(), gib / 2)]
+            ),
+            None
+        );
+        let shortage = find_shortage(
+            &volumes,
+            vec![
+                (on_root, 5 * gib),
+                (on_data.clone(), gib / 2),
+                (on_data, gib / 2),
+            ],
+        )
+        .expect("data volume is full");
+        assert_eq!(shortage.mount_point, data);
+        assert_eq!(shortage.required_bytes, gib + SPACE_MARGIN_BYTES);
+        assert_eq!(shortage.available_bytes, gib);
+    }
+}
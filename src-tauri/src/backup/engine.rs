@@ -1,7 +1,20 @@
+use crate::backup::disk_space::ensure_free_space;
+use crate::backup::error::BackupError;
+use crate::backup::path_remap::{PathTokens, RestorePathRemapper};
 use crate::backup::save_locator::{
-    locate_game_save_roots, locate_game_saves, SaveDiscovery, SaveRoot,
+    candidate_save_roots, locate_game_save_roots, locate_game_saves,
+    locate_game_saves_with_symlinks, manifest_registry_keys, SaveDiscovery, SaveLookup, SaveRoot,
+    SymlinkPolicy,
 };
+use crate::backup::shadow_copy::{is_lock_error, ShadowCopies};
 use crate::backup::sqoba_manifest::{SqobaGame, SqobaManifest};
+use crate::backup::throughput::ThroughputTuner;
+use crate::backup::verify::{verify_backup, VerifyMode};
+use crate::backup::watchdog::{
+    CopyInterrupted, OperationMonitor, CANCELLED_MESSAGE, SKIPPED_REASON,
+};
+use crate::backup::wgs;
+use chrono::Local;
 use lazy_static::lazy_static;
 use rayon::prelude::*;
 use regex::Regex;
@@ -13,6 +26,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
 use zip::write::{FileOptions, ZipWriter};
 use zip::{CompressionMethod, ZipArchive};
 
@@ -24,6 +38,10 @@ const LEGACY_MANIFEST_NAME: &str = "__arrancador_manifest.json";
 const BACKUP_MANIFEST_NAMES: [&str; 2] = [SQOBA_MANIFEST_NAME, LEGACY_MANIFEST_NAME];
 const MANIFEST_VERSION: u32 = 2;
 const LUDUSAVI_MAPPING_NAME: &str = "mapping.yaml";
+/// Levels (on the 1..=100 settings scale) tried by `benchmark_compression`.
+pub const BENCHMARK_LEVELS: [u8; 6] = [1, 20, 40, 60, 80, 100];
+/// Upper bound on how much save data is read into memory for a benchmark run.
+const BENCHMARK_SAMPLE_BYTES: u64 = 32 * 1024 * 1024;
 
 lazy_static! {
     static ref DRIVE_REGEX: Regex = Regex::new(r"^([A-Za-z]):[\\/](.*)$").expect("drive regex");
@@ -34,6 +52,40 @@ pub struct BackupArchiveManifest {
     #[serde(default)]
     pub version: u32,
     pub files: Vec<BackupFileEntry>,
+    /// Source files left out because another process kept them locked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BackupMetadata>,
+}
+
+/// Context recorded with a backup so it can be told apart by more than its timestamp.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct BackupMetadata {
+    /// The game's total playtime when the backup was made.
+    pub playtime_seconds: i64,
+    /// Length of the most recent play session, if there was one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_session_seconds: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Outcome of `BackupEngine::repair_directory_manifest`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestRepair {
+    /// False when the existing manifest was readable and left untouched.
+    pub rebuilt: bool,
+    pub files: usize,
+    /// `files/<label>` folders whose original location could not be inferred; their
+    /// files stay in the backup but are not listed in the new manifest.
+    pub unresolved_roots: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SkippedFile {
+    pub original_path: String,
+    pub reason: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -41,6 +93,13 @@ pub struct BackupFileEntry {
     #[serde(rename = "backup_path", alias = "zip_path")]
     pub backup_path: String,
     pub original_path: String,
+    /// `original_path` expressed against machine tokens (e.g. `<winAppData>/...`)
+    /// so restores survive a different user name or drive layout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub portable_path: Option<String>,
+    /// Target of a symlink stored as a link (no file data in the backup).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_target: Option<String>,
     pub size: u64,
     #[serde(default)]
     pub mtime: Option<i64>,
@@ -70,15 +129,32 @@ pub enum ZipCompression {
     Zstd,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct CompressionBenchmark {
+    pub level: u8,
+    pub sample_files: usize,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    pub ratio: f64,
+    pub duration_ms: u64,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct BackupOptions {
     pub mode: BackupMode,
+    pub verify: VerifyMode,
+    pub symlinks: SymlinkPolicy,
+    /// Retry locked files from a Volume Shadow Copy snapshot before skipping them.
+    pub shadow_copy: bool,
 }
 
 impl BackupOptions {
     pub fn directory() -> Self {
         Self {
             mode: BackupMode::Directory,
+            verify: VerifyMode::Off,
+            symlinks: SymlinkPolicy::Skip,
+            shadow_copy: false,
         }
     }
 
@@ -88,6 +164,9 @@ impl BackupOptions {
                 level,
                 compression: ZipCompression::Zstd,
             },
+            verify: VerifyMode::Off,
+            symlinks: SymlinkPolicy::Skip,
+            shadow_copy: false,
         }
     }
 
@@ -98,8 +177,26 @@ impl BackupOptions {
                 level,
                 compression: ZipCompression::Deflate,
             },
+            verify: VerifyMode::Off,
+            symlinks: SymlinkPolicy::Skip,
+            shadow_copy: false,
         }
     }
+
+    pub fn with_verify(mut self, verify: VerifyMode) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    pub fn with_symlinks(mut self, symlinks: SymlinkPolicy) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    pub fn with_shadow_copy(mut self, shadow_copy: bool) -> Self {
+        self.shadow_copy = shadow_copy;
+        self
+    }
 }
 
 impl Default for BackupOptions {
@@ -140,11 +237,66 @@ pub struct LudusaviRegistry {
 
 pub struct BackupEngine {
     manifest: Option<SqobaManifest>,
+    /// Parallelism learned by the throughput tuner during the last directory backup (0 = none).
+    tuned_threads: AtomicUsize,
+    /// Heartbeat for the stall watchdog; set by the command running the operation.
+    monitor: Option<Arc<OperationMonitor>>,
+    /// Written into the manifest of the next backups; set by the command creating them.
+    metadata: Option<BackupMetadata>,
 }
 
 impl BackupEngine {
     pub fn new() -> Self {
-        Self { manifest: None }
+        Self {
+            manifest: None,
+            tuned_threads: AtomicUsize::new(0),
+            monitor: None,
+            metadata: None,
+        }
+    }
+
+    pub fn set_metadata(&mut self, metadata: Option<BackupMetadata>) {
+        self.metadata = metadata;
+    }
+
+    pub fn set_monitor(&mut self, monitor: Option<Arc<OperationMonitor>>) {
+        self.monitor = monitor;
+    }
+
+    /// Copies through the watchdog monitor when one is attached so a stuck file can be
+    /// reported, skipped or cancelled.
+    fn copy_stream<R: Read, W: Write>(
+        &self,
+        path: &Path,
+        reader: &mut R,
+        writer: &mut W,
+    ) -> Result<u64, CopyInterrupted> {
+        match &self.monitor {
+            Some(monitor) => {
+                monitor.begin_file(path);
+                monitor.copy(reader, writer)
+            }
+            None => std::io::copy(reader, writer).map_err(CopyInterrupted::Io),
+        }
+    }
+
+    fn copy_file(&self, source: &Path, target: &Path) -> Result<u64, CopyInterrupted> {
+        match &self.monitor {
+            Some(monitor) => {
+                monitor.begin_file(source);
+                monitor.copy_file(source, target)
+            }
+            None => fs::copy(source, target).map_err(CopyInterrupted::Io),
+        }
+    }
+
+    /// Returns (and clears) the thread count the last backup settled on, if it ran long
+    /// enough to measure throughput.
+    pub fn take_tuned_threads(&self) -> Option<usize> {
+        match self.tuned_threads.swap(0, Ordering::Relaxed) {
+            0 => None,
+            threads => Some(threads),
+        }
     }
 
     pub fn load_manifest(&mut self) -> Result<(), String> {
@@ -162,6 +314,21 @@ impl BackupEngine {
         Ok(())
     }
 
+    pub fn manifest_game_count(&self) -> Option<usize> {
+        self.manifest.as_ref().map(|manifest| manifest.games.len())
+    }
+
+    /// Best manifest key for `name` with its score, below the match threshold included.
+    pub fn match_manifest_key(&self, name: &str) -> Option<(String, f32)> {
+        self.manifest.as_ref()?.match_game(name)
+    }
+
+    pub fn has_manifest_entry(&self, key: &str) -> bool {
+        self.manifest
+            .as_ref()
+            .is_some_and(|manifest| manifest.games.contains_key(key))
+    }
+
     #[allow(dead_code)]
     pub fn find_game_entry(&self, name: &str) -> Option<SqobaGame> {
         self.find_game_entry_with_key(name).map(|(_, entry)| entry)
@@ -170,17 +337,30 @@ impl BackupEngine {
     pub fn discover_game_saves(
         &self,
         name: &str,
-        override_path: Option<&str>,
+        lookup: SaveLookup,
     ) -> Result<Option<SaveDiscovery>, String> {
-        locate_game_saves(name, self.manifest.as_ref(), override_path)
+        locate_game_saves(name, self.manifest.as_ref(), lookup)
+    }
+
+    pub fn manifest_registry_keys(&self, name: &str, lookup: SaveLookup) -> Vec<String> {
+        manifest_registry_keys(name, self.manifest.as_ref(), lookup)
     }
 
     pub fn discover_game_save_roots(
         &self,
         name: &str,
-        override_path: Option<&str>,
+        lookup: SaveLookup,
     ) -> Result<Vec<SaveRoot>, String> {
-        locate_game_save_roots(name, self.manifest.as_ref(), override_path)
+        locate_game_save_roots(name, self.manifest.as_ref(), lookup)
+    }
+
+    pub fn candidate_save_roots(
+        &self,
+        name: &str,
+        wiki_paths: &[String],
+        game_dir: Option<&Path>,
+    ) -> Vec<(PathBuf, &'static str)> {
+        candidate_save_roots(name, self.manifest.as_ref(), wiki_paths, game_dir)
     }
 
     fn find_game_entry_with_key(&self, name: &str) -> Option<(String, SqobaGame)> {
@@ -191,7 +371,7 @@ impl BackupEngine {
     /// Finds save files for a game without backing them up
     #[allow(dead_code)]
     pub fn find_game_files(&self, name: &str) -> Result<Option<(Vec<PathBuf>, u64)>, String> {
-        let discovery = self.discover_game_saves(name, None)?;
+        let discovery = self.discover_game_saves(name, SaveLookup::default())?;
         let Some(discovery) = discovery else {
             return Ok(None);
         };
@@ -206,7 +386,7 @@ impl BackupEngine {
     }
 
     #[allow(dead_code)]
-    pub fn backup_game(&self, name: &str, destination: &Path) -> Result<u64, String> {
+    pub fn backup_game(&self, name: &str, destination: &Path) -> Result<u64, BackupError> {
         self.backup_game_with_threads(name, destination, 4)
     }
 
@@ -216,7 +396,7 @@ impl BackupEngine {
         name: &str,
         destination: &Path,
         threads: usize,
-    ) -> Result<u64, String> {
+    ) -> Result<u64, BackupError> {
         self.backup_game_with_threads_and_progress(name, destination, threads, None)
     }
 
@@ -227,13 +407,13 @@ impl BackupEngine {
         destination: &Path,
         threads: usize,
         progress: Option<Arc<dyn Fn(BackupProgress) + Send + Sync>>,
-    ) -> Result<u64, String> {
+    ) -> Result<u64, BackupError> {
         self.backup_game_with_options_and_progress(
             name,
             destination,
             threads,
             BackupOptions::default(),
-            None,
+            SaveLookup::default(),
             progress,
         )
     }
@@ -244,8 +424,15 @@ impl BackupEngine {
         name: &str,
         destination: &Path,
         options: BackupOptions,
-    ) -> Result<u64, String> {
-        self.backup_game_with_options_and_progress(name, destination, 4, options, None, None)
+    ) -> Result<u64, BackupError> {
+        self.backup_game_with_options_and_progress(
+            name,
+            destination,
+            4,
+            options,
+            SaveLookup::default(),
+            None,
+        )
     }
 
     #[allow(dead_code)]
@@ -255,8 +442,15 @@ impl BackupEngine {
         destination: &Path,
         threads: usize,
         options: BackupOptions,
-    ) -> Result<u64, String> {
-        self.backup_game_with_options_and_progress(name, destination, threads, options, None, None)
+    ) -> Result<u64, BackupError> {
+        self.backup_game_with_options_and_progress(
+            name,
+            destination,
+            threads,
+            options,
+            SaveLookup::default(),
+            None,
+        )
     }
 
     pub fn backup_game_with_options_and_progress(
@@ -265,43 +459,85 @@ impl BackupEngine {
         destination: &Path,
         threads: usize,
         options: BackupOptions,
-        override_path: Option<&str>,
+        lookup: SaveLookup,
         progress: Option<Arc<dyn Fn(BackupProgress) + Send + Sync>>,
-    ) -> Result<u64, String> {
-        let matched_name = self.find_game_entry_with_key(name).map(|(key, _)| key);
-        let discovery = locate_game_saves(name, self.manifest.as_ref(), override_path)?;
+    ) -> Result<u64, BackupError> {
+        let matched_name = match lookup.manifest_key {
+            Some(key) => Some(key.to_string()),
+            None => self.find_game_entry_with_key(name).map(|(key, _)| key),
+        };
+        let discovery = locate_game_saves_with_symlinks(
+            name,
+            self.manifest.as_ref(),
+            lookup,
+            options.symlinks,
+        )?;
         let discovery = match discovery {
             Some(discovery) => discovery,
             None => {
                 let suggestions = self.suggest_games(name, 5);
                 if suggestions.is_empty() {
-                    return Err(format!("Сохранения не найдены для '{}'", name));
+                    return Err(format!("Сохранения не найдены для '{}'", name).into());
                 }
                 return Err(format!(
                     "Сохранения не найдены для '{}'. Ближайшие совпадения: {}",
                     name,
                     suggestions.join(", ")
-                ));
+                )
+                .into());
             }
         };
 
+        let tokens = PathTokens::for_game(lookup.game_dir);
         let file_list: Vec<BackupSourceFile> = discovery
             .files
             .iter()
             .map(|entry| BackupSourceFile {
                 path: entry.path.clone(),
                 backup_path: build_backup_rel_path(&entry.root_label, &entry.relative_path),
+                portable_path: tokens.tokenise(&entry.path),
+                link_target: entry.link_target.clone(),
             })
             .collect();
 
+        // Uncompressed size is an upper bound for zip backups too.
+        check_free_space(
+            [(destination.to_path_buf(), discovery.total_size)],
+            progress.as_ref(),
+        )?;
+
+        let shadows = ShadowCopies::new(options.shadow_copy);
         let total_bytes = match options.mode {
-            BackupMode::Directory => {
-                self.backup_to_directory(destination, &file_list, threads, progress)?
-            }
-            BackupMode::Zip { level, compression } => {
-                self.backup_to_zip(destination, &file_list, level, compression, progress)?
-            }
+            BackupMode::Directory => self.backup_to_directory(
+                destination,
+                &file_list,
+                threads,
+                &shadows,
+                progress.clone(),
+            )?,
+            BackupMode::Zip { level, compression } => self.backup_to_zip(
+                name,
+                destination,
+                &file_list,
+                zip_data_options(level, compression),
+                &shadows,
+                progress.clone(),
+            )?,
         };
+        // Release snapshots before the (possibly slow) verification pass.
+        drop(shadows);
+
+        if options.verify != VerifyMode::Off {
+            if let Some(cb) = &progress {
+                cb(BackupProgress {
+                    stage: "verify",
+                    current: destination.to_string_lossy().to_string(),
+                    done: 0,
+                    total: file_list.len(),
+                });
+            }
+            verify_backup(destination, options.verify)?;
+        }
 
         if let Some(matched_name) = matched_name {
             if matched_name != name {
@@ -315,8 +551,26 @@ impl BackupEngine {
         Ok(total_bytes)
     }
 
+    /// Compresses a sample of the game's save files at each of `levels` in memory,
+    /// so the user can compare size/time tradeoffs for `backup_compression_level`.
+    pub fn benchmark_compression(
+        &self,
+        name: &str,
+        lookup: SaveLookup,
+        levels: &[u8],
+    ) -> Result<Vec<CompressionBenchmark>, String> {
+        let discovery = locate_game_saves(name, self.manifest.as_ref(), lookup)?
+            .ok_or_else(|| format!("Сохранения не найдены для '{}'", name))?;
+        let paths: Vec<PathBuf> = discovery
+            .files
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect();
+        benchmark_files(&paths, levels, BENCHMARK_SAMPLE_BYTES)
+    }
+
     #[allow(dead_code)]
-    pub fn restore_backup(&self, backup_path: &Path) -> Result<(), String> {
+    pub fn restore_backup(&self, backup_path: &Path) -> Result<(), BackupError> {
         self.restore_backup_with_threads(backup_path, 4)
     }
 
@@ -325,28 +579,58 @@ impl BackupEngine {
         &self,
         backup_path: &Path,
         threads: usize,
-    ) -> Result<(), String> {
+    ) -> Result<(), BackupError> {
         self.restore_backup_with_threads_and_progress(backup_path, threads, None)
     }
 
+    #[allow(dead_code)]
     pub fn restore_backup_with_threads_and_progress(
         &self,
         backup_path: &Path,
         threads: usize,
         progress: Option<Arc<dyn Fn(BackupProgress) + Send + Sync>>,
-    ) -> Result<(), String> {
+    ) -> Result<(), BackupError> {
+        self.restore_backup_with_remap(
+            backup_path,
+            threads,
+            &RestorePathRemapper::identity(),
+            progress,
+        )
+    }
+
+    pub fn restore_backup_with_remap(
+        &self,
+        backup_path: &Path,
+        threads: usize,
+        remapper: &RestorePathRemapper,
+        progress: Option<Arc<dyn Fn(BackupProgress) + Send + Sync>>,
+    ) -> Result<(), BackupError> {
         if backup_path.is_dir() {
             if let Some(manifest) = read_manifest_from_dir(backup_path)? {
-                let items: Vec<(PathBuf, PathBuf)> = manifest
+                check_free_space(
+                    restore_space_needs(&manifest.files, remapper),
+                    progress.as_ref(),
+                )?;
+                let (links, files): (Vec<_>, Vec<_>) = manifest
                     .files
+                    .into_iter()
+                    .partition(|entry| entry.link_target.is_some());
+                for entry in &links {
+                    restore_link(&remapper.remap(entry), entry);
+                }
+                let items: Vec<(PathBuf, PathBuf)> = files
                     .into_iter()
                     .map(|entry| {
                         let source_path =
                             backup_path.join(path_from_backup_rel(&entry.backup_path));
-                        let target_path = PathBuf::from(&entry.original_path);
+                        let target_path = remapper.remap(&entry);
                         (source_path, target_path)
                     })
                     .collect();
+                // WGS indexes go last so they never point at containers not yet written.
+                let (indexes, items): (Vec<_>, Vec<_>) = items
+                    .into_iter()
+                    .partition(|(_, target)| wgs::is_containers_index(target));
 
                 let thread_pool = rayon::ThreadPoolBuilder::new()
                     .num_threads(threads.max(1))
@@ -361,11 +645,13 @@ impl BackupEngine {
                     items
                         .par_iter()
                         .map(|(source, target)| {
-                            if let Some(parent) = target.parent() {
+                            let source = long_path(source);
+                            let target_long = long_path(target);
+                            if let Some(parent) = target_long.parent() {
                                 fs::create_dir_all(parent).map_err(|e| e.to_string())?;
                             }
                             if source.exists() {
-                                fs::copy(source, target).map_err(|e| e.to_string())?;
+                                restore_copy_result(self.copy_file(&source, &target_long))?;
                             }
                             let done = counter.fetch_add(1, Ordering::Relaxed) + 1;
                             if let Some(cb) = &progress_ref {
@@ -386,13 +672,29 @@ impl BackupEngine {
                 for r in results {
                     r?;
                 }
+                for (source, target) in &indexes {
+                    let source = long_path(source);
+                    if !source.exists() {
+                        continue;
+                    }
+                    let target = long_path(target);
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                    }
+                    let copied = self.copy_file(&source, &target);
+                    let restored = copied.is_ok();
+                    restore_copy_result(copied)?;
+                    if restored {
+                        prune_restored_containers(&target);
+                    }
+                }
 
                 return Ok(());
             }
 
             let mapping_path = backup_path.join(LUDUSAVI_MAPPING_NAME);
             if mapping_path.exists() {
-                return self.restore_from_ludusavi_mapping(backup_path, &mapping_path);
+                return Ok(self.restore_from_ludusavi_mapping(backup_path, &mapping_path)?);
             }
         }
 
@@ -401,19 +703,39 @@ impl BackupEngine {
 
         let manifest = read_manifest_from_zip(&mut archive)?
             .ok_or_else(|| "В архиве отсутствует манифест бэкапа".to_string())?;
-
-        for entry in manifest.files {
+        check_free_space(
+            restore_space_needs(&manifest.files, remapper),
+            progress.as_ref(),
+        )?;
+
+        let mut files = manifest.files;
+        // WGS indexes go last so they never point at containers not yet written.
+        files.sort_by_key(|entry| wgs::is_containers_index(Path::new(&entry.original_path)));
+        for entry in files {
+            if entry.link_target.is_some() {
+                restore_link(&remapper.remap(&entry), &entry);
+                continue;
+            }
             let mut zipped = archive
                 .by_name(&entry.backup_path)
                 .map_err(|e| format!("В архиве отсутствует файл: {}", e))?;
 
-            let target_path = PathBuf::from(&entry.original_path);
+            let target_path = long_path(&remapper.remap(&entry));
             if let Some(parent) = target_path.parent() {
                 fs::create_dir_all(parent).map_err(|e| e.to_string())?;
             }
 
             let mut out_file = File::create(&target_path).map_err(|e| e.to_string())?;
-            std::io::copy(&mut zipped, &mut out_file).map_err(|e| e.to_string())?;
+            let copied = self.copy_stream(&target_path, &mut zipped, &mut out_file);
+            drop(out_file);
+            if matches!(copied, Err(CopyInterrupted::Skipped)) {
+                let _ = fs::remove_file(&target_path);
+            }
+            let restored = copied.is_ok();
+            restore_copy_result(copied)?;
+            if restored && wgs::is_containers_index(&target_path) {
+                prune_restored_containers(&target_path);
+            }
         }
 
         Ok(())
@@ -424,12 +746,15 @@ impl BackupEngine {
         destination: &Path,
         files: &[BackupSourceFile],
         threads: usize,
+        shadows: &ShadowCopies,
         progress: Option<Arc<dyn Fn(BackupProgress) + Send + Sync>>,
     ) -> Result<u64, String> {
         fs::create_dir_all(destination).map_err(|e| e.to_string())?;
 
+        // Start from the caller's guess but leave headroom for the tuner to climb.
+        let tuner = ThroughputTuner::new(threads, tuner_max_threads(threads));
         let thread_pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(threads.max(1))
+            .num_threads(tuner.max_threads())
             .build()
             .map_err(|e| e.to_string())?;
 
@@ -437,13 +762,31 @@ impl BackupEngine {
         let counter = AtomicUsize::new(0);
         let progress_ref = progress.clone();
 
-        let results: Vec<Result<BackupFileEntry, String>> = thread_pool.install(|| {
+        let results: Vec<Result<CopiedFile, String>> = thread_pool.install(|| {
             files
                 .par_iter()
                 .map(|file| {
-                    let size =
-                        self.copy_file_to_backup(destination, &file.path, &file.backup_path)?;
+                    if let Some(entry) = file.link_entry() {
+                        return Ok(CopiedFile::Stored(entry));
+                    }
+                    let copied = {
+                        let _permit = tuner.acquire();
+                        self.copy_file_to_backup(
+                            destination,
+                            &file.path,
+                            &file.backup_path,
+                            shadows,
+                        )?
+                    };
                     let done = counter.fetch_add(1, Ordering::Relaxed) + 1;
+                    let size = match copied {
+                        Ok(size) => size,
+                        Err(skipped) => {
+                            report_skipped(&progress_ref, &skipped, done, total);
+                            return Ok(CopiedFile::Skipped(skipped));
+                        }
+                    };
+                    tuner.record(size);
                     if let Some(cb) = &progress_ref {
                         if done == total || done.is_multiple_of(50) {
                             cb(BackupProgress {
@@ -454,25 +797,36 @@ impl BackupEngine {
                             });
                         }
                     }
-                    Ok(BackupFileEntry {
+                    Ok(CopiedFile::Stored(BackupFileEntry {
                         backup_path: file.backup_path.clone(),
                         original_path: file.path.to_string_lossy().to_string(),
+                        portable_path: file.portable_path.clone(),
+                        link_target: None,
                         size,
                         mtime: file_mtime(&file.path),
-                    })
+                    }))
                 })
                 .collect()
         });
 
+        if let Some(learned) = tuner.learned_threads() {
+            self.tuned_threads.store(learned, Ordering::Relaxed);
+        }
+
         let mut entries: Vec<BackupFileEntry> = Vec::with_capacity(results.len());
+        let mut skipped: Vec<SkippedFile> = Vec::new();
         let mut total_bytes = 0;
         for r in results {
-            let entry = r?;
-            total_bytes += entry.size;
-            entries.push(entry);
+            match r? {
+                CopiedFile::Stored(entry) => {
+                    total_bytes += entry.size;
+                    entries.push(entry);
+                }
+                CopiedFile::Skipped(file) => skipped.push(file),
+            }
         }
 
-        let manifest = build_manifest(&entries);
+        let manifest = build_manifest(&entries, skipped, self.metadata.clone());
         self.write_manifest_to_dir(destination, &manifest)?;
         self.write_readme_to_dir(destination)?;
 
@@ -481,10 +835,11 @@ impl BackupEngine {
 
     fn backup_to_zip(
         &self,
+        game_name: &str,
         destination: &Path,
         files: &[BackupSourceFile],
-        level: u8,
-        compression: ZipCompression,
+        file_options: FileOptions<'static, ()>,
+        shadows: &ShadowCopies,
         progress: Option<Arc<dyn Fn(BackupProgress) + Send + Sync>>,
     ) -> Result<u64, String> {
         if destination.exists() && destination.is_dir() {
@@ -498,14 +853,26 @@ impl BackupEngine {
         let file = File::create(destination).map_err(|e| e.to_string())?;
         let writer = BufWriter::new(file);
         let mut archive = ZipWriter::new(writer);
-        let file_options = zip_data_options(level, compression);
         let total = files.len();
 
         let mut entries: Vec<BackupFileEntry> = Vec::with_capacity(total);
+        let mut skipped: Vec<SkippedFile> = Vec::new();
         let mut total_bytes = 0u64;
 
         for (index, file) in files.iter().enumerate() {
-            let mut source = File::open(&file.path).map_err(|e| e.to_string())?;
+            if let Some(entry) = file.link_entry() {
+                entries.push(entry);
+                continue;
+            }
+            let mut source = match open_source(&file.path, shadows) {
+                Ok(source) => source,
+                Err(reason) => {
+                    let file = skipped_file(&file.path, reason);
+                    report_skipped(&progress, &file, index + 1, total);
+                    skipped.push(file);
+                    continue;
+                }
+            };
             let metadata = source.metadata().map_err(|e| e.to_string())?;
             let size = metadata.len();
             let mtime = metadata
@@ -516,11 +883,26 @@ impl BackupEngine {
             archive
                 .start_file(&file.backup_path, file_options)
                 .map_err(|e| e.to_string())?;
-            std::io::copy(&mut source, &mut archive).map_err(|e| e.to_string())?;
+            if let Err(interrupted) = self.copy_stream(&file.path, &mut source, &mut archive) {
+                // A byte-range lock can still stop the read half way; drop the partial entry.
+                let reason = match interrupted {
+                    CopyInterrupted::Io(e) if is_lock_error(&e) => e.to_string(),
+                    CopyInterrupted::Io(e) => return Err(e.to_string()),
+                    CopyInterrupted::Cancelled => return Err(CANCELLED_MESSAGE.to_string()),
+                    CopyInterrupted::Skipped => SKIPPED_REASON.to_string(),
+                };
+                archive.abort_file().map_err(|e| e.to_string())?;
+                let file = skipped_file(&file.path, reason);
+                report_skipped(&progress, &file, index + 1, total);
+                skipped.push(file);
+                continue;
+            }
 
             entries.push(BackupFileEntry {
                 backup_path: file.backup_path.clone(),
                 original_path: file.path.to_string_lossy().to_string(),
+                portable_path: file.portable_path.clone(),
+                link_target: None,
                 size,
                 mtime,
             });
@@ -539,7 +921,13 @@ impl BackupEngine {
             }
         }
 
-        let manifest = build_manifest(&entries);
+        archive.set_comment(backup_archive_comment(
+            game_name,
+            &Local::now().format("%Y-%m-%d %H:%M:%S %:z").to_string(),
+            entries.len(),
+            skipped.len(),
+        ));
+        let manifest = build_manifest(&entries, skipped, self.metadata.clone());
         self.write_manifest_to_zip(&mut archive, &manifest)?;
         self.write_readme_to_zip(&mut archive)?;
         archive.finish().map_err(|e| e.to_string())?;
@@ -547,18 +935,48 @@ impl BackupEngine {
         Ok(total_bytes)
     }
 
+    /// Copies one file into a directory backup. The inner `Err` means the source was locked
+    /// (and no snapshot could stand in for it), so the file is skipped rather than failing
+    /// the whole backup.
     fn copy_file_to_backup(
         &self,
         backup_root: &Path,
         file_path: &Path,
         backup_rel: &str,
-    ) -> Result<u64, String> {
-        let target_path = backup_root.join(path_from_backup_rel(backup_rel));
+        shadows: &ShadowCopies,
+    ) -> Result<Result<u64, SkippedFile>, String> {
+        let target_path = long_path(&backup_root.join(path_from_backup_rel(backup_rel)));
         if let Some(parent) = target_path.parent() {
             fs::create_dir_all(parent).map_err(|e| e.to_string())?;
         }
-        let bytes = fs::copy(file_path, &target_path).map_err(|e| e.to_string())?;
-        Ok(bytes)
+        let locked = match self.copy_file(&long_path(file_path), &target_path) {
+            Ok(bytes) => return Ok(Ok(bytes)),
+            Err(CopyInterrupted::Io(e)) if is_lock_error(&e) => e,
+            Err(CopyInterrupted::Io(e)) => return Err(e.to_string()),
+            Err(CopyInterrupted::Cancelled) => return Err(CANCELLED_MESSAGE.to_string()),
+            Err(CopyInterrupted::Skipped) => {
+                return Ok(Err(skipped_file(file_path, SKIPPED_REASON.to_string())))
+            }
+        };
+        let from_snapshot = shadows
+            .snapshot_path(file_path)
+            .map(|snapshot| self.copy_file(&snapshot, &target_path));
+        match from_snapshot {
+            Some(Ok(bytes)) => Ok(Ok(bytes)),
+            Some(Err(CopyInterrupted::Cancelled)) => Err(CANCELLED_MESSAGE.to_string()),
+            Some(Err(interrupted)) => {
+                let _ = fs::remove_file(&target_path);
+                let reason = match interrupted {
+                    CopyInterrupted::Io(e) => e.to_string(),
+                    _ => SKIPPED_REASON.to_string(),
+                };
+                Ok(Err(skipped_file(file_path, reason)))
+            }
+            None => {
+                let _ = fs::remove_file(&target_path);
+                Ok(Err(skipped_file(file_path, locked.to_string())))
+            }
+        }
     }
 
     fn write_manifest_to_dir(
@@ -630,18 +1048,118 @@ impl BackupEngine {
             let (drive_key, rel) = split_drive_for_restore(original, &inverse);
             let source_path =
                 backup_root.join(path_from_backup_rel(&format!("{}/{}", drive_key, rel)));
-            let target_path = PathBuf::from(original.replace('/', "\\"));
+            let source_path = long_path(&source_path);
+            let target_path = long_path(&PathBuf::from(original.replace('/', "\\")));
             if let Some(parent) = target_path.parent() {
                 fs::create_dir_all(parent).map_err(|e| e.to_string())?;
             }
             if source_path.exists() {
-                fs::copy(&source_path, &target_path).map_err(|e| e.to_string())?;
+                restore_copy_result(self.copy_file(&source_path, &target_path))?;
             }
         }
 
         Ok(())
     }
 
+    /// Rebuilds the manifest of a directory backup whose manifest is missing or unreadable
+    /// by walking `files/<label>/...`. Labels are resolved through `roots` (the game's
+    /// current save roots, keyed by label) or, for drive labels such as `drive-C`, to the
+    /// drive root. A corrupted manifest is kept next to the new one as `<name>.corrupt`.
+    pub fn repair_directory_manifest(
+        &self,
+        backup_root: &Path,
+        roots: &HashMap<String, PathBuf>,
+        tokens: &PathTokens,
+    ) -> Result<ManifestRepair, String> {
+        if !backup_root.is_dir() {
+            return Err("Восстановить манифест можно только для бэкапа-папки".to_string());
+        }
+        if let Ok(Some(manifest)) = read_manifest_from_dir(backup_root) {
+            return Ok(ManifestRepair {
+                rebuilt: false,
+                files: manifest.files.len(),
+                unresolved_roots: Vec::new(),
+            });
+        }
+        let files_dir = backup_root.join("files");
+        if !files_dir.is_dir() {
+            return Err("В бэкапе нет папки files, восстановить манифест невозможно".to_string());
+        }
+
+        let mut labels: Vec<(String, PathBuf)> = fs::read_dir(&files_dir)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| {
+                (
+                    entry.file_name().to_string_lossy().to_string(),
+                    entry.path(),
+                )
+            })
+            .collect();
+        labels.sort();
+
+        let mut entries = Vec::new();
+        let mut unresolved_roots = Vec::new();
+        for (label, label_dir) in labels {
+            let Some(root) = roots
+                .get(&label)
+                .cloned()
+                .or_else(|| drive_label_root(&label))
+            else {
+                unresolved_roots.push(label);
+                continue;
+            };
+            for file in WalkDir::new(&label_dir)
+                .sort_by_file_name()
+                .into_iter()
+                .flatten()
+                .filter(|entry| entry.file_type().is_file())
+            {
+                let relative = file
+                    .path()
+                    .strip_prefix(&label_dir)
+                    .map_err(|e| e.to_string())?;
+                // A root that was a single file is stored as `files/<label>/file`.
+                let original = if relative == Path::new("file") && root.extension().is_some() {
+                    root.clone()
+                } else {
+                    root.join(relative)
+                };
+                let metadata = file.metadata().map_err(|e| e.to_string())?;
+                entries.push(BackupFileEntry {
+                    backup_path: build_backup_rel_path(&label, relative),
+                    original_path: original.to_string_lossy().to_string(),
+                    portable_path: tokens.tokenise(&original),
+                    link_target: None,
+                    size: metadata.len(),
+                    mtime: metadata
+                        .modified()
+                        .ok()
+                        .and_then(system_time_to_epoch_seconds),
+                });
+            }
+        }
+
+        for name in BACKUP_MANIFEST_NAMES {
+            let path = backup_root.join(name);
+            if path.exists() {
+                fs::rename(&path, backup_root.join(format!("{}.corrupt", name)))
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        self.write_manifest_to_dir(backup_root, &build_manifest(&entries, Vec::new(), None))?;
+        if !backup_root.join(SQOBA_README_NAME).exists() {
+            self.write_readme_to_dir(backup_root)?;
+        }
+
+        Ok(ManifestRepair {
+            rebuilt: true,
+            files: entries.len(),
+            unresolved_roots,
+        })
+    }
+
     pub fn suggest_games(&self, name: &str, limit: usize) -> Vec<String> {
         self.manifest
             .as_ref()
@@ -654,12 +1172,184 @@ impl BackupEngine {
 struct BackupSourceFile {
     path: PathBuf,
     backup_path: String,
+    portable_path: Option<String>,
+    link_target: Option<PathBuf>,
+}
+
+impl BackupSourceFile {
+    fn link_entry(&self) -> Option<BackupFileEntry> {
+        let target = self.link_target.as_ref()?;
+        Some(BackupFileEntry {
+            backup_path: self.backup_path.clone(),
+            original_path: self.path.to_string_lossy().to_string(),
+            portable_path: self.portable_path.clone(),
+            link_target: Some(target.to_string_lossy().to_string()),
+            size: 0,
+            mtime: None,
+        })
+    }
+}
+
+/// Recreates a link stored with `SymlinkPolicy::StoreAsLink`. Existing paths are left alone,
+/// and failures (e.g. missing symlink privilege on Windows) are logged rather than aborting
+/// the rest of the restore.
+fn restore_link(link_path: &Path, entry: &BackupFileEntry) {
+    let Some(target) = entry.link_target.as_deref() else {
+        return;
+    };
+    if fs::symlink_metadata(link_path).is_ok() {
+        return;
+    }
+    if let Some(parent) = link_path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Err(e) = create_symlink(Path::new(target), link_path) {
+        eprintln!(
+            "Failed to restore link {} -> {}: {}",
+            link_path.display(),
+            target,
+            e
+        );
+    }
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link_path: &Path) -> std::io::Result<()> {
+    let resolved = link_path
+        .parent()
+        .map(|parent| parent.join(target))
+        .unwrap_or_else(|| target.to_path_buf());
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link_path)
+    } else {
+        std::os::windows::fs::symlink_file(target, link_path)
+    }
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link_path: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link_path)
+}
+
+fn tuner_max_threads(initial: usize) -> usize {
+    let cpu_count = num_cpus::get().clamp(1, 16);
+    cpu_count.max(initial.max(1))
 }
 
-fn build_manifest(entries: &[BackupFileEntry]) -> BackupArchiveManifest {
+fn build_manifest(
+    entries: &[BackupFileEntry],
+    skipped: Vec<SkippedFile>,
+    metadata: Option<BackupMetadata>,
+) -> BackupArchiveManifest {
     BackupArchiveManifest {
         version: MANIFEST_VERSION,
         files: entries.to_vec(),
+        skipped,
+        metadata,
+    }
+}
+
+enum CopiedFile {
+    Stored(BackupFileEntry),
+    Skipped(SkippedFile),
+}
+
+/// Fails with the shortage (and a `no-space` progress stage) when `needs` would not fit
+/// on the target volumes.
+fn check_free_space(
+    needs: impl IntoIterator<Item = (PathBuf, u64)>,
+    progress: Option<&Arc<dyn Fn(BackupProgress) + Send + Sync>>,
+) -> Result<(), BackupError> {
+    ensure_free_space(needs).map_err(|shortage| {
+        if let Some(cb) = progress {
+            cb(BackupProgress {
+                stage: "no-space",
+                current: shortage.to_string(),
+                done: 0,
+                total: 0,
+            });
+        }
+        BackupError::NoSpace(shortage)
+    })
+}
+
+/// Bytes each restored file adds on its target volume; files being overwritten only
+/// need the difference.
+fn restore_space_needs(
+    entries: &[BackupFileEntry],
+    remapper: &RestorePathRemapper,
+) -> Vec<(PathBuf, u64)> {
+    entries
+        .iter()
+        .filter(|entry| entry.link_target.is_none())
+        .map(|entry| {
+            let target = remapper.remap(entry);
+            let existing = fs::metadata(long_path(&target))
+                .map(|meta| meta.len())
+                .unwrap_or(0);
+            (target, entry.size.saturating_sub(existing))
+        })
+        .collect()
+}
+
+fn skipped_file(path: &Path, reason: String) -> SkippedFile {
+    SkippedFile {
+        original_path: path.to_string_lossy().to_string(),
+        reason,
+    }
+}
+
+/// Drops container folders the restored WGS index no longer references.
+fn prune_restored_containers(index_path: &Path) {
+    if let Err(e) = wgs::prune_stale_containers(index_path) {
+        eprintln!(
+            "Failed to prune WGS containers next to {}: {}",
+            index_path.display(),
+            e
+        );
+    }
+}
+
+/// A file skipped from the stall prompt is left out of the restore; cancelling aborts it.
+fn restore_copy_result(result: Result<u64, CopyInterrupted>) -> Result<(), String> {
+    match result {
+        Ok(_) | Err(CopyInterrupted::Skipped) => Ok(()),
+        Err(CopyInterrupted::Cancelled) => Err(CANCELLED_MESSAGE.to_string()),
+        Err(CopyInterrupted::Io(e)) => Err(e.to_string()),
+    }
+}
+
+/// Opens a source file for archiving, falling back to a shadow copy when it is locked.
+/// `Err` carries the reason the file has to be skipped.
+fn open_source(path: &Path, shadows: &ShadowCopies) -> Result<File, String> {
+    match File::open(long_path(path)) {
+        Ok(file) => Ok(file),
+        Err(e) if is_lock_error(&e) => match shadows.snapshot_path(path) {
+            Some(snapshot) => File::open(snapshot).map_err(|e| e.to_string()),
+            None => Err(e.to_string()),
+        },
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Skipped files are surfaced as a `skipped` stage in `backup:progress` so the UI can warn.
+fn report_skipped(
+    progress: &Option<Arc<dyn Fn(BackupProgress) + Send + Sync>>,
+    file: &SkippedFile,
+    done: usize,
+    total: usize,
+) {
+    eprintln!(
+        "Skipping locked file {}: {}",
+        file.original_path, file.reason
+    );
+    if let Some(cb) = progress {
+        cb(BackupProgress {
+            stage: "skipped",
+            current: file.original_path.clone(),
+            done,
+            total,
+        });
     }
 }
 
@@ -716,6 +1406,38 @@ To restore manually:\n\
     )
 }
 
+/// Archive comment shown by file managers and `unzip -z`, so a `.sqoba.zip` explains
+/// itself without arrancador installed.
+fn backup_archive_comment(
+    game_name: &str,
+    created: &str,
+    file_count: usize,
+    skipped_count: usize,
+) -> String {
+    let skipped = if skipped_count > 0 {
+        format!(" ({} skipped: locked during backup)", skipped_count)
+    } else {
+        String::new()
+    };
+    format!(
+        "SQOBA save backup made by arrancador {}\n\
+Game: {}\n\
+Created: {}\n\
+Files: {}{}\n\
+\n\
+Restore in arrancador: open the game and pick this backup.\n\
+Restore by hand: copy each files/<path> entry back to its original_path from {} \
+(see {}).\n",
+        env!("CARGO_PKG_VERSION"),
+        game_name,
+        created,
+        file_count,
+        skipped,
+        SQOBA_MANIFEST_NAME,
+        SQOBA_README_NAME
+    )
+}
+
 fn file_mtime(path: &Path) -> Option<i64> {
     fs::metadata(path)
         .ok()
@@ -729,6 +1451,76 @@ fn system_time_to_epoch_seconds(time: SystemTime) -> Option<i64> {
         .map(|duration| duration.as_secs() as i64)
 }
 
+fn load_benchmark_sample(
+    paths: &[PathBuf],
+    sample_bytes: u64,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let mut sample = Vec::new();
+    let mut total = 0u64;
+    for (index, path) in paths.iter().enumerate() {
+        let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if !sample.is_empty() && total + size > sample_bytes {
+            continue;
+        }
+        let mut data = Vec::new();
+        File::open(path)
+            .and_then(|file| file.take(sample_bytes).read_to_end(&mut data))
+            .map_err(|e| e.to_string())?;
+        total += data.len() as u64;
+        sample.push((format!("sample/{}", index), data));
+        if total >= sample_bytes {
+            break;
+        }
+    }
+    Ok(sample)
+}
+
+fn benchmark_files(
+    paths: &[PathBuf],
+    levels: &[u8],
+    sample_bytes: u64,
+) -> Result<Vec<CompressionBenchmark>, String> {
+    let sample = load_benchmark_sample(paths, sample_bytes)?;
+    if sample.is_empty() {
+        return Err("Нет файлов для замера сжатия".to_string());
+    }
+    let original_bytes: u64 = sample.iter().map(|(_, data)| data.len() as u64).sum();
+
+    let mut results = Vec::with_capacity(levels.len());
+    for &level in levels {
+        let level = level.clamp(1, 100);
+        let options = zip_data_options(level, ZipCompression::Zstd);
+        let started = std::time::Instant::now();
+        let mut archive = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, data) in &sample {
+            archive
+                .start_file(name.as_str(), options)
+                .map_err(|e| e.to_string())?;
+            archive.write_all(data).map_err(|e| e.to_string())?;
+        }
+        let compressed_bytes = archive
+            .finish()
+            .map_err(|e| e.to_string())?
+            .into_inner()
+            .len() as u64;
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        results.push(CompressionBenchmark {
+            level,
+            sample_files: sample.len(),
+            original_bytes,
+            compressed_bytes,
+            ratio: if original_bytes == 0 {
+                1.0
+            } else {
+                compressed_bytes as f64 / original_bytes as f64
+            },
+            duration_ms,
+        });
+    }
+    Ok(results)
+}
+
 fn zip_data_options(level: u8, compression: ZipCompression) -> FileOptions<'static, ()> {
     let method = match compression {
         ZipCompression::Deflate => CompressionMethod::Deflated,
@@ -773,6 +1565,36 @@ fn path_from_backup_rel(rel: &str) -> PathBuf {
     out
 }
 
+/// Windows only accepts paths longer than `MAX_PATH` (260 chars) in the `\\?\` extended-length
+/// form, which also disables Win32 normalisation, so the path is made absolute first.
+/// Everywhere else the path is returned unchanged.
+#[cfg(windows)]
+pub(crate) fn long_path(path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    PathBuf::from(extended_length_path(&absolute.to_string_lossy()))
+}
+
+#[cfg(not(windows))]
+pub(crate) fn long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+#[cfg_attr(not(any(windows, test)), allow(dead_code))]
+fn extended_length_path(path: &str) -> String {
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return path.to_string();
+    }
+    let path = path.replace('/', "\\");
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return format!(r"\\?\UNC\{}", share);
+    }
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\' {
+        return format!(r"\\?\{}", path);
+    }
+    path
+}
+
 fn split_drive_for_restore(
     original: &str,
     inverse_drives: &HashMap<String, String>,
@@ -789,6 +1611,18 @@ fn split_drive_for_restore(
     ("drive-0".to_string(), original.replace('\\', "/"))
 }
 
+/// `C:\` for labels naming a drive (`C`, `drive-C`, as written by ludusavi-style layouts).
+fn drive_label_root(label: &str) -> Option<PathBuf> {
+    let letter = label.strip_prefix("drive-").unwrap_or(label);
+    let mut chars = letter.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => {
+            Some(PathBuf::from(format!("{}:\\", c.to_ascii_uppercase())))
+        }
+        _ => None,
+    }
+}
+
 fn build_backup_rel_path(root: &str, relative: &Path) -> String {
     let rel = relative.to_string_lossy().replace('\\', "/");
     let rel = rel.trim_start_matches('/');
@@ -838,6 +1672,7 @@ mod tests {
 
         let engine = BackupEngine {
             manifest: Some(SqobaManifest::from_games(games)),
+            ..BackupEngine::new()
         };
 
         let backup_path = dir.path().join("backup");
@@ -870,6 +1705,134 @@ mod tests {
         assert_eq!(restored_b, b"beta");
     }
 
+    #[test]
+    fn repair_rebuilds_a_corrupted_directory_manifest() {
+        let dir = tempdir().expect("tempdir");
+        let save_dir = dir.path().join("saves");
+        fs::create_dir_all(save_dir.join("profiles")).expect("mkdirs");
+        fs::write(save_dir.join("slot1.sav"), b"alpha").expect("write slot1");
+        fs::write(save_dir.join("profiles").join("p1.cfg"), b"beta").expect("write p1");
+
+        let engine = BackupEngine::new();
+        let save_path = save_dir.to_string_lossy().to_string();
+        let lookup = SaveLookup {
+            override_path: Some(&save_path),
+            extra_roots: &[],
+            game_dir: None,
+            manifest_override: None,
+            manifest_key: None,
+        };
+        let backup_path = dir.path().join("backup");
+        engine
+            .backup_game_with_options_and_progress(
+                "Repair Game",
+                &backup_path,
+                1,
+                BackupOptions::directory(),
+                lookup,
+                None,
+            )
+            .expect("backup");
+        let tokens = PathTokens::for_game(None);
+        let roots: HashMap<String, PathBuf> = engine
+            .discover_game_save_roots("Repair Game", lookup)
+            .expect("roots")
+            .into_iter()
+            .map(|root| (root.label, root.path))
+            .collect();
+
+        let intact = engine
+            .repair_directory_manifest(&backup_path, &roots, &tokens)
+            .expect("check intact");
+        assert!(!intact.rebuilt);
+
+        fs::write(backup_path.join(SQOBA_MANIFEST_NAME), b"{ not json").expect("corrupt");
+        fs::create_dir_all(backup_path.join("files").join("unknown-root")).expect("mkdir");
+        let repair = engine
+            .repair_directory_manifest(&backup_path, &roots, &tokens)
+            .expect("repair");
+        assert!(repair.rebuilt);
+        assert_eq!(repair.files, 2);
+        assert_eq!(repair.unresolved_roots, vec!["unknown-root".to_string()]);
+        assert!(backup_path
+            .join(format!("{}.corrupt", SQOBA_MANIFEST_NAME))
+            .exists());
+
+        fs::remove_dir_all(&save_dir).expect("remove saves");
+        engine.restore_backup(&backup_path).expect("restore");
+        assert_eq!(
+            fs::read(save_dir.join("slot1.sav")).expect("slot1"),
+            b"alpha"
+        );
+        assert_eq!(
+            fs::read(save_dir.join("profiles").join("p1.cfg")).expect("p1"),
+            b"beta"
+        );
+    }
+
+    #[test]
+    fn extended_length_path_prefixes_drive_and_unc_paths() {
+        assert_eq!(
+            extended_length_path("C:/Users/Player/AppData"),
+            r"\\?\C:\Users\Player\AppData"
+        );
+        assert_eq!(
+            extended_length_path(r"\\nas\saves\game"),
+            r"\\?\UNC\nas\saves\game"
+        );
+        assert_eq!(extended_length_path(r"\\?\D:\x"), r"\\?\D:\x");
+        assert_eq!(extended_length_path("relative/path"), r"relative\path");
+    }
+
+    #[test]
+    fn backup_and_restore_paths_longer_than_max_path() {
+        let dir = tempdir().expect("tempdir");
+        let save_dir = dir.path().join("saves");
+        let mut deep_dir = save_dir.clone();
+        for index in 0..12 {
+            deep_dir.push(format!("UnityPlayerPrefsSegment_{:02}", index));
+        }
+        fs::create_dir_all(long_path(&deep_dir)).expect("create deep dir");
+        let deep_file = deep_dir.join("persistent_data_slot.sav");
+        assert!(deep_file.to_string_lossy().len() > 300);
+        fs::write(long_path(&deep_file), b"deep").expect("write deep file");
+
+        let save_path = save_dir.to_string_lossy().to_string();
+        let lookup = SaveLookup {
+            override_path: Some(&save_path),
+            extra_roots: &[],
+            game_dir: None,
+            manifest_override: None,
+            manifest_key: None,
+        };
+        let engine = BackupEngine::new();
+        for (name, options) in [
+            ("deep-dir", BackupOptions::directory()),
+            ("deep.sqoba.zip", BackupOptions::zip(10)),
+        ] {
+            let backup_path = dir.path().join(name);
+            engine
+                .backup_game_with_options_and_progress(
+                    "Deep Game",
+                    &backup_path,
+                    2,
+                    options,
+                    lookup,
+                    None,
+                )
+                .expect("backup deep save");
+
+            fs::remove_file(long_path(&deep_file)).expect("remove deep file");
+            engine
+                .restore_backup(&backup_path)
+                .expect("restore deep save");
+            assert_eq!(
+                fs::read(long_path(&deep_file)).expect("read restored"),
+                b"deep"
+            );
+        }
+    }
+
     #[test]
     fn zip_backup_and_restore_roundtrip() {
         let dir = tempdir().expect("tempdir");
@@ -901,8 +1864,15 @@ mod tests {
             },
         );
 
+        let metadata = BackupMetadata {
+            playtime_seconds: 5400,
+            last_session_seconds: Some(1800),
+            label: Some("Before the final boss".to_string()),
+        };
         let engine = BackupEngine {
             manifest: Some(SqobaManifest::from_games(games)),
+            metadata: Some(metadata.clone()),
+            ..BackupEngine::new()
         };
 
         let backup_path = dir.path().join("backup.sqoba.zip");
@@ -917,6 +1887,17 @@ mod tests {
 
         assert!(total_size > 0);
         assert!(backup_path.exists());
+        let archive =
+            ZipArchive::new(File::open(&backup_path).expect("open archive")).expect("read zip");
+        let comment = String::from_utf8_lossy(archive.comment()).to_string();
+        assert!(comment.contains("Game: Test Game"));
+        assert!(comment.contains("Files: 2\n"));
+        assert!(comment.contains(SQOBA_MANIFEST_NAME));
+        drop(archive);
+        let stored = load_backup_manifest(&backup_path)
+            .expect("load manifest")
+            .expect("manifest present");
+        assert_eq!(stored.metadata, Some(metadata));
 
         fs::remove_file(&file_a).expect("remove file_a");
         fs::remove_file(&file_b).expect("remove file_b");
@@ -929,6 +1910,28 @@ mod tests {
         assert_eq!(restored_a, b"alpha");
         assert_eq!(restored_b, b"beta");
     }
+
+    #[test]
+    fn benchmark_files_reports_each_level_over_sample() {
+        let dir = tempdir().expect("tempdir");
+        let big = dir.path().join("big.sav");
+        let small = dir.path().join("small.sav");
+        fs::write(&big, "save-data ".repeat(4096)).expect("write big");
+        fs::write(&small, b"tiny").expect("write small");
+
+        let results =
+            benchmark_files(&[big.clone(), small], &[1, 100], 64 * 1024).expect("benchmark");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].level, 1);
+        assert_eq!(results[1].level, 100);
+        assert_eq!(results[0].sample_files, 2);
+        assert_eq!(results[0].original_bytes, 4096 * 10 + 4);
+        assert!(results[1].compressed_bytes < results[1].original_bytes);
+
+        let capped = benchmark_files(&[big], &[60], 1024).expect("capped");
+        assert_eq!(capped[0].original_bytes, 1024);
+        assert!(benchmark_files(&[], &[60], 1024).is_err());
+    }
 }
 
 #[cfg(test)]
@@ -972,6 +1975,7 @@ mod perf_bench {
 
         let engine = BackupEngine {
             manifest: Some(SqobaManifest::from_games(games)),
+            ..BackupEngine::new()
         };
 
         let backup_dir = dir.path().join("backup");
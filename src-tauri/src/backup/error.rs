@@ -0,0 +1,65 @@
+//! Errors of the backup and restore commands. They reach the UI as `{ kind, ... }` objects so
+//! a full disk can be shown with its numbers instead of a pre-formatted sentence.
+
+use super::disk_space::DiskSpaceShortage;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackupError {
+    /// The free-space pre-check failed before anything was copied.
+    NoSpace(DiskSpaceShortage),
+    /// Everything else: missing saves, FS and database errors.
+    Other { message: String },
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSpace(shortage) => write!(f, "{}", shortage),
+            Self::Other { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<String> for BackupError {
+    fn from(message: String) -> Self {
+        Self::Other { message }
+    }
+}
+
+impl From<&str> for BackupError {
+    fn from(message: &str) -> Self {
+        Self::Other {
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortage_reaches_the_ui_with_its_fields() {
+        let error = BackupError::NoSpace(DiskSpaceShortage {
+            mount_point: r"D:\".to_string(),
+            required_bytes: 3 * 1024 * 1024,
+            available_bytes: 1024 * 1024,
+        });
+        assert_eq!(
+            serde_json::to_value(&error).expect("serialize"),
+            serde_json::json!({
+                "kind": "no_space",
+                "mount_point": r"D:\",
+                "required_bytes": 3 * 1024 * 1024,
+                "available_bytes": 1024 * 1024,
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(BackupError::from("Сохранения не найдены")).expect("serialize"),
+            serde_json::json!({ "kind": "other", "message": "Сохранения не найдены" })
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Cold storage for rarely played games: a whole install directory is packed into one
+//! archive on the backup drive, checked against the originals and only then deleted.
+
+use crate::backup::engine::long_path;
+use crate::backup::verify::crc32_of;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+use zip::write::{FileOptions, ZipWriter};
+use zip::{CompressionMethod, ZipArchive};
+
+/// Fast zstd level: install folders are large and mostly already compressed assets.
+const ZSTD_LEVEL: i64 = 3;
+const PROGRESS_EVERY: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallArchive {
+    pub id: String,
+    pub game_id: String,
+    pub archive_path: String,
+    pub original_dir: String,
+    /// Executable relative to `original_dir`; relinks the game after unarchiving.
+    pub exe_relative: String,
+    pub file_count: usize,
+    pub original_bytes: u64,
+    pub archive_bytes: u64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    pub file_count: usize,
+    pub original_bytes: u64,
+}
+
+/// `(stage, done, total)`
+pub type ArchiveProgress<'a> = &'a dyn Fn(&'static str, usize, usize);
+
+fn entry_name(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Packs every file and (empty) directory under `source`. Unreadable entries and symlinks
+/// fail the archive: a link is never followed, and leaving it out would lose it once the
+/// folder is deleted.
+pub fn archive_directory(
+    source: &Path,
+    archive_path: &Path,
+    progress: ArchiveProgress,
+) -> Result<ArchiveSummary, String> {
+    let entries: Vec<walkdir::DirEntry> = WalkDir::new(source)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Не удалось прочитать папку установки: {}", e))?;
+    if let Some(link) = entries.iter().find(|entry| entry.path_is_symlink()) {
+        return Err(format!(
+            "В папке установки есть ссылка, её нельзя заархивировать: {}",
+            link.path().display()
+        ));
+    }
+    let total = entries.iter().filter(|e| e.file_type().is_file()).count();
+
+    if let Some(parent) = archive_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let file = File::create(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = ZipWriter::new(BufWriter::new(file));
+    let options: FileOptions<'static, ()> = FileOptions::default()
+        .compression_method(CompressionMethod::Zstd)
+        .compression_level(Some(ZSTD_LEVEL));
+
+    let mut summary = ArchiveSummary {
+        file_count: 0,
+        original_bytes: 0,
+    };
+    for entry in entries {
+        let name = entry_name(source, entry.path());
+        if entry.file_type().is_dir() {
+            archive
+                .add_directory(format!("{}/", name), options)
+                .map_err(|e| e.to_string())?;
+            continue;
+        }
+        let mut reader = File::open(long_path(entry.path())).map_err(|e| e.to_string())?;
+        let size = reader.metadata().map_err(|e| e.to_string())?.len();
+        archive
+            .start_file(name, options.large_file(size >= u32::MAX as u64))
+            .map_err(|e| e.to_string())?;
+        io::copy(&mut reader, &mut archive).map_err(|e| e.to_string())?;
+        summary.file_count += 1;
+        summary.original_bytes += size;
+        if summary.file_count == total || summary.file_count.is_multiple_of(PROGRESS_EVERY) {
+            progress("archive", summary.file_count, total);
+        }
+    }
+    archive.finish().map_err(|e| e.to_string())?;
+    Ok(summary)
+}
+
+/// Decompresses every entry (the zip reader checks its CRC) and compares it with the file it
+/// was made from. The originals are only deleted after this passes.
+pub fn verify_archive(
+    source: &Path,
+    archive_path: &Path,
+    expected: ArchiveSummary,
+    progress: ArchiveProgress,
+) -> Result<(), String> {
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut checked = 0;
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index).map_err(|e| e.to_string())?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let fail = |reason: &str| format!("Проверка архива не пройдена для {}: {}", name, reason);
+        let original = source.join(
+            entry
+                .enclosed_name()
+                .ok_or_else(|| fail("недопустимый путь"))?,
+        );
+        let stored_crc = entry.crc32();
+        let unpacked_crc = crc32_of(entry).map_err(|e| fail(&e))?;
+        let source_crc = File::open(long_path(&original))
+            .map_err(|e| e.to_string())
+            .and_then(crc32_of)
+            .map_err(|e| fail(&e))?;
+        if unpacked_crc != stored_crc || source_crc != stored_crc {
+            return Err(fail("содержимое не совпадает с исходным файлом"));
+        }
+        checked += 1;
+        if checked == expected.file_count || checked.is_multiple_of(PROGRESS_EVERY) {
+            progress("verify", checked, expected.file_count);
+        }
+    }
+    if checked != expected.file_count {
+        return Err(format!(
+            "Проверка архива не пройдена: {} файлов вместо {}",
+            checked, expected.file_count
+        ));
+    }
+    Ok(())
+}
+
+/// Files (and links) under `source` right now, to compare with what was archived before the
+/// folder is deleted.
+pub fn count_files_on_disk(source: &Path) -> Result<usize, String> {
+    WalkDir::new(source)
+        .min_depth(1)
+        .into_iter()
+        .try_fold(0, |count, entry| {
+            let entry =
+                entry.map_err(|e| format!("Не удалось прочитать папку установки: {}", e))?;
+            Ok(count + usize::from(!entry.file_type().is_dir()))
+        })
+}
+
+fn path_key(path: &Path) -> String {
+    let key = path.to_string_lossy().replace('\\', "/").to_lowercase();
+    format!("{}/", key.trim_end_matches('/'))
+}
+
+/// Refuses install folders whose deletion would take more than this game with it: a drive
+/// root, or a folder holding another library game's executable (`other_exe_paths`).
+pub fn check_install_dir(install_dir: &Path, other_exe_paths: &[String]) -> Result<(), String> {
+    if install_dir.parent().is_none() {
+        return Err(format!(
+            "Нельзя архивировать корень диска: {}",
+            install_dir.display()
+        ));
+    }
+    if install_dir
+        .components()
+        .any(|part| part == Component::ParentDir)
+    {
+        return Err(format!(
+            "Укажите папку установки без «..»: {}",
+            install_dir.display()
+        ));
+    }
+    let dir = path_key(install_dir);
+    match other_exe_paths
+        .iter()
+        .find(|exe| path_key(Path::new(exe)).starts_with(&dir))
+    {
+        Some(exe) => Err(format!(
+            "В папке установки есть другая игра из библиотеки: {}",
+            exe
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Unpacks into `target`, which must be missing or empty so nothing gets overwritten.
+pub fn extract_archive(
+    archive_path: &Path,
+    target: &Path,
+    progress: ArchiveProgress,
+) -> Result<usize, String> {
+    if fs::read_dir(target).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(format!("Папка назначения не пуста: {}", target.display()));
+    }
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let total = (0..archive.len())
+        .filter_map(|i| archive.by_index_raw(i).ok().map(|e| !e.is_dir()))
+        .filter(|is_file| *is_file)
+        .count();
+    fs::create_dir_all(target).map_err(|e| e.to_string())?;
+
+    let mut extracted = 0;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(|e| e.to_string())?;
+        let relative: PathBuf = entry
+            .enclosed_name()
+            .ok_or_else(|| format!("Недопустимый путь в архиве: {}", entry.name()))?;
+        let path = long_path(&target.join(relative));
+        if entry.is_dir() {
+            fs::create_dir_all(&path).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = File::create(&path).map_err(|e| e.to_string())?;
+        io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        extracted += 1;
+        if extracted == total || extracted.is_multiple_of(PROGRESS_EVERY) {
+            progress("extract", extracted, total);
+        }
+    }
+    Ok(extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn install_folder_roundtrips_and_verification_catches_changes() {
+        let dir = tempdir().expect("tempdir");
+        let install = dir.path().join("Arcadia");
+        fs::create_dir_all(install.join("bin")).expect("create bin");
+        fs::create_dir_all(install.join("mods")).expect("create empty dir");
+        fs::write(install.join("bin").join("arcadia.exe"), b"MZ binary").expect("write exe");
+        fs::write(install.join("data.pak"), vec![42u8; 300_000]).expect("write pak");
+        let archive_path = dir.path().join("installs").join("Arcadia.zip");
+        let noop = |_: &'static str, _: usize, _: usize| {};
+
+        let summary = archive_directory(&install, &archive_path, &noop).expect("archive");
+        assert_eq!(summary.file_count, 2);
+        assert_eq!(summary.original_bytes, 300_009);
+        verify_archive(&install, &archive_path, summary, &noop).expect("verify");
+
+        fs::write(install.join("data.pak"), vec![7u8; 300_000]).expect("modify pak");
+        assert!(verify_archive(&install, &archive_path, summary, &noop).is_err());
+
+        assert!(extract_archive(&archive_path, &install, &noop).is_err());
+        let restored = dir.path().join("D").join("Arcadia");
+        assert_eq!(
+            extract_archive(&archive_path, &restored, &noop).expect("extract"),
+            2
+        );
+        assert_eq!(
+            fs::read(restored.join("bin").join("arcadia.exe")).expect("read exe"),
+            b"MZ binary"
+        );
+        assert_eq!(
+            fs::read(restored.join("data.pak")).expect("read pak"),
+            vec![42u8; 300_000]
+        );
+        assert!(restored.join("mods").is_dir());
+    }
+
+    #[test]
+    fn unarchivable_entries_and_unsafe_install_dirs_are_refused() {
+        let dir = tempdir().expect("tempdir");
+        let install = dir.path().join("Games").join("Arcadia");
+        fs::create_dir_all(&install).expect("create install");
+        fs::write(install.join("arcadia.exe"), b"MZ").expect("write exe");
+        assert_eq!(count_files_on_disk(&install), Ok(1));
+        fs::write(install.join("new.log"), b"late").expect("write log");
+        assert_eq!(count_files_on_disk(&install), Ok(2));
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(dir.path(), install.join("link")).expect("symlink");
+            let noop = |_: &'static str, _: usize, _: usize| {};
+            let archive_path = dir.path().join("Arcadia.zip");
+            assert!(archive_directory(&install, &archive_path, &noop).is_err());
+        }
+
+        let games = install.parent().expect("games dir");
+        let others = vec![games
+            .join("Celeste")
+            .join("celeste.exe")
+            .to_string_lossy()
+            .to_string()];
+        assert!(check_install_dir(&install, &others).is_ok());
+        assert!(check_install_dir(games, &others).is_err());
+        assert!(check_install_dir(&install.join(".."), &[]).is_err());
+        assert!(check_install_dir(Path::new("/"), &[]).is_err());
+        assert!(check_install_dir(
+            Path::new("C:\\Games"),
+            &["c:/games/celeste/celeste.exe".to_string()]
+        )
+        .is_err());
+        assert!(check_install_dir(
+            Path::new("C:\\Games\\Arcadia"),
+            &["C:\\Games\\Arcadia2\\game.exe".to_string()]
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,192 @@
+//! Last-resort save discovery for games that keep saves next to their executable or under
+//! a publisher folder the manifest doesn't know about. Everything found here is a guess and
+//! is reported as low confidence.
+
+use crate::services::exe_info;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Folder names that hold saves or per-user config in many indie and GOG titles.
+const SAVE_DIR_NAMES: [&str; 10] = [
+    "save",
+    "saves",
+    "savegame",
+    "savegames",
+    "saved",
+    "savedata",
+    "saved games",
+    "profile",
+    "profiles",
+    "userdata",
+];
+const SAVE_EXTENSIONS: [&str; 5] = ["sav", "save", "sl2", "ess", "savegame"];
+/// A folder needs this many save-looking files before it counts as a save folder.
+const MIN_SAVE_CLUSTER: usize = 2;
+const MAX_DEPTH: usize = 4;
+/// Installs can hold hundreds of thousands of files; the scan stops after this many entries.
+const MAX_SCANNED_ENTRIES: usize = 20_000;
+
+fn is_save_dir_name(name: &str) -> bool {
+    SAVE_DIR_NAMES.contains(&name.to_lowercase().as_str())
+}
+
+fn is_save_file(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| SAVE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Save-named subfolders of the install and folders holding a cluster of `*.sav`-like
+/// files. Folders inside one already found are left out.
+pub fn install_dir_roots(game_dir: &Path) -> Vec<PathBuf> {
+    if !game_dir.is_dir() {
+        return Vec::new();
+    }
+    let mut named = Vec::new();
+    let mut clusters: Vec<(PathBuf, usize)> = Vec::new();
+    for entry in WalkDir::new(game_dir)
+        .min_depth(1)
+        .max_depth(MAX_DEPTH)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .take(MAX_SCANNED_ENTRIES)
+    {
+        let path = entry.path();
+        if entry.file_type().is_dir() {
+            if is_save_dir_name(&entry.file_name().to_string_lossy()) {
+                named.push(path.to_path_buf());
+            }
+        } else if is_save_file(path) {
+            let Some(parent) = path.parent() else {
+                continue;
+            };
+            match clusters.iter_mut().find(|(dir, _)| dir == parent) {
+                Some((_, count)) => *count += 1,
+                None => clusters.push((parent.to_path_buf(), 1)),
+            }
+        }
+    }
+
+    let mut roots: Vec<PathBuf> = named
+        .into_iter()
+        .chain(
+            clusters
+                .into_iter()
+                .filter(|(dir, count)| *count >= MIN_SAVE_CLUSTER && dir != game_dir)
+                .map(|(dir, _)| dir),
+        )
+        .collect();
+    roots.sort_by_key(|path| path.components().count());
+    let mut out: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        if !out.iter().any(|kept| root.starts_with(kept)) {
+            out.push(root);
+        }
+    }
+    out
+}
+
+/// `(company, product)` from the Unity player data next to the executable. Unity keeps
+/// saves under `LocalLow/<company>/<product>`.
+fn unity_app_info(game_dir: &Path) -> Option<(String, String)> {
+    let entries = fs::read_dir(game_dir).ok()?;
+    entries.flatten().find_map(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.ends_with("_Data") {
+            return None;
+        }
+        let text = fs::read_to_string(entry.path().join("app.info")).ok()?;
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        Some((lines.next()?.to_string(), lines.next()?.to_string()))
+    })
+}
+
+/// Company and product names from the version resource of the executables in `game_dir`.
+fn exe_publisher_names(game_dir: &Path) -> Vec<(Option<String>, String)> {
+    let Ok(entries) = fs::read_dir(game_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+        })
+        .filter_map(|exe| exe_version_names(&exe))
+        .collect()
+}
+
+/// `<base>/<company>/<product>` and `<base>/<product>` folders that exist, for the names
+/// found in Unity player data and in the executables' version resources.
+pub fn publisher_roots(game_dir: &Path, bases: &[&Path]) -> Vec<PathBuf> {
+    let mut names: Vec<(Option<String>, String)> = unity_app_info(game_dir)
+        .map(|(company, product)| (Some(company), product))
+        .into_iter()
+        .collect();
+    names.extend(exe_publisher_names(game_dir));
+
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for (company, product) in names {
+        for base in bases {
+            let candidates = company
+                .as_ref()
+                .map(|company| base.join(company).join(&product))
+                .into_iter()
+                .chain(std::iter::once(base.join(&product)));
+            for path in candidates {
+                if path.is_dir() && seen.insert(path.clone()) {
+                    out.push(path);
+                }
+            }
+        }
+    }
+    out
+}
+
+fn exe_version_names(exe: &Path) -> Option<(Option<String>, String)> {
+    let info = exe_info::version_info(exe)?;
+    Some((info.company_name, info.product_name?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn install_scan_finds_save_folders_and_unity_publisher_roots() {
+        let dir = tempdir().expect("tempdir");
+        let game = dir.path().join("Game");
+        for folder in ["Saves/slot1", "Data/levels", "Data/profiles", "bin/user"] {
+            fs::create_dir_all(game.join(folder)).expect("create folder");
+        }
+        for file in ["bin/user/a.sav", "bin/user/b.SAV", "Data/levels/one.sav"] {
+            fs::write(game.join(file), b"save").expect("write save");
+        }
+
+        let mut roots = install_dir_roots(&game);
+        roots.sort();
+        assert_eq!(
+            roots,
+            vec![
+                game.join("Data/profiles"),
+                game.join("Saves"),
+                game.join("bin/user"),
+            ]
+        );
+        assert!(install_dir_roots(&dir.path().join("missing")).is_empty());
+
+        fs::create_dir_all(game.join("Game_Data")).expect("create data dir");
+        fs::write(game.join("Game_Data/app.info"), "Studio\nGame\n").expect("write app.info");
+        let local_low = dir.path().join("LocalLow");
+        fs::create_dir_all(local_low.join("Studio/Game")).expect("create unity saves");
+        assert_eq!(
+            publisher_roots(&game, &[local_low.as_path()]),
+            vec![local_low.join("Studio/Game")]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! User-written manifest fragments for games the ludusavi manifest lacks or gets wrong.
+//! Save discovery merges the fragment over the upstream entry, so a missing game can be
+//! backed up without waiting for the manifest to catch up.
+
+use crate::backup::sqoba_manifest::SqobaGame;
+use serde::{Deserialize, Serialize};
+
+/// Manifest tag the fragment's paths are filed under.
+const OVERRIDE_TAG: &str = "save";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestOverride {
+    /// Path patterns in manifest syntax (`<winAppData>/Game/*.sav`, `<base>/saves`).
+    pub files: Vec<String>,
+    /// Registry keys, kept with the fragment for parity with manifest entries.
+    pub registry: Vec<String>,
+    /// Ignore the upstream entry instead of adding to it.
+    #[serde(default)]
+    pub replace_upstream: bool,
+}
+
+impl ManifestOverride {
+    /// Trims entries, unifies separators (`/` in paths as the manifest writes them, `\` in
+    /// registry keys) and drops blanks and duplicates; `None` when nothing is left to store.
+    pub fn normalized(self) -> Option<Self> {
+        let clean = |values: Vec<String>, from: char, to: &str| {
+            let mut out: Vec<String> = Vec::new();
+            for value in values {
+                let value = value.trim().replace(from, to);
+                if !value.is_empty() && !out.contains(&value) {
+                    out.push(value);
+                }
+            }
+            out
+        };
+        let files = clean(self.files, '\\', "/");
+        let registry = clean(self.registry, '/', "\\");
+        if files.is_empty() && registry.is_empty() {
+            return None;
+        }
+        Some(Self {
+            files,
+            registry,
+            replace_upstream: self.replace_upstream,
+        })
+    }
+}
+
+/// The entry save discovery should use: `base` with the override's paths and keys added,
+/// or the override alone when it replaces upstream or the game isn't in the manifest.
+pub fn merge_entry(
+    base: Option<SqobaGame>,
+    custom: Option<&ManifestOverride>,
+) -> Option<SqobaGame> {
+    let Some(custom) = custom else {
+        return base;
+    };
+    let base = base.filter(|_| !custom.replace_upstream);
+    let mut files = base
+        .as_ref()
+        .and_then(|entry| entry.files.clone())
+        .unwrap_or_default();
+    let mut registry = base.and_then(|entry| entry.registry).unwrap_or_default();
+
+    let tagged = files.entry(OVERRIDE_TAG.to_string()).or_default();
+    for path in &custom.files {
+        if !tagged.contains(path) {
+            tagged.push(path.clone());
+        }
+    }
+    files.retain(|_, paths| !paths.is_empty());
+    for key in &custom.registry {
+        if !registry.contains(key) {
+            registry.push(key.clone());
+        }
+    }
+
+    Some(SqobaGame {
+        files: (!files.is_empty()).then_some(files),
+        registry: (!registry.is_empty()).then_some(registry),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn paths(entry: &SqobaGame) -> HashMap<String, Vec<String>> {
+        entry.files.clone().unwrap_or_default()
+    }
+
+    fn upstream() -> SqobaGame {
+        let mut files = HashMap::new();
+        files.insert(
+            "save".to_string(),
+            vec!["<winDocuments>/My Games/Game".to_string()],
+        );
+        files.insert("config".to_string(), vec!["<winAppData>/Game".to_string()]);
+        SqobaGame {
+            files: Some(files),
+            registry: Some(vec!["HKEY_CURRENT_USER\\Software\\Game".to_string()]),
+        }
+    }
+
+    #[test]
+    fn override_adds_to_or_replaces_the_upstream_entry() {
+        let custom = ManifestOverride {
+            files: vec![
+                "  <winLocalAppData>\\Game\\Saved ".to_string(),
+                "".to_string(),
+                "<winLocalAppData>/Game/Saved".to_string(),
+            ],
+            registry: vec!["HKEY_CURRENT_USER/Software/Game".to_string()],
+            replace_upstream: false,
+        }
+        .normalized()
+        .expect("override kept");
+        assert_eq!(custom.files, vec!["<winLocalAppData>/Game/Saved"]);
+        assert_eq!(custom.registry, vec!["HKEY_CURRENT_USER\\Software\\Game"]);
+
+        let merged = merge_entry(Some(upstream()), Some(&custom)).expect("merged entry");
+        let files = paths(&merged);
+        assert_eq!(
+            files["save"],
+            vec![
+                "<winDocuments>/My Games/Game",
+                "<winLocalAppData>/Game/Saved"
+            ]
+        );
+        assert_eq!(files["config"], vec!["<winAppData>/Game"]);
+        assert_eq!(merged.registry.as_ref().map(Vec::len), Some(1));
+
+        let replacing = ManifestOverride {
+            replace_upstream: true,
+            ..custom.clone()
+        };
+        let replaced = merge_entry(Some(upstream()), Some(&replacing)).expect("replaced entry");
+        assert_eq!(paths(&replaced).len(), 1);
+        assert_eq!(
+            paths(&replaced)["save"],
+            vec!["<winLocalAppData>/Game/Saved"]
+        );
+
+        assert!(merge_entry(None, Some(&custom)).is_some());
+        assert!(merge_entry(None, None).is_none());
+        assert_eq!(ManifestOverride::default().normalized(), None);
+    }
+}
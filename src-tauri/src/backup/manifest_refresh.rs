@@ -0,0 +1,84 @@
+//! Keeps the cached ludusavi manifest current. A background thread asks the server whether
+//! the manifest changed once the configured interval has passed; an unchanged manifest is
+//! answered with `304 Not Modified` and costs no download.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Weekly; `0` turns automatic refreshes off.
+pub const DEFAULT_REFRESH_HOURS: i32 = 168;
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Leaves the network alone while the app is starting up.
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestProgressEvent {
+    /// `download`, `parse`, `unchanged` or `done`
+    pub stage: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+/// Whether `interval_hours` have passed since the last check. A missing or unreadable
+/// timestamp counts as due so a fresh install picks up the current manifest.
+pub fn is_refresh_due(checked_at: Option<&str>, interval_hours: i32, now: DateTime<Utc>) -> bool {
+    if interval_hours <= 0 {
+        return false;
+    }
+    let Some(checked_at) = checked_at.and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+    else {
+        return true;
+    };
+    now.signed_duration_since(checked_at) >= chrono::Duration::hours(interval_hours as i64)
+}
+
+pub fn emit_progress(app: &AppHandle, stage: &str, downloaded: u64, total: Option<u64>) {
+    let _ = app.emit(
+        "manifest:progress",
+        ManifestProgressEvent {
+            stage: stage.to_string(),
+            downloaded,
+            total,
+        },
+    );
+}
+
+pub fn start_manifest_refresh(app: AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(STARTUP_DELAY);
+        loop {
+            let hours = super::get_setting_i32("manifest_refresh_hours", DEFAULT_REFRESH_HOURS);
+            let checked_at = super::get_setting_value("manifest_checked_at");
+            if is_refresh_due(checked_at.as_deref(), hours, Utc::now()) {
+                let progress = |stage: &'static str, downloaded: u64, total: Option<u64>| {
+                    emit_progress(&app, stage, downloaded, total)
+                };
+                if let Err(e) = super::refresh_manifest_now(false, &progress) {
+                    eprintln!("Automatic manifest refresh failed: {}", e);
+                }
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_is_due_after_the_interval_or_without_a_previous_check() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z")
+            .expect("parse now")
+            .with_timezone(&Utc);
+        assert!(is_refresh_due(None, 168, now));
+        assert!(is_refresh_due(Some("garbage"), 168, now));
+        assert!(!is_refresh_due(Some("2026-03-05T12:00:00Z"), 168, now));
+        assert!(is_refresh_due(Some("2026-03-03T12:00:00Z"), 168, now));
+        assert!(is_refresh_due(Some("2026-03-10T11:00:00+00:00"), 1, now));
+        assert!(!is_refresh_due(None, 0, now));
+    }
+}
@@ -0,0 +1,170 @@
+use super::sanitize_folder_name;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use regex::Regex;
+
+/// Used when `backup_name_template` is unset or renders to nothing.
+pub const DEFAULT_NAME_TEMPLATE: &str = "{datetime}_{trigger}";
+/// Folder naming used before templates existed; still recognised on import.
+const LEGACY_FORMAT: &str = "%H%M%S_%d%m%Y";
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H%M%S";
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H-%M-%S";
+
+/// Values available to a `backup_name_template`:
+/// `{game}`, `{date}` (2026-05-02), `{time}` (153000), `{datetime}` (2026-05-02T15-30-00),
+/// `{trigger}` (`auto`/`manual`), `{note}` and `{profile}` (launch profile of the session
+/// that produced the save).
+pub struct BackupNameContext<'a> {
+    pub game: &'a str,
+    pub created: NaiveDateTime,
+    pub trigger: &'a str,
+    pub note: Option<&'a str>,
+    pub profile: Option<&'a str>,
+}
+
+pub fn render_backup_name(template: &str, ctx: &BackupNameContext) -> String {
+    let template = if template.trim().is_empty() {
+        DEFAULT_NAME_TEMPLATE
+    } else {
+        template.trim()
+    };
+    let rendered = template
+        .replace("{game}", ctx.game)
+        .replace("{date}", &ctx.created.format(DATE_FORMAT).to_string())
+        .replace("{time}", &ctx.created.format(TIME_FORMAT).to_string())
+        .replace(
+            "{datetime}",
+            &ctx.created.format(DATETIME_FORMAT).to_string(),
+        )
+        .replace("{trigger}", ctx.trigger)
+        .replace("{note}", ctx.note.unwrap_or(""))
+        .replace("{profile}", ctx.profile.unwrap_or(""));
+    let name = sanitize_folder_name(&rendered);
+    let name = name.trim_matches(|c: char| matches!(c, '_' | '-' | ' ' | '.'));
+    if name.is_empty() {
+        ctx.created.format(DATETIME_FORMAT).to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Rejects templates whose names could not be parsed back: without `{date}` or `{datetime}`
+/// a backup can neither be sorted nor re-imported. An empty template means the default.
+pub fn validate_name_template(template: &str) -> Result<(), String> {
+    let template = template.trim();
+    if template.is_empty() || template.contains("{date}") || template.contains("{datetime}") {
+        Ok(())
+    } else {
+        Err("Шаблон имени бэкапа должен содержать {date} или {datetime}".to_string())
+    }
+}
+
+fn template_regex(template: &str) -> Option<Regex> {
+    let token = Regex::new(r"\{(game|date|time|datetime|trigger|note|profile)\}").ok()?;
+    let mut pattern = String::from("^");
+    let mut last = 0;
+    for caps in token.captures_iter(template) {
+        let whole = caps.get(0)?;
+        pattern.push_str(&regex::escape(&template[last..whole.start()]));
+        pattern.push_str(match &caps[1] {
+            "date" => r"(?P<date>\d{4}-\d{2}-\d{2})",
+            "time" => r"(?P<time>\d{6})",
+            "datetime" => r"(?P<datetime>\d{4}-\d{2}-\d{2}T\d{2}-\d{2}-\d{2})",
+            _ => ".*?",
+        });
+        last = whole.end();
+    }
+    pattern.push_str(&regex::escape(&template[last..]));
+    // Collisions within the same second get a `-2`, `-3`, ... suffix.
+    pattern.push_str(r"(?:-\d+)?$");
+    Regex::new(&pattern).ok()
+}
+
+/// Recovers the creation time from a backup folder/archive name: the legacy format first,
+/// then the current template, then any ISO date/time found in the name (so backups made
+/// with an older template still sort correctly).
+pub fn parse_backup_name(name: &str, template: &str) -> Option<NaiveDateTime> {
+    let stem = name
+        .strip_suffix(".sqoba.zip")
+        .or_else(|| name.strip_suffix(".zip"))
+        .unwrap_or(name);
+    if let Ok(legacy) = NaiveDateTime::parse_from_str(stem, LEGACY_FORMAT) {
+        return Some(legacy);
+    }
+
+    if let Some(caps) = template_regex(template.trim()).and_then(|re| re.captures(stem)) {
+        if let Some(dt) = caps
+            .name("datetime")
+            .and_then(|m| NaiveDateTime::parse_from_str(m.as_str(), DATETIME_FORMAT).ok())
+        {
+            return Some(dt);
+        }
+        if let Some(date) = caps
+            .name("date")
+            .and_then(|m| NaiveDate::parse_from_str(m.as_str(), DATE_FORMAT).ok())
+        {
+            let time = caps
+                .name("time")
+                .and_then(|m| NaiveTime::parse_from_str(m.as_str(), TIME_FORMAT).ok())
+                .unwrap_or_default();
+            return Some(date.and_time(time));
+        }
+    }
+
+    let any = Regex::new(r"\d{4}-\d{2}-\d{2}T\d{2}-\d{2}-\d{2}").ok()?;
+    any.find(stem)
+        .and_then(|m| NaiveDateTime::parse_from_str(m.as_str(), DATETIME_FORMAT).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2026-05-02T15-30-00", DATETIME_FORMAT).expect("datetime")
+    }
+
+    #[test]
+    fn rendered_names_parse_back_to_their_timestamp() {
+        let ctx = BackupNameContext {
+            game: "Hades II",
+            created: created(),
+            trigger: "auto",
+            note: Some("before boss"),
+            profile: None,
+        };
+
+        let default_name = render_backup_name("", &ctx);
+        assert_eq!(default_name, "2026-05-02T15-30-00_auto");
+        assert_eq!(parse_backup_name(&default_name, ""), Some(created()));
+
+        let template = "{game}_{date}_{time}_{note}";
+        let custom = render_backup_name(template, &ctx);
+        assert_eq!(custom, "Hades II_2026-05-02_153000_before boss");
+        assert_eq!(
+            parse_backup_name(&format!("{}-2.sqoba.zip", custom), template),
+            Some(created())
+        );
+
+        // Older names keep working after the template changes.
+        assert_eq!(
+            parse_backup_name("153000_02052026.sqoba.zip", template),
+            Some(created())
+        );
+        assert_eq!(
+            parse_backup_name("2026-05-02T15-30-00_manual", template),
+            Some(created())
+        );
+        assert_eq!(parse_backup_name("random-folder", template), None);
+    }
+
+    #[test]
+    fn templates_without_a_date_are_rejected() {
+        assert!(validate_name_template("").is_ok());
+        assert!(validate_name_template(DEFAULT_NAME_TEMPLATE).is_ok());
+        assert!(validate_name_template("{game}_{date}_{time}").is_ok());
+        assert!(validate_name_template("{game}_{note}").is_err());
+        assert!(validate_name_template("{time}_{trigger}").is_err());
+    }
+}
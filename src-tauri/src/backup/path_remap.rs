@@ -0,0 +1,363 @@
+use crate::backup::engine::{BackupArchiveManifest, BackupFileEntry};
+use crate::backup::save_locator::PathResolutionContext;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreRootMapping {
+    pub root_label: String,
+    pub original_root: String,
+    pub resolved_root: String,
+    pub remapped: bool,
+    pub needs_mapping: bool,
+}
+
+/// Machine-specific directories that manifest paths can be expressed against,
+/// e.g. `<winAppData>/Game/save.dat` instead of `C:\Users\me\AppData\Roaming\...`.
+pub struct PathTokens {
+    roots: Vec<(&'static str, PathBuf)>,
+}
+
+impl PathTokens {
+    /// Tokens for this machine, plus `{PATHTOGAME}` when the game's install dir is known.
+    pub fn for_game(game_dir: Option<&Path>) -> Self {
+        Self::from_roots(
+            PathResolutionContext::new()
+                .with_game_dir(game_dir)
+                .token_roots(),
+        )
+    }
+
+    pub fn from_roots(mut roots: Vec<(&'static str, PathBuf)>) -> Self {
+        // Most specific roots first so `<winAppData>` wins over `<home>`.
+        roots.sort_by_key(|(_, path)| std::cmp::Reverse(path.components().count()));
+        Self { roots }
+    }
+
+    pub fn tokenise(&self, path: &Path) -> Option<String> {
+        for (token, root) in &self.roots {
+            if let Some(rel) = strip_prefix_path(path, root) {
+                let rel = rel.to_string_lossy().replace('\\', "/");
+                if rel.is_empty() {
+                    return Some(token.to_string());
+                }
+                return Some(format!("{}/{}", token, rel));
+            }
+        }
+        None
+    }
+
+    pub fn expand(&self, portable: &str) -> Option<PathBuf> {
+        for (token, root) in &self.roots {
+            let Some(rest) = portable.strip_prefix(token) else {
+                continue;
+            };
+            if !rest.is_empty() && !rest.starts_with('/') {
+                continue;
+            }
+            let mut out = root.clone();
+            for part in rest.split('/').filter(|p| !p.is_empty()) {
+                out.push(part);
+            }
+            return Some(out);
+        }
+        None
+    }
+}
+
+/// Translates manifest `original_path` values into restore targets on the current machine.
+///
+/// Resolution order: explicit user mappings, portable token paths recorded at backup time,
+/// then a user-profile swap for legacy backups (`C:\Users\<old>\...` -> current home).
+pub struct RestorePathRemapper {
+    tokens: PathTokens,
+    home: Option<PathBuf>,
+    overrides: Vec<(PathBuf, PathBuf)>,
+}
+
+impl RestorePathRemapper {
+    pub fn current(overrides: &HashMap<String, String>, game_dir: Option<&Path>) -> Self {
+        let context = PathResolutionContext::new().with_game_dir(game_dir);
+        let home = context.home().map(Path::to_path_buf);
+        Self::new(
+            PathTokens::from_roots(context.token_roots()),
+            home,
+            overrides,
+        )
+    }
+
+    pub fn new(
+        tokens: PathTokens,
+        home: Option<PathBuf>,
+        overrides: &HashMap<String, String>,
+    ) -> Self {
+        let mut overrides: Vec<(PathBuf, PathBuf)> = overrides
+            .iter()
+            .filter(|(from, to)| !from.trim().is_empty() && !to.trim().is_empty())
+            .map(|(from, to)| (PathBuf::from(from.trim()), PathBuf::from(to.trim())))
+            .collect();
+        overrides.sort_by_key(|(from, _)| std::cmp::Reverse(from.components().count()));
+        Self {
+            tokens,
+            home,
+            overrides,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self {
+            tokens: PathTokens::from_roots(Vec::new()),
+            home: None,
+            overrides: Vec::new(),
+        }
+    }
+
+    pub fn remap(&self, entry: &BackupFileEntry) -> PathBuf {
+        let original = PathBuf::from(&entry.original_path);
+
+        for (from, to) in &self.overrides {
+            if let Some(rel) = strip_prefix_path(&original, from) {
+                return to.join(rel);
+            }
+        }
+
+        if let Some(expanded) = entry
+            .portable_path
+            .as_deref()
+            .and_then(|portable| self.tokens.expand(portable))
+        {
+            return expanded;
+        }
+
+        if let Some(home) = &self.home {
+            if let Some(swapped) = swap_user_profile(&original, home) {
+                return swapped;
+            }
+        }
+
+        original
+    }
+
+    /// Summarises where each backup root will be restored to, flagging roots whose
+    /// volume does not exist here so the frontend can ask the user for a mapping.
+    pub fn plan(&self, manifest: &BackupArchiveManifest) -> Vec<RestoreRootMapping> {
+        let mut roots: BTreeMap<String, RestoreRootMapping> = BTreeMap::new();
+
+        for entry in &manifest.files {
+            let (label, rel) = match parse_backup_relative(&entry.backup_path) {
+                Some(value) => value,
+                None => ("files".to_string(), PathBuf::new()),
+            };
+            if roots.contains_key(&label) {
+                continue;
+            }
+
+            let original = PathBuf::from(&entry.original_path);
+            let resolved = self.remap(entry);
+            let original_root = root_of(&original, &rel);
+            let resolved_root = root_of(&resolved, &rel);
+
+            roots.insert(
+                label.clone(),
+                RestoreRootMapping {
+                    root_label: label,
+                    original_root: original_root.to_string_lossy().to_string(),
+                    resolved_root: resolved_root.to_string_lossy().to_string(),
+                    remapped: resolved_root != original_root,
+                    needs_mapping: !volume_exists(&resolved_root),
+                },
+            );
+        }
+
+        roots.into_values().collect()
+    }
+}
+
+fn root_of(path: &Path, rel: &Path) -> PathBuf {
+    strip_suffix_path(path, rel)
+        .or_else(|| path.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn swap_user_profile(path: &Path, home: &Path) -> Option<PathBuf> {
+    if strip_prefix_path(path, home).is_some() {
+        return None;
+    }
+
+    let components: Vec<Component> = path.components().collect();
+    let users_index = components.iter().take(3).position(|c| {
+        c.as_os_str()
+            .to_string_lossy()
+            .eq_ignore_ascii_case("users")
+    })?;
+    let user = components.get(users_index + 1)?;
+    if user
+        .as_os_str()
+        .to_string_lossy()
+        .eq_ignore_ascii_case("public")
+    {
+        return None;
+    }
+
+    let mut out = home.to_path_buf();
+    for component in components.iter().skip(users_index + 2) {
+        out.push(component.as_os_str());
+    }
+    Some(out)
+}
+
+fn volume_exists(path: &Path) -> bool {
+    let mut root = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => root.push(component.as_os_str()),
+            _ => break,
+        }
+    }
+    root.as_os_str().is_empty() || root.exists()
+}
+
+fn components_match(a: &Component, b: &Component) -> bool {
+    let a_str = a.as_os_str().to_string_lossy();
+    let b_str = b.as_os_str().to_string_lossy();
+    if cfg!(target_os = "windows") {
+        a_str.to_lowercase() == b_str.to_lowercase()
+    } else {
+        a_str == b_str
+    }
+}
+
+pub(crate) fn strip_prefix_path(path: &Path, prefix: &Path) -> Option<PathBuf> {
+    let path_components: Vec<_> = path.components().collect();
+    let prefix_components: Vec<_> = prefix.components().collect();
+    if prefix_components.is_empty() || path_components.len() < prefix_components.len() {
+        return None;
+    }
+    for (a, b) in path_components.iter().zip(prefix_components.iter()) {
+        if !components_match(a, b) {
+            return None;
+        }
+    }
+    let mut out = PathBuf::new();
+    for comp in path_components.iter().skip(prefix_components.len()) {
+        out.push(comp.as_os_str());
+    }
+    Some(out)
+}
+
+pub(crate) fn parse_backup_relative(backup_path: &str) -> Option<(String, PathBuf)> {
+    let parts: Vec<&str> = backup_path.split('/').filter(|p| !p.is_empty()).collect();
+    if parts.len() < 3 || parts[0] != "files" {
+        return None;
+    }
+    let root_label = parts[1].to_string();
+    let mut rel = PathBuf::new();
+    for part in parts.iter().skip(2) {
+        rel.push(part);
+    }
+    Some((root_label, rel))
+}
+
+pub(crate) fn strip_suffix_path(path: &Path, suffix: &Path) -> Option<PathBuf> {
+    let path_components: Vec<_> = path.components().collect();
+    let suffix_components: Vec<_> = suffix.components().collect();
+    if suffix_components.is_empty() {
+        return Some(path.to_path_buf());
+    }
+    if path_components.len() < suffix_components.len() {
+        return None;
+    }
+    let start = path_components.len() - suffix_components.len();
+    for (a, b) in path_components[start..]
+        .iter()
+        .zip(suffix_components.iter())
+    {
+        if !components_match(a, b) {
+            return None;
+        }
+    }
+    let mut out = PathBuf::new();
+    for comp in path_components.iter().take(start) {
+        out.push(comp.as_os_str());
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(original: &Path, portable: Option<&str>) -> BackupFileEntry {
+        BackupFileEntry {
+            backup_path: "files/root-0/slot1.sav".to_string(),
+            original_path: original.to_string_lossy().to_string(),
+            portable_path: portable.map(|p| p.to_string()),
+            link_target: None,
+            size: 5,
+            mtime: None,
+        }
+    }
+
+    #[test]
+    fn tokenise_and_expand_roundtrip_through_most_specific_root() {
+        let dir = tempdir().expect("tempdir");
+        let home = dir.path().join("home");
+        let appdata = home.join("AppData").join("Roaming");
+        let tokens = PathTokens::from_roots(vec![
+            ("<home>", home.clone()),
+            ("<winAppData>", appdata.clone()),
+        ]);
+
+        let save = appdata.join("Game").join("slot1.sav");
+        let portable = tokens.tokenise(&save).expect("tokenise");
+        assert_eq!(portable, "<winAppData>/Game/slot1.sav");
+        assert_eq!(tokens.expand(&portable), Some(save));
+        assert_eq!(tokens.expand("<winAppDataLow>/Game"), None);
+    }
+
+    #[test]
+    fn remap_prefers_overrides_then_portable_paths() {
+        let dir = tempdir().expect("tempdir");
+        let old_root = dir.path().join("old").join("Saves");
+        let new_root = dir.path().join("new").join("Saves");
+        let appdata = dir.path().join("appdata");
+
+        let tokens = PathTokens::from_roots(vec![("<winAppData>", appdata.clone())]);
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            old_root.to_string_lossy().to_string(),
+            new_root.to_string_lossy().to_string(),
+        );
+        let remapper = RestorePathRemapper::new(tokens, None, &overrides);
+
+        let overridden = remapper.remap(&entry(&old_root.join("slot1.sav"), None));
+        assert_eq!(overridden, new_root.join("slot1.sav"));
+
+        let portable = remapper.remap(&entry(
+            &dir.path().join("elsewhere").join("slot1.sav"),
+            Some("<winAppData>/Game/slot1.sav"),
+        ));
+        assert_eq!(portable, appdata.join("Game").join("slot1.sav"));
+    }
+
+    #[test]
+    fn plan_reports_original_and_resolved_roots() {
+        let dir = tempdir().expect("tempdir");
+        let save_root = dir.path().join("saves");
+        let manifest = BackupArchiveManifest {
+            metadata: None,
+            version: 2,
+            files: vec![entry(&save_root.join("slot1.sav"), None)],
+            skipped: Vec::new(),
+        };
+
+        let plan = RestorePathRemapper::identity().plan(&manifest);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].root_label, "root-0");
+        assert_eq!(plan[0].original_root, save_root.to_string_lossy());
+        assert!(!plan[0].remapped);
+        assert!(!plan[0].needs_mapping);
+    }
+}
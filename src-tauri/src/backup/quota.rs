@@ -0,0 +1,175 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+pub struct StoredBackup {
+    pub id: String,
+    pub game_id: String,
+    pub created_at: String,
+    /// Bytes the backup occupies on disk (archive size, not raw save size).
+    pub disk_size: u64,
+}
+
+/// Size limits in bytes; `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuotaLimits {
+    pub per_game: Option<u64>,
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaScope {
+    Game,
+    Total,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuotaViolation {
+    pub scope: QuotaScope,
+    pub used_bytes: u64,
+    pub quota_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QuotaPlan {
+    /// Backups to delete, oldest first.
+    pub evict: Vec<String>,
+    /// Set when the quota is still exceeded after all allowed evictions.
+    pub violation: Option<QuotaViolation>,
+}
+
+/// Works out which backups to delete so `game_id` fits its per-game quota and the whole
+/// backup set fits the global quota. The newest backup of every game is never evicted,
+/// so a single oversized backup shows up as a violation instead of being deleted.
+pub fn plan_quota_evictions(
+    backups: &[StoredBackup],
+    game_id: &str,
+    limits: QuotaLimits,
+) -> QuotaPlan {
+    let mut ordered: Vec<&StoredBackup> = backups.iter().collect();
+    ordered.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let mut newest: HashMap<&str, &str> = HashMap::new();
+    for backup in &ordered {
+        newest.insert(backup.game_id.as_str(), backup.id.as_str());
+    }
+    let protected: HashSet<&str> = newest.values().copied().collect();
+
+    let mut evicted: HashSet<&str> = HashSet::new();
+    let mut plan = QuotaPlan::default();
+
+    if let Some(quota) = limits.per_game {
+        let mut used: u64 = ordered
+            .iter()
+            .filter(|b| b.game_id == game_id)
+            .map(|b| b.disk_size)
+            .sum();
+        for backup in ordered.iter().filter(|b| b.game_id == game_id) {
+            if used <= quota {
+                break;
+            }
+            if protected.contains(backup.id.as_str()) {
+                continue;
+            }
+            used -= backup.disk_size;
+            evicted.insert(backup.id.as_str());
+            plan.evict.push(backup.id.clone());
+        }
+        if used > quota {
+            plan.violation = Some(QuotaViolation {
+                scope: QuotaScope::Game,
+                used_bytes: used,
+                quota_bytes: quota,
+            });
+        }
+    }
+
+    if let Some(quota) = limits.total {
+        let mut used: u64 = ordered
+            .iter()
+            .filter(|b| !evicted.contains(b.id.as_str()))
+            .map(|b| b.disk_size)
+            .sum();
+        for backup in &ordered {
+            if used <= quota {
+                break;
+            }
+            if protected.contains(backup.id.as_str()) || evicted.contains(backup.id.as_str()) {
+                continue;
+            }
+            used -= backup.disk_size;
+            evicted.insert(backup.id.as_str());
+            plan.evict.push(backup.id.clone());
+        }
+        if used > quota && plan.violation.is_none() {
+            plan.violation = Some(QuotaViolation {
+                scope: QuotaScope::Total,
+                used_bytes: used,
+                quota_bytes: quota,
+            });
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backup(id: &str, game_id: &str, day: u32, size: u64) -> StoredBackup {
+        StoredBackup {
+            id: id.to_string(),
+            game_id: game_id.to_string(),
+            created_at: format!("2026-01-{:02}T00:00:00+00:00", day),
+            disk_size: size,
+        }
+    }
+
+    #[test]
+    fn evicts_oldest_until_within_quotas_but_keeps_newest_per_game() {
+        let backups = vec![
+            backup("a1", "a", 1, 40),
+            backup("a2", "a", 2, 40),
+            backup("a3", "a", 3, 40),
+            backup("b1", "b", 1, 50),
+            backup("b2", "b", 4, 50),
+        ];
+
+        let plan = plan_quota_evictions(
+            &backups,
+            "a",
+            QuotaLimits {
+                per_game: Some(90),
+                total: Some(120),
+            },
+        );
+        // Per-game pass drops a1; total pass then drops b1 and a2 (oldest first).
+        assert_eq!(plan.evict, vec!["a1", "b1", "a2"]);
+        assert_eq!(plan.violation, None);
+    }
+
+    #[test]
+    fn reports_violation_when_newest_backup_alone_is_too_big() {
+        let backups = vec![backup("a1", "a", 1, 10), backup("a2", "a", 2, 200)];
+
+        let plan = plan_quota_evictions(
+            &backups,
+            "a",
+            QuotaLimits {
+                per_game: Some(100),
+                total: None,
+            },
+        );
+        assert_eq!(plan.evict, vec!["a1"]);
+        assert_eq!(
+            plan.violation,
+            Some(QuotaViolation {
+                scope: QuotaScope::Game,
+                used_bytes: 200,
+                quota_bytes: 100,
+            })
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! What a restore is about to do before it starts: how long it should take on the target
+//! drive and which live saves are newer than the backup copies that would replace them.
+
+use crate::backup::engine::BackupFileEntry;
+use crate::backup::path_remap::RestorePathRemapper;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// FAT and exFAT keep modification times with two-second precision.
+const MTIME_TOLERANCE_SECS: i64 = 2;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NewerLiveFile {
+    pub path: String,
+    pub live_mtime: i64,
+    pub backup_mtime: i64,
+    pub live_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestorePreview {
+    pub backup_id: String,
+    pub file_count: usize,
+    pub restore_bytes: u64,
+    /// Drive that receives most of the restored bytes, e.g. `C:`.
+    pub target_drive: Option<String>,
+    pub throughput_mbps: f64,
+    /// False when the speed is a default for the drive type rather than a past restore.
+    pub throughput_measured: bool,
+    pub estimated_seconds: u64,
+    /// Restoring overwrites these; `restore_backup` refuses unless `overwrite_newer` is set.
+    pub newer_files: Vec<NewerLiveFile>,
+}
+
+fn live_mtime(path: &Path) -> Option<(i64, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((modified.as_secs() as i64, metadata.len()))
+}
+
+/// Entries whose file at the restore target was modified after the backup copy. Entries
+/// without a recorded mtime (old manifests) cannot be compared and are left out.
+pub fn newer_live_files(
+    files: &[BackupFileEntry],
+    remapper: &RestorePathRemapper,
+) -> Vec<NewerLiveFile> {
+    files
+        .iter()
+        .filter(|entry| entry.link_target.is_none())
+        .filter_map(|entry| {
+            let backup_mtime = entry.mtime?;
+            let target = remapper.remap(entry);
+            let (live_mtime, live_size) = live_mtime(&target)?;
+            (live_mtime > backup_mtime + MTIME_TOLERANCE_SECS).then(|| NewerLiveFile {
+                path: target.to_string_lossy().to_string(),
+                live_mtime,
+                backup_mtime,
+                live_size,
+            })
+        })
+        .collect()
+}
+
+/// Drive letter receiving the most bytes, with a path on it for disk-type detection.
+pub fn busiest_drive(
+    files: &[BackupFileEntry],
+    remapper: &RestorePathRemapper,
+) -> Option<(String, PathBuf)> {
+    let mut drives: HashMap<String, (u64, PathBuf)> = HashMap::new();
+    for entry in files.iter().filter(|entry| entry.link_target.is_none()) {
+        let target = remapper.remap(entry);
+        let Some(letter) = super::get_drive_letter(&target) else {
+            continue;
+        };
+        let slot = drives.entry(letter.to_uppercase()).or_insert((0, target));
+        slot.0 += entry.size;
+    }
+    drives
+        .into_iter()
+        .max_by(|a, b| a.1 .0.cmp(&b.1 .0).then_with(|| b.0.cmp(&a.0)))
+        .map(|(letter, (_, path))| (letter, path))
+}
+
+pub fn estimate_seconds(bytes: u64, mbps: f64) -> u64 {
+    if mbps <= 0.0 {
+        return 0;
+    }
+    (bytes as f64 / (mbps * 1024.0 * 1024.0)).ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+    use tempfile::tempdir;
+
+    fn entry(path: &Path, size: u64, mtime: Option<i64>) -> BackupFileEntry {
+        BackupFileEntry {
+            backup_path: "files/root/save.dat".to_string(),
+            original_path: path.to_string_lossy().to_string(),
+            portable_path: None,
+            link_target: None,
+            size,
+            mtime,
+        }
+    }
+
+    #[test]
+    fn flags_only_live_files_newer_than_the_backup() {
+        let dir = tempdir().expect("tempdir");
+        let newer = dir.path().join("slot1.sav");
+        let older = dir.path().join("slot2.sav");
+        let missing = dir.path().join("slot3.sav");
+        fs::write(&newer, b"played since").expect("write newer");
+        fs::write(&older, b"untouched").expect("write older");
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock after epoch")
+            .as_secs() as i64;
+        File::options()
+            .write(true)
+            .open(&older)
+            .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(7200)))
+            .expect("age older");
+
+        let files = vec![
+            entry(&newer, 12, Some(now - 3600)),
+            entry(&older, 9, Some(now - 3600)),
+            entry(&missing, 9, Some(now - 3600)),
+            entry(&newer, 12, None),
+        ];
+        let found = newer_live_files(&files, &RestorePathRemapper::identity());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, newer.to_string_lossy());
+        assert_eq!(found[0].live_size, 12);
+
+        assert_eq!(estimate_seconds(300 * 1024 * 1024, 100.0), 3);
+        assert_eq!(estimate_seconds(1, 100.0), 1);
+        assert_eq!(estimate_seconds(0, 100.0), 0);
+    }
+}
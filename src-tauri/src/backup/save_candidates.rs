@@ -0,0 +1,202 @@
+//! Ranks possible save roots so the user can pick one instead of the first hit being
+//! committed silently.
+
+use crate::backup::sqoba_manifest::{normalize_name, similarity_score};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Large folders are walked only this far; the sample is enough to judge them.
+const MAX_SCANNED_FILES: usize = 5_000;
+/// How many trailing path components are compared with the game name.
+const NAME_COMPONENTS: usize = 3;
+/// Saves rarely grow past this; bigger folders are usually installs or caches.
+const LARGE_ROOT_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+const NAME_WEIGHT: f64 = 0.5;
+const RECENCY_WEIGHT: f64 = 0.3;
+const SIZE_WEIGHT: f64 = 0.2;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SaveCandidate {
+    pub path: String,
+    /// `manifest`, `pcgamingwiki` (a confirmed wiki location), `heuristic` or `install`
+    /// (guessed from the install folder)
+    pub source: &'static str,
+    pub score: f64,
+    pub name_score: f64,
+    pub recency_score: f64,
+    pub size_score: f64,
+    pub file_count: usize,
+    pub total_size: u64,
+    pub last_modified: Option<String>,
+    /// Already the game's save path or one of its extra roots.
+    pub configured: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RootStats {
+    pub file_count: usize,
+    pub total_size: u64,
+    pub newest: Option<SystemTime>,
+}
+
+pub fn root_stats(path: &Path) -> RootStats {
+    let mut stats = RootStats::default();
+    for entry in WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .take(MAX_SCANNED_FILES)
+    {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        stats.file_count += 1;
+        stats.total_size += metadata.len();
+        if let Ok(modified) = metadata.modified() {
+            stats.newest = Some(stats.newest.map_or(modified, |newest| newest.max(modified)));
+        }
+    }
+    stats
+}
+
+/// Best match between the game name and the last few folder names of `path`. Manifest and
+/// confirmed wiki roots are known to belong to the game even when the folder is an id.
+pub fn name_score(game_name: &str, path: &Path, source: &str) -> f64 {
+    if source == "manifest" || source == "pcgamingwiki" {
+        return 1.0;
+    }
+    let target = normalize_name(game_name);
+    path.components()
+        .rev()
+        .take(NAME_COMPONENTS)
+        .map(|c| normalize_name(&c.as_os_str().to_string_lossy()))
+        // Drive letters and short ids would match by substring alone.
+        .filter(|name| name.len() >= 3)
+        .map(|name| similarity_score(&target, &name) as f64)
+        .fold(0.0, f64::max)
+}
+
+/// 1.0 for saves touched today, halving roughly every month after that.
+pub fn recency_score(newest: Option<SystemTime>, now: SystemTime) -> f64 {
+    let Some(newest) = newest else {
+        return 0.0;
+    };
+    let age = now.duration_since(newest).unwrap_or(Duration::ZERO);
+    let days = age.as_secs_f64() / 86_400.0;
+    1.0 / (1.0 + days / 30.0)
+}
+
+/// Empty folders score nothing, typical save sizes score fully and anything huge enough
+/// to be an install or cache folder is pushed down.
+pub fn size_score(stats: &RootStats) -> f64 {
+    if stats.file_count == 0 || stats.total_size == 0 {
+        return 0.0;
+    }
+    if stats.total_size > LARGE_ROOT_BYTES {
+        return 0.2;
+    }
+    1.0
+}
+
+pub fn score_candidate(
+    game_name: &str,
+    path: &Path,
+    source: &'static str,
+    stats: RootStats,
+    now: SystemTime,
+) -> SaveCandidate {
+    let name_score = name_score(game_name, path, source);
+    let recency_score = recency_score(stats.newest, now);
+    let size_score = size_score(&stats);
+    SaveCandidate {
+        path: path.to_string_lossy().to_string(),
+        source,
+        score: NAME_WEIGHT * name_score + RECENCY_WEIGHT * recency_score + SIZE_WEIGHT * size_score,
+        name_score,
+        recency_score,
+        size_score,
+        file_count: stats.file_count,
+        total_size: stats.total_size,
+        last_modified: stats
+            .newest
+            .map(|time| DateTime::<Utc>::from(time).to_rfc3339()),
+        configured: false,
+    }
+}
+
+/// Highest score first.
+pub fn rank(mut candidates: Vec<SaveCandidate>) -> Vec<SaveCandidate> {
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn named_recent_saves_outrank_stale_and_oversized_folders() {
+        let now = SystemTime::now();
+        let day = Duration::from_secs(86_400);
+        let saves = PathBuf::from("C:/Users/me/Documents/My Games/Hollow Knight");
+        let stale = PathBuf::from("C:/Users/me/AppData/Local/Hollow Knight");
+        let install = PathBuf::from("D:/Games/Hollow Knight");
+        let unrelated = PathBuf::from("C:/Users/me/Saved Games/Other");
+
+        let recent = RootStats {
+            file_count: 3,
+            total_size: 40_000,
+            newest: Some(now - day),
+        };
+        let candidates = rank(vec![
+            score_candidate(
+                "Hollow Knight",
+                &stale,
+                "heuristic",
+                RootStats {
+                    newest: Some(now - day * 400),
+                    ..recent
+                },
+                now,
+            ),
+            score_candidate(
+                "Hollow Knight",
+                &install,
+                "heuristic",
+                RootStats {
+                    total_size: LARGE_ROOT_BYTES + 1,
+                    ..recent
+                },
+                now,
+            ),
+            score_candidate("Hollow Knight", &unrelated, "heuristic", recent, now),
+            score_candidate("Hollow Knight", &saves, "heuristic", recent, now),
+        ]);
+
+        let order: Vec<&str> = candidates.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            order,
+            vec![
+                "C:/Users/me/Documents/My Games/Hollow Knight",
+                "D:/Games/Hollow Knight",
+                "C:/Users/me/AppData/Local/Hollow Knight",
+                "C:/Users/me/Saved Games/Other",
+            ]
+        );
+        assert_eq!(candidates[0].name_score, 1.0);
+        assert_eq!(
+            name_score(
+                "Hollow Knight",
+                Path::new("C:/Steam/userdata/1/367520"),
+                "manifest"
+            ),
+            1.0
+        );
+        assert_eq!(recency_score(None, now), 0.0);
+        assert_eq!(size_score(&RootStats::default()), 0.0);
+    }
+}
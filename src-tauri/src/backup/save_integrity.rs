@@ -0,0 +1,201 @@
+//! Sanity checks on save files after a game exits. A crash or a full disk can leave a save
+//! empty or half-written; catching that before the exit backup keeps the last good copy
+//! from being rotated away.
+
+use crate::backup::engine::BackupFileEntry;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Save formats with a fixed signature at the start of the file, by extension.
+const KNOWN_HEADERS: [(&str, &[u8]); 3] = [
+    ("sl2", b"BND4"),
+    ("ess", b"TESV_SAVEGAME"),
+    ("sqlite", b"SQLite format 3\0"),
+];
+/// Bytes read from the start of a file for the header and zero-fill checks.
+const PROBE_BYTES: u64 = 4096;
+/// A save that shrank to less than 1/10 of its size in the last backup counts as truncated.
+const TRUNCATED_RATIO: u64 = 10;
+/// Saves smaller than this in the last backup are too small to judge by size.
+const MIN_COMPARABLE_SIZE: u64 = 4096;
+/// Clock skew tolerated before a modification time counts as being in the future.
+const MTIME_SKEW: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Suspicion {
+    Empty,
+    /// The start of the file is nothing but zero bytes.
+    ZeroFilled,
+    /// The file doesn't start with the signature its format always has.
+    BadHeader,
+    Truncated {
+        size: u64,
+        previous_size: u64,
+    },
+    FutureMtime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SuspectFile {
+    pub path: String,
+    pub suspicion: Suspicion,
+}
+
+fn expected_header(path: &Path) -> Option<&'static [u8]> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    KNOWN_HEADERS
+        .iter()
+        .find(|(known, _)| *known == ext)
+        .map(|(_, header)| *header)
+}
+
+/// What looks wrong with the save at `path`, if anything. `previous_size` is its size in
+/// the last backup. Files that can't be read are left alone rather than reported.
+pub fn check_save_file(
+    path: &Path,
+    previous_size: Option<u64>,
+    now: SystemTime,
+) -> Option<Suspicion> {
+    let metadata = fs::metadata(path).ok().filter(|meta| meta.is_file())?;
+    let size = metadata.len();
+    if size == 0 {
+        return Some(Suspicion::Empty);
+    }
+    if let Some(previous_size) = previous_size.filter(|size| *size >= MIN_COMPARABLE_SIZE) {
+        if size.saturating_mul(TRUNCATED_RATIO) < previous_size {
+            return Some(Suspicion::Truncated {
+                size,
+                previous_size,
+            });
+        }
+    }
+
+    let mut probe = Vec::new();
+    File::open(path)
+        .ok()?
+        .take(PROBE_BYTES)
+        .read_to_end(&mut probe)
+        .ok()?;
+    if probe.iter().all(|byte| *byte == 0) {
+        return Some(Suspicion::ZeroFilled);
+    }
+    if expected_header(path).is_some_and(|header| !probe.starts_with(header)) {
+        return Some(Suspicion::BadHeader);
+    }
+    if metadata
+        .modified()
+        .is_ok_and(|modified| modified > now + MTIME_SKEW)
+    {
+        return Some(Suspicion::FutureMtime);
+    }
+    None
+}
+
+/// Checks each of `files` against its entry in `previous`, the last backup's manifest.
+pub fn find_suspect_saves(
+    files: &[String],
+    previous: &[BackupFileEntry],
+    now: SystemTime,
+) -> Vec<SuspectFile> {
+    let previous_sizes: HashMap<String, u64> = previous
+        .iter()
+        .map(|entry| (entry.original_path.to_lowercase(), entry.size))
+        .collect();
+    files
+        .iter()
+        .filter_map(|file| {
+            let previous_size = previous_sizes.get(&file.to_lowercase()).copied();
+            check_save_file(Path::new(file), previous_size, now).map(|suspicion| SuspectFile {
+                path: file.clone(),
+                suspicion,
+            })
+        })
+        .collect()
+}
+
+/// Whether a backup holds a non-empty copy of every suspect file, going by its manifest.
+pub fn has_intact_copies(manifest: &[BackupFileEntry], suspects: &[SuspectFile]) -> bool {
+    suspects.iter().all(|suspect| {
+        let path = suspect.path.to_lowercase();
+        manifest
+            .iter()
+            .any(|entry| entry.original_path.to_lowercase() == path && entry.size > 0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &Path, size: u64) -> BackupFileEntry {
+        BackupFileEntry {
+            backup_path: "files/save".to_string(),
+            original_path: path.to_string_lossy().to_string(),
+            portable_path: None,
+            link_target: None,
+            size,
+            mtime: None,
+        }
+    }
+
+    #[test]
+    fn empty_zeroed_truncated_and_mislabelled_saves_are_flagged() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let write = |name: &str, data: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, data).expect("write save");
+            path
+        };
+        let healthy = write("slot1.sav", b"progress");
+        let empty = write("slot2.sav", b"");
+        let zeroed = write("slot3.sav", &[0u8; 512]);
+        let truncated = write("slot4.sav", &[7u8; 100]);
+        let elden = write("ER0000.sl2", b"BND4\x00\x00rest");
+        let broken_elden = write("ER0001.sl2", b"garbage");
+        let now = SystemTime::now();
+
+        assert_eq!(check_save_file(&healthy, Some(8), now), None);
+        assert_eq!(check_save_file(&empty, None, now), Some(Suspicion::Empty));
+        assert_eq!(
+            check_save_file(&zeroed, None, now),
+            Some(Suspicion::ZeroFilled)
+        );
+        assert_eq!(check_save_file(&elden, None, now), None);
+        assert_eq!(
+            check_save_file(&broken_elden, None, now),
+            Some(Suspicion::BadHeader)
+        );
+        assert_eq!(
+            check_save_file(&healthy, None, now - MTIME_SKEW * 2),
+            Some(Suspicion::FutureMtime)
+        );
+        assert_eq!(
+            check_save_file(&dir.path().join("gone.sav"), None, now),
+            None
+        );
+
+        let files: Vec<String> = [&healthy, &truncated]
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let previous = vec![entry(&healthy, 8), entry(&truncated, 50_000)];
+        let suspects = find_suspect_saves(&files, &previous, now);
+        assert_eq!(suspects.len(), 1);
+        assert_eq!(
+            suspects[0].suspicion,
+            Suspicion::Truncated {
+                size: 100,
+                previous_size: 50_000
+            }
+        );
+
+        assert!(has_intact_copies(&previous, &suspects));
+        assert!(!has_intact_copies(&[entry(&truncated, 0)], &suspects));
+        assert!(!has_intact_copies(&[entry(&healthy, 8)], &suspects));
+    }
+}
@@ -1,15 +1,25 @@
+use crate::backup::install_scan::{install_dir_roots, publisher_roots};
+use crate::backup::manifest_override::{merge_entry, ManifestOverride};
 use crate::backup::sqoba_manifest::{normalize_name, similarity_score, SqobaGame, SqobaManifest};
+use crate::backup::wgs;
 use glob::glob;
-use std::collections::{HashSet, VecDeque};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+/// Placeholder for the game's install directory (parent of `exe_path`) in stored save paths.
+pub const GAME_PATH_TOKEN: &str = "{PATHTOGAME}";
+
 #[derive(Debug, Clone)]
 pub struct SaveRoot {
     pub label: String,
     pub path: PathBuf,
+    /// Guessed from the install folder rather than known from the manifest, heuristics
+    /// or the user.
+    pub low_confidence: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -17,8 +27,68 @@ pub struct SaveFile {
     pub path: PathBuf,
     pub root_label: String,
     pub relative_path: PathBuf,
-    #[allow(dead_code)]
     pub size: u64,
+    /// Set for links kept as links under `SymlinkPolicy::StoreAsLink`.
+    pub link_target: Option<PathBuf>,
+}
+
+/// What to do with symlinks and junctions found inside a save root. The root itself is
+/// always resolved, so a redirected Documents folder still works under every policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymlinkPolicy {
+    #[default]
+    Skip,
+    Follow,
+    StoreAsLink,
+}
+
+impl SymlinkPolicy {
+    pub fn from_setting(value: &str) -> Self {
+        match value.trim() {
+            "follow" => SymlinkPolicy::Follow,
+            "store_as_link" => SymlinkPolicy::StoreAsLink,
+            _ => SymlinkPolicy::Skip,
+        }
+    }
+}
+
+/// An enabled row of `game_save_paths`: a save location on top of `games.save_path`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfiguredRoot {
+    /// May contain `{PATHTOGAME}`.
+    pub path: String,
+    /// Files under an excluded path are dropped from whatever the other roots found.
+    pub exclude: bool,
+}
+
+/// Per-game hints for save discovery on top of the manifest and name heuristics.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SaveLookup<'a> {
+    /// User-chosen save path; may still contain `{PATHTOGAME}`.
+    pub override_path: Option<&'a str>,
+    /// Additional user-configured roots and exclusions.
+    pub extra_roots: &'a [ConfiguredRoot],
+    /// Game install directory used to expand `{PATHTOGAME}` and manifest `<base>`.
+    pub game_dir: Option<&'a Path>,
+    /// User-written manifest fragment merged over the game's manifest entry.
+    pub manifest_override: Option<&'a ManifestOverride>,
+    /// Manifest entry the user confirmed for this game; used instead of matching by name.
+    pub manifest_key: Option<&'a str>,
+}
+
+impl SaveLookup<'_> {
+    pub fn without_override(self) -> Self {
+        Self {
+            override_path: None,
+            extra_roots: &[],
+            ..self
+        }
+    }
+
+    pub fn has_overrides(&self) -> bool {
+        self.override_path.is_some() || !self.extra_roots.is_empty()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -26,19 +96,56 @@ pub struct SaveDiscovery {
     pub roots: Vec<SaveRoot>,
     pub files: Vec<SaveFile>,
     pub total_size: u64,
+    /// Readable `<container>/<blob>` names for Xbox / Microsoft Store (WGS) save files,
+    /// which are stored under GUIDs.
+    pub container_names: HashMap<PathBuf, String>,
+}
+
+impl SaveDiscovery {
+    /// Every root was guessed from the install folder.
+    pub fn is_low_confidence(&self) -> bool {
+        !self.roots.is_empty() && self.roots.iter().all(|root| root.low_confidence)
+    }
 }
 
 pub fn locate_game_saves(
     game_name: &str,
     manifest: Option<&SqobaManifest>,
-    override_path: Option<&str>,
+    lookup: SaveLookup,
+) -> Result<Option<SaveDiscovery>, String> {
+    locate_game_saves_with_symlinks(game_name, manifest, lookup, SymlinkPolicy::default())
+}
+
+pub fn locate_game_saves_with_symlinks(
+    game_name: &str,
+    manifest: Option<&SqobaManifest>,
+    lookup: SaveLookup,
+    symlinks: SymlinkPolicy,
+) -> Result<Option<SaveDiscovery>, String> {
+    locate_game_saves_in(
+        &PathResolutionContext::new(),
+        game_name,
+        manifest,
+        lookup,
+        symlinks,
+    )
+}
+
+/// Save discovery against `context` instead of the current machine's folders.
+pub fn locate_game_saves_in(
+    context: &PathResolutionContext,
+    game_name: &str,
+    manifest: Option<&SqobaManifest>,
+    lookup: SaveLookup,
+    symlinks: SymlinkPolicy,
 ) -> Result<Option<SaveDiscovery>, String> {
-    let roots = locate_game_save_roots(game_name, manifest, override_path)?;
+    let roots = locate_game_save_roots_in(context, game_name, manifest, lookup)?;
     if roots.is_empty() {
         return Ok(None);
     }
 
-    let discovery = collect_files(&roots)?;
+    let mut discovery = collect_files(&roots, symlinks)?;
+    drop_excluded_files(&mut discovery, &excluded_paths(lookup));
     if discovery.files.is_empty() {
         return Ok(None);
     }
@@ -49,12 +156,23 @@ pub fn locate_game_saves(
 pub fn locate_game_save_roots(
     game_name: &str,
     manifest: Option<&SqobaManifest>,
-    override_path: Option<&str>,
+    lookup: SaveLookup,
 ) -> Result<Vec<SaveRoot>, String> {
+    locate_game_save_roots_in(&PathResolutionContext::new(), game_name, manifest, lookup)
+}
+
+pub fn locate_game_save_roots_in(
+    context: &PathResolutionContext,
+    game_name: &str,
+    manifest: Option<&SqobaManifest>,
+    lookup: SaveLookup,
+) -> Result<Vec<SaveRoot>, String> {
+    let context = context.for_game(lookup.game_dir);
+    let prefixes = context.proton_prefix_contexts(game_name);
     let mut roots = Vec::new();
 
-    if let Some(path) = override_path {
-        let path = PathBuf::from(path);
+    if let Some(path) = lookup.override_path {
+        let path = PathBuf::from(expand_game_path_token(path, lookup.game_dir)?);
         if path.exists() {
             roots.push(path);
         } else {
@@ -65,51 +183,230 @@ pub fn locate_game_save_roots(
         }
     }
 
+    // Extra roots are best-effort: a config folder the game has not created yet must not
+    // block backing up the others.
+    for root in lookup.extra_roots.iter().filter(|root| !root.exclude) {
+        match expand_game_path_token(&root.path, lookup.game_dir) {
+            Ok(path) if Path::new(&path).exists() => roots.push(PathBuf::from(path)),
+            Ok(path) => eprintln!("Skipping missing save root: {}", path),
+            Err(e) => eprintln!("Skipping save root {}: {}", root.path, e),
+        }
+    }
+
     if roots.is_empty() {
-        if let Some(manifest) = manifest {
-            if let Some((_, entry)) = manifest.find_game_entry(game_name) {
-                roots = manifest_roots(&entry);
+        if let Some(entry) = lookup_manifest_entry(game_name, manifest, lookup) {
+            roots = manifest_roots(&entry, &context);
+            for prefix in &prefixes {
+                roots.extend(manifest_roots(&entry, prefix));
             }
         }
     }
 
     if roots.is_empty() {
-        roots = heuristic_roots(game_name);
+        roots = heuristic_roots(game_name, &context);
+        for prefix in &prefixes {
+            roots.extend(heuristic_roots(game_name, prefix));
+        }
     }
 
-    let roots = build_roots(roots);
-    Ok(roots)
+    if roots.is_empty() {
+        if let Some(game_dir) = lookup.game_dir {
+            return Ok(build_roots(install_roots(game_dir, &context), true));
+        }
+    }
+
+    Ok(build_roots(roots, false))
+}
+
+/// The game's manifest entry — the confirmed key if any, else the best name match — with
+/// the user's manifest fragment merged in.
+fn lookup_manifest_entry(
+    game_name: &str,
+    manifest: Option<&SqobaManifest>,
+    lookup: SaveLookup,
+) -> Option<SqobaGame> {
+    let upstream = manifest.and_then(|manifest| {
+        lookup
+            .manifest_key
+            .and_then(|key| manifest.games.get(key).cloned())
+            .or_else(|| manifest.find_game_entry(game_name).map(|(_, entry)| entry))
+    });
+    merge_entry(upstream, lookup.manifest_override)
+}
+
+/// Registry keys the game's manifest entry lists, as `HKEY_CURRENT_USER\...` paths.
+pub fn manifest_registry_keys(
+    game_name: &str,
+    manifest: Option<&SqobaManifest>,
+    lookup: SaveLookup,
+) -> Vec<String> {
+    lookup_manifest_entry(game_name, manifest, lookup)
+        .and_then(|entry| entry.registry)
+        .unwrap_or_default()
 }
 
-fn build_roots(paths: Vec<PathBuf>) -> Vec<SaveRoot> {
+/// Save folders guessed from the install: publisher folders named by Unity player data or
+/// the executables' version info, then save-looking folders inside the install itself.
+fn install_roots(game_dir: &Path, context: &PathResolutionContext) -> Vec<PathBuf> {
+    let my_games = context.documents.as_ref().map(|docs| docs.join("My Games"));
+    let bases: Vec<&Path> = [
+        context.local_low.as_deref(),
+        context.appdata.as_deref(),
+        context.local_appdata.as_deref(),
+        my_games.as_deref(),
+        context.documents.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let mut roots = publisher_roots(game_dir, &bases);
+    roots.extend(install_dir_roots(game_dir));
+    roots
+}
+
+/// Every root the manifest, the confirmed PCGamingWiki locations (`wiki_paths`, in manifest
+/// path syntax) and the heuristics can come up with, tagged with where it came from, without
+/// stopping at the first source that finds something.
+pub fn candidate_save_roots(
+    game_name: &str,
+    manifest: Option<&SqobaManifest>,
+    wiki_paths: &[String],
+    game_dir: Option<&Path>,
+) -> Vec<(PathBuf, &'static str)> {
+    candidate_save_roots_in(
+        &PathResolutionContext::new(),
+        game_name,
+        manifest,
+        wiki_paths,
+        game_dir,
+    )
+}
+
+pub fn candidate_save_roots_in(
+    context: &PathResolutionContext,
+    game_name: &str,
+    manifest: Option<&SqobaManifest>,
+    wiki_paths: &[String],
+    game_dir: Option<&Path>,
+) -> Vec<(PathBuf, &'static str)> {
+    let context = context.for_game(game_dir);
+    let mut contexts = vec![context.clone()];
+    contexts.extend(context.proton_prefix_contexts(game_name));
+    let mut out: Vec<(PathBuf, &'static str)> = Vec::new();
+    let entry = manifest
+        .and_then(|manifest| manifest.find_game_entry(game_name))
+        .map(|(_, entry)| entry);
+    let manifest_paths: Vec<PathBuf> = entry
+        .map(|entry| {
+            contexts
+                .iter()
+                .flat_map(|context| manifest_roots(&entry, context))
+                .collect()
+        })
+        .unwrap_or_default();
+    let wiki_roots: Vec<PathBuf> = contexts
+        .iter()
+        .flat_map(|context| {
+            wiki_paths
+                .iter()
+                .flat_map(move |raw_path| resolve_path(raw_path, context))
+        })
+        .collect();
+    let heuristic_paths: Vec<PathBuf> = contexts
+        .iter()
+        .flat_map(|context| heuristic_roots(game_name, context))
+        .collect();
+    let sourced = manifest_paths
+        .into_iter()
+        .map(|path| (path, "manifest"))
+        .chain(wiki_roots.into_iter().map(|path| (path, "pcgamingwiki")))
+        .chain(heuristic_paths.into_iter().map(|path| (path, "heuristic")))
+        .chain(
+            game_dir
+                .map(|game_dir| install_roots(game_dir, &context))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|path| (path, "install")),
+        );
+    for (path, source) in sourced {
+        if path.exists() && !out.iter().any(|(known, _)| *known == path) {
+            out.push((path, source));
+        }
+    }
+    out
+}
+
+fn build_roots(paths: Vec<PathBuf>, low_confidence: bool) -> Vec<SaveRoot> {
     let mut seen = HashSet::new();
     let mut out = Vec::new();
     for path in paths {
         if seen.insert(path.clone()) {
             let label = format!("root-{}", out.len());
-            out.push(SaveRoot { label, path });
+            out.push(SaveRoot {
+                label,
+                path,
+                low_confidence,
+            });
         }
     }
     out
 }
 
-fn manifest_roots(entry: &SqobaGame) -> Vec<PathBuf> {
-    let context = PathResolutionContext::new();
+fn excluded_paths(lookup: SaveLookup) -> Vec<PathBuf> {
+    lookup
+        .extra_roots
+        .iter()
+        .filter(|root| root.exclude)
+        .filter_map(|root| expand_game_path_token(&root.path, lookup.game_dir).ok())
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn drop_excluded_files(discovery: &mut SaveDiscovery, excluded: &[PathBuf]) {
+    if excluded.is_empty() {
+        return;
+    }
+    let is_excluded = |path: &Path| excluded.iter().any(|dir| path.starts_with(dir));
+    discovery.files.retain(|file| !is_excluded(&file.path));
+    discovery
+        .container_names
+        .retain(|path, _| !is_excluded(path));
+    discovery.total_size = discovery.files.iter().map(|file| file.size).sum();
+}
+
+/// Replaces `{PATHTOGAME}` with the game's install directory.
+pub fn expand_game_path_token(raw: &str, game_dir: Option<&Path>) -> Result<String, String> {
+    if !raw.contains(GAME_PATH_TOKEN) {
+        return Ok(raw.to_string());
+    }
+    let game_dir = game_dir
+        .ok_or_else(|| "Не удалось подставить {PATHTOGAME}: путь к игре не найден".to_string())?;
+    Ok(raw.replace(GAME_PATH_TOKEN, &game_dir.to_string_lossy()))
+}
+
+fn manifest_roots(entry: &SqobaGame, context: &PathResolutionContext) -> Vec<PathBuf> {
     let mut roots = Vec::new();
     if let Some(files_map) = &entry.files {
         for paths in files_map.values() {
             for raw_path in paths {
-                roots.extend(resolve_path(raw_path, &context));
+                roots.extend(resolve_path(raw_path, context));
             }
         }
     }
     roots
 }
 
-fn collect_files(roots: &[SaveRoot]) -> Result<SaveDiscovery, String> {
+fn collect_files(roots: &[SaveRoot], symlinks: SymlinkPolicy) -> Result<SaveDiscovery, String> {
     let mut files = Vec::new();
     let mut total_size = 0u64;
     let mut seen = HashSet::new();
+    // Canonical paths already collected when following links, so a link back into a root
+    // (or into another root) never copies the same file twice.
+    let mut seen_real = HashSet::new();
+    let mut is_new_real = |path: &Path| {
+        symlinks != SymlinkPolicy::Follow
+            || seen_real.insert(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()))
+    };
 
     for root in roots {
         if root.path.is_file() {
@@ -120,31 +417,53 @@ fn collect_files(roots: &[SaveRoot]) -> Result<SaveDiscovery, String> {
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_else(|| "file".to_string());
             let relative = PathBuf::from(name);
-            if seen.insert(root.path.clone()) {
+            if seen.insert(root.path.clone()) && is_new_real(&root.path) {
                 files.push(SaveFile {
                     path: root.path.clone(),
                     root_label: root.label.clone(),
                     relative_path: relative,
                     size,
+                    link_target: None,
                 });
                 total_size += size;
             }
         } else if root.path.is_dir() {
-            for entry in WalkDir::new(&root.path).into_iter().filter_map(|e| e.ok()) {
+            // With `follow_links`, walkdir reports link cycles as errors, which are skipped here.
+            let walker = WalkDir::new(&root.path).follow_links(symlinks == SymlinkPolicy::Follow);
+            for entry in walker.into_iter().filter_map(|e| e.ok()) {
+                let relative = entry
+                    .path()
+                    .strip_prefix(&root.path)
+                    .unwrap_or(entry.path())
+                    .to_path_buf();
+                let entry_path = entry.path().to_path_buf();
+
+                if entry.path_is_symlink() && symlinks != SymlinkPolicy::Follow {
+                    if symlinks == SymlinkPolicy::StoreAsLink && entry.depth() > 0 {
+                        if let Ok(target) = fs::read_link(entry.path()) {
+                            if seen.insert(entry_path.clone()) {
+                                files.push(SaveFile {
+                                    path: entry_path,
+                                    root_label: root.label.clone(),
+                                    relative_path: relative,
+                                    size: 0,
+                                    link_target: Some(target),
+                                });
+                            }
+                        }
+                    }
+                    continue;
+                }
+
                 if entry.file_type().is_file() {
-                    let relative = entry
-                        .path()
-                        .strip_prefix(&root.path)
-                        .unwrap_or(entry.path())
-                        .to_path_buf();
                     let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
-                    let entry_path = entry.path().to_path_buf();
-                    if seen.insert(entry_path.clone()) {
+                    if seen.insert(entry_path.clone()) && is_new_real(entry.path()) {
                         files.push(SaveFile {
                             path: entry_path,
                             root_label: root.label.clone(),
                             relative_path: relative,
                             size,
+                            link_target: None,
                         });
                         total_size += size;
                     }
@@ -153,14 +472,25 @@ fn collect_files(roots: &[SaveRoot]) -> Result<SaveDiscovery, String> {
         }
     }
 
+    let container_names = files
+        .iter()
+        .filter(|file| wgs::is_containers_index(&file.path))
+        .flat_map(|file| wgs::container_names(&file.path))
+        .collect();
+
     Ok(SaveDiscovery {
         roots: roots.to_vec(),
         files,
         total_size,
+        container_names,
     })
 }
 
-struct PathResolutionContext {
+/// The folders manifest tokens and the heuristics resolve against. `new()` reads the
+/// current machine; tests start from `empty()` and point the builder methods at a fixture
+/// tree, which also turns off registry lookups and the process environment.
+#[derive(Debug, Clone)]
+pub struct PathResolutionContext {
     home: Option<PathBuf>,
     documents: Option<PathBuf>,
     appdata: Option<PathBuf>,
@@ -172,10 +502,19 @@ struct PathResolutionContext {
     program_data: Option<PathBuf>,
     steam: Option<PathBuf>,
     steam_userdata: Option<PathBuf>,
+    xdg_data: Option<PathBuf>,
+    xdg_config: Option<PathBuf>,
+    ubisoft_launcher: Option<PathBuf>,
+    game_dir: Option<PathBuf>,
+    /// Variables for `%VAR%` expansion; `None` reads the process environment.
+    env: Option<HashMap<String, String>>,
+    /// Whether launcher installs may be read from the Windows registry.
+    use_registry: bool,
 }
 
 impl PathResolutionContext {
-    fn new() -> Self {
+    #[cfg(target_os = "windows")]
+    pub fn new() -> Self {
         let home = dirs::home_dir();
         let documents = dirs::document_dir();
         let appdata = dirs::data_dir();
@@ -186,7 +525,10 @@ impl PathResolutionContext {
         let saved_games = home.as_ref().map(|h| h.join("Saved Games"));
         let public = env::var("PUBLIC").ok().map(PathBuf::from);
         let public_documents = public.as_ref().map(|p| p.join("Documents"));
-        let program_data = env::var("ProgramData").ok().map(PathBuf::from);
+        let program_data = env::var("ProgramData")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| Some(PathBuf::from("C:\\ProgramData")));
         let steam = find_steam_path();
         let steam_userdata = steam.as_ref().map(|p| p.join("userdata"));
 
@@ -202,8 +544,247 @@ impl PathResolutionContext {
             program_data,
             steam,
             steam_userdata,
+            xdg_data: None,
+            xdg_config: None,
+            ubisoft_launcher: find_ubisoft_launcher_path(),
+            game_dir: None,
+            env: None,
+            use_registry: true,
+        }
+    }
+
+    /// Outside Windows the Windows folders only exist inside Wine/Proton prefixes, which
+    /// `proton_prefix_contexts` adds per game; natively saves live under the XDG dirs.
+    #[cfg(not(target_os = "windows"))]
+    pub fn new() -> Self {
+        let steam = find_steam_path();
+        let steam_userdata = steam.as_ref().map(|p| p.join("userdata"));
+        Self {
+            home: dirs::home_dir(),
+            documents: dirs::document_dir(),
+            steam,
+            steam_userdata,
+            xdg_data: dirs::data_dir(),
+            xdg_config: dirs::config_dir(),
+            ubisoft_launcher: find_ubisoft_launcher_path(),
+            env: None,
+            ..Self::empty()
+        }
+    }
+
+    /// The same lookup with the Windows folders pointing into the Wine prefix `pfx`
+    /// (the folder holding `drive_c`). Launcher lookups are left to the native context.
+    fn inside_wine_prefix(&self, pfx: &Path) -> Self {
+        let drive_c = pfx.join("drive_c");
+        let home = wine_user_dir(&drive_c);
+        let documents = ["Documents", "My Documents"]
+            .iter()
+            .map(|name| home.join(name))
+            .find(|path| path.is_dir())
+            .unwrap_or_else(|| home.join("Documents"));
+        let public = drive_c.join("users").join("Public");
+        Self {
+            documents: Some(documents),
+            appdata: Some(home.join("AppData").join("Roaming")),
+            local_appdata: Some(home.join("AppData").join("Local")),
+            local_low: Some(home.join("AppData").join("LocalLow")),
+            saved_games: Some(home.join("Saved Games")),
+            public_documents: Some(public.join("Documents")),
+            public: Some(public),
+            program_data: Some(drive_c.join("ProgramData")),
+            home: Some(home),
+            steam: None,
+            ubisoft_launcher: None,
+            use_registry: false,
+            ..self.clone()
+        }
+    }
+
+    /// One context per Proton prefix (`steamapps/compatdata/<appid>/pfx`) of the Steam
+    /// apps matching `game_name`. Empty when no Steam library has a `compatdata` folder,
+    /// which is always the case on Windows.
+    fn proton_prefix_contexts(&self, game_name: &str) -> Vec<Self> {
+        let Some(steam) = self.steam.as_deref() else {
+            return Vec::new();
+        };
+        let all_libraries = find_steam_library_paths(steam);
+        let libraries: Vec<&PathBuf> = all_libraries
+            .iter()
+            .filter(|library| library.join("steamapps").join("compatdata").is_dir())
+            .collect();
+        if libraries.is_empty() {
+            return Vec::new();
+        }
+        let app_ids = find_steam_app_ids(game_name, &all_libraries);
+        let mut out = Vec::new();
+        for app_id in &app_ids {
+            for library in &libraries {
+                let pfx = library
+                    .join("steamapps")
+                    .join("compatdata")
+                    .join(app_id)
+                    .join("pfx");
+                if pfx.join("drive_c").is_dir() {
+                    out.push(self.inside_wine_prefix(&pfx));
+                }
+            }
+        }
+        out
+    }
+
+    pub fn with_game_dir(mut self, game_dir: Option<&Path>) -> Self {
+        self.game_dir = game_dir.map(Path::to_path_buf);
+        self
+    }
+
+    /// A copy with `game_dir` set when one is known, keeping any directory set earlier.
+    fn for_game(&self, game_dir: Option<&Path>) -> Self {
+        match game_dir {
+            Some(game_dir) => self.clone().with_game_dir(Some(game_dir)),
+            None => self.clone(),
         }
     }
+
+    fn env_var(&self, key: &str) -> Option<String> {
+        match &self.env {
+            Some(vars) => vars.get(key).cloned(),
+            None => env::var(key).ok(),
+        }
+    }
+
+    pub(crate) fn home(&self) -> Option<&Path> {
+        self.home.as_deref()
+    }
+
+    /// Known manifest tokens with the directories they resolve to on this machine.
+    pub(crate) fn token_roots(&self) -> Vec<(&'static str, PathBuf)> {
+        let pairs: [(&'static str, &Option<PathBuf>); 14] = [
+            ("<home>", &self.home),
+            ("<winDocuments>", &self.documents),
+            ("<winAppData>", &self.appdata),
+            ("<winLocalAppData>", &self.local_appdata),
+            ("<winLocalAppDataLow>", &self.local_low),
+            ("<winSavedGames>", &self.saved_games),
+            ("<winPublic>", &self.public),
+            ("<winPublicDocuments>", &self.public_documents),
+            ("<winProgramData>", &self.program_data),
+            ("<steam>", &self.steam),
+            ("<steamUserData>", &self.steam_userdata),
+            ("<xdgData>", &self.xdg_data),
+            ("<xdgConfig>", &self.xdg_config),
+            (GAME_PATH_TOKEN, &self.game_dir),
+        ];
+        pairs
+            .into_iter()
+            .filter_map(|(token, path)| path.clone().map(|path| (token, path)))
+            .collect()
+    }
+}
+
+// Only the fixture tests build a context by hand so far; the app always starts from `new()`.
+#[allow(dead_code)]
+impl PathResolutionContext {
+    /// No folders, no environment variables and no registry.
+    pub fn empty() -> Self {
+        Self {
+            home: None,
+            documents: None,
+            appdata: None,
+            local_appdata: None,
+            local_low: None,
+            saved_games: None,
+            public: None,
+            public_documents: None,
+            program_data: None,
+            steam: None,
+            steam_userdata: None,
+            xdg_data: None,
+            xdg_config: None,
+            ubisoft_launcher: None,
+            game_dir: None,
+            env: Some(HashMap::new()),
+            use_registry: false,
+        }
+    }
+
+    pub fn with_home(mut self, path: impl Into<PathBuf>) -> Self {
+        self.home = Some(path.into());
+        self
+    }
+
+    pub fn with_documents(mut self, path: impl Into<PathBuf>) -> Self {
+        self.documents = Some(path.into());
+        self
+    }
+
+    pub fn with_appdata(mut self, path: impl Into<PathBuf>) -> Self {
+        self.appdata = Some(path.into());
+        self
+    }
+
+    pub fn with_local_appdata(mut self, path: impl Into<PathBuf>) -> Self {
+        self.local_appdata = Some(path.into());
+        self
+    }
+
+    pub fn with_local_low(mut self, path: impl Into<PathBuf>) -> Self {
+        self.local_low = Some(path.into());
+        self
+    }
+
+    pub fn with_saved_games(mut self, path: impl Into<PathBuf>) -> Self {
+        self.saved_games = Some(path.into());
+        self
+    }
+
+    /// Also sets `<winPublicDocuments>` to its `Documents` folder.
+    pub fn with_public(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        self.public_documents = Some(path.join("Documents"));
+        self.public = Some(path);
+        self
+    }
+
+    pub fn with_program_data(mut self, path: impl Into<PathBuf>) -> Self {
+        self.program_data = Some(path.into());
+        self
+    }
+
+    /// Also sets `<steamUserData>` to its `userdata` folder.
+    pub fn with_steam(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        self.steam_userdata = Some(path.join("userdata"));
+        self.steam = Some(path);
+        self
+    }
+
+    pub fn with_xdg_data(mut self, path: impl Into<PathBuf>) -> Self {
+        self.xdg_data = Some(path.into());
+        self
+    }
+
+    pub fn with_xdg_config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.xdg_config = Some(path.into());
+        self
+    }
+
+    pub fn with_ubisoft_launcher(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ubisoft_launcher = Some(path.into());
+        self
+    }
+
+    /// Switches `%VAR%` expansion from the process environment to the variables given here.
+    pub fn with_env_var(mut self, key: &str, value: &str) -> Self {
+        self.env
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_registry(mut self, use_registry: bool) -> Self {
+        self.use_registry = use_registry;
+        self
+    }
 }
 
 fn resolve_path(raw_path: &str, context: &PathResolutionContext) -> Vec<PathBuf> {
@@ -254,12 +835,16 @@ fn resolve_path(raw_path: &str, context: &PathResolutionContext) -> Vec<PathBuf>
         &context.steam_userdata,
         &mut missing,
     );
+    path = replace_token(path, "<xdgData>", &context.xdg_data, &mut missing);
+    path = replace_token(path, "<xdgConfig>", &context.xdg_config, &mut missing);
+    path = replace_token(path, GAME_PATH_TOKEN, &context.game_dir, &mut missing);
+    path = replace_token(path, "<base>", &context.game_dir, &mut missing);
 
     if missing {
         return Vec::new();
     }
 
-    let path = expand_env_vars(&path);
+    let path = expand_env_vars(&path, context);
     let path = expand_tilde(&path, context.home.as_deref());
 
     if path.contains('*') || path.contains('?') {
@@ -296,7 +881,7 @@ fn replace_token(
     base
 }
 
-fn expand_env_vars(path: &str) -> String {
+fn expand_env_vars(path: &str, context: &PathResolutionContext) -> String {
     let mut out = String::new();
     let mut chars = path.chars().peekable();
     while let Some(ch) = chars.next() {
@@ -310,7 +895,7 @@ fn expand_env_vars(path: &str) -> String {
             }
             if key.is_empty() {
                 out.push('%');
-            } else if let Ok(val) = env::var(&key) {
+            } else if let Some(val) = context.env_var(&key) {
                 out.push_str(&val);
             } else {
                 out.push('%');
@@ -335,8 +920,7 @@ fn expand_tilde(path: &str, home: Option<&Path>) -> String {
     path.to_string()
 }
 
-fn heuristic_roots(game_name: &str) -> Vec<PathBuf> {
-    let context = PathResolutionContext::new();
+fn heuristic_roots(game_name: &str, context: &PathResolutionContext) -> Vec<PathBuf> {
     let variants = candidate_names(game_name);
     let mut roots = Vec::new();
 
@@ -363,7 +947,18 @@ fn heuristic_roots(game_name: &str) -> Vec<PathBuf> {
         roots.extend(find_named_paths(local_low, &variants));
     }
 
-    roots.extend(find_steam_save_paths(game_name));
+    for xdg in [&context.xdg_data, &context.xdg_config]
+        .into_iter()
+        .flatten()
+    {
+        roots.extend(find_named_paths(xdg, &variants));
+    }
+
+    roots.extend(find_steam_save_paths(game_name, context));
+    roots.extend(find_epic_save_paths(game_name, context));
+    roots.extend(find_gog_save_paths(game_name, context));
+    roots.extend(find_ubisoft_save_paths(game_name, context));
+    roots.extend(find_ea_save_paths(game_name, context));
     roots
 }
 
@@ -455,13 +1050,12 @@ fn find_windows_store_paths(local_appdata: &Path, game_name: &str) -> Vec<PathBu
     matches
 }
 
-fn find_steam_save_paths(game_name: &str) -> Vec<PathBuf> {
-    let steam_path = match find_steam_path() {
-        Some(path) => path,
-        None => return Vec::new(),
+fn find_steam_save_paths(game_name: &str, context: &PathResolutionContext) -> Vec<PathBuf> {
+    let Some(steam_path) = context.steam.as_deref() else {
+        return Vec::new();
     };
 
-    let library_paths = find_steam_library_paths(&steam_path);
+    let library_paths = find_steam_library_paths(steam_path);
     let app_ids = find_steam_app_ids(game_name, &library_paths);
     if app_ids.is_empty() {
         return Vec::new();
@@ -502,6 +1096,661 @@ fn find_steam_save_paths(game_name: &str) -> Vec<PathBuf> {
     out
 }
 
+/// An installed Epic Games Store title, read from the launcher's `*.item` manifests.
+#[derive(Debug, Clone, PartialEq)]
+struct EpicInstall {
+    display_name: String,
+    install_location: PathBuf,
+    app_name: Option<String>,
+    /// First component of `LaunchExecutable`; the Unreal project folder for UE games.
+    project: Option<String>,
+}
+
+fn find_epic_save_paths(game_name: &str, context: &PathResolutionContext) -> Vec<PathBuf> {
+    let Some(program_data) = context.program_data.as_deref() else {
+        return Vec::new();
+    };
+    let manifests_dir = program_data
+        .join("Epic")
+        .join("EpicGamesLauncher")
+        .join("Data")
+        .join("Manifests");
+    let target = normalize_name(game_name);
+    let mut out = Vec::new();
+    for install in read_epic_installs(&manifests_dir) {
+        if similarity_score(&target, &normalize_name(&install.display_name)) < 0.7 {
+            continue;
+        }
+        for path in epic_save_candidates(
+            &install,
+            context.local_appdata.as_deref(),
+            context.documents.as_deref(),
+            context.saved_games.as_deref(),
+        ) {
+            if !out.contains(&path) {
+                out.push(path);
+            }
+        }
+    }
+    out
+}
+
+fn read_epic_installs(manifests_dir: &Path) -> Vec<EpicInstall> {
+    let Ok(entries) = fs::read_dir(manifests_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("item"))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|text| parse_epic_item(&text))
+        .collect()
+}
+
+fn parse_epic_item(text: &str) -> Option<EpicInstall> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let field = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    // DLC entries point at their parent game's folder; the base game covers them.
+    if field("MainGameAppName").is_some_and(|main| Some(&main) != field("AppName").as_ref()) {
+        return None;
+    }
+    let project = field("LaunchExecutable").and_then(|exe| {
+        let mut parts = exe.split(['/', '\\']).filter(|part| !part.is_empty());
+        let first = parts.next()?;
+        // A bare executable name has no project folder in front of it.
+        parts.next().map(|_| first.to_string())
+    });
+    Some(EpicInstall {
+        display_name: field("DisplayName")?,
+        install_location: PathBuf::from(field("InstallLocation")?),
+        app_name: field("AppName"),
+        project,
+    })
+}
+
+/// Usual save locations for an Epic title: Unreal's `Saved/SaveGames` under LocalAppData or
+/// the install folder, and per-game folders named after the project, install folder or app.
+fn epic_save_candidates(
+    install: &EpicInstall,
+    local_appdata: Option<&Path>,
+    documents: Option<&Path>,
+    saved_games: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut names: Vec<String> = Vec::new();
+    let folder = install
+        .install_location
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+    for name in [install.project.clone(), folder, install.app_name.clone()]
+        .into_iter()
+        .flatten()
+    {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for name in &names {
+        if let Some(local) = local_appdata {
+            candidates.push(local.join(name).join("Saved").join("SaveGames"));
+            candidates.push(local.join(name));
+        }
+        if let Some(documents) = documents {
+            candidates.push(documents.join("My Games").join(name));
+        }
+        if let Some(saved_games) = saved_games {
+            candidates.push(saved_games.join(name));
+        }
+    }
+    if let Some(project) = &install.project {
+        candidates.push(
+            install
+                .install_location
+                .join(project)
+                .join("Saved")
+                .join("SaveGames"),
+        );
+    }
+
+    // Prefer the most specific folder: skip a parent once its `Saved/SaveGames` matched.
+    let mut out: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if candidate.is_dir() && !out.iter().any(|found| found.starts_with(&candidate)) {
+            out.push(candidate);
+        }
+    }
+    out
+}
+
+/// An installed GOG title, from GOG Galaxy's database or the `GOG.com\Games` registry keys.
+#[derive(Debug, Clone, PartialEq)]
+struct GogInstall {
+    product_id: String,
+    title: String,
+    install_location: PathBuf,
+    /// Galaxy client id from `goggame-<id>.info`; names the cloud-save folder.
+    client_id: Option<String>,
+}
+
+fn find_gog_save_paths(game_name: &str, context: &PathResolutionContext) -> Vec<PathBuf> {
+    let mut installs = context
+        .program_data
+        .as_deref()
+        .map(|program_data| {
+            read_gog_galaxy_installs(
+                &program_data
+                    .join("GOG.com")
+                    .join("Galaxy")
+                    .join("storage")
+                    .join("galaxy-2.0.db"),
+            )
+        })
+        .unwrap_or_default();
+    let registry_installs = if context.use_registry {
+        read_gog_registry_installs()
+    } else {
+        Vec::new()
+    };
+    for install in registry_installs {
+        if !installs
+            .iter()
+            .any(|known| known.product_id == install.product_id)
+        {
+            installs.push(install);
+        }
+    }
+
+    let target = normalize_name(game_name);
+    let mut out = Vec::new();
+    for mut install in installs {
+        if similarity_score(&target, &normalize_name(&install.title)) < 0.7 {
+            continue;
+        }
+        if install.client_id.is_none() {
+            install.client_id = read_gog_client_id(&install.install_location, &install.product_id);
+        }
+        for path in gog_save_candidates(
+            &install,
+            context.local_appdata.as_deref(),
+            context.documents.as_deref(),
+            context.saved_games.as_deref(),
+        ) {
+            if !out.contains(&path) {
+                out.push(path);
+            }
+        }
+    }
+    out
+}
+
+/// Installed base products (DLC live in a separate table) with their store titles.
+fn read_gog_galaxy_installs(db_path: &Path) -> Vec<GogInstall> {
+    if !db_path.is_file() {
+        return Vec::new();
+    }
+    let Ok(conn) =
+        rusqlite::Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+    else {
+        return Vec::new();
+    };
+    let query = "SELECT p.productId, p.installationPath, COALESCE(d.title, '')
+                 FROM InstalledBaseProducts p
+                 LEFT JOIN LimitedDetails d ON d.productId = p.productId";
+    let Ok(mut stmt) = conn.prepare(query) else {
+        return Vec::new();
+    };
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    });
+    let Ok(rows) = rows else {
+        return Vec::new();
+    };
+    rows.flatten()
+        .map(|(product_id, path, title)| {
+            let install_location = PathBuf::from(path);
+            let title = if title.trim().is_empty() {
+                install_location
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            } else {
+                title
+            };
+            GogInstall {
+                product_id: product_id.to_string(),
+                title,
+                install_location,
+                client_id: None,
+            }
+        })
+        .collect()
+}
+
+/// Games installed by the offline GOG installers register under `GOG.com\Games\<id>` even
+/// without Galaxy.
+fn read_gog_registry_installs() -> Vec<GogInstall> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let mut out = Vec::new();
+        for root in [
+            "SOFTWARE\\WOW6432Node\\GOG.com\\Games",
+            "SOFTWARE\\GOG.com\\Games",
+        ] {
+            let Ok(games) = hklm.open_subkey(root) else {
+                continue;
+            };
+            for product_id in games.enum_keys().flatten() {
+                let Ok(key) = games.open_subkey(&product_id) else {
+                    continue;
+                };
+                let (Ok(title), Ok(path)) = (
+                    key.get_value::<String, _>("gameName"),
+                    key.get_value::<String, _>("path"),
+                ) else {
+                    continue;
+                };
+                if out
+                    .iter()
+                    .any(|known: &GogInstall| known.product_id == product_id)
+                {
+                    continue;
+                }
+                out.push(GogInstall {
+                    product_id,
+                    title,
+                    install_location: PathBuf::from(path),
+                    client_id: None,
+                });
+            }
+        }
+        out
+    }
+    #[cfg(not(target_os = "windows"))]
+    Vec::new()
+}
+
+fn read_gog_client_id(install_location: &Path, product_id: &str) -> Option<String> {
+    let text =
+        fs::read_to_string(install_location.join(format!("goggame-{}.info", product_id))).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&text).ok()?;
+    value
+        .get("clientId")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// GOG's cloud-save folder (`GOG.com\Galaxy\Applications\<clientId>\Storage`) first, then the
+/// usual per-game folders named after the title or install folder.
+fn gog_save_candidates(
+    install: &GogInstall,
+    local_appdata: Option<&Path>,
+    documents: Option<&Path>,
+    saved_games: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let (Some(local), Some(client_id)) = (local_appdata, &install.client_id) {
+        let storage = local
+            .join("GOG.com")
+            .join("Galaxy")
+            .join("Applications")
+            .join(client_id)
+            .join("Storage");
+        candidates.push(storage.join("Shared").join("Files"));
+        candidates.push(storage);
+    }
+
+    let mut names: Vec<String> = vec![sanitize_name(&install.title)];
+    if let Some(folder) = install.install_location.file_name() {
+        let folder = folder.to_string_lossy().to_string();
+        if !names.contains(&folder) {
+            names.push(folder);
+        }
+    }
+    for name in names.iter().filter(|name| !name.is_empty()) {
+        if let Some(documents) = documents {
+            candidates.push(documents.join("My Games").join(name));
+        }
+        if let Some(saved_games) = saved_games {
+            candidates.push(saved_games.join(name));
+        }
+        if let Some(local) = local_appdata {
+            candidates.push(local.join(name));
+        }
+    }
+
+    let mut out: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if candidate.is_dir() && !out.iter().any(|found| found.starts_with(&candidate)) {
+            out.push(candidate);
+        }
+    }
+    out
+}
+
+/// A Ubisoft Connect title; `id` names its folder under `savegames/<user>`.
+#[derive(Debug, Clone, PartialEq)]
+struct UbisoftGame {
+    id: String,
+    name: String,
+}
+
+fn find_ubisoft_save_paths(game_name: &str, context: &PathResolutionContext) -> Vec<PathBuf> {
+    let Some(launcher) = context.ubisoft_launcher.as_deref() else {
+        return Vec::new();
+    };
+    let configurations = launcher
+        .join("cache")
+        .join("configuration")
+        .join("configurations");
+    let mut games = fs::read(configurations)
+        .map(|bytes| parse_ubisoft_configurations(&bytes))
+        .unwrap_or_default();
+    let registry_games = if context.use_registry {
+        read_ubisoft_registry_installs()
+    } else {
+        Vec::new()
+    };
+    for game in registry_games {
+        if !games.iter().any(|known| known.id == game.id) {
+            games.push(game);
+        }
+    }
+
+    let target = normalize_name(game_name);
+    let mut ids: Vec<String> = Vec::new();
+    for game in games {
+        if similarity_score(&target, &normalize_name(&game.name)) >= 0.7 && !ids.contains(&game.id)
+        {
+            ids.push(game.id);
+        }
+    }
+    ubisoft_save_candidates(&launcher.join("savegames"), &ids)
+}
+
+/// The launcher caches every owned game's YAML configuration in one binary file. Each
+/// document starts with `root:`, carries the display name (directly or as a localization
+/// key) and registers its install under `Launcher\Installs\<id>`, which is the save id too.
+fn parse_ubisoft_configurations(bytes: &[u8]) -> Vec<UbisoftGame> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut games: Vec<UbisoftGame> = Vec::new();
+    for document in text.split("root:").skip(1) {
+        let Some(id) = ubisoft_install_id(document) else {
+            continue;
+        };
+        let Some(name) = yaml_value(document, "name") else {
+            continue;
+        };
+        // `name: l1` refers to `localizations: default: l1: "Far Cry 5"`.
+        let name = document
+            .find("localizations:")
+            .and_then(|start| yaml_value(&document[start..], &name))
+            .unwrap_or(name);
+        if !games.iter().any(|known| known.id == id) {
+            games.push(UbisoftGame { id, name });
+        }
+    }
+    games
+}
+
+fn ubisoft_install_id(document: &str) -> Option<String> {
+    let start = document.find("Installs\\")? + "Installs\\".len();
+    let id: String = document[start..]
+        .trim_start_matches('\\')
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    (!id.is_empty()).then_some(id)
+}
+
+/// First `key: value` line in a YAML fragment, without quotes.
+fn yaml_value(text: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}:", key);
+    text.lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| value.trim().trim_matches(['"', '\'']).trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn read_ubisoft_registry_installs() -> Vec<UbisoftGame> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let Ok(installs) = hklm.open_subkey("SOFTWARE\\WOW6432Node\\Ubisoft\\Launcher\\Installs")
+        else {
+            return Vec::new();
+        };
+        installs
+            .enum_keys()
+            .flatten()
+            .filter_map(|id| {
+                let dir: String = installs
+                    .open_subkey(&id)
+                    .ok()?
+                    .get_value("InstallDir")
+                    .ok()?;
+                let name = Path::new(dir.trim_end_matches(['/', '\\']))
+                    .file_name()?
+                    .to_string_lossy()
+                    .to_string();
+                Some(UbisoftGame { id, name })
+            })
+            .collect()
+    }
+    #[cfg(not(target_os = "windows"))]
+    Vec::new()
+}
+
+fn find_ubisoft_launcher_path() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        if let Ok(key) = hklm.open_subkey("SOFTWARE\\WOW6432Node\\Ubisoft\\Launcher") {
+            if let Ok(path) = key.get_value::<String, _>("InstallDir") {
+                return Some(PathBuf::from(path));
+            }
+        }
+    }
+
+    let path = PathBuf::from("C:\\Program Files (x86)\\Ubisoft\\Ubisoft Game Launcher");
+    path.exists().then_some(path)
+}
+
+/// `savegames/<user>/<id>` for every local Ubisoft account that has saves for one of `ids`.
+fn ubisoft_save_candidates(savegames_root: &Path, ids: &[String]) -> Vec<PathBuf> {
+    if ids.is_empty() {
+        return Vec::new();
+    }
+    let Ok(users) = fs::read_dir(savegames_root) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for user in users.flatten() {
+        let user_path = user.path();
+        if !user_path.is_dir() {
+            continue;
+        }
+        for id in ids {
+            let path = user_path.join(id);
+            if path.is_dir() {
+                out.push(path);
+            }
+        }
+    }
+    out.sort();
+    out
+}
+
+/// An EA App (or older Origin) install. EA titles save under folders named after the game,
+/// so the registry key name and the titles in `__Installer\installerdata.xml` give the
+/// names to look for.
+#[derive(Debug, Clone, PartialEq)]
+struct EaInstall {
+    titles: Vec<String>,
+    install_location: PathBuf,
+}
+
+fn find_ea_save_paths(game_name: &str, context: &PathResolutionContext) -> Vec<PathBuf> {
+    let target = normalize_name(game_name);
+    let mut names = candidate_names(game_name);
+    let installs = if context.use_registry {
+        read_ea_registry_installs()
+    } else {
+        Vec::new()
+    };
+    for install in installs {
+        let mut titles = install.titles.clone();
+        titles.extend(
+            fs::read_to_string(
+                install
+                    .install_location
+                    .join("__Installer")
+                    .join("installerdata.xml"),
+            )
+            .map(|xml| parse_ea_installer_titles(&xml))
+            .unwrap_or_default(),
+        );
+        if !titles
+            .iter()
+            .any(|title| similarity_score(&target, &normalize_name(title)) >= 0.7)
+        {
+            continue;
+        }
+        let folder = install
+            .install_location
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        for name in titles
+            .iter()
+            .map(|title| sanitize_name(title))
+            .chain(folder)
+        {
+            if !name.is_empty() && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    ea_save_candidates(
+        &names,
+        context.documents.as_deref(),
+        context.local_appdata.as_deref(),
+    )
+}
+
+/// `<gameTitle locale="..">Title</gameTitle>` values, English first as they come.
+fn parse_ea_installer_titles(xml: &str) -> Vec<String> {
+    let mut titles: Vec<String> = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<gameTitle") {
+        rest = &rest[start..];
+        let Some(open_end) = rest.find('>') else {
+            break;
+        };
+        let Some(close) = rest.find("</gameTitle>") else {
+            break;
+        };
+        if open_end < close {
+            let title = rest[open_end + 1..close].trim().to_string();
+            if !title.is_empty() && !titles.contains(&title) {
+                titles.push(title);
+            }
+        }
+        rest = &rest[close + "</gameTitle>".len()..];
+    }
+    titles
+}
+
+fn read_ea_registry_installs() -> Vec<EaInstall> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let mut out = Vec::new();
+        for root in [
+            "SOFTWARE\\WOW6432Node\\Electronic Arts",
+            "SOFTWARE\\WOW6432Node\\EA Games",
+            "SOFTWARE\\Electronic Arts",
+        ] {
+            let Ok(games) = hklm.open_subkey(root) else {
+                continue;
+            };
+            for title in games.enum_keys().flatten() {
+                let Ok(key) = games.open_subkey(&title) else {
+                    continue;
+                };
+                let Ok(path) = key.get_value::<String, _>("Install Dir") else {
+                    continue;
+                };
+                let install_location = PathBuf::from(path.trim_end_matches(['/', '\\']));
+                if out
+                    .iter()
+                    .any(|known: &EaInstall| known.install_location == install_location)
+                {
+                    continue;
+                }
+                out.push(EaInstall {
+                    titles: vec![title],
+                    install_location,
+                });
+            }
+        }
+        out
+    }
+    #[cfg(not(target_os = "windows"))]
+    Vec::new()
+}
+
+fn ea_save_candidates(
+    names: &[String],
+    documents: Option<&Path>,
+    local_appdata: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut bases = Vec::new();
+    if let Some(documents) = documents {
+        bases.push(documents.join("EA Games"));
+        bases.push(documents.join("Electronic Arts"));
+    }
+    if let Some(local) = local_appdata {
+        bases.push(local.join("Electronic Arts"));
+    }
+    let mut out = Vec::new();
+    for base in bases {
+        for path in find_named_paths(&base, names) {
+            if path.is_dir() && !out.contains(&path) {
+                out.push(path);
+            }
+        }
+    }
+    out
+}
+
 fn find_steam_library_paths(steam_path: &Path) -> Vec<PathBuf> {
     let mut paths = VecDeque::new();
     let mut seen = HashSet::new();
@@ -616,14 +1865,54 @@ fn find_steam_path() -> Option<PathBuf> {
         }
     }
 
-    let paths = vec!["C:\\Program Files (x86)\\Steam", "C:\\Program Files\\Steam"];
-    for path in paths {
-        let pb = PathBuf::from(path);
-        if pb.exists() {
-            return Some(pb);
-        }
+    steam_install_candidates()
+        .into_iter()
+        .find(|path| path.exists())
+}
+
+#[cfg(target_os = "windows")]
+fn steam_install_candidates() -> Vec<PathBuf> {
+    vec![
+        PathBuf::from("C:\\Program Files (x86)\\Steam"),
+        PathBuf::from("C:\\Program Files\\Steam"),
+    ]
+}
+
+/// Native, Debian-style symlinked and Flatpak Steam installs.
+#[cfg(not(target_os = "windows"))]
+fn steam_install_candidates() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    vec![
+        home.join(".local").join("share").join("Steam"),
+        home.join(".steam").join("steam"),
+        home.join(".var")
+            .join("app")
+            .join("com.valvesoftware.Steam")
+            .join(".local")
+            .join("share")
+            .join("Steam"),
+    ]
+}
+
+/// The Windows user folder inside a prefix's `drive_c`: `steamuser` under Proton,
+/// otherwise the first non-`Public` user Wine created.
+fn wine_user_dir(drive_c: &Path) -> PathBuf {
+    let users = drive_c.join("users");
+    let steamuser = users.join("steamuser");
+    if steamuser.is_dir() {
+        return steamuser;
     }
-    None
+    fs::read_dir(&users)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .find(|path| path.is_dir() && !path.ends_with("Public"))
+        })
+        .unwrap_or(steamuser)
 }
 
 #[cfg(test)]
@@ -644,15 +1933,113 @@ mod tests {
         fs::write(&file_b, b"beta").expect("write file b");
 
         let save_path = save_dir.to_string_lossy().to_string();
-        let discovery = locate_game_saves("Test Game", None, Some(&save_path))
-            .expect("locate saves")
-            .expect("discovery present");
+        let discovery = locate_game_saves(
+            "Test Game",
+            None,
+            SaveLookup {
+                override_path: Some(&save_path),
+                extra_roots: &[],
+                game_dir: None,
+                manifest_override: None,
+                manifest_key: None,
+            },
+        )
+        .expect("locate saves")
+        .expect("discovery present");
 
         assert_eq!(discovery.files.len(), 2);
         assert!(discovery.total_size > 0);
         assert_eq!(discovery.roots.len(), 1);
     }
 
+    #[test]
+    fn extra_roots_join_the_override_and_exclusions_drop_files() {
+        let dir = tempdir().expect("tempdir");
+        let saves = dir.path().join("Documents").join("Game");
+        let config = dir.path().join("AppData").join("Game");
+        fs::create_dir_all(saves.join("cache")).expect("create saves");
+        fs::create_dir_all(&config).expect("create config");
+        fs::write(saves.join("slot1.sav"), b"alpha").expect("write slot");
+        fs::write(saves.join("cache").join("shaders.bin"), b"cache").expect("write cache");
+        fs::write(config.join("settings.ini"), b"[video]").expect("write config");
+
+        let save_path = saves.to_string_lossy().to_string();
+        let extra_roots = vec![
+            ConfiguredRoot {
+                path: config.to_string_lossy().to_string(),
+                exclude: false,
+            },
+            ConfiguredRoot {
+                path: dir.path().join("missing").to_string_lossy().to_string(),
+                exclude: false,
+            },
+            ConfiguredRoot {
+                path: saves.join("cache").to_string_lossy().to_string(),
+                exclude: true,
+            },
+        ];
+        let lookup = SaveLookup {
+            override_path: Some(&save_path),
+            extra_roots: &extra_roots,
+            game_dir: None,
+            manifest_override: None,
+            manifest_key: None,
+        };
+        let discovery = locate_game_saves("Split Game", None, lookup)
+            .expect("locate saves")
+            .expect("discovery present");
+
+        assert_eq!(discovery.roots.len(), 2);
+        let mut files: Vec<PathBuf> = discovery.files.iter().map(|f| f.path.clone()).collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![config.join("settings.ini"), saves.join("slot1.sav")]
+        );
+        assert_eq!(discovery.total_size, 12);
+        assert!(lookup.has_overrides());
+        assert!(!lookup.without_override().has_overrides());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_policies_handle_cycles_and_links() {
+        let dir = tempdir().expect("tempdir");
+        let save_dir = dir.path().join("saves");
+        fs::create_dir_all(save_dir.join("nested")).expect("create save dir");
+        fs::write(save_dir.join("nested").join("slot1.sav"), b"alpha").expect("write slot");
+        // Cycle back to the save root: must not be walked forever when following links.
+        std::os::unix::fs::symlink(&save_dir, save_dir.join("nested").join("loop"))
+            .expect("create loop link");
+
+        let save_path = save_dir.to_string_lossy().to_string();
+        let lookup = SaveLookup {
+            override_path: Some(&save_path),
+            extra_roots: &[],
+            game_dir: None,
+            manifest_override: None,
+            manifest_key: None,
+        };
+        let locate = |policy| {
+            locate_game_saves_with_symlinks("Link Game", None, lookup, policy)
+                .expect("locate saves")
+                .expect("discovery present")
+                .files
+        };
+
+        assert_eq!(locate(SymlinkPolicy::Skip).len(), 1);
+        assert_eq!(locate(SymlinkPolicy::Follow).len(), 1);
+
+        let stored = locate(SymlinkPolicy::StoreAsLink);
+        assert_eq!(stored.len(), 2);
+        let link = stored
+            .iter()
+            .find(|file| file.link_target.is_some())
+            .expect("link entry");
+        assert_eq!(link.size, 0);
+        assert_eq!(link.link_target.as_deref(), Some(save_dir.as_path()));
+    }
+
     #[test]
     fn locate_game_saves_with_manifest_entry() {
         let dir = tempdir().expect("tempdir");
@@ -676,7 +2063,7 @@ mod tests {
         );
         let manifest = SqobaManifest::from_games(games);
 
-        let discovery = locate_game_saves("Manifest Game", Some(&manifest), None)
+        let discovery = locate_game_saves("Manifest Game", Some(&manifest), SaveLookup::default())
             .expect("locate saves")
             .expect("discovery present");
 
@@ -690,7 +2077,366 @@ mod tests {
         let dir = tempdir().expect("tempdir");
         let missing_path = dir.path().join("missing");
         let missing_path = missing_path.to_string_lossy().to_string();
-        let result = locate_game_saves("Missing", None, Some(&missing_path));
+        let lookup = SaveLookup {
+            override_path: Some(&missing_path),
+            extra_roots: &[],
+            game_dir: None,
+            manifest_override: None,
+            manifest_key: None,
+        };
+        let result = locate_game_saves("Missing", None, lookup);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn locate_game_saves_expands_game_path_token() {
+        let dir = tempdir().expect("tempdir");
+        let game_dir = dir.path().join("Game");
+        let save_dir = game_dir.join("Saves");
+        fs::create_dir_all(&save_dir).expect("create save dir");
+        fs::write(save_dir.join("slot1.sav"), b"alpha").expect("write file");
+
+        let override_path = format!("{}/Saves", GAME_PATH_TOKEN);
+        let lookup = SaveLookup {
+            override_path: Some(&override_path),
+            extra_roots: &[],
+            game_dir: Some(&game_dir),
+            manifest_override: None,
+            manifest_key: None,
+        };
+        let discovery = locate_game_saves("Token Game", None, lookup)
+            .expect("locate saves")
+            .expect("discovery present");
+        assert_eq!(discovery.files.len(), 1);
+
+        let mut files = HashMap::new();
+        files.insert("save".to_string(), vec!["<base>/Saves".to_string()]);
+        let mut games = HashMap::new();
+        games.insert(
+            "Token Game".to_string(),
+            SqobaGame {
+                files: Some(files),
+                registry: None,
+            },
+        );
+        let manifest = SqobaManifest::from_games(games);
+        let roots =
+            locate_game_save_roots("Token Game", Some(&manifest), lookup.without_override())
+                .expect("locate roots");
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].path, game_dir.join("Saves"));
+
+        let unresolved = SaveLookup {
+            override_path: Some(&override_path),
+            extra_roots: &[],
+            game_dir: None,
+            manifest_override: None,
+            manifest_key: None,
+        };
+        assert!(locate_game_saves("Token Game", None, unresolved).is_err());
+    }
+
+    #[test]
+    fn epic_manifests_map_installs_to_save_folders() {
+        let dir = tempdir().expect("tempdir");
+        let manifests = dir.path().join("Manifests");
+        let install = dir.path().join("Epic Games").join("Satisfactory");
+        fs::create_dir_all(&manifests).expect("create manifests");
+        fs::create_dir_all(&install).expect("create install dir");
+        let item = serde_json::json!({
+            "DisplayName": "Satisfactory",
+            "InstallLocation": install.to_string_lossy(),
+            "AppName": "CrabEA",
+            "MainGameAppName": "CrabEA",
+            "LaunchExecutable": "FactoryGame/Binaries/Win64/FactoryGame-Win64-Shipping.exe"
+        });
+        fs::write(manifests.join("A1B2.item"), item.to_string()).expect("write item");
+        let dlc = serde_json::json!({
+            "DisplayName": "Satisfactory Soundtrack",
+            "InstallLocation": install.to_string_lossy(),
+            "AppName": "CrabOST",
+            "MainGameAppName": "CrabEA"
+        });
+        fs::write(manifests.join("C3D4.item"), dlc.to_string()).expect("write dlc");
+        fs::write(manifests.join("notes.txt"), "ignored").expect("write other");
+
+        let installs = read_epic_installs(&manifests);
+        assert_eq!(installs.len(), 1);
+        assert_eq!(installs[0].project.as_deref(), Some("FactoryGame"));
+        assert_eq!(installs[0].app_name.as_deref(), Some("CrabEA"));
+
+        let local = dir.path().join("Local");
+        let save_games = local.join("FactoryGame").join("Saved").join("SaveGames");
+        fs::create_dir_all(&save_games).expect("create save games");
+        let documents = dir.path().join("Documents");
+        let my_games = documents.join("My Games").join("Satisfactory");
+        fs::create_dir_all(&my_games).expect("create my games");
+
+        let roots = epic_save_candidates(&installs[0], Some(&local), Some(&documents), None);
+        assert_eq!(roots, vec![save_games, my_games]);
+    }
+
+    #[test]
+    fn gog_galaxy_database_and_cloud_storage_resolve_saves() {
+        let dir = tempdir().expect("tempdir");
+        let install = dir.path().join("GOG Games").join("Disco Elysium");
+        fs::create_dir_all(&install).expect("create install dir");
+        fs::write(
+            install.join("goggame-1771589310.info"),
+            serde_json::json!({ "gameId": "1771589310", "clientId": "53129786018" }).to_string(),
+        )
+        .expect("write info");
+
+        let db_path = dir.path().join("galaxy-2.0.db");
+        let conn = rusqlite::Connection::open(&db_path).expect("open db");
+        conn.execute_batch(
+            "CREATE TABLE InstalledBaseProducts (productId INTEGER, installationPath TEXT);
+             CREATE TABLE LimitedDetails (productId INTEGER, title TEXT);",
+        )
+        .expect("create tables");
+        conn.execute(
+            "INSERT INTO InstalledBaseProducts VALUES (1771589310, ?1)",
+            [install.to_string_lossy().to_string()],
+        )
+        .expect("insert product");
+        conn.execute(
+            "INSERT INTO LimitedDetails VALUES (1771589310, 'Disco Elysium - The Final Cut')",
+            [],
+        )
+        .expect("insert details");
+        drop(conn);
+
+        let mut installs = read_gog_galaxy_installs(&db_path);
+        assert_eq!(installs.len(), 1);
+        assert_eq!(installs[0].title, "Disco Elysium - The Final Cut");
+        installs[0].client_id = read_gog_client_id(&install, &installs[0].product_id);
+        assert_eq!(installs[0].client_id.as_deref(), Some("53129786018"));
+
+        let local = dir.path().join("Local");
+        let cloud = local
+            .join("GOG.com")
+            .join("Galaxy")
+            .join("Applications")
+            .join("53129786018")
+            .join("Storage")
+            .join("Shared")
+            .join("Files");
+        fs::create_dir_all(&cloud).expect("create cloud dir");
+        let documents = dir.path().join("Documents");
+        let my_games = documents.join("My Games").join("Disco Elysium");
+        fs::create_dir_all(&my_games).expect("create my games");
+
+        let roots = gog_save_candidates(&installs[0], Some(&local), Some(&documents), None);
+        assert_eq!(roots, vec![cloud, my_games]);
+    }
+
+    #[test]
+    fn ubisoft_configurations_and_ea_installer_data_resolve_saves() {
+        let mut cache = b"\x0a\x8f\x02\x08\xd3\x2b".to_vec();
+        cache.extend_from_slice(
+            b"version: 2.0\nroot:\n  name: l1\n  start_game:\n    offline:\n      executables:\n      - working_directory:\n          register: HKEY_LOCAL_MACHINE\\SOFTWARE\\Ubisoft\\Launcher\\Installs\\5595\\InstallDir\nlocalizations:\n  default:\n    l1: \"Far Cry 5\"\n",
+        );
+        cache.extend_from_slice(b"\x12\x04\x00");
+        cache.extend_from_slice(b"version: 2.0\nroot:\n  name: Rayman Legends\n");
+        let games = parse_ubisoft_configurations(&cache);
+        assert_eq!(
+            games,
+            vec![UbisoftGame {
+                id: "5595".to_string(),
+                name: "Far Cry 5".to_string(),
+            }]
+        );
+
+        let dir = tempdir().expect("tempdir");
+        let savegames = dir.path().join("savegames");
+        let user_save = savegames
+            .join("9d2a7c1e-1111-4a4b-9c6d-0123456789ab")
+            .join("5595");
+        fs::create_dir_all(&user_save).expect("create ubisoft save");
+        fs::create_dir_all(savegames.join("other-user").join("720")).expect("create other");
+        assert_eq!(
+            ubisoft_save_candidates(&savegames, &["5595".to_string()]),
+            vec![user_save]
+        );
+
+        let titles = parse_ea_installer_titles(
+            "<DiPManifest><gameTitles>\
+             <gameTitle locale=\"en_US\">Mass Effect Legendary Edition</gameTitle>\
+             <gameTitle locale=\"ru_RU\">Mass Effect Legendary Edition</gameTitle>\
+             </gameTitles></DiPManifest>",
+        );
+        assert_eq!(titles, vec!["Mass Effect Legendary Edition".to_string()]);
+
+        let documents = dir.path().join("Documents");
+        let local = dir.path().join("Local");
+        let ea_games = documents
+            .join("EA Games")
+            .join("Mass Effect Legendary Edition");
+        let local_ea = local
+            .join("Electronic Arts")
+            .join("Mass Effect Legendary Edition");
+        fs::create_dir_all(&ea_games).expect("create ea games");
+        fs::create_dir_all(&local_ea).expect("create local ea");
+        assert_eq!(
+            ea_save_candidates(&titles, Some(&documents), Some(&local)),
+            vec![ea_games, local_ea]
+        );
+    }
+
+    #[test]
+    fn fixture_context_resolves_documents_steam_and_env_paths() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path();
+        let documents = root.join("Documents");
+        let steam = root.join("Steam");
+        let my_games = documents.join("My Games").join("Fixture Quest");
+        let remote = steam
+            .join("userdata")
+            .join("77")
+            .join("4242")
+            .join("remote");
+        for folder in [&my_games, &remote, &steam.join("steamapps")] {
+            fs::create_dir_all(folder).expect("create fixture folder");
+        }
+        fs::write(my_games.join("profile.sav"), b"alpha").expect("write documents save");
+        fs::write(remote.join("slot1.sav"), b"beta").expect("write steam save");
+        fs::write(
+            steam.join("steamapps").join("appmanifest_4242.acf"),
+            "\"AppState\"\n{\n\t\"appid\"\t\"4242\"\n\t\"name\"\t\"Fixture Quest\"\n}\n",
+        )
+        .expect("write app manifest");
+
+        let context = PathResolutionContext::empty()
+            .with_documents(&documents)
+            .with_steam(&steam);
+        let discovery = locate_game_saves_in(
+            &context,
+            "Fixture Quest",
+            None,
+            SaveLookup::default(),
+            SymlinkPolicy::default(),
+        )
+        .expect("locate saves")
+        .expect("discovery present");
+        let mut roots: Vec<PathBuf> = discovery.roots.iter().map(|r| r.path.clone()).collect();
+        roots.sort();
+        assert_eq!(roots, vec![my_games.clone(), remote]);
+        assert_eq!(discovery.files.len(), 2);
+        assert!(locate_game_saves_in(
+            &PathResolutionContext::empty(),
+            "Fixture Quest",
+            None,
+            SaveLookup::default(),
+            SymlinkPolicy::default(),
+        )
+        .expect("locate saves")
+        .is_none());
+
+        let mut files = HashMap::new();
+        files.insert(
+            "save".to_string(),
+            vec!["%FIXTURE_SAVES%/Fixture Quest".to_string()],
+        );
+        let mut games = HashMap::new();
+        games.insert(
+            "Fixture Quest".to_string(),
+            SqobaGame {
+                files: Some(files),
+                registry: None,
+            },
+        );
+        let manifest = SqobaManifest::from_games(games);
+        let context = PathResolutionContext::empty().with_env_var(
+            "FIXTURE_SAVES",
+            &documents.join("My Games").to_string_lossy(),
+        );
+        let roots = locate_game_save_roots_in(
+            &context,
+            "Fixture Quest",
+            Some(&manifest),
+            SaveLookup::default(),
+        )
+        .expect("locate roots");
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].path, my_games);
+    }
+
+    #[test]
+    fn confirmed_wiki_paths_become_candidate_roots() {
+        let dir = tempdir().expect("tempdir");
+        let appdata = dir.path().join("AppData");
+        let wiki_root = appdata.join("Studio").join("Profiles");
+        fs::create_dir_all(wiki_root.join("1234")).expect("create wiki root");
+        let context = PathResolutionContext::empty().with_appdata(&appdata);
+
+        let wiki_paths = vec![
+            "<winAppData>/Studio/Profiles/*".to_string(),
+            "<winSavedGames>/Fixture Quest".to_string(),
+        ];
+        let roots = candidate_save_roots_in(&context, "Fixture Quest", None, &wiki_paths, None);
+        assert_eq!(roots, vec![(wiki_root.join("1234"), "pcgamingwiki")]);
+        assert!(candidate_save_roots_in(&context, "Fixture Quest", None, &[], None).is_empty());
+    }
+
+    #[test]
+    fn proton_prefixes_and_xdg_dirs_resolve_manifest_paths() {
+        let dir = tempdir().expect("tempdir");
+        let steam = dir.path().join("Steam");
+        let steamapps = steam.join("steamapps");
+        let steamuser = steamapps
+            .join("compatdata")
+            .join("4242")
+            .join("pfx")
+            .join("drive_c")
+            .join("users")
+            .join("steamuser");
+        let roaming = steamuser.join("AppData").join("Roaming").join("Studio");
+        let native = dir.path().join("share").join("fixture-quest");
+        for folder in [&roaming, &native, &steamuser.join("Documents")] {
+            fs::create_dir_all(folder).expect("create fixture folder");
+        }
+        fs::write(
+            steamapps.join("appmanifest_4242.acf"),
+            "\"AppState\"\n{\n\t\"name\"\t\"Fixture Quest\"\n}\n",
+        )
+        .expect("write app manifest");
+
+        let mut files = HashMap::new();
+        files.insert(
+            "save".to_string(),
+            vec![
+                "<winAppData>/Studio".to_string(),
+                "<xdgData>/fixture-quest".to_string(),
+            ],
+        );
+        let mut games = HashMap::new();
+        games.insert(
+            "Fixture Quest".to_string(),
+            SqobaGame {
+                files: Some(files),
+                registry: None,
+            },
+        );
+        let manifest = SqobaManifest::from_games(games);
+        let context = PathResolutionContext::empty()
+            .with_steam(&steam)
+            .with_xdg_data(dir.path().join("share"));
+        let mut roots: Vec<PathBuf> = locate_game_save_roots_in(
+            &context,
+            "Fixture Quest",
+            Some(&manifest),
+            SaveLookup::default(),
+        )
+        .expect("locate roots")
+        .into_iter()
+        .map(|root| root.path)
+        .collect();
+        roots.sort();
+        let mut expected = vec![native, roaming];
+        expected.sort();
+        assert_eq!(roots, expected);
+
+        let prefixes = context.proton_prefix_contexts("Other Game");
+        assert!(prefixes.is_empty());
+    }
 }
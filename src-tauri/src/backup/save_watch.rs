@@ -0,0 +1,241 @@
+//! Watches each game's resolved save roots and emits `save:changed` once a game has stopped
+//! writing for a moment, so a new save can be picked up without waiting for the game to exit.
+
+use chrono::Utc;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Quiet period after the last write before a change is reported; games often write a save
+/// as several files in quick succession.
+pub const DEFAULT_DEBOUNCE_SECONDS: i32 = 10;
+/// How often the watched roots are compared with the library.
+const RESYNC_INTERVAL: Duration = Duration::from_secs(60);
+const TICK: Duration = Duration::from_secs(1);
+/// Paths listed per event; the rest only count towards `change_count`.
+const MAX_REPORTED_PATHS: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SaveChangedEvent {
+    pub game_id: String,
+    pub paths: Vec<String>,
+    pub change_count: usize,
+    pub changed_at: String,
+}
+
+#[derive(Debug)]
+struct PendingChange {
+    last_event: Instant,
+    paths: BTreeSet<PathBuf>,
+    count: usize,
+}
+
+/// Collects filesystem events per game and releases them once the game has been quiet for
+/// the debounce period.
+#[derive(Debug)]
+pub struct SaveChangeDebouncer {
+    quiet: Duration,
+    pending: HashMap<String, PendingChange>,
+}
+
+impl SaveChangeDebouncer {
+    pub fn new(quiet: Duration) -> Self {
+        Self {
+            quiet,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn set_quiet(&mut self, quiet: Duration) {
+        self.quiet = quiet;
+    }
+
+    pub fn record(&mut self, game_id: &str, path: &Path, now: Instant) {
+        let change = self
+            .pending
+            .entry(game_id.to_string())
+            .or_insert_with(|| PendingChange {
+                last_event: now,
+                paths: BTreeSet::new(),
+                count: 0,
+            });
+        change.last_event = now;
+        change.paths.insert(path.to_path_buf());
+        change.count += 1;
+    }
+
+    /// Games whose last event is at least the quiet period old, removed from the pending set.
+    pub fn take_due(&mut self, now: Instant) -> Vec<SaveChangedEvent> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, change)| now.duration_since(change.last_event) >= self.quiet)
+            .map(|(game_id, _)| game_id.clone())
+            .collect();
+        let mut events: Vec<SaveChangedEvent> = due
+            .into_iter()
+            .filter_map(|game_id| {
+                let change = self.pending.remove(&game_id)?;
+                Some(SaveChangedEvent {
+                    game_id,
+                    paths: change
+                        .paths
+                        .iter()
+                        .take(MAX_REPORTED_PATHS)
+                        .map(|path| path.to_string_lossy().to_string())
+                        .collect(),
+                    change_count: change.count,
+                    changed_at: Utc::now().to_rfc3339(),
+                })
+            })
+            .collect();
+        events.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+        events
+    }
+}
+
+/// The game owning `path`: the one whose root is the longest prefix of it.
+pub fn game_for_path<'a>(roots: &'a [(PathBuf, String)], path: &Path) -> Option<&'a str> {
+    roots
+        .iter()
+        .filter(|(root, _)| path.starts_with(root))
+        .max_by_key(|(root, _)| root.components().count())
+        .map(|(_, game_id)| game_id.as_str())
+}
+
+fn is_write(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    )
+}
+
+/// Brings the watcher in line with `wanted`, leaving already-watched roots alone.
+fn sync_watches(
+    watcher: &mut RecommendedWatcher,
+    watched: &mut Vec<(PathBuf, String)>,
+    wanted: Vec<(PathBuf, String)>,
+) {
+    for (root, _) in watched.iter() {
+        if !wanted.iter().any(|(path, _)| path == root) {
+            let _ = watcher.unwatch(root);
+        }
+    }
+    watched.retain(|(root, _)| wanted.iter().any(|(path, _)| path == root));
+    for (root, game_id) in wanted {
+        if let Some(entry) = watched.iter_mut().find(|(path, _)| *path == root) {
+            entry.1 = game_id;
+            continue;
+        }
+        let mode = if root.is_dir() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        match watcher.watch(&root, mode) {
+            Ok(()) => watched.push((root, game_id)),
+            Err(e) => eprintln!("Failed to watch save root {}: {}", root.display(), e),
+        }
+    }
+}
+
+pub fn start_save_watcher(app: AppHandle) {
+    thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Save watcher unavailable: {}", e);
+                return;
+            }
+        };
+        let mut watched: Vec<(PathBuf, String)> = Vec::new();
+        let mut debouncer = SaveChangeDebouncer::new(Duration::ZERO);
+        let mut last_sync: Option<Instant> = None;
+
+        loop {
+            if last_sync.is_none_or(|at| at.elapsed() >= RESYNC_INTERVAL) {
+                sync_watches(&mut watcher, &mut watched, super::watched_save_roots());
+                let seconds =
+                    super::get_setting_i32("save_watch_debounce_seconds", DEFAULT_DEBOUNCE_SECONDS);
+                debouncer.set_quiet(Duration::from_secs(seconds.max(1) as u64));
+                last_sync = Some(Instant::now());
+            }
+
+            match rx.recv_timeout(TICK) {
+                Ok(Ok(event)) if is_write(&event.kind) => {
+                    let now = Instant::now();
+                    for path in &event.paths {
+                        if let Some(game_id) = game_for_path(&watched, path) {
+                            debouncer.record(game_id, path, now);
+                        }
+                    }
+                }
+                Ok(Ok(_)) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Ok(Err(e)) => eprintln!("Save watcher error: {}", e),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            }
+
+            for event in debouncer.take_due(Instant::now()) {
+                let _ = app.emit("save:changed", event);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_are_grouped_per_game_until_the_game_goes_quiet() {
+        let roots = vec![
+            (PathBuf::from("/saves"), "outer".to_string()),
+            (PathBuf::from("/saves/game"), "inner".to_string()),
+        ];
+        assert_eq!(
+            game_for_path(&roots, Path::new("/saves/game/slot1.sav")),
+            Some("inner")
+        );
+        assert_eq!(
+            game_for_path(&roots, Path::new("/saves/other.sav")),
+            Some("outer")
+        );
+        assert_eq!(game_for_path(&roots, Path::new("/elsewhere/a.sav")), None);
+
+        let start = Instant::now();
+        let mut debouncer = SaveChangeDebouncer::new(Duration::from_secs(10));
+        debouncer.record("inner", Path::new("/saves/game/slot1.sav"), start);
+        debouncer.record(
+            "inner",
+            Path::new("/saves/game/slot1.sav"),
+            start + Duration::from_secs(4),
+        );
+        debouncer.record(
+            "outer",
+            Path::new("/saves/other.sav"),
+            start + Duration::from_secs(8),
+        );
+
+        assert!(debouncer
+            .take_due(start + Duration::from_secs(12))
+            .is_empty());
+        let due = debouncer.take_due(start + Duration::from_secs(14));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].game_id, "inner");
+        assert_eq!(due[0].paths.len(), 1);
+        assert_eq!(due[0].change_count, 2);
+
+        let due = debouncer.take_due(start + Duration::from_secs(18));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].game_id, "outer");
+        assert!(debouncer
+            .take_due(start + Duration::from_secs(60))
+            .is_empty());
+    }
+}
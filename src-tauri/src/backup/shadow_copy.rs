@@ -0,0 +1,168 @@
+//! Volume Shadow Copy fallback for save files that a running game keeps locked.
+//!
+//! Snapshots are created through the `Win32_ShadowCopy` WMI class (the VSS requester COM
+//! interface is not exposed by the `windows` crate), which requires an elevated process.
+//! Without elevation `create` fails and the caller records the file as skipped.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf, Prefix};
+use std::sync::Mutex;
+
+/// True when `err` means another process holds the file open without sharing it.
+pub fn is_lock_error(err: &io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION
+    cfg!(windows) && matches!(err.raw_os_error(), Some(32) | Some(33))
+}
+
+/// Drive root (`C:\`) of an absolute Windows path.
+fn volume_root(path: &Path) -> Option<String> {
+    match path.components().next()? {
+        Component::Prefix(prefix) => match prefix.kind() {
+            Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                Some(format!("{}:\\", (letter as char).to_ascii_uppercase()))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `path` re-rooted under the snapshot device (`\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopyN`).
+fn path_in_snapshot(device: &str, path: &Path) -> PathBuf {
+    let relative: PathBuf = path
+        .components()
+        .skip_while(|c| matches!(c, Component::Prefix(_) | Component::RootDir))
+        .collect();
+    PathBuf::from(format!("{}\\", device.trim_end_matches('\\'))).join(relative)
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+struct ShadowCopy {
+    id: String,
+    device: String,
+}
+
+impl ShadowCopy {
+    #[cfg(windows)]
+    fn create(volume: &str) -> Result<Self, String> {
+        let script = format!(
+            "$r = (Get-WmiObject -List Win32_ShadowCopy).Create('{volume}', 'ClientAccessible'); \
+             if ($r.ReturnValue -ne 0) {{ exit $r.ReturnValue }}; \
+             $s = Get-WmiObject Win32_ShadowCopy | Where-Object {{ $_.ID -eq $r.ShadowID }}; \
+             Write-Output \"$($s.ID)|$($s.DeviceObject)\""
+        );
+        let output = run_powershell(&script)?;
+        let line = output.lines().last().unwrap_or_default().trim();
+        let (id, device) = line
+            .split_once('|')
+            .filter(|(id, device)| !id.is_empty() && !device.is_empty())
+            .ok_or_else(|| format!("Не удалось создать теневую копию тома {}", volume))?;
+        Ok(Self {
+            id: id.to_string(),
+            device: device.to_string(),
+        })
+    }
+
+    #[cfg(not(windows))]
+    fn create(_volume: &str) -> Result<Self, String> {
+        Err("Теневые копии поддерживаются только в Windows".to_string())
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ShadowCopy {
+    fn drop(&mut self) {
+        let script = format!(
+            "Get-WmiObject Win32_ShadowCopy | Where-Object {{ $_.ID -eq '{}' }} | \
+             ForEach-Object {{ $_.Delete() }}",
+            self.id
+        );
+        if let Err(e) = run_powershell(&script) {
+            eprintln!("Failed to delete shadow copy {}: {}", self.id, e);
+        }
+    }
+}
+
+#[cfg(windows)]
+pub(crate) fn run_powershell(script: &str) -> Result<String, String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "powershell завершился с кодом {:?}",
+            output.status.code()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Lazily created snapshots, at most one per volume, shared by all copy workers of a backup.
+/// Snapshots are deleted when this is dropped.
+pub struct ShadowCopies {
+    enabled: bool,
+    snapshots: Mutex<HashMap<String, Option<ShadowCopy>>>,
+}
+
+impl ShadowCopies {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            snapshots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Where `path` can be read from inside a snapshot of its volume. A failed snapshot
+    /// attempt is remembered so a locked volume costs only one try per backup.
+    pub fn snapshot_path(&self, path: &Path) -> Option<PathBuf> {
+        if !self.enabled {
+            return None;
+        }
+        let volume = volume_root(path)?;
+        let mut snapshots = self.snapshots.lock().ok()?;
+        let snapshot = snapshots
+            .entry(volume.clone())
+            .or_insert_with(|| match ShadowCopy::create(&volume) {
+                Ok(snapshot) => Some(snapshot),
+                Err(e) => {
+                    eprintln!("Shadow copy unavailable for {}: {}", volume, e);
+                    None
+                }
+            });
+        Some(path_in_snapshot(&snapshot.as_ref()?.device, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_shadow_copies_never_resolve_paths() {
+        let shadows = ShadowCopies::new(false);
+        assert_eq!(
+            shadows.snapshot_path(Path::new("C:\\Saves\\slot1.sav")),
+            None
+        );
+        assert!(!is_lock_error(&io::Error::from(io::ErrorKind::NotFound)));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn snapshot_paths_are_rooted_at_the_device() {
+        let path = Path::new(r"C:\Users\Player\AppData\save.dat");
+        assert_eq!(volume_root(path).as_deref(), Some(r"C:\"));
+        assert_eq!(
+            path_in_snapshot(r"\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy3", path),
+            PathBuf::from(
+                r"\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy3\Users\Player\AppData\save.dat"
+            )
+        );
+    }
+}
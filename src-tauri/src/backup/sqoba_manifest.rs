@@ -23,8 +23,14 @@ pub struct SqobaManifest {
 struct SqobaManifestIndex {
     normalized_keys: Vec<(String, String)>,
     normalized_exact: HashMap<String, String>,
+    /// Keys by punctuation-free lowercase name with edition words kept, so
+    /// "Dark Souls Remastered" finds its own entry before the original's.
+    loose_exact: HashMap<String, String>,
 }
 
+/// Fuzzy matches scoring below this are not used without confirmation.
+pub const MATCH_THRESHOLD: f32 = 0.6;
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SqobaGame {
     pub files: Option<HashMap<String, Vec<String>>>,
@@ -44,9 +50,17 @@ impl SqobaManifest {
     fn rebuild_index(&mut self) {
         self.index.normalized_keys.clear();
         self.index.normalized_exact.clear();
+        self.index.loose_exact.clear();
         self.index.normalized_keys.reserve(self.games.len());
 
-        for key in self.games.keys() {
+        // Sorted so that which of two colliding keys wins doesn't depend on hash order.
+        let mut keys: Vec<&String> = self.games.keys().collect();
+        keys.sort();
+        for key in keys {
+            self.index
+                .loose_exact
+                .entry(loose_name(key))
+                .or_insert_with(|| key.clone());
             let normalized = normalize_name(key);
             self.index
                 .normalized_exact
@@ -57,18 +71,26 @@ impl SqobaManifest {
     }
 
     pub fn find_game_entry(&self, name: &str) -> Option<(String, SqobaGame)> {
-        if let Some(entry) = self.games.get(name) {
-            return Some((name.to_string(), entry.clone()));
+        let (key, score) = self.match_game(name)?;
+        if score < MATCH_THRESHOLD {
+            return None;
+        }
+        self.games.get(&key).cloned().map(|entry| (key, entry))
+    }
+
+    /// Best manifest key for `name` and how sure the match is (1.0 for exact names),
+    /// regardless of the threshold.
+    pub fn match_game(&self, name: &str) -> Option<(String, f32)> {
+        if self.games.contains_key(name) {
+            return Some((name.to_string(), 1.0));
+        }
+        if let Some(key) = self.index.loose_exact.get(&loose_name(name)) {
+            return Some((key.clone(), 1.0));
         }
 
         let normalized = normalize_name(name);
-
         if let Some(key) = self.index.normalized_exact.get(&normalized) {
-            return self
-                .games
-                .get(key)
-                .cloned()
-                .map(|entry| (key.clone(), entry));
+            return Some((key.clone(), 1.0));
         }
 
         let mut best: Option<(String, f32)> = None;
@@ -78,18 +100,7 @@ impl SqobaManifest {
                 best = Some((key.clone(), score));
             }
         }
-
-        if let Some((best_key, best_score)) = best {
-            if best_score >= 0.6 {
-                return self
-                    .games
-                    .get(&best_key)
-                    .cloned()
-                    .map(|entry| (best_key, entry));
-            }
-        }
-
-        None
+        best
     }
 
     pub fn suggest_games(&self, name: &str, limit: usize) -> Vec<String> {
@@ -102,12 +113,20 @@ impl SqobaManifest {
             .filter(|(_, score)| *score >= 0.4)
             .collect();
 
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
         scored.into_iter().take(limit).map(|(k, _)| k).collect()
     }
 }
 
 const CACHE_FILE_NAME: &str = "sqoba_manifest.json";
+// Try both default branch names to be resilient to repo changes.
+const MANIFEST_URLS: [&str; 2] = [
+    "https://raw.githubusercontent.com/mtkennerly/ludusavi-manifest/main/data/manifest.yaml",
+    "https://raw.githubusercontent.com/mtkennerly/ludusavi-manifest/master/data/manifest.yaml",
+];
+/// The full manifest is several megabytes; a refresh may run on a slow connection.
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(120);
+const PROGRESS_CHUNK_BYTES: u64 = 256 * 1024;
 const EMBEDDED_MANIFEST_GZ: &[u8] = include_bytes!("../../resources/sqoba_manifest.yaml.gz");
 
 lazy_static! {
@@ -125,13 +144,24 @@ pub fn load_manifest_optional() -> Result<Option<SqobaManifest>, String> {
     load_manifest_optional_with_cache_and_fetcher(&cache_path, download_ludusavi_manifest_yaml)
 }
 
-fn default_cache_path() -> PathBuf {
+pub fn default_cache_path() -> PathBuf {
     dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("arrancador")
         .join(CACHE_FILE_NAME)
 }
 
+/// Drops the cached manifest so the next load downloads a fresh copy (falling back to the
+/// embedded one offline). Returns whether there was a cache to remove.
+pub fn invalidate_manifest_cache() -> Result<bool, String> {
+    let cache_path = default_cache_path();
+    if !cache_path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(&cache_path).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
 fn load_manifest_from_cache(cache_path: &Path) -> Option<SqobaManifest> {
     if !cache_path.exists() {
         return None;
@@ -153,15 +183,123 @@ fn write_manifest_cache(cache_path: &Path, manifest: &SqobaManifest) -> Result<(
     Ok(())
 }
 
-pub fn refresh_manifest_from_network() -> Result<(), String> {
-    let cache_path = default_cache_path();
-    let Some(text) = download_ludusavi_manifest_yaml()? else {
-        return Err("Не удалось скачать манифест".to_string());
-    };
+/// `ETag` / `Last-Modified` of the cached copy, sent back so an unchanged manifest costs a
+/// `304 Not Modified` instead of a full download.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManifestValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
 
-    let manifest = manifest_from_yaml(&text)?;
-    write_manifest_cache(&cache_path, &manifest)?;
-    Ok(())
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifestFetch {
+    NotModified,
+    Updated {
+        validators: ManifestValidators,
+        game_count: usize,
+        bytes: u64,
+    },
+}
+
+/// `(stage, downloaded bytes, total bytes)`; the total is unknown without `Content-Length`.
+pub type DownloadProgress<'a> = &'a dyn Fn(&'static str, u64, Option<u64>);
+
+/// Reads `reader` to the end, reporting progress every `PROGRESS_CHUNK_BYTES` and at the end.
+fn read_with_progress(
+    mut reader: impl Read,
+    total: Option<u64>,
+    progress: DownloadProgress,
+) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(total.unwrap_or(0) as usize);
+    let mut buffer = [0u8; 64 * 1024];
+    let mut reported = 0u64;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        out.extend_from_slice(&buffer[..read]);
+        let downloaded = out.len() as u64;
+        if downloaded - reported >= PROGRESS_CHUNK_BYTES {
+            progress("download", downloaded, total);
+            reported = downloaded;
+        }
+    }
+    progress("download", out.len() as u64, total);
+    Ok(out)
+}
+
+fn header_value(
+    resp: &reqwest::blocking::Response,
+    name: reqwest::header::HeaderName,
+) -> Option<String> {
+    resp.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+/// Downloads the manifest unless the server reports it unchanged since `validators`, and
+/// replaces the cache on success. Pass default validators to force a full download.
+pub fn refresh_manifest_conditional(
+    validators: &ManifestValidators,
+    progress: DownloadProgress,
+) -> Result<ManifestFetch, String> {
+    use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("arrancador (SQOBA)")
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(REFRESH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut last_error = "Не удалось скачать манифест".to_string();
+    for url in MANIFEST_URLS {
+        let mut request = client.get(url);
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        let resp = match request.send() {
+            Ok(resp) => resp,
+            Err(e) => {
+                last_error = format!("Не удалось скачать манифест: {}", e);
+                continue;
+            }
+        };
+        if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(ManifestFetch::NotModified);
+        }
+        if !resp.status().is_success() {
+            last_error = format!("Не удалось скачать манифест: HTTP {}", resp.status());
+            continue;
+        }
+
+        let fresh = ManifestValidators {
+            etag: header_value(&resp, ETAG),
+            last_modified: header_value(&resp, LAST_MODIFIED),
+        };
+        let total = resp.content_length();
+        let bytes = read_with_progress(resp, total, progress).map_err(|e| e.to_string())?;
+        let text = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+        if text.trim().is_empty() {
+            last_error = "Сервер вернул пустой манифест".to_string();
+            continue;
+        }
+
+        progress("parse", text.len() as u64, total);
+        let manifest = manifest_from_yaml(&text)?;
+        write_manifest_cache(&default_cache_path(), &manifest)?;
+        return Ok(ManifestFetch::Updated {
+            validators: fresh,
+            game_count: manifest.games.len(),
+            bytes: text.len() as u64,
+        });
+    }
+    Err(last_error)
 }
 
 fn load_manifest_optional_with_cache_and_fetcher<F>(
@@ -192,11 +330,6 @@ where
 
 fn download_ludusavi_manifest_yaml() -> Result<Option<String>, String> {
     // We cache the parsed manifest, so this should run rarely (only when cache is missing).
-    // Try both default branch names to be resilient to repo changes.
-    const URLS: [&str; 2] = [
-        "https://raw.githubusercontent.com/mtkennerly/ludusavi-manifest/main/data/manifest.yaml",
-        "https://raw.githubusercontent.com/mtkennerly/ludusavi-manifest/master/data/manifest.yaml",
-    ];
 
     let client = match reqwest::blocking::Client::builder()
         .user_agent("arrancador (SQOBA)")
@@ -208,7 +341,7 @@ fn download_ludusavi_manifest_yaml() -> Result<Option<String>, String> {
         Err(_) => return Ok(None),
     };
 
-    for url in URLS {
+    for url in MANIFEST_URLS {
         let resp = match client.get(url).send() {
             Ok(r) => r,
             Err(_) => continue,
@@ -285,13 +418,29 @@ fn manifest_from_yaml(text: &str) -> Result<SqobaManifest, String> {
             }
         }
 
+        let mut registry: Vec<String> = Vec::new();
+        if let Some(keys) = game_val
+            .as_mapping()
+            .and_then(|m| m.get(YamlValue::from("registry")))
+            .and_then(|v| v.as_mapping())
+        {
+            for (key, meta_val) in keys {
+                let Some(key) = key.as_str().and_then(normalize_registry_key) else {
+                    continue;
+                };
+                if is_path_applicable(meta_val) && !registry.contains(&key) {
+                    registry.push(key);
+                }
+            }
+        }
+
         let game_manifest = SqobaGame {
             files: if files_map.is_empty() {
                 None
             } else {
                 Some(files_map)
             },
-            registry: None,
+            registry: (!registry.is_empty()).then_some(registry),
         };
         games.insert(name, game_manifest);
     }
@@ -299,6 +448,29 @@ fn manifest_from_yaml(text: &str) -> Result<SqobaManifest, String> {
     Ok(SqobaManifest::from_games(games))
 }
 
+/// Hive aliases the manifest may use, with the names shown in the UI.
+const REGISTRY_HIVES: [(&str, &str); 4] = [
+    ("HKEY_CURRENT_USER", "HKEY_CURRENT_USER"),
+    ("HKCU", "HKEY_CURRENT_USER"),
+    ("HKEY_LOCAL_MACHINE", "HKEY_LOCAL_MACHINE"),
+    ("HKLM", "HKEY_LOCAL_MACHINE"),
+];
+
+/// `HKCU/Software/Game` as `HKEY_CURRENT_USER\Software\Game`. Keys under other hives
+/// aren't per-game data and are dropped.
+fn normalize_registry_key(key: &str) -> Option<String> {
+    let key = key.trim().replace('/', "\\");
+    let (hive, rest) = key.split_once('\\').unwrap_or((key.as_str(), ""));
+    let (_, full_hive) = REGISTRY_HIVES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(hive))?;
+    let rest = rest.trim_matches('\\');
+    if rest.is_empty() {
+        return None;
+    }
+    Some(format!("{}\\{}", full_hive, rest))
+}
+
 fn extract_tags(meta: &YamlValue) -> Vec<String> {
     if let Some(tags) = meta
         .as_mapping()
@@ -373,6 +545,96 @@ pub fn normalize_name(name: &str) -> String {
     tokens.join(" ")
 }
 
+/// Lowercase words without punctuation; unlike `normalize_name` nothing is dropped.
+fn loose_name(name: &str) -> String {
+    let lower = name.to_lowercase();
+    NORMALIZE_RE
+        .replace_all(&lower, " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Value of a roman numeral token ("ii", "iv", "x"). A lone "i" is left alone since it is
+/// usually the pronoun.
+fn roman_numeral(token: &str) -> Option<u32> {
+    if token == "i" || token.is_empty() || !token.chars().all(|c| matches!(c, 'i' | 'v' | 'x')) {
+        return None;
+    }
+    let digits: Vec<i64> = token
+        .chars()
+        .map(|c| match c {
+            'i' => 1,
+            'v' => 5,
+            _ => 10,
+        })
+        .collect();
+    let total = digits.iter().enumerate().fold(0, |total, (i, &d)| {
+        if digits.get(i + 1).is_some_and(|&next| next > d) {
+            total - d
+        } else {
+            total + d
+        }
+    });
+    u32::try_from(total).ok().filter(|n| *n > 0)
+}
+
+/// Words of a normalized name with roman numerals spelled as digits, so "VII" and "7"
+/// compare equal.
+fn canonical_tokens(name: &str) -> Vec<String> {
+    name.split_whitespace()
+        .map(|token| {
+            roman_numeral(token)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| token.to_string())
+        })
+        .collect()
+}
+
+/// Sequel numbers in a normalized name, sorted.
+fn sequel_numbers(name: &str) -> Vec<u32> {
+    let mut numbers: Vec<u32> = canonical_tokens(name)
+        .iter()
+        .filter_map(|token| token.parse::<u32>().ok())
+        .collect();
+    numbers.sort_unstable();
+    numbers
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Levenshtein similarity of the names with their words sorted, so word order doesn't
+/// matter but typos and small spelling differences only cost a little.
+fn token_sort_ratio(a: &[String], b: &[String]) -> f32 {
+    let sorted = |tokens: &[String]| {
+        let mut tokens = tokens.to_vec();
+        tokens.sort_unstable();
+        tokens.join(" ").chars().collect::<Vec<char>>()
+    };
+    let (a, b) = (sorted(a), sorted(b));
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(&a, &b) as f32 / longest as f32
+}
+
+/// Similarity of two normalized names in `0.0..=1.0`. Names whose words all appear in the
+/// other score 0.9 and typos are forgiven through `token_sort_ratio`, but names with
+/// different sequel numbers never reach `MATCH_THRESHOLD`. The edit ratio is squared so
+/// short, merely similar-looking titles ("Celeste" and "Celestia") stay below it too.
 pub fn similarity_score(a: &str, b: &str) -> f32 {
     if a.is_empty() || b.is_empty() {
         return 0.0;
@@ -380,17 +642,23 @@ pub fn similarity_score(a: &str, b: &str) -> f32 {
     if a == b {
         return 1.0;
     }
-    if a.contains(b) || b.contains(a) {
-        return 0.9;
-    }
-    let set_a: HashSet<&str> = a.split_whitespace().collect();
-    let set_b: HashSet<&str> = b.split_whitespace().collect();
+    let (tokens_a, tokens_b) = (canonical_tokens(a), canonical_tokens(b));
+    let set_a: HashSet<&String> = tokens_a.iter().collect();
+    let set_b: HashSet<&String> = tokens_b.iter().collect();
     if set_a.is_empty() || set_b.is_empty() {
         return 0.0;
     }
     let inter = set_a.intersection(&set_b).count() as f32;
     let union = set_a.union(&set_b).count() as f32;
-    inter / union
+    let score = if set_a.is_subset(&set_b) || set_b.is_subset(&set_a) {
+        0.9
+    } else {
+        (inter / union).max(token_sort_ratio(&tokens_a, &tokens_b).powi(2))
+    };
+    if sequel_numbers(a) != sequel_numbers(b) {
+        return score.min(MATCH_THRESHOLD - 0.1);
+    }
+    score
 }
 
 #[cfg(test)]
@@ -446,6 +714,56 @@ Example Game:
         assert!(cache_path.exists());
     }
 
+    #[test]
+    fn registry_keys_are_parsed_normalized_and_filtered_by_os() {
+        let yaml = r#"
+Registry Game:
+  registry:
+    HKCU/Software/Studio/Registry Game:
+      tags: ["save"]
+    "HKEY_LOCAL_MACHINE\\SOFTWARE\\Studio\\Registry Game":
+      when:
+        - store: steam
+    hkcu/Software/Studio/Registry Game/: {}
+    HKEY_CLASSES_ROOT/Studio: {}
+    HKCU/Software/Studio/Mac Only:
+      when:
+        - os: mac
+No Registry:
+  files:
+    "<home>/save.dat": {}
+"#;
+        let manifest = manifest_from_yaml(yaml).expect("parse manifest");
+        assert_eq!(
+            manifest.games["Registry Game"].registry,
+            Some(vec![
+                "HKEY_CURRENT_USER\\Software\\Studio\\Registry Game".to_string(),
+                "HKEY_LOCAL_MACHINE\\SOFTWARE\\Studio\\Registry Game".to_string(),
+            ])
+        );
+        assert_eq!(manifest.games["No Registry"].registry, None);
+    }
+
+    #[test]
+    fn download_progress_is_reported_in_chunks_and_at_the_end() {
+        let body = vec![b'x'; 600 * 1024];
+        let seen = std::cell::RefCell::new(Vec::new());
+        let record = |stage: &'static str, downloaded: u64, total: Option<u64>| {
+            seen.borrow_mut().push((stage, downloaded, total))
+        };
+        let read = read_with_progress(body.as_slice(), Some(body.len() as u64), &record)
+            .expect("read body");
+        assert_eq!(read.len(), body.len());
+
+        let seen = seen.into_inner();
+        assert_eq!(seen.len(), 3);
+        assert!(seen
+            .iter()
+            .all(|(stage, _, total)| *stage == "download" && *total == Some(body.len() as u64)));
+        assert_eq!(seen.last().map(|s| s.1), Some(body.len() as u64));
+        assert!(seen.windows(2).all(|pair| pair[0].1 < pair[1].1));
+    }
+
     #[test]
     fn find_game_entry_matches_normalized_name() {
         let mut games = HashMap::new();
@@ -461,4 +779,41 @@ Example Game:
         let found = manifest.find_game_entry("witcher 3").expect("find game");
         assert_eq!(found.0, "The Witcher 3: Game of the Year Edition");
     }
+
+    #[test]
+    fn matcher_tells_sequels_and_remasters_apart_but_forgives_typos() {
+        let mut games = HashMap::new();
+        for key in [
+            "Hades",
+            "DARK SOULS: REMASTERED",
+            "Dark Souls: Prepare to Die Edition",
+            "Hollow Knight",
+            "Final Fantasy VII",
+            "Celestia",
+        ] {
+            games.insert(
+                key.to_string(),
+                SqobaGame {
+                    files: None,
+                    registry: None,
+                },
+            );
+        }
+        let manifest = SqobaManifest::from_games(games);
+        let key = |name: &str| manifest.find_game_entry(name).map(|(key, _)| key);
+
+        assert_eq!(key("Hades II"), None);
+        assert_eq!(
+            key("Dark Souls Remastered").as_deref(),
+            Some("DARK SOULS: REMASTERED")
+        );
+        assert_eq!(key("Holow Knight").as_deref(), Some("Hollow Knight"));
+        assert_eq!(key("Final Fantasy 7").as_deref(), Some("Final Fantasy VII"));
+        assert_eq!(key("Final Fantasy VIII"), None);
+        assert_eq!(key("Celeste"), None);
+
+        assert_eq!(sequel_numbers("witcher iii wild hunt 2"), vec![2, 3]);
+        assert_eq!(sequel_numbers("i am bread"), Vec::<u32>::new());
+        assert!(similarity_score("portal", "portal 2") < MATCH_THRESHOLD);
+    }
 }
@@ -0,0 +1,217 @@
+use chrono::DateTime;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone)]
+pub struct BackupStorageRow {
+    pub id: String,
+    pub game_id: String,
+    pub game_name: String,
+    pub created_at: String,
+    /// Size of the saved files before compression (`backups.backup_size`).
+    pub raw_size: u64,
+    /// Bytes the backup occupies on disk.
+    pub disk_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupRef {
+    pub backup_id: String,
+    pub game_id: String,
+    pub game_name: String,
+    pub created_at: String,
+    pub disk_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameBackupStorage {
+    pub game_id: String,
+    pub game_name: String,
+    pub backup_count: usize,
+    pub raw_bytes: u64,
+    pub disk_bytes: u64,
+    /// `disk_bytes / raw_bytes`; `None` when nothing was measured.
+    pub compression_ratio: Option<f64>,
+    pub oldest_backup: Option<String>,
+    pub newest_backup: Option<String>,
+}
+
+/// Storage added per calendar month by the backups that still exist.
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageGrowthPoint {
+    /// `YYYY-MM`
+    pub month: String,
+    pub backups_added: usize,
+    pub bytes_added: u64,
+    pub cumulative_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupStats {
+    pub total_backups: usize,
+    pub total_raw_bytes: u64,
+    pub total_disk_bytes: u64,
+    pub compression_ratio: Option<f64>,
+    /// Largest on-disk footprint first.
+    pub games: Vec<GameBackupStorage>,
+    pub oldest: Option<BackupRef>,
+    pub newest: Option<BackupRef>,
+    pub growth: Vec<StorageGrowthPoint>,
+}
+
+fn ratio(disk: u64, raw: u64) -> Option<f64> {
+    (raw > 0).then(|| disk as f64 / raw as f64)
+}
+
+fn month_of(created_at: &str) -> String {
+    DateTime::parse_from_rfc3339(created_at)
+        .map(|dt| dt.format("%Y-%m").to_string())
+        .unwrap_or_else(|_| created_at.chars().take(7).collect())
+}
+
+fn backup_ref(row: &BackupStorageRow) -> BackupRef {
+    BackupRef {
+        backup_id: row.id.clone(),
+        game_id: row.game_id.clone(),
+        game_name: row.game_name.clone(),
+        created_at: row.created_at.clone(),
+        disk_bytes: row.disk_size,
+    }
+}
+
+pub fn summarize_backup_storage(mut rows: Vec<BackupStorageRow>) -> BackupStats {
+    rows.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let mut games: HashMap<&str, GameBackupStorage> = HashMap::new();
+    let mut months: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+    for row in &rows {
+        let game = games
+            .entry(row.game_id.as_str())
+            .or_insert_with(|| GameBackupStorage {
+                game_id: row.game_id.clone(),
+                game_name: row.game_name.clone(),
+                backup_count: 0,
+                raw_bytes: 0,
+                disk_bytes: 0,
+                compression_ratio: None,
+                oldest_backup: Some(row.created_at.clone()),
+                newest_backup: None,
+            });
+        game.backup_count += 1;
+        game.raw_bytes += row.raw_size;
+        game.disk_bytes += row.disk_size;
+        game.newest_backup = Some(row.created_at.clone());
+
+        let month = months.entry(month_of(&row.created_at)).or_default();
+        month.0 += 1;
+        month.1 += row.disk_size;
+    }
+
+    let mut games: Vec<GameBackupStorage> = games
+        .into_values()
+        .map(|mut game| {
+            game.compression_ratio = ratio(game.disk_bytes, game.raw_bytes);
+            game
+        })
+        .collect();
+    games.sort_by(|a, b| {
+        b.disk_bytes
+            .cmp(&a.disk_bytes)
+            .then_with(|| a.game_name.cmp(&b.game_name))
+    });
+
+    let mut cumulative_bytes = 0;
+    let growth = months
+        .into_iter()
+        .map(|(month, (backups_added, bytes_added))| {
+            cumulative_bytes += bytes_added;
+            StorageGrowthPoint {
+                month,
+                backups_added,
+                bytes_added,
+                cumulative_bytes,
+            }
+        })
+        .collect();
+
+    let total_raw_bytes = rows.iter().map(|row| row.raw_size).sum();
+    let total_disk_bytes = rows.iter().map(|row| row.disk_size).sum();
+    BackupStats {
+        total_backups: rows.len(),
+        total_raw_bytes,
+        total_disk_bytes,
+        compression_ratio: ratio(total_disk_bytes, total_raw_bytes),
+        games,
+        oldest: rows.first().map(backup_ref),
+        newest: rows.last().map(backup_ref),
+        growth,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: &str, game: &str, created_at: &str, raw: u64, disk: u64) -> BackupStorageRow {
+        BackupStorageRow {
+            id: id.to_string(),
+            game_id: game.to_string(),
+            game_name: game.to_uppercase(),
+            created_at: created_at.to_string(),
+            raw_size: raw,
+            disk_size: disk,
+        }
+    }
+
+    #[test]
+    fn summarizes_per_game_totals_ratios_and_monthly_growth() {
+        let stats = summarize_backup_storage(vec![
+            row("b3", "a", "2024-02-10T00:00:00+00:00", 100, 40),
+            row("b1", "a", "2024-01-05T00:00:00+00:00", 100, 60),
+            row("b2", "b", "2024-01-20T00:00:00+00:00", 0, 500),
+        ]);
+
+        assert_eq!(stats.total_backups, 3);
+        assert_eq!(stats.total_raw_bytes, 200);
+        assert_eq!(stats.total_disk_bytes, 600);
+        assert_eq!(
+            stats.oldest.as_ref().map(|b| b.backup_id.as_str()),
+            Some("b1")
+        );
+        assert_eq!(
+            stats.newest.as_ref().map(|b| b.backup_id.as_str()),
+            Some("b3")
+        );
+
+        assert_eq!(stats.games[0].game_id, "b");
+        assert_eq!(stats.games[0].compression_ratio, None);
+        let a = &stats.games[1];
+        assert_eq!((a.backup_count, a.raw_bytes, a.disk_bytes), (2, 200, 100));
+        assert_eq!(a.compression_ratio, Some(0.5));
+        assert_eq!(
+            a.oldest_backup.as_deref(),
+            Some("2024-01-05T00:00:00+00:00")
+        );
+        assert_eq!(
+            a.newest_backup.as_deref(),
+            Some("2024-02-10T00:00:00+00:00")
+        );
+
+        let growth: Vec<(&str, usize, u64, u64)> = stats
+            .growth
+            .iter()
+            .map(|p| {
+                (
+                    p.month.as_str(),
+                    p.backups_added,
+                    p.bytes_added,
+                    p.cumulative_bytes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            growth,
+            vec![("2024-01", 2, 560, 560), ("2024-02", 1, 40, 600)]
+        );
+    }
+}
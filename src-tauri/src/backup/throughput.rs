@@ -0,0 +1,203 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Length of one measurement window while probing.
+const WINDOW: Duration = Duration::from_millis(500);
+/// Probing stops after this long and the best level seen so far is kept.
+const PROBE_DURATION: Duration = Duration::from_secs(4);
+/// A new level must beat the best rate by this factor to count as an improvement.
+const MIN_GAIN: f64 = 1.05;
+
+#[derive(Debug)]
+struct TunerState {
+    window_start: Instant,
+    window_bytes: u64,
+    best_limit: usize,
+    best_rate: f64,
+    direction: isize,
+    tried_down: bool,
+    settled: bool,
+    measured: bool,
+}
+
+/// Hill-climbs the number of concurrent copies during the first seconds of a backup.
+///
+/// The rayon pool is sized for `max` workers; `acquire` gates how many of them may copy at
+/// once. Every `WINDOW` the observed bytes/sec decides whether to step the limit further
+/// or fall back to the best level seen.
+pub struct ThroughputTuner {
+    limit: AtomicUsize,
+    active: AtomicUsize,
+    max: usize,
+    started: Instant,
+    state: Mutex<TunerState>,
+}
+
+pub struct CopyPermit<'a> {
+    tuner: &'a ThroughputTuner,
+}
+
+impl Drop for CopyPermit<'_> {
+    fn drop(&mut self) {
+        self.tuner.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ThroughputTuner {
+    pub fn new(initial: usize, max: usize) -> Self {
+        let max = max.max(1);
+        let initial = initial.clamp(1, max);
+        let now = Instant::now();
+        Self {
+            limit: AtomicUsize::new(initial),
+            active: AtomicUsize::new(0),
+            max,
+            started: now,
+            state: Mutex::new(TunerState {
+                window_start: now,
+                window_bytes: 0,
+                best_limit: initial,
+                best_rate: 0.0,
+                direction: 1,
+                tried_down: false,
+                settled: false,
+                measured: false,
+            }),
+        }
+    }
+
+    pub fn max_threads(&self) -> usize {
+        self.max
+    }
+
+    pub fn current_limit(&self) -> usize {
+        self.limit.load(Ordering::SeqCst)
+    }
+
+    /// Blocks until fewer than the current limit of copies are in flight.
+    pub fn acquire(&self) -> CopyPermit<'_> {
+        loop {
+            let active = self.active.load(Ordering::SeqCst);
+            if active < self.current_limit()
+                && self
+                    .active
+                    .compare_exchange(active, active + 1, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+            {
+                return CopyPermit { tuner: self };
+            }
+            thread::sleep(Duration::from_millis(2));
+        }
+    }
+
+    pub fn record(&self, bytes: u64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.settled {
+            return;
+        }
+        state.window_bytes += bytes;
+        let elapsed = state.window_start.elapsed();
+        if elapsed < WINDOW {
+            return;
+        }
+        let rate = state.window_bytes as f64 / elapsed.as_secs_f64();
+        self.observe_window(&mut state, rate);
+        if self.started.elapsed() >= PROBE_DURATION {
+            self.settle(&mut state);
+        }
+        state.window_start = Instant::now();
+        state.window_bytes = 0;
+    }
+
+    /// Best parallelism observed, once at least one full window was measured.
+    pub fn learned_threads(&self) -> Option<usize> {
+        let state = self.state.lock().ok()?;
+        state.measured.then_some(state.best_limit)
+    }
+
+    fn observe_window(&self, state: &mut TunerState, rate: f64) {
+        if state.settled {
+            return;
+        }
+        state.measured = true;
+        let limit = self.current_limit();
+
+        if state.best_rate == 0.0 || rate > state.best_rate * MIN_GAIN {
+            state.best_limit = limit;
+            state.best_rate = rate;
+            let next = limit as isize + state.direction;
+            if next >= 1 && next as usize <= self.max {
+                self.limit.store(next as usize, Ordering::SeqCst);
+            } else {
+                self.settle(state);
+            }
+            return;
+        }
+
+        if state.direction > 0 && !state.tried_down && state.best_limit > 1 {
+            state.direction = -1;
+            state.tried_down = true;
+            self.limit.store(state.best_limit - 1, Ordering::SeqCst);
+            return;
+        }
+
+        self.settle(state);
+    }
+
+    fn settle(&self, state: &mut TunerState) {
+        state.settled = true;
+        self.limit.store(state.best_limit, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(tuner: &ThroughputTuner, rate_for: impl Fn(usize) -> f64) {
+        let mut state = tuner.state.lock().unwrap();
+        let rate = rate_for(tuner.current_limit());
+        tuner.observe_window(&mut state, rate);
+    }
+
+    #[test]
+    fn climbs_until_throughput_stops_improving() {
+        let tuner = ThroughputTuner::new(2, 8);
+        let rate = |limit: usize| {
+            if limit <= 4 {
+                limit as f64 * 100.0
+            } else {
+                380.0
+            }
+        };
+        for _ in 0..8 {
+            step(&tuner, rate);
+        }
+        assert_eq!(tuner.current_limit(), 4);
+        assert_eq!(tuner.learned_threads(), Some(4));
+    }
+
+    #[test]
+    fn steps_down_when_more_threads_hurt() {
+        let tuner = ThroughputTuner::new(4, 8);
+        let rate = |limit: usize| 1000.0 / limit as f64;
+        for _ in 0..8 {
+            step(&tuner, rate);
+        }
+        assert_eq!(tuner.current_limit(), 1);
+        assert_eq!(tuner.learned_threads(), Some(1));
+    }
+
+    #[test]
+    fn nothing_learned_without_a_full_window() {
+        let tuner = ThroughputTuner::new(3, 8);
+        tuner.record(1024);
+        assert_eq!(tuner.learned_threads(), None);
+        let _permit = tuner.acquire();
+        assert_eq!(tuner.active.load(Ordering::SeqCst), 1);
+    }
+}
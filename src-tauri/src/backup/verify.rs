@@ -0,0 +1,193 @@
+use crate::backup::engine::{load_backup_manifest, long_path, BackupFileEntry};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::ZipArchive;
+
+pub const DEFAULT_VERIFY_SAMPLE_SIZE: usize = 8;
+
+/// How much of a freshly written backup is re-read and compared against the source files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyMode {
+    Off,
+    Sample(usize),
+    All,
+}
+
+impl VerifyMode {
+    /// Parses the `backup_verify_mode` setting (`off`, `sample`, `all`).
+    pub fn from_setting(value: &str, sample_size: usize) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" | "false" | "none" => VerifyMode::Off,
+            "all" | "full" => VerifyMode::All,
+            _ => VerifyMode::Sample(sample_size.max(1)),
+        }
+    }
+}
+
+/// Re-opens the backup at `backup_path` and checks the selected entries byte-for-byte
+/// (via CRC32) against the files they were copied from. Returns how many entries were checked.
+pub fn verify_backup(backup_path: &Path, mode: VerifyMode) -> Result<usize, String> {
+    let count = match mode {
+        VerifyMode::Off => return Ok(0),
+        VerifyMode::Sample(count) => count,
+        VerifyMode::All => usize::MAX,
+    };
+
+    let manifest = load_backup_manifest(backup_path)?
+        .ok_or_else(|| "Проверка бэкапа: манифест не найден".to_string())?;
+    // Links stored as links carry no data to compare.
+    let files: Vec<&BackupFileEntry> = manifest
+        .files
+        .iter()
+        .filter(|entry| entry.link_target.is_none())
+        .collect();
+    let picked = pick_sample(files.len(), count, sample_seed());
+    let entries: Vec<&BackupFileEntry> = picked.iter().map(|&i| files[i]).collect();
+
+    if backup_path.is_dir() {
+        for entry in &entries {
+            let stored = backup_path.join(rel_path(&entry.backup_path));
+            let stored_crc = File::open(long_path(&stored))
+                .map_err(|e| e.to_string())
+                .and_then(crc32_of)
+                .map_err(|e| verify_error(entry, &e))?;
+            check_entry(entry, stored_crc)?;
+        }
+    } else {
+        let file = File::open(backup_path).map_err(|e| e.to_string())?;
+        let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+        for entry in &entries {
+            let stored_crc = archive
+                .by_name(&entry.backup_path)
+                .map_err(|e| e.to_string())
+                .and_then(crc32_of)
+                .map_err(|e| verify_error(entry, &e))?;
+            check_entry(entry, stored_crc)?;
+        }
+    }
+
+    Ok(entries.len())
+}
+
+fn check_entry(entry: &BackupFileEntry, stored_crc: u32) -> Result<(), String> {
+    let source_crc = File::open(long_path(Path::new(&entry.original_path)))
+        .map_err(|e| e.to_string())
+        .and_then(crc32_of)
+        .map_err(|e| verify_error(entry, &e))?;
+    if source_crc != stored_crc {
+        return Err(verify_error(
+            entry,
+            "содержимое не совпадает с исходным файлом",
+        ));
+    }
+    Ok(())
+}
+
+fn verify_error(entry: &BackupFileEntry, reason: &str) -> String {
+    format!(
+        "Проверка бэкапа не пройдена для {}: {}",
+        entry.original_path, reason
+    )
+}
+
+pub(crate) fn crc32_of<R: Read>(mut reader: R) -> Result<u32, String> {
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize())
+}
+
+pub(crate) fn rel_path(backup_rel: &str) -> PathBuf {
+    backup_rel
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn sample_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0x9e37_79b9_7f4a_7c15)
+}
+
+/// Picks `count` distinct indices out of `0..len` (all of them when `count >= len`),
+/// returned in ascending order so zip reads stay roughly sequential.
+fn pick_sample(len: usize, count: usize, seed: u64) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    if count >= len {
+        return indices;
+    }
+    // Partial Fisher-Yates with xorshift; good enough for spreading checks across entries.
+    let mut state = seed | 1;
+    for i in 0..count {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let j = i + (state % (len - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    indices.truncate(count);
+    indices.sort_unstable();
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::engine::{BackupEngine, BackupOptions};
+    use crate::backup::save_locator::SaveLookup;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn pick_sample_returns_distinct_sorted_indices() {
+        let picked = pick_sample(20, 5, 42);
+        assert_eq!(picked.len(), 5);
+        assert!(picked.windows(2).all(|w| w[0] < w[1]));
+        assert!(picked.iter().all(|&i| i < 20));
+        assert_eq!(pick_sample(3, 10, 42), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn verify_backup_detects_changed_source() {
+        let dir = tempdir().expect("tempdir");
+        let save_dir = dir.path().join("saves");
+        fs::create_dir_all(&save_dir).expect("create save dir");
+        fs::write(save_dir.join("slot1.sav"), b"alpha").expect("write slot1");
+        fs::write(save_dir.join("slot2.sav"), b"beta").expect("write slot2");
+
+        let archive = dir.path().join("backup.sqoba.zip");
+        let save_path = save_dir.to_string_lossy().to_string();
+        BackupEngine::new()
+            .backup_game_with_options_and_progress(
+                "Verify Game",
+                &archive,
+                1,
+                BackupOptions::zip(10),
+                SaveLookup {
+                    override_path: Some(&save_path),
+                    extra_roots: &[],
+                    game_dir: None,
+                    manifest_override: None,
+                    manifest_key: None,
+                },
+                None,
+            )
+            .expect("backup");
+
+        assert_eq!(verify_backup(&archive, VerifyMode::All).expect("verify"), 2);
+        assert_eq!(verify_backup(&archive, VerifyMode::Off).expect("off"), 0);
+
+        fs::write(save_dir.join("slot2.sav"), b"gamma").expect("modify slot2");
+        assert!(verify_backup(&archive, VerifyMode::All).is_err());
+    }
+}
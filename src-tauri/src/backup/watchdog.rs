@@ -0,0 +1,301 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+/// Used when `backup_stall_minutes` is unset; 0 turns the watchdog off.
+pub const DEFAULT_STALL_MINUTES: i32 = 5;
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+const COPY_CHUNK: usize = 1024 * 1024;
+
+lazy_static::lazy_static! {
+    static ref OPERATIONS: Mutex<HashMap<String, Arc<OperationMonitor>>> =
+        Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StallAction {
+    Cancel,
+    SkipFile,
+}
+
+/// Emitted as `backup:stalled` once an operation has made no progress for the configured
+/// time, and as `backup:stall-cleared` when it moves again or finishes.
+#[derive(Debug, Clone, Serialize)]
+pub struct StalledOperationEvent {
+    pub operation_id: String,
+    pub game_id: String,
+    /// `backup` or `restore`
+    pub kind: &'static str,
+    pub path: Option<String>,
+    pub idle_seconds: u64,
+}
+
+/// Why a monitored copy stopped early.
+#[derive(Debug)]
+pub enum CopyInterrupted {
+    Io(io::Error),
+    Cancelled,
+    Skipped,
+}
+
+impl From<io::Error> for CopyInterrupted {
+    fn from(error: io::Error) -> Self {
+        CopyInterrupted::Io(error)
+    }
+}
+
+pub const CANCELLED_MESSAGE: &str = "Операция отменена пользователем";
+pub const SKIPPED_REASON: &str = "Файл пропущен пользователем: копирование зависло";
+
+/// Heartbeat of one running backup or restore. Copies report every chunk, so a slow but
+/// moving file never looks stuck; cancel and skip requests are picked up between chunks.
+pub struct OperationMonitor {
+    id: String,
+    game_id: String,
+    kind: &'static str,
+    started: Instant,
+    last_activity_ms: AtomicU64,
+    current_path: Mutex<Option<String>>,
+    cancelled: AtomicBool,
+    skip_requested: AtomicBool,
+    warned: AtomicBool,
+}
+
+impl OperationMonitor {
+    fn new(game_id: &str, kind: &'static str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            game_id: game_id.to_string(),
+            kind,
+            started: Instant::now(),
+            last_activity_ms: AtomicU64::new(0),
+            current_path: Mutex::new(None),
+            cancelled: AtomicBool::new(false),
+            skip_requested: AtomicBool::new(false),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    fn touch(&self) {
+        let elapsed = self.started.elapsed().as_millis() as u64;
+        self.last_activity_ms.store(elapsed, Ordering::Relaxed);
+    }
+
+    /// Marks `path` as the file being worked on; a pending skip only applies to the file
+    /// that was current when it was requested.
+    pub fn begin_file(&self, path: &Path) {
+        if let Ok(mut current) = self.current_path.lock() {
+            *current = Some(path.to_string_lossy().to_string());
+        }
+        self.skip_requested.store(false, Ordering::Relaxed);
+        self.touch();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn idle(&self) -> Duration {
+        let last = Duration::from_millis(self.last_activity_ms.load(Ordering::Relaxed));
+        self.started.elapsed().saturating_sub(last)
+    }
+
+    fn event(&self) -> StalledOperationEvent {
+        StalledOperationEvent {
+            operation_id: self.id.clone(),
+            game_id: self.game_id.clone(),
+            kind: self.kind,
+            path: self.current_path.lock().ok().and_then(|path| path.clone()),
+            idle_seconds: self.idle().as_secs(),
+        }
+    }
+
+    /// Chunked copy that keeps the heartbeat alive and honours cancel / skip requests.
+    /// A read that never returns (dead network share) still blocks; the request then takes
+    /// effect as soon as the call comes back.
+    pub fn copy<R: Read, W: Write>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+    ) -> Result<u64, CopyInterrupted> {
+        let mut buffer = vec![0u8; COPY_CHUNK];
+        let mut copied = 0u64;
+        loop {
+            if self.is_cancelled() {
+                return Err(CopyInterrupted::Cancelled);
+            }
+            if self.skip_requested.swap(false, Ordering::Relaxed) {
+                return Err(CopyInterrupted::Skipped);
+            }
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => return Ok(copied),
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+            writer.write_all(&buffer[..read])?;
+            copied += read as u64;
+            self.touch();
+        }
+    }
+
+    /// `fs::copy` replacement for monitored operations; keeps the source's modified time.
+    pub fn copy_file(&self, source: &Path, target: &Path) -> Result<u64, CopyInterrupted> {
+        let mut reader = File::open(source)?;
+        let modified = reader.metadata().and_then(|meta| meta.modified()).ok();
+        let mut writer = File::create(target)?;
+        let result = self.copy(&mut reader, &mut writer);
+        if result.is_err() {
+            drop(writer);
+            let _ = std::fs::remove_file(target);
+            return result;
+        }
+        if let Some(modified) = modified {
+            let _ = writer.set_modified(modified);
+        }
+        result
+    }
+}
+
+/// Keeps an operation registered with the watchdog until dropped.
+pub struct MonitorGuard {
+    monitor: Arc<OperationMonitor>,
+    app: Option<AppHandle>,
+}
+
+impl MonitorGuard {
+    pub fn monitor(&self) -> Arc<OperationMonitor> {
+        Arc::clone(&self.monitor)
+    }
+}
+
+impl Drop for MonitorGuard {
+    fn drop(&mut self) {
+        if let Ok(mut operations) = OPERATIONS.lock() {
+            operations.remove(&self.monitor.id);
+        }
+        if self.monitor.warned.load(Ordering::Relaxed) {
+            if let Some(app) = &self.app {
+                let _ = app.emit("backup:stall-cleared", self.monitor.event());
+            }
+        }
+    }
+}
+
+pub fn register(game_id: &str, kind: &'static str, app: Option<AppHandle>) -> MonitorGuard {
+    let monitor = Arc::new(OperationMonitor::new(game_id, kind));
+    if let Ok(mut operations) = OPERATIONS.lock() {
+        operations.insert(monitor.id.clone(), Arc::clone(&monitor));
+    }
+    MonitorGuard { monitor, app }
+}
+
+pub fn request(operation_id: &str, action: StallAction) -> Result<(), String> {
+    let monitor = OPERATIONS
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get(operation_id)
+        .cloned()
+        .ok_or_else(|| "Операция уже завершена".to_string())?;
+    match action {
+        StallAction::Cancel => monitor.cancelled.store(true, Ordering::Relaxed),
+        StallAction::SkipFile => monitor.skip_requested.store(true, Ordering::Relaxed),
+    }
+    Ok(())
+}
+
+/// Operations that crossed `threshold` since the last check (`true`) or recovered (`false`).
+fn stall_transitions(threshold: Duration) -> Vec<(Arc<OperationMonitor>, bool)> {
+    let Ok(operations) = OPERATIONS.lock() else {
+        return Vec::new();
+    };
+    operations
+        .values()
+        .filter_map(|monitor| {
+            let stalled = monitor.idle() >= threshold;
+            let was_warned = monitor.warned.swap(stalled, Ordering::Relaxed);
+            (stalled != was_warned).then(|| (Arc::clone(monitor), stalled))
+        })
+        .collect()
+}
+
+pub fn start_watchdog(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        let minutes = super::get_setting_i32("backup_stall_minutes", DEFAULT_STALL_MINUTES);
+        if minutes <= 0 {
+            continue;
+        }
+        let threshold = Duration::from_secs(minutes as u64 * 60);
+        for (monitor, stalled) in stall_transitions(threshold) {
+            let event = if stalled {
+                "backup:stalled"
+            } else {
+                "backup:stall-cleared"
+            };
+            let _ = app.emit(event, monitor.event());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn monitored_copy_honours_skip_and_cancel_and_reports_stalls() {
+        let guard = register("game-1", "backup", None);
+        let monitor = guard.monitor();
+        let data = vec![7u8; COPY_CHUNK * 2 + 10];
+
+        let mut out = Vec::new();
+        let copied = monitor
+            .copy(&mut Cursor::new(&data), &mut out)
+            .expect("copy");
+        assert_eq!(copied as usize, data.len());
+        assert_eq!(out, data);
+
+        monitor.begin_file(Path::new("C:/saves/slot1.sav"));
+        request(&monitor.id, StallAction::SkipFile).expect("skip");
+        assert!(matches!(
+            monitor.copy(&mut Cursor::new(&data), &mut Vec::new()),
+            Err(CopyInterrupted::Skipped)
+        ));
+        // The skip is consumed; the next file copies normally.
+        monitor.begin_file(Path::new("C:/saves/slot2.sav"));
+        assert!(monitor
+            .copy(&mut Cursor::new(&data), &mut Vec::new())
+            .is_ok());
+
+        let stalled = stall_transitions(Duration::ZERO);
+        let mine = stalled
+            .iter()
+            .find(|(m, _)| m.id == monitor.id)
+            .expect("reported");
+        assert!(mine.1);
+        assert_eq!(mine.0.event().path.as_deref(), Some("C:/saves/slot2.sav"));
+        assert!(!stall_transitions(Duration::ZERO)
+            .iter()
+            .any(|(m, _)| m.id == monitor.id));
+
+        request(&monitor.id, StallAction::Cancel).expect("cancel");
+        assert!(matches!(
+            monitor.copy(&mut Cursor::new(&data), &mut Vec::new()),
+            Err(CopyInterrupted::Cancelled)
+        ));
+
+        let id = monitor.id.clone();
+        drop(guard);
+        assert!(request(&id, StallAction::Cancel).is_err());
+    }
+}
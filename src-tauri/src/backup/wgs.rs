@@ -0,0 +1,299 @@
+//! Xbox / Microsoft Store (WGS, "Windows Gaming Services") save containers.
+//!
+//! `SystemAppData\wgs\<user>\containers.index` lists the game's named containers; each
+//! container lives in a folder named after a GUID and holds a `container.<n>` file that maps
+//! blob names to the GUID-named files next to it. Neither the folders nor the files carry
+//! readable names, so they are resolved through these two files.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CONTAINERS_INDEX: &str = "containers.index";
+/// Blob names in `container.<n>` are fixed 64-character UTF-16 fields.
+const BLOB_NAME_CHARS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WgsBlob {
+    pub name: String,
+    pub file: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WgsContainer {
+    pub name: String,
+    pub folder: PathBuf,
+    pub blobs: Vec<WgsBlob>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct IndexEntry {
+    name: String,
+    number: u8,
+    folder: String,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| "Повреждённый контейнер WGS: неожиданный конец файла".to_string())?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), String> {
+        self.take(len).map(|_| ())
+    }
+
+    fn utf16(&mut self, chars: usize) -> Result<String, String> {
+        let units: Vec<u16> = self
+            .take(chars * 2)?
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .take_while(|unit| *unit != 0)
+            .collect();
+        Ok(String::from_utf16_lossy(&units))
+    }
+
+    /// Length-prefixed (in UTF-16 units) string.
+    fn string(&mut self) -> Result<String, String> {
+        let chars = self.u32()? as usize;
+        self.utf16(chars)
+    }
+
+    /// GUID in the mixed-endian Windows layout, rendered the way WGS names folders and files.
+    fn guid(&mut self) -> Result<String, String> {
+        let b = self.take(16)?;
+        let mut out = format!(
+            "{:08X}{:04X}{:04X}",
+            u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            u16::from_le_bytes([b[4], b[5]]),
+            u16::from_le_bytes([b[6], b[7]]),
+        );
+        for byte in &b[8..] {
+            out.push_str(&format!("{:02X}", byte));
+        }
+        Ok(out)
+    }
+}
+
+fn parse_containers_index(bytes: &[u8]) -> Result<Vec<IndexEntry>, String> {
+    let mut r = Reader::new(bytes);
+    let _version = r.u32()?;
+    let count = r.u32()?;
+    r.skip(4)?;
+    let _package = r.string()?;
+    r.skip(8 + 4)?; // last modified, flags
+    let _store_id = r.string()?;
+    r.skip(8)?;
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let name = r.string()?;
+        let _name_again = r.string()?;
+        let _etag = r.string()?;
+        let number = r.u8()?;
+        r.skip(4)?;
+        let folder = r.guid()?;
+        r.skip(8 + 8 + 8)?; // last modified, unknown, size
+        entries.push(IndexEntry {
+            name,
+            number,
+            folder,
+        });
+    }
+    Ok(entries)
+}
+
+/// `(blob name, cloud guid, local guid)` entries of a `container.<n>` file.
+fn parse_container_file(bytes: &[u8]) -> Result<Vec<(String, String, String)>, String> {
+    let mut r = Reader::new(bytes);
+    let _version = r.u32()?;
+    let count = r.u32()?;
+    let mut blobs = Vec::new();
+    for _ in 0..count {
+        let name = r.utf16(BLOB_NAME_CHARS)?;
+        let cloud = r.guid()?;
+        let local = r.guid()?;
+        blobs.push((name, cloud, local));
+    }
+    Ok(blobs)
+}
+
+pub fn is_containers_index(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        name.to_string_lossy()
+            .eq_ignore_ascii_case(CONTAINERS_INDEX)
+    })
+}
+
+/// Containers listed in `index_path` that exist on disk, with their blobs resolved to files.
+pub fn read_containers(index_path: &Path) -> Result<Vec<WgsContainer>, String> {
+    let bytes = fs::read(index_path).map_err(|e| e.to_string())?;
+    let root = index_path.parent().unwrap_or(Path::new(""));
+    let mut containers = Vec::new();
+    for entry in parse_containers_index(&bytes)? {
+        let folder = root.join(&entry.folder);
+        let Ok(bytes) = fs::read(folder.join(format!("container.{}", entry.number))) else {
+            continue;
+        };
+        let blobs = parse_container_file(&bytes)?
+            .into_iter()
+            .filter_map(|(name, cloud, local)| {
+                // The local copy is usually the one on disk; fresh cloud downloads keep the other.
+                [local, cloud]
+                    .into_iter()
+                    .map(|guid| folder.join(guid))
+                    .find(|file| file.is_file())
+                    .map(|file| WgsBlob { name, file })
+            })
+            .collect();
+        containers.push(WgsContainer {
+            name: entry.name,
+            folder,
+            blobs,
+        });
+    }
+    Ok(containers)
+}
+
+/// Readable `<container>/<blob>` names for the GUID-named files under `index_path`.
+pub fn container_names(index_path: &Path) -> HashMap<PathBuf, String> {
+    let Ok(containers) = read_containers(index_path) else {
+        return HashMap::new();
+    };
+    containers
+        .into_iter()
+        .flat_map(|container| {
+            let name = container.name;
+            container
+                .blobs
+                .into_iter()
+                .map(move |blob| (blob.file, format!("{}/{}", name, blob.name)))
+        })
+        .collect()
+}
+
+fn is_guid_folder(name: &str) -> bool {
+    name.len() == 32 && name.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// After a restore replaced `containers.index`, container folders it no longer references
+/// (written by a newer save) would be orphaned and can confuse the store's sync, so they are
+/// removed. Returns how many folders were deleted.
+pub fn prune_stale_containers(index_path: &Path) -> Result<usize, String> {
+    let bytes = fs::read(index_path).map_err(|e| e.to_string())?;
+    let referenced: HashSet<String> = parse_containers_index(&bytes)?
+        .into_iter()
+        .map(|entry| entry.folder.to_ascii_uppercase())
+        .collect();
+    if referenced.is_empty() {
+        return Ok(0);
+    }
+    let root = index_path.parent().unwrap_or(Path::new(""));
+    let mut removed = 0;
+    for entry in fs::read_dir(root).map_err(|e| e.to_string())?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_ascii_uppercase();
+        if entry.path().is_dir() && is_guid_folder(&name) && !referenced.contains(&name) {
+            fs::remove_dir_all(entry.path()).map_err(|e| e.to_string())?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn string(out: &mut Vec<u8>, value: &str) {
+        let units: Vec<u16> = value.encode_utf16().collect();
+        out.extend((units.len() as u32).to_le_bytes());
+        out.extend(units.iter().flat_map(|u| u.to_le_bytes()));
+    }
+
+    fn guid(first: u32) -> ([u8; 16], String) {
+        let mut bytes = [0x11u8; 16];
+        bytes[..4].copy_from_slice(&first.to_le_bytes());
+        (bytes, format!("{:08X}{}", first, "1111".repeat(6)))
+    }
+
+    fn index(containers: &[(&str, u8, [u8; 16])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(14u32.to_le_bytes());
+        out.extend((containers.len() as u32).to_le_bytes());
+        out.extend(0u32.to_le_bytes());
+        string(&mut out, "Game_8wekyb3d8bbwe");
+        out.extend([0u8; 12]);
+        string(&mut out, "store-id");
+        out.extend([0u8; 8]);
+        for (name, number, folder) in containers {
+            string(&mut out, name);
+            string(&mut out, name);
+            string(&mut out, "\"0x8D\"");
+            out.push(*number);
+            out.extend([0u8; 4]);
+            out.extend(folder);
+            out.extend([0u8; 24]);
+        }
+        out
+    }
+
+    #[test]
+    fn containers_resolve_to_readable_names_and_stale_folders_are_pruned() {
+        let dir = tempdir().expect("tempdir");
+        let (folder_bytes, folder) = guid(0xA1B2C3D4);
+        let (cloud_bytes, _) = guid(0x01);
+        let (local_bytes, local) = guid(0x02);
+        let (_, stale) = guid(0xDEADBEEF);
+
+        let index_path = dir.path().join(CONTAINERS_INDEX);
+        fs::write(&index_path, index(&[("Slot1", 3, folder_bytes)])).expect("write index");
+        fs::create_dir_all(dir.path().join(&folder)).expect("create container");
+        let mut container = Vec::new();
+        container.extend(4u32.to_le_bytes());
+        container.extend(1u32.to_le_bytes());
+        let mut name: Vec<u16> = "Data".encode_utf16().collect();
+        name.resize(BLOB_NAME_CHARS, 0);
+        container.extend(name.iter().flat_map(|u| u.to_le_bytes()));
+        container.extend(cloud_bytes);
+        container.extend(local_bytes);
+        fs::write(dir.path().join(&folder).join("container.3"), container).expect("write");
+        let blob = dir.path().join(&folder).join(&local);
+        fs::write(&blob, b"save").expect("write blob");
+        fs::create_dir_all(dir.path().join(&stale)).expect("create stale");
+        fs::create_dir_all(dir.path().join("not-a-guid")).expect("create other");
+
+        assert!(is_containers_index(&index_path));
+        let names = container_names(&index_path);
+        assert_eq!(names.get(&blob).map(String::as_str), Some("Slot1/Data"));
+
+        assert_eq!(prune_stale_containers(&index_path).expect("prune"), 1);
+        assert!(!dir.path().join(&stale).exists());
+        assert!(dir.path().join(&folder).exists());
+        assert!(dir.path().join("not-a-guid").exists());
+    }
+}
@@ -1,9 +1,12 @@
-use rusqlite::{params, Connection, Result};
-use std::path::PathBuf;
+use rusqlite::{params, Connection, ErrorCode, OpenFlags, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::Duration;
 
 lazy_static::lazy_static! {
     pub static ref DB: Mutex<Option<Connection>> = Mutex::new(None);
+    static ref DB_STATUS: Mutex<DatabaseStatus> = Mutex::new(DatabaseStatus::default());
 }
 
 #[cfg(test)]
@@ -29,6 +32,41 @@ pub(crate) fn set_test_db(conn: Connection) -> TestDbGuard {
     TestDbGuard
 }
 
+/// Bumped whenever `init_schema` changes shape; stored in `PRAGMA user_version`.
+pub const SCHEMA_VERSION: i32 = 18;
+
+/// Opening gives up after this many tries when another process holds the database lock.
+const OPEN_ATTEMPTS: u32 = 5;
+/// Wait before the second try; doubled after every failed one.
+const OPEN_RETRY_DELAY: Duration = Duration::from_millis(250);
+/// How long a single statement waits on a lock before reporting `SQLITE_BUSY`.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseMode {
+    #[default]
+    Ready,
+    /// Another process kept the database locked; the library can be browsed but not edited.
+    ReadOnly,
+    /// The database could not be opened at all.
+    Unavailable,
+}
+
+/// How startup went with the database, shown to the user when it isn't `Ready`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DatabaseStatus {
+    pub mode: DatabaseMode,
+    pub path: String,
+    pub attempts: u32,
+    /// The last error from SQLite, when opening didn't go cleanly.
+    pub error: Option<String>,
+}
+
+pub fn database_status() -> DatabaseStatus {
+    DB_STATUS.lock().unwrap().clone()
+}
+
 pub fn get_db_path() -> PathBuf {
     let app_data = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
     let db_dir = app_data.join("arrancador");
@@ -36,21 +74,115 @@ pub fn get_db_path() -> PathBuf {
     db_dir.join("arrancador.db")
 }
 
+/// Opens the database and records the outcome in [`database_status`]. A database locked
+/// by another process (a cloud sync client, a leftover instance) is retried with backoff
+/// and then opened read-only; only a database that can't be read at all is an error.
 pub fn init_database() -> Result<()> {
-    let db_path = get_db_path();
-    println!("Initializing database at: {:?}", db_path);
-
-    let conn = Connection::open(&db_path)?;
-
-    init_schema(&conn)?;
+    let (conn, status) = if crate::test_mode::is_enabled() {
+        println!("Test mode: using in-memory database");
+        let conn = crate::test_mode::open_test_database()?;
+        (conn, DatabaseStatus::default())
+    } else {
+        let db_path = get_db_path();
+        println!("Initializing database at: {:?}", db_path);
+        match open_database(&db_path) {
+            Ok(opened) => opened,
+            Err((e, status)) => {
+                *DB_STATUS.lock().unwrap() = status;
+                return Err(e);
+            }
+        }
+    };
 
     let mut db = DB.lock().unwrap();
     *db = Some(conn);
+    *DB_STATUS.lock().unwrap() = status;
 
     println!("Database initialized successfully");
     Ok(())
 }
 
+pub(crate) fn is_locked_error(e: &rusqlite::Error) -> bool {
+    matches!(
+        e,
+        rusqlite::Error::SqliteFailure(err, _)
+            if matches!(err.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Runs `op` until it succeeds, fails with something other than a lock, or `attempts` runs
+/// out. Returns the last result with the number of attempts made.
+fn retry_while_locked<T>(
+    attempts: u32,
+    first_delay: Duration,
+    mut op: impl FnMut() -> Result<T>,
+) -> (Result<T>, u32) {
+    let mut delay = first_delay;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if is_locked_error(&e) && attempt < attempts => {
+                eprintln!(
+                    "Database is locked (attempt {}/{}), retrying in {:?}",
+                    attempt, attempts, delay
+                );
+                std::thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return (result, attempt),
+        }
+    }
+}
+
+fn open_database(
+    path: &Path,
+) -> std::result::Result<(Connection, DatabaseStatus), (rusqlite::Error, DatabaseStatus)> {
+    let mut status = DatabaseStatus {
+        path: path.to_string_lossy().to_string(),
+        ..DatabaseStatus::default()
+    };
+    let (result, attempts) = retry_while_locked(OPEN_ATTEMPTS, OPEN_RETRY_DELAY, || {
+        let conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        init_schema(&conn)?;
+        Ok(conn)
+    });
+    status.attempts = attempts;
+    let error = match result {
+        Ok(conn) => return Ok((conn, status)),
+        Err(e) => e,
+    };
+    status.error = Some(error.to_string());
+    if !is_locked_error(&error) {
+        status.mode = DatabaseMode::Unavailable;
+        return Err((error, status));
+    }
+
+    eprintln!("Database is still locked, opening read-only: {}", error);
+    let read_only = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .and_then(|conn| {
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        // Fails right away if the lock also blocks readers.
+        schema_version(&conn)?;
+        Ok(conn)
+    });
+    match read_only {
+        Ok(conn) => {
+            status.mode = DatabaseMode::ReadOnly;
+            Ok((conn, status))
+        }
+        Err(e) => {
+            status.mode = DatabaseMode::Unavailable;
+            status.error = Some(e.to_string());
+            Err((e, status))
+        }
+    }
+}
+
 pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
     // Games table
     conn.execute(
@@ -86,6 +218,9 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
             backup_count INTEGER DEFAULT 0,
             save_path TEXT,
             save_path_checked INTEGER DEFAULT 0,
+            restore_on_launch INTEGER DEFAULT 1,
+            backup_on_exit INTEGER DEFAULT 1,
+            do_not_track INTEGER DEFAULT 0,
 
             -- User rating
             user_rating INTEGER,
@@ -116,6 +251,22 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Monthly roll-ups of playtime_daily rows older than the archive horizon
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS playtime_monthly (
+            game_id TEXT NOT NULL,
+            month TEXT NOT NULL,
+            seconds INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+            UNIQUE(game_id, month)
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_playtime_monthly_month ON playtime_monthly(month)",
+        [],
+    )?;
+
     // Backups table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS backups (
@@ -131,6 +282,312 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         [],
     )?;
     ensure_backup_indexes(conn)?;
+    ensure_backup_columns(conn)?;
+
+    // Outcome of every backup attempt, including failures that never produced a backup row
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS backup_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id TEXT NOT NULL,
+            backup_id TEXT,
+            created_at TEXT NOT NULL,
+            is_auto INTEGER DEFAULT 0,
+            success INTEGER NOT NULL,
+            attempt INTEGER NOT NULL DEFAULT 1,
+            error TEXT,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_backup_events_game_created ON backup_events(game_id, created_at DESC)",
+        [],
+    )?;
+
+    // Individual play sessions recorded by the tracker, with optional user notes
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS play_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT NOT NULL,
+            duration_seconds INTEGER NOT NULL DEFAULT 0,
+            note TEXT,
+            backup_id TEXT,
+            launch_profile TEXT,
+            store TEXT,
+            exit_reason TEXT,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    ensure_session_columns(conn)?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_play_sessions_game_ended ON play_sessions(game_id, ended_at DESC)",
+        [],
+    )?;
+
+    // Launch attempts and how they ended, for spotting games that crash on start
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS launches (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            duration_seconds INTEGER,
+            outcome TEXT NOT NULL,
+            error TEXT,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_launches_game_started ON launches(game_id, started_at DESC)",
+        [],
+    )?;
+
+    // Named launch configurations per game (target, args, env, hooks)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS launch_profiles (
+            id TEXT PRIMARY KEY,
+            game_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            target TEXT,
+            args TEXT NOT NULL DEFAULT '[]',
+            env TEXT NOT NULL DEFAULT '{}',
+            working_dir TEXT,
+            pre_launch TEXT,
+            post_exit TEXT,
+            is_default INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_launch_profiles_game ON launch_profiles(game_id)",
+        [],
+    )?;
+
+    // Manual playtime changes, e.g. hours carried over from Steam or Playnite
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS playtime_adjustments (
+            id TEXT PRIMARY KEY,
+            game_id TEXT NOT NULL,
+            seconds INTEGER NOT NULL,
+            note TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Programs started alongside a game and optionally closed with it
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_companions (
+            id TEXT PRIMARY KEY,
+            game_id TEXT NOT NULL,
+            exe_path TEXT NOT NULL,
+            args TEXT,
+            close_on_exit INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_game_companions_game ON game_companions(game_id)",
+        [],
+    )?;
+
+    // Emulators that ROM games (games.emulator_id) are started through
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS emulators (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            exe_path TEXT NOT NULL,
+            args_template TEXT NOT NULL DEFAULT '{ROM}',
+            rom_extensions TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Additional save roots (and excluded folders) per game, on top of games.save_path
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_save_paths (
+            id TEXT PRIMARY KEY,
+            game_id TEXT NOT NULL,
+            path TEXT NOT NULL,
+            label TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            exclude INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_game_save_paths_game ON game_save_paths(game_id)",
+        [],
+    )?;
+
+    // User-written manifest fragments merged over the ludusavi entry during save discovery
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_manifest_overrides (
+            game_id TEXT PRIMARY KEY,
+            files TEXT NOT NULL DEFAULT '[]',
+            registry TEXT NOT NULL DEFAULT '[]',
+            replace_upstream INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Save locations from PCGamingWiki the user confirmed, offered as candidate save roots
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_wiki_save_paths (
+            game_id TEXT NOT NULL,
+            path TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (game_id, path),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Install folders moved to cold storage on the backup drive
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS install_archives (
+            id TEXT PRIMARY KEY,
+            game_id TEXT NOT NULL UNIQUE,
+            archive_path TEXT NOT NULL,
+            original_dir TEXT NOT NULL,
+            exe_relative TEXT NOT NULL,
+            file_count INTEGER NOT NULL DEFAULT 0,
+            original_bytes INTEGER NOT NULL DEFAULT 0,
+            archive_bytes INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // SHA-256 of every install file, recorded by verify_game_files as the known-good state
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_file_hashes (
+            game_id TEXT NOT NULL,
+            rel_path TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            sha256 TEXT NOT NULL,
+            recorded_at TEXT NOT NULL,
+            PRIMARY KEY (game_id, rel_path),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Dated Markdown notes and external links (wikis, mod pages) per game
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_notes (
+            id TEXT PRIMARY KEY,
+            game_id TEXT NOT NULL,
+            body TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_game_notes_game_created ON game_notes(game_id, created_at DESC)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_links (
+            id TEXT PRIMARY KEY,
+            game_id TEXT NOT NULL,
+            title TEXT,
+            url TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // User-defined game fields and their per-game values
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS custom_fields (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            field_type TEXT NOT NULL DEFAULT 'text',
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_custom_values (
+            game_id TEXT NOT NULL,
+            field_id TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (game_id, field_id),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+            FOREIGN KEY (field_id) REFERENCES custom_fields(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_game_custom_values_field ON game_custom_values(field_id, value)",
+        [],
+    )?;
+
+    // Tags on games, and smart collections (saved GameQuery filters)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tags (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            color TEXT,
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_tags (
+            game_id TEXT NOT NULL,
+            tag_id TEXT NOT NULL,
+            PRIMARY KEY (game_id, tag_id),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+            FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_game_tags_tag ON game_tags(tag_id)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS collections (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            query TEXT NOT NULL DEFAULT '{}',
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Downloaded covers, so the same URL is fetched only once
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS image_cache (
+            url TEXT PRIMARY KEY,
+            path TEXT NOT NULL,
+            thumbnail TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL DEFAULT 0,
+            last_used TEXT
+        )",
+        [],
+    )?;
 
     // Settings table
     conn.execute(
@@ -147,7 +604,33 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             path TEXT NOT NULL UNIQUE,
             last_scanned TEXT,
-            auto_scan INTEGER DEFAULT 0
+            auto_scan INTEGER DEFAULT 0,
+            threads INTEGER
+        )",
+        [],
+    )?;
+    ensure_scan_directory_columns(conn)?;
+
+    // Directories (with mtimes) and candidate files seen under each scanned root, so a
+    // rescan only reads directories that changed
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scan_cache (
+            root TEXT NOT NULL,
+            path TEXT NOT NULL,
+            is_dir INTEGER NOT NULL,
+            mtime INTEGER NOT NULL,
+            PRIMARY KEY (root, path)
+        )",
+        [],
+    )?;
+
+    // Metadata API responses by request URL, so repeated lookups skip the network
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS http_cache (
+            url TEXT PRIMARY KEY,
+            body TEXT NOT NULL,
+            fetched_at TEXT NOT NULL,
+            expires_at TEXT NOT NULL
         )",
         [],
     )?;
@@ -162,6 +645,31 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         ("backup_compression_level", "60"),
         ("backup_skip_compression_once", "false"),
         ("max_backups_per_game", "5"),
+        ("backup_verify_mode", "off"),
+        ("backup_verify_sample_size", "8"),
+        ("backup_quota_game_mb", "0"),
+        ("backup_quota_total_mb", "0"),
+        ("backup_symlink_policy", "skip"),
+        ("backup_shadow_copy", "true"),
+        ("backup_stall_minutes", "5"),
+        ("backup_while_running", "warn"),
+        ("backup_integrity_check", "true"),
+        ("save_watch_debounce_seconds", "10"),
+        ("backup_name_template", "{datetime}_{trigger}"),
+        ("backup_nightly_enabled", "false"),
+        ("backup_nightly_time", "03:00"),
+        ("manifest_refresh_hours", "168"),
+        ("plugin_timeout_seconds", "10"),
+        ("playtime_archive_horizon_days", "365"),
+        ("session_note_prompt_minutes", "60"),
+        ("image_cache_max_mb", "1024"),
+        ("crash_threshold_seconds", "15"),
+        ("running_instance_policy", "focus"),
+        ("launch_and_minimize", "false"),
+        ("scan_blacklist", ""),
+        ("auto_scan_interval_hours", "24"),
+        ("scan_watch_debounce_seconds", "15"),
+        ("rawg_auto_match", "false"),
         ("theme", "system"),
     ];
 
@@ -172,9 +680,15 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         )?;
     }
 
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
     Ok(())
 }
 
+pub fn schema_version(conn: &Connection) -> Result<i32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
 fn ensure_game_columns(conn: &Connection) -> Result<()> {
     let mut stmt = conn.prepare("PRAGMA table_info(games)")?;
     let rows = stmt.query_map([], |row| row.get::<_, String>(1))?;
@@ -198,6 +712,93 @@ fn ensure_game_columns(conn: &Connection) -> Result<()> {
             [],
         )?;
     }
+    if !cols.contains("restore_on_launch") {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN restore_on_launch INTEGER DEFAULT 1",
+            [],
+        )?;
+    }
+    if !cols.contains("backup_on_exit") {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN backup_on_exit INTEGER DEFAULT 1",
+            [],
+        )?;
+    }
+    if !cols.contains("do_not_track") {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN do_not_track INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+    if !cols.contains("manifest_key") {
+        conn.execute("ALTER TABLE games ADD COLUMN manifest_key TEXT", [])?;
+    }
+    if !cols.contains("launch_args") {
+        conn.execute("ALTER TABLE games ADD COLUMN launch_args TEXT", [])?;
+    }
+    if !cols.contains("working_dir") {
+        conn.execute("ALTER TABLE games ADD COLUMN working_dir TEXT", [])?;
+    }
+    if !cols.contains("env_vars") {
+        conn.execute("ALTER TABLE games ADD COLUMN env_vars TEXT", [])?;
+    }
+    if !cols.contains("launch_method") {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN launch_method TEXT DEFAULT 'direct'",
+            [],
+        )?;
+    }
+    if !cols.contains("store_id") {
+        conn.execute("ALTER TABLE games ADD COLUMN store_id TEXT", [])?;
+    }
+    if !cols.contains("emulator_id") {
+        conn.execute("ALTER TABLE games ADD COLUMN emulator_id TEXT", [])?;
+    }
+    if !cols.contains("exe_size") {
+        conn.execute("ALTER TABLE games ADD COLUMN exe_size INTEGER", [])?;
+    }
+    if !cols.contains("is_hidden") {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN is_hidden INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+    if !cols.contains("cover_thumbnail") {
+        conn.execute("ALTER TABLE games ADD COLUMN cover_thumbnail TEXT", [])?;
+    }
+    if !cols.contains("install_size") {
+        conn.execute("ALTER TABLE games ADD COLUMN install_size INTEGER", [])?;
+    }
+    if !cols.contains("install_size_checked_at") {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN install_size_checked_at TEXT",
+            [],
+        )?;
+    }
+    if !cols.contains("instance_policy") {
+        conn.execute("ALTER TABLE games ADD COLUMN instance_policy TEXT", [])?;
+    }
+    if !cols.contains("igdb_id") {
+        conn.execute("ALTER TABLE games ADD COLUMN igdb_id INTEGER", [])?;
+    }
+    if !cols.contains("metadata_source") {
+        conn.execute("ALTER TABLE games ADD COLUMN metadata_source TEXT", [])?;
+    }
+    if !cols.contains("protondb_tier") {
+        conn.execute("ALTER TABLE games ADD COLUMN protondb_tier TEXT", [])?;
+    }
+    if !cols.contains("steam_deck_status") {
+        conn.execute("ALTER TABLE games ADD COLUMN steam_deck_status TEXT", [])?;
+    }
+    if !cols.contains("locked_fields") {
+        conn.execute("ALTER TABLE games ADD COLUMN locked_fields TEXT", [])?;
+    }
+    if !cols.contains("manifest_key_confirmed") {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN manifest_key_confirmed INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
 
     Ok(())
 }
@@ -211,6 +812,95 @@ fn ensure_game_indexes(conn: &Connection) -> Result<()> {
         "CREATE INDEX IF NOT EXISTS idx_games_favorite_name ON games(is_favorite, name)",
         [],
     )?;
+    // Orders offered by get_games_page.
+    for (name, column) in [
+        ("idx_games_last_played", "last_played"),
+        ("idx_games_date_added", "date_added"),
+        ("idx_games_play_count", "play_count"),
+        ("idx_games_total_playtime", "total_playtime"),
+        ("idx_games_user_rating", "user_rating"),
+        ("idx_games_install_size", "install_size"),
+    ] {
+        conn.execute(
+            &format!("CREATE INDEX IF NOT EXISTS {name} ON games({column})"),
+            [],
+        )?;
+    }
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_games_name_nocase ON games(name COLLATE NOCASE)",
+        [],
+    )?;
+    Ok(())
+}
+
+fn ensure_session_columns(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(play_sessions)")?;
+    let cols: std::collections::HashSet<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .flatten()
+        .collect();
+
+    if !cols.contains("launch_profile") {
+        conn.execute(
+            "ALTER TABLE play_sessions ADD COLUMN launch_profile TEXT",
+            [],
+        )?;
+    }
+    if !cols.contains("store") {
+        conn.execute("ALTER TABLE play_sessions ADD COLUMN store TEXT", [])?;
+    }
+    if !cols.contains("exit_reason") {
+        conn.execute("ALTER TABLE play_sessions ADD COLUMN exit_reason TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Per-folder read thread count; `NULL` keeps the scan default.
+fn ensure_scan_directory_columns(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(scan_directories)")?;
+    let cols: std::collections::HashSet<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .flatten()
+        .collect();
+
+    if !cols.contains("threads") {
+        conn.execute(
+            "ALTER TABLE scan_directories ADD COLUMN threads INTEGER",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Timing of each backup, used by the Defender throughput diagnostics.
+fn ensure_backup_columns(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(backups)")?;
+    let cols: std::collections::HashSet<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .flatten()
+        .collect();
+
+    if !cols.contains("duration_ms") {
+        conn.execute("ALTER TABLE backups ADD COLUMN duration_ms INTEGER", [])?;
+    }
+    if !cols.contains("defender_cpu") {
+        conn.execute("ALTER TABLE backups ADD COLUMN defender_cpu REAL", [])?;
+    }
+    if !cols.contains("playtime_seconds") {
+        conn.execute(
+            "ALTER TABLE backups ADD COLUMN playtime_seconds INTEGER",
+            [],
+        )?;
+    }
+    if !cols.contains("last_session_seconds") {
+        conn.execute(
+            "ALTER TABLE backups ADD COLUMN last_session_seconds INTEGER",
+            [],
+        )?;
+    }
+    if !cols.contains("label") {
+        conn.execute("ALTER TABLE backups ADD COLUMN label TEXT", [])?;
+    }
     Ok(())
 }
 
@@ -264,8 +954,67 @@ mod tests {
             .flatten()
             .collect();
 
-        for column in ["user_rating", "user_note", "save_path", "save_path_checked"] {
+        for column in [
+            "user_rating",
+            "user_note",
+            "save_path",
+            "save_path_checked",
+            "restore_on_launch",
+            "backup_on_exit",
+            "do_not_track",
+            "launch_args",
+            "working_dir",
+            "env_vars",
+            "launch_method",
+            "store_id",
+            "emulator_id",
+            "is_hidden",
+            "exe_size",
+            "cover_thumbnail",
+            "install_size",
+            "install_size_checked_at",
+            "instance_policy",
+            "igdb_id",
+            "metadata_source",
+            "protondb_tier",
+            "steam_deck_status",
+            "locked_fields",
+        ] {
             assert!(columns.contains(column));
         }
     }
+
+    #[test]
+    fn locked_database_is_retried_with_backoff() {
+        let busy = || {
+            rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                None,
+            )
+        };
+        assert!(is_locked_error(&busy()));
+        assert!(!is_locked_error(&rusqlite::Error::InvalidQuery));
+
+        let mut calls = 0;
+        let (result, attempts) = retry_while_locked(5, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                Err(busy())
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.expect("opened after retries"), 3);
+        assert_eq!(attempts, 3);
+
+        let (result, attempts) = retry_while_locked(4, Duration::ZERO, || Err::<(), _>(busy()));
+        assert!(result.is_err_and(|e| is_locked_error(&e)));
+        assert_eq!(attempts, 4);
+
+        let (result, attempts) = retry_while_locked(4, Duration::ZERO, || {
+            Err::<(), _>(rusqlite::Error::InvalidQuery)
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
 }
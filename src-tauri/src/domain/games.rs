@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Game {
@@ -18,9 +19,28 @@ pub struct Game {
     pub platforms: Option<String>,
     pub developers: Option<String>,
     pub publishers: Option<String>,
+    /// IGDB game id, once IGDB metadata has been applied.
+    #[serde(default)]
+    pub igdb_id: Option<i64>,
+    /// Provider the game's metadata is searched on and was last applied from; `None` means
+    /// RAWG.
+    #[serde(default)]
+    pub metadata_source: Option<MetadataSource>,
+    /// ProtonDB rating (`platinum`, `gold`, `silver`, `bronze`, `borked`), as last fetched.
+    #[serde(default)]
+    pub protondb_tier: Option<String>,
+    /// Valve's Steam Deck verification, as last fetched.
+    #[serde(default)]
+    pub steam_deck_status: Option<SteamDeckStatus>,
+    /// Metadata fields that applying metadata, by hand or automatically, leaves alone.
+    #[serde(default)]
+    pub locked_fields: Vec<MetadataField>,
 
     // Local metadata
     pub cover_image: Option<String>,
+    /// Downscaled copy of a locally stored cover, for the library grid.
+    #[serde(default)]
+    pub cover_thumbnail: Option<String>,
     pub is_favorite: bool,
     pub play_count: i32,
     pub total_playtime: i64,
@@ -32,9 +52,75 @@ pub struct Game {
     pub last_backup: Option<String>,
     pub backup_count: i32,
     pub save_path: Option<String>,
+    /// Offer to restore the newest backup at launch when the saves on disk look older.
+    pub restore_on_launch: bool,
+    /// Take an automatic backup when the game exits.
+    pub backup_on_exit: bool,
+    /// Keep the game out of playtime tracking, session history, last-played and stats.
+    /// Launching and backups still work.
+    pub do_not_track: bool,
+    /// Left out of the library list, favourites and search; playtime and backups are kept.
+    #[serde(default)]
+    pub is_hidden: bool,
+    /// The latest launch crashed within seconds or never started.
+    #[serde(default)]
+    pub last_launch_failed: bool,
+    /// Bytes the install folder takes, as last measured; `None` until it has been.
+    #[serde(default)]
+    pub install_size: Option<i64>,
 
     pub user_rating: Option<i32>,
     pub user_note: Option<String>,
+
+    /// Extra command-line arguments, split like a shell would (`-windowed "-name Player"`).
+    pub launch_args: Option<String>,
+    /// Directory to start the game in instead of the executable's folder.
+    pub working_dir: Option<String>,
+    /// Environment variables set for every launch; a profile's own `env` wins on conflicts.
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+    /// How the plain launch button starts the game; store launches go through `store_id`.
+    #[serde(default)]
+    pub launch_method: LaunchMethod,
+    /// Steam app id, Epic app name or GOG product id, depending on `launch_method`.
+    pub store_id: Option<String>,
+    /// Set for ROM games; their `exe_path` is then the ROM, started through this emulator.
+    pub emulator_id: Option<String>,
+    /// What launching does while the game is already running; `None` follows the
+    /// `running_instance_policy` setting.
+    #[serde(default)]
+    pub instance_policy: Option<InstancePolicy>,
+
+    /// User-defined field values keyed by custom field id.
+    #[serde(default)]
+    pub custom_fields: BTreeMap<String, String>,
+    /// Ids of the tags on this game.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// What goes along with a game removed from the library. Its settings, tags and backup
+/// records always go; backup files left on disk are picked up again if the game is re-added.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DeleteGameOptions {
+    /// Also remove the backup archives, and an archived install, from disk. Required for a
+    /// game whose install is archived.
+    #[serde(default)]
+    pub delete_backups: bool,
+    /// Also remove playtime history and sessions, which otherwise keep counting in the totals.
+    #[serde(default)]
+    pub delete_stats: bool,
+}
+
+/// A file in the scanned folders that looks like the missing executable of a library game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovedGameMatch {
+    pub game_id: String,
+    pub game_name: String,
+    pub old_exe_path: String,
+    pub new_exe_path: String,
+    /// Whether the file has the size recorded for the game; `None` when no size was recorded.
+    pub size_matches: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -44,7 +130,7 @@ pub struct NewGame {
     pub exe_name: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct UpdateGame {
     pub id: String,
     pub name: Option<String>,
@@ -64,4 +150,452 @@ pub struct UpdateGame {
     pub publishers: Option<String>,
     pub user_rating: Option<i32>,
     pub user_note: Option<String>,
+    /// An empty string clears the arguments.
+    pub launch_args: Option<String>,
+    /// An empty string goes back to the executable's folder.
+    pub working_dir: Option<String>,
+    pub launch_method: Option<LaunchMethod>,
+    /// An empty string clears the store id.
+    pub store_id: Option<String>,
+    /// An empty string turns the game back into a plain executable.
+    pub emulator_id: Option<String>,
+}
+
+/// Starting the executable directly bypasses store DRM and overlays, which some titles need.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMethod {
+    #[default]
+    Direct,
+    Steam,
+    Epic,
+    Gog,
+}
+
+impl LaunchMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LaunchMethod::Direct => "direct",
+            LaunchMethod::Steam => "steam",
+            LaunchMethod::Epic => "epic",
+            LaunchMethod::Gog => "gog",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "steam" => LaunchMethod::Steam,
+            "epic" => LaunchMethod::Epic,
+            "gog" => LaunchMethod::Gog,
+            _ => LaunchMethod::Direct,
+        }
+    }
+}
+
+/// What a launch does when the game already has a running instance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstancePolicy {
+    /// Bring the running game's window to the front instead of starting another copy.
+    #[default]
+    Focus,
+    /// Fail the launch with [`ALREADY_RUNNING_MESSAGE`](crate::services::games::ALREADY_RUNNING_MESSAGE).
+    Refuse,
+    /// Start another copy.
+    Allow,
+}
+
+impl InstancePolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InstancePolicy::Focus => "focus",
+            InstancePolicy::Refuse => "refuse",
+            InstancePolicy::Allow => "allow",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "focus" => Some(InstancePolicy::Focus),
+            "refuse" => Some(InstancePolicy::Refuse),
+            "allow" => Some(InstancePolicy::Allow),
+            _ => None,
+        }
+    }
+}
+
+/// Where game metadata is looked up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataSource {
+    #[default]
+    Rawg,
+    Igdb,
+}
+
+impl MetadataSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MetadataSource::Rawg => "rawg",
+            MetadataSource::Igdb => "igdb",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "rawg" => Some(MetadataSource::Rawg),
+            "igdb" => Some(MetadataSource::Igdb),
+            _ => None,
+        }
+    }
+}
+
+/// A metadata field that can be locked against metadata refreshes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataField {
+    Name,
+    Description,
+    Released,
+    BackgroundImage,
+    /// `metacritic` and `rating`.
+    Scores,
+    Genres,
+    Platforms,
+    Developers,
+    Publishers,
+}
+
+/// Steam Deck compatibility category from Valve's review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SteamDeckStatus {
+    Verified,
+    Playable,
+    Unsupported,
+    /// Not reviewed yet.
+    Unknown,
+}
+
+impl SteamDeckStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SteamDeckStatus::Verified => "verified",
+            SteamDeckStatus::Playable => "playable",
+            SteamDeckStatus::Unsupported => "unsupported",
+            SteamDeckStatus::Unknown => "unknown",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "verified" => Some(SteamDeckStatus::Verified),
+            "playable" => Some(SteamDeckStatus::Playable),
+            "unsupported" => Some(SteamDeckStatus::Unsupported),
+            "unknown" => Some(SteamDeckStatus::Unknown),
+            _ => None,
+        }
+    }
+
+    /// The status for the `resolved_category` number of Steam's compatibility report.
+    pub fn from_category(category: i64) -> Self {
+        match category {
+            3 => SteamDeckStatus::Verified,
+            2 => SteamDeckStatus::Playable,
+            1 => SteamDeckStatus::Unsupported,
+            _ => SteamDeckStatus::Unknown,
+        }
+    }
+}
+
+/// How a launch request ended when it did not fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchResult {
+    /// A new process was started.
+    Started,
+    /// The game was already running and its window was brought to the front.
+    Focused,
+    /// The game was already running but no window of it could be found to focus.
+    AlreadyRunning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Date,
+    Boolean,
+}
+
+impl CustomFieldType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CustomFieldType::Text => "text",
+            CustomFieldType::Number => "number",
+            CustomFieldType::Date => "date",
+            CustomFieldType::Boolean => "boolean",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "number" => CustomFieldType::Number,
+            "date" => CustomFieldType::Date,
+            "boolean" => CustomFieldType::Boolean,
+            _ => CustomFieldType::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomField {
+    pub id: String,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    pub created_at: String,
+}
+
+/// Matches games whose value for `field_id` equals `value` (case-insensitive),
+/// or that have any value for the field when `value` is omitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomFieldFilter {
+    pub field_id: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameQuery {
+    pub text: Option<String>,
+    #[serde(default)]
+    pub favorites_only: bool,
+    #[serde(default)]
+    pub include_hidden: bool,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomFieldFilter>,
+    /// Games carrying every one of these tags.
+    #[serde(default)]
+    pub tag_ids: Vec<String>,
+    /// Matched against the comma-separated `genres`, case-insensitively.
+    pub genre: Option<String>,
+    /// Bounds on `total_playtime`, in seconds.
+    pub min_playtime: Option<i64>,
+    pub max_playtime: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LibraryExportOptions {
+    /// Write paths under the user's home folder as `<home>/...` so they resolve on another
+    /// machine with a different user name.
+    #[serde(default)]
+    pub tokenise_paths: bool,
+}
+
+/// How games already in the library are treated when a library file is imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStrategy {
+    /// Update them from the file, keeping this machine's paths and the larger playtime.
+    Merge,
+    /// Leave them untouched and only add the new ones.
+    SkipExisting,
+    /// Clear the library first. Backup archives stay on disk.
+    Replace,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryImportSummary {
+    pub games_added: usize,
+    pub games_updated: usize,
+    pub games_skipped: usize,
+}
+
+/// Result of checking a game's install folder against its recorded file hashes.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileVerification {
+    /// No hashes were recorded yet (or a new baseline was asked for); this run recorded them.
+    pub baseline_created: bool,
+    /// When the hashes compared against were recorded.
+    pub baseline_at: String,
+    pub checked: usize,
+    /// Paths relative to the install folder, `/`-separated.
+    pub modified: Vec<String>,
+    pub missing: Vec<String>,
+    pub added: Vec<String>,
+}
+
+/// Folder a game shortcut is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutLocation {
+    Desktop,
+    StartMenu,
+}
+
+/// What a shortcut starts: the game's executable, or the launcher through an
+/// `arrancador://launch/<id>` link so profiles, stores, emulators and backups apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutTarget {
+    Executable,
+    Launcher,
+}
+
+/// What the library grid needs to draw a tile; `get_game` loads the rest for the detail view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub id: String,
+    pub name: String,
+    pub cover_image: Option<String>,
+    pub cover_thumbnail: Option<String>,
+    pub is_favorite: bool,
+    pub last_played: Option<String>,
+    pub total_playtime: i64,
+    pub install_size: Option<i64>,
+}
+
+/// Orders `get_games_page` can return the library in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameSort {
+    #[default]
+    Name,
+    LastPlayed,
+    DateAdded,
+    PlayCount,
+    Playtime,
+    Rating,
+}
+
+impl GameSort {
+    pub fn column(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::LastPlayed => "last_played",
+            Self::DateAdded => "date_added",
+            Self::PlayCount => "play_count",
+            Self::Playtime => "total_playtime",
+            Self::Rating => "user_rating",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamePage {
+    pub games: Vec<Game>,
+    /// Games matching the filter across all pages.
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+/// A named way to start a game ("Vanilla", "Modded via MO2", "Safe mode").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchProfile {
+    pub id: String,
+    pub game_id: String,
+    pub name: String,
+    /// Executable to start instead of the game's own `exe_path`.
+    pub target: Option<String>,
+    pub args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    /// Defaults to the target's folder.
+    pub working_dir: Option<String>,
+    /// Shell command run (and waited for) before the game starts.
+    pub pre_launch: Option<String>,
+    /// Shell command run after the tracker sees the game exit.
+    pub post_exit: Option<String>,
+    /// Used by a plain launch of the game; at most one profile per game has it.
+    pub is_default: bool,
+    pub created_at: String,
+}
+
+/// Create (`id` omitted) or replace a launch profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchProfileInput {
+    pub id: Option<String>,
+    pub game_id: String,
+    pub name: String,
+    pub target: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    pub working_dir: Option<String>,
+    pub pre_launch: Option<String>,
+    pub post_exit: Option<String>,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/// An emulator ROM games are started through.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Emulator {
+    pub id: String,
+    pub name: String,
+    pub exe_path: String,
+    /// Arguments with a `{ROM}` token for the ROM path (`-f -L cores/snes.dll "{ROM}"`).
+    pub args_template: String,
+    /// Lower-case extensions without the dot; files with them are offered as ROMs when scanning.
+    pub rom_extensions: Vec<String>,
+    pub created_at: String,
+}
+
+/// Create (`id` omitted) or replace an emulator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulatorInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub exe_path: String,
+    /// Defaults to passing the ROM as the only argument.
+    pub args_template: Option<String>,
+    #[serde(default)]
+    pub rom_extensions: Vec<String>,
+}
+
+/// A program started alongside a game (VPN, controller mapper, voice chat).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameCompanion {
+    pub id: String,
+    pub game_id: String,
+    pub exe_path: String,
+    /// Command-line arguments, split like the game's own `launch_args`.
+    pub args: Option<String>,
+    /// Close the companion when the game exits, if the launch was what started it.
+    pub close_on_exit: bool,
+    pub created_at: String,
+}
+
+/// Create (`id` omitted) or replace a companion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameCompanionInput {
+    pub id: Option<String>,
+    pub game_id: String,
+    pub exe_path: String,
+    pub args: Option<String>,
+    #[serde(default)]
+    pub close_on_exit: bool,
 }
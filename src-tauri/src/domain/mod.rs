@@ -1 +1,4 @@
 pub mod games;
+pub mod notes;
+pub mod sessions;
+pub mod tags;
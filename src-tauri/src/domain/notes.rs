@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+/// One dated note on a game, in Markdown. A game keeps any number of them alongside the
+/// single `user_note`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GameNote {
+    pub id: String,
+    pub game_id: String,
+    pub body: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// An external page about a game: wiki, mod page, guide.
+#[derive(Debug, Clone, Serialize)]
+pub struct GameLink {
+    pub id: String,
+    pub game_id: String,
+    /// Shown instead of the URL; `None` shows the URL itself.
+    pub title: Option<String>,
+    pub url: String,
+    pub created_at: String,
+}
@@ -0,0 +1,135 @@
+use serde::Serialize;
+
+/// One continuous run of a game as seen by the tracker.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaySession {
+    pub id: i64,
+    pub game_id: String,
+    pub game_name: String,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_seconds: i64,
+    pub note: Option<String>,
+    /// Auto backup taken when this session ended, if any.
+    pub backup_id: Option<String>,
+    /// Name of the launch profile the game was started with; `None` for a plain launch.
+    pub launch_profile: Option<String>,
+    /// Storefront the game copy belongs to (`steam`, `gog`, `epic`, `xbox`), when detected.
+    pub store: Option<String>,
+    /// How the run ended; `None` for sessions recorded before this was tracked.
+    pub exit_reason: Option<SessionExitReason>,
+}
+
+/// How a session's last process went away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionExitReason {
+    /// The game closed on its own.
+    Exited,
+    /// The game exited within `crash_threshold_seconds` of its launch from Arrancador.
+    Crashed,
+    /// Stopped with `kill_game_processes`.
+    Killed,
+}
+
+impl SessionExitReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SessionExitReason::Exited => "exited",
+            SessionExitReason::Crashed => "crashed",
+            SessionExitReason::Killed => "killed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exited" => Some(SessionExitReason::Exited),
+            "crashed" => Some(SessionExitReason::Crashed),
+            "killed" => Some(SessionExitReason::Killed),
+            _ => None,
+        }
+    }
+}
+
+/// How a session was started; recorded alongside it by the tracker.
+#[derive(Debug, Clone, Default)]
+pub struct SessionOrigin {
+    pub launch_profile: Option<String>,
+    pub store: Option<String>,
+}
+
+/// Emitted as `session:note-prompt` when a session longer than
+/// `session_note_prompt_minutes` ends.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionNotePrompt {
+    pub session_id: i64,
+    pub game_id: String,
+    pub game_name: String,
+    pub duration_seconds: i64,
+}
+
+/// Emitted as `game:session-ended` whenever a game's last process exits, so a big picture
+/// front end can come back with the session's numbers.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionEnded {
+    pub game_id: String,
+    pub game_name: String,
+    /// `None` for untracked games and games that exited before a session was recorded.
+    pub session_id: Option<i64>,
+    pub duration_seconds: Option<i64>,
+    pub ended_at: String,
+    /// Playtime including the session that just ended.
+    pub total_playtime: i64,
+    /// The main window was hidden by `launch_and_minimize` and has been shown again.
+    pub window_restored: bool,
+}
+
+/// What became of one launch from Arrancador.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchOutcome {
+    /// Started; the tracker hasn't seen the process yet.
+    Pending,
+    Running,
+    /// Ran past the crash threshold and exited.
+    Completed,
+    /// Exited within `crash_threshold_seconds` of the launch.
+    Crashed,
+    /// Could not be started, or never showed up in the process list.
+    Failed,
+}
+
+impl LaunchOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LaunchOutcome::Pending => "pending",
+            LaunchOutcome::Running => "running",
+            LaunchOutcome::Completed => "completed",
+            LaunchOutcome::Crashed => "crashed",
+            LaunchOutcome::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "pending" => LaunchOutcome::Pending,
+            "running" => LaunchOutcome::Running,
+            "crashed" => LaunchOutcome::Crashed,
+            "failed" => LaunchOutcome::Failed,
+            _ => LaunchOutcome::Completed,
+        }
+    }
+}
+
+/// One attempt to start a game.
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchRecord {
+    pub id: i64,
+    pub game_id: String,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration_seconds: Option<i64>,
+    pub outcome: LaunchOutcome,
+    /// Why the process could not be started, for `failed` launches.
+    pub error: Option<String>,
+}
@@ -0,0 +1,30 @@
+use crate::domain::games::GameQuery;
+use serde::{Deserialize, Serialize};
+
+/// A user label ("Co-op", "Backlog", "Finished in 2024"); a game can carry any number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+    /// CSS colour for the tag chip.
+    pub color: Option<String>,
+    pub game_count: i64,
+    pub created_at: String,
+}
+
+/// A saved library filter whose games are worked out again every time it is opened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    pub query: GameQuery,
+    pub created_at: String,
+}
+
+/// Create (`id` omitted) or replace a collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub query: GameQuery,
+}
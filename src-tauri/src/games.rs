@@ -1,26 +1,77 @@
+use crate::backup::BackupProgressEvent;
 use crate::db::GlobalDb;
-pub use crate::domain::games::{Game, NewGame, UpdateGame};
+pub use crate::domain::games::{
+    CustomField, CustomFieldType, DeleteGameOptions, Emulator, EmulatorInput, FileVerification,
+    Game, GameCompanion, GameCompanionInput, GamePage, GameQuery, GameSort, GameSummary,
+    ImportStrategy, InstancePolicy, LaunchProfile, LaunchProfileInput, LaunchResult,
+    LibraryExportOptions, LibraryImportSummary, MetadataField, MetadataSource, MovedGameMatch,
+    NewGame, ReportFormat, ShortcutLocation, ShortcutTarget, SortDirection, SteamDeckStatus,
+    UpdateGame,
+};
+use crate::metadata::auto_match;
+use crate::services::file_verify;
 use crate::services::fs::StdFileSystem;
 use crate::services::games as games_service;
+use crate::services::images::{self as images_service, ImageStore};
+use crate::services::install_size;
+use crate::services::library;
+use crate::services::report;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
 
 #[tauri::command]
 pub fn get_game(id: String) -> Result<Option<Game>, String> {
     games_service::get_game(&GlobalDb, id)
 }
 
+/// With `rawg_auto_match` on, new games are also queued for RAWG matching (see
+/// `metadata::auto_match`).
 #[tauri::command]
 pub fn add_game(game: NewGame) -> Result<Game, String> {
-    games_service::add_game(&GlobalDb, game)
+    let game = games_service::add_game(&GlobalDb, game)?;
+    crate::plugins::dispatch("game_added", &game);
+    auto_match::enqueue([game.id.clone()]);
+    Ok(game)
 }
 
 #[tauri::command]
 pub fn add_games_batch(games: Vec<NewGame>) -> Result<Vec<Game>, String> {
-    games_service::add_games_batch(&GlobalDb, games)
+    let games = games_service::add_games_batch(&GlobalDb, games)?;
+    for game in &games {
+        crate::plugins::dispatch("game_added", game);
+    }
+    auto_match::enqueue(games.iter().map(|game| game.id.clone()));
+    Ok(games)
+}
+
+#[tauri::command]
+pub fn get_all_games(include_hidden: Option<bool>) -> Result<Vec<Game>, String> {
+    games_service::get_all_games(&GlobalDb, include_hidden.unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn get_game_summaries() -> Result<Vec<GameSummary>, String> {
+    games_service::get_game_summaries(&GlobalDb)
 }
 
+/// `filter` defaults to the visible library; `limit` is capped at 500.
 #[tauri::command]
-pub fn get_all_games() -> Result<Vec<Game>, String> {
-    games_service::get_all_games(&GlobalDb)
+pub fn get_games_page(
+    offset: i64,
+    limit: i64,
+    sort_by: Option<GameSort>,
+    direction: Option<SortDirection>,
+    filter: Option<GameQuery>,
+) -> Result<GamePage, String> {
+    games_service::get_games_page(
+        &GlobalDb,
+        offset,
+        limit,
+        sort_by.unwrap_or_default(),
+        direction.unwrap_or_default(),
+        filter.unwrap_or_default(),
+    )
 }
 
 #[tauri::command]
@@ -33,14 +84,161 @@ pub fn update_game(update: UpdateGame) -> Result<Game, String> {
     games_service::update_game(&GlobalDb, update)
 }
 
+#[tauri::command]
+pub fn update_games_batch(updates: Vec<UpdateGame>) -> Result<Vec<Game>, String> {
+    games_service::update_games_batch(&GlobalDb, updates)
+}
+
+#[tauri::command]
+pub fn delete_games_batch(
+    ids: Vec<String>,
+    options: Option<DeleteGameOptions>,
+) -> Result<usize, String> {
+    let covers = images_service::cover_files(&GlobalDb, &ids)?;
+    let deleted = games_service::delete_games_batch(&GlobalDb, ids, options.unwrap_or_default())?;
+    remove_orphaned_covers(covers);
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub fn relocate_game(id: String, new_exe_path: String) -> Result<Game, String> {
+    games_service::relocate_game(&GlobalDb, id, new_exe_path)
+}
+
+/// Searches `scan_dirs`, or the configured scan folders when none are given.
+#[tauri::command]
+pub async fn find_moved_games(
+    scan_dirs: Option<Vec<String>>,
+) -> Result<Vec<MovedGameMatch>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let scan_dirs = match scan_dirs {
+            Some(dirs) => dirs,
+            None => crate::settings::get_scan_directories()?,
+        };
+        games_service::find_moved_games(&GlobalDb, &scan_dirs)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn set_game_do_not_track(id: String, do_not_track: bool) -> Result<Game, String> {
+    games_service::set_game_do_not_track(&GlobalDb, id, do_not_track)
+}
+
+#[tauri::command]
+pub fn set_game_instance_policy(
+    id: String,
+    policy: Option<InstancePolicy>,
+) -> Result<Game, String> {
+    games_service::set_game_instance_policy(&GlobalDb, id, policy)
+}
+
+#[tauri::command]
+pub fn set_game_metadata_source(
+    id: String,
+    source: Option<MetadataSource>,
+) -> Result<Game, String> {
+    games_service::set_game_metadata_source(&GlobalDb, id, source)
+}
+
+#[tauri::command]
+pub fn set_game_env_vars(id: String, env_vars: BTreeMap<String, String>) -> Result<Game, String> {
+    games_service::set_game_env_vars(&GlobalDb, id, env_vars)
+}
+
+#[tauri::command]
+pub fn set_game_locked_fields(id: String, fields: Vec<MetadataField>) -> Result<Game, String> {
+    games_service::set_game_locked_fields(&GlobalDb, id, fields)
+}
+
 #[tauri::command]
 pub fn toggle_favorite(id: String) -> Result<Game, String> {
     games_service::toggle_favorite(&GlobalDb, id)
 }
 
 #[tauri::command]
-pub fn delete_game(id: String) -> Result<(), String> {
-    games_service::delete_game(&GlobalDb, id)
+pub fn set_game_hidden(id: String, hidden: bool) -> Result<Game, String> {
+    games_service::set_game_hidden(&GlobalDb, id, hidden)
+}
+
+#[tauri::command]
+pub fn set_game_sync_options(
+    id: String,
+    restore_on_launch: Option<bool>,
+    backup_on_exit: Option<bool>,
+) -> Result<Game, String> {
+    games_service::set_game_sync_options(&GlobalDb, id, restore_on_launch, backup_on_exit)
+}
+
+#[tauri::command]
+pub fn delete_game(id: String, options: Option<DeleteGameOptions>) -> Result<(), String> {
+    let covers = images_service::cover_files(&GlobalDb, std::slice::from_ref(&id))?;
+    games_service::delete_game(&GlobalDb, id, options.unwrap_or_default())?;
+    remove_orphaned_covers(covers);
+    Ok(())
+}
+
+/// The games are already gone, so a cover that can't be removed is only logged.
+fn remove_orphaned_covers(covers: Vec<std::path::PathBuf>) {
+    if let Err(e) =
+        images_service::remove_orphaned_covers(&GlobalDb, &ImageStore::default(), covers)
+    {
+        eprintln!("Failed to remove unused covers: {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn get_recently_played(limit: i64) -> Result<Vec<GameSummary>, String> {
+    games_service::get_recently_played(&GlobalDb, limit)
+}
+
+#[tauri::command]
+pub fn get_most_played(limit: i64) -> Result<Vec<GameSummary>, String> {
+    games_service::get_most_played(&GlobalDb, limit)
+}
+
+#[tauri::command]
+pub fn get_largest_games(limit: i64) -> Result<Vec<GameSummary>, String> {
+    games_service::get_largest_games(&GlobalDb, limit)
+}
+
+/// Bytes the game's install folder takes; cached for a week unless `refresh` is set.
+#[tauri::command]
+pub fn compute_install_size(game_id: String, refresh: Option<bool>) -> Result<i64, String> {
+    install_size::compute_install_size(&GlobalDb, &game_id, refresh.unwrap_or(false))
+}
+
+/// Hashes the game's install files and compares them with the recorded baseline (recording
+/// one on the first run, or when `rebaseline` is set). Reports `verify-files:progress`.
+#[tauri::command]
+pub async fn verify_game_files(
+    app: AppHandle,
+    game_id: String,
+    rebaseline: Option<bool>,
+) -> Result<FileVerification, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let progress = |done: usize, total: usize, current: &str| {
+            // Large installs have tens of thousands of files; a few hundred updates suffice.
+            let step = (total / 200).max(1);
+            if !done.is_multiple_of(step) && done != total {
+                return;
+            }
+            let _ = app.emit(
+                "verify-files:progress",
+                BackupProgressEvent {
+                    game_id: game_id.clone(),
+                    stage: if done == total { "done" } else { "hash" }.to_string(),
+                    message: current.to_string(),
+                    done,
+                    total,
+                },
+            );
+        };
+        file_verify::verify_game_files(&GlobalDb, &game_id, rebaseline.unwrap_or(false), &progress)
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
@@ -53,6 +251,61 @@ pub fn search_games(query: String) -> Result<Vec<Game>, String> {
     games_service::search_games(&GlobalDb, query)
 }
 
+#[tauri::command]
+pub fn query_games(query: GameQuery) -> Result<Vec<Game>, String> {
+    games_service::query_games(&GlobalDb, query)
+}
+
+/// Renders the library as a standalone HTML or Markdown file; returns the number of games.
+#[tauri::command]
+pub fn export_library_report(path: String, format: ReportFormat) -> Result<usize, String> {
+    report::export_library_report(&GlobalDb, Path::new(&path), format)
+}
+
+/// Saves the library as JSON for another machine or a snapshot; returns the number of games.
+#[tauri::command]
+pub fn export_library(
+    path: String,
+    options: Option<LibraryExportOptions>,
+) -> Result<usize, String> {
+    library::export_library(&GlobalDb, Path::new(&path), options.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn import_library(
+    path: String,
+    strategy: ImportStrategy,
+) -> Result<LibraryImportSummary, String> {
+    library::import_library(&GlobalDb, Path::new(&path), strategy)
+}
+
+#[tauri::command]
+pub fn get_custom_fields() -> Result<Vec<CustomField>, String> {
+    games_service::get_custom_fields(&GlobalDb)
+}
+
+#[tauri::command]
+pub fn create_custom_field(
+    name: String,
+    field_type: CustomFieldType,
+) -> Result<CustomField, String> {
+    games_service::create_custom_field(&GlobalDb, name, field_type)
+}
+
+#[tauri::command]
+pub fn delete_custom_field(id: String) -> Result<(), String> {
+    games_service::delete_custom_field(&GlobalDb, id)
+}
+
+#[tauri::command]
+pub fn set_game_custom_field(
+    game_id: String,
+    field_id: String,
+    value: Option<String>,
+) -> Result<Game, String> {
+    games_service::set_game_custom_field(&GlobalDb, game_id, field_id, value)
+}
+
 #[tauri::command]
 pub fn game_exists_by_path(exe_path: String) -> Result<bool, String> {
     games_service::game_exists_by_path(&GlobalDb, exe_path)
@@ -78,16 +331,129 @@ pub fn kill_game_processes(id: String) -> Result<u32, String> {
     games_service::kill_game_processes(&GlobalDb, id)
 }
 
+/// Hides the main window after a launch that reached the game when `minimize` (or, without
+/// it, the `launch_and_minimize` setting) asks for it; the tracker shows it again on exit.
+fn minimize_after_launch(
+    app: &AppHandle,
+    game_id: &str,
+    result: LaunchResult,
+    minimize: Option<bool>,
+) {
+    let minimize = minimize.unwrap_or_else(|| games_service::launch_and_minimize(&GlobalDb));
+    if minimize && result != LaunchResult::AlreadyRunning {
+        games_service::remember_minimized_launch(game_id);
+        crate::hide_main_window(app);
+    }
+}
+
+#[tauri::command]
+pub async fn launch_game(
+    app: AppHandle,
+    id: String,
+    profile_id: Option<String>,
+    minimize: Option<bool>,
+) -> Result<LaunchResult, String> {
+    let result = games_service::launch_game(&GlobalDb, id.clone(), profile_id).await?;
+    minimize_after_launch(&app, &id, result, minimize);
+    Ok(result)
+}
+
+/// Writes a `.lnk` for the game to the desktop or Start Menu and returns its path.
+#[tauri::command]
+pub fn create_shortcut(
+    game_id: String,
+    location: ShortcutLocation,
+    target: Option<ShortcutTarget>,
+) -> Result<String, String> {
+    games_service::create_shortcut(&GlobalDb, game_id, location, target)
+}
+
+#[tauri::command]
+pub fn get_launch_profiles(game_id: Option<String>) -> Result<Vec<LaunchProfile>, String> {
+    games_service::get_launch_profiles(&GlobalDb, game_id)
+}
+
+#[tauri::command]
+pub fn save_launch_profile(
+    app: AppHandle,
+    profile: LaunchProfileInput,
+) -> Result<LaunchProfile, String> {
+    let saved = games_service::save_launch_profile(&GlobalDb, profile)?;
+    crate::refresh_tray_menu(&app);
+    Ok(saved)
+}
+
+#[tauri::command]
+pub fn delete_launch_profile(app: AppHandle, id: String) -> Result<(), String> {
+    games_service::delete_launch_profile(&GlobalDb, id)?;
+    crate::refresh_tray_menu(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_total_playtime(game_id: String, seconds: i64) -> Result<Game, String> {
+    games_service::set_total_playtime(&GlobalDb, game_id, seconds)
+}
+
+#[tauri::command]
+pub fn add_playtime_adjustment(
+    game_id: String,
+    seconds: i64,
+    note: Option<String>,
+) -> Result<Game, String> {
+    games_service::add_playtime_adjustment(&GlobalDb, game_id, seconds, note)
+}
+
+#[tauri::command]
+pub fn get_game_companions(game_id: String) -> Result<Vec<GameCompanion>, String> {
+    games_service::get_game_companions(&GlobalDb, game_id)
+}
+
+#[tauri::command]
+pub fn save_game_companion(companion: GameCompanionInput) -> Result<GameCompanion, String> {
+    games_service::save_game_companion(&GlobalDb, companion)
+}
+
+#[tauri::command]
+pub fn delete_game_companion(id: String) -> Result<(), String> {
+    games_service::delete_game_companion(&GlobalDb, id)
+}
+
+#[tauri::command]
+pub fn get_emulators() -> Result<Vec<Emulator>, String> {
+    games_service::get_emulators(&GlobalDb)
+}
+
+#[tauri::command]
+pub fn save_emulator(emulator: EmulatorInput) -> Result<Emulator, String> {
+    games_service::save_emulator(&GlobalDb, emulator)
+}
+
+#[tauri::command]
+pub fn delete_emulator(id: String) -> Result<(), String> {
+    games_service::delete_emulator(&GlobalDb, id)
+}
+
 #[tauri::command]
-pub async fn launch_game(id: String) -> Result<(), String> {
-    games_service::launch_game(&GlobalDb, id).await
+pub async fn launch_game_with_profile(
+    app: AppHandle,
+    game_id: String,
+    profile_id: String,
+    minimize: Option<bool>,
+) -> Result<LaunchResult, String> {
+    let result =
+        games_service::launch_game_with_profile(&GlobalDb, game_id.clone(), profile_id).await?;
+    minimize_after_launch(&app, &game_id, result, minimize);
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::database::{set_test_db, TestDbGuard, TEST_DB_MUTEX};
+    use crate::domain::games::LaunchMethod;
     use rusqlite::{params, Connection};
+    use std::fs;
 
     fn setup_db() -> TestDbGuard {
         let conn = Connection::open_in_memory().expect("open db");
@@ -118,12 +484,122 @@ mod tests {
                 backup_count INTEGER DEFAULT 0,
                 save_path TEXT,
                 save_path_checked INTEGER DEFAULT 0,
+                restore_on_launch INTEGER DEFAULT 1,
+                backup_on_exit INTEGER DEFAULT 1,
+                do_not_track INTEGER DEFAULT 0,
                 user_rating INTEGER,
-                user_note TEXT
+                user_note TEXT,
+                launch_args TEXT,
+                working_dir TEXT,
+                env_vars TEXT,
+                launch_method TEXT DEFAULT 'direct',
+                store_id TEXT,
+                emulator_id TEXT,
+                is_hidden INTEGER DEFAULT 0,
+                exe_size INTEGER,
+                cover_thumbnail TEXT,
+                install_size INTEGER,
+                install_size_checked_at TEXT,
+                instance_policy TEXT,
+                igdb_id INTEGER,
+                metadata_source TEXT
             )",
             [],
         )
         .expect("create games table");
+        conn.execute(
+            "CREATE TABLE custom_fields (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                field_type TEXT NOT NULL DEFAULT 'text',
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("create custom_fields table");
+        conn.execute(
+            "CREATE TABLE game_custom_values (
+                game_id TEXT NOT NULL,
+                field_id TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (game_id, field_id)
+            )",
+            [],
+        )
+        .expect("create game_custom_values table");
+        conn.execute_batch(
+            "CREATE TABLE tags (
+                 id TEXT PRIMARY KEY,
+                 name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                 color TEXT,
+                 created_at TEXT NOT NULL
+             );
+             CREATE TABLE game_tags (
+                 game_id TEXT NOT NULL,
+                 tag_id TEXT NOT NULL,
+                 PRIMARY KEY (game_id, tag_id)
+             );",
+        )
+        .expect("create tag tables");
+        conn.execute(
+            "CREATE TABLE play_sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                game_id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                ended_at TEXT NOT NULL,
+                duration_seconds INTEGER NOT NULL DEFAULT 0,
+                note TEXT,
+                backup_id TEXT,
+                launch_profile TEXT,
+                store TEXT,
+                exit_reason TEXT
+            )",
+            [],
+        )
+        .expect("create play_sessions table");
+        conn.execute(
+            "CREATE TABLE launch_profiles (
+                id TEXT PRIMARY KEY,
+                game_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                target TEXT,
+                args TEXT NOT NULL DEFAULT '[]',
+                env TEXT NOT NULL DEFAULT '{}',
+                working_dir TEXT,
+                pre_launch TEXT,
+                post_exit TEXT,
+                is_default INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("create launch_profiles table");
+        conn.execute_batch(
+            "CREATE TABLE playtime_daily (game_id TEXT NOT NULL, date TEXT NOT NULL,
+                 seconds INTEGER NOT NULL DEFAULT 0, UNIQUE(game_id, date));
+             CREATE TABLE playtime_monthly (game_id TEXT NOT NULL, month TEXT NOT NULL,
+                 seconds INTEGER NOT NULL DEFAULT 0);
+             CREATE TABLE backups (id TEXT PRIMARY KEY, game_id TEXT NOT NULL,
+                 backup_path TEXT NOT NULL, created_at TEXT NOT NULL);
+             CREATE TABLE backup_events (id TEXT PRIMARY KEY, game_id TEXT NOT NULL);
+             CREATE TABLE game_save_paths (id TEXT PRIMARY KEY, game_id TEXT NOT NULL);
+             CREATE TABLE game_manifest_overrides (game_id TEXT PRIMARY KEY);
+             CREATE TABLE game_wiki_save_paths (game_id TEXT NOT NULL, path TEXT NOT NULL);
+             CREATE TABLE install_archives (id TEXT PRIMARY KEY, game_id TEXT NOT NULL,
+                                            archive_path TEXT NOT NULL DEFAULT '');
+             CREATE TABLE game_file_hashes (game_id TEXT NOT NULL, rel_path TEXT NOT NULL);
+             CREATE TABLE game_notes (id TEXT PRIMARY KEY, game_id TEXT NOT NULL);
+             CREATE TABLE game_links (id TEXT PRIMARY KEY, game_id TEXT NOT NULL);
+             CREATE TABLE playtime_adjustments (id TEXT PRIMARY KEY, game_id TEXT NOT NULL,
+                 seconds INTEGER NOT NULL, note TEXT, created_at TEXT NOT NULL);
+             CREATE TABLE launches (id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 game_id TEXT NOT NULL, started_at TEXT NOT NULL, ended_at TEXT,
+                 duration_seconds INTEGER, outcome TEXT NOT NULL, error TEXT);
+             CREATE TABLE game_companions (id TEXT PRIMARY KEY, game_id TEXT NOT NULL,
+                 exe_path TEXT NOT NULL, args TEXT,
+                 close_on_exit INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL);",
+        )
+        .expect("create per-game tables");
         conn.execute(
             "CREATE TABLE settings (
                 key TEXT PRIMARY KEY,
@@ -194,10 +670,19 @@ mod tests {
             publishers: None,
             user_rating: Some(5),
             user_note: Some("solid".to_string()),
+            launch_args: Some(" -windowed ".to_string()),
+            working_dir: Some(String::new()),
+            launch_method: Some(LaunchMethod::Steam),
+            store_id: Some(" 489830 ".to_string()),
+            emulator_id: None,
         })
         .expect("update game");
 
         assert_eq!(updated.name, "Updated");
+        assert_eq!(updated.launch_args.as_deref(), Some("-windowed"));
+        assert_eq!(updated.working_dir, None);
+        assert_eq!(updated.launch_method, LaunchMethod::Steam);
+        assert_eq!(updated.store_id.as_deref(), Some("489830"));
         assert!(updated.is_favorite);
         assert_eq!(updated.save_path.as_deref(), Some("C:\\Saves\\updated"));
         assert_eq!(updated.user_rating, Some(5));
@@ -214,6 +699,85 @@ mod tests {
         assert_eq!(checked, 1);
     }
 
+    #[test]
+    fn sync_options_default_on_and_toggle_independently() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let added = add_game(NewGame {
+            name: "Synced".to_string(),
+            exe_path: "C:\\Games\\synced.exe".to_string(),
+            exe_name: "synced.exe".to_string(),
+        })
+        .expect("add game");
+        assert!(added.restore_on_launch);
+        assert!(added.backup_on_exit);
+
+        let updated =
+            set_game_sync_options(added.id.clone(), Some(false), None).expect("update flags");
+        assert!(!updated.restore_on_launch);
+        assert!(updated.backup_on_exit);
+
+        let updated = set_game_sync_options(added.id, None, Some(false)).expect("update flags");
+        assert!(!updated.restore_on_launch);
+        assert!(!updated.backup_on_exit);
+    }
+
+    #[test]
+    fn do_not_track_games_launch_without_recording_play() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let added = add_game(NewGame {
+            name: "Private".to_string(),
+            exe_path: "C:\\Games\\private.exe".to_string(),
+            exe_name: "private.exe".to_string(),
+        })
+        .expect("add game");
+        assert!(!added.do_not_track);
+
+        let private = set_game_do_not_track(added.id.clone(), true).expect("set flag");
+        assert!(private.do_not_track);
+        let launched = record_game_launch(added.id.clone()).expect("record launch");
+        assert_eq!(launched.play_count, 0);
+        assert_eq!(launched.last_played, None);
+
+        set_game_do_not_track(added.id.clone(), false).expect("clear flag");
+        let launched = record_game_launch(added.id).expect("record launch");
+        assert_eq!(launched.play_count, 1);
+        assert!(launched.last_played.is_some());
+    }
+
+    #[test]
+    fn env_vars_are_trimmed_validated_and_cleared() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let added = add_game(NewGame {
+            name: "Modded".to_string(),
+            exe_path: "C:\\Games\\modded.exe".to_string(),
+            exe_name: "modded.exe".to_string(),
+        })
+        .expect("add game");
+        assert!(added.env_vars.is_empty());
+
+        let vars = BTreeMap::from([
+            (" DXVK_HUD ".to_string(), "fps".to_string()),
+            ("  ".to_string(), "ignored".to_string()),
+        ]);
+        let updated = set_game_env_vars(added.id.clone(), vars).expect("set env vars");
+        assert_eq!(
+            updated.env_vars,
+            BTreeMap::from([("DXVK_HUD".to_string(), "fps".to_string())])
+        );
+
+        let invalid = BTreeMap::from([("A=B".to_string(), "1".to_string())]);
+        assert!(set_game_env_vars(added.id.clone(), invalid).is_err());
+
+        let cleared = set_game_env_vars(added.id, BTreeMap::new()).expect("clear env vars");
+        assert!(cleared.env_vars.is_empty());
+    }
+
     #[test]
     fn get_all_games_sorted_by_name() {
         let _lock = TEST_DB_MUTEX.lock().unwrap();
@@ -232,11 +796,148 @@ mod tests {
         })
         .expect("add alpha");
 
-        let games = get_all_games().expect("get all games");
+        let games = get_all_games(None).expect("get all games");
         let names: Vec<String> = games.into_iter().map(|g| g.name).collect();
         assert_eq!(names, vec!["Alpha", "Zeta"]);
     }
 
+    #[test]
+    fn batch_updates_are_all_or_nothing_and_batch_delete_counts_games() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let first = add_game(NewGame {
+            name: "First".to_string(),
+            exe_path: "C:\\Games\\first.exe".to_string(),
+            exe_name: "first.exe".to_string(),
+        })
+        .expect("add first");
+        let second = add_game(NewGame {
+            name: "Second".to_string(),
+            exe_path: "C:\\Games\\second.exe".to_string(),
+            exe_name: "second.exe".to_string(),
+        })
+        .expect("add second");
+
+        let backup_on = |id: &str| UpdateGame {
+            id: id.to_string(),
+            backup_enabled: Some(true),
+            ..Default::default()
+        };
+        let updated = update_games_batch(vec![backup_on(&first.id), backup_on(&second.id)])
+            .expect("update batch");
+        assert_eq!(updated.len(), 2);
+        assert!(updated.iter().all(|game| game.backup_enabled));
+
+        let renamed = UpdateGame {
+            id: first.id.clone(),
+            name: Some("Renamed".to_string()),
+            ..Default::default()
+        };
+        assert!(update_games_batch(vec![renamed, backup_on("missing")]).is_err());
+        assert_eq!(
+            get_game(first.id.clone())
+                .expect("get first")
+                .map(|g| g.name),
+            Some("First".to_string())
+        );
+
+        let deleted = delete_games_batch(vec![first.id, second.id, "missing".to_string()], None)
+            .expect("delete batch");
+        assert_eq!(deleted, 2);
+        assert!(get_all_games(Some(true)).expect("get games").is_empty());
+    }
+
+    #[test]
+    fn moved_games_are_found_by_exe_name_and_relocated() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let library = tempfile::tempdir().expect("tempdir");
+        let old_dir = library.path().join("old").join("Hollow");
+        fs::create_dir_all(old_dir.join("saves")).expect("create old dir");
+        fs::write(old_dir.join("hollow.exe"), b"game binary").expect("write exe");
+        let old_exe = old_dir.join("hollow.exe").to_string_lossy().to_string();
+
+        let added = add_game(NewGame {
+            name: "Hollow".to_string(),
+            exe_path: old_exe.clone(),
+            exe_name: "hollow.exe".to_string(),
+        })
+        .expect("add game");
+        crate::database::with_db(|conn| {
+            conn.execute(
+                "UPDATE games SET save_path = ?1 WHERE id = ?2",
+                params![old_dir.join("saves").to_string_lossy(), added.id],
+            )
+        })
+        .expect("set save path");
+
+        let scan_root = library.path().to_string_lossy().to_string();
+        let find = |root: &str| games_service::find_moved_games(&GlobalDb, &[root.to_string()]);
+        assert!(find(&scan_root).expect("nothing moved yet").is_empty());
+
+        let new_dir = library.path().join("new").join("Hollow");
+        fs::create_dir_all(new_dir.join("bin")).expect("create new dir");
+        fs::write(new_dir.join("hollow.exe"), b"game binary").expect("write moved exe");
+        fs::write(new_dir.join("bin").join("HOLLOW.EXE"), b"other").expect("write decoy");
+        fs::remove_dir_all(library.path().join("old")).expect("remove old dir");
+
+        let matches = find(&scan_root).expect("find moved games");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].game_id, added.id);
+        assert_eq!(matches[0].size_matches, Some(true));
+        assert_eq!(matches[1].size_matches, Some(false));
+        let new_exe = new_dir.join("hollow.exe").to_string_lossy().to_string();
+        assert_eq!(matches[0].new_exe_path, new_exe);
+
+        assert!(relocate_game(added.id.clone(), "/nowhere/hollow.exe".to_string()).is_err());
+        let relocated = relocate_game(added.id, new_exe.clone()).expect("relocate game");
+        assert_eq!(relocated.exe_path, new_exe);
+        let sep = std::path::MAIN_SEPARATOR;
+        assert_eq!(
+            relocated.save_path,
+            Some(format!("{{PATHTOGAME}}{sep}saves"))
+        );
+    }
+
+    #[test]
+    fn hidden_games_are_left_out_of_the_library_list() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let kept = add_game(NewGame {
+            name: "Kept".to_string(),
+            exe_path: "C:\\Games\\kept.exe".to_string(),
+            exe_name: "kept.exe".to_string(),
+        })
+        .expect("add kept");
+        let hidden = add_game(NewGame {
+            name: "Hidden".to_string(),
+            exe_path: "C:\\Games\\hidden.exe".to_string(),
+            exe_name: "hidden.exe".to_string(),
+        })
+        .expect("add hidden");
+        toggle_favorite(hidden.id.clone()).expect("favorite hidden");
+
+        let updated = set_game_hidden(hidden.id.clone(), true).expect("hide game");
+        assert!(updated.is_hidden);
+        assert!(updated.is_favorite);
+
+        let visible: Vec<String> = get_all_games(None)
+            .expect("get visible games")
+            .into_iter()
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(visible, vec![kept.id.clone()]);
+        assert!(get_favorites().expect("get favorites").is_empty());
+        assert_eq!(get_all_games(Some(true)).expect("get all games").len(), 2);
+
+        let shown = set_game_hidden(hidden.id, false).expect("show game");
+        assert!(!shown.is_hidden);
+        assert_eq!(get_all_games(None).expect("get games").len(), 2);
+    }
+
     #[test]
     fn delete_game_removes_row() {
         let _lock = TEST_DB_MUTEX.lock().unwrap();
@@ -249,9 +950,465 @@ mod tests {
         })
         .expect("add game");
 
-        delete_game(added.id.clone()).expect("delete game");
+        delete_game(added.id.clone(), None).expect("delete game");
 
         let fetched = get_game(added.id).expect("get game");
         assert!(fetched.is_none());
     }
+
+    #[test]
+    fn companions_are_saved_per_game_and_removed_with_it() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let game = add_game(NewGame {
+            name: "Raid".to_string(),
+            exe_path: "C:\\Games\\raid.exe".to_string(),
+            exe_name: "raid.exe".to_string(),
+        })
+        .expect("add game");
+        let input = GameCompanionInput {
+            id: None,
+            game_id: game.id.clone(),
+            exe_path: " C:\\Tools\\ds4windows.exe ".to_string(),
+            args: Some("  ".to_string()),
+            close_on_exit: true,
+        };
+        assert!(save_game_companion(GameCompanionInput {
+            exe_path: "  ".to_string(),
+            ..input.clone()
+        })
+        .is_err());
+
+        let saved = save_game_companion(input.clone()).expect("save companion");
+        assert_eq!(saved.exe_path, "C:\\Tools\\ds4windows.exe");
+        assert_eq!(saved.args, None);
+        assert!(saved.close_on_exit);
+
+        let updated = save_game_companion(GameCompanionInput {
+            id: Some(saved.id.clone()),
+            args: Some("--minimized".to_string()),
+            close_on_exit: false,
+            ..input
+        })
+        .expect("update companion");
+        assert_eq!(updated.id, saved.id);
+        assert_eq!(updated.args.as_deref(), Some("--minimized"));
+        assert!(!updated.close_on_exit);
+        assert_eq!(
+            get_game_companions(game.id.clone())
+                .expect("list companions")
+                .len(),
+            1
+        );
+
+        delete_game(game.id.clone(), None).expect("delete game");
+        assert!(get_game_companions(game.id)
+            .expect("list companions")
+            .is_empty());
+    }
+
+    #[test]
+    fn delete_game_keeps_backup_files_and_stats_unless_asked() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let backups = tempfile::tempdir().expect("tempdir");
+        let mut games = Vec::new();
+        for name in ["Kept", "Purged"] {
+            let game = add_game(NewGame {
+                name: name.to_string(),
+                exe_path: format!("C:\\Games\\{name}.exe"),
+                exe_name: format!("{name}.exe"),
+            })
+            .expect("add game");
+            let folder = backups.path().join(name);
+            fs::create_dir_all(&folder).expect("create backup folder");
+            let archive = folder.join("backup.zip");
+            fs::write(&archive, b"zip").expect("write backup");
+            crate::database::with_db(|conn| {
+                conn.execute(
+                    "INSERT INTO backups (id, game_id, backup_path, created_at)
+                     VALUES (?1, ?2, ?3, '2026-01-01T00:00:00Z')",
+                    params![format!("backup-{name}"), game.id, archive.to_string_lossy()],
+                )?;
+                conn.execute(
+                    "INSERT INTO playtime_daily (game_id, date, seconds) VALUES (?1, '2026-01-01', 60)",
+                    params![game.id],
+                )
+            })
+            .expect("seed backup and playtime");
+            games.push((game.id, archive));
+        }
+        let count = |table: &str, game_id: &str| -> i64 {
+            crate::database::with_db(|conn| {
+                conn.query_row(
+                    &format!("SELECT COUNT(*) FROM {table} WHERE game_id = ?1"),
+                    params![game_id],
+                    |row| row.get(0),
+                )
+            })
+            .expect("count rows")
+        };
+
+        let (kept_id, kept_archive) = &games[0];
+        delete_game(kept_id.clone(), None).expect("delete kept");
+        assert!(kept_archive.exists());
+        assert_eq!(count("backups", kept_id), 0);
+        assert_eq!(count("playtime_daily", kept_id), 1);
+
+        let (purged_id, purged_archive) = &games[1];
+        let install_archive = backups.path().join("installs").join("Purged.zip");
+        fs::create_dir_all(install_archive.parent().expect("installs folder"))
+            .expect("create installs folder");
+        fs::write(&install_archive, b"zip").expect("write install archive");
+        crate::database::with_db(|conn| {
+            conn.execute(
+                "INSERT INTO install_archives (id, game_id, archive_path) VALUES ('a1', ?1, ?2)",
+                params![purged_id, install_archive.to_string_lossy()],
+            )
+        })
+        .expect("seed install archive");
+        // Without its backups the game would leave the archived install unreachable.
+        assert!(delete_game(purged_id.clone(), None).is_err());
+        assert_eq!(count("install_archives", purged_id), 1);
+
+        let purge = DeleteGameOptions {
+            delete_backups: true,
+            delete_stats: true,
+        };
+        delete_game(purged_id.clone(), Some(purge)).expect("delete purged");
+        assert!(!purged_archive.exists());
+        assert!(!purged_archive.parent().expect("backup folder").exists());
+        assert!(!install_archive.exists());
+        assert_eq!(count("backups", purged_id), 0);
+        assert_eq!(count("playtime_daily", purged_id), 0);
+    }
+
+    #[test]
+    fn custom_fields_are_validated_exposed_and_filterable() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let steam = add_game(NewGame {
+            name: "Steam Game".to_string(),
+            exe_path: "C:\\Games\\steam.exe".to_string(),
+            exe_name: "steam.exe".to_string(),
+        })
+        .expect("add steam game");
+        let gog = add_game(NewGame {
+            name: "Gog Game".to_string(),
+            exe_path: "C:\\Games\\gog.exe".to_string(),
+            exe_name: "gog.exe".to_string(),
+        })
+        .expect("add gog game");
+
+        let store = create_custom_field("Store".to_string(), CustomFieldType::Text)
+            .expect("create store field");
+        let price = create_custom_field("Price paid".to_string(), CustomFieldType::Number)
+            .expect("create price field");
+        assert!(create_custom_field("Store".to_string(), CustomFieldType::Text).is_err());
+
+        set_game_custom_field(steam.id.clone(), store.id.clone(), Some("Steam".into()))
+            .expect("set steam store");
+        set_game_custom_field(gog.id.clone(), store.id.clone(), Some("GOG".into()))
+            .expect("set gog store");
+        let updated =
+            set_game_custom_field(steam.id.clone(), price.id.clone(), Some("9,99".into()))
+                .expect("set price");
+        assert_eq!(
+            updated.custom_fields.get(&price.id).map(String::as_str),
+            Some("9.99")
+        );
+        assert!(
+            set_game_custom_field(gog.id.clone(), price.id.clone(), Some("cheap".into())).is_err()
+        );
+
+        let filtered = query_games(GameQuery {
+            custom_fields: vec![crate::domain::games::CustomFieldFilter {
+                field_id: store.id.clone(),
+                value: Some("steam".to_string()),
+            }],
+            ..GameQuery::default()
+        })
+        .expect("query by store");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, steam.id);
+        assert_eq!(filtered[0].custom_fields.len(), 2);
+
+        delete_custom_field(store.id.clone()).expect("delete store field");
+        let gog = get_game(gog.id).expect("get gog").expect("gog exists");
+        assert!(gog.custom_fields.is_empty());
+    }
+
+    #[test]
+    fn games_page_sorts_filters_and_counts_across_pages() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let mut ids = Vec::new();
+        for (name, playtime) in [
+            ("Bravo", 300),
+            ("alpha", 100),
+            ("Charlie", 200),
+            ("Delta", 0),
+        ] {
+            let game = add_game(NewGame {
+                name: name.to_string(),
+                exe_path: format!("C:\\Games\\{name}.exe"),
+                exe_name: format!("{name}.exe"),
+            })
+            .expect("add game");
+            crate::database::with_db(|conn| {
+                conn.execute(
+                    "UPDATE games SET total_playtime = ?1 WHERE id = ?2",
+                    params![playtime, game.id],
+                )
+            })
+            .expect("set playtime");
+            ids.push(game.id);
+        }
+        set_game_hidden(ids[3].clone(), true).expect("hide game");
+
+        let names = |page: &GamePage| -> Vec<String> {
+            page.games.iter().map(|game| game.name.clone()).collect()
+        };
+        let first = get_games_page(0, 2, None, None, None).expect("first page");
+        assert_eq!(first.total, 3);
+        assert_eq!(names(&first), ["alpha", "Bravo"]);
+        let second = get_games_page(2, 2, None, None, None).expect("second page");
+        assert_eq!(names(&second), ["Charlie"]);
+
+        let summaries = get_game_summaries().expect("summaries");
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].name, "alpha");
+        assert_eq!(summaries[1].total_playtime, 300);
+
+        let by_playtime = get_games_page(
+            0,
+            10,
+            Some(GameSort::Playtime),
+            Some(SortDirection::Desc),
+            None,
+        )
+        .expect("sort by playtime");
+        assert_eq!(names(&by_playtime), ["Bravo", "Charlie", "alpha"]);
+
+        let filtered = get_games_page(
+            0,
+            10,
+            None,
+            None,
+            Some(GameQuery {
+                min_playtime: Some(150),
+                include_hidden: true,
+                ..GameQuery::default()
+            }),
+        )
+        .expect("filtered page");
+        assert_eq!(filtered.total, 2);
+        assert_eq!(names(&filtered), ["Bravo", "Charlie"]);
+    }
+
+    #[test]
+    fn quick_lists_order_by_last_launch_and_playtime() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let mut ids = Vec::new();
+        for (name, playtime, last_played) in [
+            ("Old", 500, Some("2024-01-01T10:00:00+00:00")),
+            ("Fresh", 100, Some("2024-03-01T10:00:00+00:00")),
+            ("Hidden", 900, Some("2024-04-01T10:00:00+00:00")),
+            ("Unplayed", 0, None),
+        ] {
+            let game = add_game(NewGame {
+                name: name.to_string(),
+                exe_path: format!("C:\\Games\\{name}.exe"),
+                exe_name: format!("{name}.exe"),
+            })
+            .expect("add game");
+            crate::database::with_db(|conn| {
+                conn.execute(
+                    "UPDATE games SET total_playtime = ?1, last_played = ?2 WHERE id = ?3",
+                    params![playtime, last_played, game.id],
+                )
+            })
+            .expect("set playtime");
+            ids.push(game.id);
+        }
+        set_game_hidden(ids[2].clone(), true).expect("hide game");
+
+        let names = |games: Vec<GameSummary>| -> Vec<String> {
+            games.into_iter().map(|game| game.name).collect()
+        };
+        let recent = get_recently_played(10).expect("recently played");
+        assert_eq!(names(recent), ["Fresh", "Old"]);
+        let most = get_most_played(1).expect("most played");
+        assert_eq!(names(most), ["Old"]);
+        // A zero limit still returns one game rather than an empty list.
+        assert_eq!(get_most_played(0).expect("clamped").len(), 1);
+    }
+
+    #[test]
+    fn game_flags_a_failed_latest_launch() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let game = add_game(NewGame {
+            name: "Crashy".to_string(),
+            exe_path: "C:\\Games\\Crashy.exe".to_string(),
+            exe_name: "Crashy.exe".to_string(),
+        })
+        .expect("add game");
+        assert!(!game.last_launch_failed);
+
+        let add_launch = |started_at: &str, outcome: &str| {
+            crate::database::with_db(|conn| {
+                conn.execute(
+                    "INSERT INTO launches (game_id, started_at, outcome) VALUES (?1, ?2, ?3)",
+                    params![game.id, started_at, outcome],
+                )
+            })
+            .expect("insert launch");
+        };
+        add_launch("2024-01-01T10:00:00+00:00", "completed");
+        add_launch("2024-01-02T10:00:00+00:00", "crashed");
+        let reloaded = get_game(game.id.clone()).expect("get").expect("exists");
+        assert!(reloaded.last_launch_failed);
+
+        add_launch("2024-01-03T10:00:00+00:00", "running");
+        let reloaded = get_game(game.id.clone()).expect("get").expect("exists");
+        assert!(!reloaded.last_launch_failed);
+    }
+
+    #[test]
+    fn playtime_can_be_imported_and_adjusted() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let game = add_game(NewGame {
+            name: "Imported".to_string(),
+            exe_path: "C:\\Games\\imported.exe".to_string(),
+            exe_name: "imported.exe".to_string(),
+        })
+        .expect("add game");
+        let daily_total = |game_id: &str| -> i64 {
+            crate::database::with_db(|conn| {
+                conn.query_row(
+                    "SELECT COALESCE(SUM(seconds), 0) FROM playtime_daily WHERE game_id = ?1",
+                    params![game_id],
+                    |row| row.get(0),
+                )
+            })
+            .expect("sum daily playtime")
+        };
+
+        assert!(set_total_playtime(game.id.clone(), -1).is_err());
+        let updated = set_total_playtime(game.id.clone(), 36_000).expect("set playtime");
+        assert_eq!(updated.total_playtime, 36_000);
+        assert_eq!(daily_total(&game.id), 36_000);
+
+        let updated =
+            add_playtime_adjustment(game.id.clone(), -600, Some(" Steam overlap ".to_string()))
+                .expect("subtract playtime");
+        assert_eq!(updated.total_playtime, 35_400);
+        assert_eq!(daily_total(&game.id), 35_400);
+
+        let updated =
+            add_playtime_adjustment(game.id.clone(), -100_000, None).expect("clamp at zero");
+        assert_eq!(updated.total_playtime, 0);
+        assert_eq!(daily_total(&game.id), 0);
+
+        let notes: Vec<Option<String>> = crate::database::with_db(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT note FROM playtime_adjustments WHERE game_id = ?1 ORDER BY seconds DESC",
+            )?;
+            let rows = stmt.query_map(params![game.id], |row| row.get(0))?;
+            rows.collect()
+        })
+        .expect("list adjustments");
+        assert_eq!(notes, [None, Some("Steam overlap".to_string()), None]);
+    }
+
+    #[test]
+    fn launch_profiles_round_trip_and_follow_game_deletion() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        let game = add_game(NewGame {
+            name: "Modded Game".to_string(),
+            exe_path: "C:\\Games\\modded.exe".to_string(),
+            exe_name: "modded.exe".to_string(),
+        })
+        .expect("add game");
+        let input = LaunchProfileInput {
+            id: None,
+            game_id: game.id.clone(),
+            name: " Modded via MO2 ".to_string(),
+            target: Some("C:\\MO2\\ModOrganizer.exe".to_string()),
+            args: vec!["moshortcut://:Game".to_string()],
+            env: [("DXVK_HUD".to_string(), "fps".to_string())].into(),
+            working_dir: Some("  ".to_string()),
+            pre_launch: None,
+            post_exit: Some("echo done".to_string()),
+            is_default: true,
+        };
+        let saved =
+            games_service::save_launch_profile(&GlobalDb, input.clone()).expect("save profile");
+        assert_eq!(saved.name, "Modded via MO2");
+        assert_eq!(saved.args, input.args);
+        assert_eq!(saved.env, input.env);
+        assert!(saved.working_dir.is_none());
+        assert!(saved.is_default);
+
+        let renamed = games_service::save_launch_profile(
+            &GlobalDb,
+            LaunchProfileInput {
+                id: Some(saved.id.clone()),
+                name: "Safe mode".to_string(),
+                args: Vec::new(),
+                ..input.clone()
+            },
+        )
+        .expect("update profile");
+        assert_eq!(renamed.id, saved.id);
+        assert!(renamed.args.is_empty());
+        assert!(games_service::save_launch_profile(
+            &GlobalDb,
+            LaunchProfileInput {
+                name: " ".to_string(),
+                ..input.clone()
+            }
+        )
+        .is_err());
+
+        let profiles = games_service::get_launch_profiles(&GlobalDb, Some(game.id.clone()))
+            .expect("list profiles");
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "Safe mode");
+
+        let editor = games_service::save_launch_profile(
+            &GlobalDb,
+            LaunchProfileInput {
+                id: None,
+                name: "Launch editor".to_string(),
+                ..input
+            },
+        )
+        .expect("save second default");
+        let profiles = games_service::get_launch_profiles(&GlobalDb, Some(game.id.clone()))
+            .expect("list profiles");
+        let defaults: Vec<&str> = profiles
+            .iter()
+            .filter(|p| p.is_default)
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(defaults, vec![editor.id.as_str()]);
+
+        delete_game(game.id, None).expect("delete game");
+        assert!(games_service::get_launch_profiles(&GlobalDb, None)
+            .expect("list all profiles")
+            .is_empty());
+    }
 }
@@ -0,0 +1,41 @@
+use crate::db::GlobalDb;
+use crate::domain::games::Game;
+use crate::services::images::{self as images_service, ImageCacheStats, ImageStore};
+
+#[tauri::command]
+pub fn import_cover_image(game_id: String, source_path: String) -> Result<Game, String> {
+    images_service::import_cover_image(&GlobalDb, &ImageStore::default(), game_id, source_path)
+}
+
+/// Downloads `url` into the image store, or reuses the copy from an earlier download.
+#[tauri::command]
+pub async fn cache_cover_image(game_id: String, url: String) -> Result<Game, String> {
+    let store = ImageStore::default();
+    if let Some(image) = images_service::cached_image(&GlobalDb, &url)? {
+        return images_service::use_stored_cover(&GlobalDb, &store, game_id, &image);
+    }
+    let bytes = images_service::download_image(&url).await?;
+    images_service::store_cover(&GlobalDb, &store, game_id, &bytes, Some(&url))
+}
+
+#[tauri::command]
+pub fn generate_cover_thumbnail(game_id: String) -> Result<Game, String> {
+    images_service::generate_cover_thumbnail(&GlobalDb, &ImageStore::default(), game_id)
+}
+
+#[tauri::command]
+pub fn clean_unused_images() -> Result<usize, String> {
+    images_service::clean_unused_images(&GlobalDb, &ImageStore::default())
+}
+
+#[tauri::command]
+pub fn get_image_cache_stats() -> Result<ImageCacheStats, String> {
+    images_service::image_cache_stats(&GlobalDb)
+}
+
+/// Pauses or resumes the background job that keeps the cache under `image_cache_max_mb`.
+#[tauri::command]
+pub fn set_image_cache_pruning_paused(paused: bool) -> Result<ImageCacheStats, String> {
+    images_service::set_pruning_paused(paused);
+    images_service::image_cache_stats(&GlobalDb)
+}
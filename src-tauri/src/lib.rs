@@ -1,28 +1,59 @@
+mod app_info;
 mod backup;
 mod database;
 mod db;
 mod domain;
 mod games;
+mod images;
 mod metadata;
+mod notes;
+mod plugins;
 mod scan;
 mod services;
+mod sessions;
 mod settings;
 mod stats;
 mod system;
+mod tags;
+mod test_mode;
 mod tracker;
 
+use app_info::{get_app_info, get_database_status, retry_database_open};
 use backup::*;
-use database::init_database;
+use db::GlobalDb;
 use games::*;
+use images::{
+    cache_cover_image, clean_unused_images, generate_cover_thumbnail, get_image_cache_stats,
+    import_cover_image, set_image_cache_pruning_paused,
+};
+use metadata::bundle::import_metadata_bundle;
+use metadata::igdb::{
+    apply_igdb_metadata, get_igdb_credentials, get_igdb_game_details, search_igdb,
+    set_igdb_credentials,
+};
+use metadata::name_cleanup::suggest_metadata_matches;
+use metadata::pcgamingwiki::{
+    get_pcgamingwiki_locations, get_pcgamingwiki_save_paths, set_pcgamingwiki_save_paths,
+};
+use metadata::protondb::refresh_game_compatibility;
 use metadata::*;
-use scan::{cancel_scan, get_running_processes, scan_executables_stream};
+use notes::*;
+use plugins::{get_plugins_directory, list_plugins, set_plugin_enabled};
+use scan::{
+    cancel_scan, get_running_processes, scan_all_directories, scan_directory_incremental,
+    scan_executables_stream,
+};
+use services::games as games_service;
+use sessions::*;
 use settings::*;
 use stats::*;
 use std::sync::atomic::{AtomicBool, Ordering};
 use system::*;
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tags::*;
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
 use tauri::{AppHandle, Manager, Runtime, WindowEvent};
+use test_mode::{__test_advance_clock, __test_reset_db, __test_seed_library};
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -41,13 +72,19 @@ impl AppState {
     }
 }
 
-fn show_main_window<R: Runtime>(app: &AppHandle<R>) {
+pub(crate) fn show_main_window<R: Runtime>(app: &AppHandle<R>) {
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.show();
         let _ = window.set_focus();
     }
 }
 
+pub(crate) fn hide_main_window<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+}
+
 fn toggle_main_window<R: Runtime>(app: &AppHandle<R>) {
     if let Some(window) = app.get_webview_window("main") {
         match window.is_visible() {
@@ -68,13 +105,145 @@ fn request_exit<R: Runtime>(app: &AppHandle<R>) {
     app.exit(0);
 }
 
-fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+const TRAY_ID: &str = "main";
+const LAUNCH_PROFILE_MENU_PREFIX: &str = "launch_profile:";
+const LAUNCH_GAME_MENU_PREFIX: &str = "launch_game:";
+const TRAY_RECENT_GAMES: i64 = 5;
+
+fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
     let show_item = MenuItem::with_id(app, "tray_show", "Показать", true, None::<&str>)?;
     let quit_item = MenuItem::with_id(app, "tray_quit", "Выход", true, None::<&str>)?;
     let separator = PredefinedMenuItem::separator(app)?;
-    let menu = Menu::with_items(app, &[&show_item, &separator, &quit_item])?;
 
-    let mut tray_builder = TrayIconBuilder::new()
+    let recent = games_service::get_recently_played(&GlobalDb, TRAY_RECENT_GAMES)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|game| {
+            MenuItem::with_id(
+                app,
+                format!("{LAUNCH_GAME_MENU_PREFIX}{}", game.id),
+                game.name,
+                true,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let recent_refs: Vec<&dyn IsMenuItem<R>> = recent
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<R>)
+        .collect();
+    let recent_menu = Submenu::with_items(app, "Недавние игры", true, &recent_refs)?;
+
+    let profiles = games_service::get_launch_profiles(&GlobalDb, None).unwrap_or_default();
+    if profiles.is_empty() {
+        let items: Vec<&dyn IsMenuItem<R>> = if recent.is_empty() {
+            vec![&show_item, &separator, &quit_item]
+        } else {
+            vec![&show_item, &recent_menu, &separator, &quit_item]
+        };
+        return Menu::with_items(app, &items);
+    }
+
+    let game_names: std::collections::HashMap<String, String> =
+        games_service::get_all_games(&GlobalDb, false)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|game| (game.id, game.name))
+            .collect();
+    let mut profile_items = Vec::with_capacity(profiles.len());
+    for profile in &profiles {
+        let Some(game_name) = game_names.get(&profile.game_id) else {
+            continue;
+        };
+        profile_items.push(MenuItem::with_id(
+            app,
+            format!(
+                "{LAUNCH_PROFILE_MENU_PREFIX}{}:{}",
+                profile.game_id, profile.id
+            ),
+            format!("{} — {}", game_name, profile.name),
+            true,
+            None::<&str>,
+        )?);
+    }
+    let profile_refs: Vec<&dyn IsMenuItem<R>> = profile_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<R>)
+        .collect();
+    let profiles_menu = Submenu::with_items(app, "Профили запуска", true, &profile_refs)?;
+
+    let mut items: Vec<&dyn IsMenuItem<R>> = vec![&show_item];
+    if !recent.is_empty() {
+        items.push(&recent_menu);
+    }
+    items.extend([&profiles_menu as &dyn IsMenuItem<R>, &separator, &quit_item]);
+    Menu::with_items(app, &items)
+}
+
+/// Rebuilds the tray menu after launch profiles change.
+pub(crate) fn refresh_tray_menu<R: Runtime>(app: &AppHandle<R>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => eprintln!("Failed to rebuild tray menu: {}", e),
+    }
+}
+
+fn launch_game_from_tray(menu_id: &str) {
+    let Some(game_id) = menu_id.strip_prefix(LAUNCH_GAME_MENU_PREFIX) else {
+        return;
+    };
+    let game_id = game_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = games_service::launch_game(&GlobalDb, game_id, None).await {
+            eprintln!("Failed to launch game from tray: {}", e);
+        }
+    });
+}
+
+fn launch_profile_from_tray(menu_id: &str) {
+    let Some((game_id, profile_id)) = menu_id
+        .strip_prefix(LAUNCH_PROFILE_MENU_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+    else {
+        return;
+    };
+    let (game_id, profile_id) = (game_id.to_string(), profile_id.to_string());
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) =
+            games_service::launch_game_with_profile(&GlobalDb, game_id, profile_id).await
+        {
+            eprintln!("Failed to launch profile from tray: {}", e);
+        }
+    });
+}
+
+/// Starts the game named by an `arrancador://launch/<id>` argument, as passed by the
+/// shortcuts `create_shortcut` writes. Returns whether there was one.
+fn launch_from_args(args: &[String]) -> bool {
+    let Some(game_id) = args
+        .iter()
+        .find_map(|arg| games_service::parse_launch_link(arg))
+    else {
+        return false;
+    };
+    let game_id = game_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = games_service::launch_game(&GlobalDb, game_id, None).await {
+            eprintln!("Failed to launch game from shortcut: {}", e);
+        }
+    });
+    true
+}
+
+fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let menu = build_tray_menu(app)?;
+
+    let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID)
         .menu(&menu)
         .tooltip("Arrancador")
         .show_menu_on_left_click(false)
@@ -83,6 +252,10 @@ fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
                 show_main_window(app);
             } else if event.id() == "tray_quit" {
                 request_exit(app);
+            } else if event.id().as_ref().starts_with(LAUNCH_GAME_MENU_PREFIX) {
+                launch_game_from_tray(event.id().as_ref());
+            } else {
+                launch_profile_from_tray(event.id().as_ref());
             }
         })
         .on_tray_icon_event(|tray, event| {
@@ -106,14 +279,42 @@ fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     Ok(())
 }
 
+static DATABASE_WRITERS_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Starts the jobs that write to the database on their own: the tracker (sessions, playtime,
+/// exit and nightly backups), the cover cache prune, the save and scan watchers, the install
+/// size refresh and the auto-scan. Does nothing while the database is read-only or missing;
+/// `retry_database_open` calls it again once the database is writable.
+pub(crate) fn start_database_writers(app: &AppHandle) {
+    if database::database_status().mode != database::DatabaseMode::Ready {
+        eprintln!("Database is not writable; background jobs stay stopped");
+        return;
+    }
+    if DATABASE_WRITERS_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tracker::start_tracker(app.clone());
+    services::images::start_image_cache_prune(GlobalDb, services::images::ImageStore::default());
+    if !test_mode::is_enabled() {
+        backup::save_watch::start_save_watcher(app.clone());
+        services::install_size::start_install_size_refresh(GlobalDb, app.clone());
+        services::auto_scan::start_auto_scan(GlobalDb, app.clone());
+        services::scan_watch::start_scan_watcher(GlobalDb, app.clone());
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize database before starting app
-    if let Err(e) = init_database() {
-        eprintln!("Failed to initialize database: {}", e);
-    }
+    app_info::start_database();
 
     tauri::Builder::default()
+        // A second start (e.g. from a game shortcut) hands its arguments to this instance.
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            if !launch_from_args(&args) {
+                show_main_window(app);
+            }
+        }))
         .manage(AppState::new())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
@@ -123,7 +324,14 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
             setup_tray(app.app_handle())?;
-            tracker::start_tracker(app.app_handle().clone());
+            launch_from_args(&std::env::args().collect::<Vec<_>>());
+            backup::watchdog::start_watchdog(app.app_handle().clone());
+            if !test_mode::is_enabled() {
+                plugins::init(app.app_handle().clone());
+                metadata::auto_match::init(app.app_handle().clone());
+                backup::manifest_refresh::start_manifest_refresh(app.app_handle().clone());
+            }
+            start_database_writers(app.app_handle());
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -142,6 +350,8 @@ pub fn run() {
             greet,
             // Scan commands
             scan_executables_stream,
+            scan_all_directories,
+            scan_directory_incremental,
             cancel_scan,
             get_running_processes,
             // Game commands
@@ -149,24 +359,99 @@ pub fn run() {
             add_games_batch,
             get_all_games,
             get_favorites, // Swap order to force rebuild
+            get_games_page,
+            get_game_summaries,
+            get_recently_played,
+            get_most_played,
+            get_largest_games,
+            compute_install_size,
+            verify_game_files,
             get_game,
             update_game,
             toggle_favorite,
+            set_game_hidden,
+            update_games_batch,
+            delete_games_batch,
+            relocate_game,
+            find_moved_games,
+            set_game_do_not_track,
+            set_game_instance_policy,
+            set_game_metadata_source,
+            set_game_env_vars,
+            set_game_locked_fields,
+            set_game_sync_options,
             delete_game,
             record_game_launch,
             search_games,
+            query_games,
+            export_library_report,
+            export_library,
+            import_library,
+            get_custom_fields,
+            create_custom_field,
+            delete_custom_field,
+            set_game_custom_field,
             game_exists_by_path,
             is_game_installed,
             launch_game,
+            create_shortcut,
+            get_launch_profiles,
+            save_launch_profile,
+            delete_launch_profile,
+            launch_game_with_profile,
+            get_emulators,
+            save_emulator,
+            delete_emulator,
+            set_total_playtime,
+            add_playtime_adjustment,
+            get_game_companions,
+            save_game_companion,
+            delete_game_companion,
+            get_tags,
+            add_tag,
+            delete_tag,
+            assign_tag,
+            unassign_tag,
+            get_games_by_tag,
+            get_collections,
+            save_collection,
+            delete_collection,
+            // Note commands
+            get_game_notes,
+            add_game_note,
+            update_game_note,
+            delete_game_note,
+            get_game_links,
+            add_game_link,
+            delete_game_link,
+            get_collection_games,
             get_running_instances,
             kill_game_processes,
             resolve_shortcut_target,
+            // Cover image commands
+            import_cover_image,
+            cache_cover_image,
+            generate_cover_thumbnail,
+            clean_unused_images,
+            get_image_cache_stats,
+            set_image_cache_pruning_paused,
             // Metadata commands
             search_rawg,
             get_rawg_game_details,
             apply_rawg_metadata,
             set_rawg_api_key,
             get_rawg_api_key,
+            suggest_metadata_matches,
+            search_igdb,
+            get_igdb_game_details,
+            apply_igdb_metadata,
+            set_igdb_credentials,
+            get_igdb_credentials,
+            get_pcgamingwiki_locations,
+            get_pcgamingwiki_save_paths,
+            set_pcgamingwiki_save_paths,
+            refresh_game_compatibility,
+            import_metadata_bundle,
             // Backup commands
             check_ludusavi_installed,
             get_ludusavi_executable_path,
@@ -178,7 +463,31 @@ pub fn run() {
             find_game_saves,
             create_backup,
             get_game_backups,
+            get_backup_history,
             restore_backup,
+            plan_restore_paths,
+            repair_backup,
+            diff_backups,
+            get_install_archive,
+            archive_game_install,
+            unarchive_game_install,
+            preview_restore,
+            get_game_save_paths,
+            get_manifest_override,
+            get_manifest_match,
+            confirm_manifest_match,
+            set_manifest_override,
+            add_game_save_path,
+            update_game_save_path,
+            remove_game_save_path,
+            suggest_save_paths,
+            set_backup_enabled_bulk,
+            get_manifest_info,
+            refresh_manifest,
+            get_backup_diagnostics,
+            resolve_stalled_operation,
+            get_backup_stats,
+            benchmark_compression,
             delete_backup,
             should_backup_before_launch,
             check_backup_needed,
@@ -193,11 +502,32 @@ pub fn run() {
             add_scan_directory,
             get_scan_directories,
             remove_scan_directory,
+            list_scan_directories,
+            set_scan_directory_auto_scan,
+            set_scan_directory_threads,
             // Stats commands
             get_playtime_stats,
+            generate_stats_card,
+            get_library_overview,
+            // Session commands
+            set_session_note,
+            search_session_notes,
+            get_launch_history,
+            get_game_sessions,
+            get_recent_sessions,
             // System commands
             get_system_info,
             test_disk_speed,
+            get_app_info,
+            get_database_status,
+            retry_database_open,
+            list_plugins,
+            set_plugin_enabled,
+            get_plugins_directory,
+            // E2E test-mode commands (no-ops unless ARRANCADOR_TEST_MODE=1)
+            __test_reset_db,
+            __test_seed_library,
+            __test_advance_clock,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
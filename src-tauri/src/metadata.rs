@@ -1,10 +1,36 @@
 use crate::database::with_db;
-use crate::games::Game;
+use crate::db::GlobalDb;
+use crate::games::{Game, MetadataField, MetadataSource};
+use crate::services::games::{fetch_game_by_id, locked_fields};
+use crate::services::http_cache;
+use chrono::{Duration, Utc};
 use reqwest::Client;
-use rusqlite::params;
+use rusqlite::{params, Connection};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+pub use error::MetadataError;
+
+#[path = "metadata/auto_match.rs"]
+pub mod auto_match;
+#[path = "metadata/bundle.rs"]
+pub mod bundle;
+#[path = "metadata/error.rs"]
+pub mod error;
+#[path = "metadata/igdb.rs"]
+pub mod igdb;
+#[path = "metadata/name_cleanup.rs"]
+pub mod name_cleanup;
+#[path = "metadata/pcgamingwiki.rs"]
+pub mod pcgamingwiki;
+#[path = "metadata/protondb.rs"]
+pub mod protondb;
+
 const RAWG_API_BASE: &str = "https://api.rawg.io/api";
+/// How long cached RAWG responses are used without asking RAWG again. Searches are repeated
+/// the most; game details rarely change.
+const SEARCH_TTL_HOURS: i64 = 24;
+const DETAILS_TTL_DAYS: i64 = 7;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RawgSearchResult {
@@ -77,6 +103,174 @@ pub struct RawgPublisher {
     pub slug: String,
 }
 
+/// Provider metadata in the shape of the `games` columns.
+#[derive(Debug, Clone, Default)]
+pub struct GameMetadata {
+    /// Set only when the game should be renamed.
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub released: Option<String>,
+    pub background_image: Option<String>,
+    pub metacritic: Option<i32>,
+    /// Out of 5.
+    pub rating: Option<f64>,
+    pub genres: Option<String>,
+    pub platforms: Option<String>,
+    pub developers: Option<String>,
+    pub publishers: Option<String>,
+}
+
+impl GameMetadata {
+    /// A copy with the `locked` fields cleared.
+    pub fn without_locked(&self, locked: &[MetadataField]) -> Self {
+        let keep = |field: MetadataField| !locked.contains(&field);
+        Self {
+            name: self.name.clone().filter(|_| keep(MetadataField::Name)),
+            description: self
+                .description
+                .clone()
+                .filter(|_| keep(MetadataField::Description)),
+            released: self
+                .released
+                .clone()
+                .filter(|_| keep(MetadataField::Released)),
+            background_image: self
+                .background_image
+                .clone()
+                .filter(|_| keep(MetadataField::BackgroundImage)),
+            metacritic: self.metacritic.filter(|_| keep(MetadataField::Scores)),
+            rating: self.rating.filter(|_| keep(MetadataField::Scores)),
+            genres: self.genres.clone().filter(|_| keep(MetadataField::Genres)),
+            platforms: self
+                .platforms
+                .clone()
+                .filter(|_| keep(MetadataField::Platforms)),
+            developers: self
+                .developers
+                .clone()
+                .filter(|_| keep(MetadataField::Developers)),
+            publishers: self
+                .publishers
+                .clone()
+                .filter(|_| keep(MetadataField::Publishers)),
+        }
+    }
+}
+
+/// Which fields applying metadata may replace. A field that is off is only filled in when
+/// the game has no value for it, so a hand-written description survives a metadata refresh.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct MetadataOverwrite {
+    pub description: bool,
+    pub released: bool,
+    pub background_image: bool,
+    /// `metacritic` and `rating`.
+    pub scores: bool,
+    pub genres: bool,
+    pub platforms: bool,
+    pub developers: bool,
+    pub publishers: bool,
+}
+
+impl Default for MetadataOverwrite {
+    fn default() -> Self {
+        Self {
+            description: true,
+            released: true,
+            background_image: true,
+            scores: true,
+            genres: true,
+            platforms: true,
+            developers: true,
+            publishers: true,
+        }
+    }
+}
+
+/// Writes provider metadata to the game, links it to `provider`'s id and makes that provider
+/// its metadata source. Columns are merged as `overwrite` says; locked fields are skipped.
+pub fn store_metadata(
+    conn: &Connection,
+    game_id: &str,
+    provider: (MetadataSource, i64),
+    metadata: &GameMetadata,
+    overwrite: &MetadataOverwrite,
+) -> rusqlite::Result<Game> {
+    let (source, provider_id) = provider;
+    let id_column = match source {
+        MetadataSource::Rawg => "rawg_id",
+        MetadataSource::Igdb => "igdb_id",
+    };
+    let locked = locked_fields(conn, game_id)?;
+    let metadata = metadata.without_locked(&locked);
+    // Parameters ?4..?12 in this order.
+    let columns = [
+        (
+            "description",
+            MetadataField::Description,
+            overwrite.description,
+        ),
+        ("released", MetadataField::Released, overwrite.released),
+        (
+            "background_image",
+            MetadataField::BackgroundImage,
+            overwrite.background_image,
+        ),
+        ("metacritic", MetadataField::Scores, overwrite.scores),
+        ("rating", MetadataField::Scores, overwrite.scores),
+        ("genres", MetadataField::Genres, overwrite.genres),
+        ("platforms", MetadataField::Platforms, overwrite.platforms),
+        (
+            "developers",
+            MetadataField::Developers,
+            overwrite.developers,
+        ),
+        (
+            "publishers",
+            MetadataField::Publishers,
+            overwrite.publishers,
+        ),
+    ];
+    let assignments: String = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, field, _))| !locked.contains(field))
+        .map(|(i, (column, _, replace))| {
+            if *replace {
+                format!(", {} = ?{}", column, i + 4)
+            } else {
+                format!(", {0} = COALESCE(NULLIF({0}, ''), ?{1})", column, i + 4)
+            }
+        })
+        .collect();
+    let sql = format!(
+        "UPDATE games SET name = COALESCE(?1, name), {} = ?2, metadata_source = ?3{}
+         WHERE id = ?13",
+        id_column, assignments
+    );
+    conn.execute(
+        &sql,
+        params![
+            metadata.name,
+            provider_id,
+            source.as_str(),
+            metadata.description,
+            metadata.released,
+            metadata.background_image,
+            metadata.metacritic,
+            metadata.rating,
+            metadata.genres,
+            metadata.platforms,
+            metadata.developers,
+            metadata.publishers,
+            game_id
+        ],
+    )?;
+
+    fetch_game_by_id(conn, game_id)
+}
+
 fn get_api_key() -> String {
     with_db(|conn| {
         let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = 'rawg_api_key'")?;
@@ -86,73 +280,72 @@ fn get_api_key() -> String {
     .unwrap_or_default()
 }
 
-#[tauri::command]
-pub async fn search_rawg(query: String) -> Result<Vec<RawgGame>, String> {
-    let api_key = get_api_key();
-
-    let url = if api_key.is_empty() {
-        format!(
-            "{}/games?search={}&page_size=10",
-            RAWG_API_BASE,
-            urlencoding::encode(&query)
-        )
-    } else {
-        format!(
-            "{}/games?key={}&search={}&page_size=10",
-            RAWG_API_BASE,
-            api_key,
-            urlencoding::encode(&query)
-        )
-    };
+/// Appends the API key to a RAWG URL; requests without a key share RAWG's anonymous limit.
+fn with_api_key(url: &str, api_key: &str) -> String {
+    if api_key.is_empty() {
+        return url.to_string();
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}key={}", url, separator, api_key)
+}
 
+async fn fetch_body(url: &str) -> Result<String, MetadataError> {
     let client = Client::new();
     let response = client
-        .get(&url)
+        .get(url)
         .header("User-Agent", "Arrancador/0.1.0")
         .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
+        .await?;
 
     if !response.status().is_success() {
-        return Err(format!("API error: {}", response.status()));
+        return Err(MetadataError::from_response("RAWG", &response));
     }
 
-    let result: RawgSearchResult = response
-        .json()
-        .await
-        .map_err(|e| format!("Parse error: {}", e))?;
-
-    Ok(result.results)
+    Ok(response.text().await?)
 }
 
-#[tauri::command]
-pub async fn get_rawg_game_details(rawg_id: i64) -> Result<RawgGameDetails, String> {
-    let api_key = get_api_key();
+/// GETs a RAWG URL through the response cache. The cache key is the URL without the API
+/// key, so changing the key keeps the cache. When RAWG is unreachable or rate limiting, an
+/// expired entry is better than nothing.
+async fn rawg_get<T: DeserializeOwned>(url: String, ttl: Duration) -> Result<T, MetadataError> {
+    let cached = http_cache::lookup(&GlobalDb, &url, Utc::now());
+    if let Some(cached) = cached.as_ref().filter(|cached| cached.fresh) {
+        if let Ok(value) = serde_json::from_str(&cached.body) {
+            return Ok(value);
+        }
+    }
 
-    let url = if api_key.is_empty() {
-        format!("{}/games/{}", RAWG_API_BASE, rawg_id)
-    } else {
-        format!("{}/games/{}?key={}", RAWG_API_BASE, rawg_id, api_key)
+    let body = match fetch_body(&with_api_key(&url, &get_api_key())).await {
+        Ok(body) => body,
+        Err(e @ (MetadataError::RateLimited { .. } | MetadataError::Network { .. })) => {
+            return cached
+                .and_then(|cached| serde_json::from_str(&cached.body).ok())
+                .ok_or(e);
+        }
+        Err(e) => return Err(e),
     };
-
-    let client = Client::new();
-    let response = client
-        .get(&url)
-        .header("User-Agent", "Arrancador/0.1.0")
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("API error: {}", response.status()));
+    let value = serde_json::from_str(&body)?;
+    if let Err(e) = http_cache::store(&GlobalDb, &url, &body, ttl, Utc::now()) {
+        eprintln!("Failed to cache RAWG response: {}", e);
     }
+    Ok(value)
+}
 
-    let details: RawgGameDetails = response
-        .json()
-        .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+#[tauri::command]
+pub async fn search_rawg(query: String) -> Result<Vec<RawgGame>, MetadataError> {
+    let url = format!(
+        "{}/games?search={}&page_size=10",
+        RAWG_API_BASE,
+        urlencoding::encode(&query)
+    );
+    let result: RawgSearchResult = rawg_get(url, Duration::hours(SEARCH_TTL_HOURS)).await?;
+    Ok(result.results)
+}
 
-    Ok(details)
+#[tauri::command]
+pub async fn get_rawg_game_details(rawg_id: i64) -> Result<RawgGameDetails, MetadataError> {
+    let url = format!("{}/games/{}", RAWG_API_BASE, rawg_id);
+    rawg_get(url, Duration::days(DETAILS_TTL_DAYS)).await
 }
 
 #[tauri::command]
@@ -160,7 +353,8 @@ pub async fn apply_rawg_metadata(
     game_id: String,
     rawg_id: i64,
     rename: bool,
-) -> Result<Game, String> {
+    overwrite: Option<MetadataOverwrite>,
+) -> Result<Game, MetadataError> {
     let details = get_rawg_game_details(rawg_id).await?;
 
     let genres = details.genres.as_ref().map(|g| {
@@ -191,89 +385,33 @@ pub async fn apply_rawg_metadata(
             .join(", ")
     });
 
-    let description = details.description_raw.or(details.description);
-    let new_name = if rename {
-        Some(details.name.clone())
-    } else {
-        None
+    let metadata = GameMetadata {
+        name: rename.then(|| details.name.clone()),
+        description: details.description_raw.or(details.description),
+        released: details.released,
+        background_image: details.background_image,
+        metacritic: details.metacritic,
+        rating: details.rating,
+        genres,
+        platforms,
+        developers,
+        publishers,
     };
 
-    // Update game in database
     with_db(|conn| {
-        conn.execute(
-            "UPDATE games SET
-                name = COALESCE(?1, name),
-                rawg_id = ?2,
-                description = ?3,
-                released = ?4,
-                background_image = ?5,
-                metacritic = ?6,
-                rating = ?7,
-                genres = ?8,
-                platforms = ?9,
-                developers = ?10,
-                publishers = ?11
-            WHERE id = ?12",
-            params![
-                new_name,
-                rawg_id,
-                description,
-                details.released,
-                details.background_image,
-                details.metacritic,
-                details.rating,
-                genres,
-                platforms,
-                developers,
-                publishers,
-                game_id
-            ],
-        )?;
-
-        let mut stmt = conn.prepare(
-            "SELECT id, name, exe_path, exe_name, rawg_id, description, released,
-             background_image, metacritic, rating, genres, platforms, developers, publishers,
-             cover_image, is_favorite, play_count, total_playtime, last_played, date_added,
-             backup_enabled, last_backup, backup_count, save_path, user_rating, user_note
-             FROM games WHERE id = ?1",
-        )?;
-
-        stmt.query_row(params![game_id], |row| {
-            Ok(Game {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                exe_path: row.get(2)?,
-                exe_name: row.get(3)?,
-                rawg_id: row.get(4)?,
-                description: row.get(5)?,
-                released: row.get(6)?,
-                background_image: row.get(7)?,
-                metacritic: row.get(8)?,
-                rating: row.get(9)?,
-                genres: row.get(10)?,
-                platforms: row.get(11)?,
-                developers: row.get(12)?,
-                publishers: row.get(13)?,
-                cover_image: row.get(14)?,
-                is_favorite: row.get::<_, i32>(15)? == 1,
-                play_count: row.get(16)?,
-                total_playtime: row.get(17)?,
-                last_played: row.get(18)?,
-                date_added: row.get(19)?,
-                backup_enabled: row.get::<_, i32>(20)? == 1,
-                last_backup: row.get(21)?,
-                backup_count: row.get(22)?,
-                save_path: row.get(23)?,
-                user_rating: row.get(24)?,
-                user_note: row.get(25)?,
-            })
-        })
+        store_metadata(
+            conn,
+            &game_id,
+            (MetadataSource::Rawg, rawg_id),
+            &metadata,
+            &overwrite.unwrap_or_default(),
+        )
     })
-    .map_err(|e| e.to_string())
+    .map_err(MetadataError::from)
 }
 
 #[tauri::command]
-pub fn set_rawg_api_key(key: String) -> Result<(), String> {
+pub fn set_rawg_api_key(key: String) -> Result<(), MetadataError> {
     with_db(|conn| {
         conn.execute(
             "INSERT OR REPLACE INTO settings (key, value) VALUES ('rawg_api_key', ?1)",
@@ -281,10 +419,118 @@ pub fn set_rawg_api_key(key: String) -> Result<(), String> {
         )?;
         Ok(())
     })
-    .map_err(|e| e.to_string())
+    .map_err(MetadataError::from)
 }
 
 #[tauri::command]
-pub fn get_rawg_api_key() -> Result<String, String> {
+pub fn get_rawg_api_key() -> Result<String, MetadataError> {
     Ok(get_api_key())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+
+    #[test]
+    fn fields_without_overwrite_keep_what_the_user_entered() {
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, description, genres,
+                 save_path)
+             VALUES ('g1', 'hk', 'C:/hk.exe', 'hk.exe', '2024-01-01', 'My notes', '',
+                 'D:/Saves/hk')",
+            [],
+        )
+        .expect("insert game");
+
+        let metadata = GameMetadata {
+            name: Some("Hollow Knight".to_string()),
+            description: Some("A knight descends.".to_string()),
+            released: Some("2017-02-24".to_string()),
+            genres: Some("Action".to_string()),
+            metacritic: Some(87),
+            ..GameMetadata::default()
+        };
+        let overwrite = MetadataOverwrite {
+            description: false,
+            genres: false,
+            ..MetadataOverwrite::default()
+        };
+        let game = store_metadata(
+            &conn,
+            "g1",
+            (MetadataSource::Rawg, 9767),
+            &metadata,
+            &overwrite,
+        )
+        .expect("store metadata");
+
+        assert_eq!(game.name, "Hollow Knight");
+        assert_eq!(game.rawg_id, Some(9767));
+        assert_eq!(game.metadata_source, Some(MetadataSource::Rawg));
+        assert_eq!(game.description.as_deref(), Some("My notes"));
+        // Empty fields are filled in even when they may not be overwritten.
+        assert_eq!(game.genres.as_deref(), Some("Action"));
+        assert_eq!(game.released.as_deref(), Some("2017-02-24"));
+        assert_eq!(game.metacritic, Some(87));
+        assert_eq!(game.save_path.as_deref(), Some("D:/Saves/hk"));
+
+        let game = store_metadata(
+            &conn,
+            "g1",
+            (MetadataSource::Igdb, 1),
+            &GameMetadata::default(),
+            &MetadataOverwrite::default(),
+        )
+        .expect("store igdb metadata");
+        assert_eq!(game.name, "Hollow Knight");
+        assert_eq!(game.igdb_id, Some(1));
+        assert_eq!(game.description, None);
+    }
+
+    #[test]
+    fn locked_fields_are_not_replaced() {
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, genres,
+                 background_image, locked_fields)
+             VALUES ('g1', 'My Knight', 'C:/hk.exe', 'hk.exe', '2024-01-01', 'Metroidvania',
+                 'C:/art.png', '[\"name\",\"background_image\",\"genres\"]')",
+            [],
+        )
+        .expect("insert game");
+
+        let metadata = GameMetadata {
+            name: Some("Hollow Knight".to_string()),
+            background_image: Some("https://media.rawg.io/hk.jpg".to_string()),
+            genres: Some("Action".to_string()),
+            developers: Some("Team Cherry".to_string()),
+            ..GameMetadata::default()
+        };
+        let game = store_metadata(
+            &conn,
+            "g1",
+            (MetadataSource::Rawg, 9767),
+            &metadata,
+            &MetadataOverwrite::default(),
+        )
+        .expect("store metadata");
+
+        assert_eq!(
+            game.locked_fields,
+            vec![
+                MetadataField::Name,
+                MetadataField::BackgroundImage,
+                MetadataField::Genres
+            ]
+        );
+        assert_eq!(game.name, "My Knight");
+        assert_eq!(game.background_image.as_deref(), Some("C:/art.png"));
+        assert_eq!(game.genres.as_deref(), Some("Metroidvania"));
+        assert_eq!(game.developers.as_deref(), Some("Team Cherry"));
+        assert_eq!(game.rawg_id, Some(9767));
+    }
+}
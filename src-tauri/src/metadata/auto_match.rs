@@ -0,0 +1,254 @@
+//! Opt-in RAWG matching for newly added games (`rawg_auto_match`). Added games are queued and
+//! searched one at a time in the background under their cleaned name; a confident top result
+//! is applied right away and announced as `metadata:auto-matched`, anything else is emitted as
+//! `metadata:match-needed` so the user can pick the game.
+
+use super::name_cleanup::clean_game_name;
+use super::{apply_rawg_metadata, search_rawg, MetadataError, RawgGame};
+use crate::database::with_db;
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Score the best result needs to be applied without asking.
+pub const CONFIDENT_MATCH: f64 = 0.9;
+/// How far the best result has to be ahead of the runner-up.
+const MIN_LEAD: f64 = 0.15;
+/// Pause between searches so a large batch does not hammer the API.
+const REQUEST_GAP: Duration = Duration::from_millis(500);
+/// Results passed along with `metadata:match-needed`.
+const MAX_CANDIDATES: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoMatched {
+    pub game_id: String,
+    pub rawg_id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchNeeded {
+    pub game_id: String,
+    pub game_name: String,
+    /// Top search results in RAWG's order; empty when the search found nothing.
+    pub candidates: Vec<RawgGame>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MatchDecision {
+    Apply(RawgGame),
+    Ask(Vec<RawgGame>),
+}
+
+#[derive(Default)]
+struct AutoMatchQueue {
+    pending: VecDeque<String>,
+    running: bool,
+}
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+lazy_static::lazy_static! {
+    static ref QUEUE: Mutex<AutoMatchQueue> = Mutex::new(AutoMatchQueue::default());
+}
+
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// How well `candidate` matches the searched `query`, from `0.0` to `1.0`: identical words
+/// score `1.0`, the same letters with different spacing `0.95`, otherwise the share of
+/// common words.
+pub fn match_confidence(query: &str, candidate: &str) -> f64 {
+    let query = words(query);
+    let candidate = words(candidate);
+    if query.is_empty() || candidate.is_empty() {
+        return 0.0;
+    }
+    if query == candidate {
+        return 1.0;
+    }
+    if query.concat() == candidate.concat() {
+        return 0.95;
+    }
+    let shared = query.iter().filter(|word| candidate.contains(word)).count();
+    let total = query.len() + candidate.len() - shared;
+    0.85 * shared as f64 / total as f64
+}
+
+/// Applies the best result when it is confident and clearly ahead of the others; two equally
+/// good results (a remake and the original, say) are left to the user.
+pub fn decide(query: &str, results: Vec<RawgGame>) -> MatchDecision {
+    let scores: Vec<f64> = results
+        .iter()
+        .map(|game| match_confidence(query, &game.name))
+        .collect();
+    let best = scores.iter().enumerate().fold(
+        None,
+        |best: Option<(usize, f64)>, (index, score)| match best {
+            Some((_, top)) if top >= *score => best,
+            _ => Some((index, *score)),
+        },
+    );
+    if let Some((index, top)) = best {
+        let runner_up = scores
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(_, score)| *score)
+            .fold(0.0, f64::max);
+        if top >= CONFIDENT_MATCH && top - runner_up >= MIN_LEAD {
+            return MatchDecision::Apply(results[index].clone());
+        }
+    }
+    MatchDecision::Ask(results.into_iter().take(MAX_CANDIDATES).collect())
+}
+
+pub fn is_enabled() -> bool {
+    with_db(|conn| {
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = 'rawg_auto_match'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+    })
+    .ok()
+    .flatten()
+    .is_some_and(|value| value == "true")
+}
+
+/// Lets `enqueue` start the worker; until then, and in test mode, added games are not matched.
+pub fn init(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// Queues `game_ids` for matching when `rawg_auto_match` is on and starts the worker if it is
+/// idle. Games that have a RAWG id by the time their turn comes are skipped.
+pub fn enqueue(game_ids: impl IntoIterator<Item = String>) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+    if !is_enabled() {
+        return;
+    }
+    let start = {
+        let Ok(mut queue) = QUEUE.lock() else {
+            return;
+        };
+        queue.pending.extend(game_ids);
+        let start = !queue.running && !queue.pending.is_empty();
+        queue.running |= start;
+        start
+    };
+    if start {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { run_queue(app).await });
+    }
+}
+
+/// The next queued game, or `None` after marking the worker idle.
+fn next_game() -> Option<String> {
+    let mut queue = QUEUE.lock().ok()?;
+    let next = queue.pending.pop_front();
+    if next.is_none() {
+        queue.running = false;
+    }
+    next
+}
+
+async fn run_queue(app: AppHandle) {
+    while let Some(game_id) = next_game() {
+        if let Err(e) = match_game(&app, &game_id).await {
+            eprintln!("Auto match for {} failed: {}", game_id, e);
+        }
+        tokio::time::sleep(REQUEST_GAP).await;
+    }
+}
+
+async fn match_game(app: &AppHandle, game_id: &str) -> Result<(), MetadataError> {
+    let game: Option<(String, Option<i64>)> = with_db(|conn| {
+        conn.query_row(
+            "SELECT name, rawg_id FROM games WHERE id = ?1",
+            params![game_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+    })?;
+    let Some((name, None)) = game else {
+        return Ok(());
+    };
+
+    let query = clean_game_name(&name);
+    match decide(&query, search_rawg(query.clone()).await?) {
+        MatchDecision::Apply(top) => {
+            apply_rawg_metadata(game_id.to_string(), top.id, true, None).await?;
+            let _ = app.emit(
+                "metadata:auto-matched",
+                AutoMatched {
+                    game_id: game_id.to_string(),
+                    rawg_id: top.id,
+                    name: top.name,
+                },
+            );
+        }
+        MatchDecision::Ask(candidates) => {
+            let _ = app.emit(
+                "metadata:match-needed",
+                MatchNeeded {
+                    game_id: game_id.to_string(),
+                    game_name: name,
+                    candidates,
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(id: i64, name: &str) -> RawgGame {
+        RawgGame {
+            id,
+            name: name.to_string(),
+            slug: name.to_lowercase().replace(' ', "-"),
+            released: None,
+            background_image: None,
+            metacritic: None,
+            rating: None,
+            ratings_count: None,
+            genres: None,
+            platforms: None,
+        }
+    }
+
+    #[test]
+    fn only_a_clear_best_result_is_applied_automatically() {
+        assert_eq!(match_confidence("Hollow Knight", "Hollow Knight"), 1.0);
+        assert_eq!(match_confidence("hollow_knight", "Hollow Knight"), 1.0);
+        assert_eq!(match_confidence("Witcher3", "Witcher 3"), 0.95);
+        assert!(match_confidence("Witcher 3", "The Witcher 3: Wild Hunt") < CONFIDENT_MATCH);
+
+        let decision = decide(
+            "Hollow Knight",
+            vec![game(1, "Hollow Knight: Silksong"), game(2, "Hollow Knight")],
+        );
+        assert!(matches!(decision, MatchDecision::Apply(top) if top.id == 2));
+
+        let decision = decide("Doom", vec![game(1, "DOOM"), game(2, "Doom")]);
+        assert!(matches!(decision, MatchDecision::Ask(candidates) if candidates.len() == 2));
+
+        let decision = decide("Witcher 3", vec![game(1, "The Witcher 3: Wild Hunt")]);
+        assert!(matches!(decision, MatchDecision::Ask(_)));
+        assert!(matches!(decide("Nothing", Vec::new()), MatchDecision::Ask(c) if c.is_empty()));
+    }
+}
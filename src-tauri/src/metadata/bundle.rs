@@ -0,0 +1,275 @@
+//! Offline metadata bundles: a JSON file of games downloaded elsewhere, for libraries on
+//! metered or offline connections. A bundle is an array of entries, or an object holding one
+//! as `games` or `results` (so a saved RAWG search page works as is). Entries are matched to
+//! games by name or slug and only fill fields the game does not have yet and has not locked.
+
+use super::{GameMetadata, MetadataError};
+use crate::db::{Db, GlobalDb};
+use crate::services::games::locked_fields;
+use rusqlite::params;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+
+#[derive(Debug, Clone, Default)]
+pub struct BundleEntry {
+    pub name: String,
+    pub slug: Option<String>,
+    pub rawg_id: Option<i64>,
+    pub igdb_id: Option<i64>,
+    pub metadata: GameMetadata,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MetadataBundleSummary {
+    /// Usable entries in the bundle.
+    pub entries: usize,
+    /// Games an entry was found for.
+    pub matched: usize,
+    /// Games without a matching entry.
+    pub unmatched: usize,
+}
+
+/// Lowercase alphanumeric words joined by `-`, so `Hollow Knight` and the slug
+/// `hollow-knight` compare equal.
+fn match_key(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn text(value: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| value.get(key)?.as_str())
+        .map(str::trim)
+        .find(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// A list field as the comma-separated text `games` stores: plain text, an array of names,
+/// or RAWG's `[{ "name": .. }]` / `[{ "platform": { "name": .. } }]`.
+fn list(value: &Value, key: &str) -> Option<String> {
+    let field = value.get(key)?;
+    if let Some(text) = field.as_str() {
+        return Some(text.trim().to_string()).filter(|text| !text.is_empty());
+    }
+    let names: Vec<&str> = field
+        .as_array()?
+        .iter()
+        .filter_map(|item| {
+            item.as_str()
+                .or_else(|| item.get("name")?.as_str())
+                .or_else(|| item.get("platform")?.get("name")?.as_str())
+        })
+        .collect();
+    (!names.is_empty()).then(|| names.join(", "))
+}
+
+fn entry_from_value(value: &Value) -> Option<BundleEntry> {
+    let name = text(value, &["name"])?;
+    Some(BundleEntry {
+        slug: text(value, &["slug"]),
+        rawg_id: value.get("rawg_id").and_then(Value::as_i64),
+        igdb_id: value.get("igdb_id").and_then(Value::as_i64),
+        metadata: GameMetadata {
+            name: None,
+            description: text(value, &["description_raw", "description", "summary"]),
+            released: text(value, &["released"]),
+            background_image: text(value, &["background_image", "cover_image"]),
+            metacritic: value
+                .get("metacritic")
+                .and_then(Value::as_i64)
+                .map(|score| score as i32),
+            rating: value.get("rating").and_then(Value::as_f64),
+            genres: list(value, "genres"),
+            platforms: list(value, "platforms"),
+            developers: list(value, "developers"),
+            publishers: list(value, "publishers"),
+        },
+        name,
+    })
+}
+
+pub fn parse_bundle(json: &str) -> Result<Vec<BundleEntry>, MetadataError> {
+    let value: Value = serde_json::from_str(json)?;
+    let items = value
+        .as_array()
+        .or_else(|| value.get("games")?.as_array())
+        .or_else(|| value.get("results")?.as_array())
+        .ok_or_else(|| MetadataError::other("Not a metadata bundle: expected a list of games"))?;
+    Ok(items.iter().filter_map(entry_from_value).collect())
+}
+
+/// Fills the missing metadata of every game that has an entry in the bundle. Games are
+/// matched by their RAWG or IGDB id first, then by name against entry names and slugs.
+pub fn apply_bundle<D: Db>(
+    db: &D,
+    entries: &[BundleEntry],
+) -> rusqlite::Result<MetadataBundleSummary> {
+    let mut by_key: HashMap<String, &BundleEntry> = HashMap::new();
+    let mut by_rawg: HashMap<i64, &BundleEntry> = HashMap::new();
+    let mut by_igdb: HashMap<i64, &BundleEntry> = HashMap::new();
+    for entry in entries {
+        by_key.entry(match_key(&entry.name)).or_insert(entry);
+        if let Some(slug) = &entry.slug {
+            by_key.entry(match_key(slug)).or_insert(entry);
+        }
+        if let Some(id) = entry.rawg_id {
+            by_rawg.entry(id).or_insert(entry);
+        }
+        if let Some(id) = entry.igdb_id {
+            by_igdb.entry(id).or_insert(entry);
+        }
+    }
+
+    db.with_conn(|conn| {
+        let games: Vec<(String, String, Option<i64>, Option<i64>)> = conn
+            .prepare("SELECT id, name, rawg_id, igdb_id FROM games")?
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut summary = MetadataBundleSummary {
+            entries: entries.len(),
+            ..MetadataBundleSummary::default()
+        };
+        let tx = conn.unchecked_transaction()?;
+        for (id, name, rawg_id, igdb_id) in games {
+            let entry = rawg_id
+                .and_then(|rawg_id| by_rawg.get(&rawg_id))
+                .or_else(|| igdb_id.and_then(|igdb_id| by_igdb.get(&igdb_id)))
+                .or_else(|| by_key.get(&match_key(&name)));
+            let Some(entry) = entry else {
+                summary.unmatched += 1;
+                continue;
+            };
+            let metadata = entry.metadata.without_locked(&locked_fields(&tx, &id)?);
+            tx.execute(
+                "UPDATE games SET
+                    rawg_id = COALESCE(rawg_id, ?1),
+                    igdb_id = COALESCE(igdb_id, ?2),
+                    description = COALESCE(NULLIF(description, ''), ?3),
+                    released = COALESCE(NULLIF(released, ''), ?4),
+                    background_image = COALESCE(NULLIF(background_image, ''), ?5),
+                    metacritic = COALESCE(metacritic, ?6),
+                    rating = COALESCE(rating, ?7),
+                    genres = COALESCE(NULLIF(genres, ''), ?8),
+                    platforms = COALESCE(NULLIF(platforms, ''), ?9),
+                    developers = COALESCE(NULLIF(developers, ''), ?10),
+                    publishers = COALESCE(NULLIF(publishers, ''), ?11)
+                WHERE id = ?12",
+                params![
+                    entry.rawg_id,
+                    entry.igdb_id,
+                    metadata.description,
+                    metadata.released,
+                    metadata.background_image,
+                    metadata.metacritic,
+                    metadata.rating,
+                    metadata.genres,
+                    metadata.platforms,
+                    metadata.developers,
+                    metadata.publishers,
+                    id
+                ],
+            )?;
+            summary.matched += 1;
+        }
+        tx.commit()?;
+        Ok(summary)
+    })
+}
+
+/// Reads a metadata bundle from `path` and fills the library's missing metadata from it.
+#[tauri::command]
+pub async fn import_metadata_bundle(path: String) -> Result<MetadataBundleSummary, MetadataError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let json = fs::read_to_string(&path)
+            .map_err(|e| MetadataError::other(format!("Failed to read {}: {}", path, e)))?;
+        let entries = parse_bundle(&json)?;
+        Ok(apply_bundle(&GlobalDb, &entries)?)
+    })
+    .await
+    .map_err(|e| MetadataError::other(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+
+    #[test]
+    fn bundle_entries_fill_only_missing_fields_of_matching_games() {
+        let entries = parse_bundle(
+            r#"{ "results": [
+                { "name": "Hollow Knight", "slug": "hollow-knight", "rawg_id": 9767,
+                  "description_raw": "A knight descends.", "released": "2017-02-24",
+                  "metacritic": 87, "genres": [{ "id": 4, "name": "Action" }],
+                  "platforms": [{ "platform": { "id": 4, "name": "PC" } }] },
+                { "name": "Celeste", "developers": ["Maddy Makes Games"], "genres": "Platformer" },
+                { "slug": "no-name" }
+            ] }"#,
+        )
+        .expect("parse bundle");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].metadata.genres.as_deref(), Some("Action"));
+        assert_eq!(entries[0].metadata.platforms.as_deref(), Some("PC"));
+        assert!(parse_bundle(r#"{ "count": 0 }"#).is_err());
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute_batch(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, description)
+                 VALUES ('g1', 'hollow_knight', 'a.exe', 'a.exe', '2024-01-01', 'Mine');
+             INSERT INTO games (id, name, exe_path, exe_name, date_added)
+                 VALUES ('g2', 'CELESTE', 'b.exe', 'b.exe', '2024-01-01');
+             INSERT INTO games (id, name, exe_path, exe_name, date_added)
+                 VALUES ('g3', 'Hades', 'c.exe', 'c.exe', '2024-01-01');",
+        )
+        .expect("insert games");
+        let db = ConnectionDb::new(conn);
+
+        let summary = apply_bundle(&db, &entries).expect("apply bundle");
+        assert_eq!(
+            summary,
+            MetadataBundleSummary {
+                entries: 2,
+                matched: 2,
+                unmatched: 1,
+            }
+        );
+        let row = |id: &str| {
+            db.with_conn(|conn| {
+                conn.query_row(
+                    "SELECT description, released, rawg_id, genres, developers
+                     FROM games WHERE id = ?1",
+                    params![id],
+                    |row| {
+                        Ok((
+                            row.get::<_, Option<String>>(0)?,
+                            row.get::<_, Option<String>>(1)?,
+                            row.get::<_, Option<i64>>(2)?,
+                            row.get::<_, Option<String>>(3)?,
+                            row.get::<_, Option<String>>(4)?,
+                        ))
+                    },
+                )
+            })
+            .expect("read game")
+        };
+        let (description, released, rawg_id, genres, _) = row("g1");
+        assert_eq!(description.as_deref(), Some("Mine"));
+        assert_eq!(released.as_deref(), Some("2017-02-24"));
+        assert_eq!(rawg_id, Some(9767));
+        assert_eq!(genres.as_deref(), Some("Action"));
+        let (_, _, _, genres, developers) = row("g2");
+        assert_eq!(genres.as_deref(), Some("Platformer"));
+        assert_eq!(developers.as_deref(), Some("Maddy Makes Games"));
+    }
+}
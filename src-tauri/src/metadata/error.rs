@@ -0,0 +1,169 @@
+//! Errors of the metadata commands. They reach the UI as `{ kind, ... }` objects so it can tell
+//! a rate limit (wait and retry) from rejected credentials (fix the settings) from being offline.
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MetadataError {
+    /// HTTP 429. `retry_after` is how many seconds the service asked to wait, when it said.
+    RateLimited { retry_after: Option<u64> },
+    /// Missing or rejected API key or client credentials.
+    Unauthorized { message: String },
+    /// The service has no entry with that id.
+    NotFound { message: String },
+    /// The service could not be reached, or failed on its side.
+    Network { message: String },
+    /// Unreadable responses and database errors.
+    Other { message: String },
+}
+
+impl MetadataError {
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::Unauthorized {
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound {
+            message: message.into(),
+        }
+    }
+
+    pub fn other(message: impl Into<String>) -> Self {
+        Self::Other {
+            message: message.into(),
+        }
+    }
+
+    /// The error for a failed HTTP status from `service`; `retry_after` is the raw
+    /// `Retry-After` header.
+    pub fn from_status(service: &str, status: StatusCode, retry_after: Option<&str>) -> Self {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                // Only the delay-seconds form; HTTP dates are rare enough to ignore.
+                retry_after: retry_after.and_then(|value| value.trim().parse().ok()),
+            },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Self::unauthorized(format!("{} rejected the credentials: {}", service, status))
+            }
+            StatusCode::NOT_FOUND => Self::not_found(format!("{}: {}", service, status)),
+            _ => Self::Network {
+                message: format!("{} API error: {}", service, status),
+            },
+        }
+    }
+
+    /// The error for a response from `service` that was not a success.
+    pub fn from_response(service: &str, response: &Response) -> Self {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok());
+        Self::from_status(service, response.status(), retry_after)
+    }
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RateLimited {
+                retry_after: Some(seconds),
+            } => write!(f, "Rate limited, retry in {} s", seconds),
+            Self::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            Self::Unauthorized { message }
+            | Self::NotFound { message }
+            | Self::Network { message }
+            | Self::Other { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<reqwest::Error> for MetadataError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            Self::Other {
+                message: format!("Parse error: {}", e),
+            }
+        } else {
+            Self::Network {
+                message: format!("Network error: {}", e),
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for MetadataError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Other {
+            message: format!("Parse error: {}", e),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for MetadataError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            // The game being updated is gone.
+            rusqlite::Error::QueryReturnedNoRows => Self::not_found(e.to_string()),
+            _ => Self::Other {
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_statuses_map_to_error_kinds_the_ui_can_act_on() {
+        assert_eq!(
+            MetadataError::from_status("RAWG", StatusCode::TOO_MANY_REQUESTS, Some(" 30 ")),
+            MetadataError::RateLimited {
+                retry_after: Some(30)
+            }
+        );
+        assert_eq!(
+            MetadataError::from_status(
+                "RAWG",
+                StatusCode::TOO_MANY_REQUESTS,
+                Some("Wed, 21 Oct 2015 07:28:00 GMT")
+            ),
+            MetadataError::RateLimited { retry_after: None }
+        );
+        assert!(matches!(
+            MetadataError::from_status("RAWG", StatusCode::UNAUTHORIZED, None),
+            MetadataError::Unauthorized { .. }
+        ));
+        assert!(matches!(
+            MetadataError::from_status("IGDB", StatusCode::NOT_FOUND, None),
+            MetadataError::NotFound { .. }
+        ));
+        assert!(matches!(
+            MetadataError::from_status("RAWG", StatusCode::BAD_GATEWAY, None),
+            MetadataError::Network { .. }
+        ));
+
+        let json = serde_json::to_value(MetadataError::RateLimited {
+            retry_after: Some(5),
+        })
+        .expect("serialize");
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "rate_limited", "retry_after": 5 })
+        );
+        assert_eq!(
+            MetadataError::RateLimited {
+                retry_after: Some(5)
+            }
+            .to_string(),
+            "Rate limited, retry in 5 s"
+        );
+    }
+}
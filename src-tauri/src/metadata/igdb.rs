@@ -0,0 +1,464 @@
+//! IGDB as a second metadata provider, for titles RAWG misses or while RAWG is rate limiting.
+//! IGDB signs in through Twitch: the client id and secret of a Twitch developer application
+//! (`igdb_client_id` / `igdb_client_secret`) are exchanged for an app access token, which is
+//! kept until shortly before it expires and fetched again once if IGDB rejects it.
+
+use super::{store_metadata, GameMetadata, MetadataError, MetadataOverwrite};
+use crate::database::with_db;
+use crate::games::{Game, MetadataSource};
+use chrono::DateTime;
+use reqwest::{Client, Response, StatusCode};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const IGDB_API_BASE: &str = "https://api.igdb.com/v4";
+const TWITCH_TOKEN_URL: &str = "https://id.twitch.tv/oauth2/token";
+const IGDB_IMAGE_BASE: &str = "https://images.igdb.com/igdb/image/upload";
+/// Tokens are renewed this long before Twitch says they expire.
+const TOKEN_MARGIN: Duration = Duration::from_secs(60);
+
+const SEARCH_FIELDS: &str =
+    "name, slug, first_release_date, cover.image_id, aggregated_rating, genres.name, platforms.name";
+const DETAIL_FIELDS: &str = "name, slug, summary, storyline, first_release_date, cover.image_id, \
+     artworks.image_id, screenshots.image_id, aggregated_rating, total_rating, genres.name, \
+     platforms.name, involved_companies.company.name, involved_companies.developer, \
+     involved_companies.publisher";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgdbCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgdbGame {
+    pub id: i64,
+    pub name: String,
+    pub slug: Option<String>,
+    /// First release date as `YYYY-MM-DD`.
+    pub released: Option<String>,
+    pub cover_image: Option<String>,
+    /// Critic score, 0-100.
+    pub aggregated_rating: Option<f64>,
+    pub genres: Vec<String>,
+    pub platforms: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgdbGameDetails {
+    pub id: i64,
+    pub name: String,
+    pub slug: Option<String>,
+    pub summary: Option<String>,
+    pub storyline: Option<String>,
+    pub released: Option<String>,
+    pub cover_image: Option<String>,
+    /// Artwork, or a screenshot when the game has none.
+    pub background_image: Option<String>,
+    /// Critic score, 0-100.
+    pub aggregated_rating: Option<f64>,
+    /// Critic and user score combined, 0-100.
+    pub total_rating: Option<f64>,
+    pub genres: Vec<String>,
+    pub platforms: Vec<String>,
+    pub developers: Vec<String>,
+    pub publishers: Vec<String>,
+}
+
+/// A game as the IGDB API returns it, with the expanded fields we ask for.
+#[derive(Debug, Deserialize)]
+struct IgdbRecord {
+    id: i64,
+    name: String,
+    slug: Option<String>,
+    summary: Option<String>,
+    storyline: Option<String>,
+    /// Unix seconds.
+    first_release_date: Option<i64>,
+    cover: Option<IgdbImage>,
+    #[serde(default)]
+    artworks: Vec<IgdbImage>,
+    #[serde(default)]
+    screenshots: Vec<IgdbImage>,
+    aggregated_rating: Option<f64>,
+    total_rating: Option<f64>,
+    #[serde(default)]
+    genres: Vec<IgdbNamed>,
+    #[serde(default)]
+    platforms: Vec<IgdbNamed>,
+    #[serde(default)]
+    involved_companies: Vec<IgdbInvolvedCompany>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IgdbImage {
+    image_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IgdbNamed {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IgdbInvolvedCompany {
+    company: IgdbNamed,
+    #[serde(default)]
+    developer: bool,
+    #[serde(default)]
+    publisher: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TwitchToken {
+    access_token: String,
+    /// Seconds.
+    expires_in: u64,
+}
+
+struct CachedToken {
+    client_id: String,
+    access_token: String,
+    expires_at: Instant,
+}
+
+static TOKEN: Mutex<Option<CachedToken>> = Mutex::new(None);
+
+fn image_url(size: &str, image: &IgdbImage) -> String {
+    format!("{}/{}/{}.jpg", IGDB_IMAGE_BASE, size, image.image_id)
+}
+
+fn release_date(timestamp: Option<i64>) -> Option<String> {
+    let date = DateTime::from_timestamp(timestamp?, 0)?;
+    Some(date.format("%Y-%m-%d").to_string())
+}
+
+fn names(named: &[IgdbNamed]) -> Vec<String> {
+    named.iter().map(|item| item.name.clone()).collect()
+}
+
+impl From<IgdbRecord> for IgdbGame {
+    fn from(record: IgdbRecord) -> Self {
+        Self {
+            id: record.id,
+            released: release_date(record.first_release_date),
+            cover_image: record.cover.as_ref().map(|c| image_url("t_cover_big", c)),
+            aggregated_rating: record.aggregated_rating,
+            genres: names(&record.genres),
+            platforms: names(&record.platforms),
+            name: record.name,
+            slug: record.slug,
+        }
+    }
+}
+
+impl From<IgdbRecord> for IgdbGameDetails {
+    fn from(record: IgdbRecord) -> Self {
+        let companies = |pick: fn(&IgdbInvolvedCompany) -> bool| {
+            record
+                .involved_companies
+                .iter()
+                .filter(|involved| pick(involved))
+                .map(|involved| involved.company.name.clone())
+                .collect::<Vec<_>>()
+        };
+        let developers = companies(|involved| involved.developer);
+        let publishers = companies(|involved| involved.publisher);
+        Self {
+            id: record.id,
+            released: release_date(record.first_release_date),
+            cover_image: record.cover.as_ref().map(|c| image_url("t_cover_big", c)),
+            background_image: record
+                .artworks
+                .first()
+                .or(record.screenshots.first())
+                .map(|image| image_url("t_1080p", image)),
+            aggregated_rating: record.aggregated_rating,
+            total_rating: record.total_rating,
+            genres: names(&record.genres),
+            platforms: names(&record.platforms),
+            developers,
+            publishers,
+            name: record.name,
+            slug: record.slug,
+            summary: record.summary,
+            storyline: record.storyline,
+        }
+    }
+}
+
+/// An Apicalypse search for `query`, which is quoted as a string literal.
+fn search_body(query: &str) -> String {
+    let escaped = query.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        "search \"{}\"; fields {}; limit 10;",
+        escaped, SEARCH_FIELDS
+    )
+}
+
+fn get_credentials() -> IgdbCredentials {
+    let setting = |key: &str| {
+        with_db(|conn| {
+            let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?1")?;
+            let value: String = stmt
+                .query_row(params![key], |row| row.get(0))
+                .unwrap_or_default();
+            Ok(value)
+        })
+        .unwrap_or_default()
+    };
+    IgdbCredentials {
+        client_id: setting("igdb_client_id"),
+        client_secret: setting("igdb_client_secret"),
+    }
+}
+
+/// The cached token for `client_id` unless it is about to expire.
+fn cached_token(client_id: &str, now: Instant) -> Option<String> {
+    let cache = TOKEN.lock().ok()?;
+    cache
+        .as_ref()
+        .filter(|token| token.client_id == client_id && now + TOKEN_MARGIN < token.expires_at)
+        .map(|token| token.access_token.clone())
+}
+
+fn store_token(client_id: &str, token: &TwitchToken, now: Instant) {
+    if let Ok(mut cache) = TOKEN.lock() {
+        *cache = Some(CachedToken {
+            client_id: client_id.to_string(),
+            access_token: token.access_token.clone(),
+            expires_at: now + Duration::from_secs(token.expires_in),
+        });
+    }
+}
+
+fn forget_token() {
+    if let Ok(mut cache) = TOKEN.lock() {
+        *cache = None;
+    }
+}
+
+async fn access_token(
+    client: &Client,
+    credentials: &IgdbCredentials,
+    refresh: bool,
+) -> Result<String, MetadataError> {
+    if !refresh {
+        if let Some(token) = cached_token(&credentials.client_id, Instant::now()) {
+            return Ok(token);
+        }
+    }
+
+    let url = format!(
+        "{}?client_id={}&client_secret={}&grant_type=client_credentials",
+        TWITCH_TOKEN_URL,
+        urlencoding::encode(&credentials.client_id),
+        urlencoding::encode(&credentials.client_secret)
+    );
+    let response = client
+        .post(&url)
+        .header("User-Agent", "Arrancador/0.1.0")
+        .send()
+        .await?;
+
+    if response.status() == StatusCode::BAD_REQUEST {
+        // Twitch answers an unknown client secret with 400 rather than 401.
+        return Err(MetadataError::unauthorized(format!(
+            "Twitch sign-in failed: {}",
+            response.status()
+        )));
+    }
+    if !response.status().is_success() {
+        return Err(MetadataError::from_response("Twitch", &response));
+    }
+
+    let token: TwitchToken = response.json().await?;
+    store_token(&credentials.client_id, &token, Instant::now());
+    Ok(token.access_token)
+}
+
+async fn post_query(
+    client: &Client,
+    credentials: &IgdbCredentials,
+    token: &str,
+    body: &str,
+) -> Result<Response, MetadataError> {
+    let response = client
+        .post(format!("{}/games", IGDB_API_BASE))
+        .header("Client-ID", &credentials.client_id)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "Arrancador/0.1.0")
+        .body(body.to_string())
+        .send()
+        .await?;
+    Ok(response)
+}
+
+/// Runs an Apicalypse query against the `games` endpoint.
+async fn query_games(body: String) -> Result<Vec<IgdbRecord>, MetadataError> {
+    let credentials = get_credentials();
+    if credentials.client_id.is_empty() || credentials.client_secret.is_empty() {
+        return Err(MetadataError::unauthorized(
+            "IGDB client id and secret are not set",
+        ));
+    }
+
+    let client = Client::new();
+    let token = access_token(&client, &credentials, false).await?;
+    let mut response = post_query(&client, &credentials, &token, &body).await?;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        // Revoked or expired early; one fresh token is worth a retry.
+        let token = access_token(&client, &credentials, true).await?;
+        response = post_query(&client, &credentials, &token, &body).await?;
+    }
+
+    if !response.status().is_success() {
+        return Err(MetadataError::from_response("IGDB", &response));
+    }
+
+    Ok(response.json().await?)
+}
+
+#[tauri::command]
+pub async fn search_igdb(query: String) -> Result<Vec<IgdbGame>, MetadataError> {
+    let records = query_games(search_body(&query)).await?;
+    Ok(records.into_iter().map(IgdbGame::from).collect())
+}
+
+#[tauri::command]
+pub async fn get_igdb_game_details(igdb_id: i64) -> Result<IgdbGameDetails, MetadataError> {
+    let body = format!("fields {}; where id = {};", DETAIL_FIELDS, igdb_id);
+    query_games(body)
+        .await?
+        .into_iter()
+        .next()
+        .map(IgdbGameDetails::from)
+        .ok_or_else(|| MetadataError::not_found(format!("IGDB game {} not found", igdb_id)))
+}
+
+/// Fills the game's metadata from IGDB and makes IGDB its metadata source. Scores are
+/// stored on RAWG's scales: the critic score as `metacritic`, the combined one out of 5.
+#[tauri::command]
+pub async fn apply_igdb_metadata(
+    game_id: String,
+    igdb_id: i64,
+    rename: bool,
+    overwrite: Option<MetadataOverwrite>,
+) -> Result<Game, MetadataError> {
+    let details = get_igdb_game_details(igdb_id).await?;
+
+    let join = |items: &[String]| (!items.is_empty()).then(|| items.join(", "));
+    let metadata = GameMetadata {
+        name: rename.then(|| details.name.clone()),
+        description: details.summary.or(details.storyline),
+        released: details.released,
+        background_image: details.background_image.or(details.cover_image),
+        metacritic: details.aggregated_rating.map(|score| score.round() as i32),
+        rating: details.total_rating.map(|score| score / 20.0),
+        genres: join(&details.genres),
+        platforms: join(&details.platforms),
+        developers: join(&details.developers),
+        publishers: join(&details.publishers),
+    };
+
+    with_db(|conn| {
+        store_metadata(
+            conn,
+            &game_id,
+            (MetadataSource::Igdb, igdb_id),
+            &metadata,
+            &overwrite.unwrap_or_default(),
+        )
+    })
+    .map_err(MetadataError::from)
+}
+
+#[tauri::command]
+pub fn set_igdb_credentials(client_id: String, client_secret: String) -> Result<(), MetadataError> {
+    with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('igdb_client_id', ?1)",
+            params![client_id.trim()],
+        )?;
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('igdb_client_secret', ?1)",
+            params![client_secret.trim()],
+        )?;
+        Ok(())
+    })
+    .map_err(MetadataError::from)?;
+    forget_token();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_igdb_credentials() -> Result<IgdbCredentials, MetadataError> {
+    Ok(get_credentials())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn igdb_records_become_rawg_shaped_metadata() {
+        let record: IgdbRecord = serde_json::from_value(serde_json::json!({
+            "id": 1942,
+            "name": "The Witcher 3: Wild Hunt",
+            "slug": "the-witcher-3-wild-hunt",
+            "summary": "Geralt hunts.",
+            "first_release_date": 1431993600,
+            "cover": { "id": 1, "image_id": "co1wyy" },
+            "screenshots": [{ "id": 2, "image_id": "sc1" }],
+            "aggregated_rating": 92.6,
+            "genres": [{ "id": 12, "name": "Role-playing (RPG)" }],
+            "involved_companies": [
+                { "id": 3, "company": { "id": 4, "name": "CD Projekt RED" }, "developer": true, "publisher": false },
+                { "id": 5, "company": { "id": 6, "name": "Bandai Namco" }, "developer": false, "publisher": true }
+            ]
+        }))
+        .expect("parse record");
+
+        let details = IgdbGameDetails::from(record);
+        assert_eq!(details.released.as_deref(), Some("2015-05-19"));
+        assert_eq!(
+            details.cover_image.as_deref(),
+            Some("https://images.igdb.com/igdb/image/upload/t_cover_big/co1wyy.jpg")
+        );
+        assert_eq!(
+            details.background_image.as_deref(),
+            Some("https://images.igdb.com/igdb/image/upload/t_1080p/sc1.jpg")
+        );
+        assert_eq!(details.developers, ["CD Projekt RED"]);
+        assert_eq!(details.publishers, ["Bandai Namco"]);
+        assert!(details.platforms.is_empty());
+
+        assert_eq!(
+            search_body(r#"Say "Hi" \ Bye"#),
+            format!(
+                r#"search "Say \"Hi\" \\ Bye"; fields {}; limit 10;"#,
+                SEARCH_FIELDS
+            )
+        );
+    }
+
+    #[test]
+    fn tokens_are_reused_until_shortly_before_they_expire() {
+        let now = Instant::now();
+        let token = TwitchToken {
+            access_token: "abc".to_string(),
+            expires_in: 3600,
+        };
+        store_token("client", &token, now);
+        assert_eq!(cached_token("client", now).as_deref(), Some("abc"));
+        assert_eq!(cached_token("other-client", now), None);
+        assert_eq!(
+            cached_token("client", now + Duration::from_secs(3600) - TOKEN_MARGIN),
+            None
+        );
+        forget_token();
+        assert_eq!(cached_token("client", now), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Turns names taken from executables (`DarkSoulsIII`, `witcher3_x64`, `GTA5-Win64-Shipping`)
+//! into something metadata search understands: words split on separators and camel case,
+//! architecture and launcher suffixes dropped, and well-known abbreviations spelled out.
+
+use super::auto_match::match_confidence;
+use super::{search_rawg, MetadataError, RawgGame};
+use crate::database::with_db;
+use rusqlite::params;
+use serde::Serialize;
+
+/// Build and launcher words that say nothing about which game it is.
+const NOISE_WORDS: &[&str] = &[
+    "x64", "x86", "amd64", "win32", "win64", "32bit", "64bit", "dx9", "dx10", "dx11", "dx12",
+    "vulkan", "opengl", "launcher", "shipping", "exe",
+];
+
+/// Abbreviations expanded when a name starts with them.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("cod", "Call of Duty"),
+    ("cs", "Counter-Strike"),
+    ("csgo", "Counter-Strike: Global Offensive"),
+    ("dmc", "Devil May Cry"),
+    ("ff", "Final Fantasy"),
+    ("gta", "Grand Theft Auto"),
+    ("gtav", "Grand Theft Auto V"),
+    ("kcd", "Kingdom Come: Deliverance"),
+    ("mgs", "Metal Gear Solid"),
+    ("nfs", "Need for Speed"),
+    ("pubg", "PlayerUnknown's Battlegrounds"),
+    ("rdr", "Red Dead Redemption"),
+    ("re", "Resident Evil"),
+    ("tes", "The Elder Scrolls"),
+    ("wow", "World of Warcraft"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RankedMatch {
+    #[serde(flatten)]
+    pub game: RawgGame,
+    /// `match_confidence` against the cleaned or the stored name, whichever is higher.
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchSuggestions {
+    /// The cleaned name that was searched.
+    pub query: String,
+    /// Best match first.
+    pub candidates: Vec<RankedMatch>,
+}
+
+fn is_noise(word: &str) -> bool {
+    NOISE_WORDS.contains(&word.to_lowercase().as_str())
+}
+
+/// Splits `DarkSoulsIII` into `Dark Souls III`, `XCOMEnemy` into `XCOM Enemy` and `gta5` into
+/// `gta 5`. A single letter keeps its digits (`F1`).
+fn split_word(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for (index, &c) in chars.iter().enumerate() {
+        if let Some(&prev) = index.checked_sub(1).and_then(|i| chars.get(i)) {
+            let next = chars.get(index + 1).copied();
+            let boundary = (c.is_uppercase() && prev.is_lowercase())
+                || (c.is_uppercase()
+                    && prev.is_uppercase()
+                    && next.is_some_and(char::is_lowercase))
+                || (c.is_ascii_digit() && prev.is_alphabetic() && current.chars().count() > 1);
+            if boundary {
+                parts.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    parts.push(current);
+    parts
+}
+
+fn capitalize(word: &str) -> String {
+    if word.chars().any(char::is_uppercase) {
+        return word.to_string();
+    }
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The name to search metadata for. Falls back to the trimmed name when cleaning would
+/// leave nothing.
+pub fn clean_game_name(name: &str) -> String {
+    let trimmed = name.trim();
+    let stem = match trimmed.len().checked_sub(4) {
+        Some(end)
+            if trimmed.is_char_boundary(end) && trimmed[end..].eq_ignore_ascii_case(".exe") =>
+        {
+            &trimmed[..end]
+        }
+        _ => trimmed,
+    };
+    let stem = stem
+        .replace("x86_64", " ")
+        .replace("x86-64", " ")
+        .replace("X86_64", " ");
+
+    let mut words: Vec<String> = stem
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty() && !is_noise(word))
+        .flat_map(split_word)
+        .filter(|word| !word.is_empty() && !is_noise(word))
+        .collect();
+
+    if let Some(first) = words.first_mut() {
+        let lower = first.to_lowercase();
+        if let Some((_, full)) = ABBREVIATIONS.iter().find(|(short, _)| *short == lower) {
+            *first = full.to_string();
+        }
+    }
+    if words.is_empty() {
+        return trimmed.to_string();
+    }
+    words
+        .iter()
+        .map(|word| capitalize(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Orders `results` by how well they match either name; RAWG's order breaks ties.
+pub fn rank_candidates(name: &str, query: &str, results: Vec<RawgGame>) -> Vec<RankedMatch> {
+    let mut ranked: Vec<RankedMatch> = results
+        .into_iter()
+        .map(|game| RankedMatch {
+            confidence: match_confidence(query, &game.name).max(match_confidence(name, &game.name)),
+            game,
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    ranked
+}
+
+/// Searches RAWG for the game under its cleaned name and ranks the results.
+#[tauri::command]
+pub async fn suggest_metadata_matches(game_id: String) -> Result<MatchSuggestions, MetadataError> {
+    let name: String = with_db(|conn| {
+        conn.query_row(
+            "SELECT name FROM games WHERE id = ?1",
+            params![game_id],
+            |row| row.get(0),
+        )
+    })?;
+    let query = clean_game_name(&name);
+    let results = search_rawg(query.clone()).await?;
+    Ok(MatchSuggestions {
+        candidates: rank_candidates(&name, &query, results),
+        query,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(id: i64, name: &str) -> RawgGame {
+        RawgGame {
+            id,
+            name: name.to_string(),
+            slug: name.to_lowercase().replace(' ', "-"),
+            released: None,
+            background_image: None,
+            metacritic: None,
+            rating: None,
+            ratings_count: None,
+            genres: None,
+            platforms: None,
+        }
+    }
+
+    #[test]
+    fn executable_names_are_cleaned_for_search() {
+        assert_eq!(clean_game_name("DarkSoulsIII"), "Dark Souls III");
+        assert_eq!(clean_game_name("witcher3_x64"), "Witcher 3");
+        assert_eq!(
+            clean_game_name("GTA5-Win64-Shipping.exe"),
+            "Grand Theft Auto 5"
+        );
+        assert_eq!(clean_game_name("rdr2"), "Red Dead Redemption 2");
+        assert_eq!(clean_game_name("XCOMEnemyUnknown"), "XCOM Enemy Unknown");
+        assert_eq!(clean_game_name("Cyberpunk2077 x86_64"), "Cyberpunk 2077");
+        assert_eq!(clean_game_name("F1 2020"), "F1 2020");
+        assert_eq!(clean_game_name("Baldur's Gate 3"), "Baldur's Gate 3");
+        assert_eq!(clean_game_name("Hollow Knight"), "Hollow Knight");
+        assert_eq!(clean_game_name("Launcher"), "Launcher");
+    }
+
+    #[test]
+    fn candidates_are_ranked_by_confidence() {
+        let ranked = rank_candidates(
+            "DarkSoulsIII",
+            "Dark Souls III",
+            vec![
+                game(1, "Dark Souls II"),
+                game(2, "Dark Souls III"),
+                game(3, "Dark Souls: Remastered"),
+            ],
+        );
+        assert_eq!(ranked[0].game.id, 2);
+        assert_eq!(ranked[0].confidence, 1.0);
+        assert!(ranked[1].confidence <= ranked[0].confidence);
+    }
+}
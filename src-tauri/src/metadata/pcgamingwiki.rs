@@ -0,0 +1,386 @@
+//! PCGamingWiki as a source of save and config locations. The `Game data` section of a wiki
+//! page lists them per platform with `{{p|...}}` placeholders, which are turned into the save
+//! locator's path tokens. Locations the user confirms for a game are stored in
+//! `game_wiki_save_paths` and offered as candidate roots by `suggest_save_paths`.
+
+use super::MetadataError;
+use crate::backup::save_locator::GAME_PATH_TOKEN;
+use crate::database::with_db;
+use chrono::Utc;
+use reqwest::Client;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+const PCGW_API: &str = "https://www.pcgamingwiki.com/w/api.php";
+const PCGW_PAGE_BASE: &str = "https://www.pcgamingwiki.com/wiki";
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PcgwLocation {
+    /// The wiki's platform label: `Windows`, `Steam`, `Steam Play (Linux)`, `Linux`, `OS X`, ...
+    pub platform: String,
+    /// The location in save locator syntax, e.g. `<winAppData>/Studio/Game`.
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PcgwLocations {
+    /// Title of the wiki page the locations come from.
+    pub page: String,
+    pub url: String,
+    pub save_locations: Vec<PcgwLocation>,
+    pub config_locations: Vec<PcgwLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParseResponse {
+    parse: Option<ParsedPage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedPage {
+    title: String,
+    wikitext: String,
+}
+
+/// The save locator token for a `{{p|...}}` placeholder. Registry hives and anything the
+/// locator cannot resolve give `None`.
+fn placeholder(name: &str) -> Option<&'static str> {
+    let token = match name.trim().to_lowercase().replace('/', "\\").as_str() {
+        "game" => GAME_PATH_TOKEN,
+        "steam" => "<steam>",
+        // Steam user ids and account names differ per machine.
+        "uid" | "username" => "*",
+        "userprofile" | "linuxhome" | "osxhome" => "<home>",
+        "userprofile\\documents" => "<winDocuments>",
+        "userprofile\\appdata\\locallow" => "<winLocalAppDataLow>",
+        "userprofile\\saved games" => "<winSavedGames>",
+        "appdata" => "<winAppData>",
+        "localappdata" => "<winLocalAppData>",
+        "public" => "<winPublic>",
+        "programdata" | "allusersprofile" => "<winProgramData>",
+        "xdgdatahome" => "<xdgData>",
+        "xdgconfighome" => "<xdgConfig>",
+        _ => return None,
+    };
+    Some(token)
+}
+
+/// Index just past the `}}` closing the template that opens at `start`.
+fn template_end(text: &str, start: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"{{") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"}}") {
+            depth = depth.saturating_sub(1);
+            i += 2;
+            if depth == 0 {
+                return Some(i);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// The `|`-separated arguments of a template body, ignoring `|` inside nested templates
+/// and links.
+fn split_args(body: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut depth = 0i32;
+    let mut from = 0;
+    for (i, byte) in body.bytes().enumerate() {
+        match byte {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth -= 1,
+            b'|' if depth == 0 => {
+                args.push(&body[from..i]);
+                from = i + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(&body[from..]);
+    args
+}
+
+/// Drops comments and `<ref>` footnotes, which often hold `|` and braces of their own.
+fn strip_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    loop {
+        let Some(start) = [rest.find("<!--"), rest.find("<ref")]
+            .into_iter()
+            .flatten()
+            .min()
+        else {
+            out.push_str(rest);
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let end = if tail.starts_with("<!--") {
+            tail.find("-->").map(|i| i + 3)
+        } else {
+            match tail.find('>') {
+                Some(i) if tail[..i].ends_with('/') => Some(i + 1),
+                Some(_) => tail.find("</ref>").map(|i| i + 6),
+                None => None,
+            }
+        };
+        let Some(end) = end else {
+            break;
+        };
+        rest = &tail[end..];
+    }
+    out
+}
+
+/// The separate locations of one field: entries are split by `<br>` tags and new lines.
+fn split_lines(field: &str) -> Vec<&str> {
+    let lower = field.to_ascii_lowercase();
+    let mut parts = Vec::new();
+    let mut from = 0;
+    while let Some(offset) = lower[from..].find("<br") {
+        let start = from + offset;
+        let Some(close) = lower[start..].find('>') else {
+            break;
+        };
+        parts.push(&field[from..start]);
+        from = start + close + 1;
+    }
+    parts.push(&field[from..]);
+    parts.into_iter().flat_map(str::lines).collect()
+}
+
+/// A wiki location in save locator syntax, or `None` for registry keys, unknown
+/// placeholders and plain notes. Other templates (notes, footnotes) are dropped.
+fn convert_path(raw: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = raw;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let end = template_end(rest, start)?;
+        let args = split_args(&rest[start + 2..end - 2]);
+        if args.len() == 2 && matches!(args[0].trim().to_lowercase().as_str(), "p" | "path") {
+            out.push_str(placeholder(args[1])?);
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    let path = out.trim().replace('\\', "/");
+    let path = path.trim_end_matches('/');
+    let looks_like_path = path.contains('/') || path.starts_with('<') || path.starts_with('{');
+    looks_like_path.then(|| path.to_string())
+}
+
+/// Every location of the `{{Game data/<kind>|Platform|...}}` rows on the page.
+fn game_data_locations(wikitext: &str, kind: &str) -> Vec<PcgwLocation> {
+    let text = strip_markup(wikitext);
+    // ASCII lowercasing keeps byte offsets valid for `text`.
+    let lower = text.to_ascii_lowercase();
+    let needle = format!("{{{{game data/{}|", kind);
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(offset) = lower[from..].find(&needle) {
+        let start = from + offset;
+        let Some(end) = template_end(&text, start) else {
+            break;
+        };
+        let args = split_args(&text[start + 2..end - 2]);
+        let platform = args.get(1).map(|p| p.trim()).unwrap_or_default();
+        for field in args.iter().skip(2) {
+            for raw in split_lines(field) {
+                let Some(path) = convert_path(raw) else {
+                    continue;
+                };
+                let location = PcgwLocation {
+                    platform: platform.to_string(),
+                    path,
+                };
+                if !out.contains(&location) {
+                    out.push(location);
+                }
+            }
+        }
+        from = end;
+    }
+    out
+}
+
+fn page_url(title: &str) -> String {
+    format!(
+        "{}/{}",
+        PCGW_PAGE_BASE,
+        urlencoding::encode(&title.replace(' ', "_"))
+    )
+}
+
+async fn get_json(client: &Client, url: &str) -> Result<serde_json::Value, MetadataError> {
+    let response = client
+        .get(url)
+        .header("User-Agent", "Arrancador/0.1.0")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(MetadataError::from_response("PCGamingWiki", &response));
+    }
+
+    Ok(response.json().await?)
+}
+
+/// The title of the best matching wiki page, if any.
+async fn find_page(client: &Client, game_name: &str) -> Result<Option<String>, MetadataError> {
+    let url = format!(
+        "{}?action=opensearch&search={}&limit=1&namespace=0&format=json",
+        PCGW_API,
+        urlencoding::encode(game_name)
+    );
+    // OpenSearch answers `[query, [titles], [descriptions], [urls]]`.
+    let body = get_json(client, &url).await?;
+    Ok(body
+        .get(1)
+        .and_then(|titles| titles.get(0))
+        .and_then(|title| title.as_str())
+        .map(str::to_string))
+}
+
+/// Looks the game up on PCGamingWiki and returns the save and config locations its page
+/// lists, or `None` when there is no page for it.
+#[tauri::command]
+pub async fn get_pcgamingwiki_locations(
+    game_name: String,
+) -> Result<Option<PcgwLocations>, MetadataError> {
+    let client = Client::new();
+    let Some(title) = find_page(&client, &game_name).await? else {
+        return Ok(None);
+    };
+
+    let url = format!(
+        "{}?action=parse&page={}&prop=wikitext&redirects=1&format=json&formatversion=2",
+        PCGW_API,
+        urlencoding::encode(&title)
+    );
+    let response: ParseResponse = serde_json::from_value(get_json(&client, &url).await?)?;
+    let Some(page) = response.parse else {
+        return Ok(None);
+    };
+
+    Ok(Some(PcgwLocations {
+        url: page_url(&page.title),
+        save_locations: game_data_locations(&page.wikitext, "saves"),
+        config_locations: game_data_locations(&page.wikitext, "config"),
+        page: page.title,
+    }))
+}
+
+/// Save locations confirmed for the game, in save locator syntax.
+#[tauri::command]
+pub fn get_pcgamingwiki_save_paths(game_id: String) -> Result<Vec<String>, MetadataError> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT path FROM game_wiki_save_paths WHERE game_id = ?1 ORDER BY created_at, path",
+        )?;
+        let paths = stmt
+            .query_map(params![game_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(paths)
+    })
+    .map_err(MetadataError::from)
+}
+
+/// Replaces the confirmed save locations of the game.
+#[tauri::command]
+pub fn set_pcgamingwiki_save_paths(
+    game_id: String,
+    paths: Vec<String>,
+) -> Result<Vec<String>, MetadataError> {
+    let now = Utc::now().to_rfc3339();
+    with_db(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM game_wiki_save_paths WHERE game_id = ?1",
+            params![game_id],
+        )?;
+        for path in paths
+            .iter()
+            .map(|path| path.trim())
+            .filter(|p| !p.is_empty())
+        {
+            tx.execute(
+                "INSERT OR IGNORE INTO game_wiki_save_paths (game_id, path, created_at)
+                 VALUES (?1, ?2, ?3)",
+                params![game_id, path, now],
+            )?;
+        }
+        tx.commit()
+    })
+    .map_err(MetadataError::from)?;
+    get_pcgamingwiki_save_paths(game_id)
+}
+
+/// Confirmed save locations for save discovery; a database error means none.
+pub fn confirmed_save_paths(game_id: &str) -> Vec<String> {
+    get_pcgamingwiki_save_paths(game_id.to_string()).unwrap_or_else(|e| {
+        eprintln!("Ignoring PCGamingWiki save paths for {}: {}", game_id, e);
+        Vec::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_data_rows_become_save_locator_paths() {
+        let wikitext = r#"
+===Game data===
+====Configuration file(s) location====
+{{Game data|
+{{Game data/config|Windows|{{p|localappdata}}\Fixture Quest\Config\<ref>{{Refcheck|user=A|date=2024}}</ref>}}
+{{Game data/config|Linux|{{p|xdgconfighome}}/fixture-quest/}}
+}}
+
+====Save game data location====
+{{Game data|
+{{Game data/saves|Windows|{{p|userprofile\Documents}}\My Games\Fixture Quest\<br>{{p|game}}\saves\{{p|username}}\}}
+{{Game data/saves|Steam|{{p|steam}}\userdata\{{p|uid}}\4242\remote\}}
+{{Game data/saves|Microsoft Store|{{p|hkcu}}\Software\Fixture Quest}}
+<!-- {{Game data/saves|OS X|}} -->
+{{game data/saves|Linux|{{p|linuxhome}}/.fixture-quest/ {{Note|Only the native build.}}}}
+}}
+"#;
+
+        let saves = game_data_locations(wikitext, "saves");
+        let paths: Vec<(&str, &str)> = saves
+            .iter()
+            .map(|location| (location.platform.as_str(), location.path.as_str()))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                ("Windows", "<winDocuments>/My Games/Fixture Quest"),
+                ("Windows", "{PATHTOGAME}/saves/*"),
+                ("Steam", "<steam>/userdata/*/4242/remote"),
+                ("Linux", "<home>/.fixture-quest"),
+            ]
+        );
+
+        let config = game_data_locations(wikitext, "config");
+        assert_eq!(config.len(), 2);
+        assert_eq!(config[0].path, "<winLocalAppData>/Fixture Quest/Config");
+        assert_eq!(config[1].path, "<xdgConfig>/fixture-quest");
+
+        assert_eq!(convert_path("See the notes"), None);
+        assert_eq!(convert_path("{{p|windir}}\\Game"), None);
+        assert_eq!(
+            page_url("Fixture Quest: Remastered"),
+            "https://www.pcgamingwiki.com/wiki/Fixture_Quest%3A_Remastered"
+        );
+    }
+}
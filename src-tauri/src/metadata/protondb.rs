@@ -0,0 +1,173 @@
+//! Linux and Steam Deck compatibility by Steam app id: the ProtonDB tier and Valve's Steam Deck
+//! verification. Both are stored on the game row, so `get_game` shows them without a request.
+
+use super::MetadataError;
+use crate::database::with_db;
+use crate::domain::games::{Game, LaunchMethod, SteamDeckStatus};
+use crate::services::games::fetch_game_by_id;
+use reqwest::{Client, StatusCode};
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use serde_json::Value;
+
+const PROTONDB_SUMMARY_URL: &str = "https://www.protondb.com/api/v1/reports/summaries";
+const DECK_REPORT_URL: &str =
+    "https://store.steampowered.com/saleaction/ajaxgetdeckappcompatibilityreport";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Compatibility {
+    pub protondb_tier: Option<String>,
+    pub steam_deck_status: Option<SteamDeckStatus>,
+}
+
+#[derive(Deserialize)]
+struct ProtonDbSummary {
+    tier: Option<String>,
+}
+
+/// The Deck status in Steam's compatibility report; `None` when Steam knows no such app.
+fn deck_status_from_report(report: &Value) -> Option<SteamDeckStatus> {
+    report
+        .get("results")?
+        .get("resolved_category")?
+        .as_i64()
+        .map(SteamDeckStatus::from_category)
+}
+
+/// Lowercase tier name; ProtonDB answers `pending` until a game has enough reports.
+fn normalize_tier(tier: Option<String>) -> Option<String> {
+    tier.map(|tier| tier.trim().to_lowercase())
+        .filter(|tier| !tier.is_empty() && tier != "pending")
+}
+
+async fn fetch_protondb_tier(
+    client: &Client,
+    app_id: &str,
+) -> Result<Option<String>, MetadataError> {
+    let url = format!(
+        "{}/{}.json",
+        PROTONDB_SUMMARY_URL,
+        urlencoding::encode(app_id)
+    );
+    let response = client.get(&url).send().await?;
+    // ProtonDB has no summary for games nobody reported on yet.
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(MetadataError::from_response("ProtonDB", &response));
+    }
+    let summary: ProtonDbSummary = response.json().await?;
+    Ok(normalize_tier(summary.tier))
+}
+
+async fn fetch_deck_status(
+    client: &Client,
+    app_id: &str,
+) -> Result<Option<SteamDeckStatus>, MetadataError> {
+    let url = format!("{}?nAppID={}", DECK_REPORT_URL, urlencoding::encode(app_id));
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(MetadataError::from_response("Steam", &response));
+    }
+    let report: Value = response.json().await?;
+    Ok(deck_status_from_report(&report))
+}
+
+pub fn store_compatibility(
+    conn: &Connection,
+    game_id: &str,
+    compatibility: &Compatibility,
+) -> rusqlite::Result<Game> {
+    conn.execute(
+        "UPDATE games SET protondb_tier = ?1, steam_deck_status = ?2 WHERE id = ?3",
+        params![
+            compatibility.protondb_tier,
+            compatibility.steam_deck_status.map(SteamDeckStatus::as_str),
+            game_id
+        ],
+    )?;
+    fetch_game_by_id(conn, game_id)
+}
+
+/// Fetches the ProtonDB tier and Steam Deck status of the game and stores them on it.
+/// `app_id` defaults to the game's `store_id` when it launches through Steam.
+#[tauri::command]
+pub async fn refresh_game_compatibility(
+    game_id: String,
+    app_id: Option<String>,
+) -> Result<Game, MetadataError> {
+    let game = with_db(|conn| fetch_game_by_id(conn, &game_id))?;
+    let app_id = app_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .or_else(|| {
+            game.store_id
+                .clone()
+                .filter(|_| game.launch_method == LaunchMethod::Steam)
+        })
+        .ok_or_else(|| MetadataError::other("The game has no Steam app id"))?;
+
+    let client = Client::new();
+    let (protondb_tier, steam_deck_status) = tokio::join!(
+        fetch_protondb_tier(&client, &app_id),
+        fetch_deck_status(&client, &app_id)
+    );
+    let compatibility = Compatibility {
+        protondb_tier: protondb_tier?,
+        steam_deck_status: steam_deck_status?,
+    };
+    with_db(|conn| store_compatibility(conn, &game_id, &compatibility)).map_err(MetadataError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+
+    #[test]
+    fn compatibility_reports_are_read_and_stored_on_the_game() {
+        let report = serde_json::json!({
+            "success": 1,
+            "results": { "appid": 367520, "resolved_category": 3, "resolved_items": [] }
+        });
+        assert_eq!(
+            deck_status_from_report(&report),
+            Some(SteamDeckStatus::Verified)
+        );
+        let unreviewed = serde_json::json!({ "results": { "resolved_category": 0 } });
+        assert_eq!(
+            deck_status_from_report(&unreviewed),
+            Some(SteamDeckStatus::Unknown)
+        );
+        assert_eq!(
+            deck_status_from_report(&serde_json::json!({ "success": 1 })),
+            None
+        );
+        assert_eq!(
+            normalize_tier(Some("Gold".to_string())).as_deref(),
+            Some("gold")
+        );
+        assert_eq!(normalize_tier(Some("pending".to_string())), None);
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+             VALUES ('g1', 'Hollow Knight', 'a.exe', 'a.exe', '2024-01-01')",
+            [],
+        )
+        .expect("insert game");
+        let game = store_compatibility(
+            &conn,
+            "g1",
+            &Compatibility {
+                protondb_tier: Some("platinum".to_string()),
+                steam_deck_status: Some(SteamDeckStatus::Playable),
+            },
+        )
+        .expect("store compatibility");
+        assert_eq!(game.protondb_tier.as_deref(), Some("platinum"));
+        assert_eq!(game.steam_deck_status, Some(SteamDeckStatus::Playable));
+    }
+}
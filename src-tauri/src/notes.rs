@@ -0,0 +1,42 @@
+use crate::db::GlobalDb;
+pub use crate::domain::notes::{GameLink, GameNote};
+use crate::services::notes as notes_service;
+
+#[tauri::command]
+pub fn get_game_notes(game_id: String) -> Result<Vec<GameNote>, String> {
+    notes_service::get_game_notes(&GlobalDb, game_id)
+}
+
+#[tauri::command]
+pub fn add_game_note(game_id: String, body: String) -> Result<GameNote, String> {
+    notes_service::add_game_note(&GlobalDb, game_id, body)
+}
+
+#[tauri::command]
+pub fn update_game_note(id: String, body: String) -> Result<GameNote, String> {
+    notes_service::update_game_note(&GlobalDb, id, body)
+}
+
+#[tauri::command]
+pub fn delete_game_note(id: String) -> Result<(), String> {
+    notes_service::delete_game_note(&GlobalDb, id)
+}
+
+#[tauri::command]
+pub fn get_game_links(game_id: String) -> Result<Vec<GameLink>, String> {
+    notes_service::get_game_links(&GlobalDb, game_id)
+}
+
+#[tauri::command]
+pub fn add_game_link(
+    game_id: String,
+    url: String,
+    title: Option<String>,
+) -> Result<GameLink, String> {
+    notes_service::add_game_link(&GlobalDb, game_id, url, title)
+}
+
+#[tauri::command]
+pub fn delete_game_link(id: String) -> Result<(), String> {
+    notes_service::delete_game_link(&GlobalDb, id)
+}
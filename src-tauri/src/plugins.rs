@@ -0,0 +1,471 @@
+//! Community plugins: executables and scripts dropped into the plugins folder are run on
+//! app events with a JSON payload on stdin and may answer with actions on stdout.
+//!
+//! Payload: `{"api": 1, "event": "game_added", "data": {...}}`.
+//! Response (optional): `{"actions": [{"type": "notify", "title": "...", "message": "..."}]}`.
+//! Plugins are off until enabled in settings, run one at a time with a stripped
+//! environment, and are killed when they exceed `plugin_timeout_seconds`.
+
+use crate::database::with_db;
+use crate::db::GlobalDb;
+use crate::services::games as games_service;
+use chrono::Utc;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+pub const PLUGIN_API_VERSION: u32 = 1;
+const DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+/// Anything a plugin prints past this is dropped rather than buffered.
+const MAX_OUTPUT_BYTES: u64 = 256 * 1024;
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Variables a plugin keeps from the app's environment; everything else is cleared.
+const INHERITED_ENV: [&str; 8] = [
+    "PATH",
+    "PATHEXT",
+    "SYSTEMROOT",
+    "WINDIR",
+    "COMSPEC",
+    "TEMP",
+    "TMP",
+    "HOME",
+];
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+lazy_static::lazy_static! {
+    static ref LAST_RUNS: Mutex<HashMap<String, PluginRun>> = Mutex::new(HashMap::new());
+    /// Serialises runs so two events never start the same plugin concurrently.
+    static ref RUN_LOCK: Mutex<()> = Mutex::new(());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginKind {
+    Executable,
+    Batch,
+    PowerShell,
+    Python,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginRun {
+    pub event: String,
+    pub at: String,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    /// File name inside the plugins folder; also the settings key suffix.
+    pub id: String,
+    pub path: String,
+    pub kind: PluginKind,
+    pub enabled: bool,
+    pub last_run: Option<PluginRun>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PluginAction {
+    Notify {
+        title: String,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    SetCustomField {
+        game_id: String,
+        field_id: String,
+        value: Option<String>,
+    },
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PluginResponse {
+    #[serde(default)]
+    actions: Vec<PluginAction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PluginNotifyEvent {
+    plugin: String,
+    title: String,
+    message: Option<String>,
+}
+
+pub fn plugins_directory() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("arrancador")
+        .join("plugins")
+}
+
+fn plugin_kind(path: &Path) -> Option<PluginKind> {
+    let ext = path.extension()?.to_string_lossy().to_ascii_lowercase();
+    match ext.as_str() {
+        "exe" => Some(PluginKind::Executable),
+        "bat" | "cmd" => Some(PluginKind::Batch),
+        "ps1" => Some(PluginKind::PowerShell),
+        "py" => Some(PluginKind::Python),
+        _ => None,
+    }
+}
+
+fn enabled_key(id: &str) -> String {
+    format!("plugin_enabled_{}", id)
+}
+
+fn is_enabled(id: &str) -> bool {
+    with_db(|conn| {
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![enabled_key(id)],
+            |row| row.get::<_, String>(0),
+        )
+    })
+    .is_ok_and(|value| value == "true")
+}
+
+fn timeout() -> Duration {
+    let seconds = with_db(|conn| {
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = 'plugin_timeout_seconds'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+    })
+    .ok()
+    .and_then(|value| value.parse::<u64>().ok())
+    .filter(|seconds| *seconds > 0)
+    .unwrap_or(DEFAULT_TIMEOUT_SECONDS);
+    Duration::from_secs(seconds)
+}
+
+/// Supported files directly inside `dir`, sorted by name so plugins run in a stable order.
+fn discover_plugins(dir: &Path) -> Vec<(String, PathBuf, PluginKind)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let kind = plugin_kind(&path)?;
+            let id = path.file_name()?.to_string_lossy().to_string();
+            Some((id, path, kind))
+        })
+        .collect();
+    plugins.sort_by_key(|plugin| plugin.0.to_lowercase());
+    plugins
+}
+
+fn plugin_command(path: &Path, kind: PluginKind) -> Command {
+    let mut command = match kind {
+        PluginKind::Executable => Command::new(path),
+        PluginKind::Batch => {
+            let mut command = Command::new("cmd");
+            command.arg("/C").arg(path);
+            command
+        }
+        PluginKind::PowerShell => {
+            let mut command = Command::new("powershell");
+            command
+                .args([
+                    "-NoProfile",
+                    "-NonInteractive",
+                    "-ExecutionPolicy",
+                    "Bypass",
+                ])
+                .arg("-File")
+                .arg(path);
+            command
+        }
+        PluginKind::Python => {
+            let mut command = Command::new("python");
+            command.arg(path);
+            command
+        }
+    };
+    command.env_clear();
+    for key in INHERITED_ENV {
+        if let Ok(value) = std::env::var(key) {
+            command.env(key, value);
+        }
+    }
+    command.env("ARRANCADOR_PLUGIN_API", PLUGIN_API_VERSION.to_string());
+    if let Some(dir) = path.parent() {
+        command.current_dir(dir);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
+
+fn capture(mut reader: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut out = Vec::new();
+        let _ = (&mut reader).take(MAX_OUTPUT_BYTES).read_to_end(&mut out);
+        // Keep draining so a chatty plugin never blocks on a full pipe.
+        let _ = std::io::copy(&mut reader, &mut std::io::sink());
+        out
+    })
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<bool, String> {
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            return Ok(status.success());
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!(
+                "Плагин не ответил за {} с и был остановлен",
+                timeout.as_secs()
+            ));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn parse_response(stdout: &[u8]) -> Result<Vec<PluginAction>, String> {
+    let text = String::from_utf8_lossy(stdout);
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str::<PluginResponse>(text.trim())
+        .map(|response| response.actions)
+        .map_err(|e| format!("Плагин вернул некорректный JSON: {}", e))
+}
+
+/// Runs one plugin with `payload` on stdin and returns the actions it asked for.
+pub fn run_plugin(
+    path: &Path,
+    kind: PluginKind,
+    payload: &str,
+    timeout: Duration,
+) -> Result<Vec<PluginAction>, String> {
+    let mut child = plugin_command(path, kind)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Не удалось запустить плагин: {}", e))?;
+    let stdout = capture(child.stdout.take().ok_or("Нет stdout плагина")?);
+    let stderr = capture(child.stderr.take().ok_or("Нет stderr плагина")?);
+    if let Some(mut stdin) = child.stdin.take() {
+        // Written on its own thread: a plugin that never reads a payload larger than the pipe
+        // buffer would otherwise block us before the timeout starts. A plugin that exits
+        // without reading stdin is not an error.
+        let payload = payload.to_string();
+        thread::spawn(move || {
+            let _ = stdin.write_all(payload.as_bytes());
+        });
+    }
+
+    let success = wait_with_timeout(&mut child, timeout)?;
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !success {
+        let detail = String::from_utf8_lossy(&stderr).trim().to_string();
+        return Err(if detail.is_empty() {
+            "Плагин завершился с ошибкой".to_string()
+        } else {
+            format!("Плагин завершился с ошибкой: {}", detail)
+        });
+    }
+    parse_response(&stdout)
+}
+
+fn apply_action(plugin: &str, action: PluginAction) -> Result<(), String> {
+    match action {
+        PluginAction::Notify { title, message } => {
+            if let Some(app) = APP_HANDLE.get() {
+                let _ = app.emit(
+                    "plugin:notify",
+                    PluginNotifyEvent {
+                        plugin: plugin.to_string(),
+                        title,
+                        message,
+                    },
+                );
+            }
+            Ok(())
+        }
+        PluginAction::SetCustomField {
+            game_id,
+            field_id,
+            value,
+        } => games_service::set_game_custom_field(&GlobalDb, game_id, field_id, value).map(|_| ()),
+    }
+}
+
+fn run_enabled_plugins(event: &str, payload: &str) {
+    let _guard = RUN_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let timeout = timeout();
+    for (id, path, kind) in discover_plugins(&plugins_directory()) {
+        if !is_enabled(&id) {
+            continue;
+        }
+        let started = Instant::now();
+        let result = run_plugin(&path, kind, payload, timeout).and_then(|actions| {
+            actions
+                .into_iter()
+                .try_for_each(|action| apply_action(&id, action))
+        });
+        if let Err(e) = &result {
+            eprintln!("Plugin {} failed on {}: {}", id, event, e);
+        }
+        let run = PluginRun {
+            event: event.to_string(),
+            at: Utc::now().to_rfc3339(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            error: result.err(),
+        };
+        if let Ok(mut runs) = LAST_RUNS.lock() {
+            runs.insert(id, run);
+        }
+    }
+}
+
+/// Called once at startup; until then (and in tests) `dispatch` does nothing.
+pub fn init(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// Sends `event` to every enabled plugin on a background thread.
+pub fn dispatch(event: &'static str, data: impl Serialize) {
+    if APP_HANDLE.get().is_none() || crate::test_mode::is_enabled() {
+        return;
+    }
+    let payload = serde_json::json!({
+        "api": PLUGIN_API_VERSION,
+        "event": event,
+        "data": data,
+    })
+    .to_string();
+    thread::spawn(move || run_enabled_plugins(event, &payload));
+}
+
+#[tauri::command]
+pub fn list_plugins() -> Result<Vec<PluginInfo>, String> {
+    let runs = LAST_RUNS.lock().map_err(|e| e.to_string())?;
+    Ok(discover_plugins(&plugins_directory())
+        .into_iter()
+        .map(|(id, path, kind)| PluginInfo {
+            enabled: is_enabled(&id),
+            last_run: runs.get(&id).cloned(),
+            path: path.to_string_lossy().to_string(),
+            id,
+            kind,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn set_plugin_enabled(id: String, enabled: bool) -> Result<(), String> {
+    with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            params![enabled_key(&id), if enabled { "true" } else { "false" }],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Creates the plugins folder if needed so the UI can open it.
+#[tauri::command]
+pub fn get_plugins_directory() -> Result<String, String> {
+    let dir = plugins_directory();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn plugins_are_discovered_by_extension_and_answer_with_actions() {
+        let dir = tempdir().expect("tempdir");
+        for name in ["b-sync.ps1", "a-notify.BAT", "readme.txt", "helper.py"] {
+            fs::write(dir.path().join(name), b"").expect("write plugin");
+        }
+        fs::create_dir_all(dir.path().join("lib.exe")).expect("create dir");
+
+        let found: Vec<(String, PluginKind)> = discover_plugins(dir.path())
+            .into_iter()
+            .map(|(id, _, kind)| (id, kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("a-notify.BAT".to_string(), PluginKind::Batch),
+                ("b-sync.ps1".to_string(), PluginKind::PowerShell),
+                ("helper.py".to_string(), PluginKind::Python),
+            ]
+        );
+
+        assert_eq!(parse_response(b"  \n"), Ok(Vec::new()));
+        assert_eq!(
+            parse_response(
+                br#"{"actions":[{"type":"notify","title":"Synced"},
+                    {"type":"set_custom_field","game_id":"g1","field_id":"f1","value":"Steam"}]}"#
+            ),
+            Ok(vec![
+                PluginAction::Notify {
+                    title: "Synced".to_string(),
+                    message: None,
+                },
+                PluginAction::SetCustomField {
+                    game_id: "g1".to_string(),
+                    field_id: "f1".to_string(),
+                    value: Some("Steam".to_string()),
+                },
+            ])
+        );
+        assert!(parse_response(b"not json").is_err());
+        assert!(parse_response(br#"{"actions":[{"type":"format_disk"}]}"#).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn plugins_that_never_read_stdin_still_time_out() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("stuck");
+        fs::write(&path, b"#!/bin/sh\nexec sleep 30\n").expect("write plugin");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod plugin");
+
+        // Far more than a pipe buffer holds.
+        let payload = "x".repeat(4 * 1024 * 1024);
+        let started = Instant::now();
+        let result = run_plugin(
+            &path,
+            PluginKind::Executable,
+            &payload,
+            Duration::from_secs(1),
+        );
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,20 +1,141 @@
-use jwalk::WalkDirGeneric;
-use serde::Serialize;
+use crate::db::GlobalDb;
+use crate::services::exe_info;
+use crate::services::games as games_service;
+use crate::services::net_path::{self, TimedIter};
+use crate::services::scan_cache;
+use crate::services::scan_filter::{ScanFilter, ScanFilterReason};
+use crate::services::scan_groups;
+use chrono::Utc;
+use jwalk::{Parallelism, WalkDirGeneric};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, RwLock,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex,
 };
+use std::thread;
+use std::time::Duration;
 use sysinfo::{ProcessesToUpdate, System};
 use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
 
 lazy_static::lazy_static! {
-    static ref CANCEL_SCAN_FLAG: RwLock<Option<Arc<AtomicBool>>> = RwLock::new(None);
+    /// Cancellation flags of the scans in progress, by scan id.
+    static ref SCAN_SESSIONS: Mutex<HashMap<String, Arc<AtomicBool>>> = Mutex::new(HashMap::new());
 }
 
 #[derive(Serialize)]
 pub struct ExeEntry {
     pub path: String,
     pub file_name: String,
+    /// Set when the file is a ROM for this emulator rather than an executable.
+    pub emulator_id: Option<String>,
+    pub file_size: u64,
+    /// From the PE version resource; always `None` for ROMs.
+    pub product_name: Option<String>,
+    pub company_name: Option<String>,
+    pub file_version: Option<String>,
+    /// Why the file looks like an installer, redistributable or crash handler; only set on
+    /// entries reported because the scan included filtered files.
+    pub filter_reason: Option<ScanFilterReason>,
+    /// The configured scan directory the file was found under; only set by
+    /// `scan_all_directories`.
+    pub source_dir: Option<String>,
+}
+
+/// Folder names that hold tooling or system files rather than games.
+const NOISE_DIRS: [&str; 7] = [
+    "node_modules",
+    "bower_components",
+    "__pycache__",
+    "site-packages",
+    "$recycle.bin",
+    "system volume information",
+    "winsxs",
+];
+
+/// Limits for a scan, mostly for walking whole drives. Missing fields keep the defaults:
+/// unlimited depth and size, links not followed, hidden and noise folders skipped.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ScanOptions {
+    /// Folder levels entered below the scanned folder; `0` only looks at its own files.
+    pub max_depth: Option<usize>,
+    /// Executables smaller or larger than this are left out; ROMs are not size-checked.
+    pub min_exe_bytes: Option<u64>,
+    pub max_exe_bytes: Option<u64>,
+    /// Walk into symlinked folders and junctions.
+    pub follow_links: bool,
+    /// Skip dot-folders and, on Windows, folders marked hidden or system.
+    pub skip_hidden: bool,
+    /// Skip `NOISE_DIRS` such as `node_modules`.
+    pub skip_noise: bool,
+    /// Read threads per scanned folder; network folders default to
+    /// `net_path::NETWORK_THREADS`, local ones to one per core.
+    pub threads: Option<usize>,
+    /// Longest wait for the next file before a folder counts as stalled and is given up;
+    /// network folders default to `net_path::NETWORK_ENTRY_TIMEOUT`, local ones wait forever.
+    pub entry_timeout_secs: Option<u64>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            min_exe_bytes: None,
+            max_exe_bytes: None,
+            follow_links: false,
+            skip_hidden: true,
+            skip_noise: true,
+            threads: None,
+            entry_timeout_secs: None,
+        }
+    }
+}
+
+impl ScanOptions {
+    fn is_noise(&self, name: &str) -> bool {
+        self.skip_noise && NOISE_DIRS.contains(&name.to_lowercase().as_str())
+    }
+
+    /// `metadata` is only read when the hidden/system attributes need checking.
+    fn skips_dir(&self, name: &str, metadata: impl FnOnce() -> Option<std::fs::Metadata>) -> bool {
+        self.is_noise(name)
+            || (self.skip_hidden
+                && (name.starts_with('.') || metadata().is_some_and(|m| is_hidden_or_system(&m))))
+    }
+
+    fn admits_size(&self, bytes: u64) -> bool {
+        self.min_exe_bytes.is_none_or(|min| bytes >= min)
+            && self.max_exe_bytes.is_none_or(|max| bytes <= max)
+    }
+
+    /// Whether a file at `rel_path` (relative to the scanned folder) is within the depth
+    /// limit and outside noise folders. Used where the walk itself cannot be limited.
+    fn admits_path(&self, rel_path: &Path) -> bool {
+        let folders: Vec<String> = rel_path
+            .parent()
+            .into_iter()
+            .flat_map(Path::components)
+            .map(|part| part.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        self.max_depth.is_none_or(|max| folders.len() <= max)
+            && !folders.iter().any(|name| self.is_noise(name))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn is_hidden_or_system(meta: &std::fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+    meta.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_hidden_or_system(_meta: &std::fs::Metadata) -> bool {
+    false
 }
 
 #[derive(Serialize)]
@@ -26,43 +147,307 @@ pub struct ProcessEntry {
     pub gpu_usage: f32,
 }
 
-fn scan_executables_with_callback<P, F>(dir: P, cancel_flag: &AtomicBool, mut on_entry: F) -> usize
+/// A folder a scan could not reach, or that stopped answering partway through.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnreachableFolder {
+    pub path: String,
+    pub error: String,
+}
+
+/// How the walk of one folder ended.
+struct WalkOutcome {
+    count: usize,
+    /// Set when the folder was unreachable or stopped answering before the walk finished.
+    unreachable: Option<UnreachableFolder>,
+}
+
+/// Reports `*.exe` files, and files whose extension is in `rom_extensions` (extension →
+/// emulator id) as ROMs. Files `filter` rejects are skipped unless `include_filtered` is set,
+/// in which case they come with their `filter_reason`. `options` limits the walk. Network
+/// folders are probed first and walked with few threads and a per-entry timeout, so a dead
+/// share ends the walk instead of hanging it.
+fn scan_executables_with_callback<P, F>(
+    dir: P,
+    rom_extensions: &HashMap<String, String>,
+    filter: &ScanFilter,
+    options: &ScanOptions,
+    include_filtered: bool,
+    cancel_flag: &AtomicBool,
+    mut on_entry: F,
+) -> WalkOutcome
 where
-    P: AsRef<std::path::Path>,
+    P: AsRef<Path>,
     F: FnMut(ExeEntry),
 {
-    let walker = WalkDirGeneric::<((), u8)>::new(dir).process_read_dir(|_, _, _, children| {
-        children.iter_mut().for_each(|dir_entry_result| {
-            if let Ok(dir_entry) = dir_entry_result {
-                if dir_entry.file_name().to_string_lossy().starts_with('.') {
-                    dir_entry.read_children_path = None;
+    let root = dir.as_ref().to_path_buf();
+    let unreachable = |error: String| UnreachableFolder {
+        path: root.display().to_string(),
+        error,
+    };
+    if let Err(e) = net_path::ensure_reachable(&root) {
+        return WalkOutcome {
+            count: 0,
+            unreachable: Some(unreachable(e)),
+        };
+    }
+
+    let network = net_path::is_network_path(&root);
+    let options_for_walk = options.clone();
+    let mut walker = WalkDirGeneric::<((), u8)>::new(&root)
+        .skip_hidden(options.skip_hidden)
+        .follow_links(options.follow_links)
+        .process_read_dir(move |_, _, _, children| {
+            children.iter_mut().for_each(|dir_entry_result| {
+                if let Ok(dir_entry) = dir_entry_result {
+                    if dir_entry.file_type().is_dir()
+                        && options_for_walk
+                            .skips_dir(&dir_entry.file_name().to_string_lossy(), || {
+                                dir_entry.metadata().ok()
+                            })
+                    {
+                        dir_entry.read_children_path = None;
+                    }
                 }
-            }
+            });
         });
-    });
+    // Set before `max_depth`, which switches shallow walks to a single thread.
+    if let Some(threads) = options
+        .threads
+        .or(network.then_some(net_path::NETWORK_THREADS))
+    {
+        walker = walker.parallelism(Parallelism::RayonNewPool(threads.max(1)));
+    }
+    if let Some(depth) = options.max_depth {
+        walker = walker.max_depth(depth + 1);
+    }
 
     let mut count = 0;
-    for entry in walker {
-        if cancel_flag.load(Ordering::Relaxed) {
-            break;
-        }
+    let mut visit = |entry: jwalk::Result<jwalk::DirEntry<((), u8)>>| {
         if let Ok(entry) = entry {
             if entry.file_type().is_file() {
-                let path = entry.path();
-                if let Some(ext) = path.extension() {
-                    if ext.eq_ignore_ascii_case("exe") {
-                        let data = ExeEntry {
-                            file_name: entry.file_name().to_string_lossy().into(),
-                            path: path.display().to_string(),
-                        };
-                        on_entry(data);
-                        count += 1;
-                    }
+                if let Some(data) = exe_entry(
+                    &entry.path(),
+                    &root,
+                    rom_extensions,
+                    filter,
+                    options,
+                    include_filtered,
+                ) {
+                    on_entry(data);
+                    count += 1;
                 }
             }
         }
+    };
+    let entry_timeout = options
+        .entry_timeout_secs
+        .map(Duration::from_secs)
+        .or(network.then_some(net_path::NETWORK_ENTRY_TIMEOUT));
+    let stalled = match entry_timeout {
+        Some(timeout) => {
+            let mut entries = TimedIter::new(walker, timeout);
+            while let Some(entry) = entries.next_unless(cancel_flag) {
+                visit(entry);
+            }
+            entries.timed_out().then_some(timeout)
+        }
+        None => {
+            for entry in walker {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                visit(entry);
+            }
+            None
+        }
+    };
+    WalkOutcome {
+        count,
+        unreachable: stalled.map(|timeout| {
+            unreachable(format!(
+                "{} stopped responding for {} s",
+                root.display(),
+                timeout.as_secs()
+            ))
+        }),
+    }
+}
+
+/// The emulator a ROM at `path` belongs to, `Some(None)` for an executable, or `None` when
+/// the file is neither.
+fn candidate_emulator(
+    path: &Path,
+    rom_extensions: &HashMap<String, String>,
+) -> Option<Option<String>> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    let emulator_id = rom_extensions.get(&ext).cloned();
+    (ext == "exe" || emulator_id.is_some()).then_some(emulator_id)
+}
+
+/// Describes the file at `path` for the scan list, or `None` when it is not an executable or
+/// ROM, when an executable is outside the size limits in `options`, or when `filter` rejects
+/// it and `include_filtered` is off.
+fn exe_entry(
+    path: &Path,
+    root: &Path,
+    rom_extensions: &HashMap<String, String>,
+    filter: &ScanFilter,
+    options: &ScanOptions,
+    include_filtered: bool,
+) -> Option<ExeEntry> {
+    let emulator_id = candidate_emulator(path, rom_extensions)?;
+    let file_size = path.metadata().map(|meta| meta.len()).unwrap_or(0);
+    if emulator_id.is_none() && !options.admits_size(file_size) {
+        return None;
+    }
+    let info = emulator_id
+        .is_none()
+        .then(|| exe_info::version_info(path))
+        .flatten();
+    let rel_path = path.strip_prefix(root).unwrap_or(path);
+    let filter_reason = filter.reason(rel_path, info.as_ref());
+    if filter_reason.is_some() && !include_filtered {
+        return None;
+    }
+    Some(ExeEntry {
+        filter_reason,
+        file_size,
+        file_name: path.file_name()?.to_string_lossy().into(),
+        path: path.display().to_string(),
+        emulator_id,
+        product_name: info.as_ref().and_then(|i| i.product_name.clone()),
+        company_name: info.as_ref().and_then(|i| i.company_name.clone()),
+        file_version: info.and_then(|i| i.file_version),
+        source_dir: None,
+    })
+}
+
+/// Lower-cased on Windows, where paths differing only in case are the same file.
+fn dedup_key(path: &str) -> String {
+    if cfg!(target_os = "windows") {
+        path.replace('/', "\\").to_lowercase()
+    } else {
+        path.to_string()
+    }
+}
+
+/// What `scan_directories_with_callback` got through.
+#[derive(Debug, Default)]
+struct ScanSummary {
+    reported: usize,
+    /// Directories walked to the end.
+    completed: Vec<String>,
+    unreachable: Vec<UnreachableFolder>,
+}
+
+/// Walks every directory in `dirs` on its own thread, each with its own options, and reports
+/// each file once, labelled with the directory it was found under; a file reachable from two
+/// nested directories goes to whichever walk reaches it first.
+fn scan_directories_with_callback<F>(
+    dirs: &[(String, ScanOptions)],
+    rom_extensions: &HashMap<String, String>,
+    filter: &ScanFilter,
+    include_filtered: bool,
+    cancel_flag: &AtomicBool,
+    on_entry: F,
+) -> ScanSummary
+where
+    F: Fn(ExeEntry) + Sync,
+{
+    let seen = Mutex::new(HashSet::new());
+    let reported = AtomicUsize::new(0);
+    let summary = Mutex::new(ScanSummary::default());
+    thread::scope(|scope| {
+        for (dir, options) in dirs {
+            let (seen, reported, summary, on_entry) = (&seen, &reported, &summary, &on_entry);
+            scope.spawn(move || {
+                let outcome = scan_executables_with_callback(
+                    dir,
+                    rom_extensions,
+                    filter,
+                    options,
+                    include_filtered,
+                    cancel_flag,
+                    |mut entry| {
+                        let fresh = seen
+                            .lock()
+                            .map(|mut seen| seen.insert(dedup_key(&entry.path)))
+                            .unwrap_or(true);
+                        if fresh {
+                            entry.source_dir = Some(dir.clone());
+                            reported.fetch_add(1, Ordering::Relaxed);
+                            on_entry(entry);
+                        }
+                    },
+                );
+                let Ok(mut summary) = summary.lock() else {
+                    return;
+                };
+                match outcome.unreachable {
+                    Some(folder) => summary.unreachable.push(folder),
+                    None if !cancel_flag.load(Ordering::Relaxed) => {
+                        summary.completed.push(dir.clone())
+                    }
+                    None => {}
+                }
+            });
+        }
+    });
+    ScanSummary {
+        reported: reported.into_inner(),
+        ..summary.into_inner().unwrap_or_default()
+    }
+}
+
+fn rom_extensions() -> HashMap<String, String> {
+    games_service::get_emulators(&GlobalDb)
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|emulator| {
+            emulator
+                .rom_extensions
+                .into_iter()
+                .map(move |ext| (ext, emulator.id.clone()))
+        })
+        .collect()
+}
+
+/// Starts a scan session under `scan_id`, or a fresh id when the caller left it out. Callers
+/// pick their own id so they can subscribe to its events before the scan starts emitting.
+fn register_scan(scan_id: Option<String>) -> Result<(String, Arc<AtomicBool>), String> {
+    let scan_id = scan_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    if scan_id.is_empty()
+        || !scan_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid scan id: {}", scan_id));
+    }
+    let mut sessions = SCAN_SESSIONS.lock().map_err(|e| e.to_string())?;
+    if sessions.contains_key(&scan_id) {
+        return Err(format!("Scan {} is already running", scan_id));
+    }
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    sessions.insert(scan_id.clone(), Arc::clone(&cancel_flag));
+    Ok((scan_id, cancel_flag))
+}
+
+fn finish_scan(scan_id: &str) {
+    if let Ok(mut sessions) = SCAN_SESSIONS.lock() {
+        sessions.remove(scan_id);
+    }
+}
+
+/// `scan:<id>:<name>`, the event a scan session emits its `name` events under.
+fn scan_event(scan_id: &str, name: &str) -> String {
+    format!("scan:{}:{}", scan_id, name)
+}
+
+/// Emits `scan:<id>:group` for every probable game among `entries` (see `scan_groups`).
+fn emit_groups(app: &AppHandle, scan_id: &str, root: &Path, entries: &[ExeEntry]) {
+    for group in scan_groups::group_entries(root, entries) {
+        let _ = app.emit(&scan_event(scan_id, "group"), &group);
     }
-    count
 }
 
 #[tauri::command]
@@ -97,30 +482,331 @@ pub fn get_running_processes() -> Vec<ProcessEntry> {
     processes
 }
 
+/// Streams the executables and ROMs under `dir` as `scan:<id>:entry`, then one
+/// `scan:<id>:group` per probable game and the total as `scan:<id>:done`; returns the scan id.
+/// A network folder that does not answer, or stops answering, is reported as
+/// `scan:<id>:unreachable` before `done`.
 #[tauri::command]
-pub fn scan_executables_stream(app: AppHandle, dir: String) {
-    let cancel_flag = Arc::new(AtomicBool::new(false));
-    {
-        let mut writer = CANCEL_SCAN_FLAG.write().unwrap();
-        *writer = Some(Arc::clone(&cancel_flag));
-    }
+pub fn scan_executables_stream(
+    app: AppHandle,
+    dir: String,
+    include_filtered: Option<bool>,
+    scan_id: Option<String>,
+    options: Option<ScanOptions>,
+) -> Result<String, String> {
+    let (scan_id, cancel_flag) = register_scan(scan_id)?;
+    let session = scan_id.clone();
 
     tauri::async_runtime::spawn_blocking(move || {
-        let count = scan_executables_with_callback(&dir, &cancel_flag, |data| {
-            let _ = app.emit("scan:entry", &data);
-        });
-        let _ = app.emit("scan:done", count);
+        let filter = ScanFilter::load(&GlobalDb);
+        let mut entries = Vec::new();
+        let outcome = scan_executables_with_callback(
+            &dir,
+            &rom_extensions(),
+            &filter,
+            &options.unwrap_or_default(),
+            include_filtered.unwrap_or(false),
+            &cancel_flag,
+            |data| {
+                let _ = app.emit(&scan_event(&session, "entry"), &data);
+                entries.push(data);
+            },
+        );
+        if let Some(folder) = outcome.unreachable {
+            let _ = app.emit(&scan_event(&session, "unreachable"), &folder);
+        }
+        emit_groups(&app, &session, Path::new(&dir), &entries);
+        let _ = app.emit(&scan_event(&session, "done"), outcome.count);
+        finish_scan(&session);
+    });
+    Ok(scan_id)
+}
+
+/// Rescans `dir` reading only the folders that changed since its last scan (see
+/// `scan_cache`). New files stream as `scan:<id>:added` and vanished ones as
+/// `scan:<id>:removed` (the path); the added files are grouped as `scan:<id>:group` and
+/// `scan:<id>:done` carries their number. The first run reports everything as added. Returns
+/// the scan id. The cached walk always covers the whole folder, so depth and noise limits in
+/// `options` filter the reported files. A network folder that does not answer is reported as
+/// `scan:<id>:unreachable` and nothing else is read.
+#[tauri::command]
+pub fn scan_directory_incremental(
+    app: AppHandle,
+    dir: String,
+    include_filtered: Option<bool>,
+    scan_id: Option<String>,
+    options: Option<ScanOptions>,
+) -> Result<String, String> {
+    let (scan_id, cancel_flag) = register_scan(scan_id)?;
+    let session = scan_id.clone();
+    let options = options.unwrap_or_default();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let rom_extensions = rom_extensions();
+        let filter = ScanFilter::load(&GlobalDb);
+        let root = Path::new(&dir);
+        if let Err(error) = net_path::ensure_reachable(root) {
+            let folder = UnreachableFolder {
+                path: dir.clone(),
+                error,
+            };
+            let _ = app.emit(&scan_event(&session, "unreachable"), &folder);
+            let _ = app.emit(&scan_event(&session, "done"), 0);
+            finish_scan(&session);
+            return;
+        }
+        let diff = scan_cache::incremental_scan(
+            &GlobalDb,
+            root,
+            |path| candidate_emulator(path, &rom_extensions).is_some(),
+            &cancel_flag,
+        );
+        let mut added = Vec::new();
+        match diff {
+            Ok(diff) => {
+                for path in &diff.added {
+                    let path = Path::new(path);
+                    if !options.admits_path(path.strip_prefix(root).unwrap_or(path)) {
+                        continue;
+                    }
+                    if let Some(data) = exe_entry(
+                        path,
+                        root,
+                        &rom_extensions,
+                        &filter,
+                        &options,
+                        include_filtered.unwrap_or(false),
+                    ) {
+                        let _ = app.emit(&scan_event(&session, "added"), &data);
+                        added.push(data);
+                    }
+                }
+                for path in &diff.removed {
+                    let _ = app.emit(&scan_event(&session, "removed"), path);
+                }
+            }
+            Err(e) => eprintln!("Incremental scan failed: {}", e),
+        }
+        emit_groups(&app, &session, root, &added);
+        let _ = app.emit(&scan_event(&session, "done"), added.len());
+        finish_scan(&session);
+    });
+    Ok(scan_id)
+}
+
+/// Scans every directory in `scan_directories` at once, each with its own thread count when
+/// one is set. Entries stream as `scan:<id>:entry` with `source_dir` set, followed by
+/// `scan:<id>:group` and `scan:<id>:done` like a single-folder scan; folders that could not be
+/// reached are reported as `scan:<id>:unreachable`, and each directory walked to the end gets
+/// its `last_scanned` updated. Returns the scan id.
+#[tauri::command]
+pub fn scan_all_directories(
+    app: AppHandle,
+    include_filtered: Option<bool>,
+    scan_id: Option<String>,
+    options: Option<ScanOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let dirs: Vec<(String, ScanOptions)> = crate::settings::list_scan_directories()?
+        .into_iter()
+        .map(|dir| {
+            let threads = dir.threads.map(|threads| threads.max(1) as usize);
+            let options = ScanOptions {
+                threads: threads.or(options.threads),
+                ..options.clone()
+            };
+            (dir.path, options)
+        })
+        .collect();
+    let (scan_id, cancel_flag) = register_scan(scan_id)?;
+    let session = scan_id.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let filter = ScanFilter::load(&GlobalDb);
+        let entries = Mutex::new(Vec::new());
+        let summary = scan_directories_with_callback(
+            &dirs,
+            &rom_extensions(),
+            &filter,
+            include_filtered.unwrap_or(false),
+            &cancel_flag,
+            |data| {
+                let _ = app.emit(&scan_event(&session, "entry"), &data);
+                if let Ok(mut entries) = entries.lock() {
+                    entries.push(data);
+                }
+            },
+        );
+        for folder in &summary.unreachable {
+            let _ = app.emit(&scan_event(&session, "unreachable"), folder);
+        }
+        let entries = entries.into_inner().unwrap_or_default();
+        emit_groups(&app, &session, Path::new(""), &entries);
+        if let Err(e) =
+            scan_cache::mark_directories_scanned(&GlobalDb, &summary.completed, Utc::now())
         {
-            let mut writer = CANCEL_SCAN_FLAG.write().unwrap();
-            *writer = None;
+            eprintln!("Failed to record scan time: {}", e);
         }
+        let _ = app.emit(&scan_event(&session, "done"), summary.reported);
+        finish_scan(&session);
     });
+    Ok(scan_id)
 }
 
+/// Stops the scan `scan_id`, or every running scan when no id is given.
 #[tauri::command]
-pub fn cancel_scan() {
-    if let Some(flag) = CANCEL_SCAN_FLAG.read().unwrap().as_ref() {
-        flag.store(true, Ordering::Relaxed);
+pub fn cancel_scan(scan_id: Option<String>) {
+    let Ok(sessions) = SCAN_SESSIONS.lock() else {
+        return;
+    };
+    for (id, flag) in sessions.iter() {
+        if scan_id.as_ref().is_none_or(|wanted| wanted == id) {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn overlapping_directories_report_each_file_once_with_its_source() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let games = root.path().join("Games");
+        let nested = games.join("Indie");
+        let other = root.path().join("Other");
+        fs::create_dir_all(&nested).expect("create nested");
+        fs::create_dir_all(&other).expect("create other");
+        fs::write(games.join("big.exe"), b"exe").expect("write big");
+        fs::write(nested.join("small.exe"), b"exe").expect("write small");
+        fs::write(other.join("third.exe"), b"exe").expect("write third");
+        let dirs: Vec<(String, ScanOptions)> = [&games, &nested, &other]
+            .iter()
+            .map(|dir| (dir.display().to_string(), ScanOptions::default()))
+            .collect();
+
+        let entries = Mutex::new(Vec::new());
+        let summary = scan_directories_with_callback(
+            &dirs,
+            &HashMap::new(),
+            &ScanFilter::new(&[]),
+            false,
+            &AtomicBool::new(false),
+            |entry| entries.lock().unwrap().push(entry),
+        );
+        let entries = entries.into_inner().unwrap();
+
+        assert_eq!(summary.reported, 3);
+        assert_eq!(entries.len(), 3);
+        let mut names: Vec<&str> = entries.iter().map(|e| e.file_name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["big.exe", "small.exe", "third.exe"]);
+        for entry in &entries {
+            let source = entry.source_dir.as_deref().expect("source dir");
+            assert!(
+                entry.path.starts_with(source),
+                "{} in {}",
+                entry.path,
+                source
+            );
+        }
+        assert_eq!(summary.completed.len(), 3);
+        assert!(summary.unreachable.is_empty());
+
+        let summary = scan_directories_with_callback(
+            &dirs,
+            &HashMap::new(),
+            &ScanFilter::new(&[]),
+            false,
+            &AtomicBool::new(true),
+            |_| {},
+        );
+        assert_eq!(summary.reported, 0);
+        assert!(summary.completed.is_empty());
+    }
+
+    #[test]
+    fn scan_options_limit_depth_size_and_noise_folders() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let deep = root.path().join("Studio").join("Game").join("Bin");
+        let noise = root
+            .path()
+            .join("Tools")
+            .join("node_modules")
+            .join("electron");
+        fs::create_dir_all(&deep).expect("create deep");
+        fs::create_dir_all(&noise).expect("create noise");
+        fs::write(root.path().join("top.exe"), vec![0u8; 10]).expect("write top");
+        fs::write(deep.join("deep.exe"), vec![0u8; 10]).expect("write deep");
+        fs::write(noise.join("electron.exe"), vec![0u8; 10]).expect("write noise");
+        fs::write(root.path().join("stub.exe"), b"x").expect("write stub");
+
+        let scan = |options: &ScanOptions| {
+            let mut names = Vec::new();
+            scan_executables_with_callback(
+                root.path(),
+                &HashMap::new(),
+                &ScanFilter::new(&[]),
+                options,
+                false,
+                &AtomicBool::new(false),
+                |entry| names.push(entry.file_name),
+            );
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            scan(&ScanOptions::default()),
+            ["deep.exe", "stub.exe", "top.exe"]
+        );
+        let everything = ScanOptions {
+            skip_noise: false,
+            ..ScanOptions::default()
+        };
+        assert_eq!(
+            scan(&everything),
+            ["deep.exe", "electron.exe", "stub.exe", "top.exe"]
+        );
+        let shallow = ScanOptions {
+            max_depth: Some(2),
+            min_exe_bytes: Some(2),
+            ..ScanOptions::default()
+        };
+        assert_eq!(scan(&shallow), ["top.exe"]);
+        let throttled = ScanOptions {
+            threads: Some(1),
+            entry_timeout_secs: Some(30),
+            ..ScanOptions::default()
+        };
+        assert_eq!(scan(&throttled), ["deep.exe", "stub.exe", "top.exe"]);
+
+        assert!(shallow.admits_path(Path::new("Studio/Game/game.exe")));
+        assert!(!shallow.admits_path(Path::new("Studio/Game/Bin/deep.exe")));
+        assert!(!ScanOptions::default().admits_path(Path::new("Tools/node_modules/a.exe")));
+    }
+
+    #[test]
+    fn cancelling_one_scan_leaves_the_others_running() {
+        let (first, first_flag) = register_scan(None).expect("first scan");
+        let (second, second_flag) =
+            register_scan(Some("client-picked_1".to_string())).expect("second scan");
+        assert_eq!(second, "client-picked_1");
+        assert_ne!(first, second);
+        assert!(register_scan(Some(second.clone())).is_err());
+        assert!(register_scan(Some("scan:*".to_string())).is_err());
+        assert_eq!(scan_event(&second, "entry"), "scan:client-picked_1:entry");
+
+        cancel_scan(Some(first.clone()));
+        assert!(first_flag.load(Ordering::Relaxed));
+        assert!(!second_flag.load(Ordering::Relaxed));
+
+        finish_scan(&first);
+        finish_scan(&second);
+        assert!(register_scan(Some(second)).is_ok());
+        finish_scan("client-picked_1");
     }
 }
 
@@ -151,7 +837,16 @@ mod perf_bench {
 
         let cancel = AtomicBool::new(false);
         let start = Instant::now();
-        let count = scan_executables_with_callback(root.path(), &cancel, |_| {});
+        let count = scan_executables_with_callback(
+            root.path(),
+            &HashMap::new(),
+            &ScanFilter::new(&[]),
+            &ScanOptions::default(),
+            false,
+            &cancel,
+            |_| {},
+        )
+        .count;
         let elapsed = start.elapsed();
 
         println!(
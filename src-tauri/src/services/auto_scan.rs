@@ -0,0 +1,253 @@
+//! Background rescans of the scan folders marked `auto_scan`. Each due folder gets an
+//! incremental scan (see `scan_cache`); executables that appeared since the previous run and
+//! are neither in the library nor filtered out as installers are announced as
+//! `scan:new-games-found`.
+
+use crate::db::Db;
+use crate::services::exe_info;
+use crate::services::games as games_service;
+use crate::services::net_path;
+use crate::services::scan_cache;
+use crate::services::scan_filter::ScanFilter;
+use chrono::{DateTime, Utc};
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+pub const DEFAULT_INTERVAL_HOURS: i64 = 24;
+const CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// Staggered a minute after the install size refresh so both folder walks rarely hit the
+/// drives at once; a scan that came due while the app was closed can wait that long.
+const STARTUP_DELAY: Duration = Duration::from_secs(180);
+
+/// New executables found under one auto-scan folder.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct NewGamesFound {
+    pub source_dir: String,
+    pub paths: Vec<String>,
+}
+
+/// Integer setting `key`, or `default` when unset or unparsable.
+pub(crate) fn setting_i64<D: Db>(db: &D, key: &str, default: i64) -> i64 {
+    db.with_conn(|conn| {
+        conn.query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
+            row.get::<_, String>(0)
+        })
+        .optional()
+    })
+    .ok()
+    .flatten()
+    .and_then(|value| value.trim().parse().ok())
+    .unwrap_or(default)
+}
+
+/// `auto_scan_interval_hours`; `0` turns background scans off.
+fn interval_hours<D: Db>(db: &D) -> i64 {
+    setting_i64(db, "auto_scan_interval_hours", DEFAULT_INTERVAL_HOURS)
+}
+
+/// Lowercase extensions a scan picks up: `exe` plus every emulator's ROM extensions.
+pub fn candidate_extensions<D: Db>(db: &D) -> Result<HashSet<String>, String> {
+    let mut extensions: HashSet<String> = games_service::get_emulators(db)?
+        .into_iter()
+        .flat_map(|emulator| emulator.rom_extensions)
+        .collect();
+    extensions.insert("exe".to_string());
+    Ok(extensions)
+}
+
+/// Auto-scan folders never scanned or last scanned more than the configured interval ago.
+pub fn due_directories<D: Db>(db: &D, now: DateTime<Utc>) -> Result<Vec<String>, String> {
+    let hours = interval_hours(db);
+    if hours <= 0 {
+        return Ok(Vec::new());
+    }
+    let dirs: Vec<(String, Option<String>)> = db
+        .with_conn(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT path, last_scanned FROM scan_directories WHERE auto_scan = 1")?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(rows)
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(dirs
+        .into_iter()
+        .filter(|(_, last_scanned)| {
+            last_scanned
+                .as_deref()
+                .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+                .is_none_or(|scanned| {
+                    now.signed_duration_since(scanned) >= chrono::Duration::hours(hours)
+                })
+        })
+        .map(|(path, _)| path)
+        .collect())
+}
+
+/// Rescans `dir` and returns the executables and ROMs that appeared since its last scan and
+/// are not in the library yet, skipping what the scan filter takes for helpers.
+pub fn find_new_games<D: Db>(
+    db: &D,
+    dir: &str,
+    filter: &ScanFilter,
+) -> Result<Vec<String>, String> {
+    let extensions = candidate_extensions(db)?;
+    let root = Path::new(dir);
+    let diff = scan_cache::incremental_scan(
+        db,
+        root,
+        |path| {
+            path.extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .is_some_and(|ext| extensions.contains(&ext))
+        },
+        &AtomicBool::new(false),
+    )?;
+
+    let library: HashSet<String> = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare("SELECT exe_path FROM games")?;
+            let paths = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<HashSet<_>>>()?;
+            Ok(paths)
+        })
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|path| path.to_lowercase())
+        .collect();
+
+    Ok(diff
+        .added
+        .into_iter()
+        .filter(|path| !library.contains(&path.to_lowercase()))
+        .filter(|path| {
+            let path = Path::new(path);
+            let info = exe_info::version_info(path);
+            let rel_path = path.strip_prefix(root).unwrap_or(path);
+            filter.reason(rel_path, info.as_ref()).is_none()
+        })
+        .collect())
+}
+
+/// Scans every due auto-scan folder once and stamps `last_scanned` on it.
+pub fn run_due_scans<D: Db>(db: &D, now: DateTime<Utc>) -> Result<Vec<NewGamesFound>, String> {
+    let filter = ScanFilter::load(db);
+    let mut found = Vec::new();
+    for dir in due_directories(db, now)? {
+        let root = Path::new(&dir);
+        // Dead network shares are skipped instead of blocking the background thread.
+        if net_path::ensure_reachable(root).is_err() || !root.is_dir() {
+            continue;
+        }
+        let paths = find_new_games(db, &dir, &filter)?;
+        scan_cache::mark_directories_scanned(db, std::slice::from_ref(&dir), now)?;
+        if !paths.is_empty() {
+            found.push(NewGamesFound {
+                source_dir: dir,
+                paths,
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Background job behind `auto_scan`; emits `scan:new-games-found` per folder with new games.
+pub fn start_auto_scan<D: Db + Send + 'static>(db: D, app: AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(STARTUP_DELAY);
+        loop {
+            match run_due_scans(&db, Utc::now()) {
+                Ok(found) => {
+                    for event in found {
+                        let _ = app.emit("scan:new-games-found", event);
+                    }
+                }
+                Err(e) => eprintln!("Auto scan failed: {}", e),
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::{params, Connection};
+    use std::fs;
+
+    #[test]
+    fn due_auto_scan_folders_report_new_games_once() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let games = dir.path().join("Games");
+        fs::create_dir_all(games.join("Owned")).expect("create owned");
+        fs::create_dir_all(games.join("New")).expect("create new");
+        fs::write(games.join("Owned").join("owned.exe"), b"exe").expect("write owned");
+        fs::write(games.join("New").join("new.exe"), b"exe").expect("write new");
+        fs::write(games.join("New").join("unins000.exe"), b"exe").expect("write uninstaller");
+        let manual = dir.path().join("Manual");
+        fs::create_dir_all(&manual).expect("create manual");
+        let games_dir = games.display().to_string();
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+             VALUES ('g1', 'Owned', ?1, 'owned.exe', '2026-01-01T00:00:00Z')",
+            params![games.join("Owned").join("owned.exe").to_string_lossy()],
+        )
+        .expect("insert game");
+        conn.execute(
+            "INSERT INTO scan_directories (path, auto_scan) VALUES (?1, 1), (?2, 0)",
+            params![games_dir, manual.to_string_lossy()],
+        )
+        .expect("insert dirs");
+        let db = ConnectionDb::new(conn);
+
+        let now = Utc::now();
+        assert_eq!(due_directories(&db, now).expect("due"), [games_dir.clone()]);
+        let found = run_due_scans(&db, now).expect("first run");
+        assert_eq!(
+            found,
+            [NewGamesFound {
+                source_dir: games_dir.clone(),
+                paths: vec![games.join("New").join("new.exe").display().to_string()],
+            }]
+        );
+
+        assert!(due_directories(&db, now + chrono::Duration::hours(1))
+            .expect("due")
+            .is_empty());
+        let later = now + chrono::Duration::hours(DEFAULT_INTERVAL_HOURS);
+        assert!(run_due_scans(&db, later).expect("rescan").is_empty());
+
+        fs::write(games.join("later.exe"), b"exe").expect("write later");
+        let found = run_due_scans(&db, later + chrono::Duration::hours(DEFAULT_INTERVAL_HOURS))
+            .expect("third run");
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].paths,
+            [games.join("later.exe").display().to_string()]
+        );
+
+        db.with_conn(|conn| {
+            conn.execute(
+                "UPDATE settings SET value = '0' WHERE key = 'auto_scan_interval_hours'",
+                [],
+            )
+        })
+        .expect("disable");
+        assert!(due_directories(&db, later + chrono::Duration::days(30))
+            .expect("due")
+            .is_empty());
+    }
+}
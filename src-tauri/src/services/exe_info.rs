@@ -0,0 +1,88 @@
+//! What an executable says about itself: the strings of its PE version resource.
+
+use std::path::Path;
+
+/// Strings from an executable's version resource; each is `None` when missing or blank.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExeVersionInfo {
+    pub product_name: Option<String>,
+    pub company_name: Option<String>,
+    pub file_version: Option<String>,
+    pub file_description: Option<String>,
+}
+
+/// Reads the version resource through `version.dll`, using the first language listed in
+/// its translation table.
+#[cfg(target_os = "windows")]
+pub fn version_info(exe: &Path) -> Option<ExeVersionInfo> {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
+    };
+
+    let wide = |s: &str| s.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+    let path: Vec<u16> = exe.as_os_str().encode_wide().chain(Some(0)).collect();
+    let size = unsafe { GetFileVersionInfoSizeW(PCWSTR(path.as_ptr()), None) };
+    if size == 0 {
+        return None;
+    }
+    let mut data = vec![0u8; size as usize];
+    unsafe {
+        GetFileVersionInfoW(
+            PCWSTR(path.as_ptr()),
+            0,
+            size,
+            data.as_mut_ptr() as *mut c_void,
+        )
+    }
+    .ok()?;
+
+    let query = |sub_block: &str| -> Option<(*const c_void, u32)> {
+        let sub_block = wide(sub_block);
+        let mut value: *mut c_void = std::ptr::null_mut();
+        let mut len = 0u32;
+        let found = unsafe {
+            VerQueryValueW(
+                data.as_ptr() as *const c_void,
+                PCWSTR(sub_block.as_ptr()),
+                &mut value,
+                &mut len,
+            )
+        };
+        (found.as_bool() && !value.is_null() && len > 0).then_some((value as *const c_void, len))
+    };
+
+    let (translation, len) = query("\\VarFileInfo\\Translation")?;
+    if len < 4 {
+        return None;
+    }
+    // SAFETY: the translation block is an array of (language, code page) u16 pairs.
+    let (language, code_page) = unsafe {
+        let pair = translation as *const u16;
+        (*pair, *pair.add(1))
+    };
+    let string = |name: &str| -> Option<String> {
+        let (value, len) = query(&format!(
+            "\\StringFileInfo\\{:04x}{:04x}\\{}",
+            language, code_page, name
+        ))?;
+        // SAFETY: string values are `len` UTF-16 units, including the terminator.
+        let units = unsafe { std::slice::from_raw_parts(value as *const u16, len as usize) };
+        let text = String::from_utf16_lossy(units);
+        let text = text.trim_end_matches('\0').trim();
+        (!text.is_empty()).then(|| text.to_string())
+    };
+    Some(ExeVersionInfo {
+        product_name: string("ProductName"),
+        company_name: string("CompanyName"),
+        file_version: string("FileVersion"),
+        file_description: string("FileDescription"),
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn version_info(_exe: &Path) -> Option<ExeVersionInfo> {
+    None
+}
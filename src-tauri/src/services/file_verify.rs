@@ -0,0 +1,237 @@
+//! Install integrity checks. The first run records a SHA-256 for every file under the
+//! game's folder; later runs compare against it to find files that were corrupted, changed
+//! by a mod or removed.
+
+use crate::db::Db;
+use crate::domain::games::FileVerification;
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// `(done, total, current file)`, called while hashing.
+pub type VerifyProgress<'a> = &'a dyn Fn(usize, usize, &str);
+
+/// Hex SHA-256 of the file at `path`.
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 256 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Files under `dir` as `/`-separated relative paths, sorted.
+fn install_files(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let rel = entry.path().strip_prefix(dir).ok()?;
+            let rel = rel
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            Some((rel, entry.path().to_path_buf()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn install_dir<D: Db>(db: &D, game_id: &str) -> Result<PathBuf, String> {
+    let exe_path: String = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT exe_path FROM games WHERE id = ?1",
+                params![game_id],
+                |row| row.get(0),
+            )
+            .optional()
+        })
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Игра не найдена".to_string())?;
+    Path::new(&exe_path)
+        .parent()
+        .filter(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Папка игры не найдена".to_string())
+}
+
+/// Hashes every file of the game's install folder. Without recorded hashes, or with
+/// `rebaseline`, the result becomes the new baseline; otherwise it is compared against it
+/// and the baseline is left as it was.
+pub fn verify_game_files<D: Db>(
+    db: &D,
+    game_id: &str,
+    rebaseline: bool,
+    progress: VerifyProgress,
+) -> Result<FileVerification, String> {
+    let dir = install_dir(db, game_id)?;
+    let baseline: BTreeMap<String, (i64, String, String)> = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT rel_path, size, sha256, recorded_at FROM game_file_hashes
+                 WHERE game_id = ?1",
+            )?;
+            let rows = stmt
+                .query_map(params![game_id], |row| {
+                    Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?)))
+                })?
+                .collect::<rusqlite::Result<BTreeMap<_, _>>>()?;
+            Ok(rows)
+        })
+        .map_err(|e| e.to_string())?;
+
+    let files = install_files(&dir);
+    let total = files.len();
+    let mut current = BTreeMap::new();
+    for (done, (rel, path)) in files.iter().enumerate() {
+        progress(done, total, rel);
+        let size = path.metadata().map(|meta| meta.len() as i64).unwrap_or(0);
+        current.insert(rel.clone(), (size, sha256_file(path)?));
+    }
+    progress(total, total, "");
+
+    if baseline.is_empty() || rebaseline {
+        let recorded_at = Utc::now().to_rfc3339();
+        db.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute(
+                "DELETE FROM game_file_hashes WHERE game_id = ?1",
+                params![game_id],
+            )?;
+            {
+                let mut insert = tx.prepare(
+                    "INSERT INTO game_file_hashes (game_id, rel_path, size, sha256, recorded_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )?;
+                for (rel, (size, hash)) in &current {
+                    insert.execute(params![game_id, rel, size, hash, recorded_at])?;
+                }
+            }
+            tx.commit()
+        })
+        .map_err(|e| e.to_string())?;
+        return Ok(FileVerification {
+            baseline_created: true,
+            baseline_at: recorded_at,
+            checked: total,
+            modified: Vec::new(),
+            missing: Vec::new(),
+            added: Vec::new(),
+        });
+    }
+
+    let baseline_at = baseline
+        .values()
+        .map(|(_, _, recorded_at)| recorded_at.clone())
+        .max()
+        .unwrap_or_default();
+    let modified = current
+        .iter()
+        .filter(|(rel, (size, hash))| {
+            baseline
+                .get(*rel)
+                .is_some_and(|(known_size, known_hash, _)| known_size != size || known_hash != hash)
+        })
+        .map(|(rel, _)| rel.clone())
+        .collect();
+    let missing = baseline
+        .keys()
+        .filter(|rel| !current.contains_key(*rel))
+        .cloned()
+        .collect();
+    let added = current
+        .keys()
+        .filter(|rel| !baseline.contains_key(*rel))
+        .cloned()
+        .collect();
+    Ok(FileVerification {
+        baseline_created: false,
+        baseline_at,
+        checked: total,
+        modified,
+        missing,
+        added,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+    use std::cell::Cell;
+    use std::fs;
+
+    #[test]
+    fn changed_missing_and_new_files_are_reported_against_the_baseline() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let game_dir = dir.path().join("Game");
+        fs::create_dir_all(game_dir.join("data")).expect("create game dir");
+        fs::write(game_dir.join("game.exe"), b"exe").expect("write exe");
+        fs::write(game_dir.join("data").join("pak0.pak"), b"pak0").expect("write pak0");
+        fs::write(game_dir.join("data").join("pak1.pak"), b"pak1").expect("write pak1");
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+             VALUES ('g1', 'Game', ?1, 'game.exe', '2026-01-01T00:00:00Z')",
+            params![game_dir.join("game.exe").to_string_lossy()],
+        )
+        .expect("insert game");
+        let db = ConnectionDb::new(conn);
+
+        let calls = Cell::new(0);
+        let progress = |_: usize, _: usize, _: &str| calls.set(calls.get() + 1);
+        let first = verify_game_files(&db, "g1", false, &progress).expect("baseline");
+        assert!(first.baseline_created);
+        assert_eq!(first.checked, 3);
+        assert_eq!(calls.get(), 4);
+
+        let clean = verify_game_files(&db, "g1", false, &|_, _, _| {}).expect("verify");
+        assert!(!clean.baseline_created);
+        assert!(clean.modified.is_empty() && clean.missing.is_empty() && clean.added.is_empty());
+
+        fs::write(game_dir.join("data").join("pak0.pak"), b"PAK0").expect("corrupt pak0");
+        fs::remove_file(game_dir.join("data").join("pak1.pak")).expect("remove pak1");
+        fs::write(game_dir.join("mod.dll"), b"mod").expect("add mod");
+        let report = verify_game_files(&db, "g1", false, &|_, _, _| {}).expect("verify");
+        assert_eq!(report.modified, ["data/pak0.pak"]);
+        assert_eq!(report.missing, ["data/pak1.pak"]);
+        assert_eq!(report.added, ["mod.dll"]);
+        assert_eq!(report.baseline_at, first.baseline_at);
+
+        let accepted = verify_game_files(&db, "g1", true, &|_, _, _| {}).expect("rebaseline");
+        assert!(accepted.baseline_created);
+        let clean = verify_game_files(&db, "g1", false, &|_, _, _| {}).expect("verify");
+        assert!(clean.modified.is_empty() && clean.missing.is_empty() && clean.added.is_empty());
+
+        fs::write(dir.path().join("abc.txt"), b"abc").expect("write abc");
+        assert_eq!(
+            sha256_file(&dir.path().join("abc.txt")).expect("hash"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
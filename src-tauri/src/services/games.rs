@@ -1,19 +1,30 @@
 use crate::backup::import_existing_backups_for_game;
+use crate::backup::save_locator::GAME_PATH_TOKEN;
 use crate::db::Db;
-use crate::domain::games::{Game, NewGame, UpdateGame};
+use crate::domain::games::{
+    CustomField, CustomFieldType, DeleteGameOptions, Emulator, EmulatorInput, Game, GameCompanion,
+    GameCompanionInput, GamePage, GameQuery, GameSort, GameSummary, InstancePolicy, LaunchMethod,
+    LaunchProfile, LaunchProfileInput, LaunchResult, MetadataField, MetadataSource, MovedGameMatch,
+    NewGame, ShortcutLocation, ShortcutTarget, SortDirection, SteamDeckStatus, UpdateGame,
+};
 use crate::services::fs::FileSystem;
-use chrono::Utc;
-use rusqlite::{params, Result};
+use crate::services::sessions as sessions_service;
+use chrono::{NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
 #[cfg(target_os = "windows")]
 use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fs;
 #[cfg(target_os = "windows")]
 use std::os::windows::ffi::OsStrExt;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
-use sysinfo::{ProcessesToUpdate, System};
+use std::sync::Mutex;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
 use uuid::Uuid;
+use walkdir::WalkDir;
 #[cfg(target_os = "windows")]
 use windows::core::{Interface, PCWSTR};
 #[cfg(target_os = "windows")]
@@ -32,11 +43,17 @@ use windows::Win32::System::Threading::{
 #[cfg(target_os = "windows")]
 use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
 
-const GAME_PATH_TOKEN: &str = "{PATHTOGAME}";
 const GAME_SELECT: &str = "SELECT id, name, exe_path, exe_name, rawg_id, description, released,
              background_image, metacritic, rating, genres, platforms, developers, publishers,
              cover_image, is_favorite, play_count, total_playtime, last_played, date_added,
-             backup_enabled, last_backup, backup_count, save_path, user_rating, user_note
+             backup_enabled, last_backup, backup_count, save_path, user_rating, user_note,
+             restore_on_launch, backup_on_exit, do_not_track, launch_args, working_dir,
+             env_vars, launch_method, store_id, emulator_id, is_hidden, cover_thumbnail,
+             (SELECT outcome IN ('crashed', 'failed') FROM launches
+              WHERE launches.game_id = games.id
+              ORDER BY started_at DESC, launches.id DESC LIMIT 1),
+             install_size, instance_policy, igdb_id, metadata_source, protondb_tier,
+             steam_deck_status, locked_fields
              FROM games";
 
 fn tokenise_save_path_if_possible(
@@ -87,6 +104,39 @@ fn tokenise_save_path_if_possible(
     out
 }
 
+/// `save_path` rewritten as `{PATHTOGAME}\...` when it points inside `old_game_dir`. Compared
+/// as text, since by the time a game is relocated its old folder is usually gone.
+fn tokenise_moved_save_path(save_path: &str, old_game_dir: &str) -> Option<String> {
+    if save_path.contains(GAME_PATH_TOKEN) {
+        return None;
+    }
+    let normalise = |path: &str| {
+        path.replace('\\', "/")
+            .trim_end_matches('/')
+            .to_ascii_lowercase()
+    };
+    let dir = normalise(old_game_dir);
+    let save = normalise(save_path);
+    if dir.is_empty() {
+        return None;
+    }
+    if save == dir {
+        return Some(GAME_PATH_TOKEN.to_string());
+    }
+    if !save.starts_with(&format!("{dir}/")) {
+        return None;
+    }
+    let relative = save_path[dir.len() + 1..].trim_end_matches(['/', '\\']);
+    Some(format!(
+        "{GAME_PATH_TOKEN}{}{relative}",
+        std::path::MAIN_SEPARATOR
+    ))
+}
+
+fn file_size(path: &str) -> Option<i64> {
+    fs::metadata(path).ok().map(|meta| meta.len() as i64)
+}
+
 fn map_game_row(row: &rusqlite::Row) -> Result<Game> {
     Ok(Game {
         id: row.get(0)?,
@@ -104,6 +154,7 @@ fn map_game_row(row: &rusqlite::Row) -> Result<Game> {
         developers: row.get(12)?,
         publishers: row.get(13)?,
         cover_image: row.get(14)?,
+        cover_thumbnail: row.get(36)?,
         is_favorite: row.get::<_, i32>(15)? == 1,
         play_count: row.get(16)?,
         total_playtime: row.get(17)?,
@@ -115,12 +166,137 @@ fn map_game_row(row: &rusqlite::Row) -> Result<Game> {
         save_path: row.get(23)?,
         user_rating: row.get(24)?,
         user_note: row.get(25)?,
+        restore_on_launch: row.get::<_, Option<i32>>(26)?.unwrap_or(1) == 1,
+        backup_on_exit: row.get::<_, Option<i32>>(27)?.unwrap_or(1) == 1,
+        do_not_track: row.get::<_, Option<i32>>(28)?.unwrap_or(0) == 1,
+        launch_args: row.get(29)?,
+        working_dir: row.get(30)?,
+        env_vars: row
+            .get::<_, Option<String>>(31)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        launch_method: row
+            .get::<_, Option<String>>(32)?
+            .map(|method| LaunchMethod::parse(&method))
+            .unwrap_or_default(),
+        store_id: row.get(33)?,
+        emulator_id: row.get(34)?,
+        is_hidden: row.get::<_, Option<i32>>(35)?.unwrap_or(0) == 1,
+        last_launch_failed: row.get::<_, Option<bool>>(37)?.unwrap_or(false),
+        install_size: row.get(38)?,
+        instance_policy: row
+            .get::<_, Option<String>>(39)?
+            .and_then(|policy| InstancePolicy::parse(&policy)),
+        igdb_id: row.get(40)?,
+        metadata_source: row
+            .get::<_, Option<String>>(41)?
+            .and_then(|source| MetadataSource::parse(&source)),
+        protondb_tier: row.get(42)?,
+        steam_deck_status: row
+            .get::<_, Option<String>>(43)?
+            .and_then(|status| SteamDeckStatus::parse(&status)),
+        locked_fields: row
+            .get::<_, Option<String>>(44)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        custom_fields: BTreeMap::new(),
+        tags: Vec::new(),
     })
 }
 
-fn fetch_game_by_id(conn: &rusqlite::Connection, id: &str) -> Result<Game> {
+/// Fills `custom_fields` on already loaded games.
+fn attach_custom_fields(conn: &Connection, games: &mut [Game]) -> Result<()> {
+    let (sql, filter) = match games {
+        [] => return Ok(()),
+        [game] => (
+            "SELECT game_id, field_id, value FROM game_custom_values WHERE game_id = ?1",
+            Some(game.id.clone()),
+        ),
+        _ => (
+            "SELECT game_id, field_id, value FROM game_custom_values",
+            None,
+        ),
+    };
+    let index: HashMap<String, usize> = games
+        .iter()
+        .enumerate()
+        .map(|(i, game)| (game.id.clone(), i))
+        .collect();
+
+    let mut stmt = conn.prepare(sql)?;
+    let map_row = |row: &rusqlite::Row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    };
+    let rows: Vec<(String, String, String)> = match filter {
+        Some(game_id) => stmt
+            .query_map(params![game_id], map_row)?
+            .flatten()
+            .collect(),
+        None => stmt.query_map([], map_row)?.flatten().collect(),
+    };
+    for (game_id, field_id, value) in rows {
+        if let Some(&i) = index.get(&game_id) {
+            games[i].custom_fields.insert(field_id, value);
+        }
+    }
+    Ok(())
+}
+
+/// Fills `tags` on already loaded games.
+fn attach_tags(conn: &Connection, games: &mut [Game]) -> Result<()> {
+    let filter = match games {
+        [] => return Ok(()),
+        [game] => Some(game.id.clone()),
+        _ => None,
+    };
+    let index: HashMap<String, usize> = games
+        .iter()
+        .enumerate()
+        .map(|(i, game)| (game.id.clone(), i))
+        .collect();
+
+    let mut stmt = conn.prepare(
+        "SELECT gt.game_id, gt.tag_id FROM game_tags gt JOIN tags t ON t.id = gt.tag_id
+         WHERE ?1 IS NULL OR gt.game_id = ?1
+         ORDER BY t.name COLLATE NOCASE",
+    )?;
+    let rows: Vec<(String, String)> = stmt
+        .query_map(params![filter], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .flatten()
+        .collect();
+    for (game_id, tag_id) in rows {
+        if let Some(&i) = index.get(&game_id) {
+            games[i].tags.push(tag_id);
+        }
+    }
+    Ok(())
+}
+
+fn query_game_list<P: rusqlite::Params>(
+    conn: &Connection,
+    sql: &str,
+    params: P,
+) -> Result<Vec<Game>> {
+    let mut stmt = conn.prepare(sql)?;
+    let mut games: Vec<Game> = stmt
+        .query_map(params, map_game_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+    attach_custom_fields(conn, &mut games)?;
+    attach_tags(conn, &mut games)?;
+    Ok(games)
+}
+
+pub(crate) fn fetch_game_by_id(conn: &Connection, id: &str) -> Result<Game> {
     let mut stmt = conn.prepare(&format!("{GAME_SELECT} WHERE id = ?1"))?;
-    stmt.query_row(params![id], map_game_row)
+    let mut game = stmt.query_row(params![id], map_game_row)?;
+    attach_custom_fields(conn, std::slice::from_mut(&mut game))?;
+    attach_tags(conn, std::slice::from_mut(&mut game))?;
+    Ok(game)
 }
 
 fn fetch_exe_path<D: Db>(db: &D, id: &str) -> Result<String, String> {
@@ -133,10 +309,10 @@ fn fetch_exe_path<D: Db>(db: &D, id: &str) -> Result<String, String> {
 }
 
 pub fn get_game<D: Db>(db: &D, id: String) -> Result<Option<Game>, String> {
-    db.with_conn(|conn| {
-        let mut stmt = conn.prepare(&format!("{GAME_SELECT} WHERE id = ?1"))?;
-        let game = stmt.query_row(params![id], map_game_row).ok();
-        Ok(game)
+    db.with_conn(|conn| match fetch_game_by_id(conn, &id) {
+        Ok(game) => Ok(Some(game)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
     })
     .map_err(|e| e.to_string())
 }
@@ -148,8 +324,16 @@ pub fn add_game<D: Db>(db: &D, game: NewGame) -> Result<Game, String> {
 
     db.with_conn(|conn| {
         conn.execute(
-            "INSERT INTO games (id, name, exe_path, exe_name, date_added) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![id, game.name, game.exe_path, game.exe_name, date_added],
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, exe_size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                id,
+                game.name,
+                game.exe_path,
+                game.exe_name,
+                date_added,
+                file_size(&game.exe_path)
+            ],
         )?;
         Ok(())
     })
@@ -173,13 +357,22 @@ pub fn add_games_batch<D: Db>(db: &D, games: Vec<NewGame>) -> Result<Vec<Game>,
         let mut inserted = Vec::new();
         {
             let mut stmt = conn.prepare(
-                "INSERT INTO games (id, name, exe_path, exe_name, date_added) VALUES (?1, ?2, ?3, ?4, ?5)",
+                "INSERT INTO games (id, name, exe_path, exe_name, date_added, exe_size)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             )?;
             for game in games {
                 let id = Uuid::new_v4().to_string();
                 let date_added = Utc::now().to_rfc3339();
                 let game_name = game.name.clone();
-                match stmt.execute(params![id, game.name, game.exe_path, game.exe_name, date_added]) {
+                let exe_size = file_size(&game.exe_path);
+                match stmt.execute(params![
+                    id,
+                    game.name,
+                    game.exe_path,
+                    game.exe_name,
+                    date_added,
+                    exe_size
+                ]) {
                     Ok(_) => inserted.push((id, game_name)),
                     Err(e) => {
                         if !e.to_string().contains("UNIQUE constraint failed") {
@@ -214,133 +407,266 @@ pub fn add_games_batch<D: Db>(db: &D, games: Vec<NewGame>) -> Result<Vec<Game>,
     Ok(added_games)
 }
 
-pub fn get_all_games<D: Db>(db: &D) -> Result<Vec<Game>, String> {
+/// The library sorted by name; hidden games only when `include_hidden` is set.
+pub fn get_all_games<D: Db>(db: &D, include_hidden: bool) -> Result<Vec<Game>, String> {
     db.with_conn(|conn| {
-        let mut stmt = conn.prepare(&format!("{GAME_SELECT} ORDER BY name ASC"))?;
+        query_game_list(
+            conn,
+            &format!("{GAME_SELECT} WHERE ?1 = 1 OR COALESCE(is_hidden, 0) = 0 ORDER BY name ASC"),
+            params![include_hidden as i32],
+        )
+    })
+    .map_err(|e| e.to_string())
+}
 
-        let games = stmt
-            .query_map([], map_game_row)?
-            .filter_map(|r| r.ok())
-            .collect();
+/// The visible library without descriptions and metadata, for the initial grid load.
+pub fn get_game_summaries<D: Db>(db: &D) -> Result<Vec<GameSummary>, String> {
+    db.with_conn(|conn| {
+        query_game_summaries(
+            conn,
+            "WHERE COALESCE(is_hidden, 0) = 0 ORDER BY name ASC",
+            [],
+        )
+    })
+    .map_err(|e| e.to_string())
+}
 
-        Ok(games)
+/// Visible games by most recent launch, for the home screen and the tray menu.
+pub fn get_recently_played<D: Db>(db: &D, limit: i64) -> Result<Vec<GameSummary>, String> {
+    db.with_conn(|conn| {
+        query_game_summaries(
+            conn,
+            "WHERE last_played IS NOT NULL AND COALESCE(is_hidden, 0) = 0
+             ORDER BY last_played DESC LIMIT ?1",
+            params![limit.clamp(1, MAX_QUICK_LIST_SIZE)],
+        )
     })
     .map_err(|e| e.to_string())
 }
 
-pub fn get_favorites<D: Db>(db: &D) -> Result<Vec<Game>, String> {
+/// Visible games by total playtime; games never played are left out.
+pub fn get_most_played<D: Db>(db: &D, limit: i64) -> Result<Vec<GameSummary>, String> {
     db.with_conn(|conn| {
-        let mut stmt = conn.prepare(&format!(
-            "{GAME_SELECT} WHERE is_favorite = 1 ORDER BY name ASC"
-        ))?;
+        query_game_summaries(
+            conn,
+            "WHERE total_playtime > 0 AND COALESCE(is_hidden, 0) = 0
+             ORDER BY total_playtime DESC LIMIT ?1",
+            params![limit.clamp(1, MAX_QUICK_LIST_SIZE)],
+        )
+    })
+    .map_err(|e| e.to_string())
+}
 
-        let games = stmt
-            .query_map([], map_game_row)?
-            .filter_map(|r| r.ok())
-            .collect();
+/// Games by measured install size, for the storage view.
+pub fn get_largest_games<D: Db>(db: &D, limit: i64) -> Result<Vec<GameSummary>, String> {
+    db.with_conn(|conn| {
+        query_game_summaries(
+            conn,
+            "WHERE install_size > 0 ORDER BY install_size DESC LIMIT ?1",
+            params![limit.clamp(1, MAX_QUICK_LIST_SIZE)],
+        )
+    })
+    .map_err(|e| e.to_string())
+}
 
-        Ok(games)
+const MAX_QUICK_LIST_SIZE: i64 = 50;
+
+fn query_game_summaries<P: rusqlite::Params>(
+    conn: &Connection,
+    tail: &str,
+    params: P,
+) -> Result<Vec<GameSummary>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, name, cover_image, cover_thumbnail, is_favorite, last_played,
+                total_playtime, install_size
+         FROM games {tail}"
+    ))?;
+    let summaries = stmt
+        .query_map(params, |row| {
+            Ok(GameSummary {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                cover_image: row.get(2)?,
+                cover_thumbnail: row.get(3)?,
+                is_favorite: row.get::<_, i32>(4)? == 1,
+                last_played: row.get(5)?,
+                total_playtime: row.get(6)?,
+                install_size: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(summaries)
+}
+
+pub fn get_favorites<D: Db>(db: &D) -> Result<Vec<Game>, String> {
+    db.with_conn(|conn| {
+        query_game_list(
+            conn,
+            &format!(
+                "{GAME_SELECT} WHERE is_favorite = 1 AND COALESCE(is_hidden, 0) = 0
+                 ORDER BY name ASC"
+            ),
+            [],
+        )
     })
     .map_err(|e| e.to_string())
 }
 
 pub fn update_game<D: Db>(db: &D, update: UpdateGame) -> Result<Game, String> {
+    check_store_update(db, &update)?;
+    db.with_conn(|conn| apply_game_update(conn, &update))
+        .map_err(|e| e.to_string())
+}
+
+/// Applies every update in one transaction; if any of them fails, none are kept.
+pub fn update_games_batch<D: Db>(db: &D, updates: Vec<UpdateGame>) -> Result<Vec<Game>, String> {
+    for update in &updates {
+        check_store_update(db, update)?;
+    }
     db.with_conn(|conn| {
-        let mut updates = Vec::new();
-        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        let tx = conn.unchecked_transaction()?;
+        let games = updates
+            .iter()
+            .map(|update| apply_game_update(&tx, update))
+            .collect::<Result<Vec<_>>>()?;
+        tx.commit()?;
+        Ok(games)
+    })
+    .map_err(|e| e.to_string())
+}
 
-        if let Some(ref name) = update.name {
-            updates.push("name = ?");
-            params_vec.push(Box::new(name.clone()));
-        }
-        if let Some(ref desc) = update.description {
-            updates.push("description = ?");
-            params_vec.push(Box::new(desc.clone()));
-        }
-        if let Some(ref cover) = update.cover_image {
-            updates.push("cover_image = ?");
-            params_vec.push(Box::new(cover.clone()));
-        }
-        if let Some(fav) = update.is_favorite {
-            updates.push("is_favorite = ?");
-            params_vec.push(Box::new(if fav { 1 } else { 0 }));
-        }
-        if let Some(backup) = update.backup_enabled {
-            updates.push("backup_enabled = ?");
-            params_vec.push(Box::new(if backup { 1 } else { 0 }));
-        }
-        if let Some(ref save_path) = update.save_path {
-            updates.push("save_path = ?");
-            let normalized = if save_path.trim().is_empty() {
-                None
-            } else {
-                Some(tokenise_save_path_if_possible(conn, &update.id, save_path))
-            };
-            let checked = normalized.is_some();
-            params_vec.push(Box::new(normalized));
-            updates.push("save_path_checked = ?");
-            params_vec.push(Box::new(if checked { 1 } else { 0 }));
-        }
-        if let Some(rawg_id) = update.rawg_id {
-            updates.push("rawg_id = ?");
-            params_vec.push(Box::new(rawg_id));
-        }
-        if let Some(ref released) = update.released {
-            updates.push("released = ?");
-            params_vec.push(Box::new(released.clone()));
-        }
-        if let Some(ref bg) = update.background_image {
-            updates.push("background_image = ?");
-            params_vec.push(Box::new(bg.clone()));
-        }
-        if let Some(mc) = update.metacritic {
-            updates.push("metacritic = ?");
-            params_vec.push(Box::new(mc));
-        }
-        if let Some(rating) = update.rating {
-            updates.push("rating = ?");
-            params_vec.push(Box::new(rating));
-        }
-        if let Some(ref genres) = update.genres {
-            updates.push("genres = ?");
-            params_vec.push(Box::new(genres.clone()));
-        }
-        if let Some(ref platforms) = update.platforms {
-            updates.push("platforms = ?");
-            params_vec.push(Box::new(platforms.clone()));
-        }
-        if let Some(ref devs) = update.developers {
-            updates.push("developers = ?");
-            params_vec.push(Box::new(devs.clone()));
-        }
-        if let Some(ref pubs) = update.publishers {
-            updates.push("publishers = ?");
-            params_vec.push(Box::new(pubs.clone()));
-        }
-        if let Some(user_rating) = update.user_rating {
-            updates.push("user_rating = ?");
-            params_vec.push(Box::new(user_rating));
-        }
-        if let Some(ref user_note) = update.user_note {
-            updates.push("user_note = ?");
-            params_vec.push(Box::new(user_note.clone()));
-        }
+/// Checks the store id the game ends up with against the launch method it ends up with.
+fn check_store_update<D: Db>(db: &D, update: &UpdateGame) -> Result<(), String> {
+    if update.launch_method.is_none() && update.store_id.is_none() {
+        return Ok(());
+    }
+    let game = db
+        .with_conn(|conn| fetch_game_by_id(conn, &update.id))
+        .map_err(|e| e.to_string())?;
+    let method = update.launch_method.unwrap_or(game.launch_method);
+    let store_id = update
+        .store_id
+        .clone()
+        .or(game.store_id)
+        .unwrap_or_default();
+    validate_store_id(method, &store_id)
+}
 
-        if updates.is_empty() {
-            return fetch_game_by_id(conn, &update.id);
-        }
+fn apply_game_update(conn: &Connection, update: &UpdateGame) -> Result<Game> {
+    let mut updates = Vec::new();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(ref name) = update.name {
+        updates.push("name = ?");
+        params_vec.push(Box::new(name.clone()));
+    }
+    if let Some(ref desc) = update.description {
+        updates.push("description = ?");
+        params_vec.push(Box::new(desc.clone()));
+    }
+    if let Some(ref cover) = update.cover_image {
+        // The thumbnail belongs to the previous cover.
+        updates.push("cover_image = ?");
+        updates.push("cover_thumbnail = NULL");
+        params_vec.push(Box::new(cover.clone()));
+    }
+    if let Some(fav) = update.is_favorite {
+        updates.push("is_favorite = ?");
+        params_vec.push(Box::new(if fav { 1 } else { 0 }));
+    }
+    if let Some(backup) = update.backup_enabled {
+        updates.push("backup_enabled = ?");
+        params_vec.push(Box::new(if backup { 1 } else { 0 }));
+    }
+    if let Some(ref save_path) = update.save_path {
+        updates.push("save_path = ?");
+        let normalized = if save_path.trim().is_empty() {
+            None
+        } else {
+            Some(tokenise_save_path_if_possible(conn, &update.id, save_path))
+        };
+        let checked = normalized.is_some();
+        params_vec.push(Box::new(normalized));
+        updates.push("save_path_checked = ?");
+        params_vec.push(Box::new(if checked { 1 } else { 0 }));
+    }
+    if let Some(rawg_id) = update.rawg_id {
+        updates.push("rawg_id = ?");
+        params_vec.push(Box::new(rawg_id));
+    }
+    if let Some(ref released) = update.released {
+        updates.push("released = ?");
+        params_vec.push(Box::new(released.clone()));
+    }
+    if let Some(ref bg) = update.background_image {
+        updates.push("background_image = ?");
+        params_vec.push(Box::new(bg.clone()));
+    }
+    if let Some(mc) = update.metacritic {
+        updates.push("metacritic = ?");
+        params_vec.push(Box::new(mc));
+    }
+    if let Some(rating) = update.rating {
+        updates.push("rating = ?");
+        params_vec.push(Box::new(rating));
+    }
+    if let Some(ref genres) = update.genres {
+        updates.push("genres = ?");
+        params_vec.push(Box::new(genres.clone()));
+    }
+    if let Some(ref platforms) = update.platforms {
+        updates.push("platforms = ?");
+        params_vec.push(Box::new(platforms.clone()));
+    }
+    if let Some(ref devs) = update.developers {
+        updates.push("developers = ?");
+        params_vec.push(Box::new(devs.clone()));
+    }
+    if let Some(ref pubs) = update.publishers {
+        updates.push("publishers = ?");
+        params_vec.push(Box::new(pubs.clone()));
+    }
+    if let Some(user_rating) = update.user_rating {
+        updates.push("user_rating = ?");
+        params_vec.push(Box::new(user_rating));
+    }
+    if let Some(ref user_note) = update.user_note {
+        updates.push("user_note = ?");
+        params_vec.push(Box::new(user_note.clone()));
+    }
+    if let Some(ref launch_args) = update.launch_args {
+        updates.push("launch_args = ?");
+        params_vec.push(Box::new(non_empty(Some(launch_args.clone()))));
+    }
+    if let Some(ref working_dir) = update.working_dir {
+        updates.push("working_dir = ?");
+        params_vec.push(Box::new(non_empty(Some(working_dir.clone()))));
+    }
+    if let Some(launch_method) = update.launch_method {
+        updates.push("launch_method = ?");
+        params_vec.push(Box::new(launch_method.as_str()));
+    }
+    if let Some(ref store_id) = update.store_id {
+        updates.push("store_id = ?");
+        params_vec.push(Box::new(non_empty(Some(store_id.clone()))));
+    }
+    if let Some(ref emulator_id) = update.emulator_id {
+        updates.push("emulator_id = ?");
+        params_vec.push(Box::new(non_empty(Some(emulator_id.clone()))));
+    }
 
-        params_vec.push(Box::new(update.id.clone()));
+    if updates.is_empty() {
+        return fetch_game_by_id(conn, &update.id);
+    }
 
-        let sql = format!("UPDATE games SET {} WHERE id = ?", updates.join(", "));
+    params_vec.push(Box::new(update.id.clone()));
 
-        let params_refs: Vec<&dyn rusqlite::ToSql> =
-            params_vec.iter().map(|p| p.as_ref()).collect();
-        conn.execute(&sql, params_refs.as_slice())?;
+    let sql = format!("UPDATE games SET {} WHERE id = ?", updates.join(", "));
 
-        fetch_game_by_id(conn, &update.id)
-    })
-    .map_err(|e| e.to_string())
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    conn.execute(&sql, params_refs.as_slice())?;
+
+    fetch_game_by_id(conn, &update.id)
 }
 
 pub fn toggle_favorite<D: Db>(db: &D, id: String) -> Result<Game, String> {
@@ -355,129 +681,1913 @@ pub fn toggle_favorite<D: Db>(db: &D, id: String) -> Result<Game, String> {
     .map_err(|e| e.to_string())
 }
 
-pub fn delete_game<D: Db>(db: &D, id: String) -> Result<(), String> {
+/// Opts a game in or out of the automatic restore-at-launch and backup-on-exit behaviors.
+/// `None` leaves a flag unchanged.
+pub fn set_game_sync_options<D: Db>(
+    db: &D,
+    id: String,
+    restore_on_launch: Option<bool>,
+    backup_on_exit: Option<bool>,
+) -> Result<Game, String> {
     db.with_conn(|conn| {
-        conn.execute("DELETE FROM games WHERE id = ?1", params![id])?;
-        Ok(())
+        conn.execute(
+            "UPDATE games SET restore_on_launch = COALESCE(?1, restore_on_launch),
+                              backup_on_exit = COALESCE(?2, backup_on_exit)
+             WHERE id = ?3",
+            params![
+                restore_on_launch.map(i32::from),
+                backup_on_exit.map(i32::from),
+                id
+            ],
+        )?;
+        fetch_game_by_id(conn, &id)
     })
     .map_err(|e| e.to_string())
 }
 
-pub fn record_game_launch<D: Db>(db: &D, id: String) -> Result<Game, String> {
-    let now = Utc::now().to_rfc3339();
-
+/// Replaces the game's launch environment. Names are trimmed and blank ones dropped.
+pub fn set_game_env_vars<D: Db>(
+    db: &D,
+    id: String,
+    env_vars: BTreeMap<String, String>,
+) -> Result<Game, String> {
+    let mut cleaned = BTreeMap::new();
+    for (name, value) in env_vars {
+        let name = name.trim();
+        if name.is_empty() {
+            continue;
+        }
+        if name.contains('=') || name.contains('\0') || value.contains('\0') {
+            return Err(format!("Недопустимая переменная окружения: {}", name));
+        }
+        cleaned.insert(name.to_string(), value);
+    }
+    let json = (!cleaned.is_empty())
+        .then(|| serde_json::to_string(&cleaned))
+        .transpose()
+        .map_err(|e| e.to_string())?;
     db.with_conn(|conn| {
         conn.execute(
-            "UPDATE games SET play_count = play_count + 1, last_played = ?1 WHERE id = ?2",
-            params![now, id],
+            "UPDATE games SET env_vars = ?1 WHERE id = ?2",
+            params![json, id],
         )?;
-
         fetch_game_by_id(conn, &id)
     })
     .map_err(|e| e.to_string())
 }
 
-pub fn search_games<D: Db>(db: &D, query: String) -> Result<Vec<Game>, String> {
+/// Locks `fields` of the game against applying metadata; other fields are unlocked.
+pub fn set_game_locked_fields<D: Db>(
+    db: &D,
+    id: String,
+    mut fields: Vec<MetadataField>,
+) -> Result<Game, String> {
+    fields.sort();
+    fields.dedup();
+    let json = (!fields.is_empty())
+        .then(|| serde_json::to_string(&fields))
+        .transpose()
+        .map_err(|e| e.to_string())?;
     db.with_conn(|conn| {
-        let pattern = format!("%{}%", query);
-        let mut stmt = conn.prepare(&format!(
-            "{GAME_SELECT} WHERE name LIKE ?1 OR exe_name LIKE ?1 ORDER BY name ASC"
-        ))?;
+        conn.execute(
+            "UPDATE games SET locked_fields = ?1 WHERE id = ?2",
+            params![json, id],
+        )?;
+        fetch_game_by_id(conn, &id)
+    })
+    .map_err(|e| e.to_string())
+}
 
-        let games = stmt
-            .query_map(params![pattern], map_game_row)?
-            .filter_map(|r| r.ok())
-            .collect();
+/// The game's locked metadata fields.
+pub fn locked_fields(conn: &Connection, id: &str) -> Result<Vec<MetadataField>> {
+    let json: Option<String> = conn.query_row(
+        "SELECT locked_fields FROM games WHERE id = ?1",
+        params![id],
+        |row| row.get(0),
+    )?;
+    Ok(json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
 
-        Ok(games)
+/// Turns playtime tracking, session recording and last-played updates off (or back on)
+/// for one game.
+pub fn set_game_do_not_track<D: Db>(
+    db: &D,
+    id: String,
+    do_not_track: bool,
+) -> Result<Game, String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE games SET do_not_track = ?1 WHERE id = ?2",
+            params![do_not_track as i32, id],
+        )?;
+        fetch_game_by_id(conn, &id)
     })
     .map_err(|e| e.to_string())
 }
 
-pub fn game_exists_by_path<D: Db>(db: &D, exe_path: String) -> Result<bool, String> {
+/// Sets what launching does while the game is already running; `None` goes back to the
+/// `running_instance_policy` setting.
+pub fn set_game_instance_policy<D: Db>(
+    db: &D,
+    id: String,
+    policy: Option<InstancePolicy>,
+) -> Result<Game, String> {
     db.with_conn(|conn| {
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM games WHERE exe_path = ?1")?;
-        let count: i32 = stmt.query_row(params![exe_path], |row| row.get(0))?;
-        Ok(count > 0)
+        conn.execute(
+            "UPDATE games SET instance_policy = ?1 WHERE id = ?2",
+            params![policy.map(InstancePolicy::as_str), id],
+        )?;
+        fetch_game_by_id(conn, &id)
     })
     .map_err(|e| e.to_string())
 }
 
-pub fn resolve_shortcut_target(path: String) -> Result<String, String> {
-    let input = PathBuf::from(&path);
-    let is_shortcut = input
-        .extension()
-        .and_then(|s| s.to_str())
-        .map(|s| s.eq_ignore_ascii_case("lnk"))
-        .unwrap_or(false);
-    if !is_shortcut {
-        return Ok(path);
-    }
+/// Picks the provider the game's metadata is searched on; `None` goes back to RAWG.
+pub fn set_game_metadata_source<D: Db>(
+    db: &D,
+    id: String,
+    source: Option<MetadataSource>,
+) -> Result<Game, String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE games SET metadata_source = ?1 WHERE id = ?2",
+            params![source.map(MetadataSource::as_str), id],
+        )?;
+        fetch_game_by_id(conn, &id)
+    })
+    .map_err(|e| e.to_string())
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        let resolved = resolve_shortcut_windows(&input)?;
-        Ok(resolved.to_string_lossy().to_string())
-    }
-    #[cfg(not(target_os = "windows"))]
-    {
-        Ok(path)
+/// Hides a game from the library without touching its playtime, sessions or backups.
+pub fn set_game_hidden<D: Db>(db: &D, id: String, hidden: bool) -> Result<Game, String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE games SET is_hidden = ?1 WHERE id = ?2",
+            params![hidden as i32, id],
+        )?;
+        fetch_game_by_id(conn, &id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Overwrites a game's total playtime, e.g. with the hours another launcher recorded.
+pub fn set_total_playtime<D: Db>(db: &D, game_id: String, seconds: i64) -> Result<Game, String> {
+    if seconds < 0 {
+        return Err("Время игры не может быть отрицательным".to_string());
     }
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let current = current_playtime(&tx, &game_id)?;
+        record_playtime_adjustment(&tx, &game_id, seconds - current, None)?;
+        tx.commit()?;
+        fetch_game_by_id(conn, &game_id)
+    })
+    .map_err(|e| e.to_string())
 }
 
-pub fn is_game_installed<D: Db, F: FileSystem>(db: &D, fs: &F, id: String) -> Result<bool, String> {
-    let exe_path = fetch_exe_path(db, &id)?;
-    Ok(fs.exists(Path::new(&exe_path)))
+/// Adds (or, with negative `seconds`, removes) playtime; the total never drops below zero.
+pub fn add_playtime_adjustment<D: Db>(
+    db: &D,
+    game_id: String,
+    seconds: i64,
+    note: Option<String>,
+) -> Result<Game, String> {
+    let note = note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let current = current_playtime(&tx, &game_id)?;
+        let delta = seconds.max(-current);
+        record_playtime_adjustment(&tx, &game_id, delta, note.as_deref())?;
+        tx.commit()?;
+        fetch_game_by_id(conn, &game_id)
+    })
+    .map_err(|e| e.to_string())
 }
 
-pub fn get_running_instances<D: Db>(db: &D, id: String) -> Result<u32, String> {
-    let exe_path = fetch_exe_path(db, &id)?;
+fn current_playtime(conn: &Connection, game_id: &str) -> Result<i64> {
+    conn.query_row(
+        "SELECT COALESCE(total_playtime, 0) FROM games WHERE id = ?1",
+        params![game_id],
+        |row| row.get(0),
+    )
+}
 
-    let mut sys = System::new_all();
-    sys.refresh_processes(ProcessesToUpdate::All, true);
+/// Applies `delta` to the total and books it in `playtime_daily` on the day the game was
+/// added, so imported hours count towards the stats without inflating the current week.
+fn record_playtime_adjustment(
+    conn: &Connection,
+    game_id: &str,
+    delta: i64,
+    note: Option<&str>,
+) -> Result<()> {
+    if delta == 0 {
+        return Ok(());
+    }
+    let now = Utc::now();
+    let date_added: String = conn.query_row(
+        "SELECT date_added FROM games WHERE id = ?1",
+        params![game_id],
+        |row| row.get(0),
+    )?;
+    let day = chrono::DateTime::parse_from_rfc3339(&date_added)
+        .map(|date| date.date_naive())
+        .unwrap_or_else(|_| now.date_naive())
+        .format("%Y-%m-%d")
+        .to_string();
+
+    conn.execute(
+        "UPDATE games SET total_playtime = MAX(0, COALESCE(total_playtime, 0) + ?1) WHERE id = ?2",
+        params![delta, game_id],
+    )?;
+    conn.execute(
+        "INSERT INTO playtime_daily (game_id, date, seconds)
+         VALUES (?1, ?2, MAX(0, ?3))
+         ON CONFLICT(game_id, date) DO UPDATE SET seconds = MAX(0, seconds + ?3)",
+        params![game_id, day, delta],
+    )?;
+    conn.execute(
+        "INSERT INTO playtime_adjustments (id, game_id, seconds, note, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            Uuid::new_v4().to_string(),
+            game_id,
+            delta,
+            note,
+            now.to_rfc3339()
+        ],
+    )?;
+    Ok(())
+}
 
-    let target = PathBuf::from(exe_path);
-    let mut count = 0u32;
-    for process in sys.processes().values() {
-        if let Some(path) = process.exe() {
-            if paths_match(path, &target) {
-                count += 1;
-            }
-        }
+/// Points a game at its executable's new location after the folder was moved. A save path
+/// inside the old folder is rewritten relative to the game so it moves along.
+pub fn relocate_game<D: Db>(db: &D, id: String, new_exe_path: String) -> Result<Game, String> {
+    let new_path = Path::new(&new_exe_path);
+    if !new_path.is_file() {
+        return Err(format!("Файл не найден: {}", new_exe_path));
     }
+    let exe_name = new_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| new_exe_path.clone());
 
-    Ok(count)
-}
+    db.with_conn(|conn| {
+        let (old_exe_path, save_path): (String, Option<String>) = conn.query_row(
+            "SELECT exe_path, save_path FROM games WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let old_game_dir = Path::new(&old_exe_path)
+            .parent()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let save_path = save_path
+            .as_deref()
+            .and_then(|path| tokenise_moved_save_path(path, &old_game_dir))
+            .or(save_path);
 
-pub fn kill_game_processes<D: Db>(db: &D, id: String) -> Result<u32, String> {
-    let exe_path = fetch_exe_path(db, &id)?;
+        conn.execute(
+            "UPDATE games SET exe_path = ?1, exe_name = ?2, exe_size = ?3, save_path = ?4
+             WHERE id = ?5",
+            params![
+                new_exe_path,
+                exe_name,
+                file_size(&new_exe_path),
+                save_path,
+                id
+            ],
+        )?;
+        fetch_game_by_id(conn, &id)
+    })
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => "Игра не найдена".to_string(),
+        e if e.to_string().contains("UNIQUE constraint failed") => {
+            "Этот файл уже используется другой игрой".to_string()
+        }
+        e => e.to_string(),
+    })
+}
 
-    let mut sys = System::new_all();
-    sys.refresh_processes(ProcessesToUpdate::All, true);
+/// Looks through `scan_dirs` for files named like the executables of games whose `exe_path`
+/// no longer exists. Candidates with the recorded file size come first.
+pub fn find_moved_games<D: Db>(
+    db: &D,
+    scan_dirs: &[String],
+) -> Result<Vec<MovedGameMatch>, String> {
+    let games: Vec<(String, String, String, Option<i64>)> = db
+        .with_conn(|conn| {
+            let mut stmt =
+                conn.prepare("SELECT id, name, exe_path, exe_size FROM games ORDER BY name")?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })?
+                .filter_map(|r| r.ok())
+                .collect();
+            Ok(rows)
+        })
+        .map_err(|e| e.to_string())?;
+
+    let file_key = |path: &Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+    };
+    let missing: Vec<_> = games
+        .iter()
+        .filter(|(_, _, exe_path, _)| !Path::new(exe_path).exists())
+        .collect();
+    let wanted: HashSet<String> = missing
+        .iter()
+        .filter_map(|(_, _, exe_path, _)| file_key(Path::new(exe_path)))
+        .collect();
+    if wanted.is_empty() {
+        return Ok(Vec::new());
+    }
+    let known: HashSet<String> = games
+        .iter()
+        .map(|(_, _, exe_path, _)| exe_path.to_lowercase())
+        .collect();
 
-    let target = PathBuf::from(exe_path);
-    let mut killed = 0u32;
-    for process in sys.processes().values() {
-        if let Some(path) = process.exe() {
-            if paths_match(path, &target) && process.kill() {
-                killed += 1;
+    let mut found: HashMap<String, Vec<(String, i64)>> = HashMap::new();
+    for dir in scan_dirs {
+        let entries = WalkDir::new(dir)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+            })
+            .flatten();
+        for entry in entries {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(key) = file_key(entry.path()).filter(|key| wanted.contains(key)) else {
+                continue;
+            };
+            let path = entry.path().to_string_lossy().to_string();
+            if known.contains(&path.to_lowercase()) {
+                continue;
             }
+            let size = entry.metadata().map(|meta| meta.len() as i64).unwrap_or(-1);
+            found.entry(key).or_default().push((path, size));
         }
     }
 
-    Ok(killed)
+    let mut matches = Vec::new();
+    for (id, name, exe_path, exe_size) in missing {
+        let Some(candidates) = file_key(Path::new(exe_path)).and_then(|key| found.get(&key)) else {
+            continue;
+        };
+        let mut game_matches: Vec<MovedGameMatch> = candidates
+            .iter()
+            .map(|(path, size)| MovedGameMatch {
+                game_id: id.clone(),
+                game_name: name.clone(),
+                old_exe_path: exe_path.clone(),
+                new_exe_path: path.clone(),
+                size_matches: exe_size.map(|expected| expected == *size),
+            })
+            .collect();
+        game_matches.sort_by(|a, b| {
+            b.size_matches
+                .cmp(&a.size_matches)
+                .then_with(|| a.new_exe_path.cmp(&b.new_exe_path))
+        });
+        matches.extend(game_matches);
+    }
+    Ok(matches)
 }
 
-pub async fn launch_game<D: Db + Sync>(db: &D, id: String) -> Result<(), String> {
-    let exe_path = fetch_exe_path(db, &id)?;
-
-    tauri::async_runtime::spawn_blocking(move || spawn_game_process(&exe_path))
-        .await
-        .map_err(|e| e.to_string())??;
+pub fn delete_game<D: Db>(db: &D, id: String, options: DeleteGameOptions) -> Result<(), String> {
+    delete_games_batch(db, vec![id], options).map(|_| ())
+}
 
-    record_game_launch(db, id)?;
+/// Deletes the games in one transaction; returns how many existed. Backup files are removed
+/// only after the rows are gone, so a failed delete never loses a backup. A game whose install
+/// is archived is only deleted with `delete_backups`, which removes the archive too.
+pub fn delete_games_batch<D: Db>(
+    db: &D,
+    ids: Vec<String>,
+    options: DeleteGameOptions,
+) -> Result<usize, String> {
+    // An archived install exists only as its archive; deleting the row would strand it.
+    if !options.delete_backups {
+        let archived: Vec<String> = db
+            .with_conn(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT g.name FROM install_archives a JOIN games g ON g.id = a.game_id
+                     WHERE a.game_id = ?1",
+                )?;
+                let mut names = Vec::new();
+                for id in &ids {
+                    names.extend(stmt.query_map(params![id], |row| row.get(0))?.flatten());
+                }
+                Ok(names)
+            })
+            .map_err(|e| e.to_string())?;
+        if !archived.is_empty() {
+            return Err(format!(
+                "Установка в архиве: {}. Распакуйте её или удалите игру вместе с бэкапами",
+                archived.join(", ")
+            ));
+        }
+    }
 
-    Ok(())
+    let (deleted, backup_paths) = db
+        .with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            let mut deleted = 0;
+            let mut backup_paths: Vec<String> = Vec::new();
+            for id in &ids {
+                if options.delete_backups {
+                    let mut stmt = tx.prepare(
+                        "SELECT backup_path FROM backups WHERE game_id = ?1
+                         UNION ALL
+                         SELECT archive_path FROM install_archives WHERE game_id = ?1",
+                    )?;
+                    backup_paths.extend(
+                        stmt.query_map(params![id], |row| row.get(0))?
+                            .filter_map(|r| r.ok()),
+                    );
+                }
+                deleted += delete_game_rows(&tx, id, options)?;
+            }
+            tx.commit()?;
+            Ok((deleted, backup_paths))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let failed: Vec<String> = backup_paths
+        .iter()
+        .filter_map(|path| remove_backup_file(Path::new(path)).err())
+        .collect();
+    if !failed.is_empty() {
+        return Err(format!(
+            "Игра удалена, но не все бэкапы удалось стереть: {}",
+            failed.join("; ")
+        ));
+    }
+    Ok(deleted)
+}
+
+pub(crate) fn delete_game_rows(
+    conn: &Connection,
+    id: &str,
+    options: DeleteGameOptions,
+) -> Result<usize> {
+    let mut tables = vec![
+        "game_custom_values",
+        "game_tags",
+        "launch_profiles",
+        "game_companions",
+        "game_save_paths",
+        "game_manifest_overrides",
+        "game_wiki_save_paths",
+        "install_archives",
+        "game_file_hashes",
+        "game_notes",
+        "game_links",
+        "backups",
+        "backup_events",
+    ];
+    if options.delete_stats {
+        tables.extend([
+            "play_sessions",
+            "playtime_daily",
+            "playtime_monthly",
+            "playtime_adjustments",
+            "launches",
+        ]);
+    }
+    for table in tables {
+        conn.execute(
+            &format!("DELETE FROM {table} WHERE game_id = ?1"),
+            params![id],
+        )?;
+    }
+    conn.execute("DELETE FROM games WHERE id = ?1", params![id])
+}
+
+/// Removes one backup (a zip or a folder) and its game folder once that is empty.
+fn remove_backup_file(path: &Path) -> Result<(), String> {
+    let removed = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        return Ok(());
+    };
+    removed.map_err(|e| format!("{}: {}", path.display(), e))?;
+    if let Some(parent) = path.parent() {
+        // Fails harmlessly while other backups are still in the folder.
+        let _ = fs::remove_dir(parent);
+    }
+    Ok(())
+}
+
+pub fn record_game_launch<D: Db>(db: &D, id: String) -> Result<Game, String> {
+    let now = Utc::now().to_rfc3339();
+
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE games SET play_count = play_count + 1, last_played = ?1
+             WHERE id = ?2 AND COALESCE(do_not_track, 0) = 0",
+            params![now, id],
+        )?;
+
+        fetch_game_by_id(conn, &id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn search_games<D: Db>(db: &D, query: String) -> Result<Vec<Game>, String> {
+    db.with_conn(|conn| {
+        let pattern = format!("%{}%", query);
+        query_game_list(
+            conn,
+            &format!(
+                "{GAME_SELECT} WHERE (name LIKE ?1 OR exe_name LIKE ?1)
+                 AND COALESCE(is_hidden, 0) = 0 ORDER BY name ASC"
+            ),
+            params![pattern],
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Search with structured filters, including user-defined field values.
+pub fn query_games<D: Db>(db: &D, query: GameQuery) -> Result<Vec<Game>, String> {
+    db.with_conn(|conn| {
+        let (where_clause, params_vec) = game_query_filter(&query);
+        let sql = format!("{GAME_SELECT}{where_clause} ORDER BY name ASC");
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+        query_game_list(conn, &sql, params_refs.as_slice())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// One page of the library in the requested order, with the number of games matching
+/// `filter` overall. Ties fall back to name and id so pages never overlap.
+pub fn get_games_page<D: Db>(
+    db: &D,
+    offset: i64,
+    limit: i64,
+    sort_by: GameSort,
+    direction: SortDirection,
+    filter: GameQuery,
+) -> Result<GamePage, String> {
+    let offset = offset.max(0);
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    let order = direction.as_sql();
+    let order_by = match sort_by {
+        GameSort::Name => format!("name COLLATE NOCASE {order}, id"),
+        // Games never played sort after the played ones in both directions.
+        GameSort::LastPlayed => {
+            format!("last_played IS NULL, last_played {order}, name COLLATE NOCASE, id")
+        }
+        column => format!("{} {order}, name COLLATE NOCASE, id", column.column()),
+    };
+    db.with_conn(|conn| {
+        let (where_clause, mut params_vec) = game_query_filter(&filter);
+        let total: i64 = {
+            let params_refs: Vec<&dyn rusqlite::ToSql> =
+                params_vec.iter().map(|p| p.as_ref()).collect();
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM games{where_clause}"),
+                params_refs.as_slice(),
+                |row| row.get(0),
+            )?
+        };
+        params_vec.push(Box::new(limit));
+        params_vec.push(Box::new(offset));
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+        let games = query_game_list(
+            conn,
+            &format!("{GAME_SELECT}{where_clause} ORDER BY {order_by} LIMIT ? OFFSET ?"),
+            params_refs.as_slice(),
+        )?;
+        Ok(GamePage { games, total })
+    })
+    .map_err(|e| e.to_string())
+}
+
+const MAX_PAGE_SIZE: i64 = 500;
+
+/// The `WHERE` clause (with a leading space, or empty) and its parameters for `query`.
+fn game_query_filter(query: &GameQuery) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(text) = query
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        conditions.push("(name LIKE ? OR exe_name LIKE ?)");
+        let pattern = format!("%{}%", text);
+        params_vec.push(Box::new(pattern.clone()));
+        params_vec.push(Box::new(pattern));
+    }
+    if query.favorites_only {
+        conditions.push("is_favorite = 1");
+    }
+    if !query.include_hidden {
+        conditions.push("COALESCE(is_hidden, 0) = 0");
+    }
+    for filter in &query.custom_fields {
+        params_vec.push(Box::new(filter.field_id.clone()));
+        match &filter.value {
+            Some(value) => {
+                conditions.push(
+                    "EXISTS (SELECT 1 FROM game_custom_values v
+                     WHERE v.game_id = games.id AND v.field_id = ?
+                     AND v.value = ? COLLATE NOCASE)",
+                );
+                params_vec.push(Box::new(value.trim().to_string()));
+            }
+            None => conditions.push(
+                "EXISTS (SELECT 1 FROM game_custom_values v
+                 WHERE v.game_id = games.id AND v.field_id = ?)",
+            ),
+        }
+    }
+    for tag_id in &query.tag_ids {
+        conditions
+            .push("EXISTS (SELECT 1 FROM game_tags t WHERE t.game_id = games.id AND t.tag_id = ?)");
+        params_vec.push(Box::new(tag_id.clone()));
+    }
+    if let Some(genre) = query
+        .genre
+        .as_deref()
+        .map(str::trim)
+        .filter(|g| !g.is_empty())
+    {
+        // Padding with commas keeps "RPG" from matching "Action RPG".
+        conditions.push("(',' || REPLACE(LOWER(COALESCE(genres, '')), ', ', ',') || ',') LIKE ?");
+        params_vec.push(Box::new(format!("%,{},%", genre.to_lowercase())));
+    }
+    if let Some(min) = query.min_playtime {
+        conditions.push("total_playtime >= ?");
+        params_vec.push(Box::new(min));
+    }
+    if let Some(max) = query.max_playtime {
+        conditions.push("total_playtime <= ?");
+        params_vec.push(Box::new(max));
+    }
+
+    if conditions.is_empty() {
+        (String::new(), params_vec)
+    } else {
+        (format!(" WHERE {}", conditions.join(" AND ")), params_vec)
+    }
+}
+
+fn map_custom_field_row(row: &rusqlite::Row) -> Result<CustomField> {
+    Ok(CustomField {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        field_type: CustomFieldType::parse(&row.get::<_, String>(2)?),
+        created_at: row.get(3)?,
+    })
+}
+
+pub fn get_custom_fields<D: Db>(db: &D) -> Result<Vec<CustomField>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, name, field_type, created_at FROM custom_fields ORDER BY name ASC",
+        )?;
+        let fields = stmt
+            .query_map([], map_custom_field_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(fields)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn create_custom_field<D: Db>(
+    db: &D,
+    name: String,
+    field_type: CustomFieldType,
+) -> Result<CustomField, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Название поля не может быть пустым".to_string());
+    }
+    let field = CustomField {
+        id: Uuid::new_v4().to_string(),
+        name,
+        field_type,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO custom_fields (id, name, field_type, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![field.id, field.name, field_type.as_str(), field.created_at],
+        )
+    })
+    .map_err(|e| {
+        if e.to_string().contains("UNIQUE constraint failed") {
+            format!("Поле \"{}\" уже существует", field.name)
+        } else {
+            e.to_string()
+        }
+    })?;
+    Ok(field)
+}
+
+pub fn delete_custom_field<D: Db>(db: &D, id: String) -> Result<(), String> {
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM game_custom_values WHERE field_id = ?1",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM custom_fields WHERE id = ?1", params![id])?;
+        tx.commit()
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Checks a raw value against the field type and returns the form it is stored in.
+fn normalize_custom_value(field_type: CustomFieldType, raw: &str) -> Result<String, String> {
+    let value = raw.trim();
+    match field_type {
+        CustomFieldType::Text => Ok(value.to_string()),
+        CustomFieldType::Number => value
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(|n| n.to_string())
+            .ok_or_else(|| format!("Ожидалось число: {}", value)),
+        CustomFieldType::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .map_err(|_| format!("Ожидалась дата в формате ГГГГ-ММ-ДД: {}", value)),
+        CustomFieldType::Boolean => match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" => Ok("true".to_string()),
+            "false" | "0" | "no" => Ok("false".to_string()),
+            _ => Err(format!("Ожидалось true или false: {}", value)),
+        },
+    }
+}
+
+/// Sets (or clears, when `value` is empty or `None`) one custom field of a game.
+pub fn set_game_custom_field<D: Db>(
+    db: &D,
+    game_id: String,
+    field_id: String,
+    value: Option<String>,
+) -> Result<Game, String> {
+    let field_type: Option<String> = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare("SELECT field_type FROM custom_fields WHERE id = ?1")?;
+            Ok(stmt.query_row(params![field_id], |row| row.get(0)).ok())
+        })
+        .map_err(|e| e.to_string())?;
+    let field_type = field_type.ok_or("Пользовательское поле не найдено")?;
+    let normalized = value
+        .as_deref()
+        .filter(|v| !v.trim().is_empty())
+        .map(|raw| normalize_custom_value(CustomFieldType::parse(&field_type), raw))
+        .transpose()?;
+
+    db.with_conn(|conn| {
+        match &normalized {
+            Some(value) => conn.execute(
+                "INSERT INTO game_custom_values (game_id, field_id, value) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(game_id, field_id) DO UPDATE SET value = excluded.value",
+                params![game_id, field_id, value],
+            )?,
+            None => conn.execute(
+                "DELETE FROM game_custom_values WHERE game_id = ?1 AND field_id = ?2",
+                params![game_id, field_id],
+            )?,
+        };
+        fetch_game_by_id(conn, &game_id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn game_exists_by_path<D: Db>(db: &D, exe_path: String) -> Result<bool, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM games WHERE exe_path = ?1")?;
+        let count: i32 = stmt.query_row(params![exe_path], |row| row.get(0))?;
+        Ok(count > 0)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn resolve_shortcut_target(path: String) -> Result<String, String> {
+    let input = PathBuf::from(&path);
+    let is_shortcut = input
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(|s| s.eq_ignore_ascii_case("lnk"))
+        .unwrap_or(false);
+    if !is_shortcut {
+        return Ok(path);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let resolved = resolve_shortcut_windows(&input)?;
+        Ok(resolved.to_string_lossy().to_string())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(path)
+    }
+}
+
+/// Prefix of the links shortcuts pass to the launcher; the game id follows it.
+pub const LAUNCH_LINK_PREFIX: &str = "arrancador://launch/";
+
+/// The game id in an `arrancador://launch/<id>` argument.
+pub fn parse_launch_link(arg: &str) -> Option<&str> {
+    let id = arg.strip_prefix(LAUNCH_LINK_PREFIX)?.trim_end_matches('/');
+    (!id.is_empty()).then_some(id)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ShortcutSpec {
+    target: PathBuf,
+    args: String,
+    working_dir: PathBuf,
+    icon: PathBuf,
+}
+
+/// Shortcuts go through the launcher unless the caller asks for the executable, so tracking,
+/// backups and environment variables apply. Only a direct game without environment variables
+/// can be started from its executable.
+fn shortcut_spec(
+    game: &Game,
+    target: Option<ShortcutTarget>,
+    launcher_exe: &Path,
+) -> Result<ShortcutSpec, String> {
+    let exe = PathBuf::from(&game.exe_path);
+    let direct_ok = game.launch_method == LaunchMethod::Direct && game.emulator_id.is_none();
+    match target.unwrap_or(ShortcutTarget::Launcher) {
+        ShortcutTarget::Executable if !direct_ok => {
+            Err("Эта игра запускается только через лаунчер".to_string())
+        }
+        ShortcutTarget::Executable if !game.env_vars.is_empty() => {
+            Err("Переменные окружения игры задаются только при запуске через лаунчер".to_string())
+        }
+        ShortcutTarget::Executable => Ok(ShortcutSpec {
+            working_dir: non_empty(game.working_dir.clone())
+                .map(PathBuf::from)
+                .or_else(|| exe.parent().map(Path::to_path_buf))
+                .unwrap_or_default(),
+            args: game.launch_args.clone().unwrap_or_default(),
+            icon: exe.clone(),
+            target: exe,
+        }),
+        ShortcutTarget::Launcher => Ok(ShortcutSpec {
+            target: launcher_exe.to_path_buf(),
+            args: format!("{LAUNCH_LINK_PREFIX}{}", game.id),
+            working_dir: launcher_exe
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            icon: if exe.is_file() {
+                exe
+            } else {
+                launcher_exe.to_path_buf()
+            },
+        }),
+    }
+}
+
+/// File name for a shortcut, with the characters Windows forbids in names replaced.
+fn shortcut_file_name(game_name: &str) -> String {
+    let cleaned: String = game_name
+        .chars()
+        .map(|c| {
+            if c.is_control() || r#"<>:"/\|?*"#.contains(c) {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.').trim();
+    if cleaned.is_empty() {
+        "Game.lnk".to_string()
+    } else {
+        format!("{cleaned}.lnk")
+    }
+}
+
+fn shortcut_dir(location: ShortcutLocation) -> Option<PathBuf> {
+    match location {
+        ShortcutLocation::Desktop => dirs::desktop_dir(),
+        ShortcutLocation::StartMenu => dirs::data_dir().map(|dir| {
+            dir.join("Microsoft")
+                .join("Windows")
+                .join("Start Menu")
+                .join("Programs")
+        }),
+    }
+}
+
+/// Writes a `.lnk` for the game and returns its path; an existing shortcut of the same name
+/// is replaced.
+pub fn create_shortcut<D: Db>(
+    db: &D,
+    game_id: String,
+    location: ShortcutLocation,
+    target: Option<ShortcutTarget>,
+) -> Result<String, String> {
+    let game = db
+        .with_conn(|conn| fetch_game_by_id(conn, &game_id))
+        .map_err(|_| "Игра не найдена".to_string())?;
+    let launcher_exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let spec = shortcut_spec(&game, target, &launcher_exe)?;
+    let dir = shortcut_dir(location).ok_or_else(|| "Папка для ярлыка не найдена".to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(shortcut_file_name(&game.name));
+
+    #[cfg(target_os = "windows")]
+    {
+        write_shortcut_windows(&path, &spec, &game.name)?;
+        Ok(path.to_string_lossy().to_string())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (path, spec);
+        Err("Ярлыки можно создавать только в Windows".to_string())
+    }
+}
+
+pub fn is_game_installed<D: Db, F: FileSystem>(db: &D, fs: &F, id: String) -> Result<bool, String> {
+    let exe_path = fetch_exe_path(db, &id)?;
+    Ok(fs.exists(Path::new(&exe_path)))
+}
+
+/// What a running game looks like in the process list: its executable, and for ROM games
+/// the emulator's executable with the ROM somewhere on its command line.
+#[derive(Debug, Clone)]
+pub struct ProcessTarget {
+    pub exe_path: PathBuf,
+    pub rom_path: Option<PathBuf>,
+}
+
+impl ProcessTarget {
+    pub fn matches(&self, exe: &Path, cmd: &[OsString]) -> bool {
+        if !paths_match(exe, &self.exe_path) {
+            return false;
+        }
+        let Some(rom) = &self.rom_path else {
+            return true;
+        };
+        let rom = rom.to_string_lossy().to_lowercase();
+        cmd.iter()
+            .any(|arg| arg.to_string_lossy().to_lowercase().contains(&rom))
+    }
+}
+
+/// Selects `(id, name, exe_path, do_not_track, rom_path)` with `exe_path` already swapped for
+/// the emulator's on ROM games.
+pub const PROCESS_TARGET_SELECT: &str = "SELECT g.id, g.name, COALESCE(e.exe_path, g.exe_path),
+             COALESCE(g.do_not_track, 0), CASE WHEN e.id IS NULL THEN NULL ELSE g.exe_path END
+             FROM games g LEFT JOIN emulators e ON e.id = g.emulator_id";
+
+fn fetch_process_target<D: Db>(db: &D, id: &str) -> Result<ProcessTarget, String> {
+    db.with_conn(|conn| {
+        conn.query_row(
+            &format!("{PROCESS_TARGET_SELECT} WHERE g.id = ?1"),
+            params![id],
+            |row| {
+                Ok(ProcessTarget {
+                    exe_path: PathBuf::from(row.get::<_, String>(2)?),
+                    rom_path: row.get::<_, Option<String>>(4)?.map(PathBuf::from),
+                })
+            },
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn running_pids(target: &ProcessTarget) -> Vec<u32> {
+    let mut sys = System::new_all();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+
+    sys.processes()
+        .values()
+        .filter(|process| {
+            process
+                .exe()
+                .is_some_and(|path| target.matches(path, process.cmd()))
+        })
+        .map(|process| process.pid().as_u32())
+        .collect()
+}
+
+pub fn get_running_instances<D: Db>(db: &D, id: String) -> Result<u32, String> {
+    let target = fetch_process_target(db, &id)?;
+    Ok(running_pids(&target).len() as u32)
+}
+
+/// Returned by a launch refused under [`InstancePolicy::Refuse`]; the frontend matches on it.
+pub const ALREADY_RUNNING_MESSAGE: &str = "Игра уже запущена";
+
+fn instance_policy<D: Db>(db: &D, game: &Game) -> InstancePolicy {
+    if let Some(policy) = game.instance_policy {
+        return policy;
+    }
+    db.with_conn(|conn| {
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = 'running_instance_policy'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+    })
+    .ok()
+    .flatten()
+    .and_then(|value| InstancePolicy::parse(value.trim()))
+    .unwrap_or_default()
+}
+
+/// Applies the game's instance policy before a launch. `Some` means the launch is settled
+/// without starting anything: the running copy was focused (or had no window to focus).
+fn check_running_instance<D: Db>(db: &D, game: &Game) -> Result<Option<LaunchResult>, String> {
+    let policy = instance_policy(db, game);
+    if policy == InstancePolicy::Allow {
+        return Ok(None);
+    }
+    let pids = running_pids(&fetch_process_target(db, &game.id)?);
+    if pids.is_empty() {
+        return Ok(None);
+    }
+    if policy == InstancePolicy::Refuse {
+        return Err(ALREADY_RUNNING_MESSAGE.to_string());
+    }
+    Ok(Some(if focus_process_window(&pids) {
+        LaunchResult::Focused
+    } else {
+        LaunchResult::AlreadyRunning
+    }))
+}
+
+/// Brings the first visible top-level window owned by one of `pids` to the front, restoring
+/// it when minimised.
+#[cfg(target_os = "windows")]
+fn focus_process_window(pids: &[u32]) -> bool {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowThreadProcessId, IsIconic, IsWindowVisible, SetForegroundWindow,
+        ShowWindow, SW_RESTORE,
+    };
+
+    struct Search<'a> {
+        pids: &'a [u32],
+        found: Option<HWND>,
+    }
+
+    unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam.0 as *mut Search);
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if search.pids.contains(&pid) && IsWindowVisible(hwnd).as_bool() {
+            search.found = Some(hwnd);
+            return BOOL(0);
+        }
+        BOOL(1)
+    }
+
+    let mut search = Search { pids, found: None };
+    unsafe {
+        let _ = EnumWindows(Some(visit), LPARAM(&mut search as *mut Search as isize));
+        let Some(hwnd) = search.found else {
+            return false;
+        };
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        SetForegroundWindow(hwnd).as_bool()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn focus_process_window(_pids: &[u32]) -> bool {
+    false
+}
+
+pub fn kill_game_processes<D: Db>(db: &D, id: String) -> Result<u32, String> {
+    let target = fetch_process_target(db, &id)?;
+
+    let mut sys = System::new_all();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+
+    let mut killed = 0u32;
+    for process in sys.processes().values() {
+        if let Some(path) = process.exe() {
+            if target.matches(path, process.cmd()) && process.kill() {
+                killed += 1;
+            }
+        }
+    }
+    if killed > 0 {
+        remember_killed_game(&id);
+    }
+
+    Ok(killed)
+}
+
+/// Starts a game through `profile_id`, or through its default profile when none is given;
+/// games without a default profile start their own executable. A game that is already
+/// running is handled by its [`InstancePolicy`] first.
+pub async fn launch_game<D: Db + Sync>(
+    db: &D,
+    id: String,
+    profile_id: Option<String>,
+) -> Result<LaunchResult, String> {
+    let profile_id = match profile_id {
+        Some(profile_id) => Some(profile_id),
+        None => db
+            .with_conn(|conn| {
+                conn.query_row(
+                    "SELECT id FROM launch_profiles WHERE game_id = ?1 AND is_default = 1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()
+            })
+            .map_err(|e| e.to_string())?,
+    };
+    if let Some(profile_id) = profile_id {
+        return launch_game_with_profile(db, id, profile_id).await;
+    }
+
+    let game = db
+        .with_conn(|conn| fetch_game_by_id(conn, &id))
+        .map_err(|e| e.to_string())?;
+    if let Some(settled) = check_running_instance(db, &game)? {
+        return Ok(settled);
+    }
+    start_companions(db, &id).await?;
+
+    let launched = match store_launch_uri(&game)? {
+        Some(uri) => open_store_uri(&game, &uri),
+        None => {
+            let emulator = match &game.emulator_id {
+                Some(emulator_id) => Some(fetch_emulator(db, emulator_id)?),
+                None => None,
+            };
+            let spec = LaunchSpec::for_game(&game, emulator.as_ref());
+            tauri::async_runtime::spawn_blocking(move || spawn_game_process(&spec))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|spawned| spawned.map(|_| ()))
+        }
+    };
+    sessions_service::record_launch(db, &id, launched.as_ref().err().map(String::as_str))?;
+    launched?;
+
+    remember_profile_launch(None, &id);
+    record_game_launch(db, id)?;
+
+    Ok(LaunchResult::Started)
+}
+
+const LAUNCH_PROFILE_SELECT: &str = "SELECT id, game_id, name, target, args, env, working_dir,
+             pre_launch, post_exit, created_at, is_default
+             FROM launch_profiles";
+
+/// A game started through a launch profile; kept until the tracker sees it exit.
+pub struct ProfileLaunch {
+    pub game_id: String,
+    pub profile_name: String,
+    post_exit: Option<(String, PathBuf)>,
+}
+
+static PROFILE_LAUNCHES: Mutex<Vec<ProfileLaunch>> = Mutex::new(Vec::new());
+
+/// Games whose launch hid the main window; it is shown again when they exit.
+static MINIMIZED_LAUNCHES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+/// Games stopped from Arrancador; their session ends as `killed`.
+static KILLED_GAMES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Whether launches hide the main window when the caller does not say; the
+/// `launch_and_minimize` setting.
+pub fn launch_and_minimize<D: Db>(db: &D) -> bool {
+    db.with_conn(|conn| {
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = 'launch_and_minimize'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+    })
+    .ok()
+    .flatten()
+    .is_some_and(|value| value == "true")
+}
+
+pub fn remember_minimized_launch(game_id: &str) {
+    if let Ok(mut launches) = MINIMIZED_LAUNCHES.lock() {
+        if !launches.iter().any(|id| id == game_id) {
+            launches.push(game_id.to_string());
+        }
+    }
+}
+
+/// Called by the tracker when a game exits; true when its launch had hidden the window.
+pub fn take_minimized_launch(game_id: &str) -> bool {
+    MINIMIZED_LAUNCHES.lock().is_ok_and(|mut launches| {
+        let before = launches.len();
+        launches.retain(|id| id != game_id);
+        launches.len() != before
+    })
+}
+
+fn remember_killed_game(game_id: &str) {
+    if let Ok(mut games) = KILLED_GAMES.lock() {
+        if !games.iter().any(|id| id == game_id) {
+            games.push(game_id.to_string());
+        }
+    }
+}
+
+/// Called by the tracker when a game exits; true when `kill_game_processes` stopped it.
+pub fn take_killed_game(game_id: &str) -> bool {
+    KILLED_GAMES.lock().is_ok_and(|mut games| {
+        let before = games.len();
+        games.retain(|id| id != game_id);
+        games.len() != before
+    })
+}
+
+fn remember_profile_launch(launch: Option<ProfileLaunch>, game_id: &str) {
+    if let Ok(mut launches) = PROFILE_LAUNCHES.lock() {
+        launches.retain(|l| l.game_id != game_id);
+        launches.extend(launch);
+    }
+}
+
+fn map_launch_profile_row(row: &rusqlite::Row) -> Result<LaunchProfile> {
+    let args: String = row.get(4)?;
+    let env: String = row.get(5)?;
+    Ok(LaunchProfile {
+        id: row.get(0)?,
+        game_id: row.get(1)?,
+        name: row.get(2)?,
+        target: row.get(3)?,
+        args: serde_json::from_str(&args).unwrap_or_default(),
+        env: serde_json::from_str(&env).unwrap_or_default(),
+        working_dir: row.get(6)?,
+        pre_launch: row.get(7)?,
+        post_exit: row.get(8)?,
+        created_at: row.get(9)?,
+        is_default: row.get::<_, i32>(10)? == 1,
+    })
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Profiles of one game, or of every game when `game_id` is `None`, ordered by name.
+pub fn get_launch_profiles<D: Db>(
+    db: &D,
+    game_id: Option<String>,
+) -> Result<Vec<LaunchProfile>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{LAUNCH_PROFILE_SELECT} WHERE ?1 IS NULL OR game_id = ?1 ORDER BY name COLLATE NOCASE"
+        ))?;
+        let profiles = stmt
+            .query_map(params![game_id], map_launch_profile_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(profiles)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn save_launch_profile<D: Db>(
+    db: &D,
+    input: LaunchProfileInput,
+) -> Result<LaunchProfile, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Название профиля не может быть пустым".to_string());
+    }
+    let args = serde_json::to_string(&input.args).map_err(|e| e.to_string())?;
+    let env = serde_json::to_string(&input.env).map_err(|e| e.to_string())?;
+    let id = input.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    db.with_conn(|conn| {
+        if input.is_default {
+            conn.execute(
+                "UPDATE launch_profiles SET is_default = 0 WHERE game_id = ?1 AND id != ?2",
+                params![input.game_id, id],
+            )?;
+        }
+        conn.execute(
+            "INSERT INTO launch_profiles
+                 (id, game_id, name, target, args, env, working_dir, pre_launch, post_exit,
+                  created_at, is_default)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT(id) DO UPDATE SET
+                 name = excluded.name, target = excluded.target, args = excluded.args,
+                 env = excluded.env, working_dir = excluded.working_dir,
+                 pre_launch = excluded.pre_launch, post_exit = excluded.post_exit,
+                 is_default = excluded.is_default",
+            params![
+                id,
+                input.game_id,
+                name,
+                non_empty(input.target),
+                args,
+                env,
+                non_empty(input.working_dir),
+                non_empty(input.pre_launch),
+                non_empty(input.post_exit),
+                Utc::now().to_rfc3339(),
+                input.is_default
+            ],
+        )?;
+        conn.query_row(
+            &format!("{LAUNCH_PROFILE_SELECT} WHERE id = ?1"),
+            params![id],
+            map_launch_profile_row,
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn delete_launch_profile<D: Db>(db: &D, id: String) -> Result<(), String> {
+    db.with_conn(|conn| conn.execute("DELETE FROM launch_profiles WHERE id = ?1", params![id]))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+const ROM_TOKEN: &str = "{ROM}";
+const EMULATOR_SELECT: &str =
+    "SELECT id, name, exe_path, args_template, rom_extensions, created_at FROM emulators";
+
+fn map_emulator_row(row: &rusqlite::Row) -> Result<Emulator> {
+    let extensions: String = row.get(4)?;
+    Ok(Emulator {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        exe_path: row.get(2)?,
+        args_template: row.get(3)?,
+        rom_extensions: serde_json::from_str(&extensions).unwrap_or_default(),
+        created_at: row.get(5)?,
+    })
+}
+
+fn fetch_emulator<D: Db>(db: &D, id: &str) -> Result<Emulator, String> {
+    db.with_conn(|conn| {
+        conn.query_row(
+            &format!("{EMULATOR_SELECT} WHERE id = ?1"),
+            params![id],
+            map_emulator_row,
+        )
+    })
+    .map_err(|_| "Эмулятор не найден".to_string())
+}
+
+pub fn get_emulators<D: Db>(db: &D) -> Result<Vec<Emulator>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!("{EMULATOR_SELECT} ORDER BY name COLLATE NOCASE"))?;
+        let emulators = stmt
+            .query_map([], map_emulator_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(emulators)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn save_emulator<D: Db>(db: &D, input: EmulatorInput) -> Result<Emulator, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Название эмулятора не может быть пустым".to_string());
+    }
+    let exe_path = input.exe_path.trim().to_string();
+    if exe_path.is_empty() {
+        return Err("Не указан исполняемый файл эмулятора".to_string());
+    }
+    let args_template = non_empty(input.args_template).unwrap_or_else(|| ROM_TOKEN.to_string());
+    let mut extensions: Vec<String> = input
+        .rom_extensions
+        .iter()
+        .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+    extensions.sort();
+    extensions.dedup();
+    let extensions = serde_json::to_string(&extensions).map_err(|e| e.to_string())?;
+    let id = input.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO emulators (id, name, exe_path, args_template, rom_extensions, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                 name = excluded.name, exe_path = excluded.exe_path,
+                 args_template = excluded.args_template,
+                 rom_extensions = excluded.rom_extensions",
+            params![
+                id,
+                name,
+                exe_path,
+                args_template,
+                extensions,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+    fetch_emulator(db, &id)
+}
+
+/// Refuses while games still point at the emulator; they would be left launching their ROM.
+pub fn delete_emulator<D: Db>(db: &D, id: String) -> Result<(), String> {
+    let in_use: i64 = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT COUNT(*) FROM games WHERE emulator_id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+        })
+        .map_err(|e| e.to_string())?;
+    if in_use > 0 {
+        return Err(format!("Эмулятор используется играми: {}", in_use));
+    }
+    db.with_conn(|conn| conn.execute("DELETE FROM emulators WHERE id = ?1", params![id]))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Starts a game through one of its launch profiles: runs the pre-launch hook, spawns the
+/// profile's target with its arguments and environment, and queues the post-exit hook.
+pub async fn launch_game_with_profile<D: Db + Sync>(
+    db: &D,
+    game_id: String,
+    profile_id: String,
+) -> Result<LaunchResult, String> {
+    let game = db
+        .with_conn(|conn| fetch_game_by_id(conn, &game_id))
+        .map_err(|e| e.to_string())?;
+    if let Some(settled) = check_running_instance(db, &game)? {
+        return Ok(settled);
+    }
+    let exe_path = game.exe_path.clone();
+    let profile = db
+        .with_conn(|conn| {
+            conn.query_row(
+                &format!("{LAUNCH_PROFILE_SELECT} WHERE id = ?1 AND game_id = ?2"),
+                params![profile_id, game_id],
+                map_launch_profile_row,
+            )
+        })
+        .map_err(|_| "Профиль запуска не найден".to_string())?;
+    let spec = LaunchSpec::for_profile(&game, &profile);
+    start_companions(db, &game_id).await?;
+
+    let cwd = spec.cwd.clone();
+    let pre_launch = profile.pre_launch.clone();
+    let launched = tauri::async_runtime::spawn_blocking(move || {
+        if let Some(hook) = pre_launch {
+            run_hook(&hook, &cwd, true)?;
+        }
+        spawn_game_process(&spec).map(|_| ())
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|launched| launched);
+    sessions_service::record_launch(db, &game_id, launched.as_ref().err().map(String::as_str))?;
+    launched?;
+
+    let post_exit = profile
+        .post_exit
+        .clone()
+        .map(|hook| (hook, spec_cwd(&exe_path, &profile)));
+    remember_profile_launch(
+        Some(ProfileLaunch {
+            game_id: game_id.clone(),
+            profile_name: profile.name,
+            post_exit,
+        }),
+        &game_id,
+    );
+
+    record_game_launch(db, game_id)?;
+    Ok(LaunchResult::Started)
+}
+
+/// Called by the tracker when a game exits: forgets its profile launch, runs the profile's
+/// post-exit hook and returns the launch so the session can be labelled with it.
+pub fn finish_profile_launch(game_id: &str) -> Option<ProfileLaunch> {
+    let launch = PROFILE_LAUNCHES.lock().ok().and_then(|mut launches| {
+        let index = launches.iter().position(|l| l.game_id == game_id)?;
+        Some(launches.remove(index))
+    })?;
+    if let Some((hook, cwd)) = &launch.post_exit {
+        if let Err(e) = run_hook(hook, cwd, false) {
+            eprintln!("Post-exit hook failed for {}: {}", game_id, e);
+        }
+    }
+    Some(launch)
+}
+
+const COMPANION_SELECT: &str =
+    "SELECT id, game_id, exe_path, args, close_on_exit, created_at FROM game_companions";
+
+fn map_companion_row(row: &rusqlite::Row) -> Result<GameCompanion> {
+    Ok(GameCompanion {
+        id: row.get(0)?,
+        game_id: row.get(1)?,
+        exe_path: row.get(2)?,
+        args: row.get(3)?,
+        close_on_exit: row.get::<_, i32>(4)? == 1,
+        created_at: row.get(5)?,
+    })
+}
+
+pub fn get_game_companions<D: Db>(db: &D, game_id: String) -> Result<Vec<GameCompanion>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{COMPANION_SELECT} WHERE game_id = ?1 ORDER BY created_at"
+        ))?;
+        let companions = stmt
+            .query_map(params![game_id], map_companion_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(companions)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn save_game_companion<D: Db>(
+    db: &D,
+    input: GameCompanionInput,
+) -> Result<GameCompanion, String> {
+    let exe_path = input.exe_path.trim().to_string();
+    if exe_path.is_empty() {
+        return Err("Не указан исполняемый файл программы".to_string());
+    }
+    let id = input.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO game_companions (id, game_id, exe_path, args, close_on_exit, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                 exe_path = excluded.exe_path, args = excluded.args,
+                 close_on_exit = excluded.close_on_exit",
+            params![
+                id,
+                input.game_id,
+                exe_path,
+                non_empty(input.args),
+                input.close_on_exit,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        conn.query_row(
+            &format!("{COMPANION_SELECT} WHERE id = ?1"),
+            params![id],
+            map_companion_row,
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn delete_game_companion<D: Db>(db: &D, id: String) -> Result<(), String> {
+    db.with_conn(|conn| conn.execute("DELETE FROM game_companions WHERE id = ?1", params![id]))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// A companion process a launch started and should close when its game exits.
+struct CompanionLaunch {
+    game_id: String,
+    pid: u32,
+    exe_path: PathBuf,
+}
+
+static COMPANION_LAUNCHES: Mutex<Vec<CompanionLaunch>> = Mutex::new(Vec::new());
+
+fn companion_processes() -> System {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::OnlyIfNotSet),
+    );
+    sys
+}
+
+/// Starts the game's companions that aren't running yet. A companion that fails to start is
+/// logged rather than keeping the game from launching.
+async fn start_companions<D: Db>(db: &D, game_id: &str) -> Result<(), String> {
+    let companions = get_game_companions(db, game_id.to_string())?;
+    if companions.is_empty() {
+        return Ok(());
+    }
+    let game_id = game_id.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let sys = companion_processes();
+        let mut started = Vec::new();
+        for companion in companions {
+            let exe_path = PathBuf::from(&companion.exe_path);
+            let running = sys
+                .processes()
+                .values()
+                .any(|process| process.exe().is_some_and(|exe| paths_match(exe, &exe_path)));
+            if running {
+                continue;
+            }
+            let spec = LaunchSpec {
+                args: companion
+                    .args
+                    .as_deref()
+                    .map(split_launch_args)
+                    .unwrap_or_default(),
+                env: BTreeMap::new(),
+                cwd: exe_path.parent().unwrap_or(&exe_path).to_path_buf(),
+                program: exe_path.clone(),
+            };
+            match spawn_game_process(&spec) {
+                Ok(pid) if companion.close_on_exit => started.push(CompanionLaunch {
+                    game_id: game_id.clone(),
+                    pid,
+                    exe_path,
+                }),
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to start companion {}: {}", companion.exe_path, e),
+            }
+        }
+        if let Ok(mut launches) = COMPANION_LAUNCHES.lock() {
+            launches.retain(|launch| launch.game_id != game_id);
+            launches.extend(started);
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Called by the tracker when a game exits: closes the companions its launch started with
+/// `close_on_exit`. A pid that now belongs to another program is left alone.
+pub fn finish_companions(game_id: &str) {
+    let launches: Vec<CompanionLaunch> = match COMPANION_LAUNCHES.lock() {
+        Ok(mut launches) => {
+            let (finished, kept) = launches
+                .drain(..)
+                .partition(|launch| launch.game_id == game_id);
+            *launches = kept;
+            finished
+        }
+        Err(_) => return,
+    };
+    if launches.is_empty() {
+        return;
+    }
+    let sys = companion_processes();
+    for launch in launches {
+        let Some(process) = sys.process(Pid::from_u32(launch.pid)) else {
+            continue;
+        };
+        if process
+            .exe()
+            .is_some_and(|exe| paths_match(exe, &launch.exe_path))
+            && !process.kill()
+        {
+            eprintln!("Failed to close companion {}", launch.exe_path.display());
+        }
+    }
+}
+
+struct LaunchSpec {
+    program: PathBuf,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+    cwd: PathBuf,
+}
+
+impl LaunchSpec {
+    fn for_game(game: &Game, emulator: Option<&Emulator>) -> Self {
+        let (program, mut args) = match emulator {
+            Some(emulator) => (
+                PathBuf::from(&emulator.exe_path),
+                emulator_args(&emulator.args_template, &game.exe_path),
+            ),
+            None => (PathBuf::from(&game.exe_path), Vec::new()),
+        };
+        args.extend(
+            game.launch_args
+                .as_deref()
+                .map(split_launch_args)
+                .unwrap_or_default(),
+        );
+        let cwd = match &game.working_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => program.parent().unwrap_or(&program).to_path_buf(),
+        };
+        Self {
+            program,
+            args,
+            env: game.env_vars.clone(),
+            cwd,
+        }
+    }
+
+    fn for_profile(game: &Game, profile: &LaunchProfile) -> Self {
+        let program = PathBuf::from(profile.target.as_deref().unwrap_or(&game.exe_path));
+        let mut env = game.env_vars.clone();
+        env.extend(profile.env.clone());
+        Self {
+            program,
+            args: profile.args.clone(),
+            env,
+            cwd: spec_cwd(&game.exe_path, profile),
+        }
+    }
+}
+
+/// Store ids end up in store URIs and on Galaxy's command line, so only the characters each
+/// store uses are accepted: digits for Steam and GOG, `[A-Za-z0-9_-]` for Epic plus `:` for
+/// its `namespace:catalogItemId:appName` form.
+pub fn validate_store_id(method: LaunchMethod, store_id: &str) -> Result<(), String> {
+    let id = store_id.trim();
+    let valid = match method {
+        LaunchMethod::Direct => true,
+        LaunchMethod::Steam | LaunchMethod::Gog => id.chars().all(|c| c.is_ascii_digit()),
+        LaunchMethod::Epic => id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':')),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Недопустимый ID игры в магазине: {}", id))
+    }
+}
+
+/// The store URI that starts `game`, or `None` for a direct launch.
+fn store_launch_uri(game: &Game) -> Result<Option<String>, String> {
+    let store_id = || {
+        let id = game
+            .store_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| "Не указан ID игры в магазине".to_string())?;
+        validate_store_id(game.launch_method, id).map(|_| id)
+    };
+    let uri = match game.launch_method {
+        LaunchMethod::Direct => return Ok(None),
+        LaunchMethod::Steam => format!("steam://rungameid/{}", store_id()?),
+        // The launcher expects the separators of the full id percent-encoded.
+        LaunchMethod::Epic => format!(
+            "com.epicgames.launcher://apps/{}?action=launch&silent=true",
+            store_id()?.replace(':', "%3A")
+        ),
+        LaunchMethod::Gog => format!("goggalaxy://openGameView/{}", store_id()?),
+    };
+    Ok(Some(uri))
+}
+
+/// Hands the launch to the store client. Galaxy has no URI that starts a game, so GOG titles
+/// go through the Galaxy client's `runGame` command when it is installed.
+fn open_store_uri(game: &Game, uri: &str) -> Result<(), String> {
+    if game.launch_method == LaunchMethod::Gog {
+        if let (Some(client), Some(store_id)) = (gog_galaxy_client(), game.store_id.as_deref()) {
+            let game_dir = Path::new(&game.exe_path)
+                .parent()
+                .unwrap_or(Path::new(&game.exe_path));
+            return std::process::Command::new(client)
+                .arg("/command=runGame")
+                .arg(format!("/gameId={}", store_id.trim()))
+                .arg(format!("/path={}", game_dir.display()))
+                .spawn()
+                .map(|_| ())
+                .map_err(|e| format!("Не удалось запустить GOG Galaxy: {}", e));
+        }
+    }
+    tauri_plugin_opener::open_url(uri, None::<&str>)
+        .map_err(|e| format!("Не удалось открыть {}: {}", uri, e))
+}
+
+#[cfg(target_os = "windows")]
+fn gog_galaxy_client() -> Option<PathBuf> {
+    ["ProgramFiles(x86)", "ProgramFiles"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(|dir| {
+            PathBuf::from(dir)
+                .join("GOG Galaxy")
+                .join("GalaxyClient.exe")
+        })
+        .find(|path| path.is_file())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn gog_galaxy_client() -> Option<PathBuf> {
+    None
+}
+
+/// The emulator's arguments with `{ROM}` replaced by `rom`; the ROM goes last when the
+/// template doesn't mention it.
+fn emulator_args(template: &str, rom: &str) -> Vec<String> {
+    let mut args = split_launch_args(template);
+    if !args.iter().any(|arg| arg.contains(ROM_TOKEN)) {
+        args.push(rom.to_string());
+        return args;
+    }
+    for arg in &mut args {
+        *arg = arg.replace(ROM_TOKEN, rom);
+    }
+    args
+}
+
+/// Splits on whitespace outside double quotes; the quotes themselves are dropped.
+fn split_launch_args(raw: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    for ch in raw.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+    args
+}
+
+fn spec_cwd(exe_path: &str, profile: &LaunchProfile) -> PathBuf {
+    if let Some(dir) = &profile.working_dir {
+        return PathBuf::from(dir);
+    }
+    let program = Path::new(profile.target.as_deref().unwrap_or(exe_path));
+    program.parent().unwrap_or(program).to_path_buf()
+}
+
+/// Runs a hook through the system shell; `wait` blocks until it finishes and fails on a
+/// non-zero exit code.
+fn run_hook(hook: &str, cwd: &Path, wait: bool) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", hook]).creation_flags(CREATE_NO_WINDOW);
+        command
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut command = {
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", hook]);
+        command
+    };
+    if cwd.is_dir() {
+        command.current_dir(cwd);
+    }
+    if !wait {
+        return command
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("Не удалось запустить хук: {}", e));
+    }
+    let status = command
+        .status()
+        .map_err(|e| format!("Не удалось запустить хук: {}", e))?;
+    if !status.success() {
+        return Err(format!(
+            "Хук перед запуском завершился с кодом {:?}",
+            status.code()
+        ));
+    }
+    Ok(())
 }
 
 fn paths_match(p1: &Path, p2: &Path) -> bool {
@@ -541,25 +2651,74 @@ fn resolve_shortcut_windows(path: &PathBuf) -> Result<PathBuf, String> {
     Ok(PathBuf::from(target))
 }
 
-fn spawn_game_process(exe_path: &str) -> Result<(), String> {
-    let path = Path::new(exe_path);
-    let parent = path.parent().unwrap_or(path);
+#[cfg(target_os = "windows")]
+fn write_shortcut_windows(
+    path: &Path,
+    spec: &ShortcutSpec,
+    description: &str,
+) -> Result<(), String> {
+    unsafe {
+        CoInitializeEx(None, COINIT_APARTMENTTHREADED)
+            .ok()
+            .map_err(|e| e.to_string())?;
+    }
+    let _guard = ComGuard;
+
+    let wide =
+        |value: &OsStr| -> Vec<u16> { value.encode_wide().chain(std::iter::once(0)).collect() };
+    let target = wide(spec.target.as_os_str());
+    let args = wide(OsStr::new(&spec.args));
+    let working_dir = wide(spec.working_dir.as_os_str());
+    let icon = wide(spec.icon.as_os_str());
+    let description = wide(OsStr::new(description));
+    let file = wide(path.as_os_str());
+
+    let link: IShellLinkW = unsafe {
+        CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER).map_err(|e| e.to_string())?
+    };
+    unsafe {
+        link.SetPath(PCWSTR(target.as_ptr()))
+            .and_then(|_| link.SetArguments(PCWSTR(args.as_ptr())))
+            .and_then(|_| link.SetWorkingDirectory(PCWSTR(working_dir.as_ptr())))
+            .and_then(|_| link.SetIconLocation(PCWSTR(icon.as_ptr()), 0))
+            .and_then(|_| link.SetDescription(PCWSTR(description.as_ptr())))
+            .map_err(|e: windows::core::Error| e.to_string())?;
+    }
+    let persist: IPersistFile = link
+        .cast::<IPersistFile>()
+        .map_err(|e: windows::core::Error| e.to_string())?;
+    unsafe {
+        persist
+            .Save(PCWSTR(file.as_ptr()), true)
+            .map_err(|e: windows::core::Error| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn game_command(spec: &LaunchSpec) -> std::process::Command {
+    let mut command = std::process::Command::new(&spec.program);
+    command
+        .args(&spec.args)
+        .envs(&spec.env)
+        .current_dir(&spec.cwd);
+    command
+}
 
+/// Spawns the process detached from the launcher and returns its pid.
+fn spawn_game_process(spec: &LaunchSpec) -> Result<u32, String> {
     #[cfg(target_os = "windows")]
     {
-        let mut command = std::process::Command::new(path);
-        command.current_dir(parent);
+        let mut command = game_command(spec);
         let flags = CREATE_NEW_PROCESS_GROUP | DETACHED_PROCESS | CREATE_BREAKAWAY_FROM_JOB;
         match command.creation_flags(flags.0).spawn() {
-            Ok(_) => Ok(()),
+            Ok(child) => Ok(child.id()),
             Err(_) => {
-                let mut fallback = std::process::Command::new(path);
-                fallback.current_dir(parent);
+                let mut fallback = game_command(spec);
                 let fallback_flags = CREATE_NEW_PROCESS_GROUP | DETACHED_PROCESS;
                 fallback
                     .creation_flags(fallback_flags.0)
                     .spawn()
-                    .map(|_| ())
+                    .map(|child| child.id())
                     .map_err(|e| format!("Failed to launch game: {}", e))
             }
         }
@@ -567,14 +2726,257 @@ fn spawn_game_process(exe_path: &str) -> Result<(), String> {
 
     #[cfg(not(target_os = "windows"))]
     {
-        std::process::Command::new(path)
-            .current_dir(parent)
+        game_command(spec)
             .spawn()
-            .map(|_| ())
+            .map(|child| child.id())
             .map_err(|e| format!("Failed to launch game: {}", e))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moved_save_paths_are_rewritten_relative_to_the_game() {
+        let sep = std::path::MAIN_SEPARATOR;
+        assert_eq!(
+            tokenise_moved_save_path("C:\\Games\\Hollow\\Saves\\", "C:\\games\\hollow"),
+            Some(format!("{GAME_PATH_TOKEN}{sep}Saves"))
+        );
+        assert_eq!(
+            tokenise_moved_save_path("/games/hollow", "/games/hollow/"),
+            Some(GAME_PATH_TOKEN.to_string())
+        );
+        assert_eq!(
+            tokenise_moved_save_path("/games/hollow-knight/saves", "/games/hollow"),
+            None
+        );
+        assert_eq!(
+            tokenise_moved_save_path("{PATHTOGAME}/saves", "/games/hollow"),
+            None
+        );
+    }
+
+    #[test]
+    fn launch_args_split_on_spaces_outside_quotes() {
+        assert_eq!(
+            split_launch_args(r#" -windowed  -name "Player One" "" -skipintro"#),
+            vec!["-windowed", "-name", "Player One", "", "-skipintro"]
+        );
+        assert!(split_launch_args("   ").is_empty());
+    }
+
+    #[test]
+    fn rom_games_launch_through_the_emulator_and_match_its_process() {
+        assert_eq!(
+            emulator_args(
+                r#"-f -L cores/snes.dll "{ROM}""#,
+                "C:\\Roms\\Chrono Trigger.sfc"
+            ),
+            vec!["-f", "-L", "cores/snes.dll", "C:\\Roms\\Chrono Trigger.sfc"]
+        );
+        assert_eq!(
+            emulator_args("--fullscreen", "/roms/zelda.n64"),
+            vec!["--fullscreen", "/roms/zelda.n64"]
+        );
+
+        let target = ProcessTarget {
+            exe_path: PathBuf::from("/emu/retroarch"),
+            rom_path: Some(PathBuf::from("/roms/zelda.n64")),
+        };
+        let cmd = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+        assert!(target.matches(
+            Path::new("/emu/retroarch"),
+            &cmd(&["/emu/retroarch", "/roms/zelda.n64"])
+        ));
+        assert!(!target.matches(
+            Path::new("/emu/retroarch"),
+            &cmd(&["/emu/retroarch", "/roms/mario.n64"])
+        ));
+        assert!(!target.matches(Path::new("/other/retroarch"), &cmd(&["/roms/zelda.n64"])));
+    }
+
+    #[test]
+    fn store_launches_build_the_store_uri() {
+        let mut game: Game = serde_json::from_value(serde_json::json!({
+            "id": "g1", "name": "Game", "exe_path": "C:\\Games\\game.exe", "exe_name": "game.exe",
+            "rawg_id": null, "description": null, "released": null, "background_image": null,
+            "metacritic": null, "rating": null, "genres": null, "platforms": null,
+            "developers": null, "publishers": null, "cover_image": null, "is_favorite": false,
+            "play_count": 0, "total_playtime": 0, "last_played": null, "date_added": "",
+            "backup_enabled": false, "last_backup": null, "backup_count": 0, "save_path": null,
+            "restore_on_launch": true, "backup_on_exit": true, "do_not_track": false,
+            "user_rating": null, "user_note": null, "launch_args": null, "working_dir": null,
+            "store_id": null
+        }))
+        .expect("game json");
+        assert_eq!(game.launch_method, LaunchMethod::Direct);
+        assert_eq!(store_launch_uri(&game), Ok(None));
+
+        game.launch_method = LaunchMethod::Steam;
+        assert!(store_launch_uri(&game).is_err());
+        game.store_id = Some(" 489830 ".to_string());
+        assert_eq!(
+            store_launch_uri(&game),
+            Ok(Some("steam://rungameid/489830".to_string()))
+        );
+
+        game.launch_method = LaunchMethod::Epic;
+        game.store_id = Some("Fortnite".to_string());
+        assert_eq!(
+            store_launch_uri(&game).expect("epic uri").as_deref(),
+            Some("com.epicgames.launcher://apps/Fortnite?action=launch&silent=true")
+        );
+        game.store_id = Some("Fortnite?action=uninstall".to_string());
+        assert!(store_launch_uri(&game).is_err());
+
+        assert!(validate_store_id(LaunchMethod::Steam, "489830").is_ok());
+        assert!(validate_store_id(LaunchMethod::Steam, "489830 --exec").is_err());
+        assert!(validate_store_id(LaunchMethod::Gog, "1207658924").is_ok());
+        assert!(validate_store_id(LaunchMethod::Gog, "1 /path=C:\\").is_err());
+        assert!(validate_store_id(LaunchMethod::Epic, "Sugar_Hill-2").is_ok());
+        let epic_full_id = "fn:4fe75bbc5a674f4f9b356b5c90567da5:Fortnite";
+        assert!(validate_store_id(LaunchMethod::Epic, epic_full_id).is_ok());
+        assert!(validate_store_id(LaunchMethod::Epic, "fn:Fortnite/../x").is_err());
+        game.store_id = Some(epic_full_id.to_string());
+        assert_eq!(
+            store_launch_uri(&game).expect("epic uri").as_deref(),
+            Some(
+                "com.epicgames.launcher://apps/fn%3A4fe75bbc5a674f4f9b356b5c90567da5%3AFortnite?action=launch&silent=true"
+            )
+        );
+        assert!(validate_store_id(LaunchMethod::Direct, "anything at all").is_ok());
+    }
+
+    #[test]
+    fn shortcuts_point_at_the_exe_or_the_launch_link() {
+        let mut game: Game = serde_json::from_value(serde_json::json!({
+            "id": "g1", "name": "Half-Life: Source?", "exe_path": "/games/hl/hl.exe",
+            "exe_name": "hl.exe", "rawg_id": null, "description": null, "released": null,
+            "background_image": null, "metacritic": null, "rating": null, "genres": null,
+            "platforms": null, "developers": null, "publishers": null, "cover_image": null,
+            "is_favorite": false, "play_count": 0, "total_playtime": 0, "last_played": null,
+            "date_added": "", "backup_enabled": false, "last_backup": null, "backup_count": 0,
+            "save_path": null, "restore_on_launch": true, "backup_on_exit": true,
+            "do_not_track": false, "user_rating": null, "user_note": null,
+            "launch_args": "-novid", "working_dir": null, "store_id": null
+        }))
+        .expect("game json");
+        let launcher = Path::new("/apps/arrancador/arrancador.exe");
+
+        assert_eq!(shortcut_file_name(&game.name), "Half-Life  Source.lnk");
+        assert_eq!(shortcut_file_name(" ... "), "Game.lnk");
+
+        let spec =
+            shortcut_spec(&game, Some(ShortcutTarget::Executable), launcher).expect("exe shortcut");
+        assert_eq!(spec.target, PathBuf::from("/games/hl/hl.exe"));
+        assert_eq!(spec.args, "-novid");
+        assert_eq!(spec.working_dir, PathBuf::from("/games/hl"));
+
+        let spec = shortcut_spec(&game, None, launcher).expect("link shortcut");
+        assert_eq!(spec.target, launcher);
+        assert_eq!(spec.args, "arrancador://launch/g1");
+        assert_eq!(parse_launch_link(&spec.args), Some("g1"));
+        assert_eq!(parse_launch_link("arrancador://launch/"), None);
+        assert_eq!(parse_launch_link("--minimized"), None);
+
+        // The executable alone would start the game without its environment.
+        game.env_vars
+            .insert("DXVK_HUD".to_string(), "fps".to_string());
+        assert!(shortcut_spec(&game, Some(ShortcutTarget::Executable), launcher).is_err());
+        game.env_vars.clear();
+
+        game.launch_method = LaunchMethod::Steam;
+        assert!(shortcut_spec(&game, Some(ShortcutTarget::Executable), launcher).is_err());
+        assert_eq!(
+            shortcut_spec(&game, None, launcher)
+                .expect("store shortcut")
+                .target,
+            launcher
+        );
+    }
+
+    #[test]
+    fn running_games_are_focused_refused_or_started_by_policy() {
+        use crate::database::init_schema;
+        use crate::db::ConnectionDb;
+
+        // The test binary itself stands in for a running game.
+        let running = std::env::current_exe().expect("current exe");
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+             VALUES ('running', 'Running', ?1, 'test.exe', '2026-01-01T00:00:00Z'),
+                    ('stopped', 'Stopped', '/missing/game.exe', 'game.exe', '2026-01-01T00:00:00Z')",
+            params![running.to_string_lossy()],
+        )
+        .expect("insert games");
+        let db = ConnectionDb::new(conn);
+        let fetch = |id: &str| {
+            db.with_conn(|conn| fetch_game_by_id(conn, id))
+                .expect("game")
+        };
+
+        assert_eq!(fetch("running").instance_policy, None);
+        assert_eq!(
+            instance_policy(&db, &fetch("running")),
+            InstancePolicy::Focus
+        );
+        let settled = check_running_instance(&db, &fetch("running")).expect("focus");
+        assert!(matches!(
+            settled,
+            Some(LaunchResult::Focused | LaunchResult::AlreadyRunning)
+        ));
+        assert_eq!(check_running_instance(&db, &fetch("stopped")), Ok(None));
+
+        db.with_conn(|conn| {
+            conn.execute(
+                "UPDATE settings SET value = 'refuse' WHERE key = 'running_instance_policy'",
+                [],
+            )
+        })
+        .expect("set policy");
+        assert_eq!(
+            check_running_instance(&db, &fetch("running")),
+            Err(ALREADY_RUNNING_MESSAGE.to_string())
+        );
+
+        let game = set_game_instance_policy(&db, "running".into(), Some(InstancePolicy::Allow))
+            .expect("set game policy");
+        assert_eq!(game.instance_policy, Some(InstancePolicy::Allow));
+        assert_eq!(check_running_instance(&db, &game), Ok(None));
+        let game = set_game_instance_policy(&db, "running".into(), None).expect("clear policy");
+        assert_eq!(instance_policy(&db, &game), InstancePolicy::Refuse);
+    }
+
+    #[test]
+    fn minimized_launches_are_restored_once() {
+        use crate::database::init_schema;
+        use crate::db::ConnectionDb;
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        let db = ConnectionDb::new(conn);
+        assert!(!launch_and_minimize(&db));
+        db.with_conn(|conn| {
+            conn.execute(
+                "UPDATE settings SET value = 'true' WHERE key = 'launch_and_minimize'",
+                [],
+            )
+        })
+        .expect("enable setting");
+        assert!(launch_and_minimize(&db));
+
+        remember_minimized_launch("minimized-game");
+        remember_minimized_launch("minimized-game");
+        assert!(take_minimized_launch("minimized-game"));
+        assert!(!take_minimized_launch("minimized-game"));
+        assert!(!take_minimized_launch("other-game"));
+    }
+}
+
 #[cfg(test)]
 mod perf_bench {
     use super::*;
@@ -612,7 +3014,7 @@ mod perf_bench {
 
         let db = ConnectionDb::new(conn);
         let start = Instant::now();
-        let games = get_all_games(&db).expect("get all games");
+        let games = get_all_games(&db, false).expect("get all games");
         let serialized = serde_json::to_vec(&games).expect("serialize games");
         let elapsed = start.elapsed();
 
@@ -622,5 +3024,17 @@ mod perf_bench {
             serialized.len(),
             elapsed.as_millis()
         );
+
+        let start = Instant::now();
+        let summaries = get_game_summaries(&db).expect("get game summaries");
+        let serialized = serde_json::to_vec(&summaries).expect("serialize summaries");
+        let elapsed = start.elapsed();
+
+        println!(
+            "perf: library_summaries rows={} bytes={} duration_ms={}",
+            summaries.len(),
+            serialized.len(),
+            elapsed.as_millis()
+        );
     }
 }
@@ -0,0 +1,99 @@
+//! Cache of metadata API responses, keyed by request URL. A fresh entry answers a request
+//! without touching the network; an expired one is still served when the request fails, so
+//! metadata search keeps working offline and UI retries don't use up RAWG's rate limit.
+
+use crate::db::Db;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, OptionalExtension};
+
+/// Expired entries are kept this many days as an offline fallback before they are pruned.
+const STALE_KEEP_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    pub body: String,
+    /// Whether the entry is still within its time to live.
+    pub fresh: bool,
+}
+
+/// The cached body for `url`, fresh or not.
+pub fn lookup<D: Db>(db: &D, url: &str, now: DateTime<Utc>) -> Option<CachedResponse> {
+    let (body, expires_at) = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT body, expires_at FROM http_cache WHERE url = ?1",
+                params![url],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()
+        })
+        .ok()
+        .flatten()?;
+    let fresh = DateTime::parse_from_rfc3339(&expires_at)
+        .map(|expires_at| expires_at > now)
+        .unwrap_or(false);
+    Some(CachedResponse { body, fresh })
+}
+
+/// Stores `body` for `url` for `ttl`, and prunes entries that expired long ago.
+pub fn store<D: Db>(
+    db: &D,
+    url: &str,
+    body: &str,
+    ttl: Duration,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO http_cache (url, body, fetched_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![url, body, now.to_rfc3339(), (now + ttl).to_rfc3339()],
+        )?;
+        conn.execute(
+            "DELETE FROM http_cache WHERE expires_at < ?1",
+            params![(now - Duration::days(STALE_KEEP_DAYS)).to_rfc3339()],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+
+    #[test]
+    fn entries_expire_but_stay_as_a_fallback_until_pruned() {
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        let db = ConnectionDb::new(conn);
+        let now = Utc::now();
+        let url = "https://api.rawg.io/api/games?search=hades&page_size=10";
+
+        assert_eq!(lookup(&db, url, now), None);
+        store(&db, url, "{\"results\":[]}", Duration::hours(24), now).expect("store");
+        assert_eq!(
+            lookup(&db, url, now + Duration::hours(1)),
+            Some(CachedResponse {
+                body: "{\"results\":[]}".to_string(),
+                fresh: true,
+            })
+        );
+        let stale = lookup(&db, url, now + Duration::hours(25)).expect("stale entry");
+        assert!(!stale.fresh);
+
+        let later = now + Duration::days(STALE_KEEP_DAYS + 2);
+        store(
+            &db,
+            "https://api.rawg.io/api/games/1",
+            "{}",
+            Duration::days(7),
+            later,
+        )
+        .expect("store other");
+        assert_eq!(lookup(&db, url, later), None);
+    }
+}
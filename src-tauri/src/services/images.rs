@@ -0,0 +1,732 @@
+//! Covers kept under the app data folder so they keep working offline. Every stored image
+//! gets a JPEG thumbnail next to it; files no game points at any more are swept away.
+//! Downloaded covers are capped by `image_cache_max_mb`: a background job evicts the least
+//! recently used ones, and games showing an evicted cover fall back to its URL.
+
+use crate::db::Db;
+use crate::domain::games::Game;
+use crate::services::games::fetch_game_by_id;
+use chrono::Utc;
+use image::{DynamicImage, ImageFormat};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Bounding box of a thumbnail; covers are portrait, so this keeps the 2:3 shape.
+const THUMBNAIL_WIDTH: u32 = 320;
+const THUMBNAIL_HEIGHT: u32 = 480;
+/// Larger downloads are refused rather than kept as a cover.
+const MAX_IMAGE_BYTES: usize = 32 * 1024 * 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// Used when `image_cache_max_mb` is unset; 0 turns the cap off.
+pub const DEFAULT_IMAGE_CACHE_MAX_MB: i64 = 1024;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// The first pass waits until covers shown at startup have been looked up, so their
+/// `last_used` is fresh before anything is evicted.
+const PRUNE_STARTUP_DELAY: Duration = Duration::from_secs(180);
+/// Pause between two evictions, so pruning a large cache never hogs the disk.
+const PRUNE_THROTTLE: Duration = Duration::from_millis(50);
+
+static PRUNE_PAUSED: AtomicBool = AtomicBool::new(false);
+/// Wakes the prune job early, e.g. when pruning is resumed.
+static PRUNE_WAKE: Mutex<Option<mpsc::Sender<()>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImageCacheStats {
+    /// Downloaded covers kept in the cache.
+    pub entries: i64,
+    /// Their size on disk, thumbnails included.
+    pub total_bytes: i64,
+    /// `None` when the cap is off.
+    pub max_bytes: Option<i64>,
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredImage {
+    pub path: PathBuf,
+    pub thumbnail: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageStore {
+    root: PathBuf,
+}
+
+impl Default for ImageStore {
+    fn default() -> Self {
+        let app_data = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
+        Self::new(app_data.join("arrancador").join("images"))
+    }
+}
+
+impl ImageStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn thumbnails_dir(&self) -> PathBuf {
+        self.root.join("thumbs")
+    }
+
+    /// Keeps a copy of `bytes` under a fresh name, together with its thumbnail.
+    pub fn store(&self, bytes: &[u8]) -> Result<StoredImage, String> {
+        let format = image::guess_format(bytes)
+            .map_err(|e| format!("Не удалось распознать изображение: {}", e))?;
+        let image = image::load_from_memory_with_format(bytes, format)
+            .map_err(|e| format!("Не удалось прочитать изображение: {}", e))?;
+        let extension = format.extensions_str().first().copied().unwrap_or("img");
+
+        fs::create_dir_all(self.thumbnails_dir()).map_err(|e| e.to_string())?;
+        let name = Uuid::new_v4().to_string();
+        let path = self.root.join(format!("{name}.{extension}"));
+        fs::write(&path, bytes).map_err(|e| e.to_string())?;
+        let thumbnail = self.thumbnails_dir().join(format!("{name}.jpg"));
+        if let Err(e) = write_thumbnail(&image, &thumbnail) {
+            let _ = fs::remove_file(&path);
+            return Err(e);
+        }
+        Ok(StoredImage { path, thumbnail })
+    }
+
+    /// A thumbnail for an image that lives outside the store, e.g. a cover picked before
+    /// the store existed.
+    pub fn thumbnail_for(&self, source: &Path) -> Result<PathBuf, String> {
+        let image =
+            image::open(source).map_err(|e| format!("Не удалось прочитать изображение: {}", e))?;
+        fs::create_dir_all(self.thumbnails_dir()).map_err(|e| e.to_string())?;
+        let thumbnail = self
+            .thumbnails_dir()
+            .join(format!("{}.jpg", Uuid::new_v4()));
+        write_thumbnail(&image, &thumbnail)?;
+        Ok(thumbnail)
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    /// Removes stored files not listed in `referenced`; returns how many were deleted.
+    pub fn remove_unreferenced(&self, referenced: &HashSet<PathBuf>) -> usize {
+        let mut removed = 0;
+        for dir in [self.root.clone(), self.thumbnails_dir()] {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_file() && !referenced.contains(&path) && fs::remove_file(&path).is_ok() {
+                    removed += 1;
+                }
+            }
+        }
+        removed
+    }
+}
+
+fn write_thumbnail(image: &DynamicImage, target: &Path) -> Result<(), String> {
+    // JPEG has no alpha channel, so transparent covers are flattened first.
+    DynamicImage::ImageRgb8(image.thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT).to_rgb8())
+        .save_with_format(target, ImageFormat::Jpeg)
+        .map_err(|e| format!("Не удалось создать миниатюру: {}", e))
+}
+
+fn set_cover(conn: &Connection, game_id: &str, image: &StoredImage) -> rusqlite::Result<Game> {
+    let changed = conn.execute(
+        "UPDATE games SET cover_image = ?1, cover_thumbnail = ?2 WHERE id = ?3",
+        params![
+            image.path.to_string_lossy(),
+            image.thumbnail.to_string_lossy(),
+            game_id
+        ],
+    )?;
+    if changed == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+    fetch_game_by_id(conn, game_id)
+}
+
+/// Copies a local picture into the store and makes it the game's cover.
+pub fn import_cover_image<D: Db>(
+    db: &D,
+    store: &ImageStore,
+    game_id: String,
+    source_path: String,
+) -> Result<Game, String> {
+    let bytes =
+        fs::read(&source_path).map_err(|e| format!("Не удалось открыть {}: {}", source_path, e))?;
+    store_cover(db, store, game_id, &bytes, None)
+}
+
+/// The stored copy of `url`, if it was downloaded before and is still on disk. A hit counts
+/// as a use for the cache's least-recently-used eviction.
+pub fn cached_image<D: Db>(db: &D, url: &str) -> Result<Option<StoredImage>, String> {
+    let cached = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT path, thumbnail FROM image_cache WHERE url = ?1",
+                params![url],
+                |row| {
+                    Ok(StoredImage {
+                        path: PathBuf::from(row.get::<_, String>(0)?),
+                        thumbnail: PathBuf::from(row.get::<_, String>(1)?),
+                    })
+                },
+            )
+            .optional()
+        })
+        .map_err(|e| e.to_string())?;
+    let Some(image) = cached.filter(|image| image.path.is_file() && image.thumbnail.is_file())
+    else {
+        return Ok(None);
+    };
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE image_cache SET last_used = ?1 WHERE url = ?2",
+            params![Utc::now().to_rfc3339(), url],
+        )
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(Some(image))
+}
+
+pub async fn download_image(url: &str) -> Result<Vec<u8>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent("Arrancador/0.1.0")
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Не удалось скачать изображение: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Не удалось скачать изображение: {}",
+            response.status()
+        ));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Не удалось скачать изображение: {}", e))?;
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err("Изображение слишком большое".to_string());
+    }
+    Ok(bytes.to_vec())
+}
+
+/// Stores `bytes` as the game's cover; with `source_url` the copy is remembered so the same
+/// URL is not downloaded again.
+pub fn store_cover<D: Db>(
+    db: &D,
+    store: &ImageStore,
+    game_id: String,
+    bytes: &[u8],
+    source_url: Option<&str>,
+) -> Result<Game, String> {
+    let image = store.store(bytes)?;
+    let replaced = cover_files(db, std::slice::from_ref(&game_id))?;
+    let size_bytes = bytes.len() as u64 + fs::metadata(&image.thumbnail).map_or(0, |m| m.len());
+    let result = db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        if let Some(url) = source_url {
+            let now = Utc::now().to_rfc3339();
+            tx.execute(
+                "INSERT INTO image_cache (url, path, thumbnail, created_at, size_bytes, last_used)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?4)
+                 ON CONFLICT(url) DO UPDATE SET
+                    path = excluded.path,
+                    thumbnail = excluded.thumbnail,
+                    created_at = excluded.created_at,
+                    size_bytes = excluded.size_bytes,
+                    last_used = excluded.last_used",
+                params![
+                    url,
+                    image.path.to_string_lossy(),
+                    image.thumbnail.to_string_lossy(),
+                    now,
+                    size_bytes as i64
+                ],
+            )?;
+        }
+        let game = set_cover(&tx, &game_id, &image)?;
+        tx.commit()?;
+        Ok(game)
+    });
+    match result {
+        Ok(game) => {
+            remove_orphaned_covers(db, store, replaced)?;
+            Ok(game)
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            let _ = fs::remove_file(&image.path);
+            let _ = fs::remove_file(&image.thumbnail);
+            Err("Игра не найдена".to_string())
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&image.path);
+            let _ = fs::remove_file(&image.thumbnail);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Points the game at an image that is already stored, e.g. one found in the URL cache.
+pub fn use_stored_cover<D: Db>(
+    db: &D,
+    store: &ImageStore,
+    game_id: String,
+    image: &StoredImage,
+) -> Result<Game, String> {
+    let replaced = cover_files(db, std::slice::from_ref(&game_id))?;
+    let game = db
+        .with_conn(|conn| set_cover(conn, &game_id, image))
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => "Игра не найдена".to_string(),
+            e => e.to_string(),
+        })?;
+    remove_orphaned_covers(db, store, replaced)?;
+    Ok(game)
+}
+
+/// Cover and thumbnail paths of `game_ids`, to be passed to [`remove_orphaned_covers`] once
+/// the games are deleted or their covers replaced.
+pub fn cover_files<D: Db>(db: &D, game_ids: &[String]) -> Result<Vec<PathBuf>, String> {
+    db.with_conn(|conn| {
+        let mut stmt =
+            conn.prepare("SELECT cover_image, cover_thumbnail FROM games WHERE id = ?1")?;
+        let mut paths = Vec::new();
+        for id in game_ids {
+            let row = stmt
+                .query_row(params![id], |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                    ))
+                })
+                .optional()?;
+            if let Some((cover, thumbnail)) = row {
+                paths.extend(cover.into_iter().chain(thumbnail).map(PathBuf::from));
+            }
+        }
+        Ok(paths)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Deletes those of `paths` that live in the store and that no game points at any more.
+pub fn remove_orphaned_covers<D: Db>(
+    db: &D,
+    store: &ImageStore,
+    paths: Vec<PathBuf>,
+) -> Result<usize, String> {
+    let candidates: Vec<PathBuf> = paths.into_iter().filter(|p| store.contains(p)).collect();
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+    db.with_conn(|conn| {
+        let mut removed = 0;
+        for path in candidates {
+            let path_text = path.to_string_lossy();
+            let used: bool = conn.query_row(
+                "SELECT EXISTS (SELECT 1 FROM games
+                 WHERE cover_image = ?1 OR cover_thumbnail = ?1 OR background_image = ?1)",
+                params![path_text],
+                |row| row.get(0),
+            )?;
+            if used {
+                continue;
+            }
+            conn.execute(
+                "DELETE FROM image_cache WHERE path = ?1 OR thumbnail = ?1",
+                params![path_text],
+            )?;
+            if fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Creates the missing thumbnail for a game whose cover is a local file.
+pub fn generate_cover_thumbnail<D: Db>(
+    db: &D,
+    store: &ImageStore,
+    game_id: String,
+) -> Result<Game, String> {
+    let cover: Option<String> = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT cover_image FROM games WHERE id = ?1",
+                params![game_id],
+                |row| row.get(0),
+            )
+            .optional()
+        })
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Игра не найдена".to_string())?;
+    let cover = cover
+        .filter(|path| Path::new(path).is_file())
+        .ok_or_else(|| "У игры нет локальной обложки".to_string())?;
+    let thumbnail = store.thumbnail_for(Path::new(&cover))?;
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE games SET cover_thumbnail = ?1 WHERE id = ?2",
+            params![thumbnail.to_string_lossy(), game_id],
+        )?;
+        fetch_game_by_id(conn, &game_id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Deletes stored images and thumbnails that no game uses, and forgets cached URLs whose
+/// files are gone. Returns the number of files removed.
+pub fn clean_unused_images<D: Db>(db: &D, store: &ImageStore) -> Result<usize, String> {
+    let referenced: HashSet<PathBuf> = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT cover_image FROM games WHERE cover_image IS NOT NULL
+                 UNION SELECT cover_thumbnail FROM games WHERE cover_thumbnail IS NOT NULL
+                 UNION SELECT background_image FROM games WHERE background_image IS NOT NULL",
+            )?;
+            let paths = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .flatten()
+                .map(PathBuf::from)
+                .collect();
+            Ok(paths)
+        })
+        .map_err(|e| e.to_string())?;
+    let removed = store.remove_unreferenced(&referenced);
+
+    let stale: Vec<String> = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare("SELECT url, path FROM image_cache")?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?
+                .flatten()
+                .filter(|(_, path)| !Path::new(path).is_file())
+                .map(|(url, _)| url)
+                .collect();
+            Ok(rows)
+        })
+        .map_err(|e| e.to_string())?;
+    db.with_conn(|conn| {
+        for url in &stale {
+            conn.execute("DELETE FROM image_cache WHERE url = ?1", params![url])?;
+        }
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(removed)
+}
+
+/// The cache cap from `image_cache_max_mb` in bytes; `None` when it is 0 (no cap).
+pub fn image_cache_max_bytes<D: Db>(db: &D) -> Option<i64> {
+    let megabytes = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT value FROM settings WHERE key = 'image_cache_max_mb'",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+        })
+        .ok()
+        .flatten()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_IMAGE_CACHE_MAX_MB);
+    (megabytes > 0).then(|| megabytes * 1024 * 1024)
+}
+
+/// Pauses or resumes the prune job; resuming wakes it instead of waiting for its next run.
+pub fn set_pruning_paused(paused: bool) {
+    PRUNE_PAUSED.store(paused, Ordering::SeqCst);
+    if !paused {
+        if let Some(wake) = PRUNE_WAKE.lock().unwrap().as_ref() {
+            let _ = wake.send(());
+        }
+    }
+}
+
+pub fn image_cache_stats<D: Db>(db: &D) -> Result<ImageCacheStats, String> {
+    let (entries, total_bytes) = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT COUNT(*), COALESCE(SUM(size_bytes), 0) FROM image_cache",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(ImageCacheStats {
+        entries,
+        total_bytes,
+        max_bytes: image_cache_max_bytes(db),
+        paused: PRUNE_PAUSED.load(Ordering::SeqCst),
+    })
+}
+
+/// Drops one cached download. Games showing it go back to the URL it came from.
+fn evict_cached_image<D: Db>(
+    db: &D,
+    store: &ImageStore,
+    url: &str,
+    image: &StoredImage,
+) -> Result<(), String> {
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let path = image.path.to_string_lossy();
+        tx.execute(
+            "UPDATE games SET cover_image = ?1, cover_thumbnail = NULL WHERE cover_image = ?2",
+            params![url, path],
+        )?;
+        tx.execute(
+            "UPDATE games SET background_image = ?1 WHERE background_image = ?2",
+            params![url, path],
+        )?;
+        tx.execute("DELETE FROM image_cache WHERE url = ?1", params![url])?;
+        tx.commit()
+    })
+    .map_err(|e| e.to_string())?;
+    for file in [&image.path, &image.thumbnail] {
+        if store.contains(file) {
+            let _ = fs::remove_file(file);
+        }
+    }
+    Ok(())
+}
+
+/// Evicts the least recently used downloads until the cache fits in `max_bytes`, waiting
+/// `throttle` between evictions. Stops early while `paused` is set; the next run carries on
+/// from the oldest entry left. Returns how many entries were evicted.
+pub fn prune_image_cache<D: Db>(
+    db: &D,
+    store: &ImageStore,
+    max_bytes: i64,
+    paused: &AtomicBool,
+    throttle: Duration,
+) -> Result<usize, String> {
+    let entries: Vec<(String, StoredImage, i64)> = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT url, path, thumbnail, size_bytes FROM image_cache
+                 ORDER BY COALESCE(last_used, created_at), created_at",
+            )?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        StoredImage {
+                            path: PathBuf::from(row.get::<_, String>(1)?),
+                            thumbnail: PathBuf::from(row.get::<_, String>(2)?),
+                        },
+                        row.get::<_, i64>(3)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(rows)
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut total: i64 = entries.iter().map(|(_, _, size)| size).sum();
+    let mut evicted = 0;
+    for (url, image, size) in entries {
+        if total <= max_bytes || paused.load(Ordering::SeqCst) {
+            break;
+        }
+        if evicted > 0 {
+            thread::sleep(throttle);
+        }
+        evict_cached_image(db, store, &url, &image)?;
+        total -= size;
+        evicted += 1;
+    }
+    Ok(evicted)
+}
+
+/// Background job keeping the image cache under `image_cache_max_mb`. It runs every
+/// `PRUNE_INTERVAL`, and right away when pruning is resumed.
+pub fn start_image_cache_prune<D: Db + Send + 'static>(db: D, store: ImageStore) {
+    let (wake, woken) = mpsc::channel();
+    *PRUNE_WAKE.lock().unwrap() = Some(wake);
+    thread::spawn(move || {
+        thread::sleep(PRUNE_STARTUP_DELAY);
+        loop {
+            if let Some(max_bytes) = image_cache_max_bytes(&db) {
+                if let Err(e) =
+                    prune_image_cache(&db, &store, max_bytes, &PRUNE_PAUSED, PRUNE_THROTTLE)
+                {
+                    eprintln!("Image cache pruning failed: {}", e);
+                }
+            }
+            if let Err(mpsc::RecvTimeoutError::Disconnected) = woken.recv_timeout(PRUNE_INTERVAL) {
+                return;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use image::{ImageBuffer, Rgba};
+    use std::io::Cursor;
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let image = ImageBuffer::from_pixel(width, height, Rgba([200u8, 40, 40, 128]));
+        let mut bytes = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut bytes, ImageFormat::Png)
+            .expect("encode png");
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn covers_are_stored_thumbnailed_and_cleaned_up() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let store = ImageStore::new(dir.path().join("images"));
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+             VALUES ('g1', 'Game', 'C:/Games/game.exe', 'game.exe', '2026-01-01T00:00:00Z')",
+            [],
+        )
+        .expect("insert game");
+        let db = ConnectionDb::new(conn);
+
+        assert!(store.store(b"not an image").is_err());
+
+        let source = dir.path().join("cover.png");
+        fs::write(&source, png_bytes(800, 1200)).expect("write cover");
+        let game = import_cover_image(
+            &db,
+            &store,
+            "g1".to_string(),
+            source.to_string_lossy().to_string(),
+        )
+        .expect("import cover");
+        let first_cover = PathBuf::from(game.cover_image.expect("cover"));
+        let thumbnail = PathBuf::from(game.cover_thumbnail.expect("thumbnail"));
+        assert!(first_cover.starts_with(dir.path().join("images")));
+        let (width, height) = image::image_dimensions(&thumbnail).expect("thumbnail size");
+        assert_eq!((width, height), (320, 480));
+
+        let url = "https://media.example/cover.png";
+        let game = store_cover(&db, &store, "g1".to_string(), &png_bytes(60, 90), Some(url))
+            .expect("store downloaded cover");
+        assert!(!first_cover.exists());
+        assert!(!thumbnail.exists());
+        let cached = cached_image(&db, url)
+            .expect("cache lookup")
+            .expect("cached");
+        assert_eq!(
+            game.cover_image,
+            Some(cached.path.to_string_lossy().to_string())
+        );
+
+        db.with_conn(|conn| conn.execute("DELETE FROM games WHERE id = 'g1'", []))
+            .expect("delete game");
+        assert_eq!(clean_unused_images(&db, &store), Ok(2));
+        assert_eq!(cached_image(&db, url), Ok(None));
+        assert!(store
+            .store(&png_bytes(10, 10))
+            .is_ok_and(|image| image.thumbnail.is_file()));
+    }
+
+    #[test]
+    fn image_cache_is_pruned_least_recently_used_first() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let store = ImageStore::new(dir.path().join("images"));
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        for id in ["g1", "g2", "g3"] {
+            conn.execute(
+                "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+                 VALUES (?1, ?1, 'C:/Games/game.exe', 'game.exe', '2026-01-01T00:00:00Z')",
+                params![id],
+            )
+            .expect("insert game");
+        }
+        let db = ConnectionDb::new(conn);
+        assert_eq!(image_cache_max_bytes(&db), Some(1024 * 1024 * 1024));
+
+        let url = |id: &str| format!("https://media.example/{id}.png");
+        let mut covers = Vec::new();
+        for id in ["g1", "g2", "g3"] {
+            let game = store_cover(
+                &db,
+                &store,
+                id.to_string(),
+                &png_bytes(60, 90),
+                Some(&url(id)),
+            )
+            .expect("store cover");
+            covers.push(PathBuf::from(game.cover_image.expect("cover")));
+        }
+        // g2 was shown last, g1 before it; g3 is the least recently used.
+        db.with_conn(|conn| {
+            conn.execute_batch(
+                "UPDATE image_cache SET last_used = '2026-01-01T00:00:00Z';
+                 UPDATE image_cache SET last_used = '2026-01-02T00:00:00Z'
+                     WHERE url LIKE '%g1.png';",
+            )
+        })
+        .expect("set last use");
+        assert!(cached_image(&db, &url("g2")).expect("lookup").is_some());
+
+        let stats = image_cache_stats(&db).expect("stats");
+        assert_eq!(stats.entries, 3);
+        assert!(stats.total_bytes > 0);
+
+        let paused = AtomicBool::new(true);
+        assert_eq!(
+            prune_image_cache(&db, &store, 0, &paused, Duration::ZERO),
+            Ok(0)
+        );
+        paused.store(false, Ordering::SeqCst);
+        let max_bytes = stats.total_bytes - 1;
+        assert_eq!(
+            prune_image_cache(&db, &store, max_bytes, &paused, Duration::ZERO),
+            Ok(1)
+        );
+        assert!(!covers[2].exists());
+        assert!(covers[0].exists() && covers[1].exists());
+        let g3 = db
+            .with_conn(|conn| fetch_game_by_id(conn, "g3"))
+            .expect("g3");
+        assert_eq!(g3.cover_image, Some(url("g3")));
+        assert_eq!(g3.cover_thumbnail, None);
+
+        assert_eq!(
+            prune_image_cache(&db, &store, 0, &paused, Duration::ZERO),
+            Ok(2)
+        );
+        assert_eq!(image_cache_stats(&db).expect("stats").entries, 0);
+
+        db.with_conn(|conn| {
+            conn.execute(
+                "UPDATE settings SET value = '0' WHERE key = 'image_cache_max_mb'",
+                [],
+            )
+        })
+        .expect("turn off cap");
+        assert_eq!(image_cache_max_bytes(&db), None);
+    }
+}
@@ -0,0 +1,200 @@
+//! Disk space taken by installed games. Sizes are cached on the game row and a background
+//! thread re-measures the ones older than a week, so the library never walks an install
+//! folder while the user waits.
+
+use crate::db::Db;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use walkdir::WalkDir;
+
+/// How long a measured size is trusted before it is walked again.
+pub const INSTALL_SIZE_MAX_AGE_HOURS: i64 = 168;
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Walking every install folder is heavy I/O; it waits until the library, covers and the
+/// tracker's first process sweep have loaded.
+const STARTUP_DELAY: Duration = Duration::from_secs(120);
+
+/// Total size of the files under `dir`. Symlinks are not followed, so a link to another
+/// drive is not counted against the game.
+pub fn directory_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+/// The folder measured for a game: the executable's parent. ROMs usually share a folder,
+/// so for emulated games only the ROM file itself counts.
+fn measured_size(exe_path: &str, emulated: bool) -> Option<u64> {
+    let path = PathBuf::from(exe_path);
+    if emulated {
+        return path.metadata().ok().map(|meta| meta.len());
+    }
+    let dir = path.parent()?;
+    dir.is_dir().then(|| directory_size(dir))
+}
+
+fn is_fresh(checked_at: Option<&str>, now: DateTime<Utc>) -> bool {
+    checked_at
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .is_some_and(|checked| {
+            now.signed_duration_since(checked) < chrono::Duration::hours(INSTALL_SIZE_MAX_AGE_HOURS)
+        })
+}
+
+fn store_size<D: Db>(db: &D, game_id: &str, size: u64, now: DateTime<Utc>) -> Result<(), String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE games SET install_size = ?1, install_size_checked_at = ?2 WHERE id = ?3",
+            params![size as i64, now.to_rfc3339(), game_id],
+        )
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Bytes the game takes on disk. The cached size is returned while it is less than a week
+/// old unless `refresh` is set.
+pub fn compute_install_size<D: Db>(db: &D, game_id: &str, refresh: bool) -> Result<i64, String> {
+    let now = Utc::now();
+    let (exe_path, emulated, cached, checked_at) = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT exe_path, emulator_id IS NOT NULL, install_size, install_size_checked_at
+                 FROM games WHERE id = ?1",
+                params![game_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, bool>(1)?,
+                        row.get::<_, Option<i64>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                    ))
+                },
+            )
+            .optional()
+        })
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Игра не найдена".to_string())?;
+
+    if let Some(size) = cached.filter(|_| !refresh && is_fresh(checked_at.as_deref(), now)) {
+        return Ok(size);
+    }
+    let size =
+        measured_size(&exe_path, emulated).ok_or_else(|| "Папка игры не найдена".to_string())?;
+    store_size(db, game_id, size, now)?;
+    Ok(size as i64)
+}
+
+/// Re-measures installed games whose size is missing or older than a week; returns how
+/// many were updated. Games whose folder is gone keep their last known size.
+pub fn refresh_stale_install_sizes<D: Db>(db: &D, now: DateTime<Utc>) -> Result<usize, String> {
+    let games: Vec<(String, String, bool, Option<String>)> = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, exe_path, emulator_id IS NOT NULL, install_size_checked_at
+                 FROM games",
+            )?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(rows)
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut updated = 0;
+    for (id, exe_path, emulated, checked_at) in games {
+        if is_fresh(checked_at.as_deref(), now) {
+            continue;
+        }
+        let Some(size) = measured_size(&exe_path, emulated) else {
+            continue;
+        };
+        store_size(db, &id, size, now)?;
+        updated += 1;
+    }
+    Ok(updated)
+}
+
+/// Background job behind the cached sizes; emits `games:install-sizes` with the number of
+/// games re-measured so an open library can reload.
+pub fn start_install_size_refresh<D: Db + Send + 'static>(db: D, app: AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(STARTUP_DELAY);
+        loop {
+            match refresh_stale_install_sizes(&db, Utc::now()) {
+                Ok(0) => {}
+                Ok(updated) => {
+                    let _ = app.emit("games:install-sizes", updated);
+                }
+                Err(e) => eprintln!("Install size refresh failed: {}", e),
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+    use std::fs;
+
+    #[test]
+    fn install_sizes_are_measured_cached_and_refreshed() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let game_dir = dir.path().join("Game");
+        fs::create_dir_all(game_dir.join("data")).expect("create game dir");
+        fs::write(game_dir.join("game.exe"), vec![0u8; 100]).expect("write exe");
+        fs::write(game_dir.join("data").join("pak0.pak"), vec![0u8; 900]).expect("write pak");
+        let roms = dir.path().join("roms");
+        fs::create_dir_all(&roms).expect("create roms");
+        fs::write(roms.join("a.sfc"), vec![0u8; 40]).expect("write rom");
+        fs::write(roms.join("b.sfc"), vec![0u8; 60]).expect("write rom");
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, emulator_id)
+             VALUES ('pc', 'PC', ?1, 'game.exe', '2026-01-01T00:00:00Z', NULL),
+                    ('rom', 'ROM', ?2, 'a.sfc', '2026-01-01T00:00:00Z', 'snes'),
+                    ('gone', 'Gone', ?3, 'gone.exe', '2026-01-01T00:00:00Z', NULL)",
+            params![
+                game_dir.join("game.exe").to_string_lossy(),
+                roms.join("a.sfc").to_string_lossy(),
+                dir.path()
+                    .join("missing")
+                    .join("gone.exe")
+                    .to_string_lossy(),
+            ],
+        )
+        .expect("insert games");
+        let db = ConnectionDb::new(conn);
+
+        assert_eq!(compute_install_size(&db, "pc", false), Ok(1000));
+        assert_eq!(compute_install_size(&db, "rom", false), Ok(40));
+        assert!(compute_install_size(&db, "gone", false).is_err());
+        assert!(compute_install_size(&db, "unknown", false).is_err());
+
+        // Cached until refreshed.
+        fs::write(game_dir.join("data").join("pak1.pak"), vec![0u8; 500]).expect("write pak");
+        assert_eq!(compute_install_size(&db, "pc", false), Ok(1000));
+        assert_eq!(compute_install_size(&db, "pc", true), Ok(1500));
+
+        let now = Utc::now();
+        assert_eq!(refresh_stale_install_sizes(&db, now), Ok(0));
+        let next_week = now + chrono::Duration::hours(INSTALL_SIZE_MAX_AGE_HOURS + 1);
+        assert_eq!(refresh_stale_install_sizes(&db, next_week), Ok(2));
+    }
+}
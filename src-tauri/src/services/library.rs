@@ -0,0 +1,591 @@
+//! Library snapshots as JSON: games, tags, custom fields, settings and playtime history, for
+//! moving to another machine or keeping a versioned copy. Backups, launch profiles and other
+//! per-machine setup are not part of it.
+
+use crate::backup::naming::validate_name_template;
+use crate::db::Db;
+use crate::domain::games::{
+    DeleteGameOptions, ImportStrategy, LibraryExportOptions, LibraryImportSummary,
+};
+use crate::services::games::delete_game_rows;
+use chrono::Utc;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+pub const LIBRARY_FORMAT_VERSION: u32 = 1;
+/// Same token the save manifests use for the home folder.
+const HOME_TOKEN: &str = "<home>";
+
+/// Parents first, so an import can map ids before the rows that refer to them.
+const EXPORTED_TABLES: [&str; 12] = [
+    "tags",
+    "custom_fields",
+    "settings",
+    "games",
+    "game_tags",
+    "game_custom_values",
+    "game_notes",
+    "game_links",
+    "play_sessions",
+    "playtime_daily",
+    "playtime_monthly",
+    "playtime_adjustments",
+];
+/// Per-game history; it is only imported for games the import creates, so importing the
+/// same file twice doesn't count the hours twice.
+const HISTORY_TABLES: [&str; 4] = [
+    "play_sessions",
+    "playtime_daily",
+    "playtime_monthly",
+    "playtime_adjustments",
+];
+/// Settings that are secrets rather than preferences.
+const PRIVATE_SETTINGS: [&str; 1] = ["rawg_api_key"];
+/// Game columns a merge leaves alone because they describe this machine.
+const MACHINE_GAME_COLUMNS: [&str; 5] = ["id", "exe_path", "exe_name", "working_dir", "save_path"];
+
+type Row = Map<String, Value>;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LibrarySnapshot {
+    format_version: u32,
+    exported_at: String,
+    tables: BTreeMap<String, Vec<Row>>,
+}
+
+/// Columns that only make sense on the machine that wrote them.
+fn skipped_columns(table: &str) -> &'static [&'static str] {
+    match table {
+        "games" => &[
+            "exe_size",
+            "cover_thumbnail",
+            "install_size",
+            "install_size_checked_at",
+            "last_backup",
+            "backup_count",
+            "save_path_checked",
+        ],
+        // Session ids are local row numbers; the importing database hands out its own.
+        "play_sessions" => &["id", "backup_id"],
+        _ => &[],
+    }
+}
+
+fn path_columns(table: &str) -> &'static [&'static str] {
+    match table {
+        "games" => &[
+            "exe_path",
+            "working_dir",
+            "save_path",
+            "cover_image",
+            "background_image",
+        ],
+        "settings" => &["value"],
+        _ => &[],
+    }
+}
+
+fn tokenise_path(value: &str, home: &str) -> Option<String> {
+    let rest = value.strip_prefix(home)?;
+    (rest.is_empty() || rest.starts_with(['/', '\\'])).then(|| format!("{HOME_TOKEN}{rest}"))
+}
+
+fn rewrite_paths(table: &str, row: &mut Row, rewrite: impl Fn(&str) -> Option<String>) {
+    for column in path_columns(table) {
+        let Some(Value::String(value)) = row.get_mut(*column) else {
+            continue;
+        };
+        if let Some(rewritten) = rewrite(value) {
+            *value = rewritten;
+        }
+    }
+}
+
+fn dump_table(conn: &Connection, table: &str) -> Result<Vec<Row>> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {table} ORDER BY rowid"))?;
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let skipped = skipped_columns(table);
+    let rows = stmt
+        .query_map([], |row| {
+            let mut map = Row::new();
+            for (index, name) in names.iter().enumerate() {
+                if skipped.contains(&name.as_str()) {
+                    continue;
+                }
+                let value = match row.get_ref(index)? {
+                    ValueRef::Null => Value::Null,
+                    ValueRef::Integer(value) => Value::from(value),
+                    ValueRef::Real(value) => Value::from(value),
+                    ValueRef::Text(value) => Value::from(String::from_utf8_lossy(value)),
+                    ValueRef::Blob(_) => continue,
+                };
+                map.insert(name.clone(), value);
+            }
+            Ok(map)
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
+/// Writes the library to `path`; returns the number of games in it.
+pub fn export_library<D: Db>(
+    db: &D,
+    path: &Path,
+    options: LibraryExportOptions,
+) -> Result<usize, String> {
+    export_library_with_home(db, path, options, dirs::home_dir().as_deref())
+}
+
+fn export_library_with_home<D: Db>(
+    db: &D,
+    path: &Path,
+    options: LibraryExportOptions,
+    home: Option<&Path>,
+) -> Result<usize, String> {
+    let home = home
+        .filter(|_| options.tokenise_paths)
+        .map(|home| home.to_string_lossy().to_string());
+    let tables = db
+        .with_conn(|conn| {
+            let mut tables = BTreeMap::new();
+            for table in EXPORTED_TABLES {
+                let mut rows = dump_table(conn, table)?;
+                if table == "settings" {
+                    rows.retain(|row| {
+                        !row.get("key")
+                            .and_then(Value::as_str)
+                            .is_some_and(|key| PRIVATE_SETTINGS.contains(&key))
+                    });
+                }
+                if let Some(home) = &home {
+                    for row in &mut rows {
+                        rewrite_paths(table, row, |value| tokenise_path(value, home));
+                    }
+                }
+                tables.insert(table.to_string(), rows);
+            }
+            Ok(tables)
+        })
+        .map_err(|e| e.to_string())?;
+    let games = tables.get("games").map_or(0, Vec::len);
+
+    let snapshot = LibrarySnapshot {
+        format_version: LIBRARY_FORMAT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        tables,
+    };
+    let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("Не удалось записать {}: {}", path.display(), e))?;
+    Ok(games)
+}
+
+/// Loads a file written by [`export_library`] in one transaction.
+pub fn import_library<D: Db>(
+    db: &D,
+    path: &Path,
+    strategy: ImportStrategy,
+) -> Result<LibraryImportSummary, String> {
+    import_library_with_home(db, path, strategy, dirs::home_dir().as_deref())
+}
+
+fn import_library_with_home<D: Db>(
+    db: &D,
+    path: &Path,
+    strategy: ImportStrategy,
+    home: Option<&Path>,
+) -> Result<LibraryImportSummary, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Не удалось прочитать {}: {}", path.display(), e))?;
+    let snapshot: LibrarySnapshot = serde_json::from_str(&text)
+        .map_err(|e| format!("Файл не похож на экспорт библиотеки: {}", e))?;
+    if snapshot.format_version > LIBRARY_FORMAT_VERSION {
+        return Err("Файл создан более новой версией Arrancador".to_string());
+    }
+    let home = home.map(|home| home.to_string_lossy().to_string());
+
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let summary = apply_snapshot(&tx, snapshot.tables, strategy, home.as_deref())?;
+        tx.commit()?;
+        Ok(summary)
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn apply_snapshot(
+    conn: &Connection,
+    mut tables: BTreeMap<String, Vec<Row>>,
+    strategy: ImportStrategy,
+    home: Option<&str>,
+) -> Result<LibraryImportSummary> {
+    let mut take = |table: &str| -> Vec<Row> {
+        let mut rows = tables.remove(table).unwrap_or_default();
+        for row in &mut rows {
+            rewrite_paths(table, row, |value| {
+                let rest = value.strip_prefix(HOME_TOKEN)?;
+                home.map(|home| format!("{home}{rest}"))
+            });
+        }
+        rows
+    };
+
+    if strategy == ImportStrategy::Replace {
+        let ids: Vec<String> = conn
+            .prepare("SELECT id FROM games")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_>>()?;
+        let options = DeleteGameOptions {
+            delete_backups: false,
+            delete_stats: true,
+        };
+        for id in &ids {
+            delete_game_rows(conn, id, options)?;
+        }
+        conn.execute("DELETE FROM tags", [])?;
+        conn.execute("DELETE FROM custom_fields", [])?;
+    }
+
+    let tag_ids = import_named(conn, "tags", take("tags"))?;
+    let field_ids = import_named(conn, "custom_fields", take("custom_fields"))?;
+    let settings_verb = match strategy {
+        ImportStrategy::SkipExisting => "INSERT OR IGNORE",
+        _ => "INSERT OR REPLACE",
+    };
+    for row in take("settings") {
+        // A template that names backups without a date would break sorting and re-import.
+        let template = (row.get("key").and_then(Value::as_str) == Some("backup_name_template"))
+            .then(|| row.get("value").and_then(Value::as_str).unwrap_or_default());
+        if template.is_some_and(|template| validate_name_template(template).is_err()) {
+            continue;
+        }
+        insert_row(conn, "settings", &row, settings_verb)?;
+    }
+
+    let mut summary = LibraryImportSummary::default();
+    // File game id -> id in this library.
+    let mut game_ids: HashMap<String, String> = HashMap::new();
+    let mut created: HashSet<String> = HashSet::new();
+    for row in take("games") {
+        let (Some(file_id), Some(exe_path)) = (
+            row.get("id").and_then(Value::as_str),
+            row.get("exe_path").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        // The same install may have been added again on this machine under a new id.
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT id FROM games WHERE id = ?1 OR exe_path = ?2
+                 ORDER BY id = ?1 DESC LIMIT 1",
+                params![file_id, exe_path],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match existing {
+            None => {
+                insert_row(conn, "games", &row, "INSERT")?;
+                game_ids.insert(file_id.to_string(), file_id.to_string());
+                created.insert(file_id.to_string());
+                summary.games_added += 1;
+            }
+            Some(_) if strategy == ImportStrategy::SkipExisting => summary.games_skipped += 1,
+            Some(local_id) => {
+                merge_game(conn, &local_id, &row)?;
+                game_ids.insert(file_id.to_string(), local_id);
+                summary.games_updated += 1;
+            }
+        }
+    }
+
+    for table in &EXPORTED_TABLES[4..] {
+        let history = HISTORY_TABLES.contains(table);
+        for mut row in take(table) {
+            let Some(game_id) = row
+                .get("game_id")
+                .and_then(Value::as_str)
+                .and_then(|id| game_ids.get(id))
+                .cloned()
+            else {
+                continue;
+            };
+            if history && !created.contains(&game_id) {
+                continue;
+            }
+            row.insert("game_id".to_string(), Value::from(game_id));
+            let remapped = match *table {
+                "game_tags" => remap(&mut row, "tag_id", &tag_ids),
+                "game_custom_values" => remap(&mut row, "field_id", &field_ids),
+                _ => true,
+            };
+            if !remapped {
+                continue;
+            }
+            let verb = if *table == "game_custom_values" && strategy == ImportStrategy::Merge {
+                "INSERT OR REPLACE"
+            } else {
+                "INSERT OR IGNORE"
+            };
+            insert_row(conn, table, &row, verb)?;
+        }
+    }
+    Ok(summary)
+}
+
+fn remap(row: &mut Row, column: &str, ids: &HashMap<String, String>) -> bool {
+    match row
+        .get(column)
+        .and_then(Value::as_str)
+        .and_then(|id| ids.get(id))
+    {
+        Some(local) => {
+            row.insert(column.to_string(), Value::from(local.clone()));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Tags and custom fields are matched by name; returns file id -> id in this library.
+fn import_named(conn: &Connection, table: &str, rows: Vec<Row>) -> Result<HashMap<String, String>> {
+    let mut ids = HashMap::new();
+    for row in rows {
+        let (Some(id), Some(name)) = (
+            row.get("id").and_then(Value::as_str),
+            row.get("name").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        let existing: Option<String> = conn
+            .query_row(
+                &format!("SELECT id FROM {table} WHERE name = ?1 COLLATE NOCASE"),
+                params![name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let local = match existing {
+            Some(local) => local,
+            None => {
+                insert_row(conn, table, &row, "INSERT OR IGNORE")?;
+                id.to_string()
+            }
+        };
+        ids.insert(id.to_string(), local);
+    }
+    Ok(ids)
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<_>>()?;
+    Ok(columns)
+}
+
+fn sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(flag) => SqlValue::Integer(*flag as i64),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => SqlValue::Integer(integer),
+            None => SqlValue::Real(number.as_f64().unwrap_or_default()),
+        },
+        Value::String(text) => SqlValue::Text(text.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+/// Inserts the columns of `row` this database knows, so files from older or newer versions
+/// still load.
+fn insert_row(conn: &Connection, table: &str, row: &Row, verb: &str) -> Result<usize> {
+    let known = table_columns(conn, table)?;
+    let skipped = skipped_columns(table);
+    let (columns, values): (Vec<&str>, Vec<SqlValue>) = row
+        .iter()
+        .filter(|(column, _)| known.contains(*column) && !skipped.contains(&column.as_str()))
+        .map(|(column, value)| (column.as_str(), sql_value(value)))
+        .unzip();
+    if columns.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = vec!["?"; columns.len()].join(", ");
+    conn.execute(
+        &format!(
+            "{verb} INTO {table} ({}) VALUES ({placeholders})",
+            columns.join(", ")
+        ),
+        params_from_iter(values),
+    )
+}
+
+/// Takes the file's metadata and settings for a game already here, keeping this machine's
+/// paths; playtime, launch count and last played keep whichever side is further along.
+fn merge_game(conn: &Connection, local_id: &str, row: &Row) -> Result<usize> {
+    let known = table_columns(conn, "games")?;
+    let skipped = skipped_columns("games");
+    let mut assignments = Vec::new();
+    let mut values = Vec::new();
+    for (column, value) in row {
+        let column = column.as_str();
+        if !known.contains(column)
+            || skipped.contains(&column)
+            || MACHINE_GAME_COLUMNS.contains(&column)
+        {
+            continue;
+        }
+        assignments.push(match column {
+            "total_playtime" | "play_count" => {
+                format!("{column} = MAX(COALESCE({column}, 0), COALESCE(?, 0))")
+            }
+            "last_played" => {
+                "last_played = NULLIF(MAX(COALESCE(last_played, ''), COALESCE(?, '')), '')"
+                    .to_string()
+            }
+            _ => format!("{column} = ?"),
+        });
+        values.push(sql_value(value));
+    }
+    if assignments.is_empty() {
+        return Ok(0);
+    }
+    values.push(SqlValue::Text(local_id.to_string()));
+    conn.execute(
+        &format!("UPDATE games SET {} WHERE id = ?", assignments.join(", ")),
+        params_from_iter(values),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+
+    fn library() -> ConnectionDb {
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        ConnectionDb::new(conn)
+    }
+
+    fn run(db: &ConnectionDb, sql: &str) {
+        db.with_conn(|conn| conn.execute_batch(sql))
+            .expect("run sql");
+    }
+
+    fn query<T: rusqlite::types::FromSql>(db: &ConnectionDb, sql: &str) -> T {
+        db.with_conn(|conn| conn.query_row(sql, [], |row| row.get(0)))
+            .expect("query")
+    }
+
+    #[test]
+    fn library_round_trips_between_machines() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let file = dir.path().join("library.json");
+
+        let source = library();
+        run(
+            &source,
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, total_playtime, exe_size)
+             VALUES ('g1', 'Arcadia', '/home/ana/Games/arcadia.exe', 'arcadia.exe',
+                     '2026-01-01T00:00:00Z', 7200, 1234),
+                    ('g2', 'Zelda', '/mnt/games/zelda.exe', 'zelda.exe',
+                     '2026-01-02T00:00:00Z', 600, NULL);
+             INSERT INTO tags (id, name, created_at) VALUES ('t1', 'Co-op', '2026-01-01');
+             INSERT INTO game_tags (game_id, tag_id) VALUES ('g1', 't1');
+             INSERT INTO playtime_daily (game_id, date, seconds) VALUES ('g1', '2026-01-05', 7200);
+             INSERT INTO settings (key, value) VALUES
+                 ('theme', 'dark'), ('rawg_api_key', 'secret'),
+                 ('backup_directory', '/home/ana/Backups');",
+        );
+        let exported = export_library_with_home(
+            &source,
+            &file,
+            LibraryExportOptions {
+                tokenise_paths: true,
+            },
+            Some(Path::new("/home/ana")),
+        )
+        .expect("export");
+        assert_eq!(exported, 2);
+        let text = fs::read_to_string(&file).expect("read export");
+        assert!(text.contains("<home>/Games/arcadia.exe"));
+        assert!(!text.contains("secret"));
+        assert!(!text.contains("exe_size"));
+
+        let target = library();
+        run(
+            &target,
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, total_playtime)
+             VALUES ('local', 'Zelda (old)', '/mnt/games/zelda.exe', 'zelda.exe',
+                     '2025-12-01T00:00:00Z', 900);
+             INSERT INTO tags (id, name, created_at) VALUES ('t-local', 'co-op', '2025-12-01');",
+        );
+        let summary = import_library_with_home(
+            &target,
+            &file,
+            ImportStrategy::Merge,
+            Some(Path::new("/home/bo")),
+        )
+        .expect("import");
+        assert_eq!(
+            summary,
+            LibraryImportSummary {
+                games_added: 1,
+                games_updated: 1,
+                games_skipped: 0,
+            }
+        );
+        let exe: String = query(&target, "SELECT exe_path FROM games WHERE id = 'g1'");
+        assert_eq!(exe, "/home/bo/Games/arcadia.exe");
+        let tag: String = query(&target, "SELECT tag_id FROM game_tags WHERE game_id = 'g1'");
+        assert_eq!(tag, "t-local");
+        let daily: i64 = query(
+            &target,
+            "SELECT seconds FROM playtime_daily WHERE game_id = 'g1'",
+        );
+        assert_eq!(daily, 7200);
+        let (name, playtime): (String, i64) = target
+            .with_conn(|conn| {
+                conn.query_row(
+                    "SELECT name, total_playtime FROM games WHERE id = 'local'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+            })
+            .expect("merged game");
+        assert_eq!((name.as_str(), playtime), ("Zelda", 900));
+        let backups: String = query(
+            &target,
+            "SELECT value FROM settings WHERE key = 'backup_directory'",
+        );
+        assert_eq!(backups, "/home/bo/Backups");
+
+        // A second import adds nothing and doesn't double the history.
+        let again = import_library_with_home(
+            &target,
+            &file,
+            ImportStrategy::SkipExisting,
+            Some(Path::new("/home/bo")),
+        )
+        .expect("import again");
+        assert_eq!(again.games_skipped, 2);
+        let daily: i64 = query(&target, "SELECT SUM(seconds) FROM playtime_daily");
+        assert_eq!(daily, 7200);
+
+        let replaced = import_library_with_home(
+            &target,
+            &file,
+            ImportStrategy::Replace,
+            Some(Path::new("/home/bo")),
+        )
+        .expect("replace");
+        assert_eq!(replaced.games_added, 2);
+        let games: i64 = query(&target, "SELECT COUNT(*) FROM games");
+        assert_eq!(games, 2);
+    }
+}
@@ -1,3 +1,19 @@
+pub mod auto_scan;
+pub mod exe_info;
+pub mod file_verify;
 pub mod fs;
 pub mod games;
+pub mod http_cache;
+pub mod images;
+pub mod install_size;
+pub mod library;
+pub mod net_path;
+pub mod notes;
+pub mod report;
+pub mod scan_cache;
+pub mod scan_filter;
+pub mod scan_groups;
+pub mod scan_watch;
+pub mod sessions;
+pub mod tags;
 pub mod tracker;
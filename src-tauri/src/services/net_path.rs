@@ -0,0 +1,179 @@
+//! Network folders (`\\NAS\games` shares and mapped network drives) for scans. Reads on a
+//! dead share can block for minutes, so anything that might touch one goes through a helper
+//! thread with a timeout instead of blocking the scan thread.
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long the reachability check waits for a share to answer.
+pub const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest wait for the next entry of a network walk before the folder counts as stalled.
+pub const NETWORK_ENTRY_TIMEOUT: Duration = Duration::from_secs(30);
+/// Read threads per network folder; shares get slower, not faster, with many readers.
+pub const NETWORK_THREADS: usize = 2;
+/// How often a waiting walk checks whether the scan was cancelled.
+const STOP_POLL: Duration = Duration::from_millis(200);
+
+/// `\\server\share\...` and `//server/share/...` paths.
+pub fn is_unc_path(path: &Path) -> bool {
+    let text = path.to_string_lossy();
+    text.starts_with(r"\\") || text.starts_with("//")
+}
+
+/// UNC paths, and on Windows drive letters mapped to a network share.
+pub fn is_network_path(path: &Path) -> bool {
+    is_unc_path(path) || is_mapped_network_drive(path)
+}
+
+#[cfg(target_os = "windows")]
+fn is_mapped_network_drive(path: &Path) -> bool {
+    use std::path::Component;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+
+    const DRIVE_REMOTE: u32 = 4;
+    let Some(Component::Prefix(prefix)) = path.components().next() else {
+        return false;
+    };
+    let root: Vec<u16> = format!("{}\\", prefix.as_os_str().to_string_lossy())
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe { GetDriveTypeW(PCWSTR(root.as_ptr())) == DRIVE_REMOTE }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_mapped_network_drive(_path: &Path) -> bool {
+    false
+}
+
+/// Checks that `path` is a readable folder, giving up after `timeout`.
+pub fn check_reachable(path: &Path, timeout: Duration) -> Result<(), String> {
+    let (tx, rx) = mpsc::channel();
+    let probe = path.to_path_buf();
+    thread::spawn(move || {
+        let result = fs::read_dir(&probe).map(|_| ());
+        let _ = tx.send(result);
+    });
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => Err(format!("{} is not reachable: {}", path.display(), e)),
+        Err(_) => Err(format!(
+            "{} did not respond within {} s",
+            path.display(),
+            timeout.as_secs()
+        )),
+    }
+}
+
+/// Probes network folders with `check_reachable`; local paths are not checked.
+pub fn ensure_reachable(path: &Path) -> Result<(), String> {
+    if is_network_path(path) {
+        check_reachable(path, REACHABILITY_TIMEOUT)
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs `iter` on a helper thread and yields its items until one takes longer than `timeout`
+/// to arrive. After a timeout the iterator ends and `timed_out` reports it; the helper thread
+/// is left to finish (or stay blocked) on its own.
+pub struct TimedIter<T> {
+    rx: mpsc::Receiver<T>,
+    timeout: Duration,
+    timed_out: bool,
+}
+
+impl<T: Send + 'static> TimedIter<T> {
+    pub fn new<I>(iter: I, timeout: Duration) -> Self
+    where
+        I: IntoIterator<Item = T> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::sync_channel(256);
+        thread::spawn(move || {
+            for item in iter {
+                if tx.send(item).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            rx,
+            timeout,
+            timed_out: false,
+        }
+    }
+}
+
+impl<T> TimedIter<T> {
+    /// Whether the iterator ended because the next item took too long.
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Like `next`, but also ends as soon as `stop` is set instead of waiting out the timeout.
+    pub fn next_unless(&mut self, stop: &AtomicBool) -> Option<T> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return None;
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                self.timed_out = true;
+                return None;
+            }
+            match self.rx.recv_timeout(left.min(STOP_POLL)) {
+                Ok(item) => return Some(item),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+}
+
+impl<T> Iterator for TimedIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.next_unless(&AtomicBool::new(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stalled_walks_and_missing_folders_give_up_instead_of_hanging() {
+        assert!(is_unc_path(Path::new(r"\\NAS\games")));
+        assert!(is_unc_path(Path::new("//nas/games")));
+        assert!(!is_unc_path(Path::new(r"C:\Games")));
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        assert!(check_reachable(dir.path(), REACHABILITY_TIMEOUT).is_ok());
+        assert!(check_reachable(&dir.path().join("missing"), REACHABILITY_TIMEOUT).is_err());
+
+        let items: Vec<u32> = TimedIter::new(0..3, Duration::from_secs(1)).collect();
+        assert_eq!(items, [0, 1, 2]);
+
+        let slow = (0..3).map(|n| {
+            if n == 1 {
+                thread::sleep(Duration::from_millis(500));
+            }
+            n
+        });
+        let mut timed = TimedIter::new(slow.clone(), Duration::from_millis(50));
+        assert_eq!(timed.next(), Some(0));
+        assert_eq!(timed.next(), None);
+        assert!(timed.timed_out());
+
+        let mut cancelled = TimedIter::new(slow, Duration::from_secs(60));
+        assert_eq!(cancelled.next_unless(&AtomicBool::new(true)), None);
+        assert!(!cancelled.timed_out());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::db::Db;
+use crate::domain::notes::{GameLink, GameNote};
+use chrono::Utc;
+use rusqlite::{params, Result};
+use uuid::Uuid;
+
+const NOTE_SELECT: &str = "SELECT id, game_id, body, created_at, updated_at FROM game_notes";
+const LINK_SELECT: &str = "SELECT id, game_id, title, url, created_at FROM game_links";
+
+fn map_note_row(row: &rusqlite::Row) -> Result<GameNote> {
+    Ok(GameNote {
+        id: row.get(0)?,
+        game_id: row.get(1)?,
+        body: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+fn map_link_row(row: &rusqlite::Row) -> Result<GameLink> {
+    Ok(GameLink {
+        id: row.get(0)?,
+        game_id: row.get(1)?,
+        title: row.get(2)?,
+        url: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+fn note_body(body: String) -> Result<String, String> {
+    let body = body.trim().to_string();
+    if body.is_empty() {
+        return Err("Заметка не может быть пустой".to_string());
+    }
+    Ok(body)
+}
+
+/// The game's notes, newest first.
+pub fn get_game_notes<D: Db>(db: &D, game_id: String) -> Result<Vec<GameNote>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{NOTE_SELECT} WHERE game_id = ?1 ORDER BY created_at DESC"
+        ))?;
+        let notes = stmt
+            .query_map(params![game_id], map_note_row)?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(notes)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn add_game_note<D: Db>(db: &D, game_id: String, body: String) -> Result<GameNote, String> {
+    let body = note_body(body)?;
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO game_notes (id, game_id, body, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)",
+            params![id, game_id, body, now],
+        )?;
+        conn.query_row(
+            &format!("{NOTE_SELECT} WHERE id = ?1"),
+            params![id],
+            map_note_row,
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn update_game_note<D: Db>(db: &D, id: String, body: String) -> Result<GameNote, String> {
+    let body = note_body(body)?;
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE game_notes SET body = ?1, updated_at = ?2 WHERE id = ?3",
+            params![body, Utc::now().to_rfc3339(), id],
+        )?;
+        conn.query_row(
+            &format!("{NOTE_SELECT} WHERE id = ?1"),
+            params![id],
+            map_note_row,
+        )
+    })
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => "Заметка не найдена".to_string(),
+        e => e.to_string(),
+    })
+}
+
+pub fn delete_game_note<D: Db>(db: &D, id: String) -> Result<(), String> {
+    db.with_conn(|conn| conn.execute("DELETE FROM game_notes WHERE id = ?1", params![id]))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// The game's links in the order they were added.
+pub fn get_game_links<D: Db>(db: &D, game_id: String) -> Result<Vec<GameLink>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{LINK_SELECT} WHERE game_id = ?1 ORDER BY created_at ASC"
+        ))?;
+        let links = stmt
+            .query_map(params![game_id], map_link_row)?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(links)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Adds a web link; only `http` and `https` URLs are accepted since they are opened in
+/// the browser.
+pub fn add_game_link<D: Db>(
+    db: &D,
+    game_id: String,
+    url: String,
+    title: Option<String>,
+) -> Result<GameLink, String> {
+    let url = url.trim().to_string();
+    let valid = url.split_once("://").is_some_and(|(scheme, rest)| {
+        matches!(scheme.to_ascii_lowercase().as_str(), "http" | "https")
+            && !rest.is_empty()
+            && !rest.contains(char::is_whitespace)
+    });
+    if !valid {
+        return Err("Ссылка должна начинаться с http:// или https://".to_string());
+    }
+    let title = title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+    let id = Uuid::new_v4().to_string();
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO game_links (id, game_id, title, url, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, game_id, title, url, Utc::now().to_rfc3339()],
+        )?;
+        conn.query_row(
+            &format!("{LINK_SELECT} WHERE id = ?1"),
+            params![id],
+            map_link_row,
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn delete_game_link<D: Db>(db: &D, id: String) -> Result<(), String> {
+    db.with_conn(|conn| conn.execute("DELETE FROM game_links WHERE id = ?1", params![id]))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+
+    #[test]
+    fn notes_and_links_are_kept_per_game() {
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+             VALUES ('g1', 'Game', 'C:/Games/game.exe', 'game.exe', '2026-01-01T00:00:00Z')",
+            [],
+        )
+        .expect("insert game");
+        let db = ConnectionDb::new(conn);
+
+        assert!(add_game_note(&db, "g1".to_string(), "   ".to_string()).is_err());
+        let first = add_game_note(&db, "g1".to_string(), "Beat the **first** boss".to_string())
+            .expect("add note");
+        let second = add_game_note(&db, "g1".to_string(), "Try the stealth build".to_string())
+            .expect("add note");
+        let edited = update_game_note(&db, first.id.clone(), " Beat both bosses ".to_string())
+            .expect("edit note");
+        assert_eq!(edited.body, "Beat both bosses");
+        assert_eq!(edited.created_at, first.created_at);
+        assert!(update_game_note(&db, "missing".to_string(), "x".to_string()).is_err());
+
+        let notes = get_game_notes(&db, "g1".to_string()).expect("notes");
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].id, second.id);
+        delete_game_note(&db, second.id).expect("delete note");
+        assert_eq!(
+            get_game_notes(&db, "g1".to_string()).expect("notes").len(),
+            1
+        );
+
+        assert!(add_game_link(&db, "g1".to_string(), "file:///C:/x".to_string(), None).is_err());
+        assert!(add_game_link(&db, "g1".to_string(), "https://".to_string(), None).is_err());
+        let wiki = add_game_link(
+            &db,
+            "g1".to_string(),
+            " https://pcgamingwiki.com/wiki/Game ".to_string(),
+            Some("  ".to_string()),
+        )
+        .expect("add link");
+        assert_eq!(wiki.url, "https://pcgamingwiki.com/wiki/Game");
+        assert_eq!(wiki.title, None);
+        add_game_link(
+            &db,
+            "g1".to_string(),
+            "https://www.nexusmods.com/game".to_string(),
+            Some("Nexus".to_string()),
+        )
+        .expect("add link");
+        let links = get_game_links(&db, "g1".to_string()).expect("links");
+        assert_eq!(links.len(), 2);
+        delete_game_link(&db, wiki.id).expect("delete link");
+        let links = get_game_links(&db, "g1".to_string()).expect("links");
+        assert_eq!(links[0].title.as_deref(), Some("Nexus"));
+    }
+}
@@ -0,0 +1,398 @@
+//! Human-readable library reports (HTML or Markdown) for sharing or archiving outside the
+//! app. Local cover files are embedded in the HTML so the report stays self-contained.
+
+use crate::db::Db;
+use crate::domain::games::{Game, ReportFormat};
+use crate::services::games as games_service;
+use chrono::{DateTime, Local};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Covers bigger than this are linked instead of embedded to keep the report shareable.
+const MAX_EMBEDDED_COVER_BYTES: u64 = 2 * 1024 * 1024;
+
+fn format_playtime(seconds: i64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{} ч {} мин", hours, minutes)
+    } else {
+        format!("{} мин", minutes)
+    }
+}
+
+fn format_date(value: &str) -> String {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Local).format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+fn backup_status(game: &Game) -> String {
+    if !game.backup_enabled {
+        return "выключен".to_string();
+    }
+    match &game.last_backup {
+        Some(last) => format!(
+            "включен · копий: {} · последняя {}",
+            game.backup_count,
+            format_date(last)
+        ),
+        None => "включен · копий пока нет".to_string(),
+    }
+}
+
+fn rating_line(game: &Game) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(rating) = game.user_rating {
+        parts.push(format!("моя оценка {}/7", rating));
+    }
+    if let Some(metacritic) = game.metacritic {
+        parts.push(format!("Metacritic {}", metacritic));
+    }
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+fn cover_source(game: &Game) -> Option<&str> {
+    game.cover_image
+        .as_deref()
+        .or(game.background_image.as_deref())
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// A `data:` URI for local cover files, the original value for URLs.
+fn html_cover_src(source: &str) -> Option<String> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return Some(source.to_string());
+    }
+    let path = Path::new(source);
+    let size = fs::metadata(path).ok()?.len();
+    if size > MAX_EMBEDDED_COVER_BYTES {
+        return Some(format!("file:///{}", source.replace('\\', "/")));
+    }
+    let mime = match path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        _ => "image/jpeg",
+    };
+    let bytes = fs::read(path).ok()?;
+    Some(format!("data:{};base64,{}", mime, base64(&bytes)))
+}
+
+fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_markdown(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '#' | '<' | '>' | '|'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn sorted(games: &[Game]) -> Vec<&Game> {
+    let mut games: Vec<&Game> = games.iter().collect();
+    games.sort_by_key(|game| game.name.to_lowercase());
+    games
+}
+
+fn summary_line(games: &[Game]) -> String {
+    let playtime: i64 = games.iter().map(|game| game.total_playtime).sum();
+    format!(
+        "Игр: {} · общее время: {}",
+        games.len(),
+        format_playtime(playtime)
+    )
+}
+
+fn details(game: &Game) -> Vec<(&'static str, String)> {
+    let mut rows = vec![(
+        "Время в игре",
+        format!(
+            "{} · запусков: {}",
+            format_playtime(game.total_playtime),
+            game.play_count
+        ),
+    )];
+    if let Some(last) = &game.last_played {
+        rows.push(("Последний запуск", format_date(last)));
+    }
+    if let Some(genres) = game.genres.as_deref().filter(|g| !g.is_empty()) {
+        rows.push(("Жанры", genres.to_string()));
+    }
+    if let Some(rating) = rating_line(game) {
+        rows.push(("Оценка", rating));
+    }
+    rows.push(("Бэкап", backup_status(game)));
+    rows
+}
+
+fn release_year(game: &Game) -> Option<&str> {
+    game.released.as_deref().and_then(|date| date.get(..4))
+}
+
+pub fn render_markdown(games: &[Game], generated: DateTime<Local>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Библиотека Arrancador\n");
+    let _ = writeln!(
+        out,
+        "_{} · {}_\n",
+        generated.format("%d.%m.%Y %H:%M"),
+        summary_line(games)
+    );
+    for game in sorted(games) {
+        let favorite = if game.is_favorite { " ★" } else { "" };
+        match release_year(game) {
+            Some(year) => {
+                let _ = writeln!(
+                    out,
+                    "## {} ({}){}\n",
+                    escape_markdown(&game.name),
+                    year,
+                    favorite
+                );
+            }
+            None => {
+                let _ = writeln!(out, "## {}{}\n", escape_markdown(&game.name), favorite);
+            }
+        }
+        if let Some(cover) = cover_source(game) {
+            let target = if cover.starts_with("http") {
+                cover.to_string()
+            } else {
+                format!("file:///{}", cover.replace('\\', "/"))
+            };
+            let _ = writeln!(out, "![]({})\n", target.replace(' ', "%20"));
+        }
+        for (label, value) in details(game) {
+            let _ = writeln!(out, "- **{}:** {}", label, escape_markdown(&value));
+        }
+        if let Some(note) = game.user_note.as_deref().filter(|n| !n.trim().is_empty()) {
+            out.push('\n');
+            for line in note.trim().lines() {
+                let _ = writeln!(out, "> {}", escape_markdown(line));
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn render_html(games: &[Game], generated: DateTime<Local>) -> String {
+    let mut out = String::new();
+    out.push_str(
+        "<!DOCTYPE html>\n<html lang=\"ru\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Библиотека Arrancador</title>\n<style>\n\
+         body{font-family:system-ui,sans-serif;background:#0f1117;color:#e6e6e6;margin:2rem auto;max-width:960px;padding:0 1rem}\n\
+         .game{display:flex;gap:1rem;padding:1rem 0;border-bottom:1px solid #2a2d36}\n\
+         .game img{width:120px;height:160px;object-fit:cover;border-radius:8px;flex-shrink:0}\n\
+         .game h2{margin:0 0 .5rem;font-size:1.2rem}\n\
+         dl{display:grid;grid-template-columns:max-content 1fr;gap:.2rem 1rem;margin:0;font-size:.9rem}\n\
+         dt{color:#9aa0ad}dd{margin:0}\n\
+         blockquote{margin:.5rem 0 0;padding-left:.75rem;border-left:3px solid #3b82f6;color:#c9cdd6;white-space:pre-wrap}\n\
+         .muted{color:#9aa0ad}\n\
+         </style>\n</head>\n<body>\n",
+    );
+    let _ = writeln!(out, "<h1>Библиотека Arrancador</h1>");
+    let _ = writeln!(
+        out,
+        "<p class=\"muted\">{} · {}</p>",
+        generated.format("%d.%m.%Y %H:%M"),
+        escape_html(&summary_line(games))
+    );
+    for game in sorted(games) {
+        out.push_str("<section class=\"game\">\n");
+        if let Some(src) = cover_source(game).and_then(html_cover_src) {
+            let _ = writeln!(out, "<img src=\"{}\" alt=\"\">", escape_html(&src));
+        }
+        out.push_str("<div>\n");
+        let year = release_year(game)
+            .map(|year| format!(" <span class=\"muted\">({})</span>", year))
+            .unwrap_or_default();
+        let favorite = if game.is_favorite { " ★" } else { "" };
+        let _ = writeln!(
+            out,
+            "<h2>{}{}{}</h2>\n<dl>",
+            escape_html(&game.name),
+            year,
+            favorite
+        );
+        for (label, value) in details(game) {
+            let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", label, escape_html(&value));
+        }
+        out.push_str("</dl>\n");
+        if let Some(note) = game.user_note.as_deref().filter(|n| !n.trim().is_empty()) {
+            let _ = writeln!(out, "<blockquote>{}</blockquote>", escape_html(note.trim()));
+        }
+        out.push_str("</div>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+pub fn render(games: &[Game], format: ReportFormat, generated: DateTime<Local>) -> String {
+    match format {
+        ReportFormat::Html => render_html(games, generated),
+        ReportFormat::Markdown => render_markdown(games, generated),
+    }
+}
+
+/// Writes a report of the whole library to `path`; returns how many games it lists.
+pub fn export_library_report<D: Db>(
+    db: &D,
+    path: &Path,
+    format: ReportFormat,
+) -> Result<usize, String> {
+    let games = games_service::get_all_games(db, false)?;
+    let report = render(&games, format, Local::now());
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, report)
+        .map_err(|e| format!("Не удалось сохранить отчёт {}: {}", path.display(), e))?;
+    Ok(games.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    fn game(name: &str) -> Game {
+        Game {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            exe_path: format!("C:/Games/{}/game.exe", name),
+            exe_name: "game.exe".to_string(),
+            rawg_id: None,
+            description: None,
+            released: None,
+            background_image: None,
+            metacritic: None,
+            rating: None,
+            genres: None,
+            platforms: None,
+            developers: None,
+            publishers: None,
+            cover_image: None,
+            is_favorite: false,
+            play_count: 0,
+            total_playtime: 0,
+            last_played: None,
+            date_added: "2026-01-01T00:00:00Z".to_string(),
+            backup_enabled: false,
+            last_backup: None,
+            backup_count: 0,
+            save_path: None,
+            restore_on_launch: false,
+            backup_on_exit: false,
+            do_not_track: false,
+            user_rating: None,
+            user_note: None,
+            launch_args: None,
+            working_dir: None,
+            env_vars: BTreeMap::new(),
+            launch_method: Default::default(),
+            store_id: None,
+            emulator_id: None,
+            is_hidden: false,
+            last_launch_failed: false,
+            install_size: None,
+            instance_policy: None,
+            igdb_id: None,
+            metadata_source: None,
+            protondb_tier: None,
+            steam_deck_status: None,
+            locked_fields: Vec::new(),
+            cover_thumbnail: None,
+            tags: Vec::new(),
+            custom_fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn reports_list_games_by_name_with_escaped_notes_and_embedded_covers() {
+        let dir = tempdir().expect("tempdir");
+        let cover = dir.path().join("cover.png");
+        fs::write(&cover, b"PNG").expect("write cover");
+
+        let mut zelda = game("Zelda <Remake>");
+        zelda.total_playtime = 3 * 3600 + 20 * 60;
+        zelda.play_count = 4;
+        zelda.user_rating = Some(6);
+        zelda.released = Some("2023-05-12".to_string());
+        zelda.user_note = Some("Best *dungeon* & boss".to_string());
+        zelda.cover_image = Some(cover.to_string_lossy().to_string());
+        let mut arcadia = game("Arcadia");
+        arcadia.backup_enabled = true;
+        arcadia.backup_count = 2;
+        arcadia.last_backup = Some("2026-03-01T10:00:00Z".to_string());
+        arcadia.cover_image = Some("https://media.example/arcadia.jpg".to_string());
+        let games = vec![zelda, arcadia];
+        let generated = Local::now();
+
+        let markdown = render(&games, ReportFormat::Markdown, generated);
+        let arcadia_at = markdown.find("## Arcadia").expect("arcadia heading");
+        let zelda_at = markdown
+            .find("## Zelda \\<Remake\\> (2023)")
+            .expect("zelda heading");
+        assert!(arcadia_at < zelda_at);
+        assert!(markdown.contains("Игр: 2 · общее время: 3 ч 20 мин"));
+        assert!(markdown.contains("- **Оценка:** моя оценка 6/7"));
+        assert!(markdown.contains("> Best \\*dungeon\\* & boss"));
+        assert!(markdown.contains("![](https://media.example/arcadia.jpg)"));
+        assert!(markdown.contains("- **Бэкап:** выключен"));
+
+        let html = render(&games, ReportFormat::Html, generated);
+        assert!(html.contains("<h2>Zelda &lt;Remake&gt;"));
+        assert!(html.contains("<blockquote>Best *dungeon* &amp; boss</blockquote>"));
+        assert!(html.contains("src=\"data:image/png;base64,UE5H\""));
+        assert!(html.contains("включен · копий: 2"));
+
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(base64(b"Man"), "TWFu");
+    }
+}
@@ -0,0 +1,242 @@
+//! Incremental folder scans. Every directory under a scanned root is remembered with its
+//! mtime, and every candidate file with the directory it sits in. A directory whose mtime is
+//! unchanged has the same entries as last time, so its files and subfolders come from the
+//! cache and only changed directories are read again.
+
+use crate::db::Db;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::UNIX_EPOCH;
+
+/// What changed under a root since its previous scan.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ScanDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Directories that had to be read again.
+    pub walked_dirs: usize,
+    /// Directories taken from the cache.
+    pub cached_dirs: usize,
+}
+
+fn mtime_nanos(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    i64::try_from(nanos).ok()
+}
+
+fn path_key(path: &Path) -> String {
+    path.display().to_string()
+}
+
+fn parent_key(path: &str) -> String {
+    Path::new(path).parent().map(path_key).unwrap_or_default()
+}
+
+/// Walks `root`, reading only directories whose mtime changed since the last scan, and
+/// returns the candidate files that appeared or disappeared. Files are candidates when
+/// `is_candidate` accepts them; hidden folders are skipped like in a full scan. A cancelled
+/// scan leaves the cache as it was and reports nothing.
+pub fn incremental_scan<D: Db>(
+    db: &D,
+    root: &Path,
+    is_candidate: impl Fn(&Path) -> bool,
+    cancel_flag: &AtomicBool,
+) -> Result<ScanDiff, String> {
+    let root_key = path_key(root);
+    let (cached_dirs, cached_files): (HashMap<String, i64>, Vec<String>) = db
+        .with_conn(|conn| {
+            let mut stmt =
+                conn.prepare("SELECT path, is_dir, mtime FROM scan_cache WHERE root = ?1")?;
+            let rows = stmt
+                .query_map(params![root_key], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, bool>(1)?,
+                        row.get::<_, i64>(2)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let mut dirs = HashMap::new();
+            let mut files = Vec::new();
+            for (path, is_dir, mtime) in rows {
+                if is_dir {
+                    dirs.insert(path, mtime);
+                } else {
+                    files.push(path);
+                }
+            }
+            Ok((dirs, files))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut children: HashMap<String, (Vec<String>, Vec<String>)> = HashMap::new();
+    for dir in cached_dirs.keys().filter(|dir| **dir != root_key) {
+        children
+            .entry(parent_key(dir))
+            .or_default()
+            .0
+            .push(dir.clone());
+    }
+    for file in &cached_files {
+        children
+            .entry(parent_key(file))
+            .or_default()
+            .1
+            .push(file.clone());
+    }
+
+    let mut diff = ScanDiff::default();
+    let mut dirs: Vec<(String, i64)> = Vec::new();
+    let mut files: BTreeSet<String> = BTreeSet::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Ok(ScanDiff::default());
+        }
+        let Some(mtime) = mtime_nanos(&dir) else {
+            continue;
+        };
+        let key = path_key(&dir);
+        if cached_dirs.get(&key) == Some(&mtime) {
+            diff.cached_dirs += 1;
+            if let Some((subdirs, dir_files)) = children.get(&key) {
+                stack.extend(subdirs.iter().map(PathBuf::from));
+                files.extend(dir_files.iter().cloned());
+            }
+        } else {
+            diff.walked_dirs += 1;
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                let path = entry.path();
+                if file_type.is_dir() {
+                    if !entry.file_name().to_string_lossy().starts_with('.') {
+                        stack.push(path);
+                    }
+                } else if file_type.is_file() && is_candidate(&path) {
+                    files.insert(path_key(&path));
+                }
+            }
+        }
+        dirs.push((key, mtime));
+    }
+
+    let previous: BTreeSet<String> = cached_files.into_iter().collect();
+    diff.added = files.difference(&previous).cloned().collect();
+    diff.removed = previous.difference(&files).cloned().collect();
+
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM scan_cache WHERE root = ?1", params![root_key])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO scan_cache (root, path, is_dir, mtime)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (dir, mtime) in &dirs {
+                insert.execute(params![root_key, dir, true, mtime])?;
+            }
+            for file in &files {
+                insert.execute(params![root_key, file, false, 0])?;
+            }
+        }
+        tx.commit()
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(diff)
+}
+
+/// Stamps `last_scanned` on the given `scan_directories` rows.
+pub fn mark_directories_scanned<D: Db>(
+    db: &D,
+    paths: &[String],
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    db.with_conn(|conn| {
+        for path in paths {
+            conn.execute(
+                "UPDATE scan_directories SET last_scanned = ?1 WHERE path = ?2",
+                params![now.to_rfc3339(), path],
+            )?;
+        }
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+
+    fn is_exe(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext == "exe")
+    }
+
+    fn names(paths: &[String]) -> Vec<String> {
+        paths
+            .iter()
+            .map(|path| {
+                Path::new(path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn only_changed_directories_are_walked_again() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let a = root.path().join("A");
+        let b = root.path().join("B");
+        fs::create_dir_all(&a).expect("create A");
+        fs::create_dir_all(b.join(".hidden")).expect("create B");
+        fs::write(a.join("a.exe"), b"exe").expect("write a");
+        fs::write(a.join("readme.txt"), b"txt").expect("write readme");
+        fs::write(b.join("b.exe"), b"exe").expect("write b");
+        fs::write(b.join(".hidden").join("h.exe"), b"exe").expect("write hidden");
+
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        let db = ConnectionDb::new(conn);
+        let cancel = AtomicBool::new(false);
+
+        let first = incremental_scan(&db, root.path(), is_exe, &cancel).expect("first scan");
+        assert_eq!(names(&first.added), ["a.exe", "b.exe"]);
+        assert!(first.removed.is_empty());
+        assert_eq!((first.walked_dirs, first.cached_dirs), (3, 0));
+
+        let unchanged = incremental_scan(&db, root.path(), is_exe, &cancel).expect("rescan");
+        assert!(unchanged.added.is_empty() && unchanged.removed.is_empty());
+        assert_eq!((unchanged.walked_dirs, unchanged.cached_dirs), (0, 3));
+
+        fs::remove_file(a.join("a.exe")).expect("remove a");
+        fs::write(a.join("c.exe"), b"exe").expect("write c");
+        let changed = incremental_scan(&db, root.path(), is_exe, &cancel).expect("rescan");
+        assert_eq!(names(&changed.added), ["c.exe"]);
+        assert_eq!(names(&changed.removed), ["a.exe"]);
+        assert_eq!(changed.cached_dirs, 2);
+        assert_eq!(changed.walked_dirs, 1);
+
+        fs::remove_dir_all(&b).expect("remove B");
+        let removed = incremental_scan(&db, root.path(), is_exe, &cancel).expect("rescan");
+        assert_eq!(names(&removed.removed), ["b.exe"]);
+
+        let cancelled = incremental_scan(&db, root.path(), is_exe, &AtomicBool::new(true))
+            .expect("cancelled scan");
+        assert_eq!(cancelled, ScanDiff::default());
+    }
+}
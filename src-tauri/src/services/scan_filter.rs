@@ -0,0 +1,228 @@
+//! Heuristics that keep installers, uninstallers, redistributables and crash handlers out
+//! of folder scans. The user can add their own patterns through the `scan_blacklist`
+//! setting, one per line.
+
+use crate::db::Db;
+use crate::services::exe_info::ExeVersionInfo;
+use glob::{MatchOptions, Pattern};
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+use std::path::{Component, Path};
+
+/// File names that are never the game, matched case-insensitively.
+const HELPER_NAMES: [&str; 24] = [
+    "unins*",
+    "uninstall*",
+    "*setup*",
+    "*installer*",
+    "vcredist*",
+    "vc_redist*",
+    "dxsetup*",
+    "dxwebsetup*",
+    "dotnet*",
+    "ndp*",
+    "oalinst*",
+    "physx*",
+    "ue4prereq*",
+    "ueprereq*",
+    "*crashhandler*",
+    "*crashreport*",
+    "crashpad*",
+    "*errorreport*",
+    "*bugreport*",
+    "easyanticheat*",
+    "battleye*",
+    "beservice*",
+    "cefprocess*",
+    "*webhelper*",
+];
+
+/// Folders that only hold installers and runtimes; anything below them is skipped.
+const HELPER_FOLDERS: [&str; 10] = [
+    "__installer",
+    "_commonredist",
+    "commonredist",
+    "_redist",
+    "redist",
+    "redistributables",
+    "directx",
+    "vcredist",
+    "easyanticheat",
+    "battleye",
+];
+
+/// Version resource descriptions that give a helper away under a neutral file name.
+const HELPER_DESCRIPTIONS: [&str; 6] = [
+    "installer",
+    "uninstall",
+    "setup",
+    "redistributable",
+    "crash report",
+    "crash handler",
+];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// Why a scanned file was taken for something other than a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanFilterReason {
+    /// The file name matches a known installer, runtime or crash handler.
+    HelperName,
+    /// The file sits in a folder such as `__Installer` or `_CommonRedist`.
+    HelperFolder,
+    /// The version resource describes it as an installer or crash reporter.
+    HelperDescription,
+    /// The file name or one of its folders matches a `scan_blacklist` pattern.
+    UserBlacklist,
+}
+
+pub struct ScanFilter {
+    helper_names: Vec<Pattern>,
+    user_patterns: Vec<Pattern>,
+}
+
+impl ScanFilter {
+    /// The built-in rules plus `user_patterns`; invalid patterns are ignored.
+    pub fn new(user_patterns: &[String]) -> Self {
+        let compile = |patterns: &mut dyn Iterator<Item = &str>| {
+            patterns
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .filter_map(|pattern| Pattern::new(pattern).ok())
+                .collect()
+        };
+        Self {
+            helper_names: compile(&mut HELPER_NAMES.iter().copied()),
+            user_patterns: compile(&mut user_patterns.iter().map(String::as_str)),
+        }
+    }
+
+    /// The built-in rules plus the patterns saved in `scan_blacklist`.
+    pub fn load<D: Db>(db: &D) -> Self {
+        let saved = db
+            .with_conn(|conn| {
+                conn.query_row(
+                    "SELECT value FROM settings WHERE key = 'scan_blacklist'",
+                    [],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()
+            })
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let patterns: Vec<String> = saved.lines().map(str::to_string).collect();
+        Self::new(&patterns)
+    }
+
+    /// Why the file at `rel_path` (relative to the scanned folder) should be left out, or
+    /// `None` when it may be a game. User patterns are checked against the file name and
+    /// each folder on the way to it.
+    pub fn reason(
+        &self,
+        rel_path: &Path,
+        info: Option<&ExeVersionInfo>,
+    ) -> Option<ScanFilterReason> {
+        let names: Vec<String> = rel_path
+            .components()
+            .filter_map(|part| match part {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        let (file_name, folders) = names.split_last()?;
+
+        let matches = |patterns: &[Pattern], name: &str| {
+            patterns.iter().any(|p| p.matches_with(name, MATCH_OPTIONS))
+        };
+        if names.iter().any(|name| matches(&self.user_patterns, name)) {
+            return Some(ScanFilterReason::UserBlacklist);
+        }
+        let stem = Path::new(file_name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if matches(&self.helper_names, &stem) {
+            return Some(ScanFilterReason::HelperName);
+        }
+        if folders
+            .iter()
+            .any(|folder| HELPER_FOLDERS.contains(&folder.to_lowercase().as_str()))
+        {
+            return Some(ScanFilterReason::HelperFolder);
+        }
+        let description = info
+            .and_then(|info| info.file_description.as_deref())
+            .map(str::to_lowercase);
+        if description.is_some_and(|description| {
+            HELPER_DESCRIPTIONS
+                .iter()
+                .any(|part| description.contains(part))
+        }) {
+            return Some(ScanFilterReason::HelperDescription);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn helpers_are_filtered_by_name_folder_description_and_user_patterns() {
+        let filter = ScanFilter::new(&["*Launcher*".to_string(), "Tools".to_string()]);
+        let reason = |path: &str| filter.reason(Path::new(path), None);
+
+        for name in [
+            "Game/unins000.exe",
+            "Game/UnityCrashHandler64.exe",
+            "vcredist_x64.exe",
+            "Game/DXSETUP.exe",
+            "Game/EasyAntiCheat_EOS_Setup.exe",
+        ] {
+            assert_eq!(reason(name), Some(ScanFilterReason::HelperName), "{name}");
+        }
+        assert_eq!(
+            reason("Game/__Installer/directx/redist/tool.exe"),
+            Some(ScanFilterReason::HelperFolder)
+        );
+        assert_eq!(
+            reason("Game/_CommonRedist/something.exe"),
+            Some(ScanFilterReason::HelperFolder)
+        );
+        assert_eq!(
+            reason("Game/GameLauncher.exe"),
+            Some(ScanFilterReason::UserBlacklist)
+        );
+        assert_eq!(
+            reason("Game/tools/editor.exe"),
+            Some(ScanFilterReason::UserBlacklist)
+        );
+        assert_eq!(reason("Hollow Knight/hollow_knight.exe"), None);
+        assert_eq!(reason("Celeste/Celeste.exe"), None);
+
+        let installer = ExeVersionInfo {
+            file_description: Some("Game Installer".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.reason(Path::new("Game/tool.exe"), Some(&installer)),
+            Some(ScanFilterReason::HelperDescription)
+        );
+        let game = ExeVersionInfo {
+            product_name: Some("Hollow Knight".to_string()),
+            file_description: Some("Hollow Knight".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.reason(Path::new("hollow_knight.exe"), Some(&game)),
+            None
+        );
+    }
+}
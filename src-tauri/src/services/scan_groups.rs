@@ -0,0 +1,213 @@
+//! Groups scan results by the game they probably belong to. Files are clustered by the
+//! top-level folder under the scanned folder, and each cluster gets a main binary so the UI
+//! can add one game per folder instead of every launcher, tool and crash handler in it.
+
+use crate::scan::ExeEntry;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path};
+
+/// Files that probably belong to one game.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ScanGroup {
+    /// The top-level folder the files share, or the file itself for loose files and ROMs.
+    pub dir: String,
+    /// Suggested game name: the folder name, or the product name / file stem of a loose file.
+    pub name: String,
+    /// The most likely main binary.
+    pub main_path: String,
+    /// Every file in the group, `main_path` included, sorted.
+    pub paths: Vec<String>,
+}
+
+/// Lowercase alphanumeric words of `text`.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// How much a file name looks like the folder name, from `0.0` to `1.0`.
+pub fn name_similarity(folder: &str, file_stem: &str) -> f64 {
+    let folder_words = words(folder);
+    let stem_words = words(file_stem);
+    let folder_joined = folder_words.concat();
+    let stem_joined = stem_words.concat();
+    if folder_joined.is_empty() || stem_joined.is_empty() {
+        return 0.0;
+    }
+    if folder_joined == stem_joined {
+        return 1.0;
+    }
+    if folder_joined.contains(&stem_joined) || stem_joined.contains(&folder_joined) {
+        return 0.8;
+    }
+    let folder_set: HashSet<&String> = folder_words.iter().collect();
+    let stem_set: HashSet<&String> = stem_words.iter().collect();
+    let shared = folder_set.intersection(&stem_set).count();
+    let total = folder_set.union(&stem_set).count();
+    0.6 * shared as f64 / total as f64
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Ranks `entry` as the main binary of a group named `folder`: name similarity counts most,
+/// then size relative to the biggest file, and shallower files win ties. Helpers the filter
+/// flagged only win when nothing else is left.
+fn main_score(entry: &ExeEntry, rel_depth: usize, folder: &str, max_size: u64) -> f64 {
+    let path = Path::new(&entry.path);
+    let similarity = name_similarity(folder, &file_stem(path)).max(
+        entry
+            .product_name
+            .as_deref()
+            .map(|product| name_similarity(folder, product))
+            .unwrap_or(0.0),
+    );
+    let size = if max_size == 0 {
+        0.0
+    } else {
+        entry.file_size as f64 / max_size as f64
+    };
+    let helper_penalty = if entry.filter_reason.is_some() {
+        10.0
+    } else {
+        0.0
+    };
+    2.0 * similarity + size - 0.1 * rel_depth as f64 - helper_penalty
+}
+
+/// Clusters `entries` by top-level folder under the folder they were scanned from
+/// (`source_dir`, or `root` when unset). Executables directly in that folder and ROMs stay on
+/// their own, since such folders usually hold many games.
+pub fn group_entries(root: &Path, entries: &[ExeEntry]) -> Vec<ScanGroup> {
+    let mut clusters: BTreeMap<String, (String, Vec<(&ExeEntry, usize)>)> = BTreeMap::new();
+    for entry in entries {
+        let base = entry.source_dir.as_deref().map(Path::new).unwrap_or(root);
+        let path = Path::new(&entry.path);
+        let rel = path.strip_prefix(base).unwrap_or(path);
+        let parts: Vec<String> = rel
+            .components()
+            .filter_map(|part| match part {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        let (key, name) = if parts.len() > 1 && entry.emulator_id.is_none() {
+            let folder = &parts[0];
+            (base.join(folder).display().to_string(), folder.clone())
+        } else {
+            let name = entry
+                .product_name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| file_stem(path));
+            (entry.path.clone(), name)
+        };
+        clusters
+            .entry(key)
+            .or_insert_with(|| (name, Vec::new()))
+            .1
+            .push((entry, parts.len().saturating_sub(2)));
+    }
+
+    clusters
+        .into_iter()
+        .filter_map(|(dir, (name, members))| {
+            let max_size = members
+                .iter()
+                .map(|(entry, _)| entry.file_size)
+                .max()
+                .unwrap_or(0);
+            let main = members
+                .iter()
+                .map(|(entry, depth)| (main_score(entry, *depth, &name, max_size), entry))
+                .max_by(|(a_score, a), (b_score, b)| {
+                    a_score.total_cmp(b_score).then_with(|| b.path.cmp(&a.path))
+                })?
+                .1;
+            let mut paths: Vec<String> = members
+                .iter()
+                .map(|(entry, _)| entry.path.clone())
+                .collect();
+            paths.sort();
+            Some(ScanGroup {
+                dir,
+                name,
+                main_path: main.path.clone(),
+                paths,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::scan_filter::ScanFilterReason;
+
+    fn entry(path: &str, size: u64) -> ExeEntry {
+        ExeEntry {
+            path: path.to_string(),
+            file_name: Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            emulator_id: None,
+            file_size: size,
+            product_name: None,
+            company_name: None,
+            file_version: None,
+            filter_reason: None,
+            source_dir: None,
+        }
+    }
+
+    #[test]
+    fn files_are_grouped_per_game_folder_with_a_likely_main_binary() {
+        let mut crash = entry("/games/Hollow Knight/tools/CrashReporter.exe", 90_000_000);
+        crash.filter_reason = Some(ScanFilterReason::HelperName);
+        let mut rom = entry("/games/snes/zelda.sfc", 1_000);
+        rom.emulator_id = Some("snes9x".to_string());
+        let entries = vec![
+            entry("/games/Hollow Knight/hollow_knight.exe", 600_000),
+            entry("/games/Hollow Knight/UnityCrashHandler64.exe", 1_400_000),
+            crash,
+            entry("/games/Witcher 3/bin/x64/witcher3.exe", 50_000_000),
+            entry("/games/Witcher 3/launcher.exe", 8_000_000),
+            entry("/games/portable.exe", 3_000),
+            rom,
+        ];
+
+        let groups = group_entries(Path::new("/games"), &entries);
+        let mains: Vec<(&str, &str)> = groups
+            .iter()
+            .map(|group| (group.name.as_str(), group.main_path.as_str()))
+            .collect();
+        assert_eq!(
+            mains,
+            [
+                ("Hollow Knight", "/games/Hollow Knight/hollow_knight.exe"),
+                ("Witcher 3", "/games/Witcher 3/bin/x64/witcher3.exe"),
+                ("portable", "/games/portable.exe"),
+                ("zelda", "/games/snes/zelda.sfc"),
+            ]
+        );
+        assert_eq!(groups[0].paths.len(), 3);
+        assert_eq!(
+            groups[0].dir,
+            Path::new("/games")
+                .join("Hollow Knight")
+                .display()
+                .to_string()
+        );
+
+        assert_eq!(name_similarity("Hollow Knight", "hollow_knight"), 1.0);
+        assert_eq!(name_similarity("The Witcher 3", "witcher3"), 0.8);
+        assert_eq!(name_similarity("Hades", "launcher"), 0.0);
+    }
+}
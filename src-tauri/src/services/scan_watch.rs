@@ -0,0 +1,292 @@
+//! Watches the auto-scan folders so a game copied or installed into one is reported right
+//! away instead of at the next scheduled auto-scan. Changes are debounced per folder, then the
+//! folder goes through the same incremental scan and filters as `auto_scan` and any new games
+//! are announced as `scan:new-games-found`.
+
+use crate::db::Db;
+use crate::services::auto_scan::{self, NewGamesFound};
+use crate::services::net_path;
+use crate::services::scan_cache;
+use crate::services::scan_filter::ScanFilter;
+use chrono::Utc;
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Quiet period after the last change before a folder is rescanned; installers and copies
+/// create many files in a row.
+pub const DEFAULT_DEBOUNCE_SECONDS: i64 = 15;
+/// How often the watched folders are compared with `scan_directories`.
+const RESYNC_INTERVAL: Duration = Duration::from_secs(60);
+const TICK: Duration = Duration::from_secs(1);
+
+/// Collects changes per watched folder and releases each one once it has been quiet for the
+/// debounce period.
+#[derive(Debug)]
+pub struct ScanWatchDebouncer {
+    quiet: Duration,
+    pending: HashMap<PathBuf, Instant>,
+}
+
+impl ScanWatchDebouncer {
+    pub fn new(quiet: Duration) -> Self {
+        Self {
+            quiet,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn set_quiet(&mut self, quiet: Duration) {
+        self.quiet = quiet;
+    }
+
+    pub fn record(&mut self, root: &Path, now: Instant) {
+        self.pending.insert(root.to_path_buf(), now);
+    }
+
+    /// Folders whose last change is at least the quiet period old, removed from the pending set.
+    pub fn take_due(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut due: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, last)| now.duration_since(**last) >= self.quiet)
+            .map(|(root, _)| root.clone())
+            .collect();
+        for root in &due {
+            self.pending.remove(root);
+        }
+        due.sort();
+        due
+    }
+}
+
+/// The watched folder containing `path`, preferring the deepest one when folders nest.
+pub fn root_for_path<'a>(roots: &'a [PathBuf], path: &Path) -> Option<&'a Path> {
+    roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())
+        .map(PathBuf::as_path)
+}
+
+/// Whether an event can mean a new game: something created or renamed that is a folder or
+/// has a scanned extension. Writes to existing files (logs, saves) are ignored.
+fn is_relevant(kind: &EventKind, path: &Path, extensions: &HashSet<String>) -> bool {
+    if !matches!(
+        kind,
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+    ) {
+        return false;
+    }
+    path.is_dir()
+        || path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| extensions.contains(&ext))
+}
+
+/// Auto-scan folders that exist on disk, leaving out network shares that do not answer.
+fn watched_roots<D: Db>(db: &D) -> Vec<PathBuf> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare("SELECT path FROM scan_directories WHERE auto_scan = 1")?;
+        let paths = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(paths)
+    })
+    .unwrap_or_default()
+    .into_iter()
+    .map(PathBuf::from)
+    .filter(|path| net_path::ensure_reachable(path).is_ok() && path.is_dir())
+    .collect()
+}
+
+/// Brings the watcher in line with `wanted`, leaving already-watched folders alone.
+fn sync_watches(
+    watcher: &mut RecommendedWatcher,
+    watched: &mut Vec<PathBuf>,
+    wanted: Vec<PathBuf>,
+) {
+    for root in watched.iter() {
+        if !wanted.contains(root) {
+            let _ = watcher.unwatch(root);
+        }
+    }
+    watched.retain(|root| wanted.contains(root));
+    for root in wanted {
+        if watched.contains(&root) {
+            continue;
+        }
+        match watcher.watch(&root, RecursiveMode::Recursive) {
+            Ok(()) => watched.push(root),
+            Err(e) => eprintln!("Failed to watch scan folder {}: {}", root.display(), e),
+        }
+    }
+}
+
+/// Rescans `root` after it changed and returns the new games found there, if any.
+pub fn rescan_changed<D: Db>(db: &D, root: &Path) -> Result<Option<NewGamesFound>, String> {
+    let dir = root.display().to_string();
+    let paths = auto_scan::find_new_games(db, &dir, &ScanFilter::load(db))?;
+    scan_cache::mark_directories_scanned(db, std::slice::from_ref(&dir), Utc::now())?;
+    Ok((!paths.is_empty()).then_some(NewGamesFound {
+        source_dir: dir,
+        paths,
+    }))
+}
+
+pub fn start_scan_watcher<D: Db + Send + 'static>(db: D, app: AppHandle) {
+    thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Scan watcher unavailable: {}", e);
+                return;
+            }
+        };
+        let mut watched: Vec<PathBuf> = Vec::new();
+        let mut extensions: HashSet<String> = HashSet::new();
+        let mut debouncer = ScanWatchDebouncer::new(Duration::ZERO);
+        let mut last_sync: Option<Instant> = None;
+
+        loop {
+            if last_sync.is_none_or(|at| at.elapsed() >= RESYNC_INTERVAL) {
+                sync_watches(&mut watcher, &mut watched, watched_roots(&db));
+                extensions = auto_scan::candidate_extensions(&db).unwrap_or_default();
+                let seconds = auto_scan::setting_i64(
+                    &db,
+                    "scan_watch_debounce_seconds",
+                    DEFAULT_DEBOUNCE_SECONDS,
+                );
+                debouncer.set_quiet(Duration::from_secs(seconds.max(1) as u64));
+                last_sync = Some(Instant::now());
+            }
+
+            match rx.recv_timeout(TICK) {
+                Ok(Ok(event)) => {
+                    let now = Instant::now();
+                    for path in &event.paths {
+                        if !is_relevant(&event.kind, path, &extensions) {
+                            continue;
+                        }
+                        if let Some(root) = root_for_path(&watched, path) {
+                            debouncer.record(root, now);
+                        }
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Ok(Err(e)) => eprintln!("Scan watcher error: {}", e),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            }
+
+            for root in debouncer.take_due(Instant::now()) {
+                match rescan_changed(&db, &root) {
+                    Ok(Some(found)) => {
+                        let _ = app.emit("scan:new-games-found", found);
+                    }
+                    Ok(None) => {}
+                    Err(e) => eprintln!("Rescan of {} failed: {}", root.display(), e),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use notify::event::{CreateKind, DataChange, RenameMode};
+    use rusqlite::{params, Connection};
+    use std::fs;
+
+    #[test]
+    fn new_files_in_a_quiet_folder_are_reported_once() {
+        let roots = vec![PathBuf::from("/games"), PathBuf::from("/games/emu")];
+        assert_eq!(
+            root_for_path(&roots, Path::new("/games/emu/rom.sfc")),
+            Some(Path::new("/games/emu"))
+        );
+        assert_eq!(root_for_path(&roots, Path::new("/other/a.exe")), None);
+
+        let extensions: HashSet<String> = ["exe".to_string()].into();
+        let create = EventKind::Create(CreateKind::File);
+        assert!(is_relevant(
+            &create,
+            Path::new("/games/New/Game.EXE"),
+            &extensions
+        ));
+        assert!(!is_relevant(
+            &create,
+            Path::new("/games/New/log.txt"),
+            &extensions
+        ));
+        assert!(is_relevant(
+            &EventKind::Modify(ModifyKind::Name(RenameMode::To)),
+            Path::new("/games/New/game.exe"),
+            &extensions
+        ));
+        assert!(!is_relevant(
+            &EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+            Path::new("/games/New/game.exe"),
+            &extensions
+        ));
+
+        let start = Instant::now();
+        let mut debouncer = ScanWatchDebouncer::new(Duration::from_secs(10));
+        debouncer.record(Path::new("/games"), start);
+        debouncer.record(Path::new("/games"), start + Duration::from_secs(6));
+        debouncer.record(Path::new("/games/emu"), start + Duration::from_secs(2));
+        assert_eq!(
+            debouncer.take_due(start + Duration::from_secs(12)),
+            [PathBuf::from("/games/emu")]
+        );
+        assert_eq!(
+            debouncer.take_due(start + Duration::from_secs(16)),
+            [PathBuf::from("/games")]
+        );
+        assert!(debouncer
+            .take_due(start + Duration::from_secs(60))
+            .is_empty());
+    }
+
+    #[test]
+    fn rescanning_a_changed_folder_reports_only_games_new_since_the_last_scan() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        fs::create_dir_all(dir.path().join("Old")).expect("create old");
+        fs::write(dir.path().join("Old").join("old.exe"), b"exe").expect("write old");
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute(
+            "INSERT INTO scan_directories (path, auto_scan) VALUES (?1, 1)",
+            params![dir.path().display().to_string()],
+        )
+        .expect("insert dir");
+        let db = ConnectionDb::new(conn);
+        assert_eq!(watched_roots(&db), [dir.path().to_path_buf()]);
+
+        let first = rescan_changed(&db, dir.path())
+            .expect("first rescan")
+            .expect("old game is new to the cache");
+        assert_eq!(first.paths.len(), 1);
+
+        fs::create_dir_all(dir.path().join("New")).expect("create new");
+        fs::write(dir.path().join("New").join("new.exe"), b"exe").expect("write new");
+        fs::write(dir.path().join("New").join("setup.exe"), b"exe").expect("write setup");
+        let found = rescan_changed(&db, dir.path())
+            .expect("second rescan")
+            .expect("new game");
+        assert_eq!(
+            found.paths,
+            [dir.path().join("New").join("new.exe").display().to_string()]
+        );
+        assert!(rescan_changed(&db, dir.path()).expect("third").is_none());
+    }
+}
@@ -0,0 +1,648 @@
+use crate::db::Db;
+use crate::domain::sessions::{
+    LaunchOutcome, LaunchRecord, PlaySession, SessionExitReason, SessionOrigin,
+};
+use crate::stats::StatsRange;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Result};
+use std::path::Path;
+
+pub const DEFAULT_NOTE_PROMPT_MINUTES: i64 = 60;
+pub const DEFAULT_CRASH_THRESHOLD_SECONDS: i64 = 15;
+/// How long a launched game has to show up in the process list before the launch counts
+/// as failed; store launches can sit behind an update for a while.
+const LAUNCH_DETECT_SECONDS: i64 = 120;
+const SEARCH_LIMIT: i64 = 200;
+const LAUNCH_HISTORY_LIMIT: i64 = 100;
+const GAME_SESSIONS_LIMIT: i64 = 500;
+const RECENT_SESSIONS_LIMIT: i64 = 50;
+
+const SESSION_COLUMNS: &str = "s.id, s.game_id, COALESCE(g.name, ''), s.started_at, s.ended_at,
+     s.duration_seconds, s.note, s.backup_id, s.launch_profile, s.store, s.exit_reason";
+
+fn map_session_row(row: &rusqlite::Row) -> Result<PlaySession> {
+    Ok(PlaySession {
+        id: row.get(0)?,
+        game_id: row.get(1)?,
+        game_name: row.get(2)?,
+        started_at: row.get(3)?,
+        ended_at: row.get(4)?,
+        duration_seconds: row.get(5)?,
+        note: row.get(6)?,
+        backup_id: row.get(7)?,
+        launch_profile: row.get(8)?,
+        store: row.get(9)?,
+        exit_reason: row
+            .get::<_, Option<String>>(10)?
+            .and_then(|reason| SessionExitReason::parse(&reason)),
+    })
+}
+
+/// Stores a finished session and returns its id.
+pub fn record_session<D: Db>(
+    db: &D,
+    game_id: &str,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    origin: &SessionOrigin,
+    exit_reason: SessionExitReason,
+) -> Result<i64, String> {
+    let duration = (ended_at - started_at).num_seconds().max(0);
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO play_sessions
+                 (game_id, started_at, ended_at, duration_seconds, launch_profile, store,
+                  exit_reason)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                game_id,
+                started_at.to_rfc3339(),
+                ended_at.to_rfc3339(),
+                duration,
+                origin.launch_profile,
+                origin.store,
+                exit_reason.as_str()
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Guesses which storefront installed the game from its exe path: library folder names
+/// first, then the marker files GOG (`goggame-*.info`) and Epic (`.egstore`) leave in the
+/// game root.
+pub fn detect_store(exe_path: &Path) -> Option<&'static str> {
+    let lower = exe_path.to_string_lossy().to_lowercase().replace('\\', "/");
+    if lower.contains("/steamapps/") {
+        return Some("steam");
+    }
+    if lower.contains("/gog galaxy/") || lower.contains("/gog games/") {
+        return Some("gog");
+    }
+    if lower.contains("/epic games/") {
+        return Some("epic");
+    }
+    if lower.contains("/xboxgames/") || lower.contains("/windowsapps/") {
+        return Some("xbox");
+    }
+
+    for dir in exe_path.ancestors().skip(1).take(3) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if name.starts_with("goggame-") && name.ends_with(".info") {
+                return Some("gog");
+            }
+            if name == ".egstore" {
+                return Some("epic");
+            }
+        }
+    }
+    None
+}
+
+/// Minimum session length that triggers the note prompt; `None` when prompts are off
+/// (`session_note_prompt_minutes` set to 0).
+pub fn note_prompt_threshold<D: Db>(db: &D) -> Option<i64> {
+    let minutes = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT value FROM settings WHERE key = 'session_note_prompt_minutes'",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+        })
+        .ok()
+        .flatten()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_NOTE_PROMPT_MINUTES);
+    (minutes > 0).then_some(minutes * 60)
+}
+
+/// Attaches the exit auto backup to its session. A note entered before the backup
+/// finished is copied onto the backup.
+pub fn link_session_backup<D: Db>(db: &D, session_id: i64, backup_id: &str) -> Result<(), String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE play_sessions SET backup_id = ?1 WHERE id = ?2",
+            params![backup_id, session_id],
+        )?;
+        conn.execute(
+            "UPDATE backups SET notes = (SELECT note FROM play_sessions WHERE id = ?1)
+             WHERE id = ?2 AND (SELECT note FROM play_sessions WHERE id = ?1) IS NOT NULL",
+            params![session_id, backup_id],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Saves (or clears, when blank) the note of a session and mirrors it onto the
+/// session's exit auto backup. Clearing only removes the backup note the session put
+/// there; a note the backup was created with is left alone.
+pub fn set_session_note<D: Db>(
+    db: &D,
+    session_id: i64,
+    note: Option<String>,
+) -> Result<PlaySession, String> {
+    let note = note
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let previous: Option<String> = tx.query_row(
+            "SELECT note FROM play_sessions WHERE id = ?1",
+            params![session_id],
+            |row| row.get(0),
+        )?;
+        tx.execute(
+            "UPDATE play_sessions SET note = ?1 WHERE id = ?2",
+            params![note, session_id],
+        )?;
+        match &note {
+            Some(note) => tx.execute(
+                "UPDATE backups SET notes = ?1
+                 WHERE id = (SELECT backup_id FROM play_sessions WHERE id = ?2)",
+                params![note, session_id],
+            )?,
+            None => tx.execute(
+                "UPDATE backups SET notes = NULL
+                 WHERE id = (SELECT backup_id FROM play_sessions WHERE id = ?1)
+                   AND notes IS ?2",
+                params![session_id, previous],
+            )?,
+        };
+        let session = tx.query_row(
+            &format!(
+                "SELECT {} FROM play_sessions s LEFT JOIN games g ON g.id = s.game_id
+                 WHERE s.id = ?1",
+                SESSION_COLUMNS
+            ),
+            params![session_id],
+            map_session_row,
+        )?;
+        tx.commit()?;
+        Ok(session)
+    })
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => "Сессия не найдена".to_string(),
+        e => e.to_string(),
+    })
+}
+
+/// Sessions of one game, newest first. `range` bounds the start date (`YYYY-MM-DD`,
+/// inclusive); either end may be left open.
+pub fn get_game_sessions<D: Db>(
+    db: &D,
+    game_id: String,
+    range: Option<StatsRange>,
+) -> Result<Vec<PlaySession>, String> {
+    let range = range.unwrap_or_default();
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM play_sessions s LEFT JOIN games g ON g.id = s.game_id
+             WHERE s.game_id = ?1
+               AND (?2 IS NULL OR substr(s.started_at, 1, 10) >= ?2)
+               AND (?3 IS NULL OR substr(s.started_at, 1, 10) <= ?3)
+             ORDER BY s.started_at DESC
+             LIMIT ?4",
+            SESSION_COLUMNS
+        ))?;
+        let sessions = stmt
+            .query_map(
+                params![game_id, range.start, range.end, GAME_SESSIONS_LIMIT],
+                map_session_row,
+            )?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(sessions)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// The latest sessions across the library, newest first; hidden games are left out.
+pub fn get_recent_sessions<D: Db>(db: &D) -> Result<Vec<PlaySession>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM play_sessions s JOIN games g ON g.id = s.game_id
+             WHERE COALESCE(g.is_hidden, 0) = 0
+             ORDER BY s.ended_at DESC
+             LIMIT ?1",
+            SESSION_COLUMNS
+        ))?;
+        let sessions = stmt
+            .query_map(params![RECENT_SESSIONS_LIMIT], map_session_row)?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(sessions)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Sessions with a note, newest first. A non-empty `query` matches the note or the
+/// game name; `game_id` narrows the search to one game.
+pub fn search_session_notes<D: Db>(
+    db: &D,
+    query: String,
+    game_id: Option<String>,
+) -> Result<Vec<PlaySession>, String> {
+    let pattern = format!("%{}%", query.trim());
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM play_sessions s LEFT JOIN games g ON g.id = s.game_id
+             WHERE s.note IS NOT NULL
+               AND (s.note LIKE ?1 OR g.name LIKE ?1)
+               AND (?2 IS NULL OR s.game_id = ?2)
+             ORDER BY s.ended_at DESC
+             LIMIT ?3",
+            SESSION_COLUMNS
+        ))?;
+        let sessions = stmt
+            .query_map(params![pattern, game_id, SEARCH_LIMIT], map_session_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(sessions)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Stores a launch attempt: `pending` until the tracker sees the process, or `failed`
+/// with `error` when it could not be started at all.
+pub fn record_launch<D: Db>(db: &D, game_id: &str, error: Option<&str>) -> Result<i64, String> {
+    let outcome = if error.is_some() {
+        LaunchOutcome::Failed
+    } else {
+        LaunchOutcome::Pending
+    };
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO launches (game_id, started_at, outcome, error) VALUES (?1, ?2, ?3, ?4)",
+            params![game_id, Utc::now().to_rfc3339(), outcome.as_str(), error],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Called by the tracker when a game's process appears.
+pub fn mark_launch_running<D: Db>(db: &D, game_id: &str) -> Result<(), String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE launches SET outcome = 'running'
+             WHERE id = (SELECT id FROM launches WHERE game_id = ?1 AND outcome = 'pending'
+                         ORDER BY started_at DESC, id DESC LIMIT 1)",
+            params![game_id],
+        )
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Called by the tracker when a game exits. A run shorter than the crash threshold
+/// (`crash_threshold_seconds`, 0 turns it off) counts as a crash. Returns the outcome, or
+/// `None` when the game was not launched from Arrancador.
+pub fn finish_launch<D: Db>(
+    db: &D,
+    game_id: &str,
+    ended_at: DateTime<Utc>,
+) -> Result<Option<LaunchOutcome>, String> {
+    let threshold = crash_threshold(db);
+    db.with_conn(|conn| {
+        let launch = conn
+            .query_row(
+                "SELECT id, started_at FROM launches
+                 WHERE game_id = ?1 AND outcome IN ('pending', 'running')
+                 ORDER BY started_at DESC, id DESC LIMIT 1",
+                params![game_id],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        let Some((id, started_at)) = launch else {
+            return Ok(None);
+        };
+        let duration = DateTime::parse_from_rfc3339(&started_at)
+            .map(|started| {
+                (ended_at - started.with_timezone(&Utc))
+                    .num_seconds()
+                    .max(0)
+            })
+            .unwrap_or(0);
+        let outcome = match threshold {
+            Some(threshold) if duration < threshold => LaunchOutcome::Crashed,
+            _ => LaunchOutcome::Completed,
+        };
+        conn.execute(
+            "UPDATE launches SET ended_at = ?1, duration_seconds = ?2, outcome = ?3 WHERE id = ?4",
+            params![ended_at.to_rfc3339(), duration, outcome.as_str(), id],
+        )?;
+        Ok(Some(outcome))
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Marks launches whose process never showed up as failed.
+pub fn expire_pending_launches<D: Db>(db: &D, now: DateTime<Utc>) -> Result<usize, String> {
+    let cutoff = (now - chrono::Duration::seconds(LAUNCH_DETECT_SECONDS)).to_rfc3339();
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE launches SET outcome = 'failed'
+             WHERE outcome = 'pending' AND started_at < ?1",
+            params![cutoff],
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn crash_threshold<D: Db>(db: &D) -> Option<i64> {
+    let seconds = db
+        .with_conn(|conn| {
+            conn.query_row(
+                "SELECT value FROM settings WHERE key = 'crash_threshold_seconds'",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+        })
+        .ok()
+        .flatten()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_CRASH_THRESHOLD_SECONDS);
+    (seconds > 0).then_some(seconds)
+}
+
+/// The game's launches, newest first.
+pub fn get_launch_history<D: Db>(db: &D, game_id: String) -> Result<Vec<LaunchRecord>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, game_id, started_at, ended_at, duration_seconds, outcome, error
+             FROM launches WHERE game_id = ?1
+             ORDER BY started_at DESC, id DESC LIMIT ?2",
+        )?;
+        let launches = stmt
+            .query_map(params![game_id, LAUNCH_HISTORY_LIMIT], |row| {
+                Ok(LaunchRecord {
+                    id: row.get(0)?,
+                    game_id: row.get(1)?,
+                    started_at: row.get(2)?,
+                    ended_at: row.get(3)?,
+                    duration_seconds: row.get(4)?,
+                    outcome: LaunchOutcome::parse(&row.get::<_, String>(5)?),
+                    error: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(launches)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+
+    fn setup_db() -> ConnectionDb {
+        let conn = Connection::open_in_memory().expect("open db");
+        conn.execute_batch(
+            "CREATE TABLE games (id TEXT PRIMARY KEY, name TEXT NOT NULL, is_hidden INTEGER);
+             CREATE TABLE settings (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+             CREATE TABLE backups (id TEXT PRIMARY KEY, game_id TEXT NOT NULL, notes TEXT);
+             CREATE TABLE play_sessions (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 game_id TEXT NOT NULL,
+                 started_at TEXT NOT NULL,
+                 ended_at TEXT NOT NULL,
+                 duration_seconds INTEGER NOT NULL,
+                 note TEXT,
+                 backup_id TEXT,
+                 launch_profile TEXT,
+                 store TEXT,
+                 exit_reason TEXT
+             );
+             CREATE TABLE launches (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 game_id TEXT NOT NULL,
+                 started_at TEXT NOT NULL,
+                 ended_at TEXT,
+                 duration_seconds INTEGER,
+                 outcome TEXT NOT NULL,
+                 error TEXT
+             );
+             INSERT INTO games (id, name) VALUES ('g1', 'Disco Elysium');
+             INSERT INTO backups (id, game_id, notes) VALUES ('b1', 'g1', 'Auto backup after exit');",
+        )
+        .expect("create tables");
+        ConnectionDb::new(conn)
+    }
+
+    #[test]
+    fn session_notes_reach_the_exit_backup_and_are_searchable() {
+        let db = setup_db();
+        assert_eq!(note_prompt_threshold(&db), Some(3600));
+
+        let ended = Utc::now();
+        let started = ended - chrono::Duration::hours(2);
+        let origin = SessionOrigin {
+            launch_profile: Some("Modded".to_string()),
+            store: Some("gog".to_string()),
+        };
+        let id = record_session(
+            &db,
+            "g1",
+            started,
+            ended,
+            &origin,
+            SessionExitReason::Exited,
+        )
+        .expect("record");
+
+        // Note typed before the exit backup finished.
+        let session =
+            set_session_note(&db, id, Some("  reached act 3 ".to_string())).expect("set note");
+        assert_eq!(session.note.as_deref(), Some("reached act 3"));
+        assert_eq!(session.duration_seconds, 7200);
+        assert_eq!(session.launch_profile.as_deref(), Some("Modded"));
+        assert_eq!(session.store.as_deref(), Some("gog"));
+        link_session_backup(&db, id, "b1").expect("link");
+
+        let backup_note = |db: &ConnectionDb| {
+            db.with_conn(|conn| {
+                conn.query_row("SELECT notes FROM backups WHERE id = 'b1'", [], |row| {
+                    row.get::<_, Option<String>>(0)
+                })
+            })
+            .expect("backup notes")
+        };
+        assert_eq!(backup_note(&db).as_deref(), Some("reached act 3"));
+
+        set_session_note(&db, id, Some("act 3, before the tribunal".to_string()))
+            .expect("edit note");
+        assert_eq!(
+            backup_note(&db).as_deref(),
+            Some("act 3, before the tribunal")
+        );
+
+        let found = search_session_notes(&db, "tribunal".to_string(), None).expect("search");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].game_name, "Disco Elysium");
+        assert_eq!(found[0].backup_id.as_deref(), Some("b1"));
+        assert!(search_session_notes(&db, "boss".to_string(), None)
+            .expect("search")
+            .is_empty());
+        assert!(set_session_note(&db, id + 1, Some("x".to_string())).is_err());
+    }
+
+    #[test]
+    fn clearing_a_session_note_keeps_the_backup_note_it_did_not_write() {
+        let db = setup_db();
+        let ended = Utc::now();
+        let id = record_session(
+            &db,
+            "g1",
+            ended - chrono::Duration::hours(1),
+            ended,
+            &SessionOrigin::default(),
+            SessionExitReason::Exited,
+        )
+        .expect("record");
+        link_session_backup(&db, id, "b1").expect("link");
+        let backup_note = || {
+            db.with_conn(|conn| {
+                conn.query_row("SELECT notes FROM backups WHERE id = 'b1'", [], |row| {
+                    row.get::<_, Option<String>>(0)
+                })
+            })
+            .expect("backup notes")
+        };
+
+        set_session_note(&db, id, Some("  ".to_string())).expect("clear blank note");
+        assert_eq!(backup_note().as_deref(), Some("Auto backup after exit"));
+
+        set_session_note(&db, id, Some("boss fight".to_string())).expect("set note");
+        assert_eq!(backup_note().as_deref(), Some("boss fight"));
+        let session = set_session_note(&db, id, None).expect("clear note");
+        assert_eq!(session.note, None);
+        assert_eq!(backup_note(), None);
+    }
+
+    #[test]
+    fn sessions_are_listed_per_game_by_date_and_across_the_library() {
+        let db = setup_db();
+        db.with_conn(|conn| {
+            conn.execute_batch("INSERT INTO games (id, name, is_hidden) VALUES ('g2', 'Hades', 1);")
+        })
+        .expect("insert hidden game");
+        let day = |date: &str| {
+            DateTime::parse_from_rfc3339(&format!("{}T20:00:00Z", date))
+                .expect("date")
+                .with_timezone(&Utc)
+        };
+        let origin = SessionOrigin::default();
+        for (game_id, date, reason) in [
+            ("g1", "2024-03-01", SessionExitReason::Exited),
+            ("g1", "2024-03-05", SessionExitReason::Crashed),
+            ("g1", "2024-03-09", SessionExitReason::Killed),
+            ("g2", "2024-03-10", SessionExitReason::Exited),
+        ] {
+            let started = day(date);
+            record_session(
+                &db,
+                game_id,
+                started,
+                started + chrono::Duration::minutes(30),
+                &origin,
+                reason,
+            )
+            .expect("record");
+        }
+
+        let all = get_game_sessions(&db, "g1".to_string(), None).expect("sessions");
+        assert_eq!(
+            all.iter().map(|s| s.exit_reason).collect::<Vec<_>>(),
+            [
+                Some(SessionExitReason::Killed),
+                Some(SessionExitReason::Crashed),
+                Some(SessionExitReason::Exited)
+            ]
+        );
+        assert_eq!(all[0].duration_seconds, 1800);
+
+        let march_early = get_game_sessions(
+            &db,
+            "g1".to_string(),
+            Some(StatsRange {
+                start: Some("2024-03-02".to_string()),
+                end: Some("2024-03-05".to_string()),
+            }),
+        )
+        .expect("ranged sessions");
+        assert_eq!(march_early.len(), 1);
+        assert_eq!(march_early[0].started_at, day("2024-03-05").to_rfc3339());
+
+        let recent = get_recent_sessions(&db).expect("recent");
+        assert_eq!(recent.len(), 3);
+        assert!(recent.iter().all(|session| session.game_id == "g1"));
+    }
+
+    #[test]
+    fn short_runs_and_missing_processes_count_as_failed_launches() {
+        let db = setup_db();
+        let outcomes = |db: &ConnectionDb| -> Vec<LaunchOutcome> {
+            get_launch_history(db, "g1".to_string())
+                .expect("history")
+                .into_iter()
+                .map(|launch| launch.outcome)
+                .collect()
+        };
+
+        record_launch(&db, "g1", None).expect("record");
+        mark_launch_running(&db, "g1").expect("running");
+        assert_eq!(outcomes(&db), [LaunchOutcome::Running]);
+        let outcome =
+            finish_launch(&db, "g1", Utc::now() + chrono::Duration::seconds(3)).expect("finish");
+        assert_eq!(outcome, Some(LaunchOutcome::Crashed));
+        assert_eq!(outcomes(&db), [LaunchOutcome::Crashed]);
+
+        record_launch(&db, "g1", None).expect("record");
+        finish_launch(&db, "g1", Utc::now() + chrono::Duration::hours(1)).expect("finish");
+        let history = get_launch_history(&db, "g1".to_string()).expect("history");
+        assert_eq!(history[0].outcome, LaunchOutcome::Completed);
+        assert!(history[0].duration_seconds.unwrap_or(0) >= 3600);
+
+        // Started outside Arrancador.
+        assert_eq!(finish_launch(&db, "g1", Utc::now()).expect("finish"), None);
+
+        record_launch(&db, "g1", Some("Файл не найден")).expect("record failure");
+        let history = get_launch_history(&db, "g1".to_string()).expect("history");
+        assert_eq!(history[0].outcome, LaunchOutcome::Failed);
+        assert_eq!(history[0].error.as_deref(), Some("Файл не найден"));
+
+        // Never seen by the tracker.
+        record_launch(&db, "g1", None).expect("record");
+        assert_eq!(expire_pending_launches(&db, Utc::now()).expect("expire"), 0);
+        let later = Utc::now() + chrono::Duration::seconds(LAUNCH_DETECT_SECONDS + 1);
+        assert_eq!(expire_pending_launches(&db, later).expect("expire"), 1);
+        assert_eq!(outcomes(&db)[0], LaunchOutcome::Failed);
+    }
+
+    #[test]
+    fn detect_store_recognises_library_folders() {
+        let store = |path: &str| detect_store(Path::new(path));
+        assert_eq!(
+            store("D:\\SteamLibrary\\steamapps\\common\\Hades\\Hades.exe"),
+            Some("steam")
+        );
+        assert_eq!(
+            store("C:\\GOG Games\\Disco Elysium\\disco.exe"),
+            Some("gog")
+        );
+        assert_eq!(
+            store("C:/Program Files/Epic Games/Control/Control.exe"),
+            Some("epic")
+        );
+        assert_eq!(store("/nonexistent/arrancador/game.exe"), None);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::db::Db;
+use crate::domain::games::{Game, GameQuery};
+use crate::domain::tags::{Collection, CollectionInput, Tag};
+use crate::services::games as games_service;
+use chrono::Utc;
+use rusqlite::{params, Result};
+use uuid::Uuid;
+
+const TAG_SELECT: &str = "SELECT t.id, t.name, t.color, t.created_at,
+             (SELECT COUNT(*) FROM game_tags gt WHERE gt.tag_id = t.id)
+             FROM tags t";
+
+fn map_tag_row(row: &rusqlite::Row) -> Result<Tag> {
+    Ok(Tag {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        color: row.get(2)?,
+        created_at: row.get(3)?,
+        game_count: row.get(4)?,
+    })
+}
+
+pub fn get_tags<D: Db>(db: &D) -> Result<Vec<Tag>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!("{TAG_SELECT} ORDER BY t.name COLLATE NOCASE"))?;
+        let tags = stmt.query_map([], map_tag_row)?.flatten().collect();
+        Ok(tags)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Creates a tag, or returns the existing one when the name is already taken.
+pub fn add_tag<D: Db>(db: &D, name: String, color: Option<String>) -> Result<Tag, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Название тега не может быть пустым".to_string());
+    }
+    let color = color
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty());
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO tags (id, name, color, created_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO NOTHING",
+            params![
+                Uuid::new_v4().to_string(),
+                name,
+                color,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        conn.query_row(
+            &format!("{TAG_SELECT} WHERE t.name = ?1 COLLATE NOCASE"),
+            params![name],
+            map_tag_row,
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn delete_tag<D: Db>(db: &D, id: String) -> Result<(), String> {
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM game_tags WHERE tag_id = ?1", params![id])?;
+        tx.execute("DELETE FROM tags WHERE id = ?1", params![id])?;
+        tx.commit()
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn assign_tag<D: Db>(db: &D, game_id: String, tag_id: String) -> Result<Game, String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO game_tags (game_id, tag_id) VALUES (?1, ?2)",
+            params![game_id, tag_id],
+        )?;
+        games_service::fetch_game_by_id(conn, &game_id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn unassign_tag<D: Db>(db: &D, game_id: String, tag_id: String) -> Result<Game, String> {
+    db.with_conn(|conn| {
+        conn.execute(
+            "DELETE FROM game_tags WHERE game_id = ?1 AND tag_id = ?2",
+            params![game_id, tag_id],
+        )?;
+        games_service::fetch_game_by_id(conn, &game_id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn get_games_by_tag<D: Db>(db: &D, tag_id: String) -> Result<Vec<Game>, String> {
+    games_service::query_games(
+        db,
+        GameQuery {
+            tag_ids: vec![tag_id],
+            ..GameQuery::default()
+        },
+    )
+}
+
+fn map_collection_row(row: &rusqlite::Row) -> Result<Collection> {
+    let query: String = row.get(2)?;
+    Ok(Collection {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        query: serde_json::from_str(&query).unwrap_or_default(),
+        created_at: row.get(3)?,
+    })
+}
+
+fn fetch_collection<D: Db>(db: &D, id: &str) -> Result<Collection, String> {
+    db.with_conn(|conn| {
+        conn.query_row(
+            "SELECT id, name, query, created_at FROM collections WHERE id = ?1",
+            params![id],
+            map_collection_row,
+        )
+    })
+    .map_err(|_| "Коллекция не найдена".to_string())
+}
+
+pub fn get_collections<D: Db>(db: &D) -> Result<Vec<Collection>, String> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, name, query, created_at FROM collections ORDER BY name COLLATE NOCASE",
+        )?;
+        let collections = stmt.query_map([], map_collection_row)?.flatten().collect();
+        Ok(collections)
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn save_collection<D: Db>(db: &D, input: CollectionInput) -> Result<Collection, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Название коллекции не может быть пустым".to_string());
+    }
+    let query = serde_json::to_string(&input.query).map_err(|e| e.to_string())?;
+    let id = input.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO collections (id, name, query, created_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, query = excluded.query",
+            params![id, name, query, Utc::now().to_rfc3339()],
+        )
+    })
+    .map_err(|e| e.to_string())?;
+    fetch_collection(db, &id)
+}
+
+pub fn delete_collection<D: Db>(db: &D, id: String) -> Result<(), String> {
+    db.with_conn(|conn| conn.execute("DELETE FROM collections WHERE id = ?1", params![id]))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// The games matching a collection's filter right now.
+pub fn get_collection_games<D: Db>(db: &D, id: String) -> Result<Vec<Game>, String> {
+    let collection = fetch_collection(db, &id)?;
+    games_service::query_games(db, collection.query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_schema;
+    use crate::db::ConnectionDb;
+    use rusqlite::Connection;
+
+    fn setup_db() -> ConnectionDb {
+        let conn = Connection::open_in_memory().expect("open db");
+        init_schema(&conn).expect("init schema");
+        conn.execute_batch(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added, genres, total_playtime)
+             VALUES ('g1', 'Hades', 'C:\\Games\\hades.exe', 'hades.exe', '', 'Action, RPG', 90000),
+                    ('g2', 'Celeste', 'C:\\Games\\celeste.exe', 'celeste.exe', '', 'Platformer', 600),
+                    ('g3', 'Diablo', 'C:\\Games\\diablo.exe', 'diablo.exe', '', 'Action RPG', 0);",
+        )
+        .expect("seed games");
+        ConnectionDb::new(conn)
+    }
+
+    #[test]
+    fn tags_group_games_and_collections_filter_by_genre_and_playtime() {
+        let db = setup_db();
+
+        let coop =
+            add_tag(&db, " Co-op ".to_string(), Some("#22c55e".to_string())).expect("add tag");
+        assert_eq!(coop.name, "Co-op");
+        let again = add_tag(&db, "co-op".to_string(), None).expect("add duplicate tag");
+        assert_eq!(again.id, coop.id);
+        assert!(add_tag(&db, "  ".to_string(), None).is_err());
+
+        let hades = assign_tag(&db, "g1".to_string(), coop.id.clone()).expect("assign");
+        assert_eq!(hades.tags, vec![coop.id.clone()]);
+        assign_tag(&db, "g2".to_string(), coop.id.clone()).expect("assign");
+        assign_tag(&db, "g2".to_string(), coop.id.clone()).expect("assign twice");
+        let tagged: Vec<String> = get_games_by_tag(&db, coop.id.clone())
+            .expect("games by tag")
+            .into_iter()
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(tagged, vec!["g2", "g1"]);
+        assert_eq!(get_tags(&db).expect("tags")[0].game_count, 2);
+
+        let rpgs = save_collection(
+            &db,
+            CollectionInput {
+                id: None,
+                name: "Long RPGs".to_string(),
+                query: GameQuery {
+                    genre: Some("rpg".to_string()),
+                    min_playtime: Some(3600),
+                    ..GameQuery::default()
+                },
+            },
+        )
+        .expect("save collection");
+        let games = get_collection_games(&db, rpgs.id.clone()).expect("collection games");
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].id, "g1");
+
+        unassign_tag(&db, "g1".to_string(), coop.id.clone()).expect("unassign");
+        delete_tag(&db, coop.id).expect("delete tag");
+        assert!(get_tags(&db).expect("tags").is_empty());
+        delete_collection(&db, rpgs.id.clone()).expect("delete collection");
+        assert!(get_collection_games(&db, rpgs.id).is_err());
+    }
+}
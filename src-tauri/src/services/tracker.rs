@@ -1,20 +1,30 @@
-use crate::backup::auto_backup_on_exit;
+use crate::backup::{auto_backup_on_exit, maybe_start_nightly_backup};
 use crate::db::Db;
+use crate::domain::sessions::{
+    LaunchOutcome, SessionEnded, SessionExitReason, SessionNotePrompt, SessionOrigin,
+};
+use crate::services::games::{self as games_service, ProcessTarget, PROCESS_TARGET_SELECT};
+use crate::services::sessions as sessions_service;
+use crate::stats::{archive_cutoff, archive_playtime_before, DEFAULT_ARCHIVE_HORIZON_DAYS};
 use chrono::{DateTime, NaiveDate, Utc};
 use rusqlite::params;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::thread;
 use std::time::Duration;
-use sysinfo::{ProcessesToUpdate, System};
-use tauri::AppHandle;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tauri::{AppHandle, Emitter};
 
 pub const UPDATE_INTERVAL_SECS: u64 = 10;
 
 #[derive(Clone)]
 struct GameInfo {
     id: String,
-    exe_path: PathBuf,
+    name: String,
+    target: ProcessTarget,
+    /// False for games flagged "do not track"; they are still watched so the exit
+    /// backup runs, but no playtime or session is recorded.
+    track: bool,
 }
 
 pub trait Clock {
@@ -47,7 +57,7 @@ impl<D: Db, C: Clock> TrackerService<D, C> {
 
     pub fn start(self, app: AppHandle)
     where
-        D: Send + 'static,
+        D: Clone + Send + 'static,
         C: Send + 'static,
     {
         thread::spawn(move || {
@@ -56,9 +66,12 @@ impl<D: Db, C: Clock> TrackerService<D, C> {
             let mut last_cache_update = std::time::Instant::now();
             let cache_ttl = Duration::from_secs(60);
             let mut previously_active: HashSet<String> = HashSet::new();
+            let mut session_starts: HashMap<String, DateTime<Utc>> = HashMap::new();
             let app_handle = app;
+            let mut last_archive_day = self.clock.today();
 
             self.update_games_cache(&mut games_cache);
+            self.archive_playtime();
 
             loop {
                 if last_cache_update.elapsed() > cache_ttl {
@@ -66,14 +79,27 @@ impl<D: Db, C: Clock> TrackerService<D, C> {
                     last_cache_update = std::time::Instant::now();
                 }
 
-                sys.refresh_processes(ProcessesToUpdate::All, true);
+                let today = self.clock.today();
+                if today != last_archive_day {
+                    self.archive_playtime();
+                    last_archive_day = today;
+                }
+
+                // The command line tells apart ROM games sharing one emulator.
+                sys.refresh_processes_specifics(
+                    ProcessesToUpdate::All,
+                    true,
+                    ProcessRefreshKind::nothing()
+                        .with_exe(UpdateKind::OnlyIfNotSet)
+                        .with_cmd(UpdateKind::OnlyIfNotSet),
+                );
 
                 let mut active_game_ids = Vec::new();
 
                 for process in sys.processes().values() {
                     if let Some(exe_path) = process.exe() {
                         for game in &games_cache {
-                            if paths_match(exe_path, &game.exe_path) {
+                            if game.target.matches(exe_path, process.cmd()) {
                                 active_game_ids.push(game.id.clone());
                             }
                         }
@@ -88,17 +114,96 @@ impl<D: Db, C: Clock> TrackerService<D, C> {
                     .difference(&current_active)
                     .cloned()
                     .collect();
+                let tracked_ids: Vec<String> = active_game_ids
+                    .iter()
+                    .filter(|id| games_cache.iter().any(|game| &game.id == *id && game.track))
+                    .cloned()
+                    .collect();
+                // Untracked games never get a start time, so no session is recorded when
+                // they exit.
+                for game_id in current_active.difference(&previously_active) {
+                    if let Err(e) = sessions_service::mark_launch_running(&self.db, game_id) {
+                        eprintln!("Failed to update launch of {}: {}", game_id, e);
+                    }
+                    if tracked_ids.contains(game_id) {
+                        session_starts.insert(game_id.clone(), self.clock.now());
+                    }
+                }
 
-                if !active_game_ids.is_empty() {
-                    self.update_playtime(&active_game_ids);
+                if active_game_ids.is_empty() {
+                    maybe_start_nightly_backup(&app_handle);
+                } else if !tracked_ids.is_empty() {
+                    self.update_playtime(&tracked_ids);
+                }
+
+                if let Err(e) =
+                    sessions_service::expire_pending_launches(&self.db, self.clock.now())
+                {
+                    eprintln!("Failed to expire pending launches: {}", e);
                 }
 
                 for game_id in ended {
+                    games_service::finish_companions(&game_id);
+                    let outcome =
+                        sessions_service::finish_launch(&self.db, &game_id, self.clock.now())
+                            .unwrap_or_else(|e| {
+                                eprintln!("Failed to finish launch of {}: {}", game_id, e);
+                                None
+                            });
+                    let exit_reason = if games_service::take_killed_game(&game_id) {
+                        SessionExitReason::Killed
+                    } else if outcome == Some(LaunchOutcome::Crashed) {
+                        SessionExitReason::Crashed
+                    } else {
+                        SessionExitReason::Exited
+                    };
+                    let origin = SessionOrigin {
+                        launch_profile: games_service::finish_profile_launch(&game_id)
+                            .map(|launch| launch.profile_name),
+                        store: games_cache
+                            .iter()
+                            .find(|game| game.id == game_id)
+                            .and_then(|game| sessions_service::detect_store(&game.target.exe_path))
+                            .map(str::to_string),
+                    };
+                    let started = session_starts.remove(&game_id);
+                    let session_id = started.and_then(|started| {
+                        self.finish_session(
+                            &app_handle,
+                            &games_cache,
+                            &game_id,
+                            started,
+                            &origin,
+                            exit_reason,
+                        )
+                    });
+                    self.announce_session_end(
+                        &app_handle,
+                        &games_cache,
+                        &game_id,
+                        started.filter(|_| session_id.is_some()),
+                        session_id,
+                    );
                     let id_clone = game_id.clone();
                     let app_clone = app_handle.clone();
+                    let db = self.db.clone();
+                    let profile = origin.launch_profile;
                     thread::spawn(move || {
-                        if let Err(e) = auto_backup_on_exit(&id_clone, Some(app_clone)) {
-                            eprintln!("Auto-backup failed for {}: {}", id_clone, e);
+                        match auto_backup_on_exit(&id_clone, Some(app_clone), profile.as_deref()) {
+                            Ok(Some(backup_id)) => {
+                                if let Some(session_id) = session_id {
+                                    if let Err(e) = sessions_service::link_session_backup(
+                                        &db, session_id, &backup_id,
+                                    ) {
+                                        eprintln!(
+                                            "Failed to link backup to session {}: {}",
+                                            session_id, e
+                                        );
+                                    }
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => eprintln!("Auto-backup failed for {}: {}", id_clone, e),
                         }
                     });
                 }
@@ -130,13 +235,141 @@ impl<D: Db, C: Clock> TrackerService<D, C> {
         });
     }
 
+    pub fn archive_playtime(&self) {
+        let result = self.db.with_conn(|conn| {
+            let horizon: i64 = conn
+                .query_row(
+                    "SELECT value FROM settings WHERE key = 'playtime_archive_horizon_days'",
+                    [],
+                    |row| row.get::<_, String>(0),
+                )
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_ARCHIVE_HORIZON_DAYS);
+            archive_playtime_before(conn, archive_cutoff(self.clock.today(), horizon))
+        });
+
+        if let Err(e) = result {
+            eprintln!("Playtime archive failed: {}", e);
+        }
+    }
+
+    /// Records the session that just ended and, when it ran longer than the configured
+    /// threshold, asks the frontend for a note via `session:note-prompt`.
+    fn finish_session(
+        &self,
+        app: &AppHandle,
+        games: &[GameInfo],
+        game_id: &str,
+        started: DateTime<Utc>,
+        origin: &SessionOrigin,
+        exit_reason: SessionExitReason,
+    ) -> Option<i64> {
+        let ended = self.clock.now();
+        let session_id = match sessions_service::record_session(
+            &self.db,
+            game_id,
+            started,
+            ended,
+            origin,
+            exit_reason,
+        ) {
+            Ok(id) => id,
+            Err(e) => {
+                eprintln!("Failed to record session for {}: {}", game_id, e);
+                return None;
+            }
+        };
+        let duration_seconds = (ended - started).num_seconds();
+        let prompt_due = sessions_service::note_prompt_threshold(&self.db)
+            .is_some_and(|threshold| duration_seconds >= threshold);
+        if prompt_due {
+            let game_name = games
+                .iter()
+                .find(|game| game.id == game_id)
+                .map(|game| game.name.clone())
+                .unwrap_or_default();
+            let _ = app.emit(
+                "session:note-prompt",
+                SessionNotePrompt {
+                    session_id,
+                    game_id: game_id.to_string(),
+                    game_name,
+                    duration_seconds,
+                },
+            );
+        }
+        crate::plugins::dispatch(
+            "session_ended",
+            serde_json::json!({
+                "session_id": session_id,
+                "game_id": game_id,
+                "started_at": started.to_rfc3339(),
+                "ended_at": ended.to_rfc3339(),
+                "duration_seconds": duration_seconds,
+                "exit_reason": exit_reason,
+            }),
+        );
+        // Keeps the tray's recently played list current.
+        crate::refresh_tray_menu(app);
+        Some(session_id)
+    }
+
+    /// Emits `game:session-ended` and brings the main window back when the launch had
+    /// hidden it.
+    fn announce_session_end(
+        &self,
+        app: &AppHandle,
+        games: &[GameInfo],
+        game_id: &str,
+        started: Option<DateTime<Utc>>,
+        session_id: Option<i64>,
+    ) {
+        let ended = self.clock.now();
+        let total_playtime = self
+            .db
+            .with_conn(|conn| {
+                conn.query_row(
+                    "SELECT total_playtime FROM games WHERE id = ?1",
+                    params![game_id],
+                    |row| row.get::<_, i64>(0),
+                )
+            })
+            .unwrap_or(0);
+        let window_restored = games_service::take_minimized_launch(game_id);
+        if window_restored {
+            crate::show_main_window(app);
+        }
+        let _ = app.emit(
+            "game:session-ended",
+            SessionEnded {
+                game_id: game_id.to_string(),
+                game_name: games
+                    .iter()
+                    .find(|game| game.id == game_id)
+                    .map(|game| game.name.clone())
+                    .unwrap_or_default(),
+                session_id,
+                duration_seconds: started.map(|started| (ended - started).num_seconds()),
+                ended_at: ended.to_rfc3339(),
+                total_playtime,
+                window_restored,
+            },
+        );
+    }
+
     fn update_games_cache(&self, cache: &mut Vec<GameInfo>) {
         let result = self.db.with_conn(|conn| {
-            let mut stmt = conn.prepare("SELECT id, exe_path FROM games")?;
+            let mut stmt = conn.prepare(PROCESS_TARGET_SELECT)?;
             let rows = stmt.query_map([], |row| {
                 Ok(GameInfo {
                     id: row.get(0)?,
-                    exe_path: PathBuf::from(row.get::<_, String>(1)?),
+                    name: row.get(1)?,
+                    target: ProcessTarget {
+                        exe_path: PathBuf::from(row.get::<_, String>(2)?),
+                        rom_path: row.get::<_, Option<String>>(4)?.map(PathBuf::from),
+                    },
+                    track: row.get::<_, i32>(3)? == 0,
                 })
             })?;
 
@@ -152,11 +385,3 @@ impl<D: Db, C: Clock> TrackerService<D, C> {
         }
     }
 }
-
-fn paths_match(p1: &std::path::Path, p2: &std::path::Path) -> bool {
-    if cfg!(target_os = "windows") {
-        p1.to_string_lossy().to_lowercase() == p2.to_string_lossy().to_lowercase()
-    } else {
-        p1 == p2
-    }
-}
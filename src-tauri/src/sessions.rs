@@ -0,0 +1,36 @@
+use crate::db::GlobalDb;
+pub use crate::domain::sessions::{LaunchRecord, PlaySession};
+use crate::services::sessions as sessions_service;
+use crate::stats::StatsRange;
+
+#[tauri::command]
+pub fn set_session_note(session_id: i64, note: Option<String>) -> Result<PlaySession, String> {
+    sessions_service::set_session_note(&GlobalDb, session_id, note)
+}
+
+#[tauri::command]
+pub fn search_session_notes(
+    query: String,
+    game_id: Option<String>,
+) -> Result<Vec<PlaySession>, String> {
+    sessions_service::search_session_notes(&GlobalDb, query, game_id)
+}
+
+/// Sessions of one game, newest first, optionally limited to a range of start dates.
+#[tauri::command]
+pub fn get_game_sessions(
+    game_id: String,
+    range: Option<StatsRange>,
+) -> Result<Vec<PlaySession>, String> {
+    sessions_service::get_game_sessions(&GlobalDb, game_id, range)
+}
+
+#[tauri::command]
+pub fn get_recent_sessions() -> Result<Vec<PlaySession>, String> {
+    sessions_service::get_recent_sessions(&GlobalDb)
+}
+
+#[tauri::command]
+pub fn get_launch_history(game_id: String) -> Result<Vec<LaunchRecord>, String> {
+    sessions_service::get_launch_history(&GlobalDb, game_id)
+}
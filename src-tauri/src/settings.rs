@@ -1,6 +1,9 @@
+use crate::backup::naming::validate_name_template;
 use crate::database::with_db;
+use crate::services::net_path;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
@@ -12,6 +15,8 @@ pub struct AppSettings {
     pub backup_compression_enabled: bool,
     pub backup_compression_level: i32,
     pub backup_skip_compression_once: bool,
+    /// Check saves for signs of corruption after a game exits.
+    pub backup_integrity_check: bool,
     pub max_backups_per_game: i32,
     pub rawg_api_key: String,
 }
@@ -27,6 +32,7 @@ impl Default for AppSettings {
             backup_compression_enabled: true,
             backup_compression_level: 60,
             backup_skip_compression_once: false,
+            backup_integrity_check: true,
             max_backups_per_game: 5,
             rawg_api_key: String::new(),
         }
@@ -69,6 +75,7 @@ pub fn get_all_settings() -> Result<AppSettings, String> {
                 "backup_skip_compression_once" => {
                     settings.backup_skip_compression_once = value == "true"
                 }
+                "backup_integrity_check" => settings.backup_integrity_check = value == "true",
                 "max_backups_per_game" => {
                     settings.max_backups_per_game = clamp_max_backups(value.parse().unwrap_or(5))
                 }
@@ -128,6 +135,15 @@ pub fn update_settings(settings: AppSettings) -> Result<(), String> {
                 }
                 .to_string(),
             ),
+            (
+                "backup_integrity_check",
+                if settings.backup_integrity_check {
+                    "true"
+                } else {
+                    "false"
+                }
+                .to_string(),
+            ),
             ("max_backups_per_game", max_backups.to_string()),
             ("rawg_api_key", settings.rawg_api_key),
         ];
@@ -154,8 +170,17 @@ pub fn get_setting(key: String) -> Result<Option<String>, String> {
     .map_err(|e| e.to_string())
 }
 
+/// Checks a raw `key`/`value` setting before it is stored, whichever command writes it.
+pub(crate) fn validate_setting(key: &str, value: &str) -> Result<(), String> {
+    match key {
+        "backup_name_template" => validate_name_template(value),
+        _ => Ok(()),
+    }
+}
+
 #[tauri::command]
 pub fn set_setting(key: String, value: String) -> Result<(), String> {
+    validate_setting(&key, &value)?;
     with_db(|conn| {
         conn.execute(
             "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
@@ -191,6 +216,72 @@ pub fn get_scan_directories() -> Result<Vec<String>, String> {
     .map_err(|e| e.to_string())
 }
 
+/// A saved scan folder with its background-scan state.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanDirectory {
+    pub path: String,
+    pub last_scanned: Option<String>,
+    pub auto_scan: bool,
+    /// Read threads used when scanning this folder; `None` keeps the scan default.
+    pub threads: Option<i64>,
+    /// UNC path or mapped network drive.
+    pub network: bool,
+}
+
+#[tauri::command]
+pub fn list_scan_directories() -> Result<Vec<ScanDirectory>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT path, last_scanned, COALESCE(auto_scan, 0), threads
+             FROM scan_directories ORDER BY path",
+        )?;
+        let dirs = stmt
+            .query_map([], |row| {
+                let path: String = row.get(0)?;
+                Ok(ScanDirectory {
+                    network: net_path::is_network_path(Path::new(&path)),
+                    path,
+                    last_scanned: row.get(1)?,
+                    auto_scan: row.get(2)?,
+                    threads: row.get(3)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(dirs)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Includes the folder in (or drops it from) the periodic background scan.
+#[tauri::command]
+pub fn set_scan_directory_auto_scan(path: String, enabled: bool) -> Result<(), String> {
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE scan_directories SET auto_scan = ?1 WHERE path = ?2",
+            params![enabled, path],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Sets how many threads read the folder during scans; `None` goes back to the default.
+#[tauri::command]
+pub fn set_scan_directory_threads(path: String, threads: Option<i64>) -> Result<(), String> {
+    if threads.is_some_and(|threads| !(1..=64).contains(&threads)) {
+        return Err("Thread count must be between 1 and 64".to_string());
+    }
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE scan_directories SET threads = ?1 WHERE path = ?2",
+            params![threads, path],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn remove_scan_directory(path: String) -> Result<(), String> {
     with_db(|conn| {
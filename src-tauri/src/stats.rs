@@ -1,22 +1,37 @@
 use crate::database::with_db;
-use chrono::{Duration, NaiveDate, Utc};
-use rusqlite::params;
-use serde::Serialize;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DailyTotal {
     pub date: String,
     pub seconds: i64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GameTotal {
     pub id: String,
     pub name: String,
     pub seconds: i64,
 }
 
+/// Session time of one game split by launch profile and store copy.
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchTotal {
+    pub game_id: String,
+    pub game_name: String,
+    /// `None` for plain launches.
+    pub launch_profile: Option<String>,
+    pub store: Option<String>,
+    pub sessions: i64,
+    pub seconds: i64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PlaytimeStats {
     pub range_start: String,
@@ -24,88 +39,656 @@ pub struct PlaytimeStats {
     pub total_seconds: i64,
     pub daily_totals: Vec<DailyTotal>,
     pub per_game_totals: Vec<GameTotal>,
+    /// Built from recorded play sessions, so time tracked before sessions existed is missing.
+    pub per_launch_totals: Vec<LaunchTotal>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsRange {
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+/// Shareable recap of a date range; written as JSON or rendered to SVG by `generate_stats_card`.
+#[derive(Debug, Serialize)]
+pub struct StatsCard {
+    pub range_start: String,
+    pub range_end: String,
+    pub total_seconds: i64,
+    pub total_hours: f64,
+    pub days_played: usize,
+    pub longest_streak_days: usize,
+    pub top_games: Vec<GameTotal>,
+}
+
+const STATS_CARD_TOP_GAMES: usize = 5;
+const OVERVIEW_TOP_GENRES: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GenreCount {
+    pub genre: String,
+    pub games: i64,
+}
+
+/// The newest thing that happened in the library.
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryActivity {
+    pub game_id: String,
+    pub game_name: String,
+    /// `played`, `backup` or `added`
+    pub kind: String,
+    pub at: String,
+}
+
+/// Whole-library numbers for the dashboard tiles, loaded in one call.
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryOverview {
+    pub total_games: i64,
+    /// Games whose install is not packed away in an install archive. Taken from the
+    /// database, so the overview never touches the disk.
+    pub installed_games: i64,
+    pub favorite_games: i64,
+    pub games_with_backups: i64,
+    /// Excludes games with tracking turned off.
+    pub total_playtime_seconds: i64,
+    pub total_backup_bytes: i64,
+    pub recent_activity: Option<LibraryActivity>,
+    pub top_genres: Vec<GenreCount>,
+}
+
+pub const DEFAULT_ARCHIVE_HORIZON_DAYS: i64 = 365;
+const MIN_ARCHIVE_HORIZON_DAYS: i64 = 31;
+
+/// Counts games per genre from the comma-separated `games.genres` column, most common
+/// first; ties are broken by name.
+pub fn top_genres<I: IntoIterator<Item = String>>(genre_lists: I, limit: usize) -> Vec<GenreCount> {
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for list in genre_lists {
+        let mut seen = Vec::new();
+        for genre in list
+            .split(',')
+            .map(str::trim)
+            .filter(|genre| !genre.is_empty())
+        {
+            if !seen.contains(&genre) {
+                seen.push(genre);
+                *counts.entry(genre.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut genres: Vec<GenreCount> = counts
+        .into_iter()
+        .map(|(genre, games)| GenreCount { genre, games })
+        .collect();
+    genres.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.genre.cmp(&b.genre)));
+    genres.truncate(limit);
+    genres
+}
+
+#[tauri::command]
+pub fn get_library_overview() -> Result<LibraryOverview, String> {
+    with_db(load_library_overview).map_err(|e| e.to_string())
+}
+
+fn load_library_overview(conn: &Connection) -> rusqlite::Result<LibraryOverview> {
+    let (total_games, installed_games, favorite_games, total_playtime_seconds, games_with_backups, total_backup_bytes) =
+        conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(id NOT IN (SELECT game_id FROM install_archives)), 0),
+                    COALESCE(SUM(is_favorite = 1), 0),
+                    COALESCE(SUM(CASE WHEN COALESCE(do_not_track, 0) = 0 THEN total_playtime ELSE 0 END), 0),
+                    (SELECT COUNT(DISTINCT game_id) FROM backups),
+                    (SELECT COALESCE(SUM(backup_size), 0) FROM backups)
+             FROM games",
+            [],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            },
+        )?;
+
+    let mut stmt = conn.prepare("SELECT genres FROM games WHERE genres IS NOT NULL")?;
+    let genre_lists: Vec<String> = stmt.query_map([], |row| row.get(0))?.flatten().collect();
+    let top_genres = top_genres(genre_lists, OVERVIEW_TOP_GENRES);
+
+    let mut activity_stmt = conn.prepare(
+        "SELECT * FROM (SELECT id, name, 'played', last_played FROM games
+                        WHERE last_played IS NOT NULL AND COALESCE(do_not_track, 0) = 0
+                        ORDER BY last_played DESC LIMIT 1)
+         UNION ALL
+         SELECT * FROM (SELECT g.id, g.name, 'backup', b.created_at FROM backups b
+                        JOIN games g ON g.id = b.game_id
+                        ORDER BY b.created_at DESC LIMIT 1)
+         UNION ALL
+         SELECT * FROM (SELECT id, name, 'added', date_added FROM games
+                        ORDER BY date_added DESC LIMIT 1)",
+    )?;
+    let recent_activity = activity_stmt
+        .query_map([], |row| {
+            Ok(LibraryActivity {
+                game_id: row.get(0)?,
+                game_name: row.get(1)?,
+                kind: row.get(2)?,
+                at: row.get(3)?,
+            })
+        })?
+        .flatten()
+        .max_by_key(|activity| {
+            chrono::DateTime::parse_from_rfc3339(&activity.at)
+                .map(|at| at.timestamp_millis())
+                .unwrap_or(i64::MIN)
+        });
+
+    Ok(LibraryOverview {
+        total_games,
+        installed_games,
+        favorite_games,
+        games_with_backups,
+        total_playtime_seconds,
+        total_backup_bytes,
+        recent_activity,
+        top_genres,
+    })
 }
 
 fn parse_date(input: &str) -> Option<NaiveDate> {
     NaiveDate::parse_from_str(input, "%Y-%m-%d").ok()
 }
 
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// Splits an archived month's `seconds` evenly over its days. The remainder goes to the first
+/// days, so the shares always add up to `seconds`.
+fn spread_over_month(first_day: NaiveDate, seconds: i64) -> Vec<(NaiveDate, i64)> {
+    let days: Vec<NaiveDate> = first_day
+        .iter_days()
+        .take_while(|date| date.month() == first_day.month())
+        .collect();
+    let count = days.len() as i64;
+    days.into_iter()
+        .enumerate()
+        .map(|(index, date)| {
+            let extra = i64::from((index as i64) < seconds % count);
+            (date, seconds / count + extra)
+        })
+        .collect()
+}
+
+/// First day of the oldest month that must stay in `playtime_daily`.
+/// Only whole months are rolled up so a month never ends up split between
+/// the daily and the monthly table by a single archive run.
+pub fn archive_cutoff(today: NaiveDate, horizon_days: i64) -> NaiveDate {
+    let horizon = horizon_days.max(MIN_ARCHIVE_HORIZON_DAYS);
+    month_start(today - Duration::days(horizon))
+}
+
+/// Rolls `playtime_daily` rows older than `cutoff` into `playtime_monthly`.
+/// Returns the number of daily rows that were archived.
+pub fn archive_playtime_before(conn: &Connection, cutoff: NaiveDate) -> rusqlite::Result<usize> {
+    let cutoff = cutoff.format("%Y-%m-%d").to_string();
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO playtime_monthly (game_id, month, seconds)
+         SELECT game_id, substr(date, 1, 7), SUM(seconds)
+         FROM playtime_daily
+         WHERE date < ?1
+         GROUP BY game_id, substr(date, 1, 7)
+         ON CONFLICT(game_id, month) DO UPDATE SET seconds = seconds + excluded.seconds",
+        params![cutoff],
+    )?;
+    let archived = tx.execute(
+        "DELETE FROM playtime_daily WHERE date < ?1",
+        params![cutoff],
+    )?;
+    tx.commit()?;
+    Ok(archived)
+}
+
+/// Resolves optional bounds to an ordered range; defaults to the last 30 days.
+fn resolve_range(start: Option<&str>, end: Option<&str>) -> (NaiveDate, NaiveDate) {
+    let end_date = end
+        .and_then(parse_date)
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let start_date = start
+        .and_then(parse_date)
+        .unwrap_or_else(|| end_date - Duration::days(29));
+    if start_date > end_date {
+        (end_date, start_date)
+    } else {
+        (start_date, end_date)
+    }
+}
+
 #[tauri::command]
 pub fn get_playtime_stats(
     start: Option<String>,
     end: Option<String>,
 ) -> Result<PlaytimeStats, String> {
-    let mut end_date = end
-        .as_deref()
-        .and_then(parse_date)
-        .unwrap_or_else(|| Utc::now().date_naive());
-    let mut start_date = start
-        .as_deref()
-        .and_then(parse_date)
-        .unwrap_or_else(|| end_date - Duration::days(29));
+    let (start_date, end_date) = resolve_range(start.as_deref(), end.as_deref());
+    with_db(|conn| load_playtime_stats(conn, start_date, end_date)).map_err(|e| e.to_string())
+}
 
-    if start_date > end_date {
-        std::mem::swap(&mut start_date, &mut end_date);
+/// Builds the recap for `range` and writes it to `path`: SVG when the path ends in `.svg`,
+/// JSON otherwise. Returns the card so the UI can preview it.
+#[tauri::command]
+pub fn generate_stats_card(range: Option<StatsRange>, path: String) -> Result<StatsCard, String> {
+    let range = range.unwrap_or_default();
+    let (start_date, end_date) = resolve_range(range.start.as_deref(), range.end.as_deref());
+    let stats = with_db(|conn| load_playtime_stats(conn, start_date, end_date))
+        .map_err(|e| e.to_string())?;
+    let card = build_stats_card(&stats);
+
+    let path = Path::new(&path);
+    let is_svg = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("svg"))
+        .unwrap_or(false);
+    let contents = if is_svg {
+        render_stats_card_svg(&card)
+    } else {
+        serde_json::to_string_pretty(&card).map_err(|e| e.to_string())?
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, contents)
+        .map_err(|e| format!("Не удалось сохранить карточку статистики: {}", e))?;
+    Ok(card)
+}
+
+pub fn build_stats_card(stats: &PlaytimeStats) -> StatsCard {
+    let mut longest_streak_days = 0;
+    let mut streak = 0;
+    let mut days_played = 0;
+    for day in &stats.daily_totals {
+        if day.seconds > 0 {
+            days_played += 1;
+            streak += 1;
+            longest_streak_days = longest_streak_days.max(streak);
+        } else {
+            streak = 0;
+        }
     }
 
+    StatsCard {
+        range_start: stats.range_start.clone(),
+        range_end: stats.range_end.clone(),
+        total_seconds: stats.total_seconds,
+        total_hours: (stats.total_seconds as f64 / 360.0).round() / 10.0,
+        days_played,
+        longest_streak_days,
+        top_games: stats
+            .per_game_totals
+            .iter()
+            .take(STATS_CARD_TOP_GAMES)
+            .cloned()
+            .collect(),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Fixed-size 600px-wide card with totals and a bar per top game.
+pub fn render_stats_card_svg(card: &StatsCard) -> String {
+    let height = 220 + card.top_games.len() * 44;
+    let max_seconds = card
+        .top_games
+        .first()
+        .map(|game| game.seconds)
+        .unwrap_or(0)
+        .max(1);
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="600" height="{height}" viewBox="0 0 600 {height}" font-family="Segoe UI, sans-serif">
+<rect width="600" height="{height}" rx="16" fill="#111827"/>
+<text x="32" y="52" font-size="26" font-weight="700" fill="#f9fafb">Arrancador</text>
+<text x="32" y="80" font-size="15" fill="#9ca3af">{start} — {end}</text>
+<text x="32" y="130" font-size="34" font-weight="700" fill="#f9fafb">{hours} ч</text>
+<text x="220" y="130" font-size="17" fill="#d1d5db">Дней в игре: {days}</text>
+<text x="220" y="156" font-size="17" fill="#d1d5db">Лучшая серия: {streak} дн.</text>
+"##,
+        start = escape_xml(&card.range_start),
+        end = escape_xml(&card.range_end),
+        hours = card.total_hours,
+        days = card.days_played,
+        streak = card.longest_streak_days,
+    );
+
+    for (index, game) in card.top_games.iter().enumerate() {
+        let y = 200 + index * 44;
+        let width = 8 + (game.seconds.max(0) as f64 / max_seconds as f64 * 528.0) as i64;
+        let hours = (game.seconds as f64 / 360.0).round() / 10.0;
+        let _ = write!(
+            svg,
+            r##"<rect x="32" y="{bar_y}" width="{width}" height="30" rx="6" fill="#2563eb"/>
+<text x="44" y="{text_y}" font-size="15" fill="#f9fafb">{name} · {hours} ч</text>
+"##,
+            bar_y = y,
+            text_y = y + 20,
+            name = escape_xml(&game.name),
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn load_playtime_stats(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> rusqlite::Result<PlaytimeStats> {
     let range_start = start_date.format("%Y-%m-%d").to_string();
     let range_end = end_date.format("%Y-%m-%d").to_string();
+    let month_range_start = start_date.format("%Y-%m").to_string();
+    let month_range_end = end_date.format("%Y-%m").to_string();
 
-    with_db(|conn| {
-        let mut daily_stmt = conn.prepare(
-            "SELECT date, SUM(seconds) as seconds
-             FROM playtime_daily
-             WHERE date BETWEEN ?1 AND ?2
-             GROUP BY date
-             ORDER BY date",
-        )?;
+    let mut daily_stmt = conn.prepare(
+        "SELECT date, SUM(seconds) as seconds
+         FROM playtime_daily
+         WHERE date BETWEEN ?1 AND ?2
+           AND game_id NOT IN (SELECT id FROM games WHERE do_not_track = 1)
+         GROUP BY date
+         ORDER BY date",
+    )?;
 
-        let mut daily_map: HashMap<String, i64> = HashMap::new();
-        let rows = daily_stmt.query_map(params![&range_start, &range_end], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
-        })?;
-        for (date, seconds) in rows.flatten() {
-            daily_map.insert(date, seconds);
+    let mut daily_map: HashMap<String, i64> = HashMap::new();
+    let rows = daily_stmt.query_map(params![&range_start, &range_end], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+    for (date, seconds) in rows.flatten() {
+        daily_map.insert(date, seconds);
+    }
+
+    // Archived months have no per-day breakdown; spread each month evenly over its days so a
+    // range covering part of the month only counts the days it covers.
+    let mut monthly_stmt = conn.prepare(
+        "SELECT playtime_monthly.game_id, games.name, playtime_monthly.month,
+                playtime_monthly.seconds
+         FROM playtime_monthly
+         LEFT JOIN games ON games.id = playtime_monthly.game_id
+         WHERE playtime_monthly.month BETWEEN ?1 AND ?2
+           AND COALESCE(games.do_not_track, 0) = 0",
+    )?;
+    let monthly_rows: Vec<(String, Option<String>, String, i64)> = monthly_stmt
+        .query_map(params![&month_range_start, &month_range_end], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .flatten()
+        .collect();
+    // Per-game totals only list games that still exist, like the daily query below.
+    let mut per_game: HashMap<String, (String, i64)> = HashMap::new();
+    for (game_id, name, month, seconds) in monthly_rows {
+        let Some(first_day) = parse_date(&format!("{month}-01")) else {
+            continue;
+        };
+        let mut in_range = 0;
+        for (date, share) in spread_over_month(first_day, seconds) {
+            if date >= start_date && date <= end_date {
+                *daily_map
+                    .entry(date.format("%Y-%m-%d").to_string())
+                    .or_insert(0) += share;
+                in_range += share;
+            }
+        }
+        if let Some(name) = name {
+            per_game.entry(game_id).or_insert((name, 0)).1 += in_range;
         }
+    }
+
+    let mut daily_totals = Vec::new();
+    let mut total_seconds = 0;
+    let mut cursor = start_date;
+    while cursor <= end_date {
+        let date = cursor.format("%Y-%m-%d").to_string();
+        let seconds = *daily_map.get(&date).unwrap_or(&0);
+        total_seconds += seconds;
+        daily_totals.push(DailyTotal { date, seconds });
+        cursor += Duration::days(1);
+    }
 
-        let mut daily_totals = Vec::new();
-        let mut total_seconds = 0;
-        let mut cursor = start_date;
-        while cursor <= end_date {
-            let date = cursor.format("%Y-%m-%d").to_string();
-            let seconds = *daily_map.get(&date).unwrap_or(&0);
-            total_seconds += seconds;
-            daily_totals.push(DailyTotal { date, seconds });
-            cursor += Duration::days(1);
+    let mut game_stmt = conn.prepare(
+        "SELECT games.id, games.name, SUM(playtime_daily.seconds) as seconds
+         FROM playtime_daily
+         JOIN games ON games.id = playtime_daily.game_id
+         WHERE playtime_daily.date BETWEEN ?1 AND ?2
+           AND COALESCE(games.do_not_track, 0) = 0
+         GROUP BY games.id, games.name",
+    )?;
+    let rows = game_stmt.query_map(params![&range_start, &range_end], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    })?;
+    for (id, name, seconds) in rows.flatten() {
+        per_game.entry(id).or_insert((name, 0)).1 += seconds;
+    }
+    let mut per_game_totals: Vec<GameTotal> = per_game
+        .into_iter()
+        .filter(|(_, (_, seconds))| *seconds > 0)
+        .map(|(id, (name, seconds))| GameTotal { id, name, seconds })
+        .collect();
+    per_game_totals.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.id.cmp(&b.id)));
+
+    let mut launch_stmt = conn.prepare(
+        "SELECT s.game_id, COALESCE(g.name, ''), s.launch_profile, s.store,
+                COUNT(*), SUM(s.duration_seconds) as seconds
+         FROM play_sessions s
+         LEFT JOIN games g ON g.id = s.game_id
+         WHERE substr(s.started_at, 1, 10) BETWEEN ?1 AND ?2
+           AND COALESCE(g.do_not_track, 0) = 0
+         GROUP BY s.game_id, s.launch_profile, s.store
+         HAVING seconds > 0
+         ORDER BY seconds DESC",
+    )?;
+    let per_launch_totals = launch_stmt
+        .query_map(params![&range_start, &range_end], |row| {
+            Ok(LaunchTotal {
+                game_id: row.get(0)?,
+                game_name: row.get(1)?,
+                launch_profile: row.get(2)?,
+                store: row.get(3)?,
+                sessions: row.get(4)?,
+                seconds: row.get(5)?,
+            })
+        })?
+        .filter_map(|row| row.ok())
+        .collect();
+
+    Ok(PlaytimeStats {
+        range_start,
+        range_end,
+        total_seconds,
+        daily_totals,
+        per_game_totals,
+        per_launch_totals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str, seconds: i64) -> DailyTotal {
+        DailyTotal {
+            date: date.to_string(),
+            seconds,
         }
+    }
 
-        let mut game_stmt = conn.prepare(
-            "SELECT games.id, games.name, SUM(playtime_daily.seconds) as seconds
-             FROM playtime_daily
-             JOIN games ON games.id = playtime_daily.game_id
-             WHERE playtime_daily.date BETWEEN ?1 AND ?2
-             GROUP BY games.id, games.name
-             HAVING seconds > 0
-             ORDER BY seconds DESC",
-        )?;
-        let per_game_totals = game_stmt
-            .query_map(params![&range_start, &range_end], |row| {
-                Ok(GameTotal {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    seconds: row.get(2)?,
+    #[test]
+    fn library_overview_counts_games_backups_and_genres() {
+        let conn = Connection::open_in_memory().expect("open db");
+        crate::database::init_schema(&conn).expect("init schema");
+        for (id, exe, favorite, playtime, untracked, genres, last_played) in [
+            (
+                "a",
+                "C:/a.exe",
+                1,
+                3600,
+                0,
+                Some("Action, RPG"),
+                Some("2026-05-01T10:00:00+00:00"),
+            ),
+            (
+                "b",
+                "C:/b.exe",
+                0,
+                600,
+                1,
+                Some("RPG"),
+                Some("2026-05-03T10:00:00+00:00"),
+            ),
+            ("c", "C:/gone.exe", 0, 0, 0, None, None),
+        ] {
+            conn.execute(
+                "INSERT INTO games (id, name, exe_path, exe_name, date_added, is_favorite,
+                                    total_playtime, do_not_track, genres, last_played)
+                 VALUES (?1, ?1, ?2, ?2, '2026-01-01T00:00:00+00:00', ?3, ?4, ?5, ?6, ?7)",
+                params![id, exe, favorite, playtime, untracked, genres, last_played],
+            )
+            .expect("insert game");
+        }
+        conn.execute(
+            "INSERT INTO backups (id, game_id, backup_path, backup_size, created_at)
+             VALUES ('b1', 'c', 'x', 2048, '2026-05-02T10:00:00Z')",
+            [],
+        )
+        .expect("insert backup");
+
+        conn.execute(
+            "INSERT INTO install_archives (id, game_id, archive_path, original_dir, exe_relative,
+                                           file_count, original_bytes, archive_bytes, created_at)
+             VALUES ('a1', 'c', 'C:/Backups/installs/c.zip', 'C:/c', 'gone.exe', 1, 10, 5,
+                     '2026-05-02T10:00:00Z')",
+            [],
+        )
+        .expect("insert install archive");
+
+        let overview = load_library_overview(&conn).expect("overview");
+        assert_eq!(overview.total_games, 3);
+        assert_eq!(overview.installed_games, 2);
+        assert_eq!(overview.favorite_games, 1);
+        assert_eq!(overview.games_with_backups, 1);
+        assert_eq!(overview.total_playtime_seconds, 3600);
+        assert_eq!(overview.total_backup_bytes, 2048);
+        // The later session of "b" is hidden because the game isn't tracked.
+        let activity = overview.recent_activity.expect("activity");
+        assert_eq!(
+            (activity.game_id.as_str(), activity.kind.as_str()),
+            ("c", "backup")
+        );
+        assert_eq!(
+            overview.top_genres,
+            vec![
+                GenreCount {
+                    genre: "RPG".to_string(),
+                    games: 2
+                },
+                GenreCount {
+                    genre: "Action".to_string(),
+                    games: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn archived_months_are_prorated_over_the_days_in_range() {
+        let conn = Connection::open_in_memory().expect("open db");
+        crate::database::init_schema(&conn).expect("init schema");
+        conn.execute_batch(
+            "INSERT INTO games (id, name, exe_path, exe_name, date_added)
+                 VALUES ('a', 'Alpha', 'a.exe', 'a.exe', '2026-01-01T00:00:00+00:00');
+             INSERT INTO playtime_monthly (game_id, month, seconds) VALUES ('a', '2026-01', 3100);
+             INSERT INTO playtime_daily (game_id, date, seconds) VALUES ('a', '2026-02-02', 50);",
+        )
+        .expect("insert playtime");
+        let date = |text: &str| parse_date(text).expect("date");
+
+        // 11 of January's 31 days are in range.
+        let stats =
+            load_playtime_stats(&conn, date("2026-01-21"), date("2026-02-05")).expect("stats");
+        assert_eq!(stats.total_seconds, 11 * 100 + 50);
+        assert_eq!(stats.daily_totals[0].date, "2026-01-21");
+        assert_eq!(stats.daily_totals[0].seconds, 100);
+        assert_eq!(stats.per_game_totals.len(), 1);
+        assert_eq!(stats.per_game_totals[0].seconds, 11 * 100 + 50);
+
+        let whole = load_playtime_stats(&conn, date("2026-01-01"), date("2026-01-31"))
+            .expect("whole month");
+        assert_eq!(whole.total_seconds, 3100);
+        assert_eq!(
+            spread_over_month(date("2026-02-01"), 30)
+                .iter()
+                .map(|(_, share)| share)
+                .sum::<i64>(),
+            30
+        );
+    }
+
+    #[test]
+    fn stats_card_counts_streaks_and_escapes_names_in_svg() {
+        let stats = PlaytimeStats {
+            range_start: "2026-03-01".to_string(),
+            range_end: "2026-03-06".to_string(),
+            total_seconds: 5 * 3600,
+            daily_totals: vec![
+                day("2026-03-01", 600),
+                day("2026-03-02", 0),
+                day("2026-03-03", 1200),
+                day("2026-03-04", 1800),
+                day("2026-03-05", 60),
+                day("2026-03-06", 0),
+            ],
+            per_game_totals: (0..7)
+                .map(|i| GameTotal {
+                    id: format!("g{i}"),
+                    name: if i == 0 {
+                        "Tom & Jerry <Remastered>".to_string()
+                    } else {
+                        format!("Game {i}")
+                    },
+                    seconds: 3600 - i * 100,
                 })
-            })?
-            .filter_map(|row| row.ok())
-            .collect();
-
-        Ok(PlaytimeStats {
-            range_start,
-            range_end,
-            total_seconds,
-            daily_totals,
-            per_game_totals,
-        })
-    })
-    .map_err(|e| e.to_string())
+                .collect(),
+            per_launch_totals: Vec::new(),
+        };
+
+        let card = build_stats_card(&stats);
+        assert_eq!(card.days_played, 4);
+        assert_eq!(card.longest_streak_days, 3);
+        assert_eq!(card.total_hours, 5.0);
+        assert_eq!(card.top_games.len(), STATS_CARD_TOP_GAMES);
+
+        let svg = render_stats_card_svg(&card);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Tom &amp; Jerry &lt;Remastered&gt;"));
+        assert!(!svg.contains("Game 6"));
+    }
 }
@@ -0,0 +1,54 @@
+use crate::db::GlobalDb;
+use crate::domain::games::Game;
+pub use crate::domain::tags::{Collection, CollectionInput, Tag};
+use crate::services::tags as tags_service;
+
+#[tauri::command]
+pub fn get_tags() -> Result<Vec<Tag>, String> {
+    tags_service::get_tags(&GlobalDb)
+}
+
+#[tauri::command]
+pub fn add_tag(name: String, color: Option<String>) -> Result<Tag, String> {
+    tags_service::add_tag(&GlobalDb, name, color)
+}
+
+#[tauri::command]
+pub fn delete_tag(id: String) -> Result<(), String> {
+    tags_service::delete_tag(&GlobalDb, id)
+}
+
+#[tauri::command]
+pub fn assign_tag(game_id: String, tag_id: String) -> Result<Game, String> {
+    tags_service::assign_tag(&GlobalDb, game_id, tag_id)
+}
+
+#[tauri::command]
+pub fn unassign_tag(game_id: String, tag_id: String) -> Result<Game, String> {
+    tags_service::unassign_tag(&GlobalDb, game_id, tag_id)
+}
+
+#[tauri::command]
+pub fn get_games_by_tag(tag_id: String) -> Result<Vec<Game>, String> {
+    tags_service::get_games_by_tag(&GlobalDb, tag_id)
+}
+
+#[tauri::command]
+pub fn get_collections() -> Result<Vec<Collection>, String> {
+    tags_service::get_collections(&GlobalDb)
+}
+
+#[tauri::command]
+pub fn save_collection(collection: CollectionInput) -> Result<Collection, String> {
+    tags_service::save_collection(&GlobalDb, collection)
+}
+
+#[tauri::command]
+pub fn delete_collection(id: String) -> Result<(), String> {
+    tags_service::delete_collection(&GlobalDb, id)
+}
+
+#[tauri::command]
+pub fn get_collection_games(id: String) -> Result<Vec<Game>, String> {
+    tags_service::get_collection_games(&GlobalDb, id)
+}
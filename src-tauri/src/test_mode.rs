@@ -0,0 +1,243 @@
+//! Deterministic backend state for the frontend E2E suite.
+//!
+//! Enabled only when the app is started with `ARRANCADOR_TEST_MODE=1`. In that mode the
+//! database lives in memory (the real AppData file is never opened), the tracker runs on
+//! an adjustable clock, and the `__test_*` commands below become callable.
+
+use crate::database::{init_schema, DB};
+use crate::db::{Db, GlobalDb};
+use crate::services::tracker::Clock;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicI64, Ordering};
+use uuid::Uuid;
+
+pub const TEST_MODE_ENV: &str = "ARRANCADOR_TEST_MODE";
+
+static CLOCK_OFFSET_SECS: AtomicI64 = AtomicI64::new(0);
+
+pub fn is_enabled() -> bool {
+    std::env::var(TEST_MODE_ENV)
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+fn ensure_enabled() -> Result<(), String> {
+    if is_enabled() {
+        Ok(())
+    } else {
+        Err("Тестовый режим не включен".to_string())
+    }
+}
+
+/// System time shifted by the offset accumulated through `__test_advance_clock`.
+#[derive(Clone, Copy, Default)]
+pub struct TestClock;
+
+impl Clock for TestClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now() + Duration::seconds(CLOCK_OFFSET_SECS.load(Ordering::SeqCst))
+    }
+
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct LibraryFixture {
+    #[serde(default)]
+    pub games: Vec<FixtureGame>,
+    #[serde(default)]
+    pub playtime: Vec<FixturePlaytime>,
+    #[serde(default)]
+    pub settings: Vec<FixtureSetting>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FixtureGame {
+    pub id: Option<String>,
+    pub name: String,
+    pub exe_path: String,
+    pub exe_name: Option<String>,
+    #[serde(default)]
+    pub is_favorite: bool,
+    #[serde(default)]
+    pub total_playtime: i64,
+    pub last_played: Option<String>,
+    pub date_added: Option<String>,
+    pub released: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FixturePlaytime {
+    pub game_id: String,
+    pub date: String,
+    pub seconds: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FixtureSetting {
+    pub key: String,
+    pub value: String,
+}
+
+/// Inserts the fixture in one transaction and returns the ids of the seeded games.
+pub fn seed_library<D: Db, C: Clock>(
+    db: &D,
+    clock: &C,
+    fixture: &LibraryFixture,
+) -> rusqlite::Result<Vec<String>> {
+    db.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let now = clock.now().to_rfc3339();
+        let mut ids = Vec::with_capacity(fixture.games.len());
+
+        for game in &fixture.games {
+            let id = game
+                .id
+                .clone()
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            let exe_name = game.exe_name.clone().unwrap_or_else(|| {
+                game.exe_path
+                    .rsplit(['/', '\\'])
+                    .next()
+                    .unwrap_or(&game.exe_path)
+                    .to_string()
+            });
+            tx.execute(
+                "INSERT INTO games (id, name, exe_path, exe_name, is_favorite, total_playtime,
+                                    last_played, date_added, released)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    id,
+                    game.name,
+                    game.exe_path,
+                    exe_name,
+                    game.is_favorite as i32,
+                    game.total_playtime,
+                    game.last_played,
+                    game.date_added.as_deref().unwrap_or(&now),
+                    game.released,
+                ],
+            )?;
+            ids.push(id);
+        }
+
+        for row in &fixture.playtime {
+            tx.execute(
+                "INSERT INTO playtime_daily (game_id, date, seconds) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(game_id, date) DO UPDATE SET seconds = seconds + excluded.seconds",
+                params![row.game_id, row.date, row.seconds],
+            )?;
+        }
+
+        for setting in &fixture.settings {
+            tx.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                params![setting.key, setting.value],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(ids)
+    })
+}
+
+pub fn open_test_database() -> rusqlite::Result<Connection> {
+    let conn = Connection::open_in_memory()?;
+    init_schema(&conn)?;
+    Ok(conn)
+}
+
+#[tauri::command]
+pub fn __test_reset_db() -> Result<(), String> {
+    ensure_enabled()?;
+    let conn = open_test_database().map_err(|e| e.to_string())?;
+    let mut db = DB.lock().map_err(|e| e.to_string())?;
+    *db = Some(conn);
+    CLOCK_OFFSET_SECS.store(0, Ordering::SeqCst);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn __test_seed_library(fixture: LibraryFixture) -> Result<Vec<String>, String> {
+    ensure_enabled()?;
+    seed_library(&GlobalDb, &TestClock, &fixture).map_err(|e| e.to_string())
+}
+
+/// Moves the test clock forward by `seconds` and returns the new current time.
+#[tauri::command]
+pub fn __test_advance_clock(seconds: i64) -> Result<String, String> {
+    ensure_enabled()?;
+    CLOCK_OFFSET_SECS.fetch_add(seconds, Ordering::SeqCst);
+    Ok(TestClock.now().to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::ConnectionDb;
+
+    struct FixedClock(DateTime<Utc>);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+
+        fn today(&self) -> NaiveDate {
+            self.0.date_naive()
+        }
+    }
+
+    #[test]
+    fn seed_library_inserts_games_playtime_and_settings() {
+        let db = ConnectionDb::new(open_test_database().expect("open test db"));
+        let clock = FixedClock(
+            DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+                .expect("parse")
+                .with_timezone(&Utc),
+        );
+        let fixture: LibraryFixture = serde_json::from_value(serde_json::json!({
+            "games": [
+                { "id": "g1", "name": "Alpha", "exe_path": "C:/Games/Alpha/alpha.exe", "is_favorite": true },
+                { "name": "Beta", "exe_path": "C:\\Games\\Beta\\beta.exe" }
+            ],
+            "playtime": [{ "game_id": "g1", "date": "2026-01-01", "seconds": 600 }],
+            "settings": [{ "key": "theme", "value": "dark" }]
+        }))
+        .expect("fixture");
+
+        let ids = seed_library(&db, &clock, &fixture).expect("seed");
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], "g1");
+
+        db.with_conn(|conn| {
+            let (exe_name, date_added): (String, String) = conn.query_row(
+                "SELECT exe_name, date_added FROM games WHERE id = ?1",
+                params![ids[1]],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            assert_eq!(exe_name, "beta.exe");
+            assert_eq!(date_added, clock.now().to_rfc3339());
+
+            let seconds: i64 = conn.query_row(
+                "SELECT seconds FROM playtime_daily WHERE game_id = 'g1'",
+                [],
+                |row| row.get(0),
+            )?;
+            assert_eq!(seconds, 600);
+
+            let theme: String = conn.query_row(
+                "SELECT value FROM settings WHERE key = 'theme'",
+                [],
+                |row| row.get(0),
+            )?;
+            assert_eq!(theme, "dark");
+            Ok(())
+        })
+        .expect("verify");
+    }
+}
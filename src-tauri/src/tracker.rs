@@ -1,9 +1,14 @@
 use crate::db::GlobalDb;
 use crate::services::tracker::{SystemClock, TrackerService};
+use crate::test_mode::{self, TestClock};
 use tauri::AppHandle;
 
 pub fn start_tracker(app: AppHandle) {
-    TrackerService::new(GlobalDb, SystemClock).start(app);
+    if test_mode::is_enabled() {
+        TrackerService::new(GlobalDb, TestClock).start(app);
+    } else {
+        TrackerService::new(GlobalDb, SystemClock).start(app);
+    }
 }
 
 #[cfg(test)]
@@ -35,6 +40,24 @@ mod tests {
             [],
         )
         .expect("create playtime_daily table");
+        conn.execute(
+            "CREATE TABLE playtime_monthly (
+                game_id TEXT NOT NULL,
+                month TEXT NOT NULL,
+                seconds INTEGER NOT NULL DEFAULT 0,
+                UNIQUE(game_id, month)
+            )",
+            [],
+        )
+        .expect("create playtime_monthly table");
+        conn.execute(
+            "CREATE TABLE settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("create settings table");
 
         set_test_db(conn)
     }
@@ -98,4 +121,52 @@ mod tests {
         assert_eq!(total, expected);
         assert_eq!(daily, expected);
     }
+
+    #[test]
+    fn archive_playtime_rolls_old_days_into_months() {
+        let _lock = TEST_DB_MUTEX.lock().unwrap();
+        let _db_guard = setup_db();
+
+        crate::database::with_db(|conn| {
+            conn.execute(
+                "INSERT INTO settings (key, value) VALUES ('playtime_archive_horizon_days', '60')",
+                [],
+            )?;
+            for (date, seconds) in [
+                ("2024-01-05", 100),
+                ("2024-01-20", 50),
+                ("2024-02-10", 30),
+                ("2024-04-01", 70),
+            ] {
+                conn.execute(
+                    "INSERT INTO playtime_daily (game_id, date, seconds) VALUES ('game-1', ?1, ?2)",
+                    params![date, seconds],
+                )?;
+            }
+            Ok(())
+        })
+        .expect("seed playtime");
+
+        let now = DateTime::parse_from_rfc3339("2024-04-15T12:00:00Z")
+            .expect("parse now")
+            .with_timezone(&Utc);
+        let service = TrackerService::new(GlobalDb, FixedClock::new(now));
+        service.archive_playtime();
+
+        let (monthly, remaining_days) = crate::database::with_db(|conn| {
+            let mut stmt =
+                conn.prepare("SELECT month, seconds FROM playtime_monthly ORDER BY month")?;
+            let monthly: Vec<(String, i64)> = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .flatten()
+                .collect();
+            let mut stmt = conn.prepare("SELECT date FROM playtime_daily ORDER BY date")?;
+            let days: Vec<String> = stmt.query_map([], |row| row.get(0))?.flatten().collect();
+            Ok((monthly, days))
+        })
+        .expect("fetch playtime");
+
+        assert_eq!(monthly, vec![("2024-01".to_string(), 150)]);
+        assert_eq!(remaining_days, vec!["2024-02-10", "2024-04-01"]);
+    }
 }
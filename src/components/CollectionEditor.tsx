@@ -0,0 +1,131 @@
+import { Save, X } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { tagsApi } from "@/lib/api";
+import type { Collection, Tag } from "@/types";
+
+const hoursToSeconds = (text: string) => {
+  const hours = Number.parseFloat(text.replace(",", "."));
+  return Number.isFinite(hours) ? Math.round(hours * 3600) : null;
+};
+
+// A smart collection: a named filter by genre, playtime, tag and favourites whose games are
+// picked again every time it is opened.
+export function CollectionEditor({
+  tags,
+  onSaved,
+  onClose,
+}: {
+  tags: Tag[];
+  onSaved: (collection: Collection) => Promise<void> | void;
+  onClose: () => void;
+}) {
+  const { notify } = useToast();
+  const [name, setName] = useState("");
+  const [genre, setGenre] = useState("");
+  const [minHours, setMinHours] = useState("");
+  const [maxHours, setMaxHours] = useState("");
+  const [tagId, setTagId] = useState("");
+  const [favoritesOnly, setFavoritesOnly] = useState(false);
+  const [busy, setBusy] = useState(false);
+
+  const save = async () => {
+    setBusy(true);
+    try {
+      const collection = await tagsApi.saveCollection({
+        name,
+        query: {
+          genre: genre.trim() || null,
+          min_playtime: hoursToSeconds(minHours),
+          max_playtime: hoursToSeconds(maxHours),
+          tag_ids: tagId ? [tagId] : [],
+          favorites_only: favoritesOnly,
+        },
+      });
+      await onSaved(collection);
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить коллекцию",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="flex items-center justify-between">
+        <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+          {"Новая коллекция"}
+        </div>
+        <Button variant="ghost" size="icon" className="h-6 w-6" onClick={onClose}>
+          <X className="w-4 h-4" />
+        </Button>
+      </div>
+      <Input
+        value={name}
+        onChange={(event) => setName(event.target.value)}
+        placeholder="Название"
+        className="h-8 text-sm"
+      />
+      <div className="grid grid-cols-1 sm:grid-cols-3 gap-2">
+        <Input
+          value={genre}
+          onChange={(event) => setGenre(event.target.value)}
+          placeholder="Жанр, например RPG"
+          className="h-8 text-xs"
+        />
+        <Input
+          value={minHours}
+          onChange={(event) => setMinHours(event.target.value)}
+          placeholder="Наиграно от, ч"
+          inputMode="decimal"
+          className="h-8 text-xs"
+        />
+        <Input
+          value={maxHours}
+          onChange={(event) => setMaxHours(event.target.value)}
+          placeholder="Наиграно до, ч"
+          inputMode="decimal"
+          className="h-8 text-xs"
+        />
+      </div>
+      <div className="flex flex-wrap items-center gap-3">
+        <select
+          value={tagId}
+          onChange={(event) => setTagId(event.target.value)}
+          className="h-8 rounded-md border border-input bg-background px-2 text-xs"
+          aria-label="Тег"
+        >
+          <option value="">Любой тег</option>
+          {tags.map((tag) => (
+            <option key={tag.id} value={tag.id}>
+              {tag.name}
+            </option>
+          ))}
+        </select>
+        <label className="flex items-center gap-2 text-xs text-muted-foreground">
+          <input
+            type="checkbox"
+            checked={favoritesOnly}
+            onChange={(event) => setFavoritesOnly(event.target.checked)}
+          />
+          {"Только избранное"}
+        </label>
+        <Button
+          size="sm"
+          className="gap-2 ml-auto"
+          disabled={busy || !name.trim()}
+          onClick={save}
+        >
+          <Save className="w-4 h-4" />
+          {"Сохранить"}
+        </Button>
+      </div>
+    </div>
+  );
+}
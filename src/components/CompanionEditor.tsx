@@ -0,0 +1,157 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Switch } from "@/components/ui/switch";
+import { gamesApi } from "@/lib/api";
+import type { Game, GameCompanion } from "@/types";
+
+const fileName = (path: string) => path.split(/[\\/]/).pop() ?? path;
+
+// Programs started with the game: a VPN, a controller mapper, voice chat. Ones already running
+// are left alone, and only the ones the launch started are closed with the game.
+export function CompanionEditor({ game }: { game: Game }) {
+  const { notify } = useToast();
+  const [companions, setCompanions] = useState<GameCompanion[]>([]);
+
+  const load = () =>
+    gamesApi
+      .getCompanions(game.id)
+      .then(setCompanions)
+      .catch((e) => {
+        console.error("Failed to load companions:", e);
+        setCompanions([]);
+      });
+
+  useEffect(() => {
+    load();
+  }, [game.id]);
+
+  const save = async (companion: GameCompanion) => {
+    try {
+      await gamesApi.saveCompanion({
+        id: companion.id,
+        game_id: game.id,
+        exe_path: companion.exe_path,
+        args: companion.args,
+        close_on_exit: companion.close_on_exit,
+      });
+      await load();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить программу",
+        description: String(e),
+      });
+      await load();
+    }
+  };
+
+  const handleAdd = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: "Исполняемые файлы", extensions: ["exe"] }],
+      title: "Программа, запускаемая вместе с игрой",
+    });
+    const path = Array.isArray(selected) ? selected[0] : selected;
+    if (!path) return;
+    try {
+      await gamesApi.saveCompanion({ game_id: game.id, exe_path: path });
+      await load();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось добавить программу",
+        description: String(e),
+      });
+    }
+  };
+
+  const handleRemove = async (companion: GameCompanion) => {
+    try {
+      await gamesApi.deleteCompanion(companion.id);
+      setCompanions((prev) => prev.filter((item) => item.id !== companion.id));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось удалить программу",
+        description: String(e),
+      });
+    }
+  };
+
+  const edit = (id: string, changes: Partial<GameCompanion>) =>
+    setCompanions((prev) =>
+      prev.map((item) => (item.id === id ? { ...item, ...changes } : item)),
+    );
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="flex items-center justify-between">
+        <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+          {"Запускать вместе с игрой"}
+        </div>
+        <Button
+          variant="ghost"
+          size="icon"
+          className="h-6 w-6"
+          onClick={handleAdd}
+          title="Добавить программу"
+        >
+          <Plus className="w-4 h-4" />
+        </Button>
+      </div>
+      {companions.map((companion) => (
+        <div
+          key={companion.id}
+          className="rounded-xl border border-border/60 p-3 space-y-2"
+        >
+          <div className="flex items-center gap-2">
+            <div
+              className="flex-1 truncate text-sm font-medium"
+              title={companion.exe_path}
+            >
+              {fileName(companion.exe_path)}
+            </div>
+            <Button
+              variant="ghost"
+              size="icon"
+              className="shrink-0 h-7 w-7"
+              onClick={() => handleRemove(companion)}
+              title="Удалить программу"
+            >
+              <Trash2 className="w-4 h-4" />
+            </Button>
+          </div>
+          <Input
+            value={companion.args ?? ""}
+            onChange={(event) =>
+              edit(companion.id, { args: event.target.value })
+            }
+            onBlur={() => save(companion)}
+            placeholder="Аргументы"
+            aria-label="Аргументы"
+            className="h-8 font-mono text-xs"
+          />
+          <label className="flex items-center justify-between gap-2 text-xs text-muted-foreground">
+            {"Закрывать после выхода из игры"}
+            <Switch
+              checked={companion.close_on_exit}
+              onCheckedChange={(checked) =>
+                save({ ...companion, close_on_exit: checked })
+              }
+              aria-label="Закрывать после выхода из игры"
+            />
+          </label>
+        </div>
+      ))}
+      {companions.length === 0 && (
+        <p className="text-xs text-muted-foreground">
+          {"VPN, раскладка геймпада, голосовой чат — всё, что нужно игре."}
+        </p>
+      )}
+    </div>
+  );
+}
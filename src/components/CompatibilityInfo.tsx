@@ -0,0 +1,99 @@
+import { Gamepad2, Loader2, RefreshCw } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { compatibilityApi, describeMetadataError } from "@/lib/api";
+import type { Game, SteamDeckStatus } from "@/types";
+
+const DECK_LABELS: Record<SteamDeckStatus, string> = {
+  verified: "Проверено",
+  playable: "Играбельно",
+  unsupported: "Не поддерживается",
+  unknown: "Не проверено",
+};
+
+// ProtonDB tier and Steam Deck status, for playing on Linux or streaming to a Deck. Games
+// launched through Steam use their store id; others need the app id typed in.
+export function CompatibilityInfo({
+  game,
+  onUpdated,
+}: {
+  game: Game;
+  onUpdated: (game: Game) => void;
+}) {
+  const { notify } = useToast();
+  const steamAppId = game.launch_method === "steam" ? game.store_id : null;
+  const [appId, setAppId] = useState("");
+  const [loading, setLoading] = useState(false);
+
+  const refresh = async () => {
+    setLoading(true);
+    try {
+      onUpdated(
+        await compatibilityApi.refresh(game.id, appId.trim() || undefined),
+      );
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось получить данные о совместимости",
+        description: describeMetadataError(e, "ProtonDB / Steam"),
+      });
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <div className="mt-6 pt-4 border-t border-border/60 space-y-2 text-sm">
+      <div className="flex items-center justify-between gap-2">
+        <span className="flex items-center gap-2 text-muted-foreground">
+          <Gamepad2 className="w-4 h-4" />
+          {"Linux и Steam Deck"}
+        </span>
+        <div className="flex items-center gap-2">
+          {!steamAppId && (
+            <Input
+              value={appId}
+              onChange={(e) => setAppId(e.target.value)}
+              placeholder="Steam App ID"
+              className="h-8 w-32 text-xs"
+            />
+          )}
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={refresh}
+            disabled={loading || (!steamAppId && !appId.trim())}
+            className="gap-2 text-xs"
+          >
+            {loading ? (
+              <Loader2 className="w-3 h-3 animate-spin" />
+            ) : (
+              <RefreshCw className="w-3 h-3" />
+            )}
+            {"Обновить"}
+          </Button>
+        </div>
+      </div>
+      {(game.protondb_tier || game.steam_deck_status) && (
+        <div className="grid grid-cols-2 gap-4">
+          <div>
+            <span className="text-muted-foreground block mb-1">ProtonDB</span>
+            <span className="capitalize">{game.protondb_tier ?? "—"}</span>
+          </div>
+          <div>
+            <span className="text-muted-foreground block mb-1">
+              Steam Deck
+            </span>
+            <span>
+              {game.steam_deck_status
+                ? DECK_LABELS[game.steam_deck_status]
+                : "—"}
+            </span>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,124 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { Download, FolderOpen, ImageIcon } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { imagesApi } from "@/lib/api";
+import { imageSrc } from "@/lib/images";
+import type { Game } from "@/types";
+
+const isRemote = (path: string | null) => !!path && /^https?:/i.test(path);
+
+// The cover shown in the library grid. Picked files and downloaded URLs are copied into the
+// local image store, so the cover keeps working offline.
+export function CoverEditor({
+  game,
+  onSaved,
+}: {
+  game: Game;
+  onSaved: () => Promise<void> | void;
+}) {
+  const { notify } = useToast();
+  const [url, setUrl] = useState(
+    isRemote(game.background_image) ? (game.background_image ?? "") : "",
+  );
+  const [busy, setBusy] = useState(false);
+  const preview = game.cover_thumbnail ?? game.cover_image;
+  const needsThumbnail =
+    !!game.cover_image && !isRemote(game.cover_image) && !game.cover_thumbnail;
+
+  const run = async (action: () => Promise<unknown>) => {
+    setBusy(true);
+    try {
+      await action();
+      await onSaved();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить обложку",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const pickFile = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [
+        { name: "Изображения", extensions: ["png", "jpg", "jpeg", "webp"] },
+      ],
+      title: "Обложка игры",
+    });
+    const path = Array.isArray(selected) ? selected[0] : selected;
+    if (!path) return;
+    await run(() => imagesApi.importCover(game.id, path));
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Обложка"}
+      </div>
+      <div className="flex gap-3">
+        <div className="w-16 aspect-[2/3] shrink-0 overflow-hidden rounded-md bg-muted flex items-center justify-center">
+          {preview ? (
+            <img
+              src={imageSrc(preview)}
+              alt={game.name}
+              className="w-full h-full object-cover"
+            />
+          ) : (
+            <ImageIcon className="w-5 h-5 text-muted-foreground" />
+          )}
+        </div>
+        <div className="flex-1 space-y-2">
+          <div className="flex items-center gap-2">
+            <Input
+              value={url}
+              onChange={(event) => setUrl(event.target.value)}
+              placeholder="https://..."
+              className="h-8 text-xs"
+            />
+            <Button
+              variant="outline"
+              size="icon"
+              className="shrink-0 h-8 w-8"
+              disabled={busy || !isRemote(url.trim())}
+              onClick={() => run(() => imagesApi.cacheCover(game.id, url.trim()))}
+              title="Скачать и сохранить"
+            >
+              <Download className="w-4 h-4" />
+            </Button>
+          </div>
+          <div className="flex flex-wrap gap-2">
+            <Button
+              variant="outline"
+              size="sm"
+              className="gap-2"
+              disabled={busy}
+              onClick={pickFile}
+            >
+              <FolderOpen className="w-4 h-4" />
+              {"Выбрать файл"}
+            </Button>
+            {needsThumbnail && (
+              <Button
+                variant="ghost"
+                size="sm"
+                disabled={busy}
+                onClick={() =>
+                  run(() => imagesApi.generateThumbnail(game.id))
+                }
+              >
+                {"Создать миниатюру"}
+              </Button>
+            )}
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,83 @@
+import { DatabaseZap, Loader2, RotateCcw } from "lucide-react";
+import { useEffect, useState } from "react";
+import { Button } from "@/components/ui/button";
+import { systemApi } from "@/lib/api";
+import type { DatabaseStatus } from "@/types";
+
+// Shown when startup couldn't get a writable database, usually because a sync client or a
+// leftover arrancador process holds the lock. Retrying reopens it and reloads the app.
+export function DatabaseStatusBanner() {
+  const [status, setStatus] = useState<DatabaseStatus | null>(null);
+  const [retrying, setRetrying] = useState(false);
+
+  useEffect(() => {
+    systemApi
+      .getDatabaseStatus()
+      .then(setStatus)
+      .catch((e) => console.error("Failed to load database status:", e));
+  }, []);
+
+  const retry = async () => {
+    setRetrying(true);
+    try {
+      const next = await systemApi.retryDatabaseOpen();
+      if (next.mode === "ready") {
+        window.location.reload();
+        return;
+      }
+      setStatus(next);
+    } catch (e) {
+      console.error("Failed to reopen database:", e);
+    } finally {
+      setRetrying(false);
+    }
+  };
+
+  if (!status || status.mode === "ready") return null;
+
+  return (
+    <div className="border-b border-amber-500/40 bg-amber-500/10 px-4 py-3">
+      <div className="flex items-start gap-3">
+        <DatabaseZap className="h-4 w-4 mt-0.5 text-amber-500" />
+        <div className="flex-1 min-w-0 space-y-1">
+          <div className="text-sm font-semibold">
+            {status.mode === "read_only"
+              ? "База данных занята другим процессом — только чтение"
+              : "Не удалось открыть базу данных"}
+          </div>
+          <div className="text-xs text-muted-foreground">
+            {status.mode === "read_only"
+              ? "Изменения не сохранятся, время игры и автобэкапы не записываются. Закройте программы синхронизации или другой экземпляр Arrancador и повторите."
+              : "Библиотека недоступна. Закройте программы, которые могут держать файл, и повторите."}
+          </div>
+          <div
+            className="text-xs text-muted-foreground truncate"
+            title={status.path}
+          >
+            {status.path}
+            {status.attempts > 1 && ` · попыток: ${status.attempts}`}
+          </div>
+          {status.error && (
+            <div className="font-mono text-[11px] text-muted-foreground">
+              {status.error}
+            </div>
+          )}
+        </div>
+        <Button
+          size="sm"
+          variant="outline"
+          disabled={retrying}
+          onClick={retry}
+          className="gap-2"
+        >
+          {retrying ? (
+            <Loader2 className="w-4 h-4 animate-spin" />
+          ) : (
+            <RotateCcw className="w-4 h-4" />
+          )}
+          {"Повторить"}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,182 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { Joystick, Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { gamesApi } from "@/lib/api";
+import type { Emulator } from "@/types";
+
+const splitExtensions = (text: string) =>
+  text
+    .split(/[\s,]+/)
+    .map((ext) => ext.trim())
+    .filter(Boolean);
+
+// Emulators ROM games start through. Files with an emulator's extensions show up in the
+// folder scan next to executables.
+export function EmulatorSettings() {
+  const { notify } = useToast();
+  const [emulators, setEmulators] = useState<Emulator[]>([]);
+
+  const load = () =>
+    gamesApi
+      .getEmulators()
+      .then(setEmulators)
+      .catch((e) => {
+        console.error("Failed to load emulators:", e);
+        setEmulators([]);
+      });
+
+  useEffect(() => {
+    load();
+  }, []);
+
+  const save = async (emulator: Emulator) => {
+    try {
+      await gamesApi.saveEmulator({
+        id: emulator.id,
+        name: emulator.name,
+        exe_path: emulator.exe_path,
+        args_template: emulator.args_template,
+        rom_extensions: emulator.rom_extensions,
+      });
+      await load();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить эмулятор",
+        description: String(e),
+      });
+      await load();
+    }
+  };
+
+  const handleAdd = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: "Исполняемые файлы", extensions: ["exe"] }],
+      title: "Исполняемый файл эмулятора",
+    });
+    const path = Array.isArray(selected) ? selected[0] : selected;
+    if (!path) return;
+    const fileName = path.split(/[\\/]/).pop() ?? path;
+    try {
+      await gamesApi.saveEmulator({
+        name: fileName.replace(/\.exe$/i, ""),
+        exe_path: path,
+      });
+      await load();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось добавить эмулятор",
+        description: String(e),
+      });
+    }
+  };
+
+  const handleRemove = async (emulator: Emulator) => {
+    try {
+      await gamesApi.deleteEmulator(emulator.id);
+      setEmulators((prev) => prev.filter((item) => item.id !== emulator.id));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось удалить эмулятор",
+        description: String(e),
+      });
+    }
+  };
+
+  const edit = (id: string, changes: Partial<Emulator>) =>
+    setEmulators((prev) =>
+      prev.map((item) => (item.id === id ? { ...item, ...changes } : item)),
+    );
+
+  return (
+    <section className="space-y-4">
+      <div className="flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2">
+          <Joystick className="w-5 h-5" />
+          <h2 className="text-lg font-semibold">Эмуляторы</h2>
+        </div>
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={handleAdd}
+          className="gap-2"
+        >
+          <Plus className="w-4 h-4" />
+          Добавить
+        </Button>
+      </div>
+
+      <div className="bg-card rounded-lg border p-4 space-y-3">
+        {emulators.length === 0 && (
+          <p className="text-sm text-muted-foreground">
+            Эмуляторы не добавлены
+          </p>
+        )}
+        {emulators.map((emulator) => (
+          <div
+            key={emulator.id}
+            className="rounded-xl border border-border/60 p-3 space-y-2"
+          >
+            <div className="flex items-center gap-2">
+              <Input
+                value={emulator.name}
+                onChange={(event) =>
+                  edit(emulator.id, { name: event.target.value })
+                }
+                onBlur={() => save(emulator)}
+                className="h-8 text-sm font-medium"
+              />
+              <Button
+                variant="ghost"
+                size="icon"
+                className="shrink-0"
+                onClick={() => handleRemove(emulator)}
+                title="Удалить эмулятор"
+              >
+                <Trash2 className="w-4 h-4" />
+              </Button>
+            </div>
+            <div
+              className="truncate text-[11px] text-muted-foreground"
+              title={emulator.exe_path}
+            >
+              {emulator.exe_path}
+            </div>
+            <Input
+              value={emulator.args_template}
+              onChange={(event) =>
+                edit(emulator.id, { args_template: event.target.value })
+              }
+              onBlur={() => save(emulator)}
+              placeholder={'-f "{ROM}"'}
+              aria-label="Аргументы запуска"
+              className="h-8 font-mono text-xs"
+            />
+            <Input
+              key={emulator.rom_extensions.join(",")}
+              defaultValue={emulator.rom_extensions.join(", ")}
+              onBlur={(event) =>
+                save({
+                  ...emulator,
+                  rom_extensions: splitExtensions(event.target.value),
+                })
+              }
+              placeholder="Расширения ROM, например sfc, smc"
+              aria-label="Расширения ROM"
+              className="h-8 text-xs"
+            />
+          </div>
+        ))}
+        <p className="text-xs text-muted-foreground">
+          {"{ROM} в аргументах заменяется путём к файлу игры."}
+        </p>
+      </div>
+    </section>
+  );
+}
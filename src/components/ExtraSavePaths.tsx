@@ -0,0 +1,177 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { FolderMinus, FolderPlus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Switch } from "@/components/ui/switch";
+import { backupApi } from "@/lib/api";
+import { cn } from "@/lib/utils";
+import type { GameSavePath } from "@/types";
+
+// Save roots backed up together with the main save path (configs in AppData next to
+// saves in Documents, for example) and folders to leave out of every backup.
+export function ExtraSavePaths({ gameId }: { gameId: string }) {
+  const { notify } = useToast();
+  const [paths, setPaths] = useState<GameSavePath[]>([]);
+
+  const load = () =>
+    backupApi
+      .getSavePaths(gameId)
+      .then(setPaths)
+      .catch((e) => {
+        console.error("Failed to load save paths:", e);
+        setPaths([]);
+      });
+
+  useEffect(() => {
+    load();
+  }, [gameId]);
+
+  const handleAdd = async (exclude: boolean) => {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: exclude
+        ? "Папка, которую не включать в бэкап"
+        : "Дополнительная папка с сохранениями",
+    });
+    const path = Array.isArray(selected) ? selected[0] : selected;
+    if (!path) return;
+    try {
+      await backupApi.addSavePath(gameId, path, undefined, exclude);
+      await load();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось добавить путь",
+        description: String(e),
+      });
+    }
+  };
+
+  const handleUpdate = async (
+    entry: GameSavePath,
+    changes: { label?: string; enabled?: boolean; exclude?: boolean },
+  ) => {
+    setPaths((prev) =>
+      prev.map((item) => (item.id === entry.id ? { ...item, ...changes } : item)),
+    );
+    try {
+      await backupApi.updateSavePath(
+        entry.id,
+        changes.label,
+        changes.enabled,
+        changes.exclude,
+      );
+    } catch (e) {
+      console.error("Failed to update save path:", e);
+      await load();
+    }
+  };
+
+  const handleRemove = async (entry: GameSavePath) => {
+    try {
+      await backupApi.removeSavePath(entry.id);
+      setPaths((prev) => prev.filter((item) => item.id !== entry.id));
+    } catch (e) {
+      console.error("Failed to remove save path:", e);
+    }
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Дополнительные пути"}
+      </div>
+
+      {paths.map((entry) => (
+        <div
+          key={entry.id}
+          className={cn(
+            "rounded-xl border border-border/60 p-3 space-y-2",
+            !entry.enabled && "opacity-60",
+          )}
+        >
+          <div className="flex items-center gap-2">
+            <Input
+              value={entry.label ?? ""}
+              onChange={(event) =>
+                setPaths((prev) =>
+                  prev.map((item) =>
+                    item.id === entry.id
+                      ? { ...item, label: event.target.value }
+                      : item,
+                  ),
+                )
+              }
+              onBlur={(event) =>
+                handleUpdate(entry, { label: event.target.value })
+              }
+              placeholder={entry.exclude ? "Исключение" : "Название"}
+              className="h-8 text-xs"
+            />
+            <Switch
+              checked={entry.enabled}
+              onCheckedChange={(checked) =>
+                handleUpdate(entry, { enabled: checked })
+              }
+              title={entry.enabled ? "Выключить" : "Включить"}
+            />
+            <Button
+              variant="ghost"
+              size="icon"
+              onClick={() => handleRemove(entry)}
+              title="Удалить"
+            >
+              <Trash2 className="w-4 h-4" />
+            </Button>
+          </div>
+          <div className="flex items-center justify-between gap-2">
+            <span className="truncate font-mono text-[11px] text-muted-foreground">
+              {entry.path}
+            </span>
+            <button
+              type="button"
+              className={cn(
+                "shrink-0 rounded-md border px-1.5 py-0.5 text-[10px] uppercase tracking-wider",
+                entry.exclude
+                  ? "border-amber-500/40 text-amber-300"
+                  : "border-emerald-500/40 text-emerald-300",
+              )}
+              onClick={() => handleUpdate(entry, { exclude: !entry.exclude })}
+            >
+              {entry.exclude ? "Исключить" : "Включить в бэкап"}
+            </button>
+          </div>
+        </div>
+      ))}
+
+      <div className="flex gap-2">
+        <Button
+          variant="outline"
+          size="sm"
+          className="text-xs"
+          onClick={() => handleAdd(false)}
+        >
+          <FolderPlus className="w-3 h-3" />
+          {"Добавить папку"}
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          className="text-xs"
+          onClick={() => handleAdd(true)}
+        >
+          <FolderMinus className="w-3 h-3" />
+          {"Исключить папку"}
+        </Button>
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {
+          "Эти папки попадают в бэкап вместе с основным путём сохранений. Исключённые папки пропускаются, даже если лежат внутри других путей."
+        }
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,136 @@
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { FileCheck, Loader2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { gamesApi } from "@/lib/api";
+import type { FileVerification, Game } from "@/types";
+
+type VerifyProgressPayload = {
+  game_id: string;
+  done: number;
+  total: number;
+};
+
+const LISTED_FILES = 5;
+
+function FileList({ title, files }: { title: string; files: string[] }) {
+  if (files.length === 0) return null;
+  return (
+    <div className="space-y-1">
+      <div className="text-xs font-medium">{`${title}: ${files.length}`}</div>
+      <ul className="text-[11px] text-muted-foreground space-y-0.5">
+        {files.slice(0, LISTED_FILES).map((file) => (
+          <li key={file} className="truncate" title={file}>
+            {file}
+          </li>
+        ))}
+        {files.length > LISTED_FILES && (
+          <li>{`и ещё ${files.length - LISTED_FILES}`}</li>
+        )}
+      </ul>
+    </div>
+  );
+}
+
+// Hashes the install folder and compares it with the files recorded on the first check,
+// to spot installs damaged by disk errors or changed by mods.
+export function FileVerifier({ game }: { game: Game }) {
+  const { notify } = useToast();
+  const [busy, setBusy] = useState(false);
+  const [progress, setProgress] = useState<{ done: number; total: number } | null>(
+    null,
+  );
+  const [result, setResult] = useState<FileVerification | null>(null);
+
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+    const setup = async () => {
+      unlisten = await listen<VerifyProgressPayload>(
+        "verify-files:progress",
+        (event) => {
+          if (event.payload.game_id !== game.id) return;
+          setProgress({ done: event.payload.done, total: event.payload.total });
+        },
+      );
+    };
+    void setup();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [game.id]);
+
+  const verify = async (rebaseline: boolean) => {
+    setBusy(true);
+    setProgress(null);
+    try {
+      setResult(await gamesApi.verifyFiles(game.id, rebaseline));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось проверить файлы",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+      setProgress(null);
+    }
+  };
+
+  const changed =
+    result &&
+    result.modified.length + result.missing.length + result.added.length > 0;
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Проверка файлов"}
+      </div>
+      <div className="flex flex-wrap items-center gap-2">
+        <Button
+          variant="outline"
+          size="sm"
+          className="gap-2"
+          disabled={busy}
+          onClick={() => verify(false)}
+        >
+          {busy ? (
+            <Loader2 className="w-4 h-4 animate-spin" />
+          ) : (
+            <FileCheck className="w-4 h-4" />
+          )}
+          {"Проверить"}
+        </Button>
+        {changed && (
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={busy}
+            onClick={() => verify(true)}
+          >
+            {"Принять текущие файлы"}
+          </Button>
+        )}
+        {busy && progress && (
+          <span className="text-xs text-muted-foreground">
+            {`${progress.done} / ${progress.total}`}
+          </span>
+        )}
+      </div>
+      {result && !busy && (
+        <div className="space-y-2 text-xs">
+          <div className="text-muted-foreground">
+            {result.baseline_created
+              ? `Сохранены контрольные суммы ${result.checked} файлов`
+              : changed
+                ? `Есть отличия от проверки ${new Date(result.baseline_at).toLocaleDateString()}`
+                : `Все ${result.checked} файлов совпадают`}
+          </div>
+          <FileList title="Изменены" files={result.modified} />
+          <FileList title="Отсутствуют" files={result.missing} />
+          <FileList title="Новые" files={result.added} />
+        </div>
+      )}
+    </div>
+  );
+}
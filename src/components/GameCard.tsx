@@ -1,5 +1,6 @@
 import { Link } from "react-router-dom";
 import { Star, Play, Gamepad2 } from "lucide-react";
+import { imageSrc } from "@/lib/images";
 import { cn } from "@/lib/utils";
 import type { Game } from "@/types";
 
@@ -8,15 +9,16 @@ interface GameCardProps {
 }
 
 export function GameCard({ game }: GameCardProps) {
+  const image = game.cover_thumbnail ?? game.cover_image ?? game.background_image;
   return (
     <Link
       to={`/game/${game.id}`}
       className="group relative aspect-[3/4] rounded-lg overflow-hidden bg-muted hover-lift"
     >
       {/* Background Image */}
-      {game.background_image ? (
+      {image ? (
         <img
-          src={game.background_image}
+          src={imageSrc(image)}
           alt={game.name}
           className="absolute inset-0 w-full h-full object-cover transition-transform duration-300 group-hover:scale-105"
         />
@@ -0,0 +1,307 @@
+import { openUrl } from "@tauri-apps/plugin-opener";
+import { ExternalLink, Loader2, Pencil, Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { notesApi } from "@/lib/api";
+import type { GameLink, GameNote } from "@/types";
+
+const textareaClass =
+  "flex min-h-[80px] w-full rounded-xl border border-border/60 bg-background/20 px-3 py-2 text-sm shadow-sm placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-1 focus-visible:ring-ring/60 disabled:cursor-not-allowed disabled:opacity-50";
+
+function NoteItem({
+  note,
+  onChanged,
+  onDeleted,
+}: {
+  note: GameNote;
+  onChanged: (note: GameNote) => void;
+  onDeleted: (id: string) => void;
+}) {
+  const { notify } = useToast();
+  const [editing, setEditing] = useState(false);
+  const [draft, setDraft] = useState(note.body);
+  const [saving, setSaving] = useState(false);
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      onChanged(await notesApi.update(note.id, draft));
+      setEditing(false);
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить заметку",
+        description: String(e),
+      });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const remove = async () => {
+    try {
+      await notesApi.delete(note.id);
+      onDeleted(note.id);
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось удалить заметку",
+        description: String(e),
+      });
+    }
+  };
+
+  return (
+    <li className="rounded-xl border border-border/60 bg-background/20 p-3 space-y-2">
+      <div className="flex items-center justify-between gap-2 text-xs text-muted-foreground">
+        <span>
+          {new Date(note.created_at).toLocaleString()}
+          {note.updated_at !== note.created_at && " (изменено)"}
+        </span>
+        <div className="flex items-center gap-1">
+          <Button
+            variant="ghost"
+            size="icon"
+            className="h-7 w-7"
+            title="Изменить"
+            onClick={() => {
+              setDraft(note.body);
+              setEditing((value) => !value);
+            }}
+          >
+            <Pencil className="w-3.5 h-3.5" />
+          </Button>
+          <Button
+            variant="ghost"
+            size="icon"
+            className="h-7 w-7"
+            title="Удалить"
+            onClick={() => void remove()}
+          >
+            <Trash2 className="w-3.5 h-3.5" />
+          </Button>
+        </div>
+      </div>
+      {editing ? (
+        <div className="space-y-2">
+          <textarea
+            className={textareaClass}
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+          />
+          <div className="flex justify-end gap-2">
+            <Button variant="ghost" size="sm" onClick={() => setEditing(false)}>
+              {"Отмена"}
+            </Button>
+            <Button
+              size="sm"
+              disabled={saving || !draft.trim()}
+              onClick={() => void save()}
+            >
+              {saving && <Loader2 className="w-4 h-4 animate-spin mr-2" />}
+              {"Сохранить"}
+            </Button>
+          </div>
+        </div>
+      ) : (
+        <p className="text-sm whitespace-pre-wrap leading-relaxed">{note.body}</p>
+      )}
+    </li>
+  );
+}
+
+// Dated Markdown notes and external links (wiki, mod pages, guides) for one game.
+export function GameNotes({ gameId }: { gameId: string }) {
+  const { notify } = useToast();
+  const [notes, setNotes] = useState<GameNote[]>([]);
+  const [links, setLinks] = useState<GameLink[]>([]);
+  const [body, setBody] = useState("");
+  const [linkUrl, setLinkUrl] = useState("");
+  const [linkTitle, setLinkTitle] = useState("");
+  const [adding, setAdding] = useState(false);
+
+  useEffect(() => {
+    let cancelled = false;
+    const load = async () => {
+      try {
+        const [loadedNotes, loadedLinks] = await Promise.all([
+          notesApi.list(gameId),
+          notesApi.listLinks(gameId),
+        ]);
+        if (cancelled) return;
+        setNotes(loadedNotes);
+        setLinks(loadedLinks);
+      } catch (e) {
+        console.error("Failed to load game notes:", e);
+      }
+    };
+    void load();
+    return () => {
+      cancelled = true;
+    };
+  }, [gameId]);
+
+  const addNote = async () => {
+    setAdding(true);
+    try {
+      const note = await notesApi.add(gameId, body);
+      setNotes((current) => [note, ...current]);
+      setBody("");
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось добавить заметку",
+        description: String(e),
+      });
+    } finally {
+      setAdding(false);
+    }
+  };
+
+  const addLink = async () => {
+    try {
+      const link = await notesApi.addLink(
+        gameId,
+        linkUrl.trim(),
+        linkTitle.trim() || undefined,
+      );
+      setLinks((current) => [...current, link]);
+      setLinkUrl("");
+      setLinkTitle("");
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось добавить ссылку",
+        description: String(e),
+      });
+    }
+  };
+
+  const removeLink = async (id: string) => {
+    try {
+      await notesApi.deleteLink(id);
+      setLinks((current) => current.filter((link) => link.id !== id));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось удалить ссылку",
+        description: String(e),
+      });
+    }
+  };
+
+  return (
+    <div className="grid md:grid-cols-3 gap-5 mt-5">
+      <div className="md:col-span-2 bg-card/60 backdrop-blur-xl rounded-2xl p-5 border border-border/60 shadow-[0_18px_40px_rgba(8,12,24,0.35)] space-y-4">
+        <div>
+          <div className="text-sm text-muted-foreground">{"Журнал"}</div>
+          <div className="text-lg font-semibold">{"Заметки"}</div>
+        </div>
+        <div className="space-y-2">
+          <textarea
+            className={textareaClass}
+            placeholder="Markdown: прогресс, коды, что сделать в следующий раз..."
+            value={body}
+            onChange={(e) => setBody(e.target.value)}
+          />
+          <div className="flex justify-end">
+            <Button
+              size="sm"
+              className="gap-2"
+              disabled={adding || !body.trim()}
+              onClick={() => void addNote()}
+            >
+              {adding ? (
+                <Loader2 className="w-4 h-4 animate-spin" />
+              ) : (
+                <Plus className="w-4 h-4" />
+              )}
+              {"Добавить заметку"}
+            </Button>
+          </div>
+        </div>
+        {notes.length > 0 && (
+          <ul className="space-y-2">
+            {notes.map((note) => (
+              <NoteItem
+                key={note.id}
+                note={note}
+                onChanged={(updated) =>
+                  setNotes((current) =>
+                    current.map((item) =>
+                      item.id === updated.id ? updated : item,
+                    ),
+                  )
+                }
+                onDeleted={(id) =>
+                  setNotes((current) => current.filter((item) => item.id !== id))
+                }
+              />
+            ))}
+          </ul>
+        )}
+      </div>
+
+      <div className="bg-card/60 backdrop-blur-xl rounded-2xl p-5 border border-border/60 shadow-[0_18px_40px_rgba(8,12,24,0.35)] space-y-4">
+        <div>
+          <div className="text-sm text-muted-foreground">{"Полезное"}</div>
+          <div className="text-lg font-semibold">{"Ссылки"}</div>
+        </div>
+        {links.length > 0 ? (
+          <ul className="space-y-1">
+            {links.map((link) => (
+              <li key={link.id} className="flex items-center gap-2 text-sm">
+                <button
+                  type="button"
+                  className="flex min-w-0 flex-1 items-center gap-2 text-left hover:text-primary"
+                  title={link.url}
+                  onClick={() => void openUrl(link.url)}
+                >
+                  <ExternalLink className="w-3.5 h-3.5 shrink-0" />
+                  <span className="truncate">{link.title ?? link.url}</span>
+                </button>
+                <Button
+                  variant="ghost"
+                  size="icon"
+                  className="h-7 w-7"
+                  title="Удалить"
+                  onClick={() => void removeLink(link.id)}
+                >
+                  <Trash2 className="w-3.5 h-3.5" />
+                </Button>
+              </li>
+            ))}
+          </ul>
+        ) : (
+          <div className="text-xs text-muted-foreground">
+            {"Вики, страницы модов, гайды"}
+          </div>
+        )}
+        <div className="space-y-2">
+          <Input
+            placeholder="https://"
+            value={linkUrl}
+            onChange={(e) => setLinkUrl(e.target.value)}
+          />
+          <Input
+            placeholder="Название (необязательно)"
+            value={linkTitle}
+            onChange={(e) => setLinkTitle(e.target.value)}
+          />
+          <Button
+            variant="outline"
+            size="sm"
+            className="w-full gap-2"
+            disabled={!linkUrl.trim()}
+            onClick={() => void addLink()}
+          >
+            <Plus className="w-4 h-4" />
+            {"Добавить ссылку"}
+          </Button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,97 @@
+import { ExternalLink } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { describeMetadataError, igdbApi } from "@/lib/api";
+
+// IGDB signs in through a Twitch developer application; saved on its own, outside the main
+// settings form.
+export function IgdbCredentialsSetting() {
+  const { notify } = useToast();
+  const [clientId, setClientId] = useState("");
+  const [clientSecret, setClientSecret] = useState("");
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    igdbApi
+      .getCredentials()
+      .then((credentials) => {
+        setClientId(credentials.client_id);
+        setClientSecret(credentials.client_secret);
+      })
+      .catch((e) => console.error("Failed to load IGDB credentials:", e));
+  }, []);
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      await igdbApi.setCredentials(clientId, clientSecret);
+      notify({ tone: "success", title: "Данные IGDB сохранены" });
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить данные IGDB",
+        description: describeMetadataError(e, "IGDB"),
+      });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div>
+        <label
+          htmlFor="igdb-client-id"
+          className="text-sm font-medium mb-2 block"
+        >
+          Twitch Client ID
+        </label>
+        <Input
+          id="igdb-client-id"
+          value={clientId}
+          onChange={(e) => setClientId(e.target.value)}
+          placeholder="Client ID приложения Twitch"
+        />
+      </div>
+      <div>
+        <label
+          htmlFor="igdb-client-secret"
+          className="text-sm font-medium mb-2 block"
+        >
+          Twitch Client Secret
+        </label>
+        <Input
+          id="igdb-client-secret"
+          type="password"
+          value={clientSecret}
+          onChange={(e) => setClientSecret(e.target.value)}
+          placeholder="Client Secret приложения Twitch"
+        />
+      </div>
+      <div className="flex items-center justify-between gap-3">
+        <p className="text-xs text-muted-foreground">
+          Создайте приложение в{" "}
+          <a
+            href="https://dev.twitch.tv/console/apps"
+            target="_blank"
+            rel="noopener noreferrer"
+            className="text-primary hover:underline inline-flex items-center gap-1"
+          >
+            Twitch Developer Console <ExternalLink className="w-3 h-3" />
+          </a>
+          , чтобы искать метаданные в IGDB
+        </p>
+        <Button
+          size="sm"
+          variant="outline"
+          onClick={() => void save()}
+          disabled={saving}
+        >
+          Сохранить
+        </Button>
+      </div>
+    </div>
+  );
+}
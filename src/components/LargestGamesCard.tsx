@@ -0,0 +1,73 @@
+import { HardDrive } from "lucide-react";
+import { useEffect, useState } from "react";
+import { Link } from "react-router-dom";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import { gamesApi } from "@/lib/api";
+import type { GameSummary } from "@/types";
+
+const formatBytes = (bytes: number) => {
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(0)} МБ`;
+  return `${(bytes / 1024 / 1024 / 1024).toFixed(1)} ГБ`;
+};
+
+// Storage view: the installs taking the most disk space, by their last measured size.
+export function LargestGamesCard() {
+  const [games, setGames] = useState<GameSummary[]>([]);
+
+  useEffect(() => {
+    gamesApi
+      .getLargest(10)
+      .then(setGames)
+      .catch((e) => console.error("Failed to load largest games:", e));
+  }, []);
+
+  if (games.length === 0) return null;
+
+  const largest = games[0].install_size ?? 1;
+  const total = games.reduce((sum, game) => sum + (game.install_size ?? 0), 0);
+
+  return (
+    <Card>
+      <CardHeader className="space-y-1">
+        <CardTitle className="flex items-center gap-2 text-base">
+          <HardDrive className="h-4 w-4 text-muted-foreground" />
+          {"Место на диске"}
+        </CardTitle>
+        <CardDescription>
+          {`Самые большие установки, вместе ${formatBytes(total)}`}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-2">
+        {games.map((game) => {
+          const size = game.install_size ?? 0;
+          return (
+            <Link
+              key={game.id}
+              to={`/game/${game.id}`}
+              className="block space-y-1 rounded-md px-2 py-1 hover:bg-accent"
+            >
+              <div className="flex items-center justify-between gap-2 text-sm">
+                <span className="truncate">{game.name}</span>
+                <span className="text-muted-foreground flex-shrink-0">
+                  {formatBytes(size)}
+                </span>
+              </div>
+              <div className="h-1.5 w-full rounded-full bg-muted/40">
+                <div
+                  className="h-full rounded-full bg-primary/70"
+                  style={{ width: `${(size / largest) * 100}%` }}
+                />
+              </div>
+            </Link>
+          );
+        })}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,239 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { FolderOpen, Save } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { gamesApi } from "@/lib/api";
+import type { Emulator, Game, InstancePolicy, LaunchMethod } from "@/types";
+
+const LAUNCH_METHODS: { value: LaunchMethod; label: string; idHint: string }[] = [
+  { value: "direct", label: "Напрямую через exe", idHint: "" },
+  { value: "steam", label: "Через Steam", idHint: "App ID, например 489830" },
+  { value: "epic", label: "Через Epic Games", idHint: "Имя приложения в Epic" },
+  { value: "gog", label: "Через GOG Galaxy", idHint: "ID продукта GOG" },
+];
+
+const INSTANCE_POLICIES: { value: InstancePolicy | ""; label: string }[] = [
+  { value: "", label: "Если уже запущена: как в настройках" },
+  { value: "focus", label: "Если уже запущена: переключиться на окно" },
+  { value: "refuse", label: "Если уже запущена: не запускать" },
+  { value: "allow", label: "Если уже запущена: запустить ещё копию" },
+];
+
+const formatEnv = (vars: Record<string, string>) =>
+  Object.entries(vars)
+    .map(([name, value]) => `${name}=${value}`)
+    .join("\n");
+
+// One `NAME=value` per line; lines without `=` set the variable to an empty string.
+const parseEnv = (text: string) =>
+  Object.fromEntries(
+    text
+      .split("\n")
+      .map((line) => line.trim())
+      .filter(Boolean)
+      .map((line) => {
+        const at = line.indexOf("=");
+        return at === -1
+          ? [line, ""]
+          : [line.slice(0, at).trim(), line.slice(at + 1)];
+      }),
+  ) as Record<string, string>;
+
+// Extra command-line flags, a custom working directory and environment variables for the
+// plain launch button; launch profiles keep their own arguments and add to the variables.
+export function LaunchOptionsEditor({
+  game,
+  onSaved,
+}: {
+  game: Game;
+  onSaved: () => Promise<void> | void;
+}) {
+  const { notify } = useToast();
+  const [args, setArgs] = useState(game.launch_args ?? "");
+  const [workingDir, setWorkingDir] = useState(game.working_dir ?? "");
+  const [env, setEnv] = useState(formatEnv(game.env_vars ?? {}));
+  const [method, setMethod] = useState<LaunchMethod>(game.launch_method);
+  const [storeId, setStoreId] = useState(game.store_id ?? "");
+  const [emulatorId, setEmulatorId] = useState(game.emulator_id ?? "");
+  const [instancePolicy, setInstancePolicy] = useState<InstancePolicy | "">(
+    game.instance_policy ?? "",
+  );
+  const [emulators, setEmulators] = useState<Emulator[]>([]);
+  const [busy, setBusy] = useState(false);
+  const savedEnv = formatEnv(game.env_vars ?? {});
+
+  useEffect(() => {
+    gamesApi
+      .getEmulators()
+      .then(setEmulators)
+      .catch((e) => console.error("Failed to load emulators:", e));
+  }, []);
+
+  useEffect(() => {
+    setArgs(game.launch_args ?? "");
+    setWorkingDir(game.working_dir ?? "");
+    setEnv(savedEnv);
+    setMethod(game.launch_method);
+    setStoreId(game.store_id ?? "");
+    setEmulatorId(game.emulator_id ?? "");
+    setInstancePolicy(game.instance_policy ?? "");
+  }, [
+    game.id,
+    game.launch_args,
+    game.working_dir,
+    savedEnv,
+    game.launch_method,
+    game.store_id,
+    game.emulator_id,
+    game.instance_policy,
+  ]);
+
+  const envDirty = env.trim() !== savedEnv;
+  const policyDirty = instancePolicy !== (game.instance_policy ?? "");
+  const dirty =
+    args.trim() !== (game.launch_args ?? "") ||
+    workingDir.trim() !== (game.working_dir ?? "") ||
+    method !== game.launch_method ||
+    storeId.trim() !== (game.store_id ?? "") ||
+    emulatorId !== (game.emulator_id ?? "") ||
+    envDirty ||
+    policyDirty;
+  const methodInfo = LAUNCH_METHODS.find((m) => m.value === method);
+
+  const pickDir = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (typeof selected === "string") setWorkingDir(selected);
+  };
+
+  const save = async () => {
+    setBusy(true);
+    try {
+      await gamesApi.update({
+        id: game.id,
+        launch_args: args.trim(),
+        working_dir: workingDir.trim(),
+        launch_method: method,
+        store_id: storeId.trim(),
+        emulator_id: emulatorId,
+      });
+      if (envDirty) {
+        await gamesApi.setEnvVars(game.id, parseEnv(env));
+      }
+      if (policyDirty) {
+        await gamesApi.setInstancePolicy(game.id, instancePolicy || null);
+      }
+      await onSaved();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить параметры запуска",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Параметры запуска"}
+      </div>
+      <div className="flex items-center gap-2">
+        <select
+          value={method}
+          onChange={(event) => setMethod(event.target.value as LaunchMethod)}
+          className="h-9 rounded-md border border-input bg-background px-2 text-xs"
+          aria-label="Способ запуска"
+        >
+          {LAUNCH_METHODS.map((m) => (
+            <option key={m.value} value={m.value}>
+              {m.label}
+            </option>
+          ))}
+        </select>
+        {method !== "direct" && (
+          <Input
+            value={storeId}
+            onChange={(event) => setStoreId(event.target.value)}
+            placeholder={methodInfo?.idHint}
+            className="font-mono text-xs"
+          />
+        )}
+      </div>
+      {(emulators.length > 0 || emulatorId) && (
+        <select
+          value={emulatorId}
+          onChange={(event) => setEmulatorId(event.target.value)}
+          className="h-9 w-full rounded-md border border-input bg-background px-2 text-xs"
+          aria-label="Эмулятор"
+        >
+          <option value="">Без эмулятора</option>
+          {emulators.map((emulator) => (
+            <option key={emulator.id} value={emulator.id}>
+              {`ROM через ${emulator.name}`}
+            </option>
+          ))}
+        </select>
+      )}
+      <select
+        value={instancePolicy}
+        onChange={(event) =>
+          setInstancePolicy(event.target.value as InstancePolicy | "")
+        }
+        className="h-9 w-full rounded-md border border-input bg-background px-2 text-xs"
+        aria-label="Повторный запуск"
+      >
+        {INSTANCE_POLICIES.map((p) => (
+          <option key={p.value} value={p.value}>
+            {p.label}
+          </option>
+        ))}
+      </select>
+      <Input
+        value={args}
+        onChange={(event) => setArgs(event.target.value)}
+        placeholder={"Аргументы, например -windowed -skipintro"}
+        className="font-mono text-xs"
+      />
+      <div className="flex items-center gap-2">
+        <Input
+          value={workingDir}
+          onChange={(event) => setWorkingDir(event.target.value)}
+          placeholder={"Рабочая папка (по умолчанию — папка игры)"}
+          className="text-xs"
+        />
+        <Button
+          variant="outline"
+          size="icon"
+          className="shrink-0"
+          onClick={pickDir}
+          title="Выбрать папку"
+        >
+          <FolderOpen className="w-4 h-4" />
+        </Button>
+      </div>
+      <textarea
+        value={env}
+        onChange={(event) => setEnv(event.target.value)}
+        placeholder={"DXVK_HUD=fps\nSteamAppId=489830"}
+        rows={3}
+        aria-label="Переменные окружения"
+        className="w-full rounded-md border border-input bg-background px-3 py-2 font-mono text-xs"
+      />
+      <div className="flex justify-end">
+        <Button
+          size="sm"
+          className="gap-2"
+          disabled={busy || !dirty}
+          onClick={save}
+        >
+          <Save className="w-4 h-4" />
+          {"Сохранить"}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,91 @@
+import { Archive, Gamepad2, Hourglass, Star } from "lucide-react";
+import { useEffect, useState } from "react";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { statsApi } from "@/lib/api";
+import type { LibraryActivity, LibraryOverview } from "@/types";
+
+const formatBytes = (bytes: number) => {
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(0)} КБ`;
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} МБ`;
+  return `${(bytes / 1024 / 1024 / 1024).toFixed(1)} ГБ`;
+};
+
+const formatHours = (seconds: number) =>
+  `${Math.round((seconds / 3600) * 10) / 10} ч`;
+
+const activityLabel: Record<LibraryActivity["kind"], string> = {
+  played: "запуск",
+  backup: "бэкап",
+  added: "добавлена",
+};
+
+// Whole-library numbers that don't depend on the selected period; one IPC call.
+export function LibraryOverviewTiles() {
+  const [overview, setOverview] = useState<LibraryOverview | null>(null);
+
+  useEffect(() => {
+    statsApi
+      .getLibraryOverview()
+      .then(setOverview)
+      .catch((e) => console.error("Failed to load library overview:", e));
+  }, []);
+
+  if (!overview) return null;
+
+  const activity = overview.recent_activity;
+  const tiles = [
+    {
+      title: "Игр в библиотеке",
+      icon: Gamepad2,
+      value: overview.total_games,
+      hint: `Установлено: ${overview.installed_games}`,
+    },
+    {
+      title: "Избранное",
+      icon: Star,
+      value: overview.favorite_games,
+      hint: overview.top_genres.length
+        ? `Жанры: ${overview.top_genres
+            .map((genre) => `${genre.genre} (${genre.games})`)
+            .join(", ")}`
+        : "Жанры не указаны",
+    },
+    {
+      title: "С бэкапами",
+      icon: Archive,
+      value: overview.games_with_backups,
+      hint: formatBytes(overview.total_backup_bytes),
+    },
+    {
+      title: "Всего сыграно",
+      icon: Hourglass,
+      value: formatHours(overview.total_playtime_seconds),
+      hint: activity
+        ? `${activity.game_name} — ${activityLabel[activity.kind]}, ${new Date(
+            activity.at,
+          ).toLocaleDateString()}`
+        : "Нет активности",
+    },
+  ];
+
+  return (
+    <div className="grid grid-cols-1 sm:grid-cols-2 xl:grid-cols-4 gap-4">
+      {tiles.map(({ title, icon: Icon, value, hint }) => (
+        <Card key={title} className="bg-card/60">
+          <CardHeader className="flex flex-row items-center justify-between pb-2">
+            <CardTitle className="text-sm font-medium text-muted-foreground">
+              {title}
+            </CardTitle>
+            <Icon className="h-4 w-4 text-muted-foreground" />
+          </CardHeader>
+          <CardContent>
+            <div className="text-2xl font-semibold">{value}</div>
+            <p className="text-xs text-muted-foreground truncate" title={hint}>
+              {hint}
+            </p>
+          </CardContent>
+        </Card>
+      ))}
+    </div>
+  );
+}
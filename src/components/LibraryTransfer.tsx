@@ -0,0 +1,132 @@
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { Download, Upload, X } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { gamesApi } from "@/lib/api";
+import type { ImportStrategy } from "@/types";
+
+// Moves the whole library (games, tags, collections, custom fields and playtime history) to
+// another machine as one JSON file.
+export function LibraryTransfer({
+  onImported,
+  onClose,
+}: {
+  onImported: () => Promise<void> | void;
+  onClose: () => void;
+}) {
+  const { notify } = useToast();
+  const [tokenisePaths, setTokenisePaths] = useState(true);
+  const [strategy, setStrategy] = useState<ImportStrategy>("merge");
+  const [busy, setBusy] = useState(false);
+
+  const exportLibrary = async () => {
+    const path = await save({
+      title: "Экспорт библиотеки",
+      defaultPath: "arrancador-library.json",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (!path) return;
+    setBusy(true);
+    try {
+      const count = await gamesApi.exportLibrary(path, {
+        tokenise_paths: tokenisePaths,
+      });
+      notify({
+        tone: "success",
+        title: `Библиотека сохранена, игр: ${count}`,
+        description: path,
+      });
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось экспортировать библиотеку",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const importLibrary = async () => {
+    const path = await open({
+      title: "Импорт библиотеки",
+      multiple: false,
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (typeof path !== "string") return;
+    setBusy(true);
+    try {
+      const summary = await gamesApi.importLibrary(path, strategy);
+      await onImported();
+      notify({
+        tone: "success",
+        title: "Библиотека импортирована",
+        description: `Добавлено: ${summary.games_added}, обновлено: ${summary.games_updated}, пропущено: ${summary.games_skipped}`,
+      });
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось импортировать библиотеку",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="flex items-center justify-between">
+        <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+          {"Перенос библиотеки"}
+        </div>
+        <Button variant="ghost" size="icon" className="h-6 w-6" onClick={onClose}>
+          <X className="w-4 h-4" />
+        </Button>
+      </div>
+      <div className="flex flex-wrap items-center gap-3">
+        <label className="flex items-center gap-2 text-xs text-muted-foreground">
+          <input
+            type="checkbox"
+            checked={tokenisePaths}
+            onChange={(event) => setTokenisePaths(event.target.checked)}
+          />
+          {"Пути относительно домашней папки"}
+        </label>
+        <Button
+          size="sm"
+          variant="outline"
+          className="gap-2 ml-auto"
+          disabled={busy}
+          onClick={exportLibrary}
+        >
+          <Download className="w-4 h-4" />
+          {"Экспорт"}
+        </Button>
+      </div>
+      <div className="flex flex-wrap items-center gap-3">
+        <select
+          value={strategy}
+          onChange={(event) => setStrategy(event.target.value as ImportStrategy)}
+          className="h-8 rounded-md border border-input bg-background px-2 text-xs"
+          aria-label="Уже добавленные игры"
+        >
+          <option value="merge">Объединить с текущими играми</option>
+          <option value="skip_existing">Пропустить уже добавленные</option>
+          <option value="replace">Заменить всю библиотеку</option>
+        </select>
+        <Button
+          size="sm"
+          variant="outline"
+          className="gap-2 ml-auto"
+          disabled={busy}
+          onClick={importLibrary}
+        >
+          <Upload className="w-4 h-4" />
+          {"Импорт"}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,109 @@
+import { BadgeCheck, RotateCcw } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { backupApi } from "@/lib/api";
+import { cn } from "@/lib/utils";
+import type { ManifestMatch } from "@/types";
+
+// Shows which manifest entry the game was matched to by name and lets the user pin the
+// right one; a pinned entry is reused for every later backup.
+export function ManifestMatchPicker({ gameId }: { gameId: string }) {
+  const { notify } = useToast();
+  const [match, setMatch] = useState<ManifestMatch | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    setMatch(null);
+    backupApi
+      .getManifestMatch(gameId)
+      .then(setMatch)
+      .catch((e) => console.error("Failed to load manifest match:", e));
+  }, [gameId]);
+
+  const confirm = async (manifestKey: string | null) => {
+    setBusy(true);
+    try {
+      setMatch(await backupApi.confirmManifestMatch(gameId, manifestKey));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить запись манифеста",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  if (!match) return null;
+
+  const alternatives = match.suggestions.filter(
+    (key) => key !== match.manifest_key,
+  );
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Запись в манифесте SQOBA"}
+      </div>
+      <div className="flex items-center justify-between gap-2">
+        <div className="min-w-0">
+          <div className="truncate text-sm font-medium">
+            {match.manifest_key ?? "Не найдена"}
+          </div>
+          <div className="text-[11px] text-muted-foreground">
+            {match.confirmed
+              ? "Подтверждена"
+              : match.score !== null
+                ? `Найдена по названию · ${Math.round(match.score * 100)}%`
+                : "Совпадений по названию нет"}
+          </div>
+        </div>
+        {match.confirmed ? (
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={busy}
+            onClick={() => confirm(null)}
+            className="gap-2"
+          >
+            <RotateCcw className="w-4 h-4" />
+            {"Сбросить"}
+          </Button>
+        ) : (
+          match.manifest_key && (
+            <Button
+              variant="outline"
+              size="sm"
+              disabled={busy}
+              onClick={() => confirm(match.manifest_key)}
+              className="gap-2"
+            >
+              <BadgeCheck className="w-4 h-4" />
+              {"Подтвердить"}
+            </Button>
+          )
+        )}
+      </div>
+      {alternatives.length > 0 && (
+        <div className="flex flex-wrap gap-1.5">
+          {alternatives.map((key) => (
+            <button
+              key={key}
+              type="button"
+              disabled={busy}
+              onClick={() => confirm(key)}
+              className={cn(
+                "rounded-md border border-border/60 px-2 py-0.5 text-[11px]",
+                "hover:border-primary/60 hover:text-primary disabled:opacity-50",
+              )}
+            >
+              {key}
+            </button>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
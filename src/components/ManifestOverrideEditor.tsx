@@ -0,0 +1,114 @@
+import { Check, Loader2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Switch } from "@/components/ui/switch";
+import { backupApi } from "@/lib/api";
+import type { ManifestOverride } from "@/types";
+
+const splitLines = (value: string) =>
+  value
+    .split("\n")
+    .map((line) => line.trim())
+    .filter(Boolean);
+
+// Custom manifest entry for games the ludusavi manifest lacks or gets wrong; merged over
+// the upstream entry when saves are discovered.
+export function ManifestOverrideEditor({ gameId }: { gameId: string }) {
+  const { notify } = useToast();
+  const [files, setFiles] = useState("");
+  const [registry, setRegistry] = useState("");
+  const [replaceUpstream, setReplaceUpstream] = useState(false);
+  const [saving, setSaving] = useState(false);
+
+  const apply = (fragment: ManifestOverride | null) => {
+    setFiles(fragment?.files.join("\n") ?? "");
+    setRegistry(fragment?.registry.join("\n") ?? "");
+    setReplaceUpstream(fragment?.replace_upstream ?? false);
+  };
+
+  useEffect(() => {
+    backupApi
+      .getManifestOverride(gameId)
+      .then(apply)
+      .catch((e) => {
+        console.error("Failed to load manifest override:", e);
+        apply(null);
+      });
+  }, [gameId]);
+
+  const handleSave = async () => {
+    setSaving(true);
+    try {
+      const saved = await backupApi.setManifestOverride(gameId, {
+        files: splitLines(files),
+        registry: splitLines(registry),
+        replace_upstream: replaceUpstream,
+      });
+      apply(saved);
+      notify({
+        tone: "success",
+        title: saved ? "Запись манифеста сохранена" : "Запись манифеста удалена",
+      });
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить запись манифеста",
+        description: String(e),
+      });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Своя запись манифеста"}
+      </div>
+      <textarea
+        value={files}
+        onChange={(event) => setFiles(event.target.value)}
+        placeholder={"<winAppData>/Game/Saves\n<base>/saves/*.sav"}
+        rows={3}
+        aria-label="Пути к файлам"
+        className="w-full rounded-md border border-input bg-background px-3 py-2 font-mono text-xs"
+      />
+      <textarea
+        value={registry}
+        onChange={(event) => setRegistry(event.target.value)}
+        placeholder={"HKEY_CURRENT_USER\\Software\\Studio\\Game"}
+        rows={2}
+        aria-label="Ключи реестра"
+        className="w-full rounded-md border border-input bg-background px-3 py-2 font-mono text-xs"
+      />
+      <div className="flex items-center justify-between gap-2">
+        <label className="flex items-center gap-2 text-xs text-muted-foreground">
+          <Switch
+            checked={replaceUpstream}
+            onCheckedChange={setReplaceUpstream}
+          />
+          {"Заменить запись из манифеста SQOBA"}
+        </label>
+        <Button
+          size="sm"
+          onClick={handleSave}
+          disabled={saving}
+          className="gap-2"
+        >
+          {saving ? (
+            <Loader2 className="w-4 h-4 animate-spin" />
+          ) : (
+            <Check className="w-4 h-4" />
+          )}
+          {"Сохранить"}
+        </Button>
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {
+          "По одному пути или ключу на строку, в синтаксисе ludusavi (<winAppData>, <winDocuments>, <base> для папки игры). Пустые поля удаляют запись."
+        }
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,64 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { FileJson, Loader2 } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { describeMetadataError, metadataApi } from "@/lib/api";
+
+// Fills missing metadata from a JSON bundle downloaded elsewhere, without calling RAWG.
+export function MetadataBundleImport() {
+  const { notify } = useToast();
+  const [busy, setBusy] = useState(false);
+
+  const importBundle = async () => {
+    const path = await open({
+      title: "Импорт метаданных",
+      multiple: false,
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (typeof path !== "string") return;
+    setBusy(true);
+    try {
+      const summary = await metadataApi.importBundle(path);
+      notify({
+        tone: summary.matched > 0 ? "success" : "warning",
+        title: `Метаданные дополнены, игр: ${summary.matched}`,
+        description: `Записей в файле: ${summary.entries}, игр без совпадения: ${summary.unmatched}.`,
+      });
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось импортировать метаданные",
+        description: describeMetadataError(e, "Файл метаданных"),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="flex items-center justify-between gap-3 mt-4">
+      <div>
+        <span className="text-sm font-medium">Импорт метаданных из файла</span>
+        <span className="text-xs text-muted-foreground block">
+          JSON со списком игр (например, сохранённый ответ RAWG). Заполняются
+          только пустые поля.
+        </span>
+      </div>
+      <Button
+        variant="outline"
+        size="sm"
+        onClick={importBundle}
+        disabled={busy}
+        className="gap-2 text-xs"
+      >
+        {busy ? (
+          <Loader2 className="w-3 h-3 animate-spin" />
+        ) : (
+          <FileJson className="w-3 h-3" />
+        )}
+        {"Выбрать файл"}
+      </Button>
+    </div>
+  );
+}
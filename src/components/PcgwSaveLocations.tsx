@@ -0,0 +1,201 @@
+import { openUrl } from "@tauri-apps/plugin-opener";
+import { BookOpen, ExternalLink, Loader2, Save } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { describeMetadataError, pcgamingwikiApi } from "@/lib/api";
+import { cn } from "@/lib/utils";
+import type { PcgwLocations } from "@/types";
+
+// Looks the game up on PCGamingWiki and lets the user confirm the save locations it lists;
+// confirmed locations are offered by "Найти" next to the manifest and heuristic folders.
+export function PcgwSaveLocations({
+  gameId,
+  gameName,
+}: {
+  gameId: string;
+  gameName: string;
+}) {
+  const { notify } = useToast();
+  const [confirmed, setConfirmed] = useState<string[]>([]);
+  const [selected, setSelected] = useState<string[]>([]);
+  const [wiki, setWiki] = useState<PcgwLocations | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    setWiki(null);
+    pcgamingwikiApi
+      .getSavePaths(gameId)
+      .then((paths) => {
+        setConfirmed(paths);
+        setSelected(paths);
+      })
+      .catch((e) => console.error("Failed to load PCGamingWiki paths:", e));
+  }, [gameId]);
+
+  const lookup = async () => {
+    setLoading(true);
+    try {
+      const result = await pcgamingwikiApi.getLocations(gameName);
+      setWiki(result);
+      if (!result) {
+        notify({
+          tone: "warning",
+          title: "Страница не найдена",
+          description: `На PCGamingWiki нет страницы для «${gameName}».`,
+        });
+      }
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Ошибка PCGamingWiki",
+        description: describeMetadataError(e, "PCGamingWiki"),
+      });
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const toggle = (path: string) =>
+    setSelected((current) =>
+      current.includes(path)
+        ? current.filter((item) => item !== path)
+        : [...current, path],
+    );
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      const paths = await pcgamingwikiApi.setSavePaths(gameId, selected);
+      setConfirmed(paths);
+      setSelected(paths);
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить пути PCGamingWiki",
+        description: describeMetadataError(e, "PCGamingWiki"),
+      });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const wikiPaths = wiki?.save_locations.map((location) => location.path) ?? [];
+  const savePaths = [
+    ...wikiPaths,
+    ...confirmed.filter((path) => !wikiPaths.includes(path)),
+  ].filter((path, index, all) => all.indexOf(path) === index);
+  const platformOf = (path: string) =>
+    wiki?.save_locations
+      .filter((location) => location.path === path)
+      .map((location) => location.platform)
+      .join(", ");
+  const dirty =
+    selected.length !== confirmed.length ||
+    selected.some((path) => !confirmed.includes(path));
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="flex items-center justify-between gap-2">
+        <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+          {"PCGamingWiki"}
+        </div>
+        <div className="flex gap-2">
+          {wiki && (
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={() => void openUrl(wiki.url)}
+              className="gap-2 text-xs"
+              title={wiki.page}
+            >
+              <ExternalLink className="w-3 h-3" />
+              {"Страница"}
+            </Button>
+          )}
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={lookup}
+            disabled={loading}
+            className="gap-2 text-xs"
+          >
+            {loading ? (
+              <Loader2 className="w-3 h-3 animate-spin" />
+            ) : (
+              <BookOpen className="w-3 h-3" />
+            )}
+            {"Проверить"}
+          </Button>
+        </div>
+      </div>
+
+      {savePaths.length > 0 && (
+        <div className="space-y-1">
+          <div className="text-[11px] text-muted-foreground">
+            {"Сохранения — отметьте пути, которые стоит предлагать:"}
+          </div>
+          {savePaths.map((path) => (
+            <button
+              key={path}
+              type="button"
+              onClick={() => toggle(path)}
+              className={cn(
+                "w-full rounded-xl border border-border/60 px-3 py-2 text-left transition-colors hover:border-foreground/30",
+                selected.includes(path) &&
+                  "border-emerald-500/50 bg-emerald-500/10",
+              )}
+            >
+              <div className="truncate font-mono text-[11px]">{path}</div>
+              <div className="text-[10px] text-muted-foreground">
+                {platformOf(path) || "Подтверждён ранее"}
+              </div>
+            </button>
+          ))}
+        </div>
+      )}
+
+      {wiki && wiki.config_locations.length > 0 && (
+        <div className="space-y-1">
+          <div className="text-[11px] text-muted-foreground">
+            {"Настройки:"}
+          </div>
+          {wiki.config_locations.map((location) => (
+            <div
+              key={`${location.platform}:${location.path}`}
+              className="truncate font-mono text-[11px] text-muted-foreground"
+              title={location.platform}
+            >
+              {location.path}
+            </div>
+          ))}
+        </div>
+      )}
+
+      {wiki && wiki.save_locations.length === 0 && (
+        <div className="text-[11px] text-muted-foreground">
+          {`На странице «${wiki.page}» нет путей сохранений, которые можно проверить.`}
+        </div>
+      )}
+
+      {dirty && (
+        <div className="flex justify-end">
+          <Button
+            size="sm"
+            onClick={save}
+            disabled={saving}
+            className="gap-2 text-xs"
+          >
+            {saving ? (
+              <Loader2 className="w-3 h-3 animate-spin" />
+            ) : (
+              <Save className="w-3 h-3" />
+            )}
+            {"Сохранить"}
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,98 @@
+import { Clock } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { gamesApi } from "@/lib/api";
+import type { Game } from "@/types";
+
+const hoursToSeconds = (text: string) => {
+  const hours = Number.parseFloat(text.replace(",", "."));
+  return Number.isFinite(hours) ? Math.round(hours * 3600) : null;
+};
+
+// Hours carried over from Steam or Playnite: either replace the total or add a correction.
+export function PlaytimeEditor({
+  game,
+  onSaved,
+}: {
+  game: Game;
+  onSaved: () => Promise<void> | void;
+}) {
+  const { notify } = useToast();
+  const [hours, setHours] = useState("");
+  const [note, setNote] = useState("");
+  const [busy, setBusy] = useState(false);
+  const seconds = hoursToSeconds(hours);
+
+  const run = async (action: (seconds: number) => Promise<unknown>) => {
+    if (seconds === null) return;
+    setBusy(true);
+    try {
+      await action(seconds);
+      setHours("");
+      setNote("");
+      await onSaved();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось изменить время игры",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="flex items-center gap-2 text-[10px] uppercase tracking-wider text-muted-foreground">
+        <Clock className="w-3 h-3" />
+        {"Время игры"}
+      </div>
+      <div className="grid grid-cols-1 sm:grid-cols-2 gap-2">
+        <Input
+          value={hours}
+          onChange={(event) => setHours(event.target.value)}
+          placeholder="Часы, например 42,5"
+          inputMode="decimal"
+          className="h-8 text-xs"
+        />
+        <Input
+          value={note}
+          onChange={(event) => setNote(event.target.value)}
+          placeholder="Комментарий"
+          className="h-8 text-xs"
+        />
+      </div>
+      <div className="flex flex-wrap gap-2">
+        <Button
+          variant="outline"
+          size="sm"
+          disabled={busy || seconds === null || seconds < 0}
+          onClick={() =>
+            run((value) => gamesApi.setTotalPlaytime(game.id, value))
+          }
+        >
+          {"Установить всего"}
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          disabled={busy || seconds === null || seconds === 0}
+          onClick={() =>
+            run((value) =>
+              gamesApi.addPlaytimeAdjustment(
+                game.id,
+                value,
+                note.trim() || undefined,
+              ),
+            )
+          }
+        >
+          {"Добавить к текущему"}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,26 @@
+import { listen } from "@tauri-apps/api/event";
+import { useEffect } from "react";
+import { useToast } from "@/components/ToastProvider";
+import type { PluginNotifyEvent } from "@/types";
+
+// Shows `notify` actions returned by plugins as toasts.
+export function PluginNotifications() {
+  const { notify } = useToast();
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<PluginNotifyEvent>("plugin:notify", (event) => {
+      if (!event.payload) return;
+      notify({
+        tone: "info",
+        title: event.payload.title,
+        description: event.payload.message ?? event.payload.plugin,
+      });
+    }).then((fn) => {
+      unlisten = fn;
+    });
+    return () => unlisten?.();
+  }, [notify]);
+
+  return null;
+}
@@ -0,0 +1,129 @@
+import { openPath } from "@tauri-apps/plugin-opener";
+import { FolderOpen, Puzzle, RefreshCw } from "lucide-react";
+import { useEffect, useState } from "react";
+import { Button } from "@/components/ui/button";
+import { Switch } from "@/components/ui/switch";
+import { pluginsApi } from "@/lib/api";
+import type { PluginInfo, PluginKind } from "@/types";
+
+const KIND_LABELS: Record<PluginKind, string> = {
+  executable: "EXE",
+  batch: "BAT",
+  powershell: "PowerShell",
+  python: "Python",
+};
+
+// Plugins are discovered from the plugins folder and stay off until enabled here.
+export function PluginSettings() {
+  const [plugins, setPlugins] = useState<PluginInfo[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = () =>
+    pluginsApi
+      .list()
+      .then((list) => {
+        setPlugins(list);
+        setError(null);
+      })
+      .catch((e) => {
+        console.error("Failed to load plugins:", e);
+        setError(String(e));
+      });
+
+  useEffect(() => {
+    load();
+  }, []);
+
+  const toggle = async (plugin: PluginInfo, enabled: boolean) => {
+    setPlugins((prev) =>
+      prev.map((item) => (item.id === plugin.id ? { ...item, enabled } : item)),
+    );
+    try {
+      await pluginsApi.setEnabled(plugin.id, enabled);
+    } catch (e) {
+      console.error("Failed to toggle plugin:", e);
+      await load();
+    }
+  };
+
+  const openFolder = async () => {
+    try {
+      await openPath(await pluginsApi.getDirectory());
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <section className="space-y-4">
+      <div className="flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2">
+          <Puzzle className="w-5 h-5" />
+          <h2 className="text-lg font-semibold">Плагины</h2>
+        </div>
+        <div className="flex gap-2">
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={load}
+            aria-label="Обновить список плагинов"
+          >
+            <RefreshCw className="w-4 h-4" />
+          </Button>
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={openFolder}
+            className="gap-2"
+          >
+            <FolderOpen className="w-4 h-4" />
+            Папка плагинов
+          </Button>
+        </div>
+      </div>
+
+      <div className="bg-card rounded-lg border divide-y">
+        {plugins.length === 0 && (
+          <p className="p-4 text-sm text-muted-foreground">
+            Положите .exe, .bat, .ps1 или .py в папку плагинов. Плагин получает
+            JSON о событии (добавлена игра, завершена сессия, готов бэкап) и
+            может ответить действиями.
+          </p>
+        )}
+        {plugins.map((plugin) => (
+          <div
+            key={plugin.id}
+            className="flex items-center justify-between gap-4 p-4"
+          >
+            <div className="min-w-0 flex-1">
+              <span className="text-sm font-medium block truncate">
+                {plugin.id}
+                <span className="ml-2 text-xs text-muted-foreground">
+                  {KIND_LABELS[plugin.kind]}
+                </span>
+              </span>
+              {plugin.last_run && (
+                <span
+                  className={
+                    plugin.last_run.error
+                      ? "text-xs text-destructive block truncate"
+                      : "text-xs text-muted-foreground block"
+                  }
+                >
+                  {plugin.last_run.error ??
+                    `${plugin.last_run.event}: ${plugin.last_run.duration_ms} мс`}
+                </span>
+              )}
+            </div>
+            <Switch
+              checked={plugin.enabled}
+              onCheckedChange={(enabled) => toggle(plugin, enabled)}
+              aria-label={plugin.id}
+            />
+          </div>
+        ))}
+      </div>
+      {error && <p className="text-xs text-destructive">{error}</p>}
+    </section>
+  );
+}
@@ -0,0 +1,52 @@
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Switch } from "@/components/ui/switch";
+import { settingsApi } from "@/lib/api";
+
+const SETTING_KEY = "rawg_auto_match";
+
+// Saved right away, like the other per-key settings outside the main form.
+export function RawgAutoMatchSetting() {
+  const { notify } = useToast();
+  const [enabled, setEnabled] = useState(false);
+
+  useEffect(() => {
+    settingsApi
+      .get(SETTING_KEY)
+      .then((value) => setEnabled(value === "true"))
+      .catch((e) => console.error("Failed to load RAWG auto match:", e));
+  }, []);
+
+  const toggle = async (value: boolean) => {
+    setEnabled(value);
+    try {
+      await settingsApi.set(SETTING_KEY, value ? "true" : "false");
+    } catch (e) {
+      setEnabled(!value);
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить настройку",
+        description: String(e),
+      });
+    }
+  };
+
+  return (
+    <div className="flex items-center justify-between gap-3 mt-4">
+      <div>
+        <span id="setting-rawg-auto-match" className="text-sm font-medium">
+          Подбирать метаданные автоматически
+        </span>
+        <span className="text-xs text-muted-foreground block">
+          Новые игры ищутся в RAWG в фоне; при неоднозначном совпадении
+          появится окно выбора.
+        </span>
+      </div>
+      <Switch
+        checked={enabled}
+        onCheckedChange={(value) => void toggle(value)}
+        aria-labelledby="setting-rawg-auto-match"
+      />
+    </div>
+  );
+}
@@ -6,7 +6,7 @@ import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { Switch } from "@/components/ui/switch";
-import { metadataApi } from "@/lib/api";
+import { describeMetadataError, metadataApi } from "@/lib/api";
 import type { Game, RawgGame } from "@/types";
 
 type GameLite = Pick<Game, "id" | "name">;
@@ -93,8 +93,7 @@ export function RawgMetadataPrompt({
 				tone: "error",
 				title:
 					"\u041d\u0435 \u0443\u0434\u0430\u043b\u043e\u0441\u044c \u043d\u0430\u0439\u0442\u0438 \u043c\u0435\u0442\u0430\u0434\u0430\u043d\u043d\u044b\u0435",
-				description:
-					"\u041f\u0440\u043e\u0432\u0435\u0440\u044c\u0442\u0435 RAWG API \u043a\u043b\u044e\u0447 \u0432 \u043d\u0430\u0441\u0442\u0440\u043e\u0439\u043a\u0430\u0445.",
+				description: describeMetadataError(e, "RAWG"),
 			});
 		} finally {
 			if (searchSeq.current === seq) {
@@ -140,6 +139,7 @@ export function RawgMetadataPrompt({
 				tone: "error",
 				title:
 					"\u041d\u0435 \u0443\u0434\u0430\u043b\u043e\u0441\u044c \u043f\u0440\u0438\u043c\u0435\u043d\u0438\u0442\u044c \u043c\u0435\u0442\u0430\u0434\u0430\u043d\u043d\u044b\u0435",
+				description: describeMetadataError(e, "RAWG"),
 			});
 		} finally {
 			if (applySeq.current === seq) {
@@ -0,0 +1,114 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { FolderSearch, Loader2, MapPin } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { gamesApi } from "@/lib/api";
+import type { Game, MovedGameMatch } from "@/types";
+
+// Shown for a game whose executable is gone: either search the scan folders for it or point
+// at the moved file by hand.
+export function RelocateGame({
+  game,
+  onRelocated,
+}: {
+  game: Game;
+  onRelocated: () => Promise<void> | void;
+}) {
+  const { notify } = useToast();
+  const [searching, setSearching] = useState(false);
+  const [matches, setMatches] = useState<MovedGameMatch[] | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const relocate = async (path: string) => {
+    setBusy(true);
+    try {
+      await gamesApi.relocate(game.id, path);
+      setMatches(null);
+      await onRelocated();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось обновить путь",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const search = async () => {
+    setSearching(true);
+    try {
+      const found = await gamesApi.findMoved();
+      setMatches(found.filter((match) => match.game_id === game.id));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось найти игру",
+        description: String(e),
+      });
+    } finally {
+      setSearching(false);
+    }
+  };
+
+  const pickFile = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: "Исполняемые файлы", extensions: ["exe"] }],
+      title: "Новое расположение игры",
+    });
+    const path = Array.isArray(selected) ? selected[0] : selected;
+    if (path) await relocate(path);
+  };
+
+  return (
+    <div className="flex flex-col items-end gap-2">
+      <div className="flex items-center gap-2">
+        <Button
+          variant="outline"
+          size="sm"
+          className="gap-2"
+          onClick={search}
+          disabled={searching || busy}
+        >
+          {searching ? (
+            <Loader2 className="w-4 h-4 animate-spin" />
+          ) : (
+            <FolderSearch className="w-4 h-4" />
+          )}
+          Найти в папках сканирования
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          className="gap-2"
+          onClick={pickFile}
+          disabled={busy}
+        >
+          <MapPin className="w-4 h-4" />
+          Указать файл
+        </Button>
+      </div>
+      {matches?.length === 0 && (
+        <div className="text-xs text-muted-foreground">
+          {"Похожих файлов не найдено"}
+        </div>
+      )}
+      {matches?.map((match) => (
+        <button
+          key={match.new_exe_path}
+          type="button"
+          disabled={busy}
+          onClick={() => relocate(match.new_exe_path)}
+          className="max-w-[320px] truncate text-right text-xs text-muted-foreground hover:text-foreground"
+          title={match.new_exe_path}
+        >
+          {match.new_exe_path}
+          {match.size_matches === false && " (другой размер)"}
+        </button>
+      ))}
+    </div>
+  );
+}
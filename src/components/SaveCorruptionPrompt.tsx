@@ -0,0 +1,139 @@
+import { listen } from "@tauri-apps/api/event";
+import { FileWarning, Loader2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { backupApi, describeBackupError } from "@/lib/api";
+import type { SaveCorruptionEvent, SaveSuspicion } from "@/types";
+
+const describeSuspicion = (suspicion: SaveSuspicion) => {
+  switch (suspicion.kind) {
+    case "empty":
+      return "пустой файл";
+    case "zero_filled":
+      return "заполнен нулями";
+    case "bad_header":
+      return "неверный заголовок";
+    case "truncated":
+      return `обрезан: ${suspicion.size} из ${suspicion.previous_size} байт`;
+    case "future_mtime":
+      return "дата изменения в будущем";
+  }
+};
+
+const fileName = (path: string) => path.split(/[\\/]/).pop() ?? path;
+
+// Raised after a game exits with saves that look damaged. The exit backup is skipped, so
+// the last good backup is still there to restore in one click.
+export function SaveCorruptionPrompt() {
+  const { notify } = useToast();
+  const [alerts, setAlerts] = useState<SaveCorruptionEvent[]>([]);
+  const [restoring, setRestoring] = useState(false);
+
+  useEffect(() => {
+    const unlisten = listen<SaveCorruptionEvent>(
+      "backup:corruption-suspected",
+      (event) => {
+        if (!event.payload) return;
+        setAlerts((prev) => [
+          ...prev.filter((alert) => alert.game_id !== event.payload.game_id),
+          event.payload,
+        ]);
+      },
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const current = alerts[0];
+
+  const dismiss = () => {
+    if (!current) return;
+    setAlerts((prev) =>
+      prev.filter((alert) => alert.game_id !== current.game_id),
+    );
+  };
+
+  const restore = async () => {
+    if (!current?.backup_id) return;
+    setRestoring(true);
+    try {
+      // The damaged files are newer than the backup by definition.
+      await backupApi.restore(current.backup_id, undefined, true);
+      notify({
+        tone: "success",
+        title: "Сохранения восстановлены",
+        description: current.game_name,
+      });
+      dismiss();
+    } catch (error) {
+      notify({
+        tone: "error",
+        title: "Не удалось восстановить бэкап",
+        description: describeBackupError(error),
+      });
+    } finally {
+      setRestoring(false);
+    }
+  };
+
+  if (!current) return null;
+
+  return (
+    <div className="fixed bottom-4 left-4 z-50 w-full max-w-sm px-4 lg:px-0">
+      <div className="rounded-2xl border border-destructive/40 bg-card/95 px-4 py-3 shadow-[0_18px_40px_rgba(8,12,24,0.45)] backdrop-blur-xl">
+        <div className="flex items-start gap-3">
+          <FileWarning className="h-4 w-4 mt-0.5 text-destructive" />
+          <div className="flex-1 min-w-0">
+            <div className="text-sm font-semibold">
+              {`Сохранения ${current.game_name} могут быть повреждены`}
+            </div>
+            <div className="text-xs text-muted-foreground mt-1">
+              {"Автобэкап после выхода пропущен."}
+            </div>
+            <ul className="mt-2 space-y-0.5">
+              {current.files.slice(0, 4).map((file) => (
+                <li
+                  key={file.path}
+                  className="text-xs text-muted-foreground truncate"
+                  title={file.path}
+                >
+                  {`${fileName(file.path)} — ${describeSuspicion(file.suspicion)}`}
+                </li>
+              ))}
+              {current.files.length > 4 && (
+                <li className="text-xs text-muted-foreground">
+                  {`и ещё ${current.files.length - 4}`}
+                </li>
+              )}
+            </ul>
+          </div>
+        </div>
+        <div className="mt-3 flex justify-end gap-2">
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={restoring}
+            onClick={dismiss}
+          >
+            {"Оставить как есть"}
+          </Button>
+          {current.backup_id && (
+            <Button
+              size="sm"
+              disabled={restoring}
+              onClick={restore}
+              className="gap-2"
+            >
+              {restoring && <Loader2 className="w-4 h-4 animate-spin" />}
+              {current.backup_created_at
+                ? `Восстановить от ${new Date(current.backup_created_at).toLocaleString()}`
+                : "Восстановить бэкап"}
+            </Button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
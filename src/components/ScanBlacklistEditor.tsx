@@ -0,0 +1,74 @@
+import { Save } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { settingsApi } from "@/lib/api";
+
+const SETTING_KEY = "scan_blacklist";
+
+// The user's own scan exclusions: one pattern per line, matched against file and folder
+// names (`*` and `?` wildcards, case-insensitive).
+export function ScanBlacklistEditor() {
+  const { notify } = useToast();
+  const [saved, setSaved] = useState("");
+  const [text, setText] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    settingsApi
+      .get(SETTING_KEY)
+      .then((value) => {
+        setSaved(value ?? "");
+        setText(value ?? "");
+      })
+      .catch((e) => console.error("Failed to load scan blacklist:", e));
+  }, []);
+
+  const save = async () => {
+    setBusy(true);
+    const value = text
+      .split("\n")
+      .map((line) => line.trim())
+      .filter(Boolean)
+      .join("\n");
+    try {
+      await settingsApi.set(SETTING_KEY, value);
+      setSaved(value);
+      setText(value);
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось сохранить исключения",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="rounded-lg border p-3 space-y-2">
+      <div className="text-xs text-muted-foreground">
+        {"Исключения: по одному шаблону на строку, например *Launcher* или Tools"}
+      </div>
+      <textarea
+        value={text}
+        onChange={(e) => setText(e.target.value)}
+        rows={3}
+        aria-label="Исключения сканирования"
+        className="w-full rounded-md border border-input bg-background px-3 py-2 font-mono text-xs"
+      />
+      <div className="flex justify-end">
+        <Button
+          size="sm"
+          className="gap-2"
+          disabled={busy || text.trim() === saved}
+          onClick={save}
+        >
+          <Save className="w-4 h-4" />
+          {"Сохранить"}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,175 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { FolderPlus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { settingsApi } from "@/lib/api";
+import type { ScanDirectory } from "@/types";
+
+// Folders walked by "Сканировать все папки"; stored in the scan_directories table.
+// Folders marked for auto-scan are also rescanned in the background; network folders can get
+// their own read thread count.
+export function ScanDirectoriesEditor() {
+  const { notify } = useToast();
+  const [dirs, setDirs] = useState<ScanDirectory[]>([]);
+
+  useEffect(() => {
+    settingsApi
+      .listScanDirectories()
+      .then(setDirs)
+      .catch((e) => console.error("Failed to load scan directories:", e));
+  }, []);
+
+  const addDirectory = async () => {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: "Добавить папку для сканирования",
+    });
+    if (!selected) return;
+    try {
+      await settingsApi.addScanDirectory(selected);
+      setDirs((current) =>
+        current.some((dir) => dir.path === selected)
+          ? current
+          : [
+              ...current,
+              {
+                path: selected,
+                last_scanned: null,
+                auto_scan: false,
+                threads: null,
+                network: /^(\\\\|\/\/)/.test(selected),
+              },
+            ],
+      );
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось добавить папку",
+        description: String(e),
+      });
+    }
+  };
+
+  const removeDirectory = async (path: string) => {
+    try {
+      await settingsApi.removeScanDirectory(path);
+      setDirs((current) => current.filter((dir) => dir.path !== path));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось удалить папку",
+        description: String(e),
+      });
+    }
+  };
+
+  const toggleAutoScan = async (path: string, enabled: boolean) => {
+    try {
+      await settingsApi.setScanDirectoryAutoScan(path, enabled);
+      setDirs((current) =>
+        current.map((dir) =>
+          dir.path === path ? { ...dir, auto_scan: enabled } : dir,
+        ),
+      );
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось изменить автосканирование",
+        description: String(e),
+      });
+    }
+  };
+
+  const changeThreads = async (path: string, value: string) => {
+    const threads = value.trim() === "" ? null : Number(value);
+    if (threads != null && (!Number.isInteger(threads) || threads < 1)) return;
+    try {
+      await settingsApi.setScanDirectoryThreads(path, threads);
+      setDirs((current) =>
+        current.map((dir) => (dir.path === path ? { ...dir, threads } : dir)),
+      );
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось изменить число потоков",
+        description: String(e),
+      });
+    }
+  };
+
+  return (
+    <div className="rounded-lg border p-3 space-y-2">
+      <div className="text-xs text-muted-foreground">
+        {"Папки для сканирования"}
+      </div>
+      {dirs.length > 0 ? (
+        <ul className="space-y-1">
+          {dirs.map((dir) => (
+            <li key={dir.path} className="flex items-center gap-2 text-xs">
+              <span className="flex-1 truncate font-mono" title={dir.path}>
+                {dir.path}
+              </span>
+              {dir.network && (
+                <span
+                  className="rounded bg-muted px-1.5 py-0.5 text-muted-foreground"
+                  title="Сетевая папка: читается в 2 потока, недоступная пропускается"
+                >
+                  {"Сеть"}
+                </span>
+              )}
+              <input
+                type="number"
+                min={1}
+                max={64}
+                value={dir.threads ?? ""}
+                placeholder="авто"
+                title="Потоков чтения при сканировании"
+                onChange={(e) => void changeThreads(dir.path, e.target.value)}
+                className="w-16 rounded-md border border-input bg-background px-2 py-1"
+              />
+              <label
+                className="flex items-center gap-1 text-muted-foreground"
+                title="Проверять папку на новые игры в фоне"
+              >
+                <input
+                  type="checkbox"
+                  checked={dir.auto_scan}
+                  onChange={(e) =>
+                    void toggleAutoScan(dir.path, e.target.checked)
+                  }
+                />
+                {"Авто"}
+              </label>
+              <Button
+                variant="ghost"
+                size="icon"
+                className="h-7 w-7"
+                title="Удалить"
+                onClick={() => void removeDirectory(dir.path)}
+              >
+                <Trash2 className="w-3.5 h-3.5" />
+              </Button>
+            </li>
+          ))}
+        </ul>
+      ) : (
+        <div className="text-xs text-muted-foreground opacity-70">
+          {"Папок пока нет"}
+        </div>
+      )}
+      <div className="flex justify-end">
+        <Button
+          size="sm"
+          variant="outline"
+          className="gap-2"
+          onClick={() => void addDirectory()}
+        >
+          <FolderPlus className="w-4 h-4" />
+          {"Добавить папку"}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,114 @@
+import type { ScanOptions } from "@/types";
+
+const MB = 1024 * 1024;
+
+interface ScanOptionsEditorProps {
+  options: ScanOptions;
+  onChange: (options: ScanOptions) => void;
+}
+
+const toNumber = (value: string) => {
+  const parsed = Number(value);
+  return value.trim() === "" || Number.isNaN(parsed) ? null : parsed;
+};
+
+// Limits for the next scan; useful when scanning a whole drive.
+export function ScanOptionsEditor({ options, onChange }: ScanOptionsEditorProps) {
+  const update = (patch: Partial<ScanOptions>) => onChange({ ...options, ...patch });
+  const sizeField = (bytes: number | null | undefined) =>
+    bytes == null ? "" : String(Math.round(bytes / MB));
+  const toBytes = (value: string) => {
+    const mb = toNumber(value);
+    return mb == null ? null : mb * MB;
+  };
+
+  return (
+    <div className="rounded-lg border p-3 space-y-2 text-xs">
+      <div className="text-muted-foreground">{"Параметры сканирования"}</div>
+      <div className="grid grid-cols-1 sm:grid-cols-3 gap-2">
+        <label className="flex flex-col gap-1">
+          {"Глубина папок"}
+          <input
+            type="number"
+            min={0}
+            value={options.max_depth ?? ""}
+            placeholder="без ограничений"
+            onChange={(e) => update({ max_depth: toNumber(e.target.value) })}
+            className="rounded-md border border-input bg-background px-2 py-1"
+          />
+        </label>
+        <label className="flex flex-col gap-1">
+          {"Мин. размер exe, МБ"}
+          <input
+            type="number"
+            min={0}
+            value={sizeField(options.min_exe_bytes)}
+            onChange={(e) => update({ min_exe_bytes: toBytes(e.target.value) })}
+            className="rounded-md border border-input bg-background px-2 py-1"
+          />
+        </label>
+        <label className="flex flex-col gap-1">
+          {"Макс. размер exe, МБ"}
+          <input
+            type="number"
+            min={0}
+            value={sizeField(options.max_exe_bytes)}
+            onChange={(e) => update({ max_exe_bytes: toBytes(e.target.value) })}
+            className="rounded-md border border-input bg-background px-2 py-1"
+          />
+        </label>
+        <label className="flex flex-col gap-1">
+          {"Потоков на папку"}
+          <input
+            type="number"
+            min={1}
+            max={64}
+            value={options.threads ?? ""}
+            placeholder="авто"
+            onChange={(e) => update({ threads: toNumber(e.target.value) })}
+            className="rounded-md border border-input bg-background px-2 py-1"
+          />
+        </label>
+        <label className="flex flex-col gap-1">
+          {"Таймаут ответа папки, с"}
+          <input
+            type="number"
+            min={1}
+            value={options.entry_timeout_secs ?? ""}
+            placeholder="сеть: 30"
+            onChange={(e) =>
+              update({ entry_timeout_secs: toNumber(e.target.value) })
+            }
+            className="rounded-md border border-input bg-background px-2 py-1"
+          />
+        </label>
+      </div>
+      <div className="flex flex-wrap gap-4">
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={options.follow_links ?? false}
+            onChange={(e) => update({ follow_links: e.target.checked })}
+          />
+          {"Переходить по ссылкам и junction"}
+        </label>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={options.skip_hidden ?? true}
+            onChange={(e) => update({ skip_hidden: e.target.checked })}
+          />
+          {"Пропускать скрытые и системные папки"}
+        </label>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={options.skip_noise ?? true}
+            onChange={(e) => update({ skip_noise: e.target.checked })}
+          />
+          {"Пропускать node_modules и похожие"}
+        </label>
+      </div>
+    </div>
+  );
+}
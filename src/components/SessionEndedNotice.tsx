@@ -0,0 +1,38 @@
+import { listen } from "@tauri-apps/api/event";
+import { useEffect } from "react";
+import { useToast } from "@/components/ToastProvider";
+import type { SessionEnded } from "@/types";
+
+function formatPlaytime(seconds: number) {
+  const hours = Math.floor(seconds / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  if (hours > 0) return `${hours} ч ${minutes} мин`;
+  return `${minutes} мин`;
+}
+
+// Greets the user with the session's numbers when a game launched with "launch and
+// minimize" exits and the window comes back.
+export function SessionEndedNotice() {
+  const { notify } = useToast();
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<SessionEnded>("game:session-ended", (event) => {
+      if (!event.payload?.window_restored) return;
+      const { game_name, duration_seconds, total_playtime } = event.payload;
+      notify({
+        tone: "info",
+        title: game_name || "Игра закрыта",
+        description:
+          duration_seconds === null
+            ? `Всего наиграно ${formatPlaytime(total_playtime)}`
+            : `Сессия ${formatPlaytime(duration_seconds)} · всего ${formatPlaytime(total_playtime)}`,
+      });
+    }).then((fn) => {
+      unlisten = fn;
+    });
+    return () => unlisten?.();
+  }, [notify]);
+
+  return null;
+}
@@ -0,0 +1,104 @@
+import { listen } from "@tauri-apps/api/event";
+import { NotebookPen, X } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { sessionsApi } from "@/lib/api";
+import type { SessionNotePrompt as SessionNotePromptEvent } from "@/types";
+
+function formatSessionLength(seconds: number) {
+  const hours = Math.floor(seconds / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  if (hours > 0) return `${hours} \u0447 ${minutes} \u043c\u0438\u043d`;
+  return `${minutes} \u043c\u0438\u043d`;
+}
+
+// Asks for a short note after a long session ends; the note is stored on the
+// session and copied onto its exit auto backup.
+export function SessionNotePrompt() {
+  const { notify } = useToast();
+  const [queue, setQueue] = useState<SessionNotePromptEvent[]>([]);
+  const [note, setNote] = useState("");
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<SessionNotePromptEvent>("session:note-prompt", (event) => {
+      if (event.payload) {
+        setQueue((prev) => [...prev, event.payload]);
+      }
+    }).then((fn) => {
+      unlisten = fn;
+    });
+    return () => unlisten?.();
+  }, []);
+
+  const current = queue[0];
+
+  const dismiss = () => {
+    setQueue((prev) => prev.slice(1));
+    setNote("");
+  };
+
+  const save = async () => {
+    if (!current || !note.trim()) return;
+    setSaving(true);
+    try {
+      await sessionsApi.setNote(current.session_id, note.trim());
+      dismiss();
+    } catch (error) {
+      notify({
+        tone: "error",
+        title: "\u041d\u0435 \u0443\u0434\u0430\u043b\u043e\u0441\u044c \u0441\u043e\u0445\u0440\u0430\u043d\u0438\u0442\u044c \u0437\u0430\u043c\u0435\u0442\u043a\u0443",
+        description: String(error),
+      });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!current) return null;
+
+  return (
+    <div className="fixed bottom-4 left-4 z-50 w-full max-w-sm px-4 lg:px-0">
+      <div className="rounded-2xl border border-border/60 bg-card/95 px-4 py-3 shadow-[0_18px_40px_rgba(8,12,24,0.45)] backdrop-blur-xl">
+        <div className="flex items-start gap-3">
+          <NotebookPen className="h-4 w-4 mt-0.5 text-primary" />
+          <div className="flex-1 min-w-0">
+            <div className="text-sm font-semibold truncate">
+              {`\u0417\u0430\u043c\u0435\u0442\u043a\u0430 \u043a \u0441\u0435\u0441\u0441\u0438\u0438: ${current.game_name}`}
+            </div>
+            <div className="text-xs text-muted-foreground mt-1">
+              {`\u0421\u044b\u0433\u0440\u0430\u043d\u043e ${formatSessionLength(current.duration_seconds)}. \u0413\u0434\u0435 \u0432\u044b \u043e\u0441\u0442\u0430\u043d\u043e\u0432\u0438\u043b\u0438\u0441\u044c?`}
+            </div>
+          </div>
+          <button
+            onClick={dismiss}
+            className="text-muted-foreground hover:text-foreground transition-colors"
+            aria-label="Dismiss"
+          >
+            <X className="h-4 w-4" />
+          </button>
+        </div>
+        <form
+          className="mt-3 flex gap-2"
+          onSubmit={(event) => {
+            event.preventDefault();
+            save();
+          }}
+        >
+          <Input
+            autoFocus
+            value={note}
+            onChange={(event) => setNote(event.target.value)}
+            placeholder={"\u041d\u0430\u043f\u0440\u0438\u043c\u0435\u0440: \u0434\u043e\u0448\u0451\u043b \u0434\u043e 3 \u0430\u043a\u0442\u0430"}
+          />
+          <Button type="submit" size="sm" disabled={saving || !note.trim()}>
+            {"\u0421\u043e\u0445\u0440\u0430\u043d\u0438\u0442\u044c"}
+          </Button>
+        </form>
+      </div>
+    </div>
+  );
+}
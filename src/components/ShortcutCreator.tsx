@@ -0,0 +1,59 @@
+import { Link2, Monitor } from "lucide-react";
+import { useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { gamesApi } from "@/lib/api";
+import type { Game, ShortcutLocation } from "@/types";
+
+// Windows shortcuts for the game. Store and emulator games get one that starts them through
+// the launcher; plain games start their executable directly.
+export function ShortcutCreator({ game }: { game: Game }) {
+  const { notify } = useToast();
+  const [busy, setBusy] = useState(false);
+
+  const create = async (location: ShortcutLocation) => {
+    setBusy(true);
+    try {
+      const path = await gamesApi.createShortcut(game.id, location);
+      notify({ tone: "success", title: "Ярлык создан", description: path });
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось создать ярлык",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Ярлык"}
+      </div>
+      <div className="flex flex-wrap gap-2">
+        <Button
+          variant="outline"
+          size="sm"
+          className="gap-2"
+          disabled={busy}
+          onClick={() => create("desktop")}
+        >
+          <Monitor className="w-4 h-4" />
+          {"На рабочий стол"}
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          className="gap-2"
+          disabled={busy}
+          onClick={() => create("start_menu")}
+        >
+          <Link2 className="w-4 h-4" />
+          {"В меню «Пуск»"}
+        </Button>
+      </div>
+    </div>
+  );
+}
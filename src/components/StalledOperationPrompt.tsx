@@ -0,0 +1,109 @@
+import { listen } from "@tauri-apps/api/event";
+import { TriangleAlert } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { backupApi } from "@/lib/api";
+import type { StallAction, StalledOperation } from "@/types";
+
+// Shown while the watchdog reports a backup or restore with no progress, so a
+// hung network drive or an antivirus lock does not leave the progress bar frozen.
+export function StalledOperationPrompt() {
+  const { notify } = useToast();
+  const [stalled, setStalled] = useState<StalledOperation[]>([]);
+  const [pending, setPending] = useState(false);
+
+  useEffect(() => {
+    const unlisteners: Promise<() => void>[] = [
+      listen<StalledOperation>("backup:stalled", (event) => {
+        if (!event.payload) return;
+        setStalled((prev) => [
+          ...prev.filter(
+            (op) => op.operation_id !== event.payload.operation_id,
+          ),
+          event.payload,
+        ]);
+      }),
+      listen<StalledOperation>("backup:stall-cleared", (event) => {
+        if (!event.payload) return;
+        setStalled((prev) =>
+          prev.filter((op) => op.operation_id !== event.payload.operation_id),
+        );
+      }),
+    ];
+    return () => {
+      unlisteners.forEach((promise) => promise.then((unlisten) => unlisten()));
+    };
+  }, []);
+
+  const current = stalled[0];
+
+  const resolve = async (action: StallAction) => {
+    if (!current) return;
+    setPending(true);
+    try {
+      await backupApi.resolveStalled(current.operation_id, action);
+    } catch (error) {
+      notify({
+        tone: "error",
+        title: "\u041d\u0435 \u0443\u0434\u0430\u043b\u043e\u0441\u044c \u0432\u044b\u043f\u043e\u043b\u043d\u0438\u0442\u044c \u0434\u0435\u0439\u0441\u0442\u0432\u0438\u0435",
+        description: String(error),
+      });
+    } finally {
+      setStalled((prev) =>
+        prev.filter((op) => op.operation_id !== current.operation_id),
+      );
+      setPending(false);
+    }
+  };
+
+  if (!current) return null;
+
+  const minutes = Math.max(1, Math.round(current.idle_seconds / 60));
+
+  return (
+    <div className="fixed bottom-4 right-4 z-50 w-full max-w-sm px-4 lg:px-0">
+      <div className="rounded-2xl border border-amber-500/40 bg-card/95 px-4 py-3 shadow-[0_18px_40px_rgba(8,12,24,0.45)] backdrop-blur-xl">
+        <div className="flex items-start gap-3">
+          <TriangleAlert className="h-4 w-4 mt-0.5 text-amber-500" />
+          <div className="flex-1 min-w-0">
+            <div className="text-sm font-semibold">
+              {current.kind === "restore"
+                ? "\u0412\u043e\u0441\u0441\u0442\u0430\u043d\u043e\u0432\u043b\u0435\u043d\u0438\u0435 \u0437\u0430\u0432\u0438\u0441\u043b\u043e"
+                : "\u0411\u044d\u043a\u0430\u043f \u0437\u0430\u0432\u0438\u0441"}
+            </div>
+            <div className="text-xs text-muted-foreground mt-1">
+              {`\u041d\u0435\u0442 \u043f\u0440\u043e\u0433\u0440\u0435\u0441\u0441\u0430 ${minutes} \u043c\u0438\u043d.`}
+            </div>
+            {current.path && (
+              <div
+                className="text-xs text-muted-foreground mt-1 truncate"
+                title={current.path}
+              >
+                {current.path}
+              </div>
+            )}
+          </div>
+        </div>
+        <div className="mt-3 flex justify-end gap-2">
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={pending}
+            onClick={() => resolve("skip_file")}
+          >
+            {"\u041f\u0440\u043e\u043f\u0443\u0441\u0442\u0438\u0442\u044c \u0444\u0430\u0439\u043b"}
+          </Button>
+          <Button
+            size="sm"
+            variant="destructive"
+            disabled={pending}
+            onClick={() => resolve("cancel")}
+          >
+            {"\u041e\u0442\u043c\u0435\u043d\u0438\u0442\u044c"}
+          </Button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,118 @@
+import { Plus, X } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useToast } from "@/components/ToastProvider";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { tagsApi } from "@/lib/api";
+import type { Game, Tag } from "@/types";
+
+// Tags on one game; typing a name that doesn't exist yet creates the tag.
+export function TagEditor({
+  game,
+  onSaved,
+}: {
+  game: Game;
+  onSaved: () => Promise<void> | void;
+}) {
+  const { notify } = useToast();
+  const [tags, setTags] = useState<Tag[]>([]);
+  const [name, setName] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  const load = () =>
+    tagsApi
+      .list()
+      .then(setTags)
+      .catch((e) => console.error("Failed to load tags:", e));
+
+  useEffect(() => {
+    load();
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    setBusy(true);
+    try {
+      await action();
+      await load();
+      await onSaved();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось изменить теги",
+        description: String(e),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const addByName = () => {
+    const trimmed = name.trim();
+    if (!trimmed) return;
+    setName("");
+    return run(async () => {
+      const tag = await tagsApi.add(trimmed);
+      await tagsApi.assign(game.id, tag.id);
+    });
+  };
+
+  const assigned = tags.filter((tag) => game.tags.includes(tag.id));
+  const available = tags.filter((tag) => !game.tags.includes(tag.id));
+
+  return (
+    <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
+      <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
+        {"Теги"}
+      </div>
+      {assigned.length > 0 && (
+        <div className="flex flex-wrap gap-1.5">
+          {assigned.map((tag) => (
+            <span
+              key={tag.id}
+              className="inline-flex items-center gap-1 rounded-md border border-border/60 px-2 py-0.5 text-[11px]"
+              style={tag.color ? { borderColor: tag.color } : undefined}
+            >
+              {tag.name}
+              <button
+                type="button"
+                disabled={busy}
+                onClick={() => run(() => tagsApi.unassign(game.id, tag.id))}
+                className="text-muted-foreground hover:text-foreground"
+                aria-label={`Убрать тег ${tag.name}`}
+              >
+                <X className="w-3 h-3" />
+              </button>
+            </span>
+          ))}
+        </div>
+      )}
+      <div className="flex items-center gap-2">
+        <Input
+          value={name}
+          onChange={(event) => setName(event.target.value)}
+          onKeyDown={(event) => {
+            if (event.key === "Enter") addByName();
+          }}
+          placeholder="Новый тег"
+          list="tag-suggestions"
+          className="h-8 text-xs"
+        />
+        <datalist id="tag-suggestions">
+          {available.map((tag) => (
+            <option key={tag.id} value={tag.name} />
+          ))}
+        </datalist>
+        <Button
+          variant="outline"
+          size="icon"
+          className="shrink-0 h-8 w-8"
+          disabled={busy || !name.trim()}
+          onClick={addByName}
+          title="Добавить тег"
+        >
+          <Plus className="w-4 h-4" />
+        </Button>
+      </div>
+    </div>
+  );
+}
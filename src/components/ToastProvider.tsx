@@ -2,6 +2,7 @@ import { createContext, useCallback, useContext, useEffect, useRef, useState } f
 import { listen } from "@tauri-apps/api/event";
 import { X } from "lucide-react";
 import { cn } from "@/lib/utils";
+import type { AutoMatched, NewGamesFound } from "@/types";
 
 export type ToastTone = "info" | "success" | "warning" | "error";
 
@@ -63,9 +64,9 @@ export function ToastProvider({ children }: { children: React.ReactNode }) {
   );
 
   useEffect(() => {
-    let unlisten: (() => void) | null = null;
+    const unlisteners: (() => void)[] = [];
     const setup = async () => {
-      unlisten = await listen<{ game_id: string; game_name: string }>(
+      const unlistenSavePath = await listen<{ game_id: string; game_name: string }>(
         "game:save-path-missing",
         (event) => {
           const name = event.payload?.game_name || "";
@@ -76,13 +77,83 @@ export function ToastProvider({ children }: { children: React.ReactNode }) {
           });
         },
       );
+      unlisteners.push(unlistenSavePath);
+
+      const unlistenQuota = await listen<{
+        game_id: string;
+        game_name: string;
+        scope: "game" | "total";
+        used_bytes: number;
+        quota_bytes: number;
+        blocked: boolean;
+      }>("backup:quota-exceeded", (event) => {
+        const payload = event.payload;
+        const name = payload?.game_name || "";
+        const used = Math.round((payload?.used_bytes ?? 0) / 1024 / 1024);
+        const quota = Math.round((payload?.quota_bytes ?? 0) / 1024 / 1024);
+        notify({
+          tone: "error",
+          durationMs: 8000,
+          title: payload?.blocked === false
+            ? "\u041f\u0440\u0435\u0432\u044b\u0448\u0435\u043d\u0430 \u043a\u0432\u043e\u0442\u0430 \u0431\u044d\u043a\u0430\u043f\u043e\u0432"
+            : "\u0411\u044d\u043a\u0430\u043f \u043d\u0435 \u0441\u043e\u0437\u0434\u0430\u043d: \u043f\u0440\u0435\u0432\u044b\u0448\u0435\u043d\u0430 \u043a\u0432\u043e\u0442\u0430",
+          description:
+            payload?.scope === "total"
+              ? `\u0412\u0441\u0435 \u0431\u044d\u043a\u0430\u043f\u044b \u0437\u0430\u043d\u0438\u043c\u0430\u044e\u0442 ${used} \u041c\u0411 \u043f\u0440\u0438 \u043e\u0431\u0449\u0435\u043c \u043b\u0438\u043c\u0438\u0442\u0435 ${quota} \u041c\u0411. \u0423\u0434\u0430\u043b\u0438\u0442\u0435 \u0441\u0442\u0430\u0440\u044b\u0435 \u0431\u044d\u043a\u0430\u043f\u044b \u0438\u043b\u0438 \u0443\u0432\u0435\u043b\u0438\u0447\u044c\u0442\u0435 \u043a\u0432\u043e\u0442\u0443 \u0432 \u043d\u0430\u0441\u0442\u0440\u043e\u0439\u043a\u0430\u0445.`
+              : `\u0411\u044d\u043a\u0430\u043f\u044b \"${name}\" \u0437\u0430\u043d\u0438\u043c\u0430\u044e\u0442 ${used} \u041c\u0411 \u043f\u0440\u0438 \u043b\u0438\u043c\u0438\u0442\u0435 ${quota} \u041c\u0411. \u0423\u0434\u0430\u043b\u0438\u0442\u0435 \u0441\u0442\u0430\u0440\u044b\u0435 \u0431\u044d\u043a\u0430\u043f\u044b \u0438\u043b\u0438 \u0443\u0432\u0435\u043b\u0438\u0447\u044c\u0442\u0435 \u043a\u0432\u043e\u0442\u0443 \u0432 \u043d\u0430\u0441\u0442\u0440\u043e\u0439\u043a\u0430\u0445.`,
+        });
+      });
+      unlisteners.push(unlistenQuota);
+      const unlistenNightly = await listen<{
+        backed_up: string[];
+        failed: { game_name: string; error: string }[];
+      }>("backup:nightly-digest", (event) => {
+        const backedUp = event.payload?.backed_up ?? [];
+        const failed = event.payload?.failed ?? [];
+        const parts = [];
+        if (backedUp.length > 0) {
+          parts.push(`\u0421\u043e\u0445\u0440\u0430\u043d\u0435\u043d\u044b: ${backedUp.join(", ")}`);
+        }
+        if (failed.length > 0) {
+          parts.push(`\u041e\u0448\u0438\u0431\u043a\u0438: ${failed.map((f) => f.game_name).join(", ")}`);
+        }
+        notify({
+          tone: failed.length > 0 ? "warning" : "success",
+          durationMs: 8000,
+          title: "\u041d\u043e\u0447\u043d\u043e\u0439 \u0431\u044d\u043a\u0430\u043f",
+          description: parts.join(". "),
+        });
+      });
+      unlisteners.push(unlistenNightly);
+      const unlistenNewGames = await listen<NewGamesFound>(
+        "scan:new-games-found",
+        (event) => {
+          const count = event.payload?.paths?.length ?? 0;
+          notify({
+            tone: "info",
+            durationMs: 8000,
+            title: "\u041d\u0430\u0439\u0434\u0435\u043d\u044b \u043d\u043e\u0432\u044b\u0435 \u0438\u0433\u0440\u044b",
+            description: `${count} \u0432 \u043f\u0430\u043f\u043a\u0435 ${event.payload?.source_dir ?? ""}. \u0414\u043e\u0431\u0430\u0432\u044c\u0442\u0435 \u0438\u0445 \u043d\u0430 \u0441\u0442\u0440\u0430\u043d\u0438\u0446\u0435 \u0441\u043a\u0430\u043d\u0438\u0440\u043e\u0432\u0430\u043d\u0438\u044f.`,
+          });
+        },
+      );
+      unlisteners.push(unlistenNewGames);
+      const unlistenAutoMatched = await listen<AutoMatched>(
+        "metadata:auto-matched",
+        (event) => {
+          notify({
+            tone: "success",
+            title: "\u041c\u0435\u0442\u0430\u0434\u0430\u043d\u043d\u044b\u0435 \u0437\u0430\u0433\u0440\u0443\u0436\u0435\u043d\u044b",
+            description: event.payload?.name ?? "",
+          });
+        },
+      );
+      unlisteners.push(unlistenAutoMatched);
     };
     setup();
 
     return () => {
-      if (unlisten) {
-        unlisten();
-      }
+      unlisteners.forEach((unlisten) => unlisten());
     };
   }, [notify]);
 
@@ -2,7 +2,7 @@ import { disable, enable, isEnabled } from "@tauri-apps/plugin-autostart";
 import { open } from "@tauri-apps/plugin-dialog";
 import { useCallback, useEffect, useState } from "react";
 import { backupApi, metadataApi, settingsApi } from "@/lib/api";
-import type { AppSettings } from "@/types";
+import type { AppSettings, ManifestInfo } from "@/types";
 
 const clampNumber = (value: number, min: number, max: number) =>
   Math.min(max, Math.max(min, value));
@@ -18,9 +18,11 @@ export function useSettingsState() {
   const [compressionEnabled, setCompressionEnabled] = useState(true);
   const [compressionLevel, setCompressionLevel] = useState(60);
   const [skipCompressionOnce, setSkipCompressionOnce] = useState(false);
+  const [integrityCheck, setIntegrityCheck] = useState(true);
   const [maxBackups, setMaxBackups] = useState(5);
   const [rawgApiKey, setRawgApiKey] = useState("");
   const [autoStart, setAutoStart] = useState(false);
+  const [manifestInfo, setManifestInfo] = useState<ManifestInfo | null>(null);
 
   const handleCompressionToggle = useCallback((next: boolean) => {
     setCompressionEnabled(next);
@@ -81,6 +83,7 @@ export function useSettingsState() {
       setCompressionEnabled(appSettings.backup_compression_enabled);
       setCompressionLevel(appSettings.backup_compression_level);
       setSkipCompressionOnce(appSettings.backup_skip_compression_once);
+      setIntegrityCheck(appSettings.backup_integrity_check);
       setMaxBackups(appSettings.max_backups_per_game);
       setRawgApiKey(appSettings.rawg_api_key);
     } catch (e) {
@@ -102,6 +105,7 @@ export function useSettingsState() {
         backup_compression_enabled: compressionEnabled,
         backup_compression_level: compressionLevel,
         backup_skip_compression_once: skipCompressionOnce,
+        backup_integrity_check: integrityCheck,
         max_backups_per_game: maxBackups,
         rawg_api_key: rawgApiKey,
         ludusavi_path: "native",
@@ -123,6 +127,7 @@ export function useSettingsState() {
     backupDirectory,
     compressionEnabled,
     compressionLevel,
+    integrityCheck,
     loadSettings,
     maxBackups,
     rawgApiKey,
@@ -143,14 +148,25 @@ export function useSettingsState() {
     }
   }, []);
 
-  const refreshSqobaManifest = useCallback(async () => {
-    await backupApi.refreshSqobaManifest();
+  const loadManifestInfo = useCallback(async () => {
+    try {
+      setManifestInfo(await backupApi.getManifestInfo());
+    } catch (e) {
+      console.error("Failed to load manifest info:", e);
+    }
+  }, []);
+
+  const refreshSqobaManifest = useCallback(async (force = false) => {
+    const info = await backupApi.refreshManifest(force);
+    setManifestInfo(info);
+    return info;
   }, []);
 
   useEffect(() => {
     loadSettings();
     checkAutoStart();
-  }, [checkAutoStart, loadSettings]);
+    loadManifestInfo();
+  }, [checkAutoStart, loadManifestInfo, loadSettings]);
 
   return {
     loading,
@@ -167,6 +183,8 @@ export function useSettingsState() {
     handleCompressionLevelChange,
     skipCompressionOnce,
     setSkipCompressionOnce,
+    integrityCheck,
+    setIntegrityCheck,
     maxBackups,
     handleMaxBackupsChange,
     rawgApiKey,
@@ -175,6 +193,7 @@ export function useSettingsState() {
     toggleAutoStart,
     saveSettings,
     selectBackupDirectory,
+    manifestInfo,
     refreshSqobaManifest,
   };
 }
@@ -1,37 +1,219 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  DeleteGameOptions,
   Game,
+  MovedGameMatch,
   NewGame,
   UpdateGame,
+  CustomField,
+  CustomFieldType,
+  FileVerification,
+  GamePage,
+  GameQuery,
+  GameSort,
+  GameSummary,
+  ImportStrategy,
+  LibraryExportOptions,
+  LibraryImportSummary,
+  ReportFormat,
+  ShortcutLocation,
+  ShortcutTarget,
+  SortDirection,
+  LaunchProfile,
+  LaunchProfileInput,
+  Emulator,
+  EmulatorInput,
+  GameCompanion,
+  GameCompanionInput,
+  Tag,
+  Collection,
+  CollectionInput,
   Backup,
+  BackupError,
+  BackupEvent,
   BackupInfo,
   SavePathLookup,
+  SaveCandidate,
+  GameSavePath,
+  ManifestMatch,
+  ManifestOverride,
   RestoreCheck,
+  RestoreRootMapping,
+  RestorePreview,
+  ManifestRepair,
+  BackupStats,
+  BackupDiff,
+  BackupDiagnostics,
+  StallAction,
+  InstallArchive,
+  BulkBackupToggle,
+  ManifestInfo,
+  CompressionBenchmark,
   RawgGame,
   RawgGameDetails,
+  IgdbCredentials,
+  IgdbGame,
+  IgdbGameDetails,
+  MatchSuggestions,
+  MetadataBundleSummary,
+  MetadataError,
+  MetadataField,
+  MetadataOverwrite,
+  MetadataSource,
+  PcgwLocations,
   AppSettings,
+  ScanDirectory,
   PlaytimeStats,
+  StatsCard,
+  LibraryOverview,
+  StatsRange,
+  LaunchRecord,
+  InstancePolicy,
+  LaunchResult,
+  GameNote,
+  GameLink,
+  PlaySession,
   ProcessEntry,
   SystemInfo,
   DiskSpeedResult,
+  ImageCacheStats,
+  AppInfo,
+  DatabaseStatus,
+  PluginInfo,
+  LibraryFixture,
 } from "@/types";
 
 // Game API
+// Error a launch rejects with when the game's instance policy is "refuse"
+export const ALREADY_RUNNING_ERROR = "Игра уже запущена";
+
 export const gamesApi = {
-  getAll: () => invoke<Game[]>("get_all_games"),
+  getAll: (includeHidden?: boolean) =>
+    invoke<Game[]>(
+      "get_all_games",
+      includeHidden === undefined ? undefined : { includeHidden },
+    ),
+  getSummaries: () => invoke<GameSummary[]>("get_game_summaries"),
+  /** `limit` is capped at 50. */
+  getRecentlyPlayed: (limit: number) =>
+    invoke<GameSummary[]>("get_recently_played", { limit }),
+  getMostPlayed: (limit: number) =>
+    invoke<GameSummary[]>("get_most_played", { limit }),
+  getLargest: (limit: number) =>
+    invoke<GameSummary[]>("get_largest_games", { limit }),
+  /** Cached for a week unless `refresh` is set. */
+  computeInstallSize: (gameId: string, refresh?: boolean) =>
+    invoke<number>("compute_install_size", { gameId, refresh }),
+  /** Progress arrives as `verify-files:progress`. */
+  verifyFiles: (gameId: string, rebaseline?: boolean) =>
+    invoke<FileVerification>("verify_game_files", { gameId, rebaseline }),
   get: (id: string) => invoke<Game | null>("get_game", { id }),
   add: (game: NewGame) => invoke<Game>("add_game", { game }),
   addBatch: (games: NewGame[]) => invoke<Game[]>("add_games_batch", { games }),
   update: (update: UpdateGame) => invoke<Game>("update_game", { update }),
-  delete: (id: string) => invoke<void>("delete_game", { id }),
+  updateBatch: (updates: UpdateGame[]) =>
+    invoke<Game[]>("update_games_batch", { updates }),
+  delete: (id: string, options?: DeleteGameOptions) =>
+    invoke<void>("delete_game", { id, options }),
+  deleteBatch: (ids: string[], options?: DeleteGameOptions) =>
+    invoke<number>("delete_games_batch", { ids, options }),
+  relocate: (id: string, newExePath: string) =>
+    invoke<Game>("relocate_game", { id, newExePath }),
+  findMoved: (scanDirs?: string[]) =>
+    invoke<MovedGameMatch[]>("find_moved_games", { scanDirs }),
   toggleFavorite: (id: string) => invoke<Game>("toggle_favorite", { id }),
+  setHidden: (id: string, hidden: boolean) =>
+    invoke<Game>("set_game_hidden", { id, hidden }),
+  setTotalPlaytime: (gameId: string, seconds: number) =>
+    invoke<Game>("set_total_playtime", { gameId, seconds }),
+  addPlaytimeAdjustment: (gameId: string, seconds: number, note?: string) =>
+    invoke<Game>("add_playtime_adjustment", { gameId, seconds, note }),
+  setSyncOptions: (
+    id: string,
+    options: { restoreOnLaunch?: boolean; backupOnExit?: boolean },
+  ) =>
+    invoke<Game>("set_game_sync_options", {
+      id,
+      restoreOnLaunch: options.restoreOnLaunch,
+      backupOnExit: options.backupOnExit,
+    }),
+  setDoNotTrack: (id: string, doNotTrack: boolean) =>
+    invoke<Game>("set_game_do_not_track", { id, doNotTrack }),
+  setInstancePolicy: (id: string, policy: InstancePolicy | null) =>
+    invoke<Game>("set_game_instance_policy", { id, policy }),
+  setMetadataSource: (id: string, source: MetadataSource | null) =>
+    invoke<Game>("set_game_metadata_source", { id, source }),
+  setEnvVars: (id: string, envVars: Record<string, string>) =>
+    invoke<Game>("set_game_env_vars", { id, envVars }),
+  setLockedFields: (id: string, fields: MetadataField[]) =>
+    invoke<Game>("set_game_locked_fields", { id, fields }),
   getFavorites: () => invoke<Game[]>("get_favorites"),
   recordLaunch: (id: string) => invoke<Game>("record_game_launch", { id }),
   search: (query: string) => invoke<Game[]>("search_games", { query }),
+  query: (query: GameQuery) => invoke<Game[]>("query_games", { query }),
+  getPage: (
+    offset: number,
+    limit: number,
+    sortBy?: GameSort,
+    direction?: SortDirection,
+    filter?: GameQuery,
+  ) =>
+    invoke<GamePage>("get_games_page", {
+      offset,
+      limit,
+      sortBy,
+      direction,
+      filter,
+    }),
+  exportReport: (path: string, format: ReportFormat) =>
+    invoke<number>("export_library_report", { path, format }),
+  exportLibrary: (path: string, options?: LibraryExportOptions) =>
+    invoke<number>("export_library", { path, options }),
+  importLibrary: (path: string, strategy: ImportStrategy) =>
+    invoke<LibraryImportSummary>("import_library", { path, strategy }),
+  getCustomFields: () => invoke<CustomField[]>("get_custom_fields"),
+  createCustomField: (name: string, fieldType: CustomFieldType) =>
+    invoke<CustomField>("create_custom_field", { name, fieldType }),
+  deleteCustomField: (id: string) =>
+    invoke<void>("delete_custom_field", { id }),
+  setCustomField: (gameId: string, fieldId: string, value: string | null) =>
+    invoke<Game>("set_game_custom_field", { gameId, fieldId, value }),
   existsByPath: (exePath: string) =>
     invoke<boolean>("game_exists_by_path", { exePath }),
   isInstalled: (id: string) => invoke<boolean>("is_game_installed", { id }),
-  launch: (id: string) => invoke<void>("launch_game", { id }),
+  /**
+   * Without `profileId` the game's default profile is used, if it has one. `minimize` hides
+   * the window until the game exits; left out, the `launch_and_minimize` setting decides.
+   */
+  launch: (id: string, profileId?: string, minimize?: boolean) =>
+    invoke<LaunchResult>("launch_game", { id, profileId, minimize }),
+  createShortcut: (
+    gameId: string,
+    location: ShortcutLocation,
+    target?: ShortcutTarget,
+  ) => invoke<string>("create_shortcut", { gameId, location, target }),
+  getLaunchProfiles: (gameId?: string) =>
+    invoke<LaunchProfile[]>("get_launch_profiles", { gameId }),
+  saveLaunchProfile: (profile: LaunchProfileInput) =>
+    invoke<LaunchProfile>("save_launch_profile", { profile }),
+  deleteLaunchProfile: (id: string) =>
+    invoke<void>("delete_launch_profile", { id }),
+  launchWithProfile: (gameId: string, profileId: string, minimize?: boolean) =>
+    invoke<LaunchResult>("launch_game_with_profile", {
+      gameId,
+      profileId,
+      minimize,
+    }),
+  getEmulators: () => invoke<Emulator[]>("get_emulators"),
+  saveEmulator: (emulator: EmulatorInput) =>
+    invoke<Emulator>("save_emulator", { emulator }),
+  deleteEmulator: (id: string) => invoke<void>("delete_emulator", { id }),
+  getCompanions: (gameId: string) =>
+    invoke<GameCompanion[]>("get_game_companions", { gameId }),
+  saveCompanion: (companion: GameCompanionInput) =>
+    invoke<GameCompanion>("save_game_companion", { companion }),
+  deleteCompanion: (id: string) =>
+    invoke<void>("delete_game_companion", { id }),
   getRunningInstances: (id: string) =>
     invoke<number>("get_running_instances", { id }),
   killProcesses: (id: string) => invoke<number>("kill_game_processes", { id }),
@@ -39,17 +221,129 @@ export const gamesApi = {
     invoke<string>("resolve_shortcut_target", { path }),
 };
 
+// Tags on games and smart collections (saved library filters)
+export const tagsApi = {
+  list: () => invoke<Tag[]>("get_tags"),
+  add: (name: string, color?: string | null) =>
+    invoke<Tag>("add_tag", { name, color }),
+  delete: (id: string) => invoke<void>("delete_tag", { id }),
+  assign: (gameId: string, tagId: string) =>
+    invoke<Game>("assign_tag", { gameId, tagId }),
+  unassign: (gameId: string, tagId: string) =>
+    invoke<Game>("unassign_tag", { gameId, tagId }),
+  getGames: (tagId: string) => invoke<Game[]>("get_games_by_tag", { tagId }),
+  getCollections: () => invoke<Collection[]>("get_collections"),
+  saveCollection: (collection: CollectionInput) =>
+    invoke<Collection>("save_collection", { collection }),
+  deleteCollection: (id: string) => invoke<void>("delete_collection", { id }),
+  getCollectionGames: (id: string) =>
+    invoke<Game[]>("get_collection_games", { id }),
+};
+
+// Covers kept in the local image store
+export const imagesApi = {
+  importCover: (gameId: string, sourcePath: string) =>
+    invoke<Game>("import_cover_image", { gameId, sourcePath }),
+  cacheCover: (gameId: string, url: string) =>
+    invoke<Game>("cache_cover_image", { gameId, url }),
+  generateThumbnail: (gameId: string) =>
+    invoke<Game>("generate_cover_thumbnail", { gameId }),
+  cleanUnused: () => invoke<number>("clean_unused_images"),
+  getCacheStats: () => invoke<ImageCacheStats>("get_image_cache_stats"),
+  setPruningPaused: (paused: boolean) =>
+    invoke<ImageCacheStats>("set_image_cache_pruning_paused", { paused }),
+};
+
 // Metadata API (RAWG)
 export const metadataApi = {
   search: (query: string) => invoke<RawgGame[]>("search_rawg", { query }),
   getDetails: (rawgId: number) =>
     invoke<RawgGameDetails>("get_rawg_game_details", { rawgId }),
-  apply: (gameId: string, rawgId: number, rename: boolean) =>
-    invoke<Game>("apply_rawg_metadata", { gameId, rawgId, rename }),
+  apply: (
+    gameId: string,
+    rawgId: number,
+    rename: boolean,
+    overwrite?: MetadataOverwrite,
+  ) =>
+    invoke<Game>("apply_rawg_metadata", { gameId, rawgId, rename, overwrite }),
   setApiKey: (key: string) => invoke<void>("set_rawg_api_key", { key }),
   getApiKey: () => invoke<string>("get_rawg_api_key"),
+  suggestMatches: (gameId: string) =>
+    invoke<MatchSuggestions>("suggest_metadata_matches", { gameId }),
+  importBundle: (path: string) =>
+    invoke<MetadataBundleSummary>("import_metadata_bundle", { path }),
+};
+
+// Metadata API (IGDB, signed in through Twitch)
+export function isMetadataError(e: unknown): e is MetadataError {
+  return typeof e === "object" && e !== null && "kind" in e;
+}
+
+// A user-facing explanation of a failed metadata command from `provider`.
+export function describeMetadataError(e: unknown, provider: string): string {
+  if (!isMetadataError(e)) return String(e);
+  switch (e.kind) {
+    case "rate_limited":
+      return e.retry_after == null
+        ? `${provider} ограничил число запросов. Повторите позже.`
+        : `${provider} ограничил число запросов. Повторите через ${e.retry_after} с.`;
+    case "unauthorized":
+      return `${provider} не принял ключ. Проверьте его в настройках.`;
+    case "not_found":
+      return `${provider}: запись не найдена.`;
+    case "network":
+      return `${provider} недоступен. Проверьте подключение к сети.`;
+    default:
+      return e.message;
+  }
+}
+
+export const igdbApi = {
+  search: (query: string) => invoke<IgdbGame[]>("search_igdb", { query }),
+  getDetails: (igdbId: number) =>
+    invoke<IgdbGameDetails>("get_igdb_game_details", { igdbId }),
+  apply: (
+    gameId: string,
+    igdbId: number,
+    rename: boolean,
+    overwrite?: MetadataOverwrite,
+  ) =>
+    invoke<Game>("apply_igdb_metadata", { gameId, igdbId, rename, overwrite }),
+  setCredentials: (clientId: string, clientSecret: string) =>
+    invoke<void>("set_igdb_credentials", { clientId, clientSecret }),
+  getCredentials: () => invoke<IgdbCredentials>("get_igdb_credentials"),
 };
 
+export const pcgamingwikiApi = {
+  getLocations: (gameName: string) =>
+    invoke<PcgwLocations | null>("get_pcgamingwiki_locations", { gameName }),
+  getSavePaths: (gameId: string) =>
+    invoke<string[]>("get_pcgamingwiki_save_paths", { gameId }),
+  setSavePaths: (gameId: string, paths: string[]) =>
+    invoke<string[]>("set_pcgamingwiki_save_paths", { gameId, paths }),
+};
+
+// ProtonDB tier and Steam Deck status by Steam app id
+export const compatibilityApi = {
+  refresh: (gameId: string, appId?: string) =>
+    invoke<Game>("refresh_game_compatibility", { gameId, appId }),
+};
+
+export function isBackupError(e: unknown): e is BackupError {
+  return typeof e === "object" && e !== null && "kind" in e;
+}
+
+// A user-facing explanation of a failed backup or restore.
+export function describeBackupError(e: unknown): string {
+  if (!isBackupError(e)) return String(e);
+  if (e.kind === "no_space") {
+    const required = Math.ceil(e.required_bytes / 1024 / 1024);
+    const available = Math.floor(e.available_bytes / 1024 / 1024);
+    return `Недостаточно места на диске ${e.mount_point}: требуется ${required} МБ, свободно ${available} МБ`;
+  }
+  return e.message;
+}
+
 // Backup API
 export const backupApi = {
   checkLudusaviInstalled: () => invoke<boolean>("check_ludusavi_installed"),
@@ -60,15 +354,102 @@ export const backupApi = {
     invoke<void>("set_backup_directory", { path }),
   getBackupDirectory: () => invoke<string>("get_backup_directory_setting"),
   refreshSqobaManifest: () => invoke<void>("refresh_sqoba_manifest"),
+  getManifestInfo: () => invoke<ManifestInfo>("get_manifest_info"),
+  refreshManifest: (force?: boolean) =>
+    invoke<ManifestInfo>("refresh_manifest", { force }),
   findGameSavePaths: (gameName: string, gameId?: string) =>
     invoke<SavePathLookup>("find_game_save_paths", { gameName, gameId }),
+  setBackupEnabledBulk: (gameIds: string[], enabled: boolean, force?: boolean) =>
+    invoke<BulkBackupToggle>("set_backup_enabled_bulk", {
+      gameIds,
+      enabled,
+      force,
+    }),
+  suggestSavePaths: (gameId: string) =>
+    invoke<SaveCandidate[]>("suggest_save_paths", { gameId }),
   findGameSaves: (gameName: string, gameId?: string) =>
     invoke<BackupInfo | null>("find_game_saves", { gameName, gameId }),
-  create: (gameId: string, gameName: string, isAuto: boolean, notes?: string) =>
-    invoke<Backup>("create_backup", { gameId, gameName, isAuto, notes }),
+  getSavePaths: (gameId: string) =>
+    invoke<GameSavePath[]>("get_game_save_paths", { gameId }),
+  addSavePath: (
+    gameId: string,
+    path: string,
+    label?: string,
+    exclude?: boolean,
+  ) =>
+    invoke<GameSavePath>("add_game_save_path", {
+      gameId,
+      path,
+      label,
+      exclude,
+    }),
+  updateSavePath: (
+    id: string,
+    label?: string,
+    enabled?: boolean,
+    exclude?: boolean,
+  ) =>
+    invoke<void>("update_game_save_path", { id, label, enabled, exclude }),
+  removeSavePath: (id: string) =>
+    invoke<void>("remove_game_save_path", { id }),
+  getManifestMatch: (gameId: string) =>
+    invoke<ManifestMatch>("get_manifest_match", { gameId }),
+  confirmManifestMatch: (gameId: string, manifestKey: string | null) =>
+    invoke<ManifestMatch>("confirm_manifest_match", { gameId, manifestKey }),
+  getManifestOverride: (gameId: string) =>
+    invoke<ManifestOverride | null>("get_manifest_override", { gameId }),
+  setManifestOverride: (gameId: string, fragment: ManifestOverride | null) =>
+    invoke<ManifestOverride | null>("set_manifest_override", {
+      gameId,
+      fragment,
+    }),
+  create: (
+    gameId: string,
+    gameName: string,
+    isAuto: boolean,
+    notes?: string,
+    label?: string,
+  ) =>
+    invoke<Backup>("create_backup", {
+      gameId,
+      gameName,
+      isAuto,
+      notes,
+      label,
+    }),
+  getHistory: (gameId: string) =>
+    invoke<BackupEvent[]>("get_backup_history", { gameId }),
   getForGame: (gameId: string) =>
     invoke<Backup[]>("get_game_backups", { gameId }),
-  restore: (backupId: string) => invoke<void>("restore_backup", { backupId }),
+  restore: (
+    backupId: string,
+    pathMappings?: Record<string, string>,
+    overwriteNewer?: boolean,
+  ) =>
+    invoke<void>("restore_backup", { backupId, pathMappings, overwriteNewer }),
+  previewRestore: (backupId: string, pathMappings?: Record<string, string>) =>
+    invoke<RestorePreview>("preview_restore", { backupId, pathMappings }),
+  planRestorePaths: (backupId: string, pathMappings?: Record<string, string>) =>
+    invoke<RestoreRootMapping[]>("plan_restore_paths", {
+      backupId,
+      pathMappings,
+    }),
+  repair: (backupId: string) =>
+    invoke<ManifestRepair>("repair_backup", { backupId }),
+  getStats: () => invoke<BackupStats>("get_backup_stats"),
+  diff: (backupA: string, backupB: string) =>
+    invoke<BackupDiff>("diff_backups", { backupA, backupB }),
+  getDiagnostics: () => invoke<BackupDiagnostics>("get_backup_diagnostics"),
+  resolveStalled: (operationId: string, action: StallAction) =>
+    invoke<void>("resolve_stalled_operation", { operationId, action }),
+  getInstallArchive: (gameId: string) =>
+    invoke<InstallArchive | null>("get_install_archive", { gameId }),
+  archiveInstall: (gameId: string, installDir?: string) =>
+    invoke<InstallArchive>("archive_game_install", { gameId, installDir }),
+  unarchiveInstall: (gameId: string, targetDir?: string) =>
+    invoke<string>("unarchive_game_install", { gameId, targetDir }),
+  benchmarkCompression: (gameId: string) =>
+    invoke<CompressionBenchmark[]>("benchmark_compression", { gameId }),
   delete: (backupId: string) => invoke<void>("delete_backup", { backupId }),
   shouldBackupBeforeLaunch: (gameId: string) =>
     invoke<boolean>("should_backup_before_launch", { gameId }),
@@ -92,6 +473,12 @@ export const settingsApi = {
   addScanDirectory: (path: string) =>
     invoke<void>("add_scan_directory", { path }),
   getScanDirectories: () => invoke<string[]>("get_scan_directories"),
+  listScanDirectories: () =>
+    invoke<ScanDirectory[]>("list_scan_directories"),
+  setScanDirectoryAutoScan: (path: string, enabled: boolean) =>
+    invoke<void>("set_scan_directory_auto_scan", { path, enabled }),
+  setScanDirectoryThreads: (path: string, threads: number | null) =>
+    invoke<void>("set_scan_directory_threads", { path, threads }),
   removeScanDirectory: (path: string) =>
     invoke<void>("remove_scan_directory", { path }),
 };
@@ -99,6 +486,36 @@ export const settingsApi = {
 export const statsApi = {
   getPlaytimeStats: (start?: string, end?: string) =>
     invoke<PlaytimeStats>("get_playtime_stats", { start, end }),
+  generateStatsCard: (path: string, range?: StatsRange) =>
+    invoke<StatsCard>("generate_stats_card", { range, path }),
+  getLibraryOverview: () => invoke<LibraryOverview>("get_library_overview"),
+};
+
+export const sessionsApi = {
+  setNote: (sessionId: number, note: string | null) =>
+    invoke<PlaySession>("set_session_note", { sessionId, note }),
+  searchNotes: (query: string, gameId?: string) =>
+    invoke<PlaySession[]>("search_session_notes", { query, gameId }),
+  getLaunchHistory: (gameId: string) =>
+    invoke<LaunchRecord[]>("get_launch_history", { gameId }),
+  getGameSessions: (gameId: string, range?: StatsRange) =>
+    invoke<PlaySession[]>("get_game_sessions", { gameId, range }),
+  getRecent: () => invoke<PlaySession[]>("get_recent_sessions"),
+};
+
+// Dated notes and external links per game
+export const notesApi = {
+  list: (gameId: string) => invoke<GameNote[]>("get_game_notes", { gameId }),
+  add: (gameId: string, body: string) =>
+    invoke<GameNote>("add_game_note", { gameId, body }),
+  update: (id: string, body: string) =>
+    invoke<GameNote>("update_game_note", { id, body }),
+  delete: (id: string) => invoke<void>("delete_game_note", { id }),
+  listLinks: (gameId: string) =>
+    invoke<GameLink[]>("get_game_links", { gameId }),
+  addLink: (gameId: string, url: string, title?: string) =>
+    invoke<GameLink>("add_game_link", { gameId, url, title }),
+  deleteLink: (id: string) => invoke<void>("delete_game_link", { id }),
 };
 
 export const scanApi = {
@@ -109,4 +526,24 @@ export const systemApi = {
   getInfo: () => invoke<SystemInfo>("get_system_info"),
   testDiskSpeed: (mountPoint: string) =>
     invoke<DiskSpeedResult>("test_disk_speed", { mountPoint }),
+  getAppInfo: () => invoke<AppInfo>("get_app_info"),
+  getDatabaseStatus: () => invoke<DatabaseStatus>("get_database_status"),
+  retryDatabaseOpen: () => invoke<DatabaseStatus>("retry_database_open"),
+};
+
+// Executables and scripts in the plugins folder, run on app events
+export const pluginsApi = {
+  list: () => invoke<PluginInfo[]>("list_plugins"),
+  setEnabled: (id: string, enabled: boolean) =>
+    invoke<void>("set_plugin_enabled", { id, enabled }),
+  getDirectory: () => invoke<string>("get_plugins_directory"),
+};
+
+// E2E-only backend hooks; rejected unless the app runs with ARRANCADOR_TEST_MODE=1
+export const testModeApi = {
+  resetDb: () => invoke<void>("__test_reset_db"),
+  seedLibrary: (fixture: LibraryFixture) =>
+    invoke<string[]>("__test_seed_library", { fixture }),
+  advanceClock: (seconds: number) =>
+    invoke<string>("__test_advance_clock", { seconds }),
 };
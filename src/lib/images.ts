@@ -0,0 +1,6 @@
+import { convertFileSrc } from "@tauri-apps/api/core";
+
+// Covers are either remote URLs or files in the local image store, which the webview can only
+// load through the asset protocol.
+export const imageSrc = (path: string) =>
+  /^(https?|data|blob|asset):/i.test(path) ? path : convertFileSrc(path);
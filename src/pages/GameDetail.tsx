@@ -3,10 +3,14 @@ import { open } from "@tauri-apps/plugin-dialog";
 import { openPath } from "@tauri-apps/plugin-opener";
 import {
   Activity,
+  Archive,
+  ArchiveRestore,
   ArrowLeft,
   Clock,
   Download,
   ExternalLink,
+  Eye,
+  EyeOff,
   File as FileIcon,
   FolderOpen,
   Gamepad2,
@@ -14,6 +18,7 @@ import {
   HardDrive,
   Image as ImageIcon,
   Loader2,
+  Lock,
   Pencil,
   Play,
   Save,
@@ -23,21 +28,98 @@ import {
   Star,
   Timer,
   Trash2,
+  TriangleAlert,
   Upload,
   X,
 } from "lucide-react";
 import { useEffect, useState } from "react";
 import { Link, useNavigate, useParams } from "react-router-dom";
+import { CompanionEditor } from "@/components/CompanionEditor";
+import { CompatibilityInfo } from "@/components/CompatibilityInfo";
+import { CoverEditor } from "@/components/CoverEditor";
+import { ExtraSavePaths } from "@/components/ExtraSavePaths";
+import { FileVerifier } from "@/components/FileVerifier";
+import { GameNotes } from "@/components/GameNotes";
+import { LaunchOptionsEditor } from "@/components/LaunchOptionsEditor";
+import { ManifestMatchPicker } from "@/components/ManifestMatchPicker";
+import { ManifestOverrideEditor } from "@/components/ManifestOverrideEditor";
+import { PcgwSaveLocations } from "@/components/PcgwSaveLocations";
+import { PlaytimeEditor } from "@/components/PlaytimeEditor";
+import { RelocateGame } from "@/components/RelocateGame";
+import { ShortcutCreator } from "@/components/ShortcutCreator";
+import { TagEditor } from "@/components/TagEditor";
 import { useToast } from "@/components/ToastProvider";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { Switch } from "@/components/ui/switch";
 import { useGameStatus } from "@/hooks/useGameStatus";
-import { backupApi, gamesApi, metadataApi } from "@/lib/api";
+import {
+  ALREADY_RUNNING_ERROR,
+  backupApi,
+  describeBackupError,
+  describeMetadataError,
+  gamesApi,
+  igdbApi,
+  metadataApi,
+} from "@/lib/api";
 import { cn } from "@/lib/utils";
 import { useGamesActions, useGamesState } from "@/store/GamesContext";
-import type { Backup, Game, RawgGame, RestoreCheck } from "@/types";
+import type {
+  Backup,
+  Game,
+  InstallArchive,
+  RestorePreview,
+  SaveCandidate,
+  LaunchProfile,
+  MetadataField,
+  MetadataOverwrite,
+  MetadataSource,
+  RestoreCheck,
+  SaveChangedEvent,
+} from "@/types";
+
+const METADATA_SOURCE_LABELS: Record<MetadataSource, string> = {
+  rawg: "RAWG",
+  igdb: "IGDB",
+};
+
+// Fields the user can protect from being replaced; protected ones are only filled when empty.
+const METADATA_FIELD_LABELS: Record<keyof MetadataOverwrite, string> = {
+  description: "Описание",
+  released: "Дата выхода",
+  background_image: "Фон",
+  scores: "Оценки",
+  genres: "Жанры",
+  platforms: "Платформы",
+  developers: "Разработчики",
+  publishers: "Издатели",
+};
+
+const LOCKABLE_FIELD_LABELS: Record<MetadataField, string> = {
+  name: "Название",
+  ...METADATA_FIELD_LABELS,
+};
+
+const OVERWRITE_ALL: MetadataOverwrite = {
+  description: true,
+  released: true,
+  background_image: true,
+  scores: true,
+  genres: true,
+  platforms: true,
+  developers: true,
+  publishers: true,
+};
+
+// Search result from either provider, reduced to what the picker shows.
+type MetadataCandidate = {
+  id: number;
+  name: string;
+  image: string | null;
+  released: string | null;
+  score: number | null;
+};
 
 type BackupProgressPayload = {
   game_id: string;
@@ -55,11 +137,71 @@ function formatPlaytime(seconds: number) {
   return `${minutes} мин`;
 }
 
+/** "Наиграно 12 ч 5 мин · сессия 40 мин" from what was captured with the backup. */
+function formatBackupContext(backup: Backup) {
+  const metadata = backup.metadata;
+  if (!metadata) return null;
+  const parts = [`Наиграно ${formatPlaytime(metadata.playtime_seconds)}`];
+  if (metadata.last_session_seconds) {
+    parts.push(`сессия ${formatPlaytime(metadata.last_session_seconds)}`);
+  }
+  return parts.join(" · ");
+}
+
+function formatBytes(bytes: number) {
+  if (!bytes || bytes <= 0) return "0 Б";
+  const units = ["Б", "КБ", "МБ", "ГБ", "ТБ"];
+  let size = bytes;
+  let unit = 0;
+  while (size >= 1024 && unit < units.length - 1) {
+    size /= 1024;
+    unit += 1;
+  }
+  const digits = size >= 10 ? 0 : 1;
+  return `${size.toFixed(digits)} ${units[unit]}`;
+}
+
+function formatRestoreEstimate(preview: RestorePreview) {
+  const seconds = preview.estimated_seconds;
+  const time =
+    seconds < 60 ? `${Math.max(seconds, 1)} сек` : formatPlaytime(seconds);
+  return `Примерное время восстановления: ${time}.`;
+}
+
+/** Asks before a restore; newer live saves always need an explicit yes. */
+async function confirmRestore(backupId: string, alwaysAsk: boolean) {
+  let preview: RestorePreview | null = null;
+  try {
+    preview = await backupApi.previewRestore(backupId);
+  } catch (e) {
+    console.error("Failed to preview restore:", e);
+  }
+  const estimate = preview ? ` ${formatRestoreEstimate(preview)}` : "";
+  const newer = preview?.newer_files ?? [];
+  if (newer.length === 0) {
+    return (
+      !alwaysAsk ||
+      confirm(
+        `Восстановить бэкап? Текущие сохранения будут перезаписаны.${estimate}`,
+      )
+    );
+  }
+  const listed = newer
+    .slice(0, 5)
+    .map((file) => file.path)
+    .join("\n");
+  const more = newer.length > 5 ? `\n…и ещё ${newer.length - 5}` : "";
+  return confirm(
+    `Эти сохранения новее бэкапа и будут перезаписаны:\n${listed}${more}\n\n${estimate.trim()}\nВсё равно восстановить?`,
+  );
+}
+
 export default function GameDetail() {
   const { id } = useParams<{ id: string }>();
   const navigate = useNavigate();
   const { games } = useGamesState();
-  const { toggleFavorite, deleteGame, refreshGames } = useGamesActions();
+  const { toggleFavorite, setHidden, deleteGame, refreshGames } =
+    useGamesActions();
 
   const [game, setGame] = useState<Game | null>(null);
 
@@ -77,22 +219,36 @@ export default function GameDetail() {
   // Metadata search
   const [showMetadataSearch, setShowMetadataSearch] = useState(false);
   const [metadataQuery, setMetadataQuery] = useState("");
-  const [metadataResults, setMetadataResults] = useState<RawgGame[]>([]);
+  const [metadataSource, setMetadataSource] = useState<MetadataSource>("rawg");
+  const [metadataResults, setMetadataResults] = useState<MetadataCandidate[]>(
+    [],
+  );
   const [searchingMetadata, setSearchingMetadata] = useState(false);
   const [applyingMetadata, setApplyingMetadata] = useState(false);
 
   const [renameFromMetadata, setRenameFromMetadata] = useState(false);
+  const [metadataOverwrite, setMetadataOverwrite] =
+    useState<MetadataOverwrite>(OVERWRITE_ALL);
 
   // Backups
   const [backups, setBackups] = useState<Backup[]>([]);
+  const [lastSaveChange, setLastSaveChange] = useState<SaveChangedEvent | null>(
+    null,
+  );
   const [loadingBackups, setLoadingBackups] = useState(false);
   const [, setCreatingBackup] = useState(false);
   const [showBackupPrompt, setShowBackupPrompt] = useState(false);
+  const [showDeleteDialog, setShowDeleteDialog] = useState(false);
+  const [deleteBackups, setDeleteBackups] = useState(false);
+  const [deleteStats, setDeleteStats] = useState(false);
+  const [deleting, setDeleting] = useState(false);
   const [savePathDraft, setSavePathDraft] = useState("");
   const [savingSavePath, setSavingSavePath] = useState(false);
   const [locatingSavePath, setLocatingSavePath] = useState(false);
+  const [saveSuggestions, setSaveSuggestions] = useState<SaveCandidate[]>([]);
 
   const [showAllBackups, setShowAllBackups] = useState(false);
+  const [backupLabel, setBackupLabel] = useState("");
   const [showRestorePrompt, setShowRestorePrompt] = useState(false);
   const [restoreInfo, setRestoreInfo] = useState<RestoreCheck | null>(null);
   const [restoring, setRestoring] = useState(false);
@@ -117,6 +273,12 @@ export default function GameDetail() {
   const [showRatingModal, setShowRatingModal] = useState(false);
   const [ratingDraft, setRatingDraft] = useState(4);
   const [showGameSettings, setShowGameSettings] = useState(false);
+  const [launchProfiles, setLaunchProfiles] = useState<LaunchProfile[]>([]);
+  const [launchProfileId, setLaunchProfileId] = useState("");
+  const [installArchive, setInstallArchive] = useState<InstallArchive | null>(
+    null,
+  );
+  const [archiving, setArchiving] = useState(false);
 
   const { notify } = useToast();
 
@@ -173,7 +335,9 @@ export default function GameDetail() {
 
   const ratingGlowTone = getRatingTone(displayRating);
   const isRunning = runningCount > 0;
-  const isMissing = !isInstalled && !checkingInstalled;
+  // An archived install keeps its exe path, so the archive record decides on its own.
+  const isMissing =
+    (!isInstalled && !checkingInstalled) || installArchive !== null;
   const playState = isMissing ? "missing" : isRunning ? "running" : "ready";
   const playLabel = isMissing
     ? "Не установлена"
@@ -210,10 +374,39 @@ export default function GameDetail() {
     }
   }, [game?.id]);
 
+  useEffect(() => {
+    if (!game) return;
+    setLaunchProfileId("");
+    gamesApi
+      .getLaunchProfiles(game.id)
+      .then((profiles) => {
+        setLaunchProfiles(profiles);
+        setLaunchProfileId(profiles.find((p) => p.is_default)?.id ?? "");
+      })
+      .catch((e) => {
+        console.error("Failed to load launch profiles:", e);
+        setLaunchProfiles([]);
+      });
+  }, [game?.id]);
+
+  useEffect(() => {
+    if (!game) return;
+    backupApi
+      .getInstallArchive(game.id)
+      .then(setInstallArchive)
+      .catch((e) => {
+        console.error("Failed to load install archive:", e);
+        setInstallArchive(null);
+      });
+  }, [game?.id]);
+
   useEffect(() => {
     if (!game) return;
     let unlistenBackup: UnlistenFn | null = null;
     let unlistenRestore: UnlistenFn | null = null;
+    let unlistenInstall: UnlistenFn | null = null;
+    let unlistenSave: UnlistenFn | null = null;
+    setLastSaveChange(null);
     const setup = async () => {
       unlistenBackup = await listen<BackupProgressPayload>(
         "backup:progress",
@@ -221,6 +414,24 @@ export default function GameDetail() {
           const payload = event.payload;
           if (payload.game_id !== game.id) return;
           const { stage, message, done, total } = payload;
+          if (stage === "skipped") {
+            notify({
+              tone: "warning",
+              title: "Файл пропущен",
+              description: `Файл занят другим процессом и не попал в бэкап: ${message}`,
+            });
+            return;
+          }
+          // Reported from the command's `no_space` error instead.
+          if (stage === "no-space") return;
+          if (stage === "running") {
+            notify({
+              tone: "warning",
+              title: "Игра запущена",
+              description: message,
+            });
+            return;
+          }
           if (stage === "done") {
             setBackupProgress({ active: false, stage, message, done, total });
           } else {
@@ -234,6 +445,8 @@ export default function GameDetail() {
           const payload = event.payload;
           if (payload.game_id !== game.id) return;
           const { stage, message, done, total } = payload;
+          // Reported from the command's `no_space` error instead.
+          if (stage === "no-space") return;
           if (stage === "done") {
             setBackupProgress({ active: false, stage, message, done, total });
           } else {
@@ -241,11 +454,32 @@ export default function GameDetail() {
           }
         },
       );
+      unlistenInstall = await listen<BackupProgressPayload>(
+        "install-archive:progress",
+        (event) => {
+          const payload = event.payload;
+          if (payload.game_id !== game.id) return;
+          const { stage, message, done, total } = payload;
+          setBackupProgress({
+            active: stage !== "done",
+            stage,
+            message,
+            done,
+            total,
+          });
+        },
+      );
+      unlistenSave = await listen<SaveChangedEvent>("save:changed", (event) => {
+        if (event.payload.game_id !== game.id) return;
+        setLastSaveChange(event.payload);
+      });
     };
     setup();
     return () => {
       if (unlistenBackup) unlistenBackup();
       if (unlistenRestore) unlistenRestore();
+      if (unlistenInstall) unlistenInstall();
+      if (unlistenSave) unlistenSave();
     };
   }, [game?.id]);
 
@@ -320,29 +554,57 @@ export default function GameDetail() {
     if (!game) return;
     try {
       // Launch and track time
-      await gamesApi.launch(game.id);
+      const result = await gamesApi.launch(
+        game.id,
+        launchProfileId || undefined,
+      );
+      if (result === "already_running") {
+        notify({
+          tone: "info",
+          title: "Игра уже запущена",
+          description: "Не удалось найти её окно",
+        });
+      }
       const count = await gamesApi.getRunningInstances(game.id);
       setRunningCount(count);
       await refreshGames();
     } catch (e) {
       console.error("Failed to launch:", e);
+      if (String(e) === ALREADY_RUNNING_ERROR) {
+        notify({ tone: "info", title: ALREADY_RUNNING_ERROR });
+      } else if (launchProfileId) {
+        alert("Не удалось запустить профиль: " + String(e));
+      }
     } finally {
       setLaunching(false);
     }
   };
 
-  const startBackupInBackground = (isAuto: boolean) => {
+  const defaultProfile = launchProfiles.find((p) => p.is_default);
+
+  const makeDefaultProfile = async () => {
+    const profile = launchProfiles.find((p) => p.id === launchProfileId);
+    if (!profile) return;
+    try {
+      await gamesApi.saveLaunchProfile({ ...profile, is_default: true });
+      setLaunchProfiles(await gamesApi.getLaunchProfiles(profile.game_id));
+    } catch (e) {
+      alert("Не удалось сохранить профиль: " + String(e));
+    }
+  };
+
+  const startBackupInBackground = (isAuto: boolean, label?: string) => {
     if (!game) return;
     setCreatingBackup(true);
     backupApi
-      .create(game.id, game.name, isAuto)
+      .create(game.id, game.name, isAuto, undefined, label)
       .then(async () => {
         await loadBackups();
         await refreshGames();
       })
       .catch((e) => {
         console.error("Backup failed:", e);
-        alert("Ошибка бэкапа: " + (e as Error).message);
+        alert("Ошибка бэкапа: " + describeBackupError(e));
       })
       .finally(() => {
         setCreatingBackup(false);
@@ -363,11 +625,13 @@ export default function GameDetail() {
     setRestoreInfo(null);
     setRestoring(true);
     try {
-      await backupApi.restore(restoreInfo.backup_id);
+      if (await confirmRestore(restoreInfo.backup_id, false)) {
+        await backupApi.restore(restoreInfo.backup_id, undefined, true);
+      }
       await refreshGames();
     } catch (e) {
       console.error("Restore failed:", e);
-      alert("Ошибка восстановления: " + (e as Error).message);
+      alert("Ошибка восстановления: " + describeBackupError(e));
     } finally {
       setRestoring(false);
     }
@@ -387,11 +651,77 @@ export default function GameDetail() {
     await toggleFavorite(game.id);
   };
 
+  const handleToggleHidden = async () => {
+    if (!game) return;
+    try {
+      await setHidden(game.id, !game.is_hidden);
+    } catch (e) {
+      console.error("Failed to update hidden flag:", e);
+    }
+  };
+
   const handleDelete = async () => {
     if (!game) return;
-    if (confirm(`Удалить "${game.name}" из библиотеки?`)) {
-      await deleteGame(game.id);
+    setDeleting(true);
+    try {
+      await deleteGame(game.id, {
+        delete_backups: deleteBackups,
+        delete_stats: deleteStats,
+      });
       navigate("/");
+    } catch (e) {
+      console.error("Failed to delete game:", e);
+      notify({
+        tone: "error",
+        title: "Не удалось удалить игру",
+        description: String(e),
+      });
+    } finally {
+      setDeleting(false);
+      setShowDeleteDialog(false);
+    }
+  };
+
+  const handleArchiveInstall = async () => {
+    if (!game) return;
+    if (
+      !confirm(
+        `Упаковать папку "${game.name}" в архив на диске бэкапов? После проверки архива исходные файлы игры будут удалены.`,
+      )
+    ) {
+      return;
+    }
+    setArchiving(true);
+    try {
+      const archive = await backupApi.archiveInstall(game.id);
+      setInstallArchive(archive);
+      await refreshGames();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось архивировать игру",
+        description: String(e),
+      });
+    } finally {
+      setArchiving(false);
+    }
+  };
+
+  const handleUnarchiveInstall = async () => {
+    if (!game || !installArchive) return;
+    setArchiving(true);
+    try {
+      await backupApi.unarchiveInstall(game.id);
+      setInstallArchive(null);
+      await refreshGames();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось распаковать игру",
+        description: String(e),
+      });
+    } finally {
+      setArchiving(false);
     }
   };
 
@@ -424,26 +754,132 @@ export default function GameDetail() {
     if (!metadataQuery.trim()) return;
     setSearchingMetadata(true);
     try {
-      const results = await metadataApi.search(metadataQuery);
+      const results: MetadataCandidate[] =
+        metadataSource === "igdb"
+          ? (await igdbApi.search(metadataQuery)).map((result) => ({
+              id: result.id,
+              name: result.name,
+              image: result.cover_image,
+              released: result.released,
+              score:
+                result.aggregated_rating == null
+                  ? null
+                  : Math.round(result.aggregated_rating),
+            }))
+          : (await metadataApi.search(metadataQuery)).map((result) => ({
+              id: result.id,
+              name: result.name,
+              image: result.background_image,
+              released: result.released,
+              score: result.metacritic,
+            }));
       setMetadataResults(results);
     } catch (e) {
       console.error("Metadata search failed:", e);
+      notify({
+        tone: "error",
+        title: "Не удалось найти метаданные",
+        description: describeMetadataError(
+          e,
+          METADATA_SOURCE_LABELS[metadataSource],
+        ),
+      });
     } finally {
       setSearchingMetadata(false);
     }
   };
 
-  const applyMetadata = async (rawgGame: RawgGame) => {
+  // RAWG searches start from the cleaned-up name with ranked results already loaded.
+  const openMetadataSearch = async () => {
+    if (!game) return;
+    const source = game.metadata_source ?? "rawg";
+    setMetadataQuery(game.name);
+    setMetadataSource(source);
+    setMetadataResults([]);
+    setShowMetadataSearch(true);
+    if (source !== "rawg") return;
+    setSearchingMetadata(true);
+    try {
+      const suggestions = await metadataApi.suggestMatches(game.id);
+      setMetadataQuery(suggestions.query);
+      setMetadataResults(
+        suggestions.candidates.map((result) => ({
+          id: result.id,
+          name: result.name,
+          image: result.background_image,
+          released: result.released,
+          score: result.metacritic,
+        })),
+      );
+    } catch (e) {
+      console.error("Metadata suggestions failed:", e);
+    } finally {
+      setSearchingMetadata(false);
+    }
+  };
+
+  // Locked fields are skipped by every metadata apply, including automatic matching.
+  const toggleLockedField = async (field: MetadataField) => {
+    if (!game) return;
+    const fields = game.locked_fields.includes(field)
+      ? game.locked_fields.filter((locked) => locked !== field)
+      : [...game.locked_fields, field];
+    try {
+      setGame(await gamesApi.setLockedFields(game.id, fields));
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось закрепить поле",
+        description: String(e),
+      });
+    }
+  };
+
+  // The chosen provider is remembered per game.
+  const changeMetadataSource = async (source: MetadataSource) => {
+    if (!game || source === metadataSource) return;
+    setMetadataSource(source);
+    setMetadataResults([]);
+    try {
+      await gamesApi.setMetadataSource(game.id, source);
+    } catch (e) {
+      console.error("Failed to save metadata source:", e);
+    }
+  };
+
+  const applyMetadata = async (candidate: MetadataCandidate) => {
     if (!game) return;
     setApplyingMetadata(true);
     try {
-      await metadataApi.apply(game.id, rawgGame.id, renameFromMetadata);
+      if (metadataSource === "igdb") {
+        await igdbApi.apply(
+          game.id,
+          candidate.id,
+          renameFromMetadata,
+          metadataOverwrite,
+        );
+      } else {
+        await metadataApi.apply(
+          game.id,
+          candidate.id,
+          renameFromMetadata,
+          metadataOverwrite,
+        );
+      }
       await refreshGames();
       setShowMetadataSearch(false);
       setMetadataResults([]);
       setMetadataQuery("");
     } catch (e) {
       console.error("Failed to apply metadata:", e);
+      notify({
+        tone: "error",
+        title: "Не удалось применить метаданные",
+        description: describeMetadataError(
+          e,
+          METADATA_SOURCE_LABELS[metadataSource],
+        ),
+      });
     } finally {
       setApplyingMetadata(false);
     }
@@ -451,21 +887,18 @@ export default function GameDetail() {
 
   const createManualBackup = async () => {
     if (!game) return;
-    startBackupInBackground(false);
+    startBackupInBackground(false, backupLabel.trim() || undefined);
+    setBackupLabel("");
   };
 
   const restoreBackup = async (backupId: string, withConfirm = true) => {
-    if (
-      withConfirm &&
-      !confirm("Восстановить бэкап? Текущие сохранения будут перезаписаны.")
-    )
-      return;
+    if (!(await confirmRestore(backupId, withConfirm))) return;
     try {
-      await backupApi.restore(backupId);
+      await backupApi.restore(backupId, undefined, true);
       alert("Бэкап успешно восстановлен!");
     } catch (e) {
       console.error("Restore failed:", e);
-      alert("Ошибка восстановления: " + (e as Error).message);
+      alert("Ошибка восстановления: " + describeBackupError(e));
     }
   };
 
@@ -484,6 +917,29 @@ export default function GameDetail() {
     }
   };
 
+  const updateSyncOptions = async (options: {
+    restoreOnLaunch?: boolean;
+    backupOnExit?: boolean;
+  }) => {
+    if (!game) return;
+    try {
+      await gamesApi.setSyncOptions(game.id, options);
+      await refreshGames();
+    } catch (e) {
+      console.error("Failed to update sync options:", e);
+    }
+  };
+
+  const toggleDoNotTrack = async (doNotTrack: boolean) => {
+    if (!game) return;
+    try {
+      await gamesApi.setDoNotTrack(game.id, doNotTrack);
+      await refreshGames();
+    } catch (e) {
+      console.error("Failed to update tracking:", e);
+    }
+  };
+
   const handleSelectSavePath = async () => {
     const selected = await open({
       directory: true,
@@ -542,18 +998,9 @@ export default function GameDetail() {
     if (!game) return;
     setLocatingSavePath(true);
     try {
-      const info = await backupApi.findGameSavePaths(game.name, game.id);
-      if (info.save_path) {
-        setSavePathDraft(info.save_path);
-        if (info.candidates.length > 1) {
-          notify({
-            tone: "info",
-            title: "Найдено несколько вариантов",
-            description:
-              "Проверьте, что выбранный путь действительно содержит сохранения.",
-          });
-        }
-      } else {
+      const candidates = await backupApi.suggestSavePaths(game.id);
+      setSaveSuggestions(candidates);
+      if (candidates.length === 0) {
         notify({
           tone: "warning",
           title: "Сохранения не найдены",
@@ -614,6 +1061,7 @@ export default function GameDetail() {
         save_path: normalizedPath === "" ? null : normalizedPath,
       });
       setSavePathDraft(normalizedPath);
+      setSaveSuggestions([]);
       await refreshGames();
     } catch (e) {
       console.error("Failed to update save path:", e);
@@ -753,12 +1201,24 @@ export default function GameDetail() {
           <Button
             variant="secondary"
             size="icon"
-            onClick={() => {
-              setMetadataQuery(game.name);
-              setShowMetadataSearch(true);
-            }}
+            onClick={handleToggleHidden}
+            className="bg-background/60 backdrop-blur-md border border-white/10"
+            title={
+              game.is_hidden ? "Показать в библиотеке" : "Скрыть из библиотеки"
+            }
+          >
+            {game.is_hidden ? (
+              <Eye className="w-4 h-4" />
+            ) : (
+              <EyeOff className="w-4 h-4" />
+            )}
+          </Button>
+          <Button
+            variant="secondary"
+            size="icon"
+            onClick={() => void openMetadataSearch()}
             className="bg-background/60 backdrop-blur-md border border-white/10"
-            title="Найти метаданные (RAWG)"
+            title={`Найти метаданные (${METADATA_SOURCE_LABELS[game.metadata_source ?? "rawg"]})`}
           >
             <Search className="w-4 h-4" />
           </Button>
@@ -780,10 +1240,26 @@ export default function GameDetail() {
           >
             <Pencil className="w-4 h-4" />
           </Button>
+          {!isMissing && (
+            <Button
+              variant="secondary"
+              size="icon"
+              onClick={handleArchiveInstall}
+              disabled={archiving || isRunning || backupProgress.active}
+              className="bg-background/60 backdrop-blur-md border border-white/10"
+              title="Архивировать установку"
+            >
+              {archiving ? (
+                <Loader2 className="w-4 h-4 animate-spin" />
+              ) : (
+                <Archive className="w-4 h-4" />
+              )}
+            </Button>
+          )}
           <Button
             variant="secondary"
             size="icon"
-            onClick={handleDelete}
+            onClick={() => setShowDeleteDialog(true)}
             className="bg-background/60 backdrop-blur-md border border-white/10 hover:bg-destructive hover:text-destructive-foreground"
             title="Удалить"
           >
@@ -832,6 +1308,42 @@ export default function GameDetail() {
               )}
               {playLabel}
             </Button>
+            {game.last_launch_failed && !isMissing && !isRunning && (
+              <div className="flex items-center gap-1 text-xs text-amber-500">
+                <TriangleAlert className="w-3 h-3" />
+                {"Прошлый запуск завершился сбоем"}
+              </div>
+            )}
+            {launchProfiles.length > 0 && !isMissing && (
+              <select
+                value={launchProfileId}
+                onChange={(e) => setLaunchProfileId(e.target.value)}
+                disabled={isRunning || launching}
+                className="text-sm bg-transparent border-none focus:outline-none text-muted-foreground cursor-pointer"
+                aria-label="Профиль запуска"
+              >
+                {!defaultProfile && (
+                  <option value="">Обычный запуск</option>
+                )}
+                {launchProfiles.map((profile) => (
+                  <option key={profile.id} value={profile.id}>
+                    {profile.is_default
+                      ? `${profile.name} (по умолчанию)`
+                      : profile.name}
+                  </option>
+                ))}
+              </select>
+            )}
+            {launchProfileId && launchProfileId !== defaultProfile?.id && (
+              <button
+                type="button"
+                onClick={makeDefaultProfile}
+                className="flex items-center gap-1 text-xs text-muted-foreground hover:text-foreground"
+              >
+                <Star className="w-3 h-3" />
+                {"Сделать профилем по умолчанию"}
+              </button>
+            )}
             {isMissing && (
               <div className="max-w-[280px] text-xs text-muted-foreground text-right">
                 {
@@ -839,6 +1351,26 @@ export default function GameDetail() {
                 }
               </div>
             )}
+            {isMissing && !installArchive && (
+              <RelocateGame game={game} onRelocated={refreshGames} />
+            )}
+            {installArchive && (
+              <Button
+                variant="outline"
+                size="sm"
+                className="gap-2"
+                onClick={handleUnarchiveInstall}
+                disabled={archiving}
+                title={installArchive.archive_path}
+              >
+                {archiving ? (
+                  <Loader2 className="w-4 h-4 animate-spin" />
+                ) : (
+                  <ArchiveRestore className="w-4 h-4" />
+                )}
+                Распаковать из архива
+              </Button>
+            )}
           </div>
         </div>
 
@@ -901,6 +1433,20 @@ export default function GameDetail() {
               <Timer className="w-5 h-5" />
               {formatPlaytime(game.total_playtime)}
             </div>
+            <label
+              className="mt-3 flex items-center justify-between gap-2 text-xs text-muted-foreground"
+              title={"Не записывать время, сессии и последний запуск; бэкапы работают как обычно"}
+            >
+              <span className="flex items-center gap-1.5">
+                <EyeOff className="w-3.5 h-3.5" />
+                {"Не отслеживать"}
+              </span>
+              <Switch
+                checked={game.do_not_track}
+                onCheckedChange={toggleDoNotTrack}
+                aria-label="Не отслеживать"
+              />
+            </label>
           </div>
 
           <div className="rounded-2xl border border-border/60 bg-card/60 backdrop-blur-xl p-4 shadow-[0_12px_30px_rgba(8,12,24,0.35)]">
@@ -970,6 +1516,8 @@ export default function GameDetail() {
                 )}
               </div>
 
+              <CompatibilityInfo game={game} onUpdated={setGame} />
+
               <div className="mt-6 pt-4 border-t border-border/60 flex items-center gap-2 text-xs text-muted-foreground">
                 <FolderOpen className="w-3 h-3" />
                 <span className="font-mono truncate">{game.exe_path}</span>
@@ -1006,6 +1554,13 @@ export default function GameDetail() {
                   </Button>{" "}
                 </div>
               </div>
+              <Input
+                value={backupLabel}
+                onChange={(e) => setBackupLabel(e.target.value)}
+                onKeyDown={(e) => e.key === "Enter" && createManualBackup()}
+                placeholder="Метка для нового бэкапа"
+                className="h-8 mb-3 text-xs"
+              />
               <div className="rounded-xl border border-border/60 bg-secondary/40 p-3 mb-3 space-y-2">
                 <div className="flex items-center justify-between">
                   <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
@@ -1027,6 +1582,16 @@ export default function GameDetail() {
                     ? game.save_path
                     : "Путь к сохранениям не указан"}
                 </div>
+                {lastSaveChange && (
+                  <div
+                    className="text-[11px] text-muted-foreground truncate"
+                    title={lastSaveChange.paths.join("\n")}
+                  >
+                    {`Сохранение изменено в ${new Date(
+                      lastSaveChange.changed_at,
+                    ).toLocaleTimeString()}`}
+                  </div>
+                )}
                 <div className="flex items-center justify-between text-[11px] text-muted-foreground">
                   <span>{"Автобэкап"}</span>
                   <span
@@ -1094,6 +1659,16 @@ export default function GameDetail() {
                               </span>
                             )}
                           </div>
+                          {latestBackup.metadata?.label && (
+                            <div className="text-xs font-medium truncate">
+                              {latestBackup.metadata.label}
+                            </div>
+                          )}
+                          {latestBackup.metadata && (
+                            <div className="text-[11px] text-muted-foreground">
+                              {formatBackupContext(latestBackup)}
+                            </div>
+                          )}
                         </div>
                       </div>
                     </div>
@@ -1147,6 +1722,16 @@ export default function GameDetail() {
                                       </span>
                                     )}
                                   </div>
+                                  {backup.metadata && (
+                                    <div className="text-[11px] text-muted-foreground truncate">
+                                      {[
+                                        backup.metadata.label,
+                                        formatBackupContext(backup),
+                                      ]
+                                        .filter(Boolean)
+                                        .join(" · ")}
+                                    </div>
+                                  )}
                                 </div>
                                 <Button
                                   size="icon"
@@ -1200,6 +1785,8 @@ export default function GameDetail() {
             />
           </div>
         </div>
+
+        <GameNotes gameId={game.id} />
       </div>
 
       {showGameSettings && (
@@ -1223,6 +1810,14 @@ export default function GameDetail() {
 
             <ScrollArea className="max-h-[70vh]">
               <div className="p-5 space-y-6">
+                <LaunchOptionsEditor game={game} onSaved={refreshGames} />
+                <CompanionEditor game={game} />
+                <CoverEditor game={game} onSaved={refreshGames} />
+                <TagEditor game={game} onSaved={refreshGames} />
+                <PlaytimeEditor game={game} onSaved={refreshGames} />
+                <ShortcutCreator game={game} />
+                <FileVerifier game={game} />
+
                 <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
                   <div className="text-[10px] uppercase tracking-wider text-muted-foreground">
                     {"Бэкапы"}
@@ -1241,6 +1836,36 @@ export default function GameDetail() {
                       }
                     />
                   </div>
+                  <div className="flex items-center justify-between gap-3">
+                    <div>
+                      <div className="text-sm font-medium">{"Бэкап при выходе"}</div>
+                      <div className="text-xs text-muted-foreground">
+                        {"Создавать копию сразу после закрытия игры"}
+                      </div>
+                    </div>
+                    <Switch
+                      checked={game.backup_on_exit}
+                      disabled={!game.backup_enabled}
+                      onCheckedChange={(checked) =>
+                        updateSyncOptions({ backupOnExit: checked })
+                      }
+                    />
+                  </div>
+                  <div className="flex items-center justify-between gap-3">
+                    <div>
+                      <div className="text-sm font-medium">{"Восстановление при запуске"}</div>
+                      <div className="text-xs text-muted-foreground">
+                        {"Предлагать восстановить бэкап, если сохранения на диске старее"}
+                      </div>
+                    </div>
+                    <Switch
+                      checked={game.restore_on_launch}
+                      disabled={!game.backup_enabled}
+                      onCheckedChange={(checked) =>
+                        updateSyncOptions({ restoreOnLaunch: checked })
+                      }
+                    />
+                  </div>
                 </div>
 
                 <div className="rounded-2xl border border-border/60 bg-secondary/30 p-4 space-y-3">
@@ -1341,12 +1966,56 @@ export default function GameDetail() {
                     </Button>
                   </div>
 
+                  {saveSuggestions.length > 0 && (
+                    <div className="space-y-1">
+                      <div className="text-[11px] text-muted-foreground">
+                        {"Найденные папки — выберите нужную и сохраните:"}
+                      </div>
+                      {saveSuggestions.map((candidate) => (
+                        <button
+                          key={candidate.path}
+                          type="button"
+                          className={cn(
+                            "w-full rounded-xl border border-border/60 px-3 py-2 text-left transition-colors hover:border-foreground/30",
+                            savePathValue === candidate.path &&
+                              "border-emerald-500/50 bg-emerald-500/10",
+                          )}
+                          onClick={() => setSavePathDraft(candidate.path)}
+                        >
+                          <div className="flex items-center justify-between gap-2">
+                            <span className="truncate font-mono text-[11px]">
+                              {candidate.path}
+                            </span>
+                            <span className="shrink-0 text-[11px] text-muted-foreground">
+                              {`${Math.round(candidate.score * 100)}%`}
+                            </span>
+                          </div>
+                          <div className="text-[10px] text-muted-foreground">
+                            {`${candidate.file_count} файлов · ${formatBytes(candidate.total_size)}`}
+                            {candidate.last_modified &&
+                              ` · изменено ${new Date(candidate.last_modified).toLocaleDateString()}`}
+                            {candidate.source === "manifest" && " · манифест SQOBA"}
+                            {candidate.source === "pcgamingwiki" &&
+                              " · PCGamingWiki"}
+                            {candidate.source === "install" && " · папка игры"}
+                            {candidate.configured && " · уже используется"}
+                          </div>
+                        </button>
+                      ))}
+                    </div>
+                  )}
+
                   <p className="text-xs text-muted-foreground">
                     {
                       "Можно указать папку или конкретный файл сохранения. Если путь пустой, SQOBA попробует найти сохранения при следующем бэкапе. Можно использовать {PATHTOGAME} как папку игры для относительных путей."
                     }
                   </p>
                 </div>
+
+                <ExtraSavePaths gameId={game.id} />
+                <ManifestMatchPicker gameId={game.id} />
+                <PcgwSaveLocations gameId={game.id} gameName={game.name} />
+                <ManifestOverrideEditor gameId={game.id} />
               </div>
             </ScrollArea>
 
@@ -1434,7 +2103,9 @@ export default function GameDetail() {
                     ? "Создание бэкапа"
                     : backupProgress.stage === "restore"
                       ? "Восстановление бэкапа"
-                      : "Обработка"}
+                      : backupProgress.stage === "waiting"
+                        ? "Ожидание закрытия игры"
+                        : "Обработка"}
               </div>
               <div className="text-xs text-muted-foreground truncate">
                 {backupProgress.message}
@@ -1568,18 +2239,31 @@ export default function GameDetail() {
         </div>
       )}
 
-      {/* RAWG Search Modal (Existing) */}
+      {/* Metadata Search Modal */}
       {showMetadataSearch && (
         <div className="fixed inset-0 bg-black/80 backdrop-blur-sm flex items-center justify-center z-50 p-4 animate-in fade-in duration-200">
           <div className="bg-card rounded-lg w-full max-w-lg max-h-[80vh] flex flex-col">
             <div className="p-4 border-b">
               <h2 className="text-lg font-semibold">Поиск метаданных</h2>
               <p className="text-sm text-muted-foreground">
-                Поиск информации об игре в базе RAWG
+                Поиск информации об игре в базе{" "}
+                {METADATA_SOURCE_LABELS[metadataSource]}
               </p>
             </div>
 
             <div className="p-4 flex-1 overflow-hidden flex flex-col">
+              <div className="flex gap-2 mb-3">
+                {(["rawg", "igdb"] as const).map((source) => (
+                  <Button
+                    key={source}
+                    size="sm"
+                    variant={metadataSource === source ? "default" : "outline"}
+                    onClick={() => void changeMetadataSource(source)}
+                  >
+                    {METADATA_SOURCE_LABELS[source]}
+                  </Button>
+                ))}
+              </div>
               <div className="flex gap-2 mb-4">
                 <Input
                   placeholder="Название игры..."
@@ -1598,7 +2282,8 @@ export default function GameDetail() {
               </div>
               <div className="flex items-center justify-between gap-3 text-sm text-muted-foreground mb-3">
                 <span id="rawg-rename-toggle">
-                  Использовать название из RAWG
+                  Использовать название из{" "}
+                  {METADATA_SOURCE_LABELS[metadataSource]}
                 </span>
                 <Switch
                   checked={renameFromMetadata}
@@ -1606,6 +2291,62 @@ export default function GameDetail() {
                   aria-labelledby="rawg-rename-toggle"
                 />
               </div>
+              <div className="mb-3 space-y-1.5">
+                <div className="text-xs text-muted-foreground">
+                  {"Заменять поля (выключенные заполняются, только если пусты):"}
+                </div>
+                <div className="flex flex-wrap gap-1.5">
+                  {(
+                    Object.keys(METADATA_FIELD_LABELS) as Array<
+                      keyof MetadataOverwrite
+                    >
+                  ).map((field) => (
+                    <button
+                      key={field}
+                      type="button"
+                      aria-pressed={metadataOverwrite[field]}
+                      onClick={() =>
+                        setMetadataOverwrite((current) => ({
+                          ...current,
+                          [field]: !current[field],
+                        }))
+                      }
+                      className={cn(
+                        "rounded-md border border-border/60 px-2 py-0.5 text-[11px] text-muted-foreground",
+                        metadataOverwrite[field] &&
+                          "border-primary/60 text-primary",
+                      )}
+                    >
+                      {METADATA_FIELD_LABELS[field]}
+                    </button>
+                  ))}
+                </div>
+                <div className="text-xs text-muted-foreground">
+                  {"Закреплённые поля не меняются никогда:"}
+                </div>
+                <div className="flex flex-wrap gap-1.5">
+                  {(
+                    Object.keys(LOCKABLE_FIELD_LABELS) as MetadataField[]
+                  ).map((field) => (
+                    <button
+                      key={field}
+                      type="button"
+                      aria-pressed={game.locked_fields.includes(field)}
+                      onClick={() => void toggleLockedField(field)}
+                      className={cn(
+                        "inline-flex items-center gap-1 rounded-md border border-border/60 px-2 py-0.5 text-[11px] text-muted-foreground",
+                        game.locked_fields.includes(field) &&
+                          "border-amber-500/60 text-amber-500",
+                      )}
+                    >
+                      {game.locked_fields.includes(field) && (
+                        <Lock className="w-3 h-3" />
+                      )}
+                      {LOCKABLE_FIELD_LABELS[field]}
+                    </button>
+                  ))}
+                </div>
+              </div>
               <ScrollArea className="flex-1">
                 {metadataResults.length > 0 ? (
                   <div className="space-y-2">
@@ -1615,9 +2356,9 @@ export default function GameDetail() {
                         className="flex items-center gap-3 p-3 rounded-md hover:bg-secondary cursor-pointer border border-transparent hover:border-border transition-colors"
                         onClick={() => applyMetadata(result)}
                       >
-                        {result.background_image ? (
+                        {result.image ? (
                           <img
-                            src={result.background_image}
+                            src={result.image}
                             alt={result.name}
                             className="w-16 h-16 object-cover rounded"
                           />
@@ -1632,7 +2373,7 @@ export default function GameDetail() {
                           </div>
                           <div className="text-sm text-muted-foreground">
                             {result.released?.slice(0, 4)}
-                            {result.metacritic && ` • ${result.metacritic}`}
+                            {result.score != null && ` • ${result.score}`}
                           </div>
                         </div>
                         {applyingMetadata ? (
@@ -1663,6 +2404,56 @@ export default function GameDetail() {
         </div>
       )}
 
+      {showDeleteDialog && (
+        <div className="fixed inset-0 bg-black/80 flex items-center justify-center z-50 p-4">
+          <div className="bg-card rounded-lg w-full max-w-md p-6">
+            <h2 className="text-lg font-semibold mb-2">
+              Удалить «{game.name}» из библиотеки?
+            </h2>
+            <p className="text-muted-foreground mb-4">
+              Файлы игры на диске не затрагиваются.
+            </p>
+            <div className="space-y-3 mb-6">
+              <label className="flex items-center justify-between gap-3 text-sm">
+                {"Удалить файлы бэкапов"}
+                <Switch
+                  checked={deleteBackups}
+                  onCheckedChange={setDeleteBackups}
+                  aria-label="Удалить файлы бэкапов"
+                />
+              </label>
+              <label className="flex items-center justify-between gap-3 text-sm">
+                {"Удалить историю игрового времени"}
+                <Switch
+                  checked={deleteStats}
+                  onCheckedChange={setDeleteStats}
+                  aria-label="Удалить историю игрового времени"
+                />
+              </label>
+            </div>
+            <div className="flex justify-end gap-2">
+              <Button
+                variant="ghost"
+                onClick={() => setShowDeleteDialog(false)}
+                disabled={deleting}
+              >
+                Отмена
+              </Button>
+              <Button
+                variant="destructive"
+                onClick={handleDelete}
+                disabled={deleting}
+              >
+                {deleting ? (
+                  <Loader2 className="w-4 h-4 animate-spin mr-2" />
+                ) : null}
+                Удалить
+              </Button>
+            </div>
+          </div>
+        </div>
+      )}
+
       {/* Backup Prompt Modal */}
       {showBackupPrompt && (
         <div className="fixed inset-0 bg-black/80 flex items-center justify-center z-50 p-4">
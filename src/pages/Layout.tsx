@@ -1,7 +1,13 @@
 import { Menu, X } from "lucide-react";
 import { useEffect, useState } from "react";
 import { Outlet, useLocation } from "react-router-dom";
+import { DatabaseStatusBanner } from "@/components/DatabaseStatusBanner";
+import { PluginNotifications } from "@/components/PluginNotifications";
+import { SaveCorruptionPrompt } from "@/components/SaveCorruptionPrompt";
+import { SessionEndedNotice } from "@/components/SessionEndedNotice";
+import { SessionNotePrompt } from "@/components/SessionNotePrompt";
 import { Sidebar } from "@/components/Sidebar";
+import { StalledOperationPrompt } from "@/components/StalledOperationPrompt";
 import { ToastProvider } from "@/components/ToastProvider";
 import { cn } from "@/lib/utils";
 import { GamesProvider } from "@/store/GamesContext";
@@ -55,11 +61,17 @@ export default function Layout() {
           </div>
 
           <main className="flex-1 min-w-0 overflow-auto pt-14 lg:pt-0">
+            <DatabaseStatusBanner />
             <div className="min-h-full max-w-[1600px] mx-auto">
               <Outlet />
             </div>
           </main>
         </div>
+        <SessionNotePrompt />
+        <SessionEndedNotice />
+        <StalledOperationPrompt />
+        <SaveCorruptionPrompt />
+        <PluginNotifications />
       </ToastProvider>
     </GamesProvider>
   );
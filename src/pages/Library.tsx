@@ -1,29 +1,43 @@
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
+import { save } from "@tauri-apps/plugin-dialog";
 import {
   Clock,
+  EyeOff,
+  ArrowLeftRight,
+  FileDown,
   Gamepad2,
   Grid3X3,
+  HardDrive,
   List,
   Play,
+  Plus,
   Search,
   Star,
 } from "lucide-react";
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { Link } from "react-router-dom";
+import { CollectionEditor } from "@/components/CollectionEditor";
 import { GameCard } from "@/components/GameCard";
+import { LibraryTransfer } from "@/components/LibraryTransfer";
 import { RawgMetadataPrompt } from "@/components/RawgMetadataPrompt";
 import { useToast } from "@/components/ToastProvider";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
-import { gamesApi } from "@/lib/api";
+import { gamesApi, tagsApi } from "@/lib/api";
 import { cn } from "@/lib/utils";
 import { useGamesActions, useGamesState } from "@/store/GamesContext";
-import type { Game, NewGame } from "@/types";
+import type { Collection, Game, NewGame, Tag } from "@/types";
 
 type ViewMode = "grid" | "list";
 type SortBy = "name" | "lastPlayed" | "dateAdded" | "playCount";
 
 // Helper to format playtime
+function formatBytes(bytes: number) {
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(0)} МБ`;
+  return `${(bytes / 1024 / 1024 / 1024).toFixed(1)} ГБ`;
+}
+
 function formatPlaytime(seconds: number) {
   if (!seconds) return "0 ч";
   const hours = Math.floor(seconds / 3600);
@@ -60,11 +74,48 @@ export default function Library() {
   const [viewMode, setViewMode] = useState<ViewMode>("grid");
   const [sortBy, setSortBy] = useState<SortBy>("name");
   const [showFavoritesOnly, setShowFavoritesOnly] = useState(false);
+  const [showHidden, setShowHidden] = useState(false);
+  // "" for the whole library, otherwise "tag:<id>" or "collection:<id>".
+  const [group, setGroup] = useState("");
+  const [tags, setTags] = useState<Tag[]>([]);
+  const [collections, setCollections] = useState<Collection[]>([]);
+  const [collectionGameIds, setCollectionGameIds] =
+    useState<Set<string> | null>(null);
+  const [creatingCollection, setCreatingCollection] = useState(false);
+  const [transferOpen, setTransferOpen] = useState(false);
 
   const [dropActive, setDropActive] = useState(false);
   const [metadataQueue, setMetadataQueue] = useState<Game[]>([]);
   const dragHasSupportedRef = useRef(false);
 
+  const exportReport = useCallback(async () => {
+    const path = await save({
+      title: "\u0421\u043e\u0445\u0440\u0430\u043d\u0438\u0442\u044c \u043e\u0442\u0447\u0451\u0442 \u043e \u0431\u0438\u0431\u043b\u0438\u043e\u0442\u0435\u043a\u0435",
+      defaultPath: "library.html",
+      filters: [
+        { name: "HTML", extensions: ["html"] },
+        { name: "Markdown", extensions: ["md"] },
+      ],
+    });
+    if (!path) return;
+    const format = path.toLowerCase().endsWith(".md") ? "markdown" : "html";
+    try {
+      const count = await gamesApi.exportReport(path, format);
+      notify({
+        tone: "success",
+        title: `\u041e\u0442\u0447\u0451\u0442 \u0441\u043e\u0445\u0440\u0430\u043d\u0451\u043d, \u0438\u0433\u0440: ${count}`,
+        description: path,
+      });
+    } catch (e) {
+      console.error("Failed to export library report:", e);
+      notify({
+        tone: "error",
+        title: "\u041d\u0435 \u0443\u0434\u0430\u043b\u043e\u0441\u044c \u0441\u043e\u0445\u0440\u0430\u043d\u0438\u0442\u044c \u043e\u0442\u0447\u0451\u0442",
+        description: String(e),
+      });
+    }
+  }, [notify]);
+
   const enqueueMetadata = useCallback((added: Game[]) => {
     setMetadataQueue((prev) => {
       if (added.length === 0) return prev;
@@ -206,8 +257,88 @@ export default function Library() {
     };
   }, [handleDroppedPaths]);
 
+  // Install sizes are re-measured in the background.
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+    const setup = async () => {
+      unlisten = await listen<number>("games:install-sizes", () => {
+        void refreshGames();
+      });
+    };
+    void setup();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [refreshGames]);
+
+  const loadGroups = useCallback(async () => {
+    try {
+      const [nextTags, nextCollections] = await Promise.all([
+        tagsApi.list(),
+        tagsApi.getCollections(),
+      ]);
+      setTags(nextTags);
+      setCollections(nextCollections);
+    } catch (e) {
+      console.error("Failed to load tags and collections:", e);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadGroups();
+  }, [loadGroups]);
+
+  useEffect(() => {
+    if (!group.startsWith("collection:")) {
+      setCollectionGameIds(null);
+      return;
+    }
+    let cancelled = false;
+    tagsApi
+      .getCollectionGames(group.slice("collection:".length))
+      .then((items) => {
+        if (!cancelled) setCollectionGameIds(new Set(items.map((g) => g.id)));
+      })
+      .catch((e) => {
+        console.error("Failed to load collection:", e);
+        if (!cancelled) setCollectionGameIds(new Set());
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [group, games]);
+
+  const deleteCollection = async (id: string) => {
+    try {
+      await tagsApi.deleteCollection(id);
+      setGroup("");
+      await loadGroups();
+    } catch (e) {
+      notify({
+        tone: "error",
+        title: "Не удалось удалить коллекцию",
+        description: String(e),
+      });
+    }
+  };
+
+  const visibleGames = useMemo(
+    () => games.filter((game) => !game.is_hidden),
+    [games],
+  );
+  const hiddenCount = games.length - visibleGames.length;
+
   const filteredGames = useMemo(() => {
-    let result = (showFavoritesOnly ? favorites : games).slice();
+    let result = showHidden
+      ? games.filter((game) => game.is_hidden)
+      : (showFavoritesOnly ? favorites : visibleGames).slice();
+
+    if (group.startsWith("tag:")) {
+      const tagId = group.slice("tag:".length);
+      result = result.filter((game) => game.tags.includes(tagId));
+    } else if (collectionGameIds) {
+      result = result.filter((game) => collectionGameIds.has(game.id));
+    }
 
     if (searchQuery.trim()) {
       const query = searchQuery.toLowerCase();
@@ -238,7 +369,17 @@ export default function Library() {
           return a.name.localeCompare(b.name);
       }
     });
-  }, [games, favorites, searchQuery, sortBy, showFavoritesOnly]);
+  }, [
+    games,
+    visibleGames,
+    favorites,
+    searchQuery,
+    sortBy,
+    showFavoritesOnly,
+    showHidden,
+    group,
+    collectionGameIds,
+  ]);
 
   const currentMetadataGame = metadataQueue[0] ?? null;
 
@@ -279,7 +420,8 @@ export default function Library() {
               Моя библиотека
             </h1>
             <p className="text-muted-foreground text-sm">
-              {games.length} {games.length === 1 ? "игра" : "игр"} в библиотеке
+              {visibleGames.length}{" "}
+              {visibleGames.length === 1 ? "игра" : "игр"} в библиотеке
             </p>
           </div>
 
@@ -314,6 +456,70 @@ export default function Library() {
               />
               Избранное
             </Button>
+            {(hiddenCount > 0 || showHidden) && (
+              <Button
+                variant={showHidden ? "secondary" : "ghost"}
+                size="sm"
+                onClick={() => setShowHidden(!showHidden)}
+                className="gap-2 flex-shrink-0"
+              >
+                <EyeOff className="w-4 h-4" />
+                Скрытые ({hiddenCount})
+              </Button>
+            )}
+
+            <div className="h-4 w-px bg-border flex-shrink-0" />
+
+            <select
+              value={group}
+              onChange={(e) => setGroup(e.target.value)}
+              className="text-sm bg-transparent border-none focus:outline-none text-muted-foreground cursor-pointer flex-shrink-0"
+              aria-label="Группа"
+            >
+              <option value="">Все игры</option>
+              {tags.length > 0 && (
+                <optgroup label="Теги">
+                  {tags.map((tag) => (
+                    <option key={tag.id} value={`tag:${tag.id}`}>
+                      {tag.name} ({tag.game_count})
+                    </option>
+                  ))}
+                </optgroup>
+              )}
+              {collections.length > 0 && (
+                <optgroup label="Коллекции">
+                  {collections.map((collection) => (
+                    <option
+                      key={collection.id}
+                      value={`collection:${collection.id}`}
+                    >
+                      {collection.name}
+                    </option>
+                  ))}
+                </optgroup>
+              )}
+            </select>
+            <Button
+              variant="ghost"
+              size="icon"
+              className="w-8 h-8 flex-shrink-0"
+              onClick={() => setCreatingCollection(true)}
+              title="Новая коллекция"
+            >
+              <Plus className="w-4 h-4" />
+            </Button>
+            {group.startsWith("collection:") && (
+              <Button
+                variant="ghost"
+                size="sm"
+                className="flex-shrink-0 text-muted-foreground"
+                onClick={() =>
+                  deleteCollection(group.slice("collection:".length))
+                }
+              >
+                Удалить коллекцию
+              </Button>
+            )}
 
             <div className="h-4 w-px bg-border flex-shrink-0" />
 
@@ -330,6 +536,25 @@ export default function Library() {
           </div>
 
           <div className="flex items-center gap-1 justify-end">
+            <Button
+              variant="ghost"
+              size="icon"
+              className="w-8 h-8"
+              onClick={exportReport}
+              disabled={games.length === 0}
+              title="Экспорт отчёта о библиотеке"
+            >
+              <FileDown className="w-4 h-4" />
+            </Button>
+            <Button
+              variant={transferOpen ? "secondary" : "ghost"}
+              size="icon"
+              className="w-8 h-8"
+              onClick={() => setTransferOpen((value) => !value)}
+              title="Экспорт и импорт библиотеки"
+            >
+              <ArrowLeftRight className="w-4 h-4" />
+            </Button>
             <Button
               variant={viewMode === "grid" ? "secondary" : "ghost"}
               size="icon"
@@ -349,6 +574,28 @@ export default function Library() {
           </div>
         </div>
 
+        {creatingCollection && (
+          <CollectionEditor
+            tags={tags}
+            onSaved={async (collection) => {
+              setCreatingCollection(false);
+              await loadGroups();
+              setGroup(`collection:${collection.id}`);
+            }}
+            onClose={() => setCreatingCollection(false)}
+          />
+        )}
+
+        {transferOpen && (
+          <LibraryTransfer
+            onImported={async () => {
+              await refreshGames();
+              await loadGroups();
+            }}
+            onClose={() => setTransferOpen(false)}
+          />
+        )}
+
         {/* Empty state */}
         {filteredGames.length === 0 && (
           <div className="flex flex-col items-center justify-center py-16 text-center">
@@ -472,6 +719,12 @@ function GameListItem({ game }: { game: Game }) {
               {new Date(game.last_played).toLocaleDateString()}
             </span>
           )}
+          {game.install_size != null && game.install_size > 0 && (
+            <span className="flex items-center gap-1 hidden sm:flex">
+              <HardDrive className="w-3 h-3" />
+              {formatBytes(game.install_size)}
+            </span>
+          )}
         </div>
       </div>
 
@@ -17,15 +17,26 @@ import {
 } from "lucide-react";
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { RawgMetadataPrompt } from "@/components/RawgMetadataPrompt";
+import { ScanBlacklistEditor } from "@/components/ScanBlacklistEditor";
+import { ScanDirectoriesEditor } from "@/components/ScanDirectoriesEditor";
+import { ScanOptionsEditor } from "@/components/ScanOptionsEditor";
 import { useToast } from "@/components/ToastProvider";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Progress } from "@/components/ui/progress";
 import { ScrollArea } from "@/components/ui/scroll-area";
-import { gamesApi, scanApi } from "@/lib/api";
+import { gamesApi, scanApi, settingsApi } from "@/lib/api";
 import { cn } from "@/lib/utils";
 import { useGamesActions } from "@/store/GamesContext";
-import type { ExeEntry, Game, NewGame } from "@/types";
+import type {
+  ExeEntry,
+  Game,
+  MatchNeeded,
+  NewGame,
+  ScanGroup,
+  ScanOptions,
+  UnreachableFolder,
+} from "@/types";
 
 interface ScanResult extends ExeEntry {
   selected: boolean;
@@ -55,6 +66,13 @@ export default function Scan() {
   const [scanning, setScanning] = useState(false);
   const [results, setResults] = useState<ScanResult[]>([]);
   const [filter, setFilter] = useState("");
+  const [includeFiltered, setIncludeFiltered] = useState(false);
+  const [showBlacklist, setShowBlacklist] = useState(false);
+  const [showDirectories, setShowDirectories] = useState(false);
+  const [incremental, setIncremental] = useState(false);
+  const [showOptions, setShowOptions] = useState(false);
+  const [scanOptions, setScanOptions] = useState<ScanOptions>({});
+  const [removedPaths, setRemovedPaths] = useState<string[]>([]);
   const [adding, setAdding] = useState(false);
   const [sortBy, setSortBy] = useState<"name" | "cpu">("cpu");
 
@@ -63,10 +81,24 @@ export default function Scan() {
   const [processes, setProcesses] = useState<ScanResult[]>([]);
   const [error, setError] = useState<string | null>(null);
   const [dropActive, setDropActive] = useState(false);
-  const [metadataQueue, setMetadataQueue] = useState<Game[]>([]);
+  const [metadataQueue, setMetadataQueue] = useState<
+    Pick<Game, "id" | "name">[]
+  >([]);
   const dragHasSupportedRef = useRef(false);
+  // With `rawg_auto_match` on, the backend matches added games itself and only sends the
+  // ambiguous ones back as `metadata:match-needed`.
+  const autoMatchRef = useRef(false);
 
-  const enqueueMetadata = useCallback((added: Game[]) => {
+  useEffect(() => {
+    settingsApi
+      .get("rawg_auto_match")
+      .then((value) => {
+        autoMatchRef.current = value === "true";
+      })
+      .catch(() => {});
+  }, []);
+
+  const queueForMetadata = useCallback((added: Pick<Game, "id" | "name">[]) => {
     setMetadataQueue((prev) => {
       if (added.length === 0) return prev;
       const seen = new Set(prev.map((g) => g.id));
@@ -80,6 +112,24 @@ export default function Scan() {
     });
   }, []);
 
+  const enqueueMetadata = useCallback(
+    (added: Game[]) => {
+      if (!autoMatchRef.current) queueForMetadata(added);
+    },
+    [queueForMetadata],
+  );
+
+  useEffect(() => {
+    const unlisten = listen<MatchNeeded>("metadata:match-needed", (event) =>
+      queueForMetadata([
+        { id: event.payload.game_id, name: event.payload.game_name },
+      ]),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [queueForMetadata]);
+
   const refreshUsage = async () => {
     setLoadingProcesses(true);
     try {
@@ -112,6 +162,96 @@ export default function Scan() {
       setLoadingProcesses(false);
     }
   };
+
+  // Each scan emits under its own `scan:<id>:` prefix; the id is picked here so the listeners
+  // are in place before the first entry arrives.
+  const scanListenersRef = useRef<(() => void) | null>(null);
+  const activeScanRef = useRef<string | null>(null);
+  // Group membership by path; groups arrive after their entries, but entries are added
+  // asynchronously, so both sides consult it.
+  const groupsRef = useRef(new Map<string, ScanGroup>());
+
+  const stopListening = useCallback(() => {
+    scanListenersRef.current?.();
+    scanListenersRef.current = null;
+  }, []);
+
+  const listenToScan = useCallback(
+    async (scanId: string) => {
+      stopListening();
+      groupsRef.current = new Map();
+      const addEntry = async (event: { payload: ExeEntry }) => {
+        const entry = event.payload;
+        const exists = await gamesApi
+          .existsByPath(entry.path)
+          .catch(() => false);
+        const baseName = entry.file_name.replace(
+          entry.emulator_id ? /\.[^.]+$/ : /\.exe$/i,
+          "",
+        );
+        const group = groupsRef.current.get(entry.path);
+        const isMain = !group || group.main_path === entry.path;
+        const cleanName =
+          entry.product_name ||
+          (group && isMain ? group.name : "") ||
+          baseName
+            .replace(/[-_]/g, " ")
+            .replace(/\s+/g, " ")
+            .trim();
+
+        setResults((prev) => [
+          ...prev,
+          {
+            ...entry,
+            selected: !exists && !entry.filter_reason && isMain,
+            alreadyAdded: exists,
+            customName: cleanName,
+          },
+        ]);
+      };
+      // One game per folder: only the group's main binary stays selected.
+      const applyGroup = (event: { payload: ScanGroup }) => {
+        const group = event.payload;
+        group.paths.forEach((path) => groupsRef.current.set(path, group));
+        setResults((prev) =>
+          prev.map((r) => {
+            if (!group.paths.includes(r.path)) return r;
+            const isMain = r.path === group.main_path;
+            return {
+              ...r,
+              selected: isMain && !r.alreadyAdded && !r.filter_reason,
+              customName: isMain && !r.product_name ? group.name : r.customName,
+            };
+          }),
+        );
+      };
+      const fns = await Promise.all([
+        listen<ExeEntry>(`scan:${scanId}:entry`, addEntry),
+        // Incremental rescans only report what changed since the previous scan.
+        listen<ExeEntry>(`scan:${scanId}:added`, addEntry),
+        listen<ScanGroup>(`scan:${scanId}:group`, applyGroup),
+        listen<string>(`scan:${scanId}:removed`, (event) => {
+          const path = event.payload;
+          setRemovedPaths((prev) => [...prev, path]);
+          setResults((prev) => prev.filter((r) => r.path !== path));
+        }),
+        listen<UnreachableFolder>(`scan:${scanId}:unreachable`, (event) => {
+          notify({
+            tone: "error",
+            title: "Папка недоступна",
+            description: event.payload.error,
+          });
+        }),
+        listen(`scan:${scanId}:done`, () => {
+          setScanning(false);
+          stopListening();
+        }),
+      ]);
+      scanListenersRef.current = () => fns.forEach((fn) => fn());
+    },
+    [notify, stopListening],
+  );
+
   const startScan = async () => {
     const selected = await open({
       directory: true,
@@ -122,19 +262,53 @@ export default function Scan() {
     if (!selected) return;
 
     setResults([]);
+    setRemovedPaths([]);
     setScanning(true);
 
+    const scanId = crypto.randomUUID();
     try {
-      await invoke("scan_executables_stream", { dir: selected });
+      activeScanRef.current = scanId;
+      await listenToScan(scanId);
+      await invoke(
+        incremental ? "scan_directory_incremental" : "scan_executables_stream",
+        {
+          dir: selected,
+          includeFiltered,
+          scanId,
+          options: scanOptions,
+        },
+      );
     } catch (e) {
       console.error("Scan failed:", e);
+      stopListening();
+      setScanning(false);
+    }
+  };
+
+  const startScanAll = async () => {
+    setResults([]);
+    setRemovedPaths([]);
+    setScanning(true);
+
+    const scanId = crypto.randomUUID();
+    try {
+      activeScanRef.current = scanId;
+      await listenToScan(scanId);
+      await invoke("scan_all_directories", {
+        includeFiltered,
+        scanId,
+        options: scanOptions,
+      });
+    } catch (e) {
+      console.error("Scan failed:", e);
+      stopListening();
       setScanning(false);
     }
   };
 
   const cancelScan = async () => {
     try {
-      await invoke("cancel_scan");
+      await invoke("cancel_scan", { scanId: activeScanRef.current });
     } catch (e) {
       console.error("Cancel failed:", e);
     }
@@ -276,37 +450,7 @@ export default function Scan() {
     }
   }, [activeTab]);
 
-  useEffect(() => {
-    const unlisten1 = listen<ExeEntry>("scan:entry", async (event) => {
-      const entry = event.payload;
-      const exists = await gamesApi.existsByPath(entry.path).catch(() => false);
-      const baseName = entry.file_name.replace(/\.exe$/i, "");
-      const cleanName = baseName
-        .replace(/[-_]/g, " ")
-        .replace(/\s+/g, " ")
-        .trim();
-
-      setResults((prev) => [
-        ...prev,
-        {
-          ...entry,
-          selected: !exists,
-          alreadyAdded: exists,
-          customName: cleanName,
-        },
-      ]);
-    });
-
-    const unlisten3 = listen("scan:done", () => {
-      setScanning(false);
-    });
-
-    return () => {
-      Promise.all([unlisten1, unlisten3]).then((fns) =>
-        fns.forEach((fn) => fn()),
-      );
-    };
-  }, []);
+  useEffect(() => () => stopListening(), [stopListening]);
 
   useEffect(() => {
     let unlisten: (() => void) | null = null;
@@ -405,6 +549,22 @@ export default function Scan() {
       }));
 
       const added = await addGames(newGames);
+      // ROMs are added like executables and then pointed at their emulator.
+      const emulatorByPath = new Map(
+        selected
+          .filter((r) => r.emulator_id)
+          .map((r) => [r.path.toLowerCase(), r.emulator_id]),
+      );
+      await Promise.all(
+        added
+          .filter((g) => emulatorByPath.has(g.exe_path.toLowerCase()))
+          .map((g) =>
+            gamesApi.update({
+              id: g.id,
+              emulator_id: emulatorByPath.get(g.exe_path.toLowerCase()),
+            }),
+          ),
+      );
       enqueueMetadata(added);
       await refreshGames();
 
@@ -524,14 +684,58 @@ export default function Scan() {
                 </div>
               </>
             ) : (
-              <Button
-                onClick={startScan}
-                className="gap-2 w-full sm:w-auto"
-                data-testid="scan-start"
-              >
-                <FolderOpen className="w-4 h-4" />
-                Выбрать папку
-              </Button>
+              <div className="flex flex-col sm:flex-row sm:items-center gap-2 sm:gap-4 w-full">
+                <Button
+                  onClick={startScan}
+                  className="gap-2 w-full sm:w-auto"
+                  data-testid="scan-start"
+                >
+                  <FolderOpen className="w-4 h-4" />
+                  Выбрать папку
+                </Button>
+                <Button
+                  variant="outline"
+                  onClick={startScanAll}
+                  className="gap-2 w-full sm:w-auto"
+                >
+                  <FolderSearch className="w-4 h-4" />
+                  Сканировать все папки
+                </Button>
+                <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+                  <input
+                    type="checkbox"
+                    checked={includeFiltered}
+                    onChange={(e) => setIncludeFiltered(e.target.checked)}
+                  />
+                  Показывать установщики и служебные файлы
+                </label>
+                <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+                  <input
+                    type="checkbox"
+                    checked={incremental}
+                    onChange={(e) => setIncremental(e.target.checked)}
+                  />
+                  Только изменения с прошлого сканирования
+                </label>
+                <button
+                  onClick={() => setShowDirectories((value) => !value)}
+                  className="text-xs text-muted-foreground hover:text-foreground sm:ml-auto"
+                >
+                  Папки
+                </button>
+                <button
+                  onClick={() => setShowBlacklist((value) => !value)}
+                  className="text-xs text-muted-foreground hover:text-foreground"
+                >
+                  Исключения
+                </button>
+                <button
+                  onClick={() => setShowOptions((value) => !value)}
+                  className="text-xs text-muted-foreground hover:text-foreground"
+                >
+                  Параметры
+                </button>
+              </div>
             )
           ) : (
             <div className="flex items-center gap-2 w-full sm:w-auto">
@@ -551,6 +755,33 @@ export default function Scan() {
           )}
         </div>
 
+        {activeTab === "folders" && removedPaths.length > 0 && (
+          <div
+            className="mb-4 text-xs text-muted-foreground"
+            title={removedPaths.join("\n")}
+          >
+            {`Пропало с диска: ${removedPaths.length}`}
+          </div>
+        )}
+
+        {activeTab === "folders" && showDirectories && !scanning && (
+          <div className="mb-4">
+            <ScanDirectoriesEditor />
+          </div>
+        )}
+
+        {activeTab === "folders" && showBlacklist && !scanning && (
+          <div className="mb-4">
+            <ScanBlacklistEditor />
+          </div>
+        )}
+
+        {activeTab === "folders" && showOptions && !scanning && (
+          <div className="mb-4">
+            <ScanOptionsEditor options={scanOptions} onChange={setScanOptions} />
+          </div>
+        )}
+
         {/* Toolbar - ALWAYS VISIBLE if list is not empty or loading */}
         {(currentList.length > 0 || loadingProcesses) && (
           <div className="flex flex-col gap-3 mb-4">
@@ -720,10 +951,27 @@ export default function Scan() {
                             Есть
                           </span>
                         )}
+                        {result.filter_reason && !result.alreadyAdded && (
+                          <span className="text-[9px] font-bold uppercase tracking-wider text-muted-foreground px-1.5 py-0.5 bg-muted rounded leading-none flex-shrink-0">
+                            Служебный
+                          </span>
+                        )}
                       </div>
                       <div className="text-[10px] sm:text-xs text-muted-foreground truncate opacity-50 mt-0.5 pr-4">
                         {result.path}
                       </div>
+                      {(result.company_name || result.file_version) && (
+                        <div className="text-[10px] sm:text-xs text-muted-foreground truncate opacity-70 pr-4">
+                          {[result.company_name, result.file_version]
+                            .filter(Boolean)
+                            .join(" · ")}
+                        </div>
+                      )}
+                      {result.source_dir && (
+                        <div className="text-[10px] sm:text-xs text-muted-foreground truncate opacity-50 pr-4">
+                          {`Из папки ${result.source_dir}`}
+                        </div>
+                      )}
                     </div>
 
                     <div className="flex flex-col items-end gap-1 flex-shrink-0 ml-auto pl-2">
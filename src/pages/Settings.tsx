@@ -1,3 +1,4 @@
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import {
   Check,
   ExternalLink,
@@ -12,12 +13,20 @@ import {
   Shield,
   Sun,
 } from "lucide-react";
-import { useState } from "react";
+import { useEffect, useState } from "react";
+import { EmulatorSettings } from "@/components/EmulatorSettings";
+import { IgdbCredentialsSetting } from "@/components/IgdbCredentialsSetting";
+import { MetadataBundleImport } from "@/components/MetadataBundleImport";
+import { PluginSettings } from "@/components/PluginSettings";
+import { RawgAutoMatchSetting } from "@/components/RawgAutoMatchSetting";
 import { useTheme } from "@/components/theme-provider";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Switch } from "@/components/ui/switch";
 import { useSettingsState } from "@/hooks/useSettingsState";
+import type { ManifestProgressEvent } from "@/types";
+
+const formatMegabytes = (bytes: number) => (bytes / 1024 / 1024).toFixed(1);
 
 export default function Settings() {
   const { theme, setTheme } = useTheme();
@@ -36,6 +45,8 @@ export default function Settings() {
     handleCompressionLevelChange,
     skipCompressionOnce,
     setSkipCompressionOnce,
+    integrityCheck,
+    setIntegrityCheck,
     maxBackups,
     handleMaxBackupsChange,
     rawgApiKey,
@@ -44,23 +55,48 @@ export default function Settings() {
     toggleAutoStart,
     saveSettings,
     selectBackupDirectory,
+    manifestInfo,
     refreshSqobaManifest,
   } = useSettingsState();
   const [manifestRefreshing, setManifestRefreshing] = useState(false);
   const [manifestStatus, setManifestStatus] = useState<string | null>(null);
+  const [manifestProgress, setManifestProgress] =
+    useState<ManifestProgressEvent | null>(null);
+
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+    let disposed = false;
+    listen<ManifestProgressEvent>("manifest:progress", (event) =>
+      setManifestProgress(event.payload),
+    ).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
 
   const handleRefreshManifest = async () => {
     if (manifestRefreshing) return;
     setManifestRefreshing(true);
     setManifestStatus(null);
+    setManifestProgress(null);
+    const previousFetch = manifestInfo?.fetched_at ?? null;
     try {
-      await refreshSqobaManifest();
-      setManifestStatus("Манифест обновлён");
+      const info = await refreshSqobaManifest();
+      setManifestStatus(
+        info.fetched_at !== previousFetch
+          ? "Манифест обновлён"
+          : "Манифест уже актуален",
+      );
     } catch (e) {
       console.error("Failed to refresh SQOBA manifest:", e);
       setManifestStatus("Не удалось обновить манифест");
     } finally {
       setManifestRefreshing(false);
+      setManifestProgress(null);
     }
   };
 
@@ -191,6 +227,17 @@ export default function Settings() {
                   <div className="text-xs text-muted-foreground">
                     Нужен для автопоиска сохранений (Ludusavi/PCGamingWiki).
                   </div>
+                  {manifestInfo?.fetched_at ? (
+                    <div className="text-xs text-muted-foreground mt-1">
+                      {`Загружен ${new Date(manifestInfo.fetched_at).toLocaleString("ru-RU")}`}
+                      {manifestInfo.game_count !== null
+                        ? ` · ${manifestInfo.game_count} игр`
+                        : null}
+                      {manifestInfo.refresh_interval_hours > 0
+                        ? ` · проверка каждые ${manifestInfo.refresh_interval_hours} ч`
+                        : " · автообновление выключено"}
+                    </div>
+                  ) : null}
                 </div>
                 <Button
                   variant="outline"
@@ -207,6 +254,13 @@ export default function Settings() {
                   Обновить
                 </Button>
               </div>
+              {manifestRefreshing && manifestProgress?.stage === "download" ? (
+                <div className="text-xs text-muted-foreground mt-2">
+                  {manifestProgress.total
+                    ? `Загрузка: ${formatMegabytes(manifestProgress.downloaded)} / ${formatMegabytes(manifestProgress.total)} МБ`
+                    : `Загрузка: ${formatMegabytes(manifestProgress.downloaded)} МБ`}
+                </div>
+              ) : null}
               {manifestStatus ? (
                 <div className="text-xs text-muted-foreground mt-2">
                   {manifestStatus}
@@ -284,6 +338,28 @@ export default function Settings() {
                   onClick={(event) => event.stopPropagation()}
                 />
               </div>
+
+              <div
+                className="flex items-center justify-between gap-3 rounded-md px-2 py-2 cursor-pointer hover:bg-accent/50 transition-colors"
+                onClick={() => setIntegrityCheck((prev) => !prev)}
+              >
+                <div>
+                  <span id="setting-integrity-check" className="text-sm">
+                    {"Проверять сохранения после выхода из игры"}
+                  </span>
+                  <span className="text-xs text-muted-foreground block">
+                    {
+                      "Пустые или обрезанные сохранения не попадут в бэкап — вместо этого будет предложено восстановление."
+                    }
+                  </span>
+                </div>
+                <Switch
+                  checked={integrityCheck}
+                  onCheckedChange={setIntegrityCheck}
+                  aria-labelledby="setting-integrity-check"
+                  onClick={(event) => event.stopPropagation()}
+                />
+              </div>
             </div>
           </div>
         </section>
@@ -422,9 +498,27 @@ export default function Settings() {
                 для расширенных возможностей поиска
               </p>
             </div>
+            <RawgAutoMatchSetting />
+            <MetadataBundleImport />
           </div>
         </section>
 
+        {/* IGDB API */}
+        <section className="space-y-4">
+          <div className="flex items-center gap-2">
+            <Key className="w-5 h-5" />
+            <h2 className="text-lg font-semibold">IGDB API</h2>
+          </div>
+
+          <div className="bg-card rounded-lg border p-4">
+            <IgdbCredentialsSetting />
+          </div>
+        </section>
+
+        <EmulatorSettings />
+
+        <PluginSettings />
+
         {/* Save Button */}
         <div className="flex justify-end pt-4 sm:border-t fixed sm:relative bottom-0 left-0 right-0 p-4 sm:p-0 bg-background/80 backdrop-blur-md sm:bg-transparent z-10 border-t sm:border-none">
           <Button
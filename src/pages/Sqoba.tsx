@@ -163,6 +163,7 @@ export default function SqobaPage() {
   const [query, setQuery] = useState("");
   const [onlyMissing, setOnlyMissing] = useState(false);
   const [scanAll, setScanAll] = useState<LookupState<null>>({ loading: false });
+  const [enablingAll, setEnablingAll] = useState(false);
 
   const [pathsByGameId, setPathsByGameId] = useState<
     Record<string, LookupState<SavePathLookup>>
@@ -369,6 +370,40 @@ export default function SqobaPage() {
     }
   };
 
+  const enableBackupsForList = async () => {
+    if (enablingAll) return;
+    setEnablingAll(true);
+    try {
+      const result = await backupApi.setBackupEnabledBulk(
+        filteredGames.map((game) => game.id),
+        true,
+      );
+      notify({
+        tone: "success",
+        title: `Бэкапы включены: ${result.updated.length}`,
+      });
+      if (result.missing_saves.length > 0) {
+        notify({
+          tone: "warning",
+          title: `Сохранения не найдены: ${result.missing_saves.length}`,
+          description: result.missing_saves
+            .map((entry) => entry.name ?? entry.game_id)
+            .join(", "),
+        });
+      }
+      await refreshGames();
+    } catch (e) {
+      console.error("Failed to enable backups:", e);
+      notify({
+        tone: "error",
+        title: "Не удалось включить бэкапы",
+        description: String(e),
+      });
+    } finally {
+      setEnablingAll(false);
+    }
+  };
+
   const handleRefreshManifest = async () => {
     try {
       await refreshSqobaManifest();
@@ -513,6 +548,23 @@ export default function SqobaPage() {
                   )}
                   Найти всё
                 </Button>
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={enableBackupsForList}
+                  disabled={
+                    enablingAll || gamesLoading || filteredGames.length === 0
+                  }
+                  className="gap-2"
+                  title="Включить бэкапы для игр в списке, у которых найдены сохранения"
+                >
+                  {enablingAll ? (
+                    <Loader2 className="h-4 w-4 animate-spin" />
+                  ) : (
+                    <Save className="h-4 w-4" />
+                  )}
+                  Включить бэкапы
+                </Button>
               </div>
             </div>
 
@@ -731,6 +783,11 @@ export default function SqobaPage() {
                                   {filesLookup.data.files.length} файлов •{" "}
                                   {formatBytes(filesLookup.data.total_size)}
                                 </div>
+                                {filesLookup.data.low_confidence && (
+                                  <div className="text-xs text-amber-400">
+                                    Найдено в папке игры по косвенным признакам — проверьте путь
+                                  </div>
+                                )}
                               </div>
                               {filesLookup.data.save_path ? (
                                 <Button
@@ -760,6 +817,11 @@ export default function SqobaPage() {
                                       key={p}
                                       className="font-mono text-[11px] text-muted-foreground break-all"
                                     >
+                                      {filesLookup.data?.container_names?.[p] ? (
+                                        <span className="text-foreground">
+                                          {filesLookup.data.container_names[p]}{" "}
+                                        </span>
+                                      ) : null}
                                       {p}
                                     </div>
                                   ))}
@@ -770,6 +832,22 @@ export default function SqobaPage() {
                                 ) : null}
                               </div>
                             </ScrollArea>
+
+                            {filesLookup.data.registry_keys?.length ? (
+                              <div className="p-3 border-t border-border/60 space-y-1">
+                                <div className="text-xs font-semibold">
+                                  Ключи реестра
+                                </div>
+                                {filesLookup.data.registry_keys.map((key) => (
+                                  <div
+                                    key={key}
+                                    className="font-mono text-[11px] text-muted-foreground break-all"
+                                  >
+                                    {key}
+                                  </div>
+                                ))}
+                              </div>
+                            ) : null}
                           </div>
                         </div>
                       ) : null}
@@ -12,6 +12,8 @@ import {
 } from "recharts";
 import { Activity, BarChart3, Clock, Gamepad2, Loader2 } from "lucide-react";
 import { statsApi } from "@/lib/api";
+import { LargestGamesCard } from "@/components/LargestGamesCard";
+import { LibraryOverviewTiles } from "@/components/LibraryOverviewTiles";
 import { Button } from "@/components/ui/button";
 import {
   Card,
@@ -205,6 +207,10 @@ export default function Statistics() {
   const hasDailyData = (stats?.total_seconds ?? 0) > 0;
   const hasPerGameData = perGameData.length > 0;
   const hasMoreGames = stats && stats.per_game_totals.length > perGameData.length;
+  // Only worth showing once some sessions ran through a profile or a detected store.
+  const launchTotals = (stats?.per_launch_totals ?? []).filter(
+    (entry) => entry.launch_profile || entry.store
+  );
   const selectedMonthValue = useMemo(
     () => getMonthValueFromRange(startDate, endDate),
     [startDate, endDate]
@@ -297,6 +303,10 @@ export default function Statistics() {
         </p>
       </div>
 
+      <LibraryOverviewTiles />
+
+      <LargestGamesCard />
+
       <Card>
         <CardHeader className="space-y-1">
           <CardTitle className="flex items-center gap-2 text-base">
@@ -571,6 +581,39 @@ export default function Statistics() {
           </CardContent>
         </Card>
       </div>
+
+      {launchTotals.length > 0 && (
+        <Card className="bg-card/60">
+          <CardHeader className="space-y-1">
+            <CardTitle className="text-base flex items-center gap-2">
+              <Gamepad2 className="h-4 w-4 text-muted-foreground" />
+              {"\u041f\u0440\u043e\u0444\u0438\u043b\u0438 \u0437\u0430\u043f\u0443\u0441\u043a\u0430 \u0438 \u043c\u0430\u0433\u0430\u0437\u0438\u043d\u044b"}
+            </CardTitle>
+            <CardDescription>{rangeLabel}</CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-2">
+            {launchTotals.map((entry) => (
+              <div
+                key={`${entry.game_id}:${entry.launch_profile ?? ""}:${entry.store ?? ""}`}
+                className="flex items-center justify-between gap-4 text-sm"
+              >
+                <span className="truncate">
+                  {entry.game_name}
+                  <span className="text-muted-foreground">
+                    {" \u2014 "}
+                    {entry.launch_profile ??
+                      "\u041e\u0431\u044b\u0447\u043d\u044b\u0439 \u0437\u0430\u043f\u0443\u0441\u043a"}
+                    {entry.store ? ` (${entry.store})` : ""}
+                  </span>
+                </span>
+                <span className="text-muted-foreground flex-shrink-0">
+                  {formatDuration(entry.seconds)}
+                </span>
+              </div>
+            ))}
+          </CardContent>
+        </Card>
+      )}
     </div>
   );
 }
@@ -8,7 +8,7 @@ import React, {
   useState,
 } from "react";
 import { gamesApi } from "@/lib/api";
-import type { Game, NewGame } from "@/types";
+import type { DeleteGameOptions, Game, NewGame } from "@/types";
 
 interface GamesStateContextType {
   games: Game[];
@@ -23,8 +23,9 @@ interface GamesActionsContextType {
   addGame: (game: NewGame) => Promise<Game>;
   addGames: (games: NewGame[]) => Promise<Game[]>;
   updateGame: (id: string, updates: Partial<Game>) => Promise<Game>;
-  deleteGame: (id: string) => Promise<void>;
+  deleteGame: (id: string, options?: DeleteGameOptions) => Promise<void>;
   toggleFavorite: (id: string) => Promise<Game>;
+  setHidden: (id: string, hidden: boolean) => Promise<Game>;
   searchGames: (query: string) => Promise<Game[]>;
 }
 
@@ -45,7 +46,8 @@ export function GamesProvider({ children }: { children: React.ReactNode }) {
     try {
       setLoading(true);
       setError(null);
-      const allGames = await gamesApi.getAll();
+      // Hidden games stay loaded so their pages keep working; lists filter them out.
+      const allGames = await gamesApi.getAll(true);
       setGames(allGames);
     } catch (e) {
       setError(e instanceof Error ? e.message : "Failed to load games");
@@ -59,7 +61,10 @@ export function GamesProvider({ children }: { children: React.ReactNode }) {
     refreshGames();
   }, [refreshGames]);
 
-  const favorites = useMemo(() => games.filter((g) => g.is_favorite), [games]);
+  const favorites = useMemo(
+    () => games.filter((g) => g.is_favorite && !g.is_hidden),
+    [games],
+  );
   const gamesById = useMemo(
     () => new Map(games.map((game) => [game.id, game])),
     [games],
@@ -85,10 +90,13 @@ export function GamesProvider({ children }: { children: React.ReactNode }) {
     return updated;
   }, []);
 
-  const deleteGame = useCallback(async (id: string): Promise<void> => {
-    await gamesApi.delete(id);
-    setGames((prev) => prev.filter((g) => g.id !== id));
-  }, []);
+  const deleteGame = useCallback(
+    async (id: string, options?: DeleteGameOptions): Promise<void> => {
+      await gamesApi.delete(id, options);
+      setGames((prev) => prev.filter((g) => g.id !== id));
+    },
+    [],
+  );
 
   const toggleFavorite = useCallback(async (id: string): Promise<Game> => {
     const updated = await gamesApi.toggleFavorite(id);
@@ -96,6 +104,15 @@ export function GamesProvider({ children }: { children: React.ReactNode }) {
     return updated;
   }, []);
 
+  const setHidden = useCallback(
+    async (id: string, hidden: boolean): Promise<Game> => {
+      const updated = await gamesApi.setHidden(id, hidden);
+      setGames((prev) => prev.map((g) => (g.id === id ? updated : g)));
+      return updated;
+    },
+    [],
+  );
+
   const getGame = useCallback((id: string): Game | undefined => gamesById.get(id), [gamesById]);
 
   const searchGames = useCallback(async (query: string): Promise<Game[]> => {
@@ -115,6 +132,7 @@ export function GamesProvider({ children }: { children: React.ReactNode }) {
       updateGame,
       deleteGame,
       toggleFavorite,
+      setHidden,
       searchGames,
     }),
     [
@@ -124,6 +142,7 @@ export function GamesProvider({ children }: { children: React.ReactNode }) {
       updateGame,
       deleteGame,
       toggleFavorite,
+      setHidden,
       searchGames,
     ],
   );
@@ -1,11 +1,22 @@
 import {
   backupApi,
+  compatibilityApi,
+  describeBackupError,
+  describeMetadataError,
   gamesApi,
+  igdbApi,
+  imagesApi,
   metadataApi,
+  notesApi,
+  pcgamingwikiApi,
+  pluginsApi,
   scanApi,
+  sessionsApi,
   settingsApi,
   statsApi,
   systemApi,
+  tagsApi,
+  testModeApi,
 } from "@/lib/api";
 import type { AppSettings, NewGame, UpdateGame } from "@/types";
 import { invoke } from "@tauri-apps/api/core";
@@ -23,6 +34,7 @@ const baseSettings: AppSettings = {
   backup_compression_enabled: true,
   backup_compression_level: 60,
   backup_skip_compression_once: false,
+  backup_integrity_check: true,
   max_backups_per_game: 5,
   rawg_api_key: "rawg-key",
 };
@@ -62,6 +74,31 @@ describe("gamesApi", () => {
     };
 
     await runInvokeCase(() => gamesApi.getAll(), "get_all_games");
+    await runInvokeCase(() => gamesApi.getSummaries(), "get_game_summaries");
+    await runInvokeCase(
+      () => gamesApi.getRecentlyPlayed(5),
+      "get_recently_played",
+      { limit: 5 },
+    );
+    await runInvokeCase(() => gamesApi.getMostPlayed(5), "get_most_played", {
+      limit: 5,
+    });
+    await runInvokeCase(() => gamesApi.getLargest(10), "get_largest_games", {
+      limit: 10,
+    });
+    await runInvokeCase(
+      () => gamesApi.computeInstallSize("game-1", true),
+      "compute_install_size",
+      { gameId: "game-1", refresh: true },
+    );
+    await runInvokeCase(
+      () => gamesApi.verifyFiles("game-1"),
+      "verify_game_files",
+      { gameId: "game-1", rebaseline: undefined },
+    );
+    await runInvokeCase(() => gamesApi.getAll(true), "get_all_games", {
+      includeHidden: true,
+    });
     await runInvokeCase(() => gamesApi.get("game-1"), "get_game", { id: "game-1" });
     await runInvokeCase(() => gamesApi.add(newGame), "add_game", { game: newGame });
     await runInvokeCase(
@@ -74,16 +111,89 @@ describe("gamesApi", () => {
       "update_game",
       { update: updateGame },
     );
+    await runInvokeCase(
+      () => gamesApi.updateBatch([updateGame]),
+      "update_games_batch",
+      { updates: [updateGame] },
+    );
     await runInvokeCase(
       () => gamesApi.delete("game-1"),
       "delete_game",
-      { id: "game-1" },
+      { id: "game-1", options: undefined },
+    );
+    await runInvokeCase(
+      () => gamesApi.delete("game-1", { delete_backups: true }),
+      "delete_game",
+      { id: "game-1", options: { delete_backups: true } },
+    );
+    await runInvokeCase(
+      () => gamesApi.deleteBatch(["game-1", "game-2"]),
+      "delete_games_batch",
+      { ids: ["game-1", "game-2"], options: undefined },
+    );
+    await runInvokeCase(
+      () => gamesApi.relocate("game-1", "D:\\Games\\Arcadia\\arcadia.exe"),
+      "relocate_game",
+      { id: "game-1", newExePath: "D:\\Games\\Arcadia\\arcadia.exe" },
+    );
+    await runInvokeCase(() => gamesApi.findMoved(), "find_moved_games", {
+      scanDirs: undefined,
+    });
+    await runInvokeCase(
+      () => gamesApi.findMoved(["D:\\Games"]),
+      "find_moved_games",
+      { scanDirs: ["D:\\Games"] },
     );
     await runInvokeCase(
       () => gamesApi.toggleFavorite("game-1"),
       "toggle_favorite",
       { id: "game-1" },
     );
+    await runInvokeCase(
+      () => gamesApi.setHidden("game-1", true),
+      "set_game_hidden",
+      { id: "game-1", hidden: true },
+    );
+    await runInvokeCase(
+      () => gamesApi.setTotalPlaytime("game-1", 36000),
+      "set_total_playtime",
+      { gameId: "game-1", seconds: 36000 },
+    );
+    await runInvokeCase(
+      () => gamesApi.addPlaytimeAdjustment("game-1", -600, "Steam"),
+      "add_playtime_adjustment",
+      { gameId: "game-1", seconds: -600, note: "Steam" },
+    );
+    await runInvokeCase(
+      () => gamesApi.setSyncOptions("game-1", { backupOnExit: false }),
+      "set_game_sync_options",
+      { id: "game-1", restoreOnLaunch: undefined, backupOnExit: false },
+    );
+    await runInvokeCase(
+      () => gamesApi.setDoNotTrack("game-1", true),
+      "set_game_do_not_track",
+      { id: "game-1", doNotTrack: true },
+    );
+    await runInvokeCase(
+      () => gamesApi.setInstancePolicy("game-1", "refuse"),
+      "set_game_instance_policy",
+      { id: "game-1", policy: "refuse" },
+    );
+    await runInvokeCase(
+      () => gamesApi.setMetadataSource("game-1", "igdb"),
+      "set_game_metadata_source",
+      { id: "game-1", source: "igdb" },
+    );
+    await runInvokeCase(
+      () => gamesApi.setEnvVars("game-1", { DXVK_HUD: "fps" }),
+      "set_game_env_vars",
+      { id: "game-1", envVars: { DXVK_HUD: "fps" } },
+    );
+    await runInvokeCase(
+      () => gamesApi.setLockedFields("game-1", ["name", "genres"]),
+      "set_game_locked_fields",
+      { id: "game-1", fields: ["name", "genres"] },
+    );
     await runInvokeCase(() => gamesApi.getFavorites(), "get_favorites");
     await runInvokeCase(
       () => gamesApi.recordLaunch("game-1"),
@@ -95,6 +205,74 @@ describe("gamesApi", () => {
       "search_games",
       { query: "arcadia" },
     );
+    await runInvokeCase(
+      () =>
+        gamesApi.query({
+          text: "arc",
+          custom_fields: [{ field_id: "field-1", value: "Steam" }],
+        }),
+      "query_games",
+      {
+        query: {
+          text: "arc",
+          custom_fields: [{ field_id: "field-1", value: "Steam" }],
+        },
+      },
+    );
+    await runInvokeCase(
+      () =>
+        gamesApi.getPage(100, 50, "last_played", "desc", {
+          favorites_only: true,
+        }),
+      "get_games_page",
+      {
+        offset: 100,
+        limit: 50,
+        sortBy: "last_played",
+        direction: "desc",
+        filter: { favorites_only: true },
+      },
+    );
+    await runInvokeCase(
+      () => gamesApi.exportReport("C:\\Reports\\library.html", "html"),
+      "export_library_report",
+      {
+        path: "C:\\Reports\\library.html",
+        format: "html",
+      },
+    );
+    await runInvokeCase(
+      () =>
+        gamesApi.exportLibrary("C:\\Backup\\library.json", {
+          tokenise_paths: true,
+        }),
+      "export_library",
+      {
+        path: "C:\\Backup\\library.json",
+        options: { tokenise_paths: true },
+      },
+    );
+    await runInvokeCase(
+      () => gamesApi.importLibrary("C:\\Backup\\library.json", "merge"),
+      "import_library",
+      { path: "C:\\Backup\\library.json", strategy: "merge" },
+    );
+    await runInvokeCase(() => gamesApi.getCustomFields(), "get_custom_fields");
+    await runInvokeCase(
+      () => gamesApi.createCustomField("Store", "text"),
+      "create_custom_field",
+      { name: "Store", fieldType: "text" },
+    );
+    await runInvokeCase(
+      () => gamesApi.deleteCustomField("field-1"),
+      "delete_custom_field",
+      { id: "field-1" },
+    );
+    await runInvokeCase(
+      () => gamesApi.setCustomField("game-1", "field-1", "Steam"),
+      "set_game_custom_field",
+      { gameId: "game-1", fieldId: "field-1", value: "Steam" },
+    );
     await runInvokeCase(
       () => gamesApi.existsByPath("C:\\Games\\Arcadia\\arcadia.exe"),
       "game_exists_by_path",
@@ -106,9 +284,70 @@ describe("gamesApi", () => {
       { id: "game-1" },
     );
     await runInvokeCase(
-      () => gamesApi.launch("game-1"),
+      () => gamesApi.launch("game-1", "profile-1", true),
       "launch_game",
-      { id: "game-1" },
+      { id: "game-1", profileId: "profile-1", minimize: true },
+    );
+    await runInvokeCase(
+      () => gamesApi.createShortcut("game-1", "desktop", "launcher"),
+      "create_shortcut",
+      { gameId: "game-1", location: "desktop", target: "launcher" },
+    );
+    const profile = { game_id: "game-1", name: "Modded", args: ["-mod"] };
+    await runInvokeCase(
+      () => gamesApi.getLaunchProfiles("game-1"),
+      "get_launch_profiles",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => gamesApi.saveLaunchProfile(profile),
+      "save_launch_profile",
+      { profile },
+    );
+    await runInvokeCase(
+      () => gamesApi.deleteLaunchProfile("profile-1"),
+      "delete_launch_profile",
+      { id: "profile-1" },
+    );
+    await runInvokeCase(
+      () => gamesApi.launchWithProfile("game-1", "profile-1", false),
+      "launch_game_with_profile",
+      { gameId: "game-1", profileId: "profile-1", minimize: false },
+    );
+    const emulator = {
+      name: "RetroArch",
+      exe_path: "C:\\RetroArch\\retroarch.exe",
+    };
+    await runInvokeCase(() => gamesApi.getEmulators(), "get_emulators");
+    await runInvokeCase(
+      () => gamesApi.saveEmulator(emulator),
+      "save_emulator",
+      { emulator },
+    );
+    await runInvokeCase(
+      () => gamesApi.deleteEmulator("emulator-1"),
+      "delete_emulator",
+      { id: "emulator-1" },
+    );
+    const companion = {
+      game_id: "game-1",
+      exe_path: "C:\\Tools\\ds4windows.exe",
+      close_on_exit: true,
+    };
+    await runInvokeCase(
+      () => gamesApi.getCompanions("game-1"),
+      "get_game_companions",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => gamesApi.saveCompanion(companion),
+      "save_game_companion",
+      { companion },
+    );
+    await runInvokeCase(
+      () => gamesApi.deleteCompanion("companion-1"),
+      "delete_game_companion",
+      { id: "companion-1" },
     );
     await runInvokeCase(
       () => gamesApi.getRunningInstances("game-1"),
@@ -151,6 +390,125 @@ describe("metadataApi", () => {
       { key: "rawg-key" },
     );
     await runInvokeCase(() => metadataApi.getApiKey(), "get_rawg_api_key");
+    await runInvokeCase(
+      () => metadataApi.suggestMatches("game-1"),
+      "suggest_metadata_matches",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => metadataApi.importBundle("C:/bundles/games.json"),
+      "import_metadata_bundle",
+      { path: "C:/bundles/games.json" },
+    );
+  });
+});
+
+describe("igdbApi", () => {
+  it("dispatches IGDB commands", async () => {
+    await runInvokeCase(() => igdbApi.search("arcadia"), "search_igdb", {
+      query: "arcadia",
+    });
+    await runInvokeCase(
+      () => igdbApi.getDetails(1942),
+      "get_igdb_game_details",
+      { igdbId: 1942 },
+    );
+    await runInvokeCase(
+      () => igdbApi.apply("game-1", 1942, false),
+      "apply_igdb_metadata",
+      { gameId: "game-1", igdbId: 1942, rename: false },
+    );
+    const overwrite = {
+      description: false,
+      released: true,
+      background_image: true,
+      scores: true,
+      genres: false,
+      platforms: true,
+      developers: true,
+      publishers: true,
+    };
+    await runInvokeCase(
+      () => igdbApi.apply("game-1", 1942, true, overwrite),
+      "apply_igdb_metadata",
+      { gameId: "game-1", igdbId: 1942, rename: true, overwrite },
+    );
+    await runInvokeCase(
+      () => igdbApi.setCredentials("client", "secret"),
+      "set_igdb_credentials",
+      { clientId: "client", clientSecret: "secret" },
+    );
+    await runInvokeCase(() => igdbApi.getCredentials(), "get_igdb_credentials");
+  });
+});
+
+describe("describeBackupError", () => {
+  it("shows the shortage numbers and passes other errors through", () => {
+    expect(
+      describeBackupError({
+        kind: "no_space",
+        mount_point: "D:\\",
+        required_bytes: 3 * 1024 * 1024 - 1,
+        available_bytes: 1024 * 1024 + 1,
+      }),
+    ).toBe("Недостаточно места на диске D:\\: требуется 3 МБ, свободно 1 МБ");
+    expect(
+      describeBackupError({ kind: "other", message: "Сохранения не найдены" }),
+    ).toBe("Сохранения не найдены");
+    expect(describeBackupError("boom")).toBe("boom");
+  });
+});
+
+describe("describeMetadataError", () => {
+  it("explains each metadata error kind", () => {
+    expect(
+      describeMetadataError({ kind: "rate_limited", retry_after: 30 }, "RAWG"),
+    ).toBe("RAWG ограничил число запросов. Повторите через 30 с.");
+    expect(
+      describeMetadataError(
+        { kind: "unauthorized", message: "RAWG rejected the credentials" },
+        "RAWG",
+      ),
+    ).toBe("RAWG не принял ключ. Проверьте его в настройках.");
+    expect(
+      describeMetadataError({ kind: "other", message: "Parse error" }, "IGDB"),
+    ).toBe("Parse error");
+    expect(describeMetadataError("boom", "IGDB")).toBe("boom");
+  });
+});
+
+describe("pcgamingwikiApi", () => {
+  it("dispatches PCGamingWiki commands", async () => {
+    await runInvokeCase(
+      () => pcgamingwikiApi.getLocations("Hollow Knight"),
+      "get_pcgamingwiki_locations",
+      { gameName: "Hollow Knight" },
+    );
+    await runInvokeCase(
+      () => pcgamingwikiApi.getSavePaths("game-1"),
+      "get_pcgamingwiki_save_paths",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => pcgamingwikiApi.setSavePaths("game-1", ["<winAppData>/Studio"]),
+      "set_pcgamingwiki_save_paths",
+      { gameId: "game-1", paths: ["<winAppData>/Studio"] },
+    );
+  });
+});
+
+describe("compatibilityApi", () => {
+  it("dispatches compatibility commands", async () => {
+    await runInvokeCase(
+      () => compatibilityApi.refresh("game-1"),
+      "refresh_game_compatibility",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => compatibilityApi.refresh("game-1", "367520"),
+      "refresh_game_compatibility",
+      { gameId: "game-1", appId: "367520" },
+    );
   });
 });
 
@@ -184,20 +542,156 @@ describe("backupApi", () => {
       { gameName: "Arcadia", gameId: "game-1" },
     );
     await runInvokeCase(
-      () => backupApi.create("game-1", "Arcadia", true, "notes"),
+      () => backupApi.getManifestInfo(),
+      "get_manifest_info",
+    );
+    await runInvokeCase(
+      () => backupApi.refreshManifest(true),
+      "refresh_manifest",
+      { force: true },
+    );
+    await runInvokeCase(
+      () => backupApi.setBackupEnabledBulk(["game-1", "game-2"], true, false),
+      "set_backup_enabled_bulk",
+      { gameIds: ["game-1", "game-2"], enabled: true, force: false },
+    );
+    await runInvokeCase(
+      () => backupApi.suggestSavePaths("game-1"),
+      "suggest_save_paths",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => backupApi.getSavePaths("game-1"),
+      "get_game_save_paths",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () =>
+        backupApi.addSavePath("game-1", "C:/Users/me/AppData/Game", "Config"),
+      "add_game_save_path",
+      {
+        gameId: "game-1",
+        path: "C:/Users/me/AppData/Game",
+        label: "Config",
+        exclude: undefined,
+      },
+    );
+    await runInvokeCase(
+      () => backupApi.updateSavePath("path-1", undefined, false),
+      "update_game_save_path",
+      { id: "path-1", label: undefined, enabled: false, exclude: undefined },
+    );
+    await runInvokeCase(
+      () => backupApi.removeSavePath("path-1"),
+      "remove_game_save_path",
+      { id: "path-1" },
+    );
+    await runInvokeCase(
+      () => backupApi.getManifestMatch("game-1"),
+      "get_manifest_match",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => backupApi.confirmManifestMatch("game-1", "Arcadia Remastered"),
+      "confirm_manifest_match",
+      { gameId: "game-1", manifestKey: "Arcadia Remastered" },
+    );
+    await runInvokeCase(
+      () => backupApi.getManifestOverride("game-1"),
+      "get_manifest_override",
+      { gameId: "game-1" },
+    );
+    const fragment = {
+      files: ["<winAppData>/Arcadia/Saves"],
+      registry: [],
+      replace_upstream: false,
+    };
+    await runInvokeCase(
+      () => backupApi.setManifestOverride("game-1", fragment),
+      "set_manifest_override",
+      { gameId: "game-1", fragment },
+    );
+    await runInvokeCase(
+      () => backupApi.create("game-1", "Arcadia", true, "notes", "Act 2"),
       "create_backup",
-      { gameId: "game-1", gameName: "Arcadia", isAuto: true, notes: "notes" },
+      {
+        gameId: "game-1",
+        gameName: "Arcadia",
+        isAuto: true,
+        notes: "notes",
+        label: "Act 2",
+      },
     );
     await runInvokeCase(
       () => backupApi.getForGame("game-1"),
       "get_game_backups",
       { gameId: "game-1" },
     );
+    await runInvokeCase(
+      () => backupApi.getHistory("game-1"),
+      "get_backup_history",
+      { gameId: "game-1" },
+    );
     await runInvokeCase(
       () => backupApi.restore("backup-1"),
       "restore_backup",
       { backupId: "backup-1" },
     );
+    await runInvokeCase(
+      () => backupApi.restore("backup-1", undefined, true),
+      "restore_backup",
+      { backupId: "backup-1", overwriteNewer: true },
+    );
+    await runInvokeCase(
+      () => backupApi.previewRestore("backup-1"),
+      "preview_restore",
+      { backupId: "backup-1" },
+    );
+    await runInvokeCase(
+      () => backupApi.planRestorePaths("backup-1", { "D:\\Saves": "E:\\Saves" }),
+      "plan_restore_paths",
+      { backupId: "backup-1", pathMappings: { "D:\\Saves": "E:\\Saves" } },
+    );
+    await runInvokeCase(
+      () => backupApi.repair("backup-1"),
+      "repair_backup",
+      { backupId: "backup-1" },
+    );
+    await runInvokeCase(() => backupApi.getStats(), "get_backup_stats");
+    await runInvokeCase(
+      () => backupApi.diff("backup-1", "backup-2"),
+      "diff_backups",
+      { backupA: "backup-1", backupB: "backup-2" },
+    );
+    await runInvokeCase(
+      () => backupApi.getDiagnostics(),
+      "get_backup_diagnostics",
+    );
+    await runInvokeCase(
+      () => backupApi.resolveStalled("op-1", "skip_file"),
+      "resolve_stalled_operation",
+      { operationId: "op-1", action: "skip_file" },
+    );
+    await runInvokeCase(
+      () => backupApi.getInstallArchive("game-1"),
+      "get_install_archive",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => backupApi.archiveInstall("game-1", "D:/Games/Arcadia"),
+      "archive_game_install",
+      { gameId: "game-1", installDir: "D:/Games/Arcadia" },
+    );
+    await runInvokeCase(
+      () => backupApi.unarchiveInstall("game-1"),
+      "unarchive_game_install",
+      { gameId: "game-1", targetDir: undefined },
+    );
+    await runInvokeCase(
+      () => backupApi.benchmarkCompression("game-1"),
+      "benchmark_compression",
+      { gameId: "game-1" },
+    );
     await runInvokeCase(
       () => backupApi.delete("backup-1"),
       "delete_backup",
@@ -257,6 +751,20 @@ describe("settingsApi", () => {
       () => settingsApi.getScanDirectories(),
       "get_scan_directories",
     );
+    await runInvokeCase(
+      () => settingsApi.listScanDirectories(),
+      "list_scan_directories",
+    );
+    await runInvokeCase(
+      () => settingsApi.setScanDirectoryAutoScan("C:\\Games", true),
+      "set_scan_directory_auto_scan",
+      { path: "C:\\Games", enabled: true },
+    );
+    await runInvokeCase(
+      () => settingsApi.setScanDirectoryThreads("\\\\NAS\\games", 2),
+      "set_scan_directory_threads",
+      { path: "\\\\NAS\\games", threads: 2 },
+    );
     await runInvokeCase(
       () => settingsApi.removeScanDirectory("C:\\Games"),
       "remove_scan_directory",
@@ -272,6 +780,22 @@ describe("statsApi", () => {
       "get_playtime_stats",
       { start: "2024-01-01", end: "2024-01-31" },
     );
+    await runInvokeCase(
+      () =>
+        statsApi.generateStatsCard("C:/recap.svg", {
+          start: "2024-01-01",
+          end: "2024-01-31",
+        }),
+      "generate_stats_card",
+      {
+        range: { start: "2024-01-01", end: "2024-01-31" },
+        path: "C:/recap.svg",
+      },
+    );
+    await runInvokeCase(
+      () => statsApi.getLibraryOverview(),
+      "get_library_overview",
+    );
   });
 });
 
@@ -292,5 +816,178 @@ describe("systemApi", () => {
       "test_disk_speed",
       { mountPoint: "C:" },
     );
+    await runInvokeCase(() => systemApi.getAppInfo(), "get_app_info");
+    await runInvokeCase(
+      () => systemApi.getDatabaseStatus(),
+      "get_database_status",
+    );
+    await runInvokeCase(
+      () => systemApi.retryDatabaseOpen(),
+      "retry_database_open",
+    );
+  });
+});
+
+describe("tagsApi", () => {
+  it("dispatches tag and collection commands", async () => {
+    await runInvokeCase(() => tagsApi.list(), "get_tags");
+    await runInvokeCase(() => tagsApi.add("Co-op", "#22c55e"), "add_tag", {
+      name: "Co-op",
+      color: "#22c55e",
+    });
+    await runInvokeCase(() => tagsApi.delete("tag-1"), "delete_tag", {
+      id: "tag-1",
+    });
+    await runInvokeCase(() => tagsApi.assign("game-1", "tag-1"), "assign_tag", {
+      gameId: "game-1",
+      tagId: "tag-1",
+    });
+    await runInvokeCase(
+      () => tagsApi.unassign("game-1", "tag-1"),
+      "unassign_tag",
+      { gameId: "game-1", tagId: "tag-1" },
+    );
+    await runInvokeCase(() => tagsApi.getGames("tag-1"), "get_games_by_tag", {
+      tagId: "tag-1",
+    });
+    const collection = { name: "Long RPGs", query: { genre: "RPG" } };
+    await runInvokeCase(() => tagsApi.getCollections(), "get_collections");
+    await runInvokeCase(
+      () => tagsApi.saveCollection(collection),
+      "save_collection",
+      { collection },
+    );
+    await runInvokeCase(
+      () => tagsApi.deleteCollection("collection-1"),
+      "delete_collection",
+      { id: "collection-1" },
+    );
+    await runInvokeCase(
+      () => tagsApi.getCollectionGames("collection-1"),
+      "get_collection_games",
+      { id: "collection-1" },
+    );
+  });
+});
+
+describe("imagesApi", () => {
+  it("dispatches cover image commands", async () => {
+    await runInvokeCase(
+      () => imagesApi.importCover("game-1", "C:\\Pictures\\cover.png"),
+      "import_cover_image",
+      { gameId: "game-1", sourcePath: "C:\\Pictures\\cover.png" },
+    );
+    await runInvokeCase(
+      () => imagesApi.cacheCover("game-1", "https://media.example/cover.jpg"),
+      "cache_cover_image",
+      { gameId: "game-1", url: "https://media.example/cover.jpg" },
+    );
+    await runInvokeCase(
+      () => imagesApi.generateThumbnail("game-1"),
+      "generate_cover_thumbnail",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(() => imagesApi.cleanUnused(), "clean_unused_images");
+    await runInvokeCase(
+      () => imagesApi.getCacheStats(),
+      "get_image_cache_stats",
+    );
+    await runInvokeCase(
+      () => imagesApi.setPruningPaused(true),
+      "set_image_cache_pruning_paused",
+      { paused: true },
+    );
+  });
+});
+
+describe("pluginsApi", () => {
+  it("dispatches plugin commands", async () => {
+    await runInvokeCase(() => pluginsApi.list(), "list_plugins");
+    await runInvokeCase(
+      () => pluginsApi.setEnabled("notify.ps1", true),
+      "set_plugin_enabled",
+      { id: "notify.ps1", enabled: true },
+    );
+    await runInvokeCase(() => pluginsApi.getDirectory(), "get_plugins_directory");
+  });
+});
+
+describe("testModeApi", () => {
+  it("dispatches test-mode commands", async () => {
+    const fixture = {
+      games: [{ id: "game-1", name: "Game", exe_path: "C:\\Game\\game.exe" }],
+    };
+    await runInvokeCase(() => testModeApi.resetDb(), "__test_reset_db");
+    await runInvokeCase(
+      () => testModeApi.seedLibrary(fixture),
+      "__test_seed_library",
+      { fixture },
+    );
+    await runInvokeCase(
+      () => testModeApi.advanceClock(86400),
+      "__test_advance_clock",
+      { seconds: 86400 },
+    );
+  });
+});
+
+describe("sessionsApi", () => {
+  it("dispatches session note commands", async () => {
+    await runInvokeCase(
+      () => sessionsApi.setNote(7, "reached act 3"),
+      "set_session_note",
+      { sessionId: 7, note: "reached act 3" },
+    );
+    await runInvokeCase(
+      () => sessionsApi.searchNotes("act", "game-1"),
+      "search_session_notes",
+      { query: "act", gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () => sessionsApi.getLaunchHistory("game-1"),
+      "get_launch_history",
+      { gameId: "game-1" },
+    );
+    await runInvokeCase(
+      () =>
+        sessionsApi.getGameSessions("game-1", { start: "2024-03-01" }),
+      "get_game_sessions",
+      { gameId: "game-1", range: { start: "2024-03-01" } },
+    );
+    await runInvokeCase(() => sessionsApi.getRecent(), "get_recent_sessions");
+  });
+});
+
+describe("notesApi", () => {
+  it("dispatches note and link commands", async () => {
+    await runInvokeCase(() => notesApi.list("game-1"), "get_game_notes", {
+      gameId: "game-1",
+    });
+    await runInvokeCase(
+      () => notesApi.add("game-1", "# Act 2"),
+      "add_game_note",
+      { gameId: "game-1", body: "# Act 2" },
+    );
+    await runInvokeCase(
+      () => notesApi.update("note-1", "# Act 3"),
+      "update_game_note",
+      { id: "note-1", body: "# Act 3" },
+    );
+    await runInvokeCase(() => notesApi.delete("note-1"), "delete_game_note", {
+      id: "note-1",
+    });
+    await runInvokeCase(() => notesApi.listLinks("game-1"), "get_game_links", {
+      gameId: "game-1",
+    });
+    await runInvokeCase(
+      () => notesApi.addLink("game-1", "https://wiki.example", "Wiki"),
+      "add_game_link",
+      { gameId: "game-1", url: "https://wiki.example", title: "Wiki" },
+    );
+    await runInvokeCase(
+      () => notesApi.deleteLink("link-1"),
+      "delete_game_link",
+      { id: "link-1" },
+    );
   });
 });
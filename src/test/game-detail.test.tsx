@@ -12,6 +12,7 @@ const {
   gamesApiMock,
   backupApiMock,
   metadataApiMock,
+  notesApiMock,
 } = vi.hoisted(() => ({
   toggleFavoriteMock: vi.fn(),
   refreshGamesMock: vi.fn(),
@@ -21,6 +22,7 @@ const {
     getRunningInstances: vi.fn(),
     killProcesses: vi.fn(),
     launch: vi.fn(),
+    getLaunchProfiles: vi.fn(),
     update: vi.fn(),
   },
   backupApiMock: {
@@ -30,13 +32,27 @@ const {
     checkBackupNeeded: vi.fn(),
     create: vi.fn(),
     restore: vi.fn(),
+    previewRestore: vi.fn(),
     delete: vi.fn(),
+    getInstallArchive: vi.fn(),
+    archiveInstall: vi.fn(),
+    unarchiveInstall: vi.fn(),
   },
   metadataApiMock: {
     search: vi.fn(),
+    suggestMatches: vi.fn(),
     getDetails: vi.fn(),
     apply: vi.fn(),
   },
+  notesApiMock: {
+    list: vi.fn(),
+    listLinks: vi.fn(),
+    add: vi.fn(),
+    update: vi.fn(),
+    delete: vi.fn(),
+    addLink: vi.fn(),
+    deleteLink: vi.fn(),
+  },
 }));
 
 vi.mock("@/store/GamesContext", () => ({
@@ -44,9 +60,19 @@ vi.mock("@/store/GamesContext", () => ({
   useGamesState: vi.fn(),
 }));
 vi.mock("@/lib/api", () => ({
+  ALREADY_RUNNING_ERROR: "Игра уже запущена",
+  describeMetadataError: (e: unknown) => String(e),
   gamesApi: gamesApiMock,
   backupApi: backupApiMock,
   metadataApi: metadataApiMock,
+  igdbApi: { search: vi.fn(), apply: vi.fn() },
+  compatibilityApi: { refresh: vi.fn() },
+  pcgamingwikiApi: {
+    getLocations: vi.fn(),
+    getSavePaths: vi.fn(),
+    setSavePaths: vi.fn(),
+  },
+  notesApi: notesApiMock,
 }));
 vi.mock("@/components/ToastProvider", () => ({
   useToast: () => ({ notify: vi.fn() }),
@@ -55,7 +81,10 @@ vi.mock("@tauri-apps/api/event", () => ({
   listen: vi.fn().mockResolvedValue(() => {}),
 }));
 vi.mock("@tauri-apps/plugin-dialog", () => ({ open: vi.fn() }));
-vi.mock("@tauri-apps/plugin-opener", () => ({ openPath: vi.fn() }));
+vi.mock("@tauri-apps/plugin-opener", () => ({
+  openPath: vi.fn(),
+  openUrl: vi.fn(),
+}));
 
 const useGamesActionsMock = vi.mocked(useGamesActions);
 const useGamesStateMock = vi.mocked(useGamesState);
@@ -65,7 +94,11 @@ describe("GameDetail", () => {
     vi.clearAllMocks();
     gamesApiMock.isInstalled.mockResolvedValue(true);
     gamesApiMock.getRunningInstances.mockResolvedValue(0);
+    gamesApiMock.getLaunchProfiles.mockResolvedValue([]);
     backupApiMock.getForGame.mockResolvedValue([]);
+    backupApiMock.getInstallArchive.mockResolvedValue(null);
+    notesApiMock.list.mockResolvedValue([]);
+    notesApiMock.listLinks.mockResolvedValue([]);
     backupApiMock.checkRestoreNeeded.mockResolvedValue({
       should_restore: false,
       backup_id: null,
@@ -17,6 +17,11 @@ vi.mock("@/components/ToastProvider", () => ({
 }));
 vi.mock("@/lib/api", () => ({
   gamesApi: { existsByPath: vi.fn(), resolveShortcutTarget: vi.fn() },
+  tagsApi: {
+    list: vi.fn().mockResolvedValue([]),
+    getCollections: vi.fn().mockResolvedValue([]),
+    getCollectionGames: vi.fn().mockResolvedValue([]),
+  },
   metadataApi: { getApiKey: vi.fn(), search: vi.fn(), apply: vi.fn() },
 }));
 
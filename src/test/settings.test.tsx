@@ -2,11 +2,15 @@ import { render, screen, waitFor } from "@testing-library/react";
 import userEvent from "@testing-library/user-event";
 import Settings from "@/pages/Settings";
 import { ThemeProvider } from "@/components/theme-provider";
-import type { AppSettings } from "@/types";
+import type { AppSettings, ManifestInfo } from "@/types";
 
 const {
   settingsApiMock,
   metadataApiMock,
+  igdbApiMock,
+  backupApiMock,
+  pluginsApiMock,
+  gamesApiMock,
   enableMock,
   disableMock,
   isEnabledMock,
@@ -14,10 +18,32 @@ const {
   settingsApiMock: {
     getAll: vi.fn(),
     update: vi.fn(),
+    get: vi.fn().mockResolvedValue(null),
+    set: vi.fn(),
   },
   metadataApiMock: {
     setApiKey: vi.fn(),
   },
+  igdbApiMock: {
+    getCredentials: vi
+      .fn()
+      .mockResolvedValue({ client_id: "", client_secret: "" }),
+    setCredentials: vi.fn(),
+  },
+  backupApiMock: {
+    getManifestInfo: vi.fn(),
+    refreshManifest: vi.fn(),
+  },
+  pluginsApiMock: {
+    list: vi.fn(),
+    setEnabled: vi.fn(),
+    getDirectory: vi.fn(),
+  },
+  gamesApiMock: {
+    getEmulators: vi.fn().mockResolvedValue([]),
+    saveEmulator: vi.fn(),
+    deleteEmulator: vi.fn(),
+  },
   enableMock: vi.fn(),
   disableMock: vi.fn(),
   isEnabledMock: vi.fn(),
@@ -26,6 +52,16 @@ const {
 vi.mock("@/lib/api", () => ({
   settingsApi: settingsApiMock,
   metadataApi: metadataApiMock,
+  igdbApi: igdbApiMock,
+  backupApi: backupApiMock,
+  pluginsApi: pluginsApiMock,
+  gamesApi: gamesApiMock,
+}));
+vi.mock("@/components/ToastProvider", () => ({
+  useToast: () => ({ notify: vi.fn() }),
+}));
+vi.mock("@tauri-apps/api/event", () => ({
+  listen: vi.fn().mockResolvedValue(() => {}),
 }));
 vi.mock("@tauri-apps/plugin-autostart", () => ({
   enable: enableMock,
@@ -33,6 +69,7 @@ vi.mock("@tauri-apps/plugin-autostart", () => ({
   isEnabled: isEnabledMock,
 }));
 vi.mock("@tauri-apps/plugin-dialog", () => ({ open: vi.fn() }));
+vi.mock("@tauri-apps/plugin-opener", () => ({ openPath: vi.fn() }));
 
 const baseSettings: AppSettings = {
   theme: "dark",
@@ -43,10 +80,22 @@ const baseSettings: AppSettings = {
   backup_compression_enabled: true,
   backup_compression_level: 60,
   backup_skip_compression_once: false,
+  backup_integrity_check: true,
   max_backups_per_game: 5,
   rawg_api_key: "rawg-key",
 };
 
+const manifestInfo: ManifestInfo = {
+  etag: '"abc"',
+  last_modified: "Tue, 10 Mar 2026 12:00:00 GMT",
+  fetched_at: "2026-03-10T12:00:00Z",
+  checked_at: "2026-03-10T12:00:00Z",
+  refresh_interval_hours: 168,
+  cache_path: "C:\\Users\\me\\AppData\\Local\\arrancador\\sqoba_manifest.json",
+  cache_bytes: 4096,
+  game_count: 1200,
+};
+
 const renderSettings = () =>
   render(
     <ThemeProvider>
@@ -60,7 +109,11 @@ describe("Settings", () => {
     settingsApiMock.getAll.mockResolvedValue(baseSettings);
     settingsApiMock.update.mockResolvedValue(undefined);
     metadataApiMock.setApiKey.mockResolvedValue(undefined);
+    backupApiMock.getManifestInfo.mockResolvedValue(manifestInfo);
+    backupApiMock.refreshManifest.mockResolvedValue(manifestInfo);
     isEnabledMock.mockResolvedValue(false);
+    pluginsApiMock.list.mockResolvedValue([]);
+    pluginsApiMock.setEnabled.mockResolvedValue(undefined);
   });
 
   it("loads settings and saves RAWG API changes", async () => {
@@ -98,4 +151,43 @@ describe("Settings", () => {
     await waitFor(() => expect(enableMock).toHaveBeenCalled());
     expect(disableMock).not.toHaveBeenCalled();
   });
+
+  it("reports an unchanged manifest after a conditional refresh", async () => {
+    renderSettings();
+
+    expect(await screen.findByText(/1200 игр/)).toBeInTheDocument();
+    await userEvent.click(screen.getByRole("button", { name: "Обновить" }));
+
+    await waitFor(() =>
+      expect(backupApiMock.refreshManifest).toHaveBeenCalledWith(false),
+    );
+    expect(await screen.findByText("Манифест уже актуален")).toBeInTheDocument();
+  });
+
+  it("enables a discovered plugin and shows its last failure", async () => {
+    pluginsApiMock.list.mockResolvedValue([
+      {
+        id: "sync.ps1",
+        path: "C:\\Users\\me\\AppData\\Local\\arrancador\\plugins\\sync.ps1",
+        kind: "powershell",
+        enabled: false,
+        last_run: {
+          event: "backup_completed",
+          at: "2026-03-10T12:00:00Z",
+          duration_ms: 10000,
+          error: "Плагин не ответил за 10 с и был остановлен",
+        },
+      },
+    ]);
+    renderSettings();
+
+    expect(
+      await screen.findByText("Плагин не ответил за 10 с и был остановлен"),
+    ).toBeInTheDocument();
+    await userEvent.click(screen.getByRole("switch", { name: "sync.ps1" }));
+
+    await waitFor(() =>
+      expect(pluginsApiMock.setEnabled).toHaveBeenCalledWith("sync.ps1", true),
+    );
+  });
 });
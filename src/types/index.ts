@@ -15,9 +15,19 @@ export interface Game {
   platforms: string | null;
   developers: string | null;
   publishers: string | null;
+  igdb_id: number | null;
+  /** Provider the metadata search uses for this game; `null` means RAWG. */
+  metadata_source: MetadataSource | null;
+  /** ProtonDB rating (`platinum` … `borked`), as last fetched. */
+  protondb_tier: string | null;
+  steam_deck_status: SteamDeckStatus | null;
+  /** Fields applying metadata leaves alone, by hand or automatically. */
+  locked_fields: MetadataField[];
 
   // Local metadata
   cover_image: string | null;
+  /** Downscaled copy of a locally stored cover, for the library grid. */
+  cover_thumbnail: string | null;
   is_favorite: boolean;
   play_count: number;
   total_playtime: number;
@@ -29,9 +39,120 @@ export interface Game {
   last_backup: string | null;
   backup_count: number;
   save_path: string | null;
+  restore_on_launch: boolean;
+  backup_on_exit: boolean;
+  /** Playtime, sessions and last-played are not recorded; launching and backups still work. */
+  do_not_track: boolean;
+  /** Left out of the library list, favourites and search; playtime and backups are kept. */
+  is_hidden: boolean;
+  /** The latest launch crashed within seconds or never started. */
+  last_launch_failed: boolean;
+  /** Bytes the install folder takes, as last measured; `null` until it has been. */
+  install_size: number | null;
 
   user_rating: number | null;
   user_note: string | null;
+  /** Extra command-line arguments for a plain launch. */
+  launch_args: string | null;
+  /** Directory the game starts in instead of the executable's folder. */
+  working_dir: string | null;
+  /** Environment variables set for every launch; a profile's own wins on conflicts. */
+  env_vars: Record<string, string>;
+  /** How the plain launch button starts the game. */
+  launch_method: LaunchMethod;
+  /** Steam app id, Epic app name (or `namespace:catalogItemId:appName`) or GOG product id, depending on `launch_method`. */
+  store_id: string | null;
+  /** Set for ROM games; `exe_path` is then the ROM, started through this emulator. */
+  emulator_id: string | null;
+  /** What launching does while the game runs; `null` follows the `running_instance_policy` setting. */
+  instance_policy: InstancePolicy | null;
+  /** User-defined field values keyed by custom field id. */
+  custom_fields: Record<string, string>;
+  /** Ids of the tags on this game. */
+  tags: string[];
+}
+
+export type CustomFieldType = "text" | "number" | "date" | "boolean";
+
+export interface CustomField {
+  id: string;
+  name: string;
+  field_type: CustomFieldType;
+  created_at: string;
+}
+
+export interface CustomFieldFilter {
+  field_id: string;
+  /** Omit to match any game that has a value for the field. */
+  value?: string | null;
+}
+
+export type ShortcutLocation = "desktop" | "start_menu";
+
+/** `launcher`, the default, starts the game through an `arrancador://launch/<id>` link. */
+export type ShortcutTarget = "executable" | "launcher";
+
+/** What the library grid needs for a tile; `gamesApi.get` loads the full game. */
+export interface GameSummary {
+  id: string;
+  name: string;
+  cover_image: string | null;
+  cover_thumbnail: string | null;
+  is_favorite: boolean;
+  last_played: string | null;
+  total_playtime: number;
+  install_size: number | null;
+}
+
+export type GameSort =
+  | "name"
+  | "last_played"
+  | "date_added"
+  | "play_count"
+  | "playtime"
+  | "rating";
+
+export type SortDirection = "asc" | "desc";
+
+export interface GamePage {
+  games: Game[];
+  /** Games matching the filter across all pages. */
+  total: number;
+}
+
+export interface GameQuery {
+  text?: string | null;
+  favorites_only?: boolean;
+  include_hidden?: boolean;
+  custom_fields?: CustomFieldFilter[];
+  /** Games carrying every one of these tags. */
+  tag_ids?: string[];
+  genre?: string | null;
+  /** Bounds on total playtime, in seconds. */
+  min_playtime?: number | null;
+  max_playtime?: number | null;
+}
+
+export interface Tag {
+  id: string;
+  name: string;
+  color: string | null;
+  game_count: number;
+  created_at: string;
+}
+
+/** A saved filter; its games are worked out again every time it is opened. */
+export interface Collection {
+  id: string;
+  name: string;
+  query: GameQuery;
+  created_at: string;
+}
+
+export interface CollectionInput {
+  id?: string | null;
+  name: string;
+  query: GameQuery;
 }
 
 export const testGameFixture: Game = {
@@ -49,6 +170,11 @@ export const testGameFixture: Game = {
   platforms: "PC",
   developers: "Arcadia Studio",
   publishers: "Arcadia Publishing",
+  igdb_id: null,
+  metadata_source: null,
+  protondb_tier: null,
+  steam_deck_status: null,
+  locked_fields: [],
   cover_image: null,
   is_favorite: false,
   play_count: 2,
@@ -59,8 +185,24 @@ export const testGameFixture: Game = {
   last_backup: "2024-01-02T09:00:00.000Z",
   backup_count: 1,
   save_path: "C:\\Games\\Arcadia\\saves",
+  restore_on_launch: true,
+  backup_on_exit: true,
+  do_not_track: false,
+  is_hidden: false,
+  last_launch_failed: false,
+  install_size: null,
+  cover_thumbnail: null,
   user_rating: 5,
   user_note: "Great game",
+  launch_args: null,
+  working_dir: null,
+  env_vars: {},
+  launch_method: "direct",
+  store_id: null,
+  emulator_id: null,
+  instance_policy: null,
+  custom_fields: {},
+  tags: [],
 };
 
 export const testFavoriteGameFixture: Game = {
@@ -82,6 +224,24 @@ export const createTestGame = (overrides: Partial<Game> = {}): Game => ({
   ...overrides,
 });
 
+/** Settings, tags and backup records always go with a deleted game. */
+export interface DeleteGameOptions {
+  /** Also remove the backup archives and an archived install; required if the install is archived. */
+  delete_backups?: boolean;
+  /** Also remove playtime history and sessions. */
+  delete_stats?: boolean;
+}
+
+/** A file in the scan folders that looks like a missing game's moved executable. */
+export interface MovedGameMatch {
+  game_id: string;
+  game_name: string;
+  old_exe_path: string;
+  new_exe_path: string;
+  /** `null` when no size was recorded for the game. */
+  size_matches: boolean | null;
+}
+
 export interface NewGame {
   name: string;
   exe_path: string;
@@ -107,6 +267,124 @@ export interface UpdateGame {
   publishers?: string | null;
   user_rating?: number | null;
   user_note?: string | null;
+  /** Empty string clears the arguments. */
+  launch_args?: string | null;
+  /** Empty string goes back to the executable's folder. */
+  working_dir?: string | null;
+  launch_method?: LaunchMethod;
+  /** Empty string clears the store id. */
+  store_id?: string | null;
+  /** Empty string turns the game back into a plain executable. */
+  emulator_id?: string | null;
+}
+
+export type LaunchMethod = "direct" | "steam" | "epic" | "gog";
+
+/** Launching a game that is already running focuses it, refuses, or starts another copy. */
+export type InstancePolicy = "focus" | "refuse" | "allow";
+
+export type MetadataSource = "rawg" | "igdb";
+
+// Valve's Steam Deck verification; `unknown` means not reviewed yet.
+export type SteamDeckStatus = "verified" | "playable" | "unsupported" | "unknown";
+
+// Fields applying metadata may replace; the rest are only filled in when empty.
+export interface MetadataOverwrite {
+  description: boolean;
+  released: boolean;
+  background_image: boolean;
+  // metacritic and rating
+  scores: boolean;
+  genres: boolean;
+  platforms: boolean;
+  developers: boolean;
+  publishers: boolean;
+}
+
+// A metadata field that can be locked against metadata refreshes.
+export type MetadataField = "name" | keyof MetadataOverwrite;
+
+// Result of importing an offline metadata bundle; `unmatched` counts library games without an entry.
+export interface MetadataBundleSummary {
+  entries: number;
+  matched: number;
+  unmatched: number;
+}
+
+// How a metadata command (RAWG, IGDB, PCGamingWiki) failed.
+export type MetadataError =
+  | { kind: "rate_limited"; retry_after: number | null }
+  | { kind: "unauthorized"; message: string }
+  | { kind: "not_found"; message: string }
+  | { kind: "network"; message: string }
+  | { kind: "other"; message: string };
+
+/** `already_running`: the game runs but no window of it could be focused. */
+export type LaunchResult = "started" | "focused" | "already_running";
+
+export interface LaunchProfile {
+  id: string;
+  game_id: string;
+  name: string;
+  target: string | null;
+  args: string[];
+  env: Record<string, string>;
+  working_dir: string | null;
+  pre_launch: string | null;
+  post_exit: string | null;
+  /** Used when the game is launched without picking a profile. */
+  is_default: boolean;
+  created_at: string;
+}
+
+export interface LaunchProfileInput {
+  id?: string | null;
+  game_id: string;
+  name: string;
+  target?: string | null;
+  args?: string[];
+  env?: Record<string, string>;
+  working_dir?: string | null;
+  pre_launch?: string | null;
+  post_exit?: string | null;
+  is_default?: boolean;
+}
+
+export interface Emulator {
+  id: string;
+  name: string;
+  exe_path: string;
+  /** Arguments with a `{ROM}` token for the ROM path. */
+  args_template: string;
+  rom_extensions: string[];
+  created_at: string;
+}
+
+export interface EmulatorInput {
+  id?: string | null;
+  name: string;
+  exe_path: string;
+  args_template?: string | null;
+  rom_extensions?: string[];
+}
+
+/** A program started alongside a game (VPN, controller mapper, voice chat). */
+export interface GameCompanion {
+  id: string;
+  game_id: string;
+  exe_path: string;
+  args: string | null;
+  /** Closed when the game exits, if the launch was what started it. */
+  close_on_exit: boolean;
+  created_at: string;
+}
+
+export interface GameCompanionInput {
+  id?: string | null;
+  game_id: string;
+  exe_path: string;
+  args?: string | null;
+  close_on_exit?: boolean;
 }
 
 export interface Backup {
@@ -117,14 +395,99 @@ export interface Backup {
   created_at: string;
   is_auto: boolean;
   notes: string | null;
+  /** Captured when the backup was made; `null` for older backups. */
+  metadata: BackupMetadata | null;
+}
+
+// How `create_backup` / `restore_backup` failed; `no_space` is the free-space pre-check.
+export type BackupError =
+  | {
+      kind: "no_space";
+      mount_point: string;
+      required_bytes: number;
+      available_bytes: number;
+    }
+  | { kind: "other"; message: string };
+
+export interface BackupMetadata {
+  playtime_seconds: number;
+  last_session_seconds?: number | null;
+  label?: string | null;
+}
+
+export interface BackupEvent {
+  id: number;
+  game_id: string;
+  backup_id: string | null;
+  created_at: string;
+  is_auto: boolean;
+  success: boolean;
+  attempt: number;
+  error: string | null;
 }
 
 export interface BackupInfo {
   game_name: string;
   save_path: string | null;
   registry_path: string | null;
+  /** Registry keys the manifest lists for the game. */
+  registry_keys: string[];
   total_size: number;
   files: string[];
+  /** Xbox / Microsoft Store container names keyed by entry of `files`. */
+  container_names: Record<string, string>;
+  /** Saves were only guessed from the install folder. */
+  low_confidence: boolean;
+}
+
+export interface GameSavePath {
+  id: string;
+  game_id: string;
+  path: string;
+  label: string | null;
+  enabled: boolean;
+  exclude: boolean;
+  created_at: string;
+}
+
+export interface ManifestMatch {
+  game_id: string;
+  manifest_key: string | null;
+  score: number | null;
+  confirmed: boolean;
+  suggestions: string[];
+}
+
+export interface ManifestOverride {
+  files: string[];
+  registry: string[];
+  replace_upstream: boolean;
+}
+
+export interface SaveCandidate {
+  path: string;
+  source: "manifest" | "pcgamingwiki" | "heuristic" | "install";
+  score: number;
+  name_score: number;
+  recency_score: number;
+  size_score: number;
+  file_count: number;
+  total_size: number;
+  last_modified: string | null;
+  configured: boolean;
+}
+
+export interface PcgwLocation {
+  platform: string;
+  // Save locator syntax, e.g. `<winAppData>/Studio/Game`.
+  path: string;
+}
+
+export interface PcgwLocations {
+  page: string;
+  url: string;
+  save_locations: PcgwLocation[];
+  config_locations: PcgwLocation[];
 }
 
 export interface SavePathLookup {
@@ -139,6 +502,248 @@ export interface RestoreCheck {
   backup_size: number;
 }
 
+export interface RestoreRootMapping {
+  root_label: string;
+  original_root: string;
+  resolved_root: string;
+  remapped: boolean;
+  needs_mapping: boolean;
+}
+
+export interface NewerLiveFile {
+  path: string;
+  live_mtime: number;
+  backup_mtime: number;
+  live_size: number;
+}
+
+export interface RestorePreview {
+  backup_id: string;
+  file_count: number;
+  restore_bytes: number;
+  target_drive: string | null;
+  throughput_mbps: number;
+  throughput_measured: boolean;
+  estimated_seconds: number;
+  newer_files: NewerLiveFile[];
+}
+
+export interface ManifestRepair {
+  rebuilt: boolean;
+  files: number;
+  unresolved_roots: string[];
+}
+
+export interface BackupFileChange {
+  kind: "added" | "removed" | "changed";
+  path: string;
+  size_a: number | null;
+  size_b: number | null;
+}
+
+export interface BackupDiff {
+  backup_a: string;
+  backup_b: string;
+  changes: BackupFileChange[];
+  unchanged: number;
+}
+
+export interface DiagnosticSuggestion {
+  kind: "defender_exclusion";
+  message: string;
+  folder: string;
+  command: string;
+}
+
+export interface BackupDiagnostics {
+  backup_directory: string;
+  realtime_protection: boolean | null;
+  backup_directory_excluded: boolean | null;
+  recent_throughput_mbps: number | null;
+  defender_cpu_percent: number | null;
+  suggestions: DiagnosticSuggestion[];
+}
+
+export type StallAction = "cancel" | "skip_file";
+
+export interface InstallArchive {
+  id: string;
+  game_id: string;
+  archive_path: string;
+  original_dir: string;
+  exe_relative: string;
+  file_count: number;
+  original_bytes: number;
+  archive_bytes: number;
+  created_at: string;
+}
+
+export type ReportFormat = "html" | "markdown";
+
+export interface FileVerification {
+  /** No hashes were recorded yet (or a new baseline was asked for); this run recorded them. */
+  baseline_created: boolean;
+  baseline_at: string;
+  checked: number;
+  /** Paths relative to the install folder, `/`-separated. */
+  modified: string[];
+  missing: string[];
+  added: string[];
+}
+
+export interface LibraryExportOptions {
+  /** Write paths under the home folder as `<home>/...`. */
+  tokenise_paths: boolean;
+}
+
+export type ImportStrategy = "merge" | "skip_existing" | "replace";
+
+export interface LibraryImportSummary {
+  games_added: number;
+  games_updated: number;
+  games_skipped: number;
+}
+
+export interface MissingSaveGame {
+  game_id: string;
+  name: string | null;
+  error: string | null;
+}
+
+export interface BulkBackupToggle {
+  enabled: boolean;
+  updated: string[];
+  missing_saves: MissingSaveGame[];
+}
+
+export interface ManifestInfo {
+  etag: string | null;
+  last_modified: string | null;
+  fetched_at: string | null;
+  checked_at: string | null;
+  refresh_interval_hours: number;
+  cache_path: string;
+  cache_bytes: number | null;
+  game_count: number | null;
+}
+
+export interface ManifestProgressEvent {
+  stage: "download" | "parse" | "unchanged" | "done";
+  downloaded: number;
+  total: number | null;
+}
+
+export interface StalledOperation {
+  operation_id: string;
+  game_id: string;
+  kind: "backup" | "restore";
+  path: string | null;
+  idle_seconds: number;
+}
+
+export type SaveSuspicion =
+  | { kind: "empty" }
+  | { kind: "zero_filled" }
+  | { kind: "bad_header" }
+  | { kind: "truncated"; size: number; previous_size: number }
+  | { kind: "future_mtime" };
+
+export interface SuspectSaveFile {
+  path: string;
+  suspicion: SaveSuspicion;
+}
+
+// Payload of `backup:corruption-suspected`, emitted instead of the exit backup.
+export interface SaveCorruptionEvent {
+  game_id: string;
+  game_name: string;
+  files: SuspectSaveFile[];
+  /** Newest backup with an intact copy of every suspect file. */
+  backup_id: string | null;
+  backup_created_at: string | null;
+}
+
+// Payload of `save:changed`, emitted once a game's save roots have been quiet for
+// `save_watch_debounce_seconds` after a write.
+export interface SaveChangedEvent {
+  game_id: string;
+  /** Up to 20 of the changed paths. */
+  paths: string[];
+  change_count: number;
+  changed_at: string;
+}
+
+export interface BackupRef {
+  backup_id: string;
+  game_id: string;
+  game_name: string;
+  created_at: string;
+  disk_bytes: number;
+}
+
+export interface GameBackupStorage {
+  game_id: string;
+  game_name: string;
+  backup_count: number;
+  raw_bytes: number;
+  disk_bytes: number;
+  compression_ratio: number | null;
+  oldest_backup: string | null;
+  newest_backup: string | null;
+}
+
+export interface StorageGrowthPoint {
+  month: string;
+  backups_added: number;
+  bytes_added: number;
+  cumulative_bytes: number;
+}
+
+export interface BackupStats {
+  total_backups: number;
+  total_raw_bytes: number;
+  total_disk_bytes: number;
+  compression_ratio: number | null;
+  games: GameBackupStorage[];
+  oldest: BackupRef | null;
+  newest: BackupRef | null;
+  growth: StorageGrowthPoint[];
+}
+
+export interface CompressionBenchmark {
+  level: number;
+  sample_files: number;
+  original_bytes: number;
+  compressed_bytes: number;
+  ratio: number;
+  duration_ms: number;
+}
+
+/** Downloaded covers, capped by the `image_cache_max_mb` setting (0 turns the cap off). */
+export interface ImageCacheStats {
+  entries: number;
+  /** Size on disk, thumbnails included. */
+  total_bytes: number;
+  /** `null` when the cap is off. */
+  max_bytes: number | null;
+  /** Background pruning is paused. */
+  paused: boolean;
+}
+
+/** Payload of `metadata:auto-matched`: RAWG metadata applied to a new game without asking. */
+export interface AutoMatched {
+  game_id: string;
+  rawg_id: number;
+  name: string;
+}
+
+/** Payload of `metadata:match-needed`: no clear RAWG match for a new game. */
+export interface MatchNeeded {
+  game_id: string;
+  game_name: string;
+  candidates: RawgGame[];
+}
+
 export interface RawgGame {
   id: number;
   name: string;
@@ -152,6 +757,12 @@ export interface RawgGame {
   platforms: { platform: { id: number; name: string; slug: string } }[] | null;
 }
 
+// RAWG results for a game's cleaned name, best match first.
+export interface MatchSuggestions {
+  query: string;
+  candidates: (RawgGame & { confidence: number })[];
+}
+
 export interface RawgGameDetails extends RawgGame {
   description: string | null;
   description_raw: string | null;
@@ -160,6 +771,35 @@ export interface RawgGameDetails extends RawgGame {
   publishers: { id: number; name: string; slug: string }[] | null;
 }
 
+/** Twitch developer application credentials used to sign in to IGDB. */
+export interface IgdbCredentials {
+  client_id: string;
+  client_secret: string;
+}
+
+export interface IgdbGame {
+  id: number;
+  name: string;
+  slug: string | null;
+  /** `YYYY-MM-DD`. */
+  released: string | null;
+  cover_image: string | null;
+  /** Critic score, 0-100. */
+  aggregated_rating: number | null;
+  genres: string[];
+  platforms: string[];
+}
+
+export interface IgdbGameDetails extends IgdbGame {
+  summary: string | null;
+  storyline: string | null;
+  background_image: string | null;
+  /** Critic and user score combined, 0-100. */
+  total_rating: number | null;
+  developers: string[];
+  publishers: string[];
+}
+
 export interface AppSettings {
   theme: string;
   ludusavi_path: string;
@@ -169,6 +809,7 @@ export interface AppSettings {
   backup_compression_enabled: boolean;
   backup_compression_level: number;
   backup_skip_compression_once: boolean;
+  backup_integrity_check: boolean;
   max_backups_per_game: number;
   rawg_api_key: string;
 }
@@ -176,8 +817,76 @@ export interface AppSettings {
 export interface ExeEntry {
   path: string;
   file_name: string;
+  /** Set when the file is a ROM for this emulator. */
+  emulator_id?: string | null;
+  file_size?: number;
+  /** From the executable's version resource; `null` for ROMs and files without one. */
+  product_name?: string | null;
+  company_name?: string | null;
+  file_version?: string | null;
+  /** Why the file looks like an installer or runtime; only set when the scan included such files. */
+  filter_reason?: ScanFilterReason | null;
+  /** The saved scan folder the file was found under; only set by `scan_all_directories`. */
+  source_dir?: string | null;
 }
 
+/** Limits for a folder scan; omitted fields keep the backend defaults. */
+export interface ScanOptions {
+  /** Folder levels entered below the scanned folder; `0` only looks at its own files. */
+  max_depth?: number | null;
+  /** Executables outside these sizes are skipped; ROMs are not size-checked. */
+  min_exe_bytes?: number | null;
+  max_exe_bytes?: number | null;
+  /** Walk into symlinked folders and junctions (default off). */
+  follow_links?: boolean;
+  /** Skip dot-folders and folders marked hidden or system (default on). */
+  skip_hidden?: boolean;
+  /** Skip `node_modules`, `$RECYCLE.BIN` and similar folders (default on). */
+  skip_noise?: boolean;
+  /** Read threads per folder; network folders default to 2, local ones to one per core. */
+  threads?: number | null;
+  /** Seconds to wait for the next file before a folder is given up; network folders default to 30. */
+  entry_timeout_secs?: number | null;
+}
+
+/** Payload of `scan:<id>:group`: scan results that probably belong to one game. */
+export interface ScanGroup {
+  dir: string;
+  name: string;
+  /** The most likely main binary; the only one selected by default. */
+  main_path: string;
+  paths: string[];
+}
+
+/** A saved scan folder; `auto_scan` folders are rescanned in the background. */
+export interface ScanDirectory {
+  path: string;
+  last_scanned: string | null;
+  auto_scan: boolean;
+  /** Read threads for this folder; `null` keeps the scan default. */
+  threads: number | null;
+  /** UNC path (`\\NAS\games`) or mapped network drive. */
+  network: boolean;
+}
+
+/** Payload of `scan:<id>:unreachable`: a network folder that did not answer. */
+export interface UnreachableFolder {
+  path: string;
+  error: string;
+}
+
+/** Payload of `scan:new-games-found`: new executables under one auto-scan folder. */
+export interface NewGamesFound {
+  source_dir: string;
+  paths: string[];
+}
+
+export type ScanFilterReason =
+  | "helper_name"
+  | "helper_folder"
+  | "helper_description"
+  | "user_blacklist";
+
 export interface ProcessEntry {
   pid: number;
   name: string;
@@ -203,6 +912,129 @@ export interface PlaytimeStats {
   total_seconds: number;
   daily_totals: DailyPlaytime[];
   per_game_totals: GamePlaytime[];
+  per_launch_totals: LaunchPlaytime[];
+}
+
+export interface LaunchPlaytime {
+  game_id: string;
+  game_name: string;
+  launch_profile: string | null;
+  store: string | null;
+  sessions: number;
+  seconds: number;
+}
+
+export interface StatsRange {
+  start?: string;
+  end?: string;
+}
+
+export interface StatsCard {
+  range_start: string;
+  range_end: string;
+  total_seconds: number;
+  total_hours: number;
+  days_played: number;
+  longest_streak_days: number;
+  top_games: GamePlaytime[];
+}
+
+export interface GenreCount {
+  genre: string;
+  games: number;
+}
+
+export interface LibraryActivity {
+  game_id: string;
+  game_name: string;
+  kind: "played" | "backup" | "added";
+  at: string;
+}
+
+export interface LibraryOverview {
+  total_games: number;
+  installed_games: number;
+  favorite_games: number;
+  games_with_backups: number;
+  total_playtime_seconds: number;
+  total_backup_bytes: number;
+  recent_activity: LibraryActivity | null;
+  top_genres: GenreCount[];
+}
+
+export interface PlaySession {
+  id: number;
+  game_id: string;
+  game_name: string;
+  started_at: string;
+  ended_at: string;
+  duration_seconds: number;
+  note: string | null;
+  backup_id: string | null;
+  launch_profile: string | null;
+  store: string | null;
+  /** `null` for sessions recorded before exit reasons were tracked. */
+  exit_reason: SessionExitReason | null;
+}
+
+// `crashed`: exited within `crash_threshold_seconds` of a launch; `killed`: stopped from the app.
+export type SessionExitReason = "exited" | "crashed" | "killed";
+
+export type LaunchOutcome =
+  | "pending"
+  | "running"
+  | "completed"
+  | "crashed"
+  | "failed";
+
+export interface LaunchRecord {
+  id: number;
+  game_id: string;
+  started_at: string;
+  ended_at: string | null;
+  duration_seconds: number | null;
+  outcome: LaunchOutcome;
+  /** Why the process could not be started, for `failed` launches. */
+  error: string | null;
+}
+
+/** A dated Markdown note on a game, kept alongside the single `user_note`. */
+export interface GameNote {
+  id: string;
+  game_id: string;
+  body: string;
+  created_at: string;
+  updated_at: string;
+}
+
+/** An external page about a game: wiki, mod page, guide. */
+export interface GameLink {
+  id: string;
+  game_id: string;
+  /** Shown instead of the URL when set. */
+  title: string | null;
+  url: string;
+  created_at: string;
+}
+
+/** Payload of `game:session-ended`, sent whenever a game's last process exits. */
+export interface SessionEnded {
+  game_id: string;
+  game_name: string;
+  /** `null` for untracked games and exits before a session was recorded. */
+  session_id: number | null;
+  duration_seconds: number | null;
+  ended_at: string;
+  total_playtime: number;
+  /** The launch had hidden the main window and it has been shown again. */
+  window_restored: boolean;
+}
+
+export interface SessionNotePrompt {
+  session_id: number;
+  game_id: string;
+  game_name: string;
+  duration_seconds: number;
 }
 
 export interface SystemCpuInfo {
@@ -272,3 +1104,61 @@ export interface DiskSpeedResult {
   elapsed_write_ms: number;
   elapsed_read_ms: number;
 }
+
+export interface AppInfo {
+  version: string;
+  previous_version: string | null;
+  schema_version: number;
+  data_dir: string;
+  database_path: string;
+  manifest_cache_path: string;
+  backup_dir: string;
+}
+
+export type DatabaseMode = "ready" | "read_only" | "unavailable";
+
+export interface DatabaseStatus {
+  mode: DatabaseMode;
+  path: string;
+  attempts: number;
+  error: string | null;
+}
+
+export type PluginKind = "executable" | "batch" | "powershell" | "python";
+
+export interface PluginRun {
+  event: string;
+  at: string;
+  duration_ms: number;
+  error: string | null;
+}
+
+export interface PluginInfo {
+  id: string;
+  path: string;
+  kind: PluginKind;
+  enabled: boolean;
+  last_run: PluginRun | null;
+}
+
+export interface PluginNotifyEvent {
+  plugin: string;
+  title: string;
+  message: string | null;
+}
+
+export interface LibraryFixture {
+  games?: {
+    id?: string;
+    name: string;
+    exe_path: string;
+    exe_name?: string;
+    is_favorite?: boolean;
+    total_playtime?: number;
+    last_played?: string;
+    date_added?: string;
+    released?: string;
+  }[];
+  playtime?: { game_id: string; date: string; seconds: number }[];
+  settings?: { key: string; value: string }[];
+}